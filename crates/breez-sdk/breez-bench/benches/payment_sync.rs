@@ -0,0 +1,110 @@
+//! Benchmarks of the storage side of the payment sync, for a wallet with
+//! 10k transfers: recording each synced payment, then listing them.
+
+use std::hint::black_box;
+
+use breez_sdk_spark::{
+    DisplayAmount, Payment, PaymentDetails, PaymentMethod, PaymentStatus, PaymentType,
+    SqliteStorage, Storage, StorageListPaymentsRequest,
+};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+
+const TRANSFERS_COUNT: u64 = 10_000;
+
+fn payment(index: u64) -> Payment {
+    Payment {
+        id: format!("transfer-{index}"),
+        payment_type: if index % 2 == 0 {
+            PaymentType::Receive
+        } else {
+            PaymentType::Send
+        },
+        status: PaymentStatus::Completed,
+        amount: u128::from(1_000 + index),
+        fees: 0,
+        timestamp: 1_700_000_000 + index,
+        method: PaymentMethod::Spark,
+        details: Some(PaymentDetails::Spark {
+            invoice_details: None,
+            htlc_details: None,
+            conversion_info: None,
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    }
+}
+
+fn payments() -> Vec<Payment> {
+    (0..TRANSFERS_COUNT).map(payment).collect()
+}
+
+/// A storage in a fresh directory, dropped with the returned directory
+fn storage() -> (TempDir, SqliteStorage) {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let storage = SqliteStorage::new(dir.path()).expect("failed to create storage");
+    (dir, storage)
+}
+
+fn bench_apply_payment_updates(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("failed to create runtime");
+    let mut group = c.benchmark_group("payment_sync");
+    group.sample_size(10);
+    group.bench_function("apply_payment_update_10k", |b| {
+        b.to_async(&runtime).iter_batched(
+            || (storage(), payments()),
+            |((_dir, storage), payments)| async move {
+                for payment in payments {
+                    black_box(storage.apply_payment_update(payment).await.unwrap());
+                }
+            },
+            BatchSize::PerIteration,
+        );
+    });
+    group.finish();
+}
+
+fn bench_list_payments(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("failed to create runtime");
+    let (_dir, storage) = storage();
+    runtime.block_on(async {
+        for payment in payments() {
+            storage.apply_payment_update(payment).await.unwrap();
+        }
+    });
+
+    let mut group = c.benchmark_group("payment_sync");
+    group.sample_size(10);
+    group.bench_function("list_payments_10k", |b| {
+        b.to_async(&runtime).iter(|| async {
+            let payments = storage
+                .list_payments(StorageListPaymentsRequest::default())
+                .await
+                .unwrap();
+            assert_eq!(payments.len() as u64, TRANSFERS_COUNT);
+            black_box(payments);
+        });
+    });
+    group.bench_function("list_payments_page_10k", |b| {
+        b.to_async(&runtime).iter(|| async {
+            black_box(
+                storage
+                    .list_payments(StorageListPaymentsRequest {
+                        offset: Some(5_000),
+                        limit: Some(100),
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap(),
+            );
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_apply_payment_updates, bench_list_payments);
+criterion_main!(benches);
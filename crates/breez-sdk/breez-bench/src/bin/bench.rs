@@ -7,7 +7,9 @@ use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::time::Instant;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, bail};
 use bip39::{Language, Mnemonic};
@@ -18,7 +20,7 @@ use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Layer};
 
 use breez_sdk_spark::{
-    BreezSdk, EventListener, GetInfoRequest, Network, PaymentRequest, PaymentType,
+    BreezSdk, EventListener, GetInfoRequest, Network, Payment, PaymentRequest, PaymentType,
     PrepareSendPaymentRequest, ReceivePaymentMethod, ReceivePaymentRequest, SdkBuilder, SdkEvent,
     Seed, SendPaymentRequest, SyncWalletRequest, default_config,
 };
@@ -30,9 +32,11 @@ use breez_bench::operation_detector::{
 };
 use breez_bench::scenarios::{
     DEFAULT_MAX_AMOUNT, DEFAULT_MAX_DELAY_MS, DEFAULT_MIN_AMOUNT, DEFAULT_MIN_DELAY_MS,
-    DEFAULT_PAYMENT_COUNT, DEFAULT_RETURN_INTERVAL, DEFAULT_SEED, MAX_INITIAL_FUNDING,
+    DEFAULT_PAYMENT_COUNT, DEFAULT_RETURN_INTERVAL, DEFAULT_SEED, MAX_INITIAL_FUNDING, PaymentSpec,
     ScenarioConfig, ScenarioPreset, generate_payments,
 };
+use breez_bench::soak::{DEFAULT_LEAK_THRESHOLD, ResourceSample, SoakReport, parse_duration};
+use breez_bench::stages::ReceiveStageRecorder;
 use breez_bench::stats::{BenchmarkResults, PaymentMeasurement};
 
 const PHRASE_FILE_NAME: &str = "phrase";
@@ -93,6 +97,27 @@ struct Args {
     /// Receiver wallet multiplicity (optimization parameter)
     #[arg(long, default_value_t = 0)]
     receiver_multiplicity: u8,
+
+    /// Run in soak mode for this long (e.g. 30m, 24h), sampling memory, file
+    /// descriptors and tokio tasks instead of measuring payment latency
+    #[arg(long)]
+    duration: Option<String>,
+
+    /// Soak mode: delay between payments in seconds
+    #[arg(long, default_value_t = 30)]
+    soak_interval_secs: u64,
+
+    /// Soak mode: how often resource usage is sampled, in seconds
+    #[arg(long, default_value_t = 60)]
+    sample_interval_secs: u64,
+
+    /// Soak mode: relative growth between start and end of the run that is flagged as a leak
+    #[arg(long, default_value_t = DEFAULT_LEAK_THRESHOLD)]
+    leak_threshold: f64,
+
+    /// How the sender is funded on regtest: onchain (deposit) or lightning (invoice paid by the lightning faucet)
+    #[arg(long, default_value = "onchain")]
+    funding: String,
 }
 
 /// SDK instance wrapper with event channel
@@ -237,11 +262,19 @@ async fn main() -> Result<()> {
         }
     };
 
+    // Stamp receive-side events to split payment latency into stages
+    let receive_stages = ReceiveStageRecorder::new();
+    receiver
+        .sdk
+        .add_event_listener(Box::new(receive_stages.clone()))
+        .await;
+
     // Get addresses for both parties
     let receiver_address = receiver
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -251,6 +284,7 @@ async fn main() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -269,11 +303,26 @@ async fn main() -> Result<()> {
     match network {
         Network::Regtest => {
             // Use itest faucet for regtest
-            info!(
-                "Funding sender with {} sats (single deposit)...",
-                MAX_INITIAL_FUNDING
-            );
-            fund_via_faucet(&mut sender, MAX_INITIAL_FUNDING).await?;
+            match args.funding.as_str() {
+                "onchain" => {
+                    info!(
+                        "Funding sender with {} sats (single deposit)...",
+                        MAX_INITIAL_FUNDING
+                    );
+                    fund_via_faucet(&mut sender, MAX_INITIAL_FUNDING).await?;
+                }
+                "lightning" => {
+                    info!(
+                        "Funding sender with {} sats (lightning invoice)...",
+                        MAX_INITIAL_FUNDING
+                    );
+                    fund_via_lightning_faucet(&mut sender, MAX_INITIAL_FUNDING).await?;
+                }
+                other => bail!(
+                    "Invalid funding method: {}. Use onchain or lightning",
+                    other
+                ),
+            }
         }
         Network::Mainnet => {
             // Check balances and consolidate to sender
@@ -324,6 +373,8 @@ async fn main() -> Result<()> {
                         prepare_response: prepare,
                         options: None,
                         idempotency_key: None,
+                        compliance_metadata: None,
+                        queue_if_offline: None,
                     })
                     .await?;
 
@@ -360,6 +411,24 @@ async fn main() -> Result<()> {
         }
     };
 
+    if let Some(duration) = &args.duration {
+        let duration = parse_duration(duration).map_err(|e| anyhow::anyhow!(e))?;
+        return run_soak(
+            &mut sender,
+            &mut receiver,
+            &sender_address,
+            &receiver_address,
+            &payments,
+            SoakOptions {
+                duration,
+                payment_interval: Duration::from_secs(args.soak_interval_secs),
+                sample_interval: Duration::from_secs(args.sample_interval_secs.max(1)),
+                leak_threshold: args.leak_threshold,
+            },
+        )
+        .await;
+    }
+
     // Run the benchmark
     info!("Starting benchmark...");
     let mut results = BenchmarkResults::new(config.seed);
@@ -483,6 +552,8 @@ async fn main() -> Result<()> {
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await;
 
@@ -494,25 +565,38 @@ async fn main() -> Result<()> {
         let duration = start.elapsed();
 
         // Wait for receiver to get the payment
-        if let Err(e) =
-            wait_for_payment_event(&mut receiver.events, PaymentType::Receive, 120).await
-        {
-            warn!("  Failed waiting for payment receipt: {} - skipping", e);
-            continue;
-        }
+        let received =
+            match wait_for_payment_event(&mut receiver.events, PaymentType::Receive, 120).await {
+                Ok(payment) => payment,
+                Err(e) => {
+                    warn!("  Failed waiting for payment receipt: {} - skipping", e);
+                    continue;
+                }
+            };
+        let claimed = receive_stages
+            .take_claimed(&received.id)
+            .await
+            .map(|at| at.saturating_duration_since(start));
+        let claim_started = receive_stages
+            .take_claim_started(&received.id)
+            .map(|at| at.saturating_duration_since(start));
 
         let had_swap = swap_guard.had_operation();
         let had_cancellation = cancellation_guard.had_operation();
 
         info!(
-            "  Completed in {:?} (swap: {}, cancellation: {})",
+            "  Completed in {:?}, claim started at {:?}, claimed at {:?} (swap: {}, cancellation: {})",
             duration,
+            claim_started,
+            claimed,
             if had_swap { "yes" } else { "no" },
             if had_cancellation { "yes" } else { "no" }
         );
 
         results.add(PaymentMeasurement {
             duration,
+            claim_started,
+            claimed,
             had_swap,
             had_cancellation,
             amount_sats: payment_spec.amount_sats,
@@ -769,12 +853,12 @@ fn expand_path(path: &str) -> PathBuf {
     }
 }
 
-/// Wait for a payment event
+/// Wait for a payment event, returning the succeeded payment
 async fn wait_for_payment_event(
     event_rx: &mut mpsc::Receiver<SdkEvent>,
     payment_type: PaymentType,
     timeout_secs: u64,
-) -> Result<()> {
+) -> Result<Payment> {
     let timeout = tokio::time::Duration::from_secs(timeout_secs);
     let deadline = tokio::time::Instant::now() + timeout;
 
@@ -792,7 +876,7 @@ async fn wait_for_payment_event(
             Ok(Some(SdkEvent::PaymentSucceeded { payment }))
                 if payment.payment_type == payment_type =>
             {
-                return Ok(());
+                return Ok(payment);
             }
             Ok(Some(_)) => continue,
             Ok(None) => bail!("Event channel closed"),
@@ -805,6 +889,103 @@ async fn wait_for_payment_event(
     }
 }
 
+struct SoakOptions {
+    duration: Duration,
+    payment_interval: Duration,
+    sample_interval: Duration,
+    leak_threshold: f64,
+}
+
+/// Run low-rate payments back and forth between the wallets until the soak
+/// duration elapses, sampling process resource usage along the way.
+async fn run_soak(
+    sender: &mut BenchSdkInstance,
+    receiver: &mut BenchSdkInstance,
+    sender_address: &str,
+    receiver_address: &str,
+    payments: &[PaymentSpec],
+    options: SoakOptions,
+) -> Result<()> {
+    if payments.is_empty() {
+        bail!("Soak mode needs at least one payment in the scenario");
+    }
+    info!(
+        "Starting soak run for {:?} (payment every {:?}, sample every {:?})...",
+        options.duration, options.payment_interval, options.sample_interval
+    );
+
+    let start = Instant::now();
+    let completed = Arc::new(AtomicU64::new(0));
+    let report = Arc::new(Mutex::new(SoakReport::new()));
+
+    let sampler = {
+        let completed = Arc::clone(&completed);
+        let report = Arc::clone(&report);
+        let sample_interval = options.sample_interval;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(sample_interval);
+            loop {
+                interval.tick().await;
+                let sample =
+                    ResourceSample::take(start.elapsed(), completed.load(Ordering::Relaxed));
+                info!(
+                    "  [Sample {:?}: rss={:?} fds={:?} tasks={} payments={}]",
+                    sample.elapsed,
+                    sample.rss_bytes,
+                    sample.open_fds,
+                    sample.alive_tasks,
+                    sample.payments
+                );
+                report.lock().unwrap().add(sample);
+            }
+        })
+    };
+
+    let mut failed: u64 = 0;
+    let mut i: usize = 0;
+    while start.elapsed() < options.duration {
+        let amount = payments[i % payments.len()].amount_sats;
+        // Alternate directions so both balances stay stable over the run
+        let result = if i % 2 == 0 {
+            return_funds_to_sender(sender, receiver, receiver_address, amount).await
+        } else {
+            return_funds_to_sender(receiver, sender, sender_address, amount).await
+        };
+        match result {
+            Ok(()) => {
+                completed.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                failed += 1;
+                warn!("  Soak payment {} of {} sats failed: {}", i + 1, amount, e);
+            }
+        }
+        i += 1;
+
+        let remaining = options.duration.saturating_sub(start.elapsed());
+        tokio::time::sleep(options.payment_interval.min(remaining)).await;
+    }
+
+    sampler.abort();
+    let mut report = std::mem::take(&mut *report.lock().unwrap());
+    report.add(ResourceSample::take(
+        start.elapsed(),
+        completed.load(Ordering::Relaxed),
+    ));
+
+    info!(
+        "Soak run finished: {} payments completed, {} failed",
+        completed.load(Ordering::Relaxed),
+        failed
+    );
+    report.print_report(options.leak_threshold);
+
+    if report.has_suspected_leak(options.leak_threshold) {
+        bail!("Suspected resource leak during soak run");
+    }
+    Ok(())
+}
+
 /// Return funds from receiver to sender
 async fn return_funds_to_sender(
     receiver: &mut BenchSdkInstance,
@@ -831,6 +1012,8 @@ async fn return_funds_to_sender(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -867,6 +1050,7 @@ async fn fund_via_faucet(sdk_instance: &mut BenchSdkInstance, min_balance: u64)
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?;
     let deposit_address = receive.payment_request;
@@ -895,6 +1079,77 @@ async fn fund_via_faucet(sdk_instance: &mut BenchSdkInstance, min_balance: u64)
     Ok(())
 }
 
+/// Fund wallet by having the regtest lightning faucet pay an invoice
+async fn fund_via_lightning_faucet(
+    sdk_instance: &mut BenchSdkInstance,
+    min_balance: u64,
+) -> Result<()> {
+    use breez_sdk_itest::{LightningFaucet, LightningNodeFixture};
+
+    sdk_instance.sdk.sync_wallet(SyncWalletRequest {}).await?;
+    let info = sdk_instance
+        .sdk
+        .get_info(GetInfoRequest {
+            ensure_synced: Some(false),
+        })
+        .await?;
+
+    if info.balance_sats >= min_balance {
+        info!("Already have {} sats, no funding needed", info.balance_sats);
+        return Ok(());
+    }
+
+    let needed = min_balance - info.balance_sats;
+    let fund_amount = needed.clamp(10_000, 50_000);
+    info!(
+        "Need {} sats, requesting {} from lightning faucet",
+        needed, fund_amount
+    );
+
+    let invoice = sdk_instance
+        .sdk
+        .receive_payment(ReceivePaymentRequest {
+            payment_method: ReceivePaymentMethod::Bolt11Invoice {
+                description: "Benchmark funding".to_string(),
+                amount_sats: Some(fund_amount),
+                expiry_secs: None,
+                payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
+            },
+            order_ref: None,
+        })
+        .await?
+        .payment_request;
+    info!("Invoice: {}", invoice);
+
+    // Without an external faucet node, a pre-funded one is started for the funding
+    let lightning_node = match std::env::var("LIGHTNING_FAUCET_URL") {
+        Ok(_) => None,
+        Err(_) => Some(LightningNodeFixture::new().await?),
+    };
+    let faucet = match &lightning_node {
+        Some(lightning_node) => lightning_node.faucet()?,
+        None => LightningFaucet::new()?,
+    };
+    faucet.pay_invoice(&invoice).await?;
+    info!("Lightning faucet paid {} sats", fund_amount);
+
+    wait_for_payment_event(&mut sdk_instance.events, PaymentType::Receive, 120).await?;
+    wait_for_balance(&sdk_instance.sdk, info.balance_sats + 1, 20).await?;
+
+    sdk_instance.sdk.sync_wallet(SyncWalletRequest {}).await?;
+    let final_info = sdk_instance
+        .sdk
+        .get_info(GetInfoRequest {
+            ensure_synced: Some(false),
+        })
+        .await?;
+    info!("Funded. New balance: {} sats", final_info.balance_sats);
+
+    Ok(())
+}
+
 /// Wait for balance to reach minimum
 async fn wait_for_balance(sdk: &BreezSdk, min_balance: u64, timeout_secs: u64) -> Result<()> {
     let start = std::time::Instant::now();
@@ -206,6 +206,7 @@ async fn run_single_claim_benchmark(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -256,6 +257,8 @@ async fn run_single_claim_benchmark(
                     prepare_response: prepare,
                     options: None,
                     idempotency_key: None,
+                    compliance_metadata: None,
+                    queue_if_offline: None,
                 })
                 .await?;
 
@@ -450,6 +453,7 @@ async fn fund_sdk_via_faucet(
     let receive = sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?;
     let deposit_address = receive.payment_request;
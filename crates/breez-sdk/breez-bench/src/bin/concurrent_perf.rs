@@ -223,6 +223,7 @@ async fn main() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -413,6 +414,8 @@ async fn execute_single_payment(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -798,6 +801,7 @@ async fn fund_via_faucet(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?;
     let deposit_address = receive.payment_request;
@@ -232,6 +232,7 @@ async fn main() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -253,7 +254,10 @@ async fn main() -> Result<()> {
                     amount_sats: Some(amount),
                     expiry_secs: Some(3600),
                     payment_hash: None,
+                    min_amount_sats: None,
+                    max_amount_sats: None,
                 },
+                order_ref: None,
             })
             .await?
             .payment_request;
@@ -426,6 +430,8 @@ async fn execute_single_payment(sender: &BreezSdk, payment_type: &PaymentType) -
                     prepare_response: prepare,
                     options: None,
                     idempotency_key: None,
+                    compliance_metadata: None,
+                    queue_if_offline: None,
                 })
                 .await?;
 
@@ -449,6 +455,8 @@ async fn execute_single_payment(sender: &BreezSdk, payment_type: &PaymentType) -
                     prepare_response: prepare,
                     options: None,
                     idempotency_key: None,
+                    compliance_metadata: None,
+                    queue_if_offline: None,
                 })
                 .await?;
 
@@ -690,6 +698,7 @@ async fn fund_via_faucet(sdk_instance: &mut BenchSdkInstance, amount: u64) -> Re
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?;
     let deposit_address = receive.payment_request;
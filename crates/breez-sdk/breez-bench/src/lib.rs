@@ -6,4 +6,6 @@
 pub mod events;
 pub mod operation_detector;
 pub mod scenarios;
+pub mod soak;
+pub mod stages;
 pub mod stats;
@@ -0,0 +1,316 @@
+//! Soak (endurance) mode resource tracking.
+//!
+//! Samples process memory, open file descriptors and live tokio tasks while a
+//! long-running benchmark executes, and flags metrics that keep growing.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Relative growth between the start and the end of a run above which a
+/// metric is flagged as a suspected leak.
+pub const DEFAULT_LEAK_THRESHOLD: f64 = 0.2;
+
+/// Parse a duration such as `90s`, `30m`, `24h` or `2d`.
+/// A bare number is interpreted as seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (value, unit) = input.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("Invalid duration '{input}'"))?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("Invalid duration unit '{unit}' in '{input}'")),
+    };
+    if value == 0 {
+        return Err(format!("Duration must be positive, got '{input}'"));
+    }
+    let secs = value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Duration '{input}' is too long"))?;
+    Ok(Duration::from_secs(secs))
+}
+
+/// A snapshot of process resource usage.
+#[derive(Debug, Clone)]
+pub struct ResourceSample {
+    /// Time since the soak run started
+    pub elapsed: Duration,
+    /// Resident set size in bytes. None where it can't be read.
+    pub rss_bytes: Option<u64>,
+    /// Number of open file descriptors. None where it can't be read.
+    pub open_fds: Option<u64>,
+    /// Number of tasks alive in the tokio runtime
+    pub alive_tasks: u64,
+    /// Payments completed so far
+    pub payments: u64,
+}
+
+impl ResourceSample {
+    /// Take a sample of the current process. Must be called from within a tokio runtime.
+    pub fn take(elapsed: Duration, payments: u64) -> Self {
+        Self {
+            elapsed,
+            rss_bytes: read_rss_bytes(),
+            open_fds: count_open_fds(),
+            alive_tasks: tokio::runtime::Handle::current()
+                .metrics()
+                .num_alive_tasks() as u64,
+            payments,
+        }
+    }
+}
+
+/// Read the resident set size from `/proc/self/status` (Linux only).
+fn read_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Count the entries of the process file descriptor directory (Linux and macOS).
+fn count_open_fds() -> Option<u64> {
+    let entries = std::fs::read_dir("/proc/self/fd")
+        .or_else(|_| std::fs::read_dir("/dev/fd"))
+        .ok()?;
+    Some(entries.count() as u64)
+}
+
+/// Growth of a single metric over a soak run.
+#[derive(Debug, Clone)]
+pub struct MetricGrowth {
+    pub name: &'static str,
+    /// Mean over the first quarter of the samples
+    pub baseline: f64,
+    /// Mean over the last quarter of the samples
+    pub end: f64,
+    /// Whether the growth exceeds the leak threshold
+    pub suspected_leak: bool,
+}
+
+impl MetricGrowth {
+    /// Relative growth from baseline to end.
+    pub fn growth(&self) -> f64 {
+        if self.baseline == 0.0 {
+            return 0.0;
+        }
+        (self.end - self.baseline) / self.baseline
+    }
+}
+
+/// Compare the start and the end of a metric series. The first quarter of the
+/// samples is the baseline, which leaves room for caches to warm up before the
+/// end of the run is judged. Returns None with fewer than 4 values.
+fn metric_growth(name: &'static str, values: &[f64], threshold: f64) -> Option<MetricGrowth> {
+    if values.len() < 4 {
+        return None;
+    }
+    let quarter = values.len() / 4;
+    let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+    let baseline = mean(&values[..quarter]);
+    let end = mean(&values[values.len() - quarter..]);
+    let suspected_leak = baseline > 0.0 && (end - baseline) / baseline > threshold;
+    Some(MetricGrowth {
+        name,
+        baseline,
+        end,
+        suspected_leak,
+    })
+}
+
+/// Samples collected over a soak run.
+#[derive(Debug, Default)]
+pub struct SoakReport {
+    pub samples: Vec<ResourceSample>,
+}
+
+impl SoakReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, sample: ResourceSample) {
+        self.samples.push(sample);
+    }
+
+    /// Growth of each tracked metric, skipping metrics that couldn't be read.
+    pub fn growth(&self, threshold: f64) -> Vec<MetricGrowth> {
+        let rss: Option<Vec<f64>> = self
+            .samples
+            .iter()
+            .map(|s| s.rss_bytes.map(|v| v as f64))
+            .collect();
+        let fds: Option<Vec<f64>> = self
+            .samples
+            .iter()
+            .map(|s| s.open_fds.map(|v| v as f64))
+            .collect();
+        let tasks: Vec<f64> = self.samples.iter().map(|s| s.alive_tasks as f64).collect();
+
+        [
+            rss.and_then(|v| metric_growth("RSS", &v, threshold)),
+            fds.and_then(|v| metric_growth("Open fds", &v, threshold)),
+            metric_growth("Tokio tasks", &tasks, threshold),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Whether any metric is suspected to leak.
+    pub fn has_suspected_leak(&self, threshold: f64) -> bool {
+        self.growth(threshold).iter().any(|g| g.suspected_leak)
+    }
+
+    /// Render the samples and the growth summary.
+    pub fn render(&self, threshold: f64) -> String {
+        let mut output = String::new();
+        let payments = self.samples.last().map(|s| s.payments).unwrap_or(0);
+
+        writeln!(
+            output,
+            "Soak Results (samples: {}, payments: {})",
+            self.samples.len(),
+            payments
+        )
+        .unwrap();
+        writeln!(output, "================================================").unwrap();
+        writeln!(
+            output,
+            "  {:>10} {:>12} {:>8} {:>8} {:>10}",
+            "elapsed", "rss", "fds", "tasks", "payments"
+        )
+        .unwrap();
+        for sample in &self.samples {
+            writeln!(
+                output,
+                "  {:>10} {:>12} {:>8} {:>8} {:>10}",
+                format_elapsed(sample.elapsed),
+                sample
+                    .rss_bytes
+                    .map(|b| format!("{:.1}MiB", b as f64 / (1024.0 * 1024.0)))
+                    .unwrap_or_else(|| "-".to_string()),
+                sample
+                    .open_fds
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                sample.alive_tasks,
+                sample.payments,
+            )
+            .unwrap();
+        }
+
+        writeln!(output).unwrap();
+        let growth = self.growth(threshold);
+        if growth.is_empty() {
+            writeln!(output, "Not enough samples to detect leaks").unwrap();
+        }
+        for metric in growth {
+            writeln!(
+                output,
+                "  {:<12} start: {:>12.1}  end: {:>12.1}  growth: {:>+6.1}%{}",
+                metric.name,
+                metric.baseline,
+                metric.end,
+                metric.growth() * 100.0,
+                if metric.suspected_leak {
+                    "  <-- SUSPECTED LEAK"
+                } else {
+                    ""
+                }
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    /// Print the report to stdout.
+    pub fn print_report(&self, threshold: f64) {
+        println!();
+        print!("{}", self.render(threshold));
+        println!();
+    }
+}
+
+fn format_elapsed(d: Duration) -> String {
+    let secs = d.as_secs();
+    format!(
+        "{}h{:02}m{:02}s",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(rss_bytes: u64, alive_tasks: u64) -> ResourceSample {
+        ResourceSample {
+            elapsed: Duration::ZERO,
+            rss_bytes: Some(rss_bytes),
+            open_fds: None,
+            alive_tasks,
+            payments: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(1800)));
+        assert_eq!(parse_duration("24h"), Ok(Duration::from_secs(86_400)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(172_800)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("0h").is_err());
+        assert!(parse_duration("5w").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("99999999999999999h").is_err());
+    }
+
+    #[test]
+    fn test_stable_metrics_not_flagged() {
+        let mut report = SoakReport::new();
+        for _ in 0..8 {
+            report.add(sample(100, 50));
+        }
+        let growth = report.growth(DEFAULT_LEAK_THRESHOLD);
+        // Open fds are missing from the samples and skipped
+        assert_eq!(growth.len(), 2);
+        assert!(!report.has_suspected_leak(DEFAULT_LEAK_THRESHOLD));
+    }
+
+    #[test]
+    fn test_growing_metric_flagged() {
+        let mut report = SoakReport::new();
+        for i in 0..8 {
+            report.add(sample(100 + i * 20, 50));
+        }
+        let growth = report.growth(DEFAULT_LEAK_THRESHOLD);
+        let rss = growth.iter().find(|g| g.name == "RSS").unwrap();
+        assert!(rss.suspected_leak);
+        let tasks = growth.iter().find(|g| g.name == "Tokio tasks").unwrap();
+        assert!(!tasks.suspected_leak);
+        assert!(
+            report
+                .render(DEFAULT_LEAK_THRESHOLD)
+                .contains("SUSPECTED LEAK")
+        );
+    }
+
+    #[test]
+    fn test_too_few_samples() {
+        let mut report = SoakReport::new();
+        report.add(sample(100, 50));
+        assert!(report.growth(DEFAULT_LEAK_THRESHOLD).is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+//! Receive-path stage timestamps.
+//!
+//! Records when the receiver starts claiming an incoming payment and when the
+//! claim succeeds, so a measurement can be split into send, claim start and
+//! claimed durations.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use breez_sdk_spark::{EventListener, PaymentType, SdkEvent};
+
+const CLAIMED_STAMP_ATTEMPTS: u32 = 50;
+const CLAIMED_STAMP_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Event listener stamping the receiver's `PaymentPending` and `PaymentSucceeded`
+/// events as they arrive.
+///
+/// Register it on the receiving SDK. The SDK emits `PaymentPending` when it starts
+/// claiming a transfer, and emits it to listeners before the `PaymentSucceeded` of
+/// the same payment, so both stamps are in place once the payment is seen as
+/// succeeded. Only the first stamp per payment id is kept.
+#[derive(Clone, Default)]
+pub struct ReceiveStageRecorder {
+    claim_started: Arc<Mutex<HashMap<String, Instant>>>,
+    claimed: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl ReceiveStageRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove and return the instant the receiver started claiming a payment.
+    /// None if no `PaymentPending` event was seen for it.
+    pub fn take_claim_started(&self, payment_id: &str) -> Option<Instant> {
+        self.claim_started.lock().unwrap().remove(payment_id)
+    }
+
+    /// Remove and return the instant the receiver's claim of a payment succeeded.
+    /// None if no `PaymentSucceeded` event was seen for it.
+    ///
+    /// Listeners are called one after the other in no fixed order, so another
+    /// listener may see the `PaymentSucceeded` event just before this one stamps
+    /// it. The stamp is awaited for a short while to cover that.
+    pub async fn take_claimed(&self, payment_id: &str) -> Option<Instant> {
+        for _ in 0..CLAIMED_STAMP_ATTEMPTS {
+            if let Some(at) = self.claimed.lock().unwrap().remove(payment_id) {
+                return Some(at);
+            }
+            tokio::time::sleep(CLAIMED_STAMP_INTERVAL).await;
+        }
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl EventListener for ReceiveStageRecorder {
+    async fn on_event(&self, event: SdkEvent) {
+        let (stamps, payment) = match event {
+            SdkEvent::PaymentPending { payment } => (&self.claim_started, payment),
+            SdkEvent::PaymentSucceeded { payment } => (&self.claimed, payment),
+            _ => return,
+        };
+        if payment.payment_type == PaymentType::Receive {
+            stamps
+                .lock()
+                .unwrap()
+                .entry(payment.id)
+                .or_insert_with(Instant::now);
+        }
+    }
+}
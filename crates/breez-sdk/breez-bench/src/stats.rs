@@ -8,6 +8,11 @@ use std::time::Duration;
 pub struct PaymentMeasurement {
     /// Total duration from prepare to completion
     pub duration: Duration,
+    /// Duration from prepare until the receiver started claiming the transfer.
+    /// None if the receiver never reported the payment as pending.
+    pub claim_started: Option<Duration>,
+    /// Duration from prepare until the receiver claimed the transfer
+    pub claimed: Option<Duration>,
     /// Whether a payment-time swap was triggered
     pub had_swap: bool,
     /// Whether leaf optimization was cancelled during this payment
@@ -340,6 +345,35 @@ impl BenchmarkResults {
         self.measurements.iter().map(|m| m.duration).collect()
     }
 
+    /// Get durations from prepare until the receiver started claiming.
+    pub fn claim_start_durations(&self) -> Vec<Duration> {
+        self.measurements
+            .iter()
+            .filter_map(|m| m.claim_started)
+            .collect()
+    }
+
+    /// Get durations from prepare until the receiver claimed the payment.
+    pub fn claimed_durations(&self) -> Vec<Duration> {
+        self.measurements.iter().filter_map(|m| m.claimed).collect()
+    }
+
+    /// Get durations between the receiver starting and finishing the claim.
+    pub fn claim_stage_durations(&self) -> Vec<Duration> {
+        self.measurements
+            .iter()
+            .filter_map(|m| Some(m.claimed?.saturating_sub(m.claim_started?)))
+            .collect()
+    }
+
+    /// Get durations between the sender completing and the receiver claiming.
+    pub fn send_to_claimed_durations(&self) -> Vec<Duration> {
+        self.measurements
+            .iter()
+            .filter_map(|m| Some(m.claimed?.saturating_sub(m.duration)))
+            .collect()
+    }
+
     /// Get durations for payments with neither swap nor cancellation.
     pub fn no_swap_no_cancel_durations(&self) -> Vec<Duration> {
         self.measurements
@@ -467,6 +501,26 @@ impl BenchmarkResults {
             println!("  Swap and cancellation: (no measurements)");
         }
 
+        println!();
+        println!("Receive path:");
+        let stages = [
+            ("  Time to send complete     ", self.all_durations()),
+            ("  Time to claim start       ", self.claim_start_durations()),
+            ("  Time to claimed           ", self.claimed_durations()),
+            (
+                "  Send complete to claimed  ",
+                self.send_to_claimed_durations(),
+            ),
+            ("  Claim start to claimed    ", self.claim_stage_durations()),
+        ];
+        for (label, durations) in stages {
+            if let Some(stats) = DurationStats::from_durations(&durations) {
+                stats.print_summary(label);
+            } else {
+                println!("{}: (no measurements)", label.trim_end());
+            }
+        }
+
         // Print histograms
         if show_histogram {
             println!();
@@ -500,6 +554,9 @@ impl BenchmarkResults {
             {
                 histogram.print("Duration Distribution (Swap and Cancellation)", 40);
             }
+            if let Some(histogram) = Histogram::from_durations(&self.claimed_durations()) {
+                histogram.print("Time to Claimed Distribution", 40);
+            }
         }
 
         println!();
@@ -644,6 +701,44 @@ mod tests {
         assert_eq!(total_in_buckets, 1);
     }
 
+    #[test]
+    fn test_receive_stage_durations() {
+        let mut results = BenchmarkResults::new(1);
+        results.add(PaymentMeasurement {
+            duration: Duration::from_millis(100),
+            claim_started: Some(Duration::from_millis(250)),
+            claimed: Some(Duration::from_millis(400)),
+            had_swap: false,
+            had_cancellation: false,
+            amount_sats: 1000,
+        });
+        results.add(PaymentMeasurement {
+            duration: Duration::from_millis(200),
+            claim_started: None,
+            claimed: Some(Duration::from_millis(300)),
+            had_swap: false,
+            had_cancellation: false,
+            amount_sats: 1000,
+        });
+
+        assert_eq!(
+            results.claim_start_durations(),
+            vec![Duration::from_millis(250)]
+        );
+        assert_eq!(
+            results.claimed_durations(),
+            vec![Duration::from_millis(400), Duration::from_millis(300)]
+        );
+        assert_eq!(
+            results.send_to_claimed_durations(),
+            vec![Duration::from_millis(300), Duration::from_millis(100)]
+        );
+        assert_eq!(
+            results.claim_stage_durations(),
+            vec![Duration::from_millis(150)]
+        );
+    }
+
     #[test]
     fn test_format_range() {
         assert_eq!(format_range(0, 100), "0ms-100ms");
@@ -162,6 +162,7 @@ where
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -185,6 +186,8 @@ where
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         }),
         instance_1.sdk.sync_wallet(SyncWalletRequest {}),
         instance_2.sdk.sync_wallet(SyncWalletRequest {})
@@ -252,6 +255,7 @@ where
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -276,6 +280,8 @@ where
             prepare_response: prepare_return,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     expected_payment_count += 1;
@@ -343,6 +349,7 @@ where
             .sdk
             .receive_payment(ReceivePaymentRequest {
                 payment_method: ReceivePaymentMethod::SparkAddress,
+                order_ref: None,
             })
             .await?
             .payment_request;
@@ -365,6 +372,8 @@ where
                         prepare_response: prepare,
                         options: None,
                         idempotency_key: None,
+                        compliance_metadata: None,
+                        queue_if_offline: None,
                     }),
                     instances[1].sdk.sync_wallet(SyncWalletRequest {}),
                     instances[2].sdk.sync_wallet(SyncWalletRequest {})
@@ -380,6 +389,8 @@ where
                         prepare_response: prepare,
                         options: None,
                         idempotency_key: None,
+                        compliance_metadata: None,
+                        queue_if_offline: None,
                     }),
                     instances[2].sdk.sync_wallet(SyncWalletRequest {})
                 );
@@ -395,6 +406,8 @@ where
                         prepare_response: prepare,
                         options: None,
                         idempotency_key: None,
+                        compliance_metadata: None,
+                        queue_if_offline: None,
                     })
                 );
                 s0?;
@@ -539,6 +552,7 @@ where
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -560,6 +574,8 @@ where
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -634,6 +650,7 @@ where
                 .sdk
                 .receive_payment(ReceivePaymentRequest {
                     payment_method: ReceivePaymentMethod::SparkAddress,
+                    order_ref: None,
                 })
                 .await?
                 .payment_request;
@@ -657,6 +674,8 @@ where
                             prepare_response: prepare,
                             options: None,
                             idempotency_key: None,
+                            compliance_metadata: None,
+                            queue_if_offline: None,
                         }),
                         instances[syncer_idxs[0]]
                             .sdk
@@ -678,6 +697,8 @@ where
                             prepare_response: prepare,
                             options: None,
                             idempotency_key: None,
+                            compliance_metadata: None,
+                            queue_if_offline: None,
                         }),
                         instances[syncer_idxs[1]]
                             .sdk
@@ -699,6 +720,8 @@ where
                             prepare_response: prepare,
                             options: None,
                             idempotency_key: None,
+                            compliance_metadata: None,
+                            queue_if_offline: None,
                         })
                     );
                     s0?;
@@ -728,6 +751,7 @@ where
                 .sdk
                 .receive_payment(ReceivePaymentRequest {
                     payment_method: ReceivePaymentMethod::SparkAddress,
+                    order_ref: None,
                 })
                 .await?
                 .payment_request;
@@ -748,6 +772,8 @@ where
                     prepare_response: prepare,
                     options: None,
                     idempotency_key: None,
+                    compliance_metadata: None,
+                    queue_if_offline: None,
                 }),
                 instances[0].sdk.sync_wallet(SyncWalletRequest {}),
                 instances[1].sdk.sync_wallet(SyncWalletRequest {}),
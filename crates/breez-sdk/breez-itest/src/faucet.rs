@@ -3,8 +3,11 @@ use std::sync::LazyLock;
 use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
+use base64::Engine;
+use breez_sdk_spark::{DevToolsConfig, RegtestLightningNode};
 use platform_utils::{
-    ContentType, DefaultHttpClient, HttpClient, add_basic_auth_header, add_content_type_header,
+    ContentType, DefaultHttpClient, HttpClient, HttpError, HttpResponse, add_basic_auth_header,
+    add_content_type_header,
 };
 use serde::{Deserialize, Serialize};
 use tokio::sync::Semaphore;
@@ -118,43 +121,10 @@ impl RegtestFaucet {
     /// # Returns
     /// The transaction hash of the funding transaction
     pub async fn fund_address(&self, address: &str, amount_sats: u64) -> Result<String> {
-        const MAX_RETRIES: u32 = 3;
-
-        // Acquire semaphore permit to limit concurrent faucet requests
-        let _permit = FAUCET_SEMAPHORE
-            .acquire()
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to acquire faucet semaphore: {}", e))?;
-
         info!(
             "Requesting funds from faucet: {} sats to address {}",
             amount_sats, address
         );
-
-        let mut last_error = None;
-
-        for attempt in 0..=MAX_RETRIES {
-            if attempt > 0 {
-                let backoff = Duration::from_secs(2u64.pow(attempt));
-                warn!(
-                    "Faucet request failed, retrying in {}s (attempt {}/{})",
-                    backoff.as_secs(),
-                    attempt + 1,
-                    MAX_RETRIES + 1,
-                );
-                tokio::time::sleep(backoff).await;
-            }
-
-            match self.try_fund_address(address, amount_sats).await {
-                Ok(txid) => return Ok(txid),
-                Err(e) => last_error = Some(e),
-            }
-        }
-
-        Err(last_error.unwrap())
-    }
-
-    async fn try_fund_address(&self, address: &str, amount_sats: u64) -> Result<String> {
         let request_body = GraphQLRequest {
             operation_name: "RequestRegtestFunds".to_string(),
             variables: FaucetVariables {
@@ -177,11 +147,15 @@ impl RegtestFaucet {
             add_basic_auth_header(&mut headers, username, password);
         }
 
-        let response = self
-            .http_client
-            .post(self.config.url.clone(), Some(headers), Some(body_json))
-            .await
-            .context("Failed to send faucet request")?;
+        let response = with_faucet_retries(|| {
+            self.http_client.post(
+                self.config.url.clone(),
+                Some(headers.clone()),
+                Some(body_json.clone()),
+            )
+        })
+        .await
+        .context("Failed to send faucet request")?;
 
         if !response.is_success() {
             bail!(
@@ -217,3 +191,183 @@ impl Default for RegtestFaucet {
         Self::new().expect("Failed to create default faucet client")
     }
 }
+
+/// Configuration for the lightning faucet
+#[derive(Debug, Clone)]
+pub struct LightningFaucetConfig {
+    /// REST endpoint of the pre-funded LND node paying the invoices
+    /// Can be set with LIGHTNING_FAUCET_URL environment variable
+    /// Default: http://127.0.0.1:8080
+    pub url: String,
+    /// Optional hex-encoded macaroon authorizing payments
+    /// Can be set with LIGHTNING_FAUCET_MACAROON environment variable
+    pub macaroon: Option<String>,
+}
+
+impl Default for LightningFaucetConfig {
+    fn default() -> Self {
+        Self {
+            url: std::env::var("LIGHTNING_FAUCET_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:8080".to_string()),
+            macaroon: std::env::var("LIGHTNING_FAUCET_MACAROON").ok(),
+        }
+    }
+}
+
+/// Client paying bolt11 invoices from a pre-funded lightning node
+///
+/// Lets tests exercise lightning receive paths without manual funding. The node
+/// is expected to expose the LND REST API and to have outbound liquidity towards
+/// the regtest SSP.
+pub struct LightningFaucet {
+    config: LightningFaucetConfig,
+    http_client: DefaultHttpClient,
+}
+
+#[derive(Debug, Serialize)]
+struct LndSendPaymentRequest {
+    payment_request: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LndSendPaymentResponse {
+    #[serde(default)]
+    payment_error: String,
+    /// Base64-encoded preimage
+    #[serde(default)]
+    payment_preimage: String,
+}
+
+impl LightningFaucet {
+    /// Create a new lightning faucet client with default configuration
+    pub fn new() -> Result<Self> {
+        Self::with_config(LightningFaucetConfig::default())
+    }
+
+    /// Create a new lightning faucet client with custom configuration
+    pub fn with_config(config: LightningFaucetConfig) -> Result<Self> {
+        info!(
+            "Initialized lightning faucet client with URL: {}",
+            config.url
+        );
+        Ok(Self {
+            config,
+            http_client: DefaultHttpClient::default(),
+        })
+    }
+
+    /// Pay a bolt11 invoice from the faucet node
+    ///
+    /// Shares the global faucet semaphore with [`RegtestFaucet`], so on-chain and
+    /// lightning payouts together stay within the configured concurrency.
+    ///
+    /// # Arguments
+    /// * `invoice` - Bolt11 invoice to pay. Must specify an amount.
+    ///
+    /// # Returns
+    /// The hex-encoded payment preimage
+    pub async fn pay_invoice(&self, invoice: &str) -> Result<String> {
+        info!("Requesting lightning faucet to pay invoice {}", invoice);
+        let body_json = serde_json::to_string(&LndSendPaymentRequest {
+            payment_request: invoice.to_string(),
+        })
+        .context("Failed to serialize request body")?;
+
+        let mut headers = HashMap::new();
+        add_content_type_header(&mut headers, ContentType::Json);
+        if let Some(macaroon) = &self.config.macaroon {
+            headers.insert("Grpc-Metadata-macaroon".to_string(), macaroon.clone());
+        }
+
+        let url = format!(
+            "{}/v1/channels/transactions",
+            self.config.url.trim_end_matches('/')
+        );
+        let response = with_faucet_retries(|| {
+            self.http_client
+                .post(url.clone(), Some(headers.clone()), Some(body_json.clone()))
+        })
+        .await
+        .context("Failed to send lightning faucet request")?;
+
+        if !response.is_success() {
+            bail!(
+                "Lightning faucet request failed with status {}: {}",
+                response.status,
+                response.body
+            );
+        }
+
+        let payment: LndSendPaymentResponse = response.json().context(response.body.to_string())?;
+        if !payment.payment_error.is_empty() {
+            bail!("Lightning faucet payment failed: {}", payment.payment_error);
+        }
+
+        let preimage = base64::engine::general_purpose::STANDARD
+            .decode(&payment.payment_preimage)
+            .context("Invalid preimage in lightning faucet response")?;
+        let preimage = hex::encode(preimage);
+
+        info!("Lightning faucet paid invoice, preimage: {}", preimage);
+        Ok(preimage)
+    }
+}
+
+/// Builds a [`DevToolsConfig`] from the faucet environment variables, so that
+/// [`breez_sdk_spark::DevTools`] reaches the same services as the faucet clients
+pub fn dev_tools_config() -> DevToolsConfig {
+    let faucet = FaucetConfig::default();
+    let lightning = LightningFaucetConfig::default();
+    DevToolsConfig {
+        faucet_url: Some(faucet.url),
+        faucet_username: faucet.username,
+        faucet_password: faucet.password,
+        lightning_node: Some(RegtestLightningNode {
+            url: lightning.url,
+            macaroon: lightning.macaroon,
+        }),
+        bitcoind: None,
+    }
+}
+
+/// Status codes the faucets reject a request with before acting on it
+const REJECTED_STATUSES: [u16; 2] = [429, 503];
+
+/// Runs a faucet request under the global faucet semaphore, retrying with
+/// exponential backoff while the faucet rejects it, e.g. when rate limited.
+///
+/// Other failures aren't retried: the faucet may have sent the funds before
+/// failing, so sending the request again could pay twice.
+async fn with_faucet_retries<F, Fut>(mut request: F) -> Result<HttpResponse, HttpError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<HttpResponse, HttpError>>,
+{
+    const MAX_RETRIES: u32 = 3;
+
+    // Acquire semaphore permit to limit concurrent faucet requests
+    let _permit = FAUCET_SEMAPHORE
+        .acquire()
+        .await
+        .map_err(|e| HttpError::Other(format!("Failed to acquire faucet semaphore: {e}")))?;
+
+    let mut attempt = 0;
+    loop {
+        if attempt > 0 {
+            let backoff = Duration::from_secs(2u64.pow(attempt));
+            warn!(
+                "Faucet request rejected, retrying in {}s (attempt {}/{})",
+                backoff.as_secs(),
+                attempt + 1,
+                MAX_RETRIES + 1,
+            );
+            tokio::time::sleep(backoff).await;
+        }
+
+        let response = request().await?;
+        if attempt == MAX_RETRIES || !REJECTED_STATUSES.contains(&response.status) {
+            return Ok(response);
+        }
+        attempt += 1;
+    }
+}
@@ -0,0 +1,94 @@
+//! Pre-funded lightning node container backing the [`LightningFaucet`].
+
+use anyhow::{Result, bail};
+use testcontainers::{
+    ContainerAsync, GenericImage, ImageExt,
+    core::{ContainerPort, WaitFor, wait::LogWaitStrategy},
+    runners::AsyncRunner,
+};
+use tracing::info;
+
+use crate::faucet::{LightningFaucet, LightningFaucetConfig};
+
+const REST_PORT: u16 = 8080;
+
+/// Configuration for the pre-funded lightning node image
+#[derive(Debug, Clone)]
+pub struct LightningNodeImageConfig {
+    /// Image name
+    pub image_name: String,
+    /// Image tag
+    pub image_tag: String,
+    /// Line logged by the node once its REST API accepts payments
+    pub ready_log: String,
+    /// Optional hex-encoded macaroon authorizing payments on the node
+    pub macaroon: Option<String>,
+}
+
+impl Default for LightningNodeImageConfig {
+    /// Reads the image from LIGHTNING_FAUCET_IMAGE (`<name>:<tag>`) and the macaroon
+    /// from LIGHTNING_FAUCET_MACAROON.
+    fn default() -> Self {
+        let image = std::env::var("LIGHTNING_FAUCET_IMAGE")
+            .unwrap_or_else(|_| "breez-lightning-faucet:latest".to_string());
+        let (image_name, image_tag) = image
+            .split_once(':')
+            .map(|(name, tag)| (name.to_string(), tag.to_string()))
+            .unwrap_or((image, "latest".to_string()));
+        Self {
+            image_name,
+            image_tag,
+            ready_log: "gRPC proxy started".to_string(),
+            macaroon: std::env::var("LIGHTNING_FAUCET_MACAROON").ok(),
+        }
+    }
+}
+
+/// A pre-funded LND node running in a container.
+///
+/// The image is expected to ship with a funded wallet and channels to the regtest
+/// SSP, so invoices generated by the SDK can be paid right away.
+pub struct LightningNodeFixture {
+    pub container: ContainerAsync<GenericImage>,
+    pub rest_url: String,
+    macaroon: Option<String>,
+}
+
+impl LightningNodeFixture {
+    /// Start the node using the default configuration
+    pub async fn new() -> Result<Self> {
+        Self::with_config(LightningNodeImageConfig::default()).await
+    }
+
+    /// Start the node with a custom configuration
+    pub async fn with_config(config: LightningNodeImageConfig) -> Result<Self> {
+        if config.image_name.is_empty() {
+            bail!("Lightning node image name must not be empty");
+        }
+
+        let container = GenericImage::new(&config.image_name, &config.image_tag)
+            .with_exposed_port(ContainerPort::Tcp(REST_PORT))
+            .with_wait_for(WaitFor::Log(LogWaitStrategy::stdout(&config.ready_log)))
+            .with_log_consumer(crate::log::TracingConsumer::new("lightning-node"))
+            .start()
+            .await?;
+
+        let host_port = container.get_host_port_ipv4(REST_PORT).await?;
+        let rest_url = format!("http://127.0.0.1:{host_port}");
+        info!("Lightning node available at REST: {rest_url}");
+
+        Ok(Self {
+            container,
+            rest_url,
+            macaroon: config.macaroon,
+        })
+    }
+
+    /// A faucet paying invoices from this node
+    pub fn faucet(&self) -> Result<LightningFaucet> {
+        LightningFaucet::with_config(LightningFaucetConfig {
+            url: self.rest_url.clone(),
+            macaroon: self.macaroon.clone(),
+        })
+    }
+}
@@ -1,5 +1,23 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::fixtures::docker::{DockerImageConfig, build_docker_image};
 use anyhow::Result;
+use axum::{
+    Json, Router,
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+    routing::get,
+};
+use bech32::{Bech32, Hrp};
+use breez_sdk_spark::{BreezSdk, PaymentRequest, PrepareSendPaymentRequest, SendPaymentRequest};
+use nostr::{
+    Event, EventBuilder, JsonUtil, Keys, Kind, RelayUrl, TagKind, nips::nip57::ZapRequestData,
+};
 use testcontainers::{
     ContainerAsync, GenericImage, ImageExt,
     core::{ContainerPort, Host, WaitFor, wait::LogWaitStrategy},
@@ -9,6 +27,9 @@ use testcontainers_modules::postgres::Postgres;
 use tracing::info;
 
 const HTTP_PORT: u16 = 8080;
+/// Nostr secret key the LNURL server signs zap receipts with (nsec encoding of key 0x00...01)
+pub const LNURL_NOSTR_NSEC: &str =
+    "nsec1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqsmhltgl";
 
 /// Configuration for building the lnurl Docker image
 #[derive(Debug, Clone)]
@@ -121,10 +142,7 @@ impl LnurlFixture {
             // Use HTTP scheme for testing
             .with_env_var("BREEZ_LNURL_SCHEME", "http")
             // Set a test nostr secret key for zap receipt support (nsec encoding of key 0x00...01)
-            .with_env_var(
-                "BREEZ_LNURL_NSEC",
-                "nsec1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqsmhltgl",
-            )
+            .with_env_var("BREEZ_LNURL_NSEC", LNURL_NOSTR_NSEC)
             .with_env_var("BREEZ_LNURL_MIN_SENDABLE", "1000")
             .with_env_var("BREEZ_LNURL_MAX_SENDABLE", "1000000000")
             .with_env_var(
@@ -182,4 +200,316 @@ impl LnurlFixture {
     pub fn http_url(&self) -> &str {
         &self.http_url
     }
+
+    /// Requests an invoice for a NIP-57 zap of `amount_msat` to `username`.
+    ///
+    /// The zap request is signed with a fresh key and asks for the zap receipt
+    /// to be published to `relay`, typically a [`MockNostrRelay`].
+    pub async fn request_zap_invoice(
+        &self,
+        username: &str,
+        amount_msat: u64,
+        relay: &MockNostrRelay,
+    ) -> Result<ZapInvoice> {
+        let pay_url = format!("{}/lnurlp/{username}", self.http_url);
+        let pay_response: serde_json::Value = reqwest::get(&pay_url).await?.json().await?;
+        if pay_response["allowsNostr"].as_bool() != Some(true) {
+            anyhow::bail!("LNURL server does not allow nostr zaps: {pay_response}");
+        }
+        let callback = pay_response["callback"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing callback in {pay_response}"))?;
+
+        let recipient = Keys::parse(LNURL_NOSTR_NSEC)?.public_key();
+        let data = ZapRequestData::new(recipient, [RelayUrl::parse(relay.container_url())?])
+            .amount(amount_msat)
+            .lnurl(encode_lnurl(&pay_url)?);
+        let zap_request =
+            EventBuilder::public_zap_request(data).sign_with_keys(&Keys::generate())?;
+
+        let invoice_response: serde_json::Value = reqwest::Client::new()
+            .get(callback)
+            .query(&[
+                ("amount", amount_msat.to_string()),
+                ("nostr", zap_request.as_json()),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+        let invoice = invoice_response["pr"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("missing invoice in {invoice_response}"))?
+            .to_string();
+
+        Ok(ZapInvoice {
+            invoice,
+            zap_request,
+        })
+    }
+}
+
+/// Invoice returned by the LNURL server for a zap request.
+pub struct ZapInvoice {
+    pub invoice: String,
+    pub zap_request: Event,
+}
+
+impl ZapInvoice {
+    /// Asserts `receipt` is a valid NIP-57 zap receipt for this zap, signed by
+    /// the LNURL server.
+    pub fn assert_receipt(&self, receipt: &Event) -> Result<()> {
+        anyhow::ensure!(
+            receipt.kind == Kind::ZapReceipt,
+            "expected zap receipt, got kind {}",
+            receipt.kind
+        );
+        receipt.verify()?;
+        anyhow::ensure!(
+            receipt.pubkey == Keys::parse(LNURL_NOSTR_NSEC)?.public_key(),
+            "zap receipt not signed by the LNURL server"
+        );
+        let tag_content = |kind: TagKind| {
+            receipt
+                .tags
+                .find(kind)
+                .and_then(|t| t.content())
+                .map(ToString::to_string)
+        };
+        anyhow::ensure!(
+            tag_content(TagKind::Bolt11).as_deref() == Some(self.invoice.as_str()),
+            "zap receipt bolt11 tag does not match the zap invoice"
+        );
+        anyhow::ensure!(
+            tag_content(TagKind::Description) == Some(self.zap_request.as_json()),
+            "zap receipt description tag does not match the zap request"
+        );
+        Ok(())
+    }
+}
+
+/// Encodes a URL as a bech32 LNURL
+pub fn encode_lnurl(url: &str) -> Result<String> {
+    let hrp = Hrp::parse("lnurl")?;
+    Ok(bech32::encode::<Bech32>(hrp, url.as_bytes())?.to_lowercase())
+}
+
+async fn bind_local_server(router: Router) -> Result<u16> {
+    // Bind on all interfaces so the LNURL container can reach the server via
+    // host.docker.internal.
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:0").await?;
+    let port = listener.local_addr()?.port();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    Ok(port)
+}
+
+// ---------------------
+// Mock LNURL-withdraw service
+// ---------------------
+
+#[derive(Clone)]
+struct WithdrawState {
+    payer: BreezSdk,
+    k1: String,
+    callback: String,
+    min_withdrawable_msat: u64,
+    max_withdrawable_msat: u64,
+    paid_invoices: Arc<tokio::sync::Mutex<Vec<String>>>,
+}
+
+/// LNURL-withdraw service that pays the invoices it receives from `payer`.
+pub struct LnurlWithdrawService {
+    url: String,
+    paid_invoices: Arc<tokio::sync::Mutex<Vec<String>>>,
+}
+
+impl LnurlWithdrawService {
+    /// Starts the service. Withdrawals are paid by `payer`, which must be funded.
+    pub async fn start(
+        payer: BreezSdk,
+        min_withdrawable_msat: u64,
+        max_withdrawable_msat: u64,
+    ) -> Result<Self> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://localhost:{}", listener.local_addr()?.port());
+        let paid_invoices = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let state = WithdrawState {
+            payer,
+            k1: uuid::Uuid::new_v4().to_string(),
+            callback: format!("{url}/withdraw/callback"),
+            min_withdrawable_msat,
+            max_withdrawable_msat,
+            paid_invoices: Arc::clone(&paid_invoices),
+        };
+        let router = Router::new()
+            .route("/withdraw", get(withdraw_handler))
+            .route("/withdraw/callback", get(withdraw_callback_handler))
+            .with_state(state);
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        info!("Mock LNURL-withdraw service started at {url}");
+
+        Ok(Self { url, paid_invoices })
+    }
+
+    /// The bech32 LNURL of the withdraw request
+    pub fn lnurl(&self) -> Result<String> {
+        encode_lnurl(&self.withdraw_url())
+    }
+
+    /// The URL of the withdraw request
+    pub fn withdraw_url(&self) -> String {
+        format!("{}/withdraw", self.url)
+    }
+
+    /// The invoices paid so far. An invoice is only listed once its payment
+    /// succeeded.
+    pub async fn paid_invoices(&self) -> Vec<String> {
+        self.paid_invoices.lock().await.clone()
+    }
+}
+
+async fn withdraw_handler(State(state): State<WithdrawState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "tag": "withdrawRequest",
+        "callback": state.callback,
+        "k1": state.k1,
+        "defaultDescription": "itest withdraw",
+        "minWithdrawable": state.min_withdrawable_msat,
+        "maxWithdrawable": state.max_withdrawable_msat,
+    }))
+}
+
+async fn withdraw_callback_handler(
+    Query(params): Query<HashMap<String, String>>,
+    State(state): State<WithdrawState>,
+) -> Json<serde_json::Value> {
+    let error = |reason: String| Json(serde_json::json!({"status": "ERROR", "reason": reason}));
+    if params.get("k1") != Some(&state.k1) {
+        return error("invalid k1".to_string());
+    }
+    let Some(invoice) = params.get("pr").cloned() else {
+        return error("missing pr".to_string());
+    };
+
+    let prepare_response = match state
+        .payer
+        .prepare_send_payment(PrepareSendPaymentRequest {
+            payment_request: PaymentRequest::Input {
+                input: invoice.clone(),
+            },
+            amount: None,
+            token_identifier: None,
+            conversion_options: None,
+            fee_policy: None,
+        })
+        .await
+    {
+        Ok(prepare_response) => prepare_response,
+        Err(e) => return error(e.to_string()),
+    };
+    // Pay in the background: per LUD-03 the service answers the callback
+    // before the payment completes. The invoice is only recorded once paid.
+    let payer = state.payer.clone();
+    let paid_invoices = Arc::clone(&state.paid_invoices);
+    tokio::spawn(async move {
+        match payer
+            .send_payment(SendPaymentRequest {
+                prepare_response,
+                options: None,
+                idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
+            })
+            .await
+        {
+            Ok(_) => paid_invoices.lock().await.push(invoice),
+            Err(e) => {
+                tracing::error!("Mock LNURL-withdraw service failed to pay invoice: {e}");
+            }
+        }
+    });
+    Json(serde_json::json!({"status": "OK"}))
+}
+
+// ---------------------
+// Mock nostr relay
+// ---------------------
+
+/// Minimal nostr relay that accepts and records published events.
+pub struct MockNostrRelay {
+    port: u16,
+    events: Arc<tokio::sync::Mutex<Vec<Event>>>,
+}
+
+impl MockNostrRelay {
+    pub async fn start() -> Result<Self> {
+        let events = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let router = Router::new()
+            .route("/", get(relay_handler))
+            .with_state(Arc::clone(&events));
+        let port = bind_local_server(router).await?;
+        info!("Mock nostr relay started on port {port}");
+        Ok(Self { port, events })
+    }
+
+    /// Relay URL reachable from inside the LNURL container
+    pub fn container_url(&self) -> String {
+        format!("ws://host.docker.internal:{}", self.port)
+    }
+
+    /// Events published to the relay so far
+    pub async fn events(&self) -> Vec<Event> {
+        self.events.lock().await.clone()
+    }
+
+    /// Waits until an event of `kind` has been published to the relay.
+    pub async fn wait_for_event(&self, kind: Kind, timeout_secs: u64) -> Result<Event> {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+        loop {
+            if let Some(event) = self.events().await.into_iter().find(|e| e.kind == kind) {
+                return Ok(event);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                anyhow::bail!("Timeout waiting for nostr event of kind {kind}");
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+}
+
+async fn relay_handler(
+    ws: WebSocketUpgrade,
+    State(events): State<Arc<tokio::sync::Mutex<Vec<Event>>>>,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_relay_socket(socket, events))
+}
+
+async fn handle_relay_socket(mut socket: WebSocket, events: Arc<tokio::sync::Mutex<Vec<Event>>>) {
+    while let Some(Ok(message)) = socket.recv().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let Ok(serde_json::Value::Array(parts)) = serde_json::from_str(&text) else {
+            continue;
+        };
+        let reply = match parts.first().and_then(|v| v.as_str()) {
+            Some("EVENT") => {
+                let Some(Ok(event)) = parts.get(1).map(|v| Event::from_json(v.to_string())) else {
+                    continue;
+                };
+                let reply = serde_json::json!(["OK", event.id.to_hex(), true, ""]);
+                events.lock().await.push(event);
+                reply
+            }
+            Some("REQ") => serde_json::json!(["EOSE", parts.get(1)]),
+            _ => continue,
+        };
+        if socket.send(Message::Text(reply.to_string())).await.is_err() {
+            return;
+        }
+    }
 }
@@ -1,5 +1,6 @@
 pub mod data_sync;
 pub mod docker;
+pub mod lightning_node;
 pub mod lnurl;
 
 use anyhow::Result;
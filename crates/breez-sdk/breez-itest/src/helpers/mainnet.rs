@@ -344,6 +344,7 @@ async fn quote_tokens_per_sat(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -600,6 +601,7 @@ pub async fn ensure_wallet_has_tokens(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -646,6 +648,8 @@ pub async fn ensure_wallet_has_tokens(
             prepare_response: topup_prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     wait_for_token_balance_increase(&recipient.sdk, token_id, before, 120).await?;
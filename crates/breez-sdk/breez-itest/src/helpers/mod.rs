@@ -477,7 +477,7 @@ pub async fn wait_for_payment_failed_event(
         timeout_secs,
         "PaymentFailed",
         |event| match event {
-            SdkEvent::PaymentFailed { payment } if payment.payment_type == payment_type => {
+            SdkEvent::PaymentFailed { payment, .. } if payment.payment_type == payment_type => {
                 info!(
                     "Received PaymentFailed event: {} sats, type: {:?}",
                     payment.amount, payment.payment_type
@@ -14,9 +14,12 @@ use testcontainers_modules::postgres::Postgres;
 use tokio::sync::{OnceCell, mpsc};
 use tracing::{Instrument, info};
 
-use super::{ChannelEventListener, wait_for_balance, wait_for_claimed_event};
+use super::{
+    ChannelEventListener, wait_for_balance, wait_for_claimed_event,
+    wait_for_payment_succeeded_event,
+};
 use crate::SdkInstance;
-use crate::faucet::RegtestFaucet;
+use crate::faucet::{LightningFaucet, RegtestFaucet};
 
 /// Shared PostgreSQL container for tree store testing.
 /// Started once on first access and kept alive for the process lifetime.
@@ -1089,6 +1092,7 @@ async fn receive_and_fund_inner(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?;
 
@@ -1119,6 +1123,51 @@ async fn receive_and_fund_inner(
     Ok((deposit_address, txid))
 }
 
+/// Receive funds over lightning by having the lightning faucet pay a fresh invoice
+///
+/// # Arguments
+/// * `sdk_instance` - The SDK instance to fund
+/// * `faucet` - The lightning faucet paying the invoice
+/// * `amount_sats` - Amount to receive
+///
+/// # Returns
+/// The received payment
+pub async fn receive_lightning_and_fund(
+    sdk_instance: &mut SdkInstance,
+    faucet: &LightningFaucet,
+    amount_sats: u64,
+) -> Result<Payment> {
+    let span = sdk_instance.span.clone();
+    async {
+        let invoice = sdk_instance
+            .sdk
+            .receive_payment(ReceivePaymentRequest {
+                payment_method: ReceivePaymentMethod::Bolt11Invoice {
+                    description: "Lightning faucet funding".to_string(),
+                    amount_sats: Some(amount_sats),
+                    expiry_secs: None,
+                    payment_hash: None,
+                    min_amount_sats: None,
+                    max_amount_sats: None,
+                },
+                order_ref: None,
+            })
+            .await?
+            .payment_request;
+        info!("Generated invoice for lightning faucet: {}", invoice);
+
+        faucet.pay_invoice(&invoice).await?;
+
+        let payment =
+            wait_for_payment_succeeded_event(&mut sdk_instance.events, PaymentType::Receive, 120)
+                .await?;
+        sdk_instance.sdk.sync_wallet(SyncWalletRequest {}).await?;
+        Ok(payment)
+    }
+    .instrument(span)
+    .await
+}
+
 /// Build and initialize a BreezSDK instance backed by PostgreSQL storage
 ///
 /// # Arguments
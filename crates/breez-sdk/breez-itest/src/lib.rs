@@ -18,9 +18,12 @@ pub use chain_service::LocalBitcoindChainService;
 pub use concurrent_scenarios::{
     RuntimeMode, run_concurrent_multi_instance_operations, run_concurrent_token_operations,
 };
-pub use faucet::RegtestFaucet;
+pub use faucet::{LightningFaucet, LightningFaucetConfig, RegtestFaucet, dev_tools_config};
 pub use fixtures::data_sync::{DataSyncFixture, DataSyncImageConfig};
-pub use fixtures::lnurl::{LnurlFixture, LnurlImageConfig};
+pub use fixtures::lightning_node::{LightningNodeFixture, LightningNodeImageConfig};
+pub use fixtures::lnurl::{
+    LnurlFixture, LnurlImageConfig, LnurlWithdrawService, MockNostrRelay, ZapInvoice,
+};
 pub use fixtures::*;
 pub use helpers::*;
 #[cfg(feature = "local-itest")]
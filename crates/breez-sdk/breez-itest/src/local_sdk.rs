@@ -6,11 +6,12 @@
 //! needs (mining, CSV maturity, package broadcast).
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use breez_sdk_spark::{
     BreezSdk, Network, SdkBuilder, Seed, SparkConfig, SparkSigningOperator, SparkSspConfig,
-    default_config,
+    TestClock, default_config,
 };
 use spark_itest::fixtures::setup::TestFixtures;
 use spark_wallet::{DefaultSigner, SparkSignerAdapter, SparkWallet, WalletEvent};
@@ -30,6 +31,8 @@ pub struct LocalSdk {
     pub spark_wallet: SparkWallet,
     pub events: mpsc::Receiver<breez_sdk_spark::SdkEvent>,
     pub fixtures: Arc<TestFixtures>,
+    /// Clock driving the SDK's wall-clock expiries. See [`LocalSdk::time_travel`].
+    pub clock: Arc<TestClock>,
     #[allow(dead_code)]
     storage_dir: TempDir,
     /// Deletes the throwaway Turnkey wallet on drop (Turnkey backend only).
@@ -105,12 +108,15 @@ pub async fn build_local_sdk(
     #[cfg(feature = "turnkey")]
     let mut turnkey_guard: Option<crate::turnkey::TurnkeyWalletGuard> = None;
 
+    let clock = Arc::new(TestClock::new());
+
     let (sdk, spark_wallet) = match backend {
         SignerBackend::Seed => {
             let mut seed = [0u8; 32];
             rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut seed);
             let sdk = SdkBuilder::new(config, Seed::Entropy(seed.to_vec()))
                 .with_chain_service(chain_service)
+                .with_clock(clock.clone())
                 .with_default_storage(storage_path)
                 .build()
                 .await?;
@@ -137,6 +143,7 @@ pub async fn build_local_sdk(
                 Arc::clone(&signers.spark_signer),
             )
             .with_chain_service(chain_service)
+            .with_clock(clock.clone())
             .with_default_storage(storage_path)
             .build()
             .await?;
@@ -176,12 +183,43 @@ pub async fn build_local_sdk(
         spark_wallet,
         events,
         fixtures,
+        clock,
         storage_dir,
         #[cfg(feature = "turnkey")]
         turnkey_guard,
     })
 }
 
+/// Largest batch requested from bitcoind in a single `generatetoaddress` call.
+const MINE_BATCH_SIZE: u64 = 1_000;
+/// Regtest block interval used to convert elapsed time into blocks.
+const BLOCK_INTERVAL_SECS: u64 = 600;
+
+impl LocalSdk {
+    /// Mine `count` blocks in bulk, returning the new chain tip height.
+    pub async fn mine_blocks(&self, count: u64) -> Result<u64> {
+        let mut remaining = count;
+        while remaining > 0 {
+            let batch = remaining.min(MINE_BATCH_SIZE);
+            self.fixtures.bitcoind.generate_blocks(batch).await?;
+            remaining -= batch;
+        }
+        let height: u64 = self.fixtures.bitcoind.rpc("getblockcount", &[]).await?;
+        debug!("mined {count} blocks, tip at {height}");
+        Ok(height)
+    }
+
+    /// Fast-forward time by `duration`: advances the SDK clock, which drives
+    /// wall-clock expiries such as Spark invoice expiry, and mines the number of
+    /// blocks that would be produced in that time, which drives block-height
+    /// timelocks such as leaf refund expiry.
+    pub async fn time_travel(&self, duration: Duration) -> Result<u64> {
+        self.clock.advance(duration);
+        self.mine_blocks(duration.as_secs().div_ceil(BLOCK_INTERVAL_SECS))
+            .await
+    }
+}
+
 struct ChannelEventListener {
     tx: mpsc::Sender<breez_sdk_spark::SdkEvent>,
 }
@@ -49,6 +49,7 @@ async fn test_01_spark_transfer(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -77,6 +78,8 @@ async fn test_01_spark_transfer(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -293,7 +296,10 @@ async fn test_03_lightning_invoice_payment(
                 amount_sats: invoice_amount_sats,
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -337,6 +343,8 @@ async fn test_03_lightning_invoice_payment(
                 completion_timeout_secs: Some(10),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -559,7 +567,10 @@ async fn test_05_lightning_invoice_prefer_spark_fee_path(
                 amount_sats: Some(invoice_amount_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -608,6 +619,8 @@ async fn test_05_lightning_invoice_prefer_spark_fee_path(
                 completion_timeout_secs: Some(10),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -657,7 +670,10 @@ async fn test_06_lightning_timeout_and_wait(
                 amount_sats: None,
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -685,6 +701,8 @@ async fn test_06_lightning_timeout_and_wait(
                 completion_timeout_secs: Some(1),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     info!("Immediate return status: {:?}", send_resp.payment.status);
@@ -746,6 +764,7 @@ async fn test_07_spark_invoice(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -767,6 +786,7 @@ async fn test_07_spark_invoice(
                 description: Some("Test invoice".to_string()),
                 sender_public_key: Some(alice_identity_public_key),
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -795,6 +815,8 @@ async fn test_07_spark_invoice(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -905,7 +927,10 @@ async fn test_08_lightning_invoice_expiry_secs(
                 amount_sats: Some(invoice_amount_sats),
                 expiry_secs: Some(custom_expiry_secs),
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -970,6 +995,8 @@ async fn test_08_lightning_invoice_expiry_secs(
                 completion_timeout_secs: Some(10),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -1062,7 +1089,10 @@ async fn test_09_bolt11_send_all_with_fee_overpayment(
                 amount_sats: None,
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1171,6 +1201,7 @@ async fn test_09_bolt11_send_all_with_fee_overpayment(
             .sdk
             .receive_payment(ReceivePaymentRequest {
                 payment_method: ReceivePaymentMethod::SparkAddress,
+                order_ref: None,
             })
             .await?
             .payment_request;
@@ -1195,6 +1226,8 @@ async fn test_09_bolt11_send_all_with_fee_overpayment(
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await?;
 
@@ -1265,6 +1298,8 @@ async fn test_09_bolt11_send_all_with_fee_overpayment(
                 completion_timeout_secs: Some(30),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -1356,7 +1391,10 @@ async fn test_10_lightning_completion_timeout_resolves_to_completed(
                 amount_sats: Some(invoice_amount_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1382,6 +1420,8 @@ async fn test_10_lightning_completion_timeout_resolves_to_completed(
                 completion_timeout_secs: Some(completion_timeout_secs),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let elapsed = start.elapsed();
@@ -147,6 +147,7 @@ async fn test_client_signing_send_with_denomination_swap() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -287,6 +288,7 @@ async fn test_client_signing_publish_twice_is_idempotent() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -420,6 +422,7 @@ async fn test_client_signing_token_send() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -534,6 +537,7 @@ async fn test_client_signing_token_publish_twice_is_idempotent() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -658,6 +662,7 @@ async fn test_client_signing_token_send_with_consolidation() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -800,6 +805,7 @@ async fn test_client_signing_token_send_to_spark_invoice() -> Result<()> {
                 description: Some("client-signing token invoice".to_string()),
                 sender_public_key: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -898,6 +904,7 @@ async fn test_client_signing_coop_exit() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1043,7 +1050,10 @@ async fn test_client_signing_lightning_send() -> Result<()> {
                 amount_sats: Some(invoice_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1108,6 +1118,7 @@ async fn test_client_signing_spark_invoice_send() -> Result<()> {
                 description: Some("client-signing spark invoice".to_string()),
                 sender_public_key: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1168,7 +1179,10 @@ async fn test_client_signing_lightning_send_fees_included() -> Result<()> {
                 amount_sats: None,
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
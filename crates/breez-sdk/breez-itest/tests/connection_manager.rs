@@ -85,6 +85,7 @@ async fn test_shared_connection_manager_spark_transfer(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -108,6 +109,8 @@ async fn test_shared_connection_manager_spark_transfer(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert!(matches!(
@@ -68,6 +68,7 @@ async fn test_onchain_withdraw_to_static_address(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -94,6 +95,8 @@ async fn test_onchain_withdraw_to_static_address(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -164,6 +167,7 @@ async fn test_deposit_fee_manual_claim(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -269,6 +273,7 @@ async fn test_send_all_to_bitcoin_address(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -327,6 +332,8 @@ async fn test_send_all_to_bitcoin_address(
                 confirmation_speed: OnchainConfirmationSpeed::Fast,
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -360,6 +367,7 @@ async fn test_deposit_fee_refund(#[future] bob_no_fee_sdk: Result<SdkInstance>)
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -468,6 +476,7 @@ async fn test_deposit_low_amount_refund_fee_rate(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -496,6 +505,8 @@ async fn test_deposit_low_amount_refund_fee_rate(
                 confirmation_speed: OnchainConfirmationSpeed::Fast,
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     info!(
@@ -560,6 +571,7 @@ async fn test_deposits_to_multiple_addresses(
                 payment_method: ReceivePaymentMethod::BitcoinAddress {
                     new_address: Some(true),
                 },
+                order_ref: None,
             })
             .await?
             .payment_request;
@@ -582,6 +594,7 @@ async fn test_deposits_to_multiple_addresses(
             payment_method: ReceivePaymentMethod::BitcoinAddress {
                 new_address: Some(false),
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -589,6 +602,7 @@ async fn test_deposits_to_multiple_addresses(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use breez_sdk_itest::*;
+use breez_sdk_spark::*;
+use rstest::*;
+use tracing::info;
+
+/// Test 1: Fund the wallet through the dev tools faucet and claim the deposit
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn test_01_dev_tools_faucet_fund(#[future] alice_sdk: Result<SdkInstance>) -> Result<()> {
+    info!("=== Starting test_01_dev_tools_faucet_fund ===");
+
+    let mut alice = alice_sdk.await?;
+    let dev_tools = alice.sdk.get_dev_tools(dev_tools_config())?;
+
+    let funded = dev_tools
+        .faucet_fund(FaucetFundRequest {
+            amount_sats: 10_000,
+        })
+        .await?;
+    info!("Faucet funded {} in txid {}", funded.address, funded.txid);
+
+    wait_for_claimed_event(&mut alice.events, 180).await?;
+    let balance = wait_for_balance(&alice.sdk, Some(1), None, 20).await?;
+    assert!(balance > 0, "Deposit should have been claimed");
+
+    info!("=== Test test_01_dev_tools_faucet_fund PASSED ===");
+    Ok(())
+}
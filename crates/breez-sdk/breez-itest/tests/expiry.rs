@@ -0,0 +1,115 @@
+//! Expiry-focused integration tests against a local Spark operator pool and a
+//! regtest bitcoind.
+//!
+//! Instead of waiting out real expiries, the tests fast-forward with
+//! `LocalSdk::time_travel`, which advances the SDK clock and mines blocks in bulk.
+//!
+//! Gated behind `local-itest`: each test stands up its own operator cluster.
+#![cfg(feature = "local-itest")]
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use bitcoin::{Address, Amount};
+use breez_sdk_itest::{LocalBitcoindChainService, SignerBackend, build_local_sdk};
+use breez_sdk_spark::{
+    BitcoinChainService, PaymentRequest, PrepareSendPaymentRequest, ReceivePaymentMethod,
+    ReceivePaymentRequest, SdkError,
+};
+use spark_itest::fixtures::setup::TestFixtures;
+use tracing::info;
+
+/// A Spark invoice that is still valid fails to prepare once the payer's clock
+/// moves past its expiry.
+#[test_log::test(tokio::test)]
+async fn test_spark_invoice_expires_after_time_travel() -> Result<()> {
+    let fixtures = Arc::new(TestFixtures::new().await?);
+    let receiver = build_local_sdk(Arc::clone(&fixtures), SignerBackend::Seed).await?;
+    let payer = build_local_sdk(fixtures, SignerBackend::Seed).await?;
+
+    let expiry_time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+    let invoice = receiver
+        .sdk
+        .receive_payment(ReceivePaymentRequest {
+            payment_method: ReceivePaymentMethod::SparkInvoice {
+                amount: Some(1_000),
+                token_identifier: None,
+                expiry_time: Some(expiry_time),
+                description: Some("expiry test".to_string()),
+                sender_public_key: None,
+            },
+            order_ref: None,
+        })
+        .await?
+        .payment_request;
+    info!("Created Spark invoice expiring at {expiry_time}: {invoice}");
+
+    let prepare_request = || PrepareSendPaymentRequest {
+        payment_request: PaymentRequest::Input {
+            input: invoice.clone(),
+        },
+        amount: None,
+        token_identifier: None,
+        conversion_options: None,
+        fee_policy: None,
+    };
+    payer.sdk.prepare_send_payment(prepare_request()).await?;
+
+    payer.time_travel(Duration::from_secs(2 * 3600)).await?;
+
+    let err = payer
+        .sdk
+        .prepare_send_payment(prepare_request())
+        .await
+        .expect_err("prepare should fail for an expired invoice");
+    assert!(
+        matches!(err, SdkError::InvalidInput(ref msg) if msg.contains("expired")),
+        "unexpected error: {err:?}"
+    );
+
+    Ok(())
+}
+
+/// Time travel mines the blocks a day would produce in one go, confirming
+/// pending transactions the SDK watches.
+#[test_log::test(tokio::test)]
+async fn test_time_travel_mines_blocks_in_bulk() -> Result<()> {
+    let fixtures = Arc::new(TestFixtures::new().await?);
+    let sdk = build_local_sdk(Arc::clone(&fixtures), SignerBackend::Seed).await?;
+    let chain_service = LocalBitcoindChainService::new(&fixtures.bitcoind);
+
+    let deposit_address = sdk
+        .sdk
+        .receive_payment(ReceivePaymentRequest {
+            payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
+        })
+        .await?
+        .payment_request;
+    let address =
+        Address::from_str(&deposit_address)?.require_network(bitcoin::Network::Regtest)?;
+    let txid = fixtures
+        .bitcoind
+        .fund_address(&address, Amount::from_sat(50_000))
+        .await?;
+
+    let status = chain_service
+        .get_transaction_status(txid.to_string())
+        .await?;
+    assert!(!status.confirmed, "deposit should start unconfirmed");
+
+    let start_height: u64 = fixtures.bitcoind.rpc("getblockcount", &[]).await?;
+    let height = sdk.time_travel(Duration::from_secs(24 * 3600)).await?;
+    assert_eq!(height, start_height + 144);
+
+    let status = chain_service
+        .get_transaction_status(txid.to_string())
+        .await?;
+    assert!(status.confirmed, "deposit should confirm after time travel");
+    let confirmations = height + 1 - u64::from(status.block_height.unwrap_or_default());
+    assert!(confirmations >= 144, "got {confirmations} confirmations");
+
+    Ok(())
+}
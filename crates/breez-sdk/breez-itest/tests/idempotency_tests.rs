@@ -46,6 +46,7 @@ async fn test_01_spark_idempotency_key(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -77,6 +78,8 @@ async fn test_01_spark_idempotency_key(
             prepare_response: prepare.clone(),
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -93,6 +96,8 @@ async fn test_01_spark_idempotency_key(
             prepare_response: prepare.clone(),
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -120,6 +125,8 @@ async fn test_01_spark_idempotency_key(
             prepare_response: prepare,
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -215,7 +222,10 @@ async fn test_02_lightning_idempotency_key(
                 amount_sats: Some(5),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -247,6 +257,8 @@ async fn test_02_lightning_idempotency_key(
             prepare_response: prepare.clone(),
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -263,6 +275,8 @@ async fn test_02_lightning_idempotency_key(
             prepare_response: prepare.clone(),
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -290,6 +304,8 @@ async fn test_02_lightning_idempotency_key(
             prepare_response: prepare,
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -370,6 +386,7 @@ async fn test_03_bitcoin_idempotency_key(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -399,6 +416,8 @@ async fn test_03_bitcoin_idempotency_key(
             prepare_response: prepare.clone(),
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -415,6 +434,8 @@ async fn test_03_bitcoin_idempotency_key(
             prepare_response: prepare.clone(),
             options: None,
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -439,6 +460,8 @@ async fn test_03_bitcoin_idempotency_key(
             prepare_response: prepare,
             options: None,
             idempotency_key: Some(idempotency_key),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -516,6 +539,7 @@ async fn test_04_spark_htlc_idempotency_key(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -554,6 +578,8 @@ async fn test_04_spark_htlc_idempotency_key(
                 }),
             }),
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -575,6 +601,8 @@ async fn test_04_spark_htlc_idempotency_key(
                 }),
             }),
             idempotency_key: Some(idempotency_key.clone()),
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use breez_sdk_itest::*;
+use breez_sdk_spark::*;
+use rstest::*;
+use tracing::info;
+
+/// Fixture: pre-funded lightning node paying the invoices of the tests
+#[fixture]
+async fn lightning_node() -> Result<LightningNodeFixture> {
+    LightningNodeFixture::new().await
+}
+
+/// Test 1: Receive over lightning from the faucet node
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn test_01_lightning_faucet_receive(
+    #[future] lightning_node: Result<LightningNodeFixture>,
+    #[future] alice_sdk: Result<SdkInstance>,
+) -> Result<()> {
+    info!("=== Starting test_01_lightning_faucet_receive ===");
+
+    let lightning_node = lightning_node.await?;
+    let mut alice = alice_sdk.await?;
+    let faucet = lightning_node.faucet()?;
+
+    let payment = receive_lightning_and_fund(&mut alice, &faucet, 10_000).await?;
+    assert_eq!(payment.amount, 10_000);
+    assert!(matches!(payment.method, PaymentMethod::Lightning));
+
+    let balance = wait_for_balance(&alice.sdk, Some(10_000), None, 20).await?;
+    assert!(balance >= 10_000, "Lightning payment should be received");
+
+    info!("=== Test test_01_lightning_faucet_receive PASSED ===");
+    Ok(())
+}
+
+/// Test 2: Receive twice over lightning, checking each payment is received once
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn test_02_lightning_faucet_receive_twice(
+    #[future] lightning_node: Result<LightningNodeFixture>,
+    #[future] alice_sdk: Result<SdkInstance>,
+) -> Result<()> {
+    info!("=== Starting test_02_lightning_faucet_receive_twice ===");
+
+    let lightning_node = lightning_node.await?;
+    let mut alice = alice_sdk.await?;
+    let faucet = lightning_node.faucet()?;
+
+    let first = receive_lightning_and_fund(&mut alice, &faucet, 5_000).await?;
+    let second = receive_lightning_and_fund(&mut alice, &faucet, 7_000).await?;
+    assert_ne!(first.id, second.id);
+
+    let balance = wait_for_balance(&alice.sdk, Some(12_000), None, 20).await?;
+    assert_eq!(balance, 12_000);
+
+    info!("=== Test test_02_lightning_faucet_receive_twice PASSED ===");
+    Ok(())
+}
@@ -30,7 +30,10 @@ async fn test_01_lightning_hodl_success(
                 amount_sats: Some(10_000),
                 expiry_secs: None,
                 payment_hash: Some(payment_hash.clone()),
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -62,6 +65,8 @@ async fn test_01_lightning_hodl_success(
                 completion_timeout_secs: Some(1),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
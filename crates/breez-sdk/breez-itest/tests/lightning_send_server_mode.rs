@@ -66,7 +66,10 @@ async fn test_send_bolt11_invoice_server_mode(
                 amount_sats: Some(invoice_amount_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -94,6 +97,8 @@ async fn test_send_bolt11_invoice_server_mode(
                 completion_timeout_secs: Some(completion_timeout_secs),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let elapsed = start.elapsed();
@@ -821,6 +821,7 @@ async fn test_08_lnurl_send_all_with_fee_overpayment(
             .sdk
             .receive_payment(ReceivePaymentRequest {
                 payment_method: ReceivePaymentMethod::SparkAddress,
+                order_ref: None,
             })
             .await?
             .payment_request;
@@ -845,6 +846,8 @@ async fn test_08_lnurl_send_all_with_fee_overpayment(
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await?;
 
@@ -1730,3 +1733,133 @@ async fn test_16_client_signing_lnurl_pay_publish_twice() -> Result<()> {
     info!("=== Test test_16_client_signing_lnurl_pay_publish_twice PASSED ===");
     Ok(())
 }
+
+/// Test LNURL-withdraw: Bob withdraws from a mock service funded by Alice
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn test_17_lnurl_withdraw_flow(#[future] alice_sdk: Result<SdkInstance>) -> Result<()> {
+    info!("=== Starting test_17_lnurl_withdraw_flow ===");
+
+    let mut alice = alice_sdk.await?;
+    let mut bob = setup_bob(false).await?;
+    let withdraw_amount_sats = 3_000;
+
+    receive_and_fund(&mut alice, 50_000, false).await?;
+    info!("Alice funded with sats");
+
+    let service = LnurlWithdrawService::start(alice.sdk.clone(), 1_000, 10_000_000).await?;
+
+    let InputType::LnurlWithdraw(withdraw_request) = bob.sdk.parse(&service.lnurl()?).await? else {
+        anyhow::bail!("Expected LNURL-withdraw");
+    };
+    assert_eq!(withdraw_request.min_withdrawable, 1_000);
+    assert_eq!(withdraw_request.max_withdrawable, 10_000_000);
+
+    let response = bob
+        .sdk
+        .lnurl_withdraw(LnurlWithdrawRequest {
+            amount_sats: withdraw_amount_sats,
+            withdraw_request,
+            completion_timeout_secs: None,
+        })
+        .await?;
+    info!(
+        "Bob requested withdraw with invoice {}",
+        response.payment_request
+    );
+
+    let bob_payment =
+        wait_for_payment_succeeded_event(&mut bob.events, PaymentType::Receive, 60).await?;
+    assert_eq!(bob_payment.amount, withdraw_amount_sats as u128);
+    assert_eq!(
+        service.paid_invoices().await,
+        vec![response.payment_request]
+    );
+
+    let Some(PaymentDetails::Lightning {
+        lnurl_withdraw_info,
+        ..
+    }) = bob
+        .sdk
+        .get_payment(GetPaymentRequest {
+            payment_id: bob_payment.id,
+        })
+        .await?
+        .payment
+        .details
+    else {
+        anyhow::bail!("Expected Lightning payment");
+    };
+    assert_eq!(
+        lnurl_withdraw_info.map(|info| info.withdraw_url),
+        Some(service.withdraw_url())
+    );
+
+    info!("=== Test test_17_lnurl_withdraw_flow PASSED ===");
+    Ok(())
+}
+
+/// Test NIP-57 zaps: Alice zaps Bob's lightning address and the LNURL server
+/// publishes a zap receipt once Bob received the payment
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn test_18_zap_receipt(#[future] alice_sdk: Result<SdkInstance>) -> Result<()> {
+    info!("=== Starting test_18_zap_receipt ===");
+
+    let mut alice = alice_sdk.await?;
+    let mut bob = setup_bob(false).await?;
+    let zap_amount_msat = 2_000_000;
+
+    let username = "bobzap";
+    bob.sdk
+        .register_lightning_address(RegisterLightningAddressRequest {
+            username: username.to_string(),
+            description: None,
+        })
+        .await?;
+
+    receive_and_fund(&mut alice, 50_000, false).await?;
+    info!("Alice funded with sats");
+
+    let relay = MockNostrRelay::start().await?;
+    let lnurl = bob
+        .lnurl_fixture
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Bob has no LNURL fixture"))?;
+    let zap = lnurl
+        .request_zap_invoice(username, zap_amount_msat, &relay)
+        .await?;
+
+    let prepare_response = alice
+        .sdk
+        .prepare_send_payment(PrepareSendPaymentRequest {
+            payment_request: PaymentRequest::Input {
+                input: zap.invoice.clone(),
+            },
+            amount: None,
+            token_identifier: None,
+            conversion_options: None,
+            fee_policy: None,
+        })
+        .await?;
+    alice
+        .sdk
+        .send_payment(SendPaymentRequest {
+            prepare_response,
+            options: None,
+            idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
+        })
+        .await?;
+
+    let bob_payment =
+        wait_for_payment_succeeded_event(&mut bob.events, PaymentType::Receive, 60).await?;
+    assert_eq!(bob_payment.amount, (zap_amount_msat / 1000) as u128);
+
+    let receipt = relay.wait_for_event(nostr::Kind::ZapReceipt, 60).await?;
+    zap.assert_receipt(&receipt)?;
+
+    info!("=== Test test_18_zap_receipt PASSED ===");
+    Ok(())
+}
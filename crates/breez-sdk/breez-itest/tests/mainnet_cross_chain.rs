@@ -389,6 +389,8 @@ async fn run_cross_chain_evm_send(
             prepare_response: prepared,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let payment_id = resp.payment.id.clone();
@@ -83,6 +83,7 @@ async fn test_server_mode_bitcoin_to_token() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -133,6 +134,8 @@ async fn test_server_mode_bitcoin_to_token() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     info!(
@@ -234,6 +237,7 @@ async fn test_server_mode_token_to_bitcoin() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -259,7 +263,10 @@ async fn test_server_mode_token_to_bitcoin() -> Result<()> {
                 amount_sats: Some(invoice_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -298,6 +305,8 @@ async fn test_server_mode_token_to_bitcoin() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     info!(
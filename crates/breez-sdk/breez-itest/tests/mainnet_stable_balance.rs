@@ -116,6 +116,7 @@ async fn test_stable_balance_auto_conversion() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -152,6 +153,8 @@ async fn test_stable_balance_auto_conversion() -> Result<()> {
             prepare_response: prepare_small,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -220,6 +223,8 @@ async fn test_stable_balance_auto_conversion() -> Result<()> {
             prepare_response: prepare_large,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -291,7 +296,10 @@ async fn test_stable_balance_auto_conversion() -> Result<()> {
                 amount_sats: Some(invoice_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -334,6 +342,8 @@ async fn test_stable_balance_auto_conversion() -> Result<()> {
             prepare_response: prepare_spend,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -451,6 +461,7 @@ async fn test_stable_balance_per_receive_conversion() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -486,6 +497,8 @@ async fn test_stable_balance_per_receive_conversion() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     wait_for_payment_succeeded_event(&mut alice.events, PaymentType::Send, 60).await?;
@@ -590,6 +603,7 @@ async fn test_stable_balance_send_lightning_address() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
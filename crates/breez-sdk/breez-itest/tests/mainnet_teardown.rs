@@ -108,6 +108,7 @@ async fn test_mainnet_teardown_drain_bob_to_alice() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -156,6 +157,8 @@ async fn test_mainnet_teardown_drain_bob_to_alice() -> Result<()> {
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await?;
         let details = resp
@@ -206,6 +209,8 @@ async fn test_mainnet_teardown_drain_bob_to_alice() -> Result<()> {
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await?;
         wait_for_payment_succeeded_event(&mut alice.events, PaymentType::Receive, 60).await?;
@@ -71,6 +71,7 @@ async fn test_token_conversion_success() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -128,6 +129,8 @@ async fn test_token_conversion_success() -> Result<()> {
             prepare_response: prepare_btc_to_token,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -263,7 +266,10 @@ async fn test_token_conversion_success() -> Result<()> {
                 amount_sats: Some(invoice_sats),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -304,6 +310,8 @@ async fn test_token_conversion_success() -> Result<()> {
             prepare_response: prepare_token_to_btc,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -453,6 +461,7 @@ async fn test_token_conversion_failure() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -574,6 +583,8 @@ async fn test_token_conversion_failure() -> Result<()> {
             prepare_response: prepare_oversize,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await;
     info!("Insufficient-funds send rejected: {}", send_result.is_err());
@@ -639,6 +650,7 @@ async fn test_token_conversion_spark_invoice_success() -> Result<()> {
                 description: Some("token conversion via spark invoice test".to_string()),
                 sender_public_key: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -698,6 +710,8 @@ async fn test_token_conversion_spark_invoice_success() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     info!(
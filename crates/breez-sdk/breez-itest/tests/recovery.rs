@@ -247,6 +247,7 @@ async fn test_setup_recovery_wallet() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -270,6 +271,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -281,6 +284,7 @@ async fn test_setup_recovery_wallet() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -303,6 +307,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -336,6 +342,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 }),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -377,6 +385,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 }),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -402,7 +412,10 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 amount_sats: Some(1_000),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -427,6 +440,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 completion_timeout_secs: Some(30),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -442,7 +457,10 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 amount_sats: Some(800),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -468,6 +486,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 completion_timeout_secs: Some(30),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -497,6 +517,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -523,6 +545,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -555,6 +579,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await?;
 
@@ -581,6 +607,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
                 prepare_response: prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await?;
 
@@ -601,6 +629,7 @@ async fn test_setup_recovery_wallet() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -624,6 +653,8 @@ async fn test_setup_recovery_wallet() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
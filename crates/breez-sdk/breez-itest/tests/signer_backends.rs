@@ -70,6 +70,7 @@ async fn info_and_address(#[case] backend: SignerBackend) -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -112,6 +113,7 @@ async fn send_receive_spark(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -132,6 +134,8 @@ async fn send_receive_spark(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert_eq!(send.payment.payment_type, PaymentType::Send);
@@ -163,7 +167,10 @@ async fn lightning_receive(#[case] backend: SignerBackend) -> Result<()> {
                 amount_sats: Some(100),
                 expiry_secs: None,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -187,6 +194,8 @@ async fn lightning_receive(#[case] backend: SignerBackend) -> Result<()> {
                 completion_timeout_secs: Some(10),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -214,6 +223,7 @@ async fn static_deposit_refund(#[case] backend: SignerBackend) -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -328,6 +338,7 @@ async fn turnkey_no_export_gates_onchain_receive() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -339,6 +350,7 @@ async fn turnkey_no_export_gates_onchain_receive() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address: None },
+            order_ref: None,
         })
         .await
         .expect_err("on-chain receive must fail when the static-deposit export is denied");
@@ -15,6 +15,7 @@ async fn send_htlc_alice_to_bob(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -48,6 +49,8 @@ async fn send_htlc_alice_to_bob(
                 }),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -340,6 +343,7 @@ async fn test_03_reconcile_stale_pending_payment(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -368,6 +372,8 @@ async fn test_03_reconcile_stale_pending_payment(
                 }),
             }),
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -380,6 +386,7 @@ async fn test_03_reconcile_stale_pending_payment(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -403,6 +410,8 @@ async fn test_03_reconcile_stale_pending_payment(
             prepare_response: prepare2,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -78,6 +78,7 @@ async fn test_shared_ssp_connection_manager_spark_transfer() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -101,6 +102,8 @@ async fn test_shared_ssp_connection_manager_spark_transfer() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     assert!(matches!(
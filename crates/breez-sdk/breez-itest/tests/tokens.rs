@@ -87,6 +87,7 @@ async fn test_01_token_transfer(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -113,6 +114,8 @@ async fn test_01_token_transfer(
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -289,6 +292,7 @@ async fn test_02_token_invoice(
                 description: Some("test invoice".to_string()),
                 sender_public_key: None,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -327,6 +331,8 @@ async fn test_02_token_invoice(
             prepare_response,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -578,6 +584,7 @@ async fn test_04_token_freeze_unfreeze(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -599,6 +606,8 @@ async fn test_04_token_freeze_unfreeze(
             prepare_response: prepare_send,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -623,6 +632,7 @@ async fn test_04_token_freeze_unfreeze(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -657,6 +667,7 @@ async fn test_04_token_freeze_unfreeze(
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -682,6 +693,8 @@ async fn test_04_token_freeze_unfreeze(
                 prepare_response: bob_prepare,
                 options: None,
                 idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
             })
             .await;
 
@@ -742,6 +755,8 @@ async fn test_04_token_freeze_unfreeze(
             prepare_response: bob_prepare_after_unfreeze,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -791,6 +806,7 @@ async fn test_05_invoice_expiry(
                 description: Some("expiring invoice".to_string()),
                 sender_public_key: None,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -846,6 +862,8 @@ async fn test_05_invoice_expiry(
             prepare_response: alice_prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await;
 
@@ -1008,6 +1026,7 @@ async fn test_07_token_payment_realtime_event() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1033,6 +1052,8 @@ async fn test_07_token_payment_realtime_event() -> Result<()> {
             prepare_response: prepare,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -1140,6 +1161,7 @@ async fn test_07_token_payment_realtime_event() -> Result<()> {
         .sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?
         .payment_request;
@@ -1162,6 +1184,8 @@ async fn test_07_token_payment_realtime_event() -> Result<()> {
             prepare_response: prepare2,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
 
@@ -1,7 +1,8 @@
 use breez_sdk_spark::signer::single_key_cpfp_signer;
 use breez_sdk_spark::{
     BreezSdk, ConfirmationStatus, CpfpFundingKind, CpfpInput, ExitLeafSelection,
-    PrepareUnilateralExitRequest, UnilateralExitRequest, UnilateralExitResponse,
+    ExportEmergencyExitPackageRequest, PrepareUnilateralExitRequest, UnilateralExitRequest,
+    UnilateralExitResponse,
 };
 use clap::{Subcommand, ValueEnum};
 use rustyline::{Editor, history::DefaultHistory};
@@ -44,6 +45,22 @@ pub enum AdvancedCommand {
         #[arg(long = "leaf")]
         leaf_ids: Vec<String>,
     },
+    /// Write an emergency exit package to a file: signed exit transactions for
+    /// every profitable leaf, funded by one set aside UTXO. Prompts for the
+    /// funding UTXO and signing key.
+    EmergencyExitPackage {
+        /// Fee rate in sat/vByte the package is signed at.
+        #[arg(long)]
+        fee_rate: u64,
+        /// Funding UTXO kind.
+        #[arg(long, value_enum, default_value_t = FundingKindArg::P2tr)]
+        funding_kind: FundingKindArg,
+        /// Destination address for the swept funds.
+        #[arg(long)]
+        destination: String,
+        /// Path of the file to write.
+        file: String,
+    },
 }
 
 pub async fn handle_command(
@@ -99,6 +116,32 @@ pub async fn handle_command(
             print_exit_transactions(&response);
             Ok(true)
         }
+        AdvancedCommand::EmergencyExitPackage {
+            fee_rate,
+            funding_kind,
+            destination,
+            file,
+        } => {
+            let utxo_line = rl.readline("Funding UTXO as txid:vout:value:pubkey: ")?;
+            let funding_input = parse_cpfp_input(utxo_line.trim(), funding_kind)?;
+            let key_line = rl.readline("Hex secret key for the funding UTXO: ")?;
+            let signer = single_key_cpfp_signer(hex::decode(key_line.trim())?)?;
+
+            let package = sdk
+                .export_emergency_exit_package(
+                    ExportEmergencyExitPackageRequest {
+                        funding_input,
+                        fee_rate_sat_per_vbyte: fee_rate,
+                        destination,
+                    },
+                    signer,
+                )
+                .await?;
+            std::fs::write(&file, serde_json::to_string_pretty(&package)?)?;
+            print_exit_transactions(&package.exit);
+            println!("Wrote emergency exit package to {file}");
+            Ok(true)
+        }
     }
 }
 
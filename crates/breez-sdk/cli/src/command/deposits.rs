@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use breez_sdk_spark::{
+    BreezSdk, ClaimDepositRequest, DepositInfo, Fee, ListUnclaimedDepositsRequest, MaxFee,
+    RefundDepositRequest, SweepPrivateKeyRequest,
+};
+use clap::Subcommand;
+use serde_json::json;
+
+use crate::command::print_value;
+
+/// A deposit output in the form `<txid>:<vout>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositOutpoint {
+    pub txid: String,
+    pub vout: u32,
+}
+
+impl FromStr for DepositOutpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (txid, vout) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected <txid>:<vout>, got '{s}'"))?;
+        if txid.is_empty() {
+            return Err(format!("missing txid in '{s}'"));
+        }
+        let vout = vout
+            .parse()
+            .map_err(|_| format!("invalid vout '{vout}' in '{s}'"))?;
+        Ok(DepositOutpoint {
+            txid: txid.to_string(),
+            vout,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum DepositCommand {
+    /// List unclaimed deposits
+    List,
+    /// Claim a deposit
+    Claim {
+        /// The deposit to claim, as <txid>:<vout>
+        outpoint: DepositOutpoint,
+
+        /// The max fee in sats to claim the deposit
+        #[arg(long, conflicts_with_all = ["sat_per_vbyte", "recommended_fee_leeway"])]
+        max_fee: Option<u64>,
+
+        /// The max fee per vbyte to claim the deposit
+        #[arg(long, conflicts_with = "recommended_fee_leeway")]
+        sat_per_vbyte: Option<u64>,
+
+        /// If provided, the max fee per vbyte will be set to the fastest recommended fee at time of claim, plus the leeway.
+        #[arg(long)]
+        recommended_fee_leeway: Option<u64>,
+
+        /// Only preview the claim fee, don't claim the deposit
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Refund a deposit to an onchain address
+    Refund {
+        /// The deposit to refund, as <txid>:<vout>
+        outpoint: DepositOutpoint,
+
+        /// Destination address
+        #[arg(long)]
+        address: String,
+
+        /// The fee rate in sats per vbyte
+        #[arg(long, required_unless_present = "fee_sat", conflicts_with = "fee_sat")]
+        fee_rate: Option<u64>,
+
+        /// The absolute fee in sats
+        #[arg(long)]
+        fee_sat: Option<u64>,
+
+        /// Only preview the refund fee, don't broadcast the refund
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Sweep the funds of a private key, e.g. a paper wallet, into a deposit
+    Sweep {
+        /// The private key in WIF format
+        wif: String,
+
+        /// The fee rate in sats per vbyte
+        #[arg(long)]
+        fee_rate: u64,
+    },
+}
+
+pub async fn handle_command(
+    sdk: &BreezSdk,
+    command: DepositCommand,
+) -> Result<bool, anyhow::Error> {
+    match command {
+        DepositCommand::List => {
+            let value = sdk
+                .list_unclaimed_deposits(ListUnclaimedDepositsRequest {})
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        DepositCommand::Claim {
+            outpoint,
+            max_fee,
+            sat_per_vbyte,
+            recommended_fee_leeway,
+            dry_run,
+        } => {
+            let max_fee = match (max_fee, sat_per_vbyte, recommended_fee_leeway) {
+                (Some(amount), _, _) => Some(MaxFee::Fixed { amount }),
+                (None, Some(sat_per_vbyte), _) => Some(MaxFee::Rate { sat_per_vbyte }),
+                (None, None, Some(leeway_sat_per_vbyte)) => Some(MaxFee::NetworkRecommended {
+                    leeway_sat_per_vbyte,
+                }),
+                (None, None, None) => None,
+            };
+            let request = ClaimDepositRequest {
+                txid: outpoint.txid.clone(),
+                vout: outpoint.vout,
+                max_fee,
+            };
+            if dry_run {
+                let deposit = find_deposit(sdk, &outpoint).await?;
+                let estimate = sdk.estimate_claim_deposit_fee(request).await?;
+                print_value(&json!({
+                    "txid": deposit.txid,
+                    "vout": deposit.vout,
+                    "amount_sats": deposit.amount_sats,
+                    "is_mature": deposit.is_mature,
+                    "fee_rate_sat_per_vbyte": estimate.fee_rate_sat_per_vbyte,
+                    "estimated_fee_sats": estimate.fee_sats,
+                    "estimated_received_sats": deposit.amount_sats.saturating_sub(estimate.fee_sats),
+                    "max_fee_sats": estimate.max_fee_sats,
+                    "within_max_fee": estimate.within_max_fee,
+                    "last_claim_error": deposit.claim_error.map(|e| e.to_string()),
+                }))?;
+                return Ok(true);
+            }
+            let value = sdk.claim_deposit(request).await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        DepositCommand::Refund {
+            outpoint,
+            address,
+            fee_rate,
+            fee_sat,
+            dry_run,
+        } => {
+            let fee = match (fee_sat, fee_rate) {
+                (Some(amount), _) => Fee::Fixed { amount },
+                (None, Some(sat_per_vbyte)) => Fee::Rate { sat_per_vbyte },
+                (None, None) => {
+                    return Err(anyhow::anyhow!("Must specify either fee_rate or fee_sat"));
+                }
+            };
+            let request = RefundDepositRequest {
+                txid: outpoint.txid.clone(),
+                vout: outpoint.vout,
+                destination_address: address,
+                fee,
+            };
+            if dry_run {
+                let deposit = find_deposit(sdk, &outpoint).await?;
+                let destination_address = request.destination_address.clone();
+                let estimate = sdk.estimate_refund_deposit_fee(request).await?;
+                let recommended_fees = sdk.recommended_fees().await?;
+                print_value(&json!({
+                    "txid": deposit.txid,
+                    "vout": deposit.vout,
+                    "amount_sats": deposit.amount_sats,
+                    "destination_address": destination_address,
+                    "estimated_fee_sats": estimate.fee_sats,
+                    "estimated_refunded_sats": estimate.refund_amount_sats,
+                    "recommended_fees": recommended_fees,
+                }))?;
+                return Ok(true);
+            }
+            let value = sdk.refund_deposit(request).await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        DepositCommand::Sweep { wif, fee_rate } => {
+            let value = sdk
+                .sweep_private_key(SweepPrivateKeyRequest {
+                    wif,
+                    fee_rate_sat_per_vbyte: fee_rate,
+                })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+    }
+}
+
+async fn find_deposit(
+    sdk: &BreezSdk,
+    outpoint: &DepositOutpoint,
+) -> Result<DepositInfo, anyhow::Error> {
+    sdk.list_unclaimed_deposits(ListUnclaimedDepositsRequest {})
+        .await?
+        .deposits
+        .into_iter()
+        .find(|d| d.txid == outpoint.txid && d.vout == outpoint.vout)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No unclaimed deposit found for {}:{}",
+                outpoint.txid,
+                outpoint.vout
+            )
+        })
+}
@@ -3,15 +3,16 @@
 //! mirror, so a grammar change here is a deliberate, reviewed act.
 
 use breez_sdk_spark::{
-    AssetFilter, PaymentStatus, PaymentType, SparkHtlcStatus, TokenTransactionType,
+    AssetFilter, DisputeStatus, PaymentStatus, PaymentType, SparkHtlcStatus, TokenTransactionType,
 };
 use clap::Parser;
 
 use super::contacts::ContactCommand;
+use super::deposits::{DepositCommand, DepositOutpoint};
 use super::issuer::IssuerCommand;
 use super::stable_balance::StableBalanceCommand;
 use super::webhooks::{WebhookCommand, WebhookEventTypeArg};
-use super::{Command, ReceivePaymentMethodArg};
+use super::{Command, ExportFormatArg, PaymentImportSourceArg, ReceivePaymentMethodArg};
 
 fn parse(line: &str) -> Result<Command, clap::Error> {
     let mut args = vec!["breez-cli".to_string()];
@@ -61,6 +62,44 @@ fn get_payment() {
     parse_err("get-payment");
 }
 
+#[test]
+fn set_payment_dispute() {
+    let Command::SetPaymentDispute {
+        payment_id,
+        status,
+        note,
+    } = parse_ok("set-payment-dispute abc123 disputed --note refund_requested")
+    else {
+        panic!("expected SetPaymentDispute");
+    };
+    assert_eq!(payment_id, "abc123");
+    assert_eq!(status, DisputeStatus::Disputed);
+    assert_eq!(note.as_deref(), Some("refund_requested"));
+    parse_err("set-payment-dispute abc123 unknown");
+}
+
+#[test]
+fn payment_acknowledgements() {
+    let Command::ListUnacknowledgedPayments { consumer_id, limit } =
+        parse_ok("list-unacknowledged-payments fulfillment --limit 10")
+    else {
+        panic!("expected ListUnacknowledgedPayments");
+    };
+    assert_eq!(consumer_id, "fulfillment");
+    assert_eq!(limit, Some(10));
+
+    let Command::AcknowledgePayment {
+        consumer_id,
+        payment_id,
+    } = parse_ok("acknowledge-payment fulfillment abc123")
+    else {
+        panic!("expected AcknowledgePayment");
+    };
+    assert_eq!(consumer_id, "fulfillment");
+    assert_eq!(payment_id, "abc123");
+    parse_err("acknowledge-payment fulfillment");
+}
+
 #[test]
 fn sync() {
     assert!(matches!(parse_ok("sync"), Command::Sync));
@@ -80,6 +119,12 @@ fn list_payments_defaults() {
         limit,
         offset,
         sort_ascending,
+        search,
+        cursor,
+        destination_pubkey,
+        lightning_address,
+        grouped,
+        dispute_status_filter,
     } = parse_ok("list-payments")
     else {
         panic!("expected ListPayments");
@@ -95,6 +140,12 @@ fn list_payments_defaults() {
     assert_eq!(limit, Some(10));
     assert_eq!(offset, Some(0));
     assert!(sort_ascending.is_none());
+    assert!(destination_pubkey.is_none());
+    assert!(lightning_address.is_none());
+    assert!(search.is_none());
+    assert!(cursor.is_none());
+    assert!(grouped.is_none());
+    assert!(dispute_status_filter.is_none());
 }
 
 #[test]
@@ -144,11 +195,110 @@ fn list_payments_filters() {
     parse_err("list-payments --limit ten");
 }
 
+#[test]
+fn list_payments_counterparty() {
+    let Command::ListPayments {
+        destination_pubkey,
+        lightning_address,
+        ..
+    } = parse_ok("list-payments --lightning-address alice@example.com")
+    else {
+        panic!("expected ListPayments");
+    };
+    assert!(destination_pubkey.is_none());
+    assert_eq!(lightning_address.as_deref(), Some("alice@example.com"));
+
+    parse_err("list-payments --destination-pubkey 02ab --lightning-address alice@example.com");
+}
+
+#[test]
+fn import_payments() {
+    let Command::ImportPayments { source, file } =
+        parse_ok("import-payments --source liquid export.json")
+    else {
+        panic!("expected ImportPayments");
+    };
+    assert!(matches!(source, PaymentImportSourceArg::Liquid));
+    assert_eq!(file, "export.json");
+    assert!(matches!(
+        parse_ok("import-payments -s greenlight export.json"),
+        Command::ImportPayments {
+            source: PaymentImportSourceArg::Greenlight,
+            ..
+        }
+    ));
+    parse_err("import-payments export.json");
+    parse_err("import-payments --source breez export.json");
+}
+
+#[test]
+fn export_payments() {
+    let Command::ExportPayments {
+        format,
+        include_metadata,
+        fiat_currency,
+        file,
+        ..
+    } = parse_ok("export-payments -f json -m --fiat-currency usd history.jsonl")
+    else {
+        panic!("expected ExportPayments");
+    };
+    assert!(matches!(format, ExportFormatArg::Json));
+    assert!(include_metadata);
+    assert_eq!(fiat_currency.as_deref(), Some("usd"));
+    assert_eq!(file, "history.jsonl");
+    assert!(matches!(
+        parse_ok("export-payments history.csv"),
+        Command::ExportPayments {
+            format: ExportFormatArg::Csv,
+            include_metadata: false,
+            ..
+        }
+    ));
+    parse_err("export-payments --format xml history.csv");
+}
+
+#[test]
+fn backup_and_restore() {
+    assert!(matches!(
+        parse_ok("backup wallet.bak"),
+        Command::Backup { file } if file == "wallet.bak"
+    ));
+    assert!(matches!(
+        parse_ok("restore wallet.bak"),
+        Command::Restore { file } if file == "wallet.bak"
+    ));
+    parse_err("restore");
+}
+
+#[test]
+fn sync_capture() {
+    assert!(matches!(
+        parse_ok("start-sync-capture"),
+        Command::StartSyncCapture
+    ));
+    assert!(matches!(
+        parse_ok("stop-sync-capture capture.json"),
+        Command::StopSyncCapture { file } if file == "capture.json"
+    ));
+    parse_err("stop-sync-capture");
+}
+
+#[test]
+fn export_viewer_bundle() {
+    assert!(matches!(
+        parse_ok("export-viewer-bundle viewer.json"),
+        Command::ExportViewerBundle { file } if file == "viewer.json"
+    ));
+    parse_err("export-viewer-bundle");
+}
+
 #[test]
 fn receive_methods() {
     for (line, expected) in [
         ("receive -m sparkaddress", "sparkaddress"),
         ("receive -m sparkinvoice", "sparkinvoice"),
+        ("receive -m tokeninvoice", "tokeninvoice"),
         ("receive -m bitcoin", "bitcoin"),
         ("receive -m bolt11", "bolt11"),
         ("receive --method bolt11", "bolt11"),
@@ -159,6 +309,7 @@ fn receive_methods() {
         let matches_expected = match payment_method {
             ReceivePaymentMethodArg::SparkAddress => expected == "sparkaddress",
             ReceivePaymentMethodArg::SparkInvoice => expected == "sparkinvoice",
+            ReceivePaymentMethodArg::TokenInvoice => expected == "tokeninvoice",
             ReceivePaymentMethodArg::Bitcoin => expected == "bitcoin",
             ReceivePaymentMethodArg::Bolt11 => expected == "bolt11",
         };
@@ -216,6 +367,7 @@ fn pay() {
         convert_max_slippage_bps,
         cross_chain_max_slippage_bps,
         fees_included,
+        queue_if_offline,
     } = parse_ok(
         "pay -r lnbc1... -a 1000 -t tok1 -i key1 -s 40 --cross-chain-max-slippage-bps 100",
     )
@@ -231,17 +383,20 @@ fn pay() {
     assert_eq!(convert_max_slippage_bps, Some(40));
     assert_eq!(cross_chain_max_slippage_bps, Some(100));
     assert!(!fees_included);
+    assert!(!queue_if_offline);
 
     let Command::Pay {
         convert_from_bitcoin,
         fees_included,
+        queue_if_offline,
         ..
-    } = parse_ok("pay -r addr1 --from-bitcoin --fees-included")
+    } = parse_ok("pay -r addr1 --from-bitcoin --fees-included --queue-if-offline")
     else {
         panic!("expected Pay");
     };
     assert_eq!(convert_from_bitcoin, Some(true));
     assert!(fees_included);
+    assert!(queue_if_offline);
 
     let Command::Pay {
         convert_from_token_identifier,
@@ -318,6 +473,18 @@ fn claim_htlc_payment() {
     assert_eq!(preimage, "deadbeef");
 }
 
+#[test]
+fn htlc_receive() {
+    let Command::CreateHtlcReceive { payment_hash } = parse_ok("create-htlc-receive abcd") else {
+        panic!("expected CreateHtlcReceive");
+    };
+    assert_eq!(payment_hash, "abcd");
+    let Command::GetHtlcReceive { payment_hash } = parse_ok("get-htlc-receive abcd") else {
+        panic!("expected GetHtlcReceive");
+    };
+    assert_eq!(payment_hash, "abcd");
+}
+
 #[test]
 fn claim_deposit() {
     let Command::ClaimDeposit {
@@ -381,6 +548,67 @@ fn refund_deposit() {
     parse_err("refund-deposit tx1 0");
 }
 
+#[test]
+fn deposits_list() {
+    assert!(matches!(
+        parse_ok("deposits list"),
+        Command::Deposits(DepositCommand::List)
+    ));
+}
+
+#[test]
+fn deposits_claim() {
+    let Command::Deposits(DepositCommand::Claim {
+        outpoint,
+        max_fee,
+        sat_per_vbyte,
+        recommended_fee_leeway,
+        dry_run,
+    }) = parse_ok("deposits claim tx1:2 --max-fee 500 --dry-run")
+    else {
+        panic!("expected Deposits Claim");
+    };
+    assert_eq!(
+        outpoint,
+        DepositOutpoint {
+            txid: "tx1".to_string(),
+            vout: 2
+        }
+    );
+    assert_eq!(max_fee, Some(500));
+    assert!(sat_per_vbyte.is_none());
+    assert!(recommended_fee_leeway.is_none());
+    assert!(dry_run);
+
+    parse_err("deposits claim tx1");
+    parse_err("deposits claim tx1:notanumber");
+    parse_err("deposits claim tx1:0 --max-fee 500 --sat-per-vbyte 2");
+}
+
+#[test]
+fn deposits_refund() {
+    let Command::Deposits(DepositCommand::Refund {
+        outpoint,
+        address,
+        fee_rate,
+        fee_sat,
+        dry_run,
+    }) = parse_ok("deposits refund tx1:0 --address bcrt1qaddr --fee-rate 5")
+    else {
+        panic!("expected Deposits Refund");
+    };
+    assert_eq!(outpoint.txid, "tx1");
+    assert_eq!(outpoint.vout, 0);
+    assert_eq!(address, "bcrt1qaddr");
+    assert_eq!(fee_rate, Some(5));
+    assert!(fee_sat.is_none());
+    assert!(!dry_run);
+
+    parse_err("deposits refund tx1:0 --fee-rate 5");
+    parse_err("deposits refund tx1:0 --address bcrt1qaddr");
+    parse_err("deposits refund tx1:0 --address bcrt1qaddr --fee-rate 5 --fee-sat 100");
+}
+
 #[test]
 fn list_unclaimed_deposits() {
     assert!(matches!(
@@ -1,6 +1,7 @@
 use breez_sdk_spark::{
     BurnIssuerTokenRequest, CreateIssuerTokenRequest, FreezeIssuerTokenRequest,
-    MintIssuerTokenRequest, TokenIssuer, UnfreezeIssuerTokenRequest,
+    MintIssuerTokenRequest, PublishTokenAnnouncementRequest, TokenIssuer,
+    UnfreezeIssuerTokenRequest,
 };
 use clap::{ArgAction, Subcommand};
 
@@ -46,6 +47,12 @@ pub enum IssuerCommand {
         /// Address holding the tokens to unfreeze
         address: String,
     },
+    /// Publishes the issuer token metadata to Nostr relays
+    AnnounceToken {
+        /// URL of the token icon
+        #[arg(long)]
+        icon_url: Option<String>,
+    },
 }
 
 pub async fn handle_command(
@@ -110,5 +117,12 @@ pub async fn handle_command(
             print_value(&response)?;
             Ok(true)
         }
+        IssuerCommand::AnnounceToken { icon_url } => {
+            let response = token_issuer
+                .publish_token_announcement(PublishTokenAnnouncementRequest { icon_url })
+                .await?;
+            print_value(&response)?;
+            Ok(true)
+        }
     }
 }
@@ -1,5 +1,6 @@
 mod advanced;
 mod contacts;
+mod deposits;
 #[cfg(test)]
 mod grammar_tests;
 mod issuer;
@@ -8,16 +9,21 @@ mod webhooks;
 
 use bitcoin::hashes::{Hash, sha256};
 use breez_sdk_spark::{
-    AssetFilter, AuthorizeTransferRequest, BreezSdk, BuyBitcoinRequest,
-    CheckLightningAddressRequest, ClaimDepositRequest, ClaimHtlcPaymentRequest,
-    ClaimTransferRequest, ConversionOptions, ConversionType, CrossChainRoutePair, Fee, FeePolicy,
-    FetchConversionLimitsRequest, GetInfoRequest, GetPaymentRequest, GetTokensMetadataRequest,
-    InputType, LightningAddressDetails, ListPaymentsRequest, ListUnclaimedDepositsRequest,
-    LnurlPayRequest, LnurlWithdrawRequest, MaxFee, OnchainConfirmationSpeed, PaymentDetailsFilter,
+    AcknowledgePaymentRequest, AssetFilter, AuthorizeTransferRequest, BreezSdk, BuyBitcoinRequest,
+    CheckLightningAddressRequest, CheckStorageIntegrityRequest, ClaimDepositRequest,
+    ClaimHtlcPaymentRequest, ClaimTransferRequest, ConversionOptions, ConversionType,
+    CounterpartyFilter, CreateHtlcReceiveRequest, CrossChainRoutePair, DiagnoseReceiveRequest,
+    DisputeStatus, ExportFormat, ExportPaymentsRequest, Fee, FeePolicy,
+    FetchConversionLimitsRequest, GetHtlcReceiveRequest, GetInfoRequest, GetPaymentRequest,
+    GetTokenAnnouncementsRequest, GetTokensMetadataRequest, ImportPaymentsRequest, InputType,
+    LightningAddressDetails, ListPaymentsRequest, ListUnacknowledgedPaymentsRequest,
+    ListUnclaimedDepositsRequest, LnurlPayRequest, LnurlWithdrawRequest, MaxFee,
+    OnchainConfirmationSpeed, PaySplitRequest, PaymentDetailsFilter, PaymentImportSource,
     PaymentRequest, PaymentStatus, PaymentType, PrepareLnurlPayRequest, PrepareSendPaymentRequest,
     ReceivePaymentMethod, ReceivePaymentRequest, RefundDepositRequest,
-    RegisterLightningAddressRequest, SendPaymentMethod, SendPaymentOptions, SendPaymentRequest,
-    SparkHtlcOptions, SparkHtlcStatus, SyncWalletRequest, TokenIssuer, TokenTransactionType,
+    RegisterLightningAddressRequest, RestoreRequest, SendPaymentMethod, SendPaymentOptions,
+    SendPaymentRequest, SetPaymentDisputeRequest, SparkHtlcOptions, SparkHtlcStatus,
+    SplitExecutionMode, SyncWalletRequest, TokenIssuer, TokenTransactionType,
     TransferAuthorization, UpdateUserSettingsRequest,
 };
 use clap::{Parser, ValueEnum};
@@ -28,11 +34,13 @@ use rustyline::{
 };
 use std::{
     borrow::Cow::{self, Owned},
+    io::Write,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use crate::command::advanced::AdvancedCommand;
 use crate::command::contacts::ContactCommand;
+use crate::command::deposits::DepositCommand;
 use crate::command::issuer::IssuerCommand;
 use crate::command::stable_balance::StableBalanceCommand;
 use crate::command::webhooks::WebhookCommand;
@@ -42,10 +50,25 @@ use crate::command::webhooks::WebhookCommand;
 pub enum ReceivePaymentMethodArg {
     SparkAddress,
     SparkInvoice,
+    TokenInvoice,
     Bitcoin,
     Bolt11,
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum PaymentImportSourceArg {
+    Greenlight,
+    Liquid,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum ExportFormatArg {
+    Csv,
+    Json,
+}
+
 #[derive(Clone, Parser)]
 pub enum Command {
     /// Exit the interactive shell (interactive mode only)
@@ -64,7 +87,58 @@ pub enum Command {
         /// The ID of the payment to retrieve
         payment_id: String,
     },
+    /// Mark a payment as disputed or resolve its dispute
+    SetPaymentDispute {
+        /// The ID of the payment
+        payment_id: String,
+
+        /// The dispute status: disputed or resolved
+        status: DisputeStatus,
+
+        /// A note describing the dispute or its resolution
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// List the received payments a consumer has not acknowledged yet
+    ListUnacknowledgedPayments {
+        /// The ID of the consumer
+        consumer_id: String,
+
+        /// The maximum number of payments to list
+        #[arg(short, long)]
+        limit: Option<u32>,
+    },
+    /// Acknowledge that a consumer has processed a received payment
+    AcknowledgePayment {
+        /// The ID of the consumer
+        consumer_id: String,
+
+        /// The ID of the payment
+        payment_id: String,
+    },
+    /// Show the stage the payment of a BOLT11 invoice created by this wallet reached
+    DiagnoseReceive {
+        /// The invoice to diagnose
+        invoice: String,
+    },
     Sync,
+    /// Start capturing the payment syncs for debugging
+    StartSyncCapture,
+    /// Stop capturing the payment syncs and write the capture to a file
+    StopSyncCapture {
+        /// Path of the JSON file to write
+        file: String,
+    },
+    /// Connect and authenticate ahead of the first payment
+    WarmUp,
+    /// Show how accurate the fee estimates of recent Lightning payments were
+    FeeEstimationStats,
+    /// Check the storage for corruption, invalid JSON values and orphaned records
+    CheckStorageIntegrity {
+        /// Repair the issues found and rebuild the derived state
+        #[arg(long)]
+        repair: bool,
+    },
     /// Lists payments
     ListPayments {
         /// Filter by payment type
@@ -107,9 +181,87 @@ pub enum Command {
         #[arg(short, long, default_value = "0")]
         offset: Option<u32>,
 
+        /// Only include payments whose text contains this term
+        #[arg(long)]
+        search: Option<String>,
+
+        /// Continue after a previous page, as returned in `next_cursor`
+        #[arg(long)]
+        cursor: Option<String>,
+
         /// Sort payments in ascending order
         #[arg(long)]
         sort_ascending: Option<bool>,
+
+        /// Only include Lightning payments to this destination node pubkey
+        #[arg(long, conflicts_with = "lightning_address")]
+        destination_pubkey: Option<String>,
+
+        /// Only include payments sent to this Lightning address
+        #[arg(long)]
+        lightning_address: Option<String>,
+
+        /// Also aggregate the payments by group
+        #[arg(long)]
+        grouped: Option<bool>,
+
+        /// Only include payments with a dispute in this status
+        #[arg(long)]
+        dispute_status_filter: Option<Vec<DisputeStatus>>,
+    },
+
+    /// Export the payment history to a file
+    ExportPayments {
+        /// The format of the export
+        #[arg(short, long, value_enum, default_value = "csv")]
+        format: ExportFormatArg,
+
+        /// Only export payments at or after this timestamp
+        #[arg(long)]
+        from_timestamp: Option<u64>,
+
+        /// Only export payments before this timestamp
+        #[arg(long)]
+        to_timestamp: Option<u64>,
+
+        /// Include the description and the invoice or transaction id
+        #[arg(short = 'm', long)]
+        include_metadata: bool,
+
+        /// Include the fiat value of Bitcoin payments in this currency
+        #[arg(long)]
+        fiat_currency: Option<String>,
+
+        /// Path of the file to write
+        file: String,
+    },
+
+    /// Write an encrypted backup of the wallet data to a file
+    Backup {
+        /// Path of the file to write
+        file: String,
+    },
+
+    /// Restore the wallet data from a backup file
+    Restore {
+        /// Path of the backup file
+        file: String,
+    },
+
+    /// Write a viewer bundle for viewing the wallet on another machine to a file
+    ExportViewerBundle {
+        /// Path of the JSON file to write
+        file: String,
+    },
+
+    /// Import the payment history exported from another Breez SDK
+    ImportPayments {
+        /// The SDK the export was produced by
+        #[arg(short, long, value_enum)]
+        source: PaymentImportSourceArg,
+
+        /// Path to the JSON file with the exported payments
+        file: String,
     },
 
     /// Receive
@@ -125,7 +277,7 @@ pub enum Command {
         #[arg(short = 'a', long)]
         amount: Option<u128>,
 
-        /// Optional token identifier. Only used if the payment method is a spark invoice. Absence indicates sats payment.
+        /// Optional token identifier. Used if the payment method is a spark invoice or token invoice. Absence indicates sats payment.
         #[arg(short = 't', long)]
         token_identifier: Option<String>,
 
@@ -141,9 +293,21 @@ pub enum Command {
         #[arg(long)]
         hodl: bool,
 
+        /// Minimum amount the payer can send to an amountless bolt11 invoice, in sats.
+        #[arg(long)]
+        min_amount: Option<u64>,
+
+        /// Maximum amount the payer can send to an amountless bolt11 invoice, in sats.
+        #[arg(long)]
+        max_amount: Option<u64>,
+
         /// Request a new bitcoin deposit address instead of reusing the current one.
         #[arg(long)]
         new_address: bool,
+
+        /// Optional order reference, used by the configured invoice description template.
+        #[arg(long)]
+        order_ref: Option<String>,
     },
 
     /// Pay the given payment request
@@ -188,6 +352,25 @@ pub enum Command {
         /// If set, fees will be deducted from the specified amount instead of added on top.
         #[arg(long = "fees-included", action = clap::ArgAction::SetTrue)]
         fees_included: bool,
+
+        /// If set, the payment is queued when the network is unreachable and sent once it is back.
+        #[arg(long = "queue-if-offline", action = clap::ArgAction::SetTrue)]
+        queue_if_offline: bool,
+    },
+
+    /// Pay several invoices that share a total budget
+    PaySplit {
+        /// The most the invoices may cost in total, fees included, in satoshis
+        #[arg(short = 'b', long)]
+        budget: u64,
+
+        /// Pay the invoices concurrently instead of one after the other
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        parallel: bool,
+
+        /// BOLT11 or Spark invoices with embedded amounts
+        #[arg(required = true)]
+        invoices: Vec<String>,
     },
 
     /// Pay using LNURL
@@ -248,6 +431,18 @@ pub enum Command {
         preimage: String,
     },
 
+    /// Register a receive for a Spark HTLC locked to the given payment hash
+    CreateHtlcReceive {
+        /// The payment hash the HTLC must lock to (hex string)
+        payment_hash: String,
+    },
+
+    /// Get the current state of an HTLC receive
+    GetHtlcReceive {
+        /// The payment hash of the HTLC receive (hex string)
+        payment_hash: String,
+    },
+
     ClaimDeposit {
         /// The txid of the deposit
         txid: String,
@@ -270,6 +465,10 @@ pub enum Command {
     Parse {
         input: String,
     },
+    /// Decode a BOLT11 invoice or a Spark invoice offline
+    Decode {
+        input: String,
+    },
     RefundDeposit {
         /// The txid of the deposit
         txid: String,
@@ -354,6 +553,11 @@ pub enum Command {
         /// The token identifiers to get metadata for
         token_identifiers: Vec<String>,
     },
+    /// Fetches the issuer announcements of tokens from Nostr relays
+    GetTokenAnnouncements {
+        /// The token identifiers to get announcements for
+        token_identifiers: Vec<String>,
+    },
     FetchConversionLimits {
         /// Whether we are converting from or to Bitcoin
         #[clap(short = 'f', long, action = clap::ArgAction::SetTrue)]
@@ -389,6 +593,10 @@ pub enum Command {
     #[command(subcommand)]
     Webhooks(WebhookCommand),
 
+    /// Unclaimed deposit management commands
+    #[command(subcommand)]
+    Deposits(DepositCommand),
+
     /// Stable balance related commands
     #[command(subcommand)]
     StableBalance(StableBalanceCommand),
@@ -428,9 +636,56 @@ pub(crate) async fn execute_command(
             print_value(&value)?;
             Ok(true)
         }
+        Command::SetPaymentDispute {
+            payment_id,
+            status,
+            note,
+        } => {
+            let value = sdk
+                .set_payment_dispute(SetPaymentDisputeRequest {
+                    payment_id,
+                    status,
+                    note,
+                })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::ListUnacknowledgedPayments { consumer_id, limit } => {
+            let value = sdk
+                .list_unacknowledged_payments(ListUnacknowledgedPaymentsRequest {
+                    consumer_id,
+                    limit,
+                })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::AcknowledgePayment {
+            consumer_id,
+            payment_id,
+        } => {
+            let value = sdk
+                .acknowledge_payment(AcknowledgePaymentRequest {
+                    payment_id,
+                    consumer_id,
+                })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::DiagnoseReceive { invoice } => {
+            let value = sdk
+                .diagnose_receive(DiagnoseReceiveRequest { invoice })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
         Command::ListPayments {
             limit,
             offset,
+            search,
+            cursor,
             type_filter,
             status_filter,
             spark_htlc_status_filter,
@@ -440,6 +695,10 @@ pub(crate) async fn execute_command(
             from_timestamp,
             to_timestamp,
             sort_ascending,
+            destination_pubkey,
+            lightning_address,
+            grouped,
+            dispute_status_filter,
         } => {
             let mut payment_details_filter = Vec::new();
             if let Some(statuses) = spark_htlc_status_filter {
@@ -467,6 +726,13 @@ pub(crate) async fn execute_command(
             } else {
                 Some(payment_details_filter)
             };
+            let counterparty_filter = match (destination_pubkey, lightning_address) {
+                (Some(destination_pubkey), _) => {
+                    Some(CounterpartyFilter::LightningDestination { destination_pubkey })
+                }
+                (None, Some(address)) => Some(CounterpartyFilter::LightningAddress { address }),
+                (None, None) => None,
+            };
             let value = sdk
                 .list_payments(ListPaymentsRequest {
                     limit,
@@ -478,16 +744,100 @@ pub(crate) async fn execute_command(
                     from_timestamp,
                     to_timestamp,
                     sort_ascending,
+                    counterparty_filter,
+                    search,
+                    cursor,
+                    grouped,
+                    dispute_status_filter,
                 })
                 .await?;
             print_value(&value)?;
             Ok(true)
         }
+        Command::ExportPayments {
+            format,
+            from_timestamp,
+            to_timestamp,
+            include_metadata,
+            fiat_currency,
+            file,
+        } => {
+            let format = match format {
+                ExportFormatArg::Csv => ExportFormat::Csv,
+                ExportFormatArg::Json => ExportFormat::Json,
+            };
+            let mut out = std::fs::File::create(&file)?;
+            let mut cursor = None;
+            loop {
+                let chunk = sdk
+                    .export_payments(ExportPaymentsRequest {
+                        format,
+                        from_timestamp,
+                        to_timestamp,
+                        include_metadata,
+                        fiat_currency: fiat_currency.clone(),
+                        cursor,
+                    })
+                    .await?;
+                out.write_all(chunk.data.as_bytes())?;
+                cursor = chunk.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            println!("Exported payments to {file}");
+            Ok(true)
+        }
+        Command::Backup { file } => {
+            let response = sdk.backup().await?;
+            std::fs::write(&file, response.backup)?;
+            println!("Wrote backup to {file}");
+            Ok(true)
+        }
+        Command::Restore { file } => {
+            let backup = std::fs::read(&file)?;
+            let value = sdk.restore(RestoreRequest { backup }).await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::ExportViewerBundle { file } => {
+            let bundle = sdk.export_viewer_bundle();
+            std::fs::write(&file, serde_json::to_string_pretty(&bundle)?)?;
+            println!("Wrote viewer bundle to {file}");
+            Ok(true)
+        }
+        Command::ImportPayments { source, file } => {
+            let source = match source {
+                PaymentImportSourceArg::Greenlight => PaymentImportSource::Greenlight,
+                PaymentImportSourceArg::Liquid => PaymentImportSource::Liquid,
+            };
+            let data = std::fs::read_to_string(&file)?;
+            let value = sdk
+                .import_payments(ImportPaymentsRequest { source, data })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
         Command::Sync => {
             let value = sdk.sync_wallet(SyncWalletRequest {}).await?;
             print_value(&value)?;
             Ok(true)
         }
+        Command::StartSyncCapture => {
+            sdk.start_sync_capture().await?;
+            println!("Capturing payment syncs");
+            Ok(true)
+        }
+        Command::StopSyncCapture { file } => {
+            let response = sdk.stop_sync_capture().await?;
+            std::fs::write(&file, response.capture)?;
+            println!("Wrote sync capture to {file}");
+            Ok(true)
+        }
+        Command::WarmUp => {
+            sdk.warm_up().await?;
+            Ok(true)
+        }
         Command::ListUnclaimedDeposits => {
             let value = sdk
                 .list_unclaimed_deposits(ListUnclaimedDepositsRequest {})
@@ -538,6 +888,42 @@ pub(crate) async fn execute_command(
             print_value(&value)?;
             Ok(true)
         }
+        Command::PaySplit {
+            budget,
+            parallel,
+            invoices,
+        } => {
+            let value = sdk
+                .pay_split(PaySplitRequest {
+                    invoices,
+                    total_budget_sats: budget,
+                    execution_mode: Some(if parallel {
+                        SplitExecutionMode::Parallel
+                    } else {
+                        SplitExecutionMode::Sequential
+                    }),
+                })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::FeeEstimationStats => {
+            let value = sdk.get_fee_estimation_stats().await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::CheckStorageIntegrity { repair } => {
+            let value = sdk
+                .check_storage_integrity(CheckStorageIntegrityRequest { repair })
+                .await?;
+            print_value(&value)?;
+            Ok(true)
+        }
+        Command::Decode { input } => {
+            let value = breez_sdk_spark::decode_payment_request(input)?;
+            print_value(&value)?;
+            Ok(true)
+        }
         Command::RefundDeposit {
             txid,
             vout,
@@ -600,7 +986,10 @@ pub(crate) async fn execute_command(
             expiry_secs,
             sender_public_key,
             hodl,
+            min_amount,
+            max_amount,
             new_address,
+            order_ref,
         } => {
             let payment_method = match payment_method {
                 ReceivePaymentMethodArg::SparkAddress => ReceivePaymentMethod::SparkAddress,
@@ -619,6 +1008,15 @@ pub(crate) async fn execute_command(
                     description,
                     sender_public_key,
                 },
+                ReceivePaymentMethodArg::TokenInvoice => ReceivePaymentMethod::TokenInvoice {
+                    token_identifier: token_identifier.ok_or_else(|| {
+                        anyhow::anyhow!("--token-identifier is required for token invoices")
+                    })?,
+                    amount: amount.ok_or_else(|| {
+                        anyhow::anyhow!("--amount is required for token invoices")
+                    })?,
+                    memo: description,
+                },
                 ReceivePaymentMethodArg::Bitcoin => ReceivePaymentMethod::BitcoinAddress {
                     new_address: Some(new_address),
                 },
@@ -643,12 +1041,17 @@ pub(crate) async fn execute_command(
                         amount_sats: amount.map(TryInto::try_into).transpose()?,
                         expiry_secs,
                         payment_hash,
+                        min_amount_sats: min_amount,
+                        max_amount_sats: max_amount,
                     }
                 }
             };
 
             let receive_result = sdk
-                .receive_payment(ReceivePaymentRequest { payment_method })
+                .receive_payment(ReceivePaymentRequest {
+                    payment_method,
+                    order_ref,
+                })
                 .await?;
 
             if receive_result.fee > 0 {
@@ -671,6 +1074,7 @@ pub(crate) async fn execute_command(
             convert_max_slippage_bps: max_slippage_bps,
             cross_chain_max_slippage_bps,
             fees_included,
+            queue_if_offline,
         } => {
             let conversion_options = match (convert_from_bitcoin, convert_from_token_identifier) {
                 (Some(true), _) => Some(ConversionOptions {
@@ -794,6 +1198,8 @@ pub(crate) async fn execute_command(
                 prepare_response,
                 options: payment_options,
                 idempotency_key,
+                compliance_metadata: None,
+                queue_if_offline: Some(queue_if_offline),
             }))
             .await?;
 
@@ -954,6 +1360,20 @@ pub(crate) async fn execute_command(
             print_value(&res.payment)?;
             Ok(true)
         }
+        Command::CreateHtlcReceive { payment_hash } => {
+            let res = sdk
+                .create_htlc_receive(CreateHtlcReceiveRequest { payment_hash })
+                .await?;
+            print_value(&res)?;
+            Ok(true)
+        }
+        Command::GetHtlcReceive { payment_hash } => {
+            let res = sdk
+                .get_htlc_receive(GetHtlcReceiveRequest { payment_hash })
+                .await?;
+            print_value(&res.htlc_receive)?;
+            Ok(true)
+        }
         Command::CheckLightningAddressAvailable { username } => {
             let res = sdk
                 .check_lightning_address_available(CheckLightningAddressRequest { username })
@@ -1033,6 +1453,13 @@ pub(crate) async fn execute_command(
             print_value(&res)?;
             Ok(true)
         }
+        Command::GetTokenAnnouncements { token_identifiers } => {
+            let res = sdk
+                .get_token_announcements(GetTokenAnnouncementsRequest { token_identifiers })
+                .await?;
+            print_value(&res)?;
+            Ok(true)
+        }
         Command::FetchConversionLimits {
             from_bitcoin,
             token_identifier,
@@ -1080,6 +1507,7 @@ pub(crate) async fn execute_command(
         }
         Command::Contacts(contact_command) => contacts::handle_command(sdk, contact_command).await,
         Command::Webhooks(webhook_command) => webhooks::handle_command(sdk, webhook_command).await,
+        Command::Deposits(deposit_command) => deposits::handle_command(sdk, deposit_command).await,
         Command::StableBalance(sb_command) => stable_balance::handle_command(sdk, sb_command).await,
     }
 }
@@ -1,15 +1,19 @@
+mod doh;
 #[cfg_attr(
     all(target_family = "wasm", target_os = "unknown"),
     path = "resolver_wasm.rs"
 )]
 mod resolver;
 
+use std::sync::Arc;
+
 use anyhow::Result;
 use dnssec_prover::rr::{Name, RR};
 use dnssec_prover::ser::parse_rr_stream;
 use dnssec_prover::validation::verify_rr_stream;
 use platform_utils::time::{SystemTime, UNIX_EPOCH};
 
+pub use doh::{DEFAULT_DOH_ENDPOINT, DohResolver};
 pub use resolver::Resolver;
 
 #[macros::async_trait]
@@ -17,6 +21,13 @@ pub trait DnsResolver {
     async fn txt_lookup(&self, dns_name: String) -> Result<Vec<String>>;
 }
 
+#[macros::async_trait]
+impl<T: DnsResolver + Send + Sync + ?Sized> DnsResolver for Arc<T> {
+    async fn txt_lookup(&self, dns_name: String) -> Result<Vec<String>> {
+        (**self).txt_lookup(dns_name).await
+    }
+}
+
 /// Normalizes a DNS name to FQDN format (with trailing dot) as required by dnssec-prover.
 fn normalize_dns_name(dns_name: String) -> String {
     if dns_name.ends_with('.') {
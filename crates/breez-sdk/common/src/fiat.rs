@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
+use platform_utils::HttpClient;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -7,6 +9,11 @@ use crate::{
     with_connection_retry,
 };
 
+/// The mempool.space historical price API
+pub const MEMPOOL_HISTORICAL_PRICE_URL: &str = "https://mempool.space/api/v1/historical-price";
+/// Historical rates are cached per currency and per hour.
+const HISTORICAL_RATE_BUCKET_SECS: u64 = 3600;
+
 /// Details about a supported currency in the fiat rate feed
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -32,6 +39,110 @@ pub trait FiatService: Send + Sync {
     async fn fetch_fiat_rates(&self) -> Result<Vec<Rate>, ServiceConnectivityError>;
 }
 
+/// Trait covering rates of fiat currencies at a past point in time
+#[macros::async_trait]
+pub trait HistoricalRatesService: Send + Sync {
+    /// Get the BTC rate of `currency` at the given unix `timestamp`, or `None`
+    /// if the currency isn't supported.
+    async fn fetch_historical_rate(
+        &self,
+        currency: &str,
+        timestamp: u64,
+    ) -> Result<Option<f64>, ServiceConnectivityError>;
+}
+
+#[derive(Deserialize)]
+struct HistoricalPriceResponse {
+    prices: Vec<HistoricalPrice>,
+}
+
+#[derive(Deserialize)]
+struct HistoricalPrice {
+    #[serde(flatten)]
+    rates: HashMap<String, f64>,
+}
+
+/// Fetches historical rates from a price API following the mempool.space one,
+/// e.g. [`MEMPOOL_HISTORICAL_PRICE_URL`]
+pub struct MempoolHistoricalRates {
+    http_client: Arc<dyn HttpClient>,
+    base_url: String,
+}
+
+impl MempoolHistoricalRates {
+    pub fn new(http_client: Arc<dyn HttpClient>, base_url: String) -> Self {
+        Self {
+            http_client,
+            base_url,
+        }
+    }
+}
+
+#[macros::async_trait]
+impl HistoricalRatesService for MempoolHistoricalRates {
+    async fn fetch_historical_rate(
+        &self,
+        currency: &str,
+        timestamp: u64,
+    ) -> Result<Option<f64>, ServiceConnectivityError> {
+        let currency = currency.to_uppercase();
+        let url = format!(
+            "{}?currency={currency}&timestamp={timestamp}",
+            self.base_url
+        );
+        let response = self.http_client.get(url, None).await?;
+        if !response.is_success() {
+            return Err(ServiceConnectivityError::Status {
+                status: response.status,
+                body: response.body,
+            });
+        }
+        let response: HistoricalPriceResponse = response.json()?;
+        // Unknown prices are reported as -1
+        Ok(response
+            .prices
+            .first()
+            .and_then(|price| price.rates.get(&currency).copied())
+            .filter(|rate| *rate > 0.0))
+    }
+}
+
+/// Caches the rates of a [`HistoricalRatesService`] in memory, per currency
+/// and per hour.
+pub struct CachedHistoricalRates {
+    inner: Arc<dyn HistoricalRatesService>,
+    cache: Mutex<HashMap<(String, u64), f64>>,
+}
+
+impl CachedHistoricalRates {
+    pub fn new(inner: Arc<dyn HistoricalRatesService>) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[macros::async_trait]
+impl HistoricalRatesService for CachedHistoricalRates {
+    async fn fetch_historical_rate(
+        &self,
+        currency: &str,
+        timestamp: u64,
+    ) -> Result<Option<f64>, ServiceConnectivityError> {
+        let bucket = timestamp.saturating_sub(timestamp % HISTORICAL_RATE_BUCKET_SECS);
+        let key = (currency.to_uppercase(), bucket);
+        if let Some(rate) = self.cache.lock().unwrap().get(&key) {
+            return Ok(Some(*rate));
+        }
+        let rate = self.inner.fetch_historical_rate(currency, bucket).await?;
+        if let Some(rate) = rate {
+            self.cache.lock().unwrap().insert(key, rate);
+        }
+        Ok(rate)
+    }
+}
+
 fn convert_to_fiat_currency_with_id(id: String, info: CurrencyInfo) -> FiatCurrency {
     FiatCurrency { id, info }
 }
@@ -123,3 +234,80 @@ pub struct Symbol {
     pub rtl: Option<bool>,
     pub position: Option<u32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use macros::async_test_all;
+
+    use super::{
+        CachedHistoricalRates, HistoricalRatesService, MEMPOOL_HISTORICAL_PRICE_URL,
+        MempoolHistoricalRates,
+    };
+    use crate::test_utils::mock_rest_client::{MockResponse, MockRestClient};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    const PRICE_RESPONSE: &str = r#"{"prices":[{"time":1700000000,"USD":37000.5,"EUR":34000,"JPY":-1}],"exchangeRates":{"USDEUR":0.92}}"#;
+
+    #[async_test_all]
+    async fn test_mempool_historical_rate() {
+        let client = Arc::new(MockRestClient::new());
+        client.add_response(MockResponse::new(200, PRICE_RESPONSE.to_string()));
+        client.add_response(MockResponse::new(200, PRICE_RESPONSE.to_string()));
+        let rates = MempoolHistoricalRates::new(client, MEMPOOL_HISTORICAL_PRICE_URL.to_string());
+
+        let rate = rates
+            .fetch_historical_rate("eur", 1_700_000_123)
+            .await
+            .unwrap();
+        assert_eq!(rate, Some(34000.0));
+        // Prices the API doesn't know are reported as -1
+        let rate = rates
+            .fetch_historical_rate("JPY", 1_700_000_123)
+            .await
+            .unwrap();
+        assert_eq!(rate, None);
+    }
+
+    #[async_test_all]
+    async fn test_mempool_historical_rate_error_status() {
+        let client = Arc::new(MockRestClient::new());
+        client.add_response(MockResponse::new(500, "error".to_string()));
+        let rates = MempoolHistoricalRates::new(client, MEMPOOL_HISTORICAL_PRICE_URL.to_string());
+
+        let result = rates.fetch_historical_rate("USD", 1_700_000_000).await;
+        assert!(result.is_err());
+    }
+
+    #[async_test_all]
+    async fn test_cached_historical_rates_per_hour() {
+        let client = Arc::new(MockRestClient::new());
+        client.add_response(MockResponse::new(200, PRICE_RESPONSE.to_string()));
+        let rates = CachedHistoricalRates::new(Arc::new(MempoolHistoricalRates::new(
+            client,
+            MEMPOOL_HISTORICAL_PRICE_URL.to_string(),
+        )));
+
+        let rate = rates
+            .fetch_historical_rate("USD", 1_699_999_300)
+            .await
+            .unwrap();
+        assert_eq!(rate, Some(37000.5));
+        // Same hour, served without another request
+        let rate = rates
+            .fetch_historical_rate("usd", 1_700_002_000)
+            .await
+            .unwrap();
+        assert_eq!(rate, Some(37000.5));
+        // Another hour needs a new request, for which there's no mock response
+        assert!(
+            rates
+                .fetch_historical_rate("USD", 1_700_003_700)
+                .await
+                .is_err()
+        );
+    }
+}
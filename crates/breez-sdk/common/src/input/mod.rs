@@ -10,4 +10,7 @@ pub use cross_chain::{
 };
 pub use error::*;
 pub use models::*;
-pub use parser::{parse, parse_invoice, parse_spark_address, validate_lightning_address_format};
+pub use parser::{
+    decode_payment_request, parse, parse_invoice, parse_spark_address, parse_with_dns_resolver,
+    validate_lightning_address_format,
+};
@@ -153,6 +153,13 @@ pub enum InputType {
     CrossChainAddress(CrossChainAddressDetails),
 }
 
+/// A payment request decoded offline by `decode_payment_request()`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum DecodedPaymentRequest {
+    Bolt11Invoice(Bolt11InvoiceDetails),
+    SparkInvoice(SparkInvoiceDetails),
+}
+
 /// Cross-chain recipient details returned by `parse()` when the input is
 /// recognized as an EVM / Solana / Tron address or URI.
 ///
@@ -11,8 +11,8 @@ use tracing::{debug, error, warn};
 use crate::{
     dns::{self, DnsResolver},
     input::{
-        Bip21Extra, ExternalInputParser, LnurlRequestDetails, ParseError, PaymentRequestSource,
-        SparkAddressDetails, SparkInvoiceDetails,
+        Bip21Extra, DecodedPaymentRequest, ExternalInputParser, LnurlRequestDetails, ParseError,
+        PaymentRequestSource, SparkAddressDetails, SparkInvoiceDetails,
     },
     lnurl::{auth, error::LnurlError, pay::LnurlPayRequestDetails},
 };
@@ -51,9 +51,18 @@ pub fn validate_lightning_address_format(input: &str) -> bool {
 pub async fn parse(
     input: &str,
     external_input_parsers: Option<Vec<ExternalInputParser>>,
+) -> Result<InputType, ParseError> {
+    parse_with_dns_resolver(input, external_input_parsers, dns::Resolver::new()).await
+}
+
+/// Like [`parse`], resolving BIP-353 names with `dns_resolver`
+pub async fn parse_with_dns_resolver<D: DnsResolver + Send + Sync>(
+    input: &str,
+    external_input_parsers: Option<Vec<ExternalInputParser>>,
+    dns_resolver: D,
 ) -> Result<InputType, ParseError> {
     InputParser::new(
-        dns::Resolver::new(),
+        dns_resolver,
         DefaultHttpClient::default(),
         external_input_parsers,
     )
@@ -65,6 +74,32 @@ pub fn parse_invoice(input: &str) -> Option<Bolt11InvoiceDetails> {
     parse_bolt11(input, &PaymentRequestSource::default())
 }
 
+/// Decodes a BOLT11 invoice or a Spark invoice without any network access.
+///
+/// Unlike [`parse`], no LNURL, lightning address or BIP-353 resolution is attempted,
+/// so any other input fails with [`ParseError::InvalidInput`].
+pub fn decode_payment_request(input: &str) -> Result<DecodedPaymentRequest, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::EmptyInput);
+    }
+
+    let source = PaymentRequestSource::default();
+    let lightning_input = if has_lightning_prefix(input) {
+        &input[LIGHTNING_PREFIX_LEN..]
+    } else {
+        input
+    };
+    if let Some(bolt11) = parse_bolt11(lightning_input, &source) {
+        return Ok(DecodedPaymentRequest::Bolt11Invoice(bolt11));
+    }
+
+    match parse_spark_address(input, &source) {
+        Some(InputType::SparkInvoice(invoice)) => Ok(DecodedPaymentRequest::SparkInvoice(invoice)),
+        _ => Err(ParseError::InvalidInput),
+    }
+}
+
 pub struct InputParser<C, D> {
     http_client: C,
     dns_resolver: D,
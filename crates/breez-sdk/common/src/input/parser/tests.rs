@@ -4,9 +4,10 @@ use macros::async_test_all;
 use serde_json::json;
 
 use crate::input::error::Bip21Error;
-use crate::input::parser::InputParser;
+use crate::input::parser::{InputParser, decode_payment_request};
 use crate::input::{
-    Bip21Details, Bip21Extra, BitcoinAddressDetails, ExternalInputParser, InputType, ParseError,
+    Bip21Details, Bip21Extra, BitcoinAddressDetails, DecodedPaymentRequest, ExternalInputParser,
+    InputType, ParseError,
 };
 use crate::test_utils::mock_dns_resolver::MockDnsResolver;
 use crate::test_utils::mock_rest_client::{MockResponse, MockRestClient};
@@ -1122,3 +1123,56 @@ async fn test_lnurl_domain_is_lowercased() {
         other => panic!("Expected LnurlPay, got: {other:?}"),
     }
 }
+
+#[macros::test_all]
+fn test_decode_payment_request_bolt11() {
+    let bolt11 = "lnbc110n1p38q3gtpp5ypz09jrd8p993snjwnm68cph4ftwp22le34xd4r8ftspwshxhmnsdqqxqyjw5qcqpxsp5htlg8ydpywvsa7h3u4hdn77ehs4z4e844em0apjyvmqfkzqhhd2q9qgsqqqyssqszpxzxt9uuqzymr7zxcdccj5g69s8q7zzjs7sgxn9ejhnvdh6gqjcy22mss2yexunagm5r2gqczh8k24cwrqml3njskm548aruhpwssq9nvrvz";
+
+    for input in [bolt11.to_string(), format!("lightning:{bolt11}")] {
+        match decode_payment_request(&input) {
+            Ok(DecodedPaymentRequest::Bolt11Invoice(details)) => {
+                assert_eq!(details.amount_msat, Some(11_000));
+                assert_eq!(details.invoice.bolt11, bolt11);
+                assert_eq!(
+                    details.payment_hash,
+                    "2044f2c86d384a58c27274f7a3e037aa56e0a95fcc6a66d4674ae01742e6bee7"
+                );
+            }
+            other => panic!("Expected Bolt11Invoice, got: {other:?}"),
+        }
+    }
+}
+
+#[macros::test_all]
+fn test_decode_payment_request_spark_invoice() {
+    let invoice = "sparkrt1pgss8cf4gru7ece2ryn8ym3vm3yz8leeend2589m7svq2mgv0xncfyx8zf8ssqgjzqqe5pmwfwyh9u4u6wgrepzk7j6j5prdv4kk7v3pqdur4y4c5nlcyr7lksm4mhrhdzakas9yt8gz4levtnfe49sgkqknywstpzxd8hk8qcgvp7x22q3qxz8gqudyp7rmuglc2axjqnlzz7d047gndmxff6ud02fvdgasdsq2en2aah6g52rq4qq7peler4s4d85s7prhm6sqzqj7gvc9nlzucy4yfh206fyqpk9zez";
+
+    match decode_payment_request(invoice) {
+        Ok(DecodedPaymentRequest::SparkInvoice(details)) => {
+            assert_eq!(details.amount, Some(1000));
+            assert_eq!(details.expiry_time, Some(1_761_061_260));
+            assert_eq!(details.description, Some("memo".to_string()));
+            assert_eq!(details.token_identifier, None);
+        }
+        other => panic!("Expected SparkInvoice, got: {other:?}"),
+    }
+}
+
+#[macros::test_all]
+fn test_decode_payment_request_rejects_other_inputs() {
+    assert!(matches!(
+        decode_payment_request("  "),
+        Err(ParseError::EmptyInput)
+    ));
+    // Inputs that would need network resolution, and plain addresses, are not decoded
+    for input in [
+        "user@domain.com",
+        "1andreas3batLhQa2FawWjeyjCqyBzypd",
+        "sparkrt1pgssyuuuhnrrdjswal5c3s3rafw9w3y5dd4cjy3duxlf7hjzkp0rqx6dc0nltx",
+    ] {
+        assert!(
+            matches!(decode_payment_request(input), Err(ParseError::InvalidInput)),
+            "{input} should not decode"
+        );
+    }
+}
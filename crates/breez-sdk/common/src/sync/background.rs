@@ -1,5 +1,6 @@
 use platform_utils::time::SystemTime;
 use platform_utils::tokio;
+use serde_json::Value;
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -9,7 +10,7 @@ use tokio::sync::{Mutex, broadcast, mpsc, watch};
 use tracing::{debug, error, warn};
 
 use crate::sync::{
-    model::{IncomingChange, OutgoingChange, RecordId},
+    model::{IncomingChange, OutgoingChange, RecordConflict, RecordId, UnversionedRecordChange},
     signing_client::SigningClient,
     storage::SyncStorage,
 };
@@ -28,6 +29,16 @@ pub trait NewRecordHandler: Send + Sync {
     async fn on_sync_failed(&self);
 }
 
+/// Resolves records changed both locally and remotely.
+///
+/// Without a resolver, the pending local fields are laid over the remote state.
+#[cfg_attr(test, mockall::automock)]
+#[macros::async_trait]
+pub trait ConflictResolver: Send + Sync {
+    /// Returns the resolved fields. Fields left out keep their remote value.
+    async fn resolve(&self, conflict: RecordConflict) -> anyhow::Result<HashMap<String, Value>>;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[must_use]
 pub enum RecordOutcome {
@@ -59,6 +70,7 @@ pub struct SyncProcessor {
     new_record_handler: Arc<dyn NewRecordHandler>,
     client: SigningClient,
     storage: Arc<dyn SyncStorage>,
+    conflict_resolver: Option<Arc<dyn ConflictResolver>>,
 }
 
 impl SyncProcessor {
@@ -73,9 +85,16 @@ impl SyncProcessor {
             new_record_handler,
             client,
             storage,
+            conflict_resolver: None,
         }
     }
 
+    #[must_use]
+    pub fn with_conflict_resolver(mut self, conflict_resolver: Arc<dyn ConflictResolver>) -> Self {
+        self.conflict_resolver = Some(conflict_resolver);
+        self
+    }
+
     pub async fn start(
         self: &Arc<Self>,
         shutdown_receiver: watch::Receiver<()>,
@@ -596,14 +615,23 @@ impl SyncProcessor {
             // true combined state.
             let mut merged_incoming: crate::sync::model::IncomingChange =
                 (&incoming_record).try_into()?;
+            let mut resolution = None;
             if let Some(outgoing_changes) = outgoing_by_record.get(&incoming_record.new_state.id) {
+                let mut local = HashMap::new();
                 for outgoing_change in outgoing_changes {
                     for (k, v) in &outgoing_change.updated_fields {
                         if let Ok(value) = serde_json::from_str(v) {
-                            merged_incoming.new_state.data.insert(k.clone(), value);
+                            local.insert(k.clone(), value);
                         }
                     }
                 }
+                let remote = merged_incoming.new_state.data.clone();
+                merged_incoming.new_state.data.extend(local.clone());
+                if let Some(resolver) = &self.conflict_resolver {
+                    resolution =
+                        resolve_conflict(resolver.as_ref(), &mut merged_incoming, local, remote)
+                            .await;
+                }
             }
 
             debug!(
@@ -616,6 +644,10 @@ impl SyncProcessor {
                 .await
             {
                 Ok(RecordOutcome::Completed) => {
+                    // Push the fields the resolver changed, so other devices converge
+                    if let Some(change) = resolution {
+                        self.storage.add_outgoing_change(change.try_into()?).await?;
+                    }
                     debug!(
                         "Removing incoming record after processing completion {:?}, revision {}",
                         incoming_record.new_state.id, incoming_revision
@@ -649,6 +681,57 @@ impl SyncProcessor {
     }
 }
 
+/// Applies the resolver outcome to the merged record. Returns the fields that
+/// differ from what the pending changes will push, if any.
+async fn resolve_conflict(
+    resolver: &dyn ConflictResolver,
+    merged: &mut IncomingChange,
+    local: HashMap<String, Value>,
+    remote: HashMap<String, Value>,
+) -> Option<UnversionedRecordChange> {
+    let conflict = RecordConflict {
+        id: merged.new_state.id.clone(),
+        base: merged
+            .old_state
+            .as_ref()
+            .map(|state| state.data.clone())
+            .unwrap_or_default(),
+        local,
+        remote: remote.clone(),
+    };
+    let resolved = match resolver.resolve(conflict).await {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            warn!(
+                "Failed to resolve conflict for record {:?}, keeping merged fields: {e}",
+                merged.new_state.id
+            );
+            return None;
+        }
+    };
+
+    let mut data = remote;
+    data.extend(resolved);
+    // Fields only set locally are pushed by the pending changes regardless
+    for (k, v) in &merged.new_state.data {
+        data.entry(k.clone()).or_insert_with(|| v.clone());
+    }
+    let updated_fields: HashMap<String, Value> = data
+        .iter()
+        .filter(|(k, v)| merged.new_state.data.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    merged.new_state.data = data;
+    if updated_fields.is_empty() {
+        return None;
+    }
+    Some(UnversionedRecordChange {
+        id: merged.new_state.id.clone(),
+        schema_version: merged.new_state.schema_version.clone(),
+        updated_fields,
+    })
+}
+
 /// Used for sync backoff timer management
 struct BackoffHandle {
     started_at: SystemTime,
@@ -661,8 +744,8 @@ mod tests {
     use crate::sync::proto::SetRecordReply;
     use crate::sync::storage::{self, MockSyncStorage};
     use crate::sync::{
-        MockNewRecordHandler, MockSyncSigner, MockSyncerClient, RecordId, RecordOutcome,
-        SigningClient, SyncProcessor,
+        MockConflictResolver, MockNewRecordHandler, MockSyncSigner, MockSyncerClient, RecordId,
+        RecordOutcome, SigningClient, SyncProcessor,
     };
 
     use anyhow::anyhow;
@@ -2107,6 +2190,76 @@ mod tests {
         assert_eq!(result.unwrap(), 1);
     }
 
+    #[macros::async_test_all]
+    async fn test_pull_merge_conflict_resolver_overrides_and_pushes_resolution() {
+        let mut mock_storage = MockSyncStorage::new();
+
+        let mut incoming_data = HashMap::new();
+        incoming_data.insert("name".to_string(), "\"Alice\"".to_string());
+        let incoming_record = crate::sync::storage::IncomingChange {
+            new_state: create_record_with_data("Contact", "c1", 5, incoming_data),
+            old_state: None,
+        };
+
+        let mut outgoing_fields = HashMap::new();
+        outgoing_fields.insert("name".to_string(), "\"Bob\"".to_string());
+        let outgoing = create_outgoing_change_with_fields("Contact", "c1", 1, outgoing_fields);
+
+        mock_storage
+            .expect_get_incoming_records()
+            .returning(move |_| Ok(vec![incoming_record.clone()]));
+        mock_storage
+            .expect_get_pending_outgoing_changes()
+            .returning(move |_| Ok(vec![outgoing.clone()]));
+        mock_storage
+            .expect_update_record_from_incoming()
+            .returning(|_| Ok(()));
+        mock_storage
+            .expect_add_outgoing_change()
+            .times(1)
+            .withf(|change| {
+                change.updated_fields.get("name") == Some(&"\"Carol\"".to_string())
+                    && change.updated_fields.len() == 1
+            })
+            .returning(|_| Ok(2));
+        mock_storage
+            .expect_delete_incoming_record()
+            .returning(|_| Ok(()));
+
+        let mut mock_resolver = MockConflictResolver::new();
+        mock_resolver
+            .expect_resolve()
+            .times(1)
+            .withf(|conflict| {
+                conflict.local.get("name") == Some(&serde_json::json!("Bob"))
+                    && conflict.remote.get("name") == Some(&serde_json::json!("Alice"))
+            })
+            .returning(|_| {
+                Ok(HashMap::from([(
+                    "name".to_string(),
+                    serde_json::json!("Carol"),
+                )]))
+            });
+
+        let mut mock_handler = MockNewRecordHandler::new();
+        mock_handler
+            .expect_on_incoming_change()
+            .times(1)
+            .withf(|change| change.new_state.data.get("name") == Some(&serde_json::json!("Carol")))
+            .returning(|_| Ok(RecordOutcome::Completed));
+
+        let sync_processor = SyncProcessor::new(
+            create_signing_client(MockSyncerClient::new(), MockSyncSigner::new()),
+            broadcast::channel(10).1,
+            Arc::new(mock_handler),
+            Arc::new(mock_storage),
+        )
+        .with_conflict_resolver(Arc::new(mock_resolver));
+
+        let result = sync_processor.pull_sync_once_local().await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
     #[macros::async_test_all]
     async fn test_pull_merge_multiple_pending_outgoing_applied_in_order() {
         let mut mock_storage = MockSyncStorage::new();
@@ -1,4 +1,8 @@
+use std::sync::Arc;
+
 use anyhow::{Result, anyhow};
+use platform_utils::ByteCounter;
+use prost::Message;
 
 use tonic::{
     Request, Status, Streaming,
@@ -28,6 +32,7 @@ pub trait SyncerClient: Send + Sync {
 pub struct BreezSyncerClient {
     #[allow(unused)]
     client: ProtoSyncerClient<InterceptedService<Transport, ApiKeyInterceptor>>,
+    byte_counter: Option<Arc<ByteCounter>>,
 }
 
 impl BreezSyncerClient {
@@ -46,30 +51,61 @@ impl BreezSyncerClient {
             GrpcClient::new(server_url, user_agent)?.into_inner(),
             ApiKeyInterceptor { api_key_metadata },
         );
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            byte_counter: None,
+        })
+    }
+
+    /// Counts the encoded size of the requests and replies. Notifications
+    /// streamed by `listen_changes` aren't counted.
+    #[must_use]
+    pub fn with_byte_counter(mut self, byte_counter: Arc<ByteCounter>) -> Self {
+        self.byte_counter = Some(byte_counter);
+        self
+    }
+
+    fn record(&self, sent: usize, received: usize) {
+        if let Some(counter) = &self.byte_counter {
+            counter.add_sent(sent as u64);
+            counter.add_received(received as u64);
+        }
     }
 }
 
 #[macros::async_trait]
 impl SyncerClient for BreezSyncerClient {
     async fn set_record(&self, req: SetRecordRequest) -> Result<SetRecordReply> {
-        Ok(self.client.clone().set_record(req).await?.into_inner())
+        let sent = req.encoded_len();
+        let reply = self.client.clone().set_record(req).await?.into_inner();
+        self.record(sent, reply.encoded_len());
+        Ok(reply)
     }
 
     async fn list_changes(&self, req: ListChangesRequest) -> Result<ListChangesReply> {
-        Ok(self.client.clone().list_changes(req).await?.into_inner())
+        let sent = req.encoded_len();
+        let reply = self.client.clone().list_changes(req).await?.into_inner();
+        self.record(sent, reply.encoded_len());
+        Ok(reply)
     }
 
     async fn listen_changes(&self, req: ListenChangesRequest) -> Result<Streaming<Notification>> {
+        self.record(req.encoded_len(), 0);
         Ok(self.client.clone().listen_changes(req).await?.into_inner())
     }
 
     async fn set_lock(&self, req: SetLockRequest) -> Result<SetLockReply> {
-        Ok(self.client.clone().set_lock(req).await?.into_inner())
+        let sent = req.encoded_len();
+        let reply = self.client.clone().set_lock(req).await?.into_inner();
+        self.record(sent, reply.encoded_len());
+        Ok(reply)
     }
 
     async fn get_lock(&self, req: GetLockRequest) -> Result<GetLockReply> {
-        Ok(self.client.clone().get_lock(req).await?.into_inner())
+        let sent = req.encoded_len();
+        let reply = self.client.clone().get_lock(req).await?.into_inner();
+        self.record(sent, reply.encoded_len());
+        Ok(reply)
     }
 }
 
@@ -159,6 +159,17 @@ pub struct IncomingChange {
     // pub pending_outgoing_changes: Vec<RecordChange>,
 }
 
+/// A record changed remotely while local changes to it were not pushed yet.
+pub struct RecordConflict {
+    pub id: RecordId,
+    /// The last synced state, which both sides changed.
+    pub base: HashMap<String, Value>,
+    /// The fields changed by the pending local changes.
+    pub local: HashMap<String, Value>,
+    /// The incoming remote state.
+    pub remote: HashMap<String, Value>,
+}
+
 pub struct RecordChange {
     pub id: RecordId,
     pub schema_version: SchemaVersion,
@@ -0,0 +1,405 @@
+//! Encrypted backups of the data the SDK can't re-derive from the operators:
+//! payments with their LNURL metadata, deposits with their refunds, contacts
+//! and a few cached items.
+//!
+//! Backups are built through the [`Storage`] trait, so they work with every
+//! storage backend, and are ECIES-encrypted under the
+//! [`ChildSecretPurpose::BackupEncryptionKey`] child secret of the wallet
+//! seed. Only the same wallet can decrypt them.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use bitcoin::secp256k1::{Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    ChildSecretPurpose, Contact, DepositInfo, ListContactsRequest, Payment, PaymentDetails,
+    PaymentMetadata, RestoreResponse, SetLnurlMetadataItem, Storage, StorageListPaymentsRequest,
+    error::SdkError,
+    persist::{
+        BACKUP_CACHE_KEYS, ObjectCacheRepository, PaymentCursor, UpdateDepositPayload,
+        deposit_backup_cache_keys, payment_backup_cache_keys,
+    },
+    sdk::derive_child_secret,
+    signer::HmacSigner,
+};
+
+/// Index of the backup encryption key child secret. Never change it: existing
+/// backups would become undecryptable.
+const BACKUP_ENCRYPTION_KEY_INDEX: u32 = 0;
+
+/// Version of the backup format, bumped on incompatible changes.
+const BACKUP_VERSION: u32 = 1;
+
+const PAYMENTS_PAGE_SIZE: u32 = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupData {
+    version: u32,
+    payments: Vec<Payment>,
+    deposits: Vec<DepositInfo>,
+    contacts: Vec<Contact>,
+    cached_items: BTreeMap<String, String>,
+}
+
+pub(crate) struct BackupService {
+    storage: Arc<dyn Storage>,
+    hmac: Arc<dyn HmacSigner>,
+}
+
+impl BackupService {
+    pub(crate) fn new(storage: Arc<dyn Storage>, hmac: Arc<dyn HmacSigner>) -> Self {
+        Self { storage, hmac }
+    }
+
+    /// Derives the backup encryption key from the wallet seed.
+    async fn encryption_key(&self) -> Result<SecretKey, SdkError> {
+        let child_secret = derive_child_secret(
+            self.hmac.as_ref(),
+            &ChildSecretPurpose::BackupEncryptionKey,
+            BACKUP_ENCRYPTION_KEY_INDEX,
+        )
+        .await?;
+        let secret = hex::decode(&child_secret.secret)
+            .map_err(|e| SdkError::Generic(format!("Invalid backup encryption key: {e}")))?;
+        SecretKey::from_slice(&secret)
+            .map_err(|e| SdkError::Generic(format!("Invalid backup encryption key: {e}")))
+    }
+
+    /// Serializes the backed up data. The snapshot is unencrypted.
+    pub(crate) async fn snapshot(&self) -> Result<Vec<u8>, SdkError> {
+        let mut payments = Vec::new();
+        let mut after = None;
+        loop {
+            let page = self
+                .storage
+                .list_payments(StorageListPaymentsRequest {
+                    limit: Some(PAYMENTS_PAGE_SIZE),
+                    sort_ascending: Some(true),
+                    after,
+                    ..Default::default()
+                })
+                .await?;
+            after = page.last().map(PaymentCursor::from_payment);
+            let full = page.len() >= PAYMENTS_PAGE_SIZE as usize;
+            payments.extend(page);
+            if !full {
+                break;
+            }
+        }
+        let deposits = self.storage.list_deposits().await?;
+        let contacts = self
+            .storage
+            .list_contacts(ListContactsRequest {
+                offset: None,
+                limit: None,
+            })
+            .await?;
+        let sponsorship_keys = ObjectCacheRepository::new(self.storage.clone())
+            .claim_fee_sponsorships_backup_cache_keys()
+            .await?;
+
+        let keys = BACKUP_CACHE_KEYS
+            .iter()
+            .map(ToString::to_string)
+            .chain(sponsorship_keys)
+            .chain(
+                payments
+                    .iter()
+                    .flat_map(|p| payment_backup_cache_keys(&p.id)),
+            )
+            .chain(
+                deposits
+                    .iter()
+                    .flat_map(|d| deposit_backup_cache_keys(&d.txid, d.vout)),
+            );
+        let mut cached_items = BTreeMap::new();
+        for key in keys {
+            if let Some(value) = self.storage.get_cached_item(key.clone()).await? {
+                cached_items.insert(key, value);
+            }
+        }
+
+        Ok(serde_json::to_vec(&BackupData {
+            version: BACKUP_VERSION,
+            payments,
+            deposits,
+            contacts,
+            cached_items,
+        })?)
+    }
+
+    pub(crate) async fn encrypt(&self, snapshot: &[u8]) -> Result<Vec<u8>, SdkError> {
+        let public_key = self
+            .encryption_key()
+            .await?
+            .public_key(&Secp256k1::signing_only());
+        utils::ecies::encrypt(&public_key.serialize(), snapshot)
+            .map_err(|e| SdkError::Generic(format!("Could not encrypt backup: {e}")))
+    }
+
+    /// Restores an encrypted backup into the storage.
+    ///
+    /// Payments don't replace a terminal stored status, and cached items
+    /// already in the storage are kept, so restoring an older backup doesn't
+    /// roll back newer data.
+    pub(crate) async fn restore(&self, backup: &[u8]) -> Result<RestoreResponse, SdkError> {
+        let key = self.encryption_key().await?;
+        let snapshot = utils::ecies::decrypt(&key.secret_bytes(), backup)
+            .map_err(|e| SdkError::InvalidInput(format!("Failed to decrypt backup: {e}")))?;
+        let data: BackupData = serde_json::from_slice(&snapshot)
+            .map_err(|e| SdkError::InvalidInput(format!("Invalid backup: {e}")))?;
+        if data.version > BACKUP_VERSION {
+            return Err(SdkError::InvalidInput(format!(
+                "Unsupported backup version {}",
+                data.version
+            )));
+        }
+
+        let response = RestoreResponse {
+            payments_count: u32::try_from(data.payments.len()).unwrap_or(u32::MAX),
+            deposits_count: u32::try_from(data.deposits.len()).unwrap_or(u32::MAX),
+            contacts_count: u32::try_from(data.contacts.len()).unwrap_or(u32::MAX),
+        };
+        let mut lnurl_metadata = Vec::new();
+        for payment in data.payments {
+            let id = payment.id.clone();
+            let metadata = payment_metadata(&payment);
+            lnurl_metadata.extend(lnurl_receive_metadata(&payment));
+            self.storage.apply_payment_update(payment).await?;
+            if let Some(metadata) = metadata {
+                self.storage.insert_payment_metadata(id, metadata).await?;
+            }
+        }
+        if !lnurl_metadata.is_empty() {
+            self.storage.set_lnurl_metadata(lnurl_metadata).await?;
+        }
+        for deposit in data.deposits {
+            self.restore_deposit(deposit).await?;
+        }
+        for contact in data.contacts {
+            self.storage.insert_contact(contact).await?;
+        }
+        for (key, value) in data.cached_items {
+            if self.storage.get_cached_item(key.clone()).await?.is_none() {
+                self.storage.set_cached_item(key, value).await?;
+            }
+        }
+        info!(
+            "Restored backup with {} payments, {} deposits and {} contacts",
+            response.payments_count, response.deposits_count, response.contacts_count
+        );
+        Ok(response)
+    }
+
+    async fn restore_deposit(&self, deposit: DepositInfo) -> Result<(), SdkError> {
+        self.storage
+            .add_deposit(
+                deposit.txid.clone(),
+                deposit.vout,
+                deposit.amount_sats,
+                deposit.is_mature,
+            )
+            .await?;
+        if let (Some(refund_txid), Some(refund_tx)) = (deposit.refund_tx_id, deposit.refund_tx) {
+            self.storage
+                .update_deposit(
+                    deposit.txid.clone(),
+                    deposit.vout,
+                    UpdateDepositPayload::Refund {
+                        refund_txid,
+                        refund_tx,
+                    },
+                )
+                .await?;
+        }
+        if let Some(error) = deposit.claim_error {
+            self.storage
+                .update_deposit(
+                    deposit.txid,
+                    deposit.vout,
+                    UpdateDepositPayload::ClaimError { error },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// The metadata of a payment that isn't stored with the payment itself.
+fn payment_metadata(payment: &Payment) -> Option<PaymentMetadata> {
+    let (lnurl_description, lnurl_pay_info, lnurl_withdraw_info, conversion_info) =
+        match payment.details.clone()? {
+            PaymentDetails::Lightning {
+                description,
+                lnurl_pay_info,
+                lnurl_withdraw_info,
+                conversion_info,
+                ..
+            } => (
+                description,
+                lnurl_pay_info,
+                lnurl_withdraw_info,
+                conversion_info,
+            ),
+            PaymentDetails::Spark {
+                conversion_info, ..
+            }
+            | PaymentDetails::Token {
+                conversion_info, ..
+            } => (None, None, None, conversion_info),
+            PaymentDetails::Withdraw { .. } | PaymentDetails::Deposit { .. } => {
+                (None, None, None, None)
+            }
+        };
+    let conversion_status = payment
+        .conversion_details
+        .as_ref()
+        .map(|details| details.status.clone());
+    if lnurl_pay_info.is_none()
+        && lnurl_withdraw_info.is_none()
+        && conversion_info.is_none()
+        && conversion_status.is_none()
+        && payment.group_id.is_none()
+        && payment.dispute.is_none()
+    {
+        return None;
+    }
+    Some(PaymentMetadata {
+        lnurl_pay_info,
+        lnurl_withdraw_info,
+        lnurl_description,
+        conversion_info,
+        conversion_status,
+        group_id: payment.group_id.clone(),
+        dispute: payment.dispute.clone(),
+        ..Default::default()
+    })
+}
+
+fn lnurl_receive_metadata(payment: &Payment) -> Option<SetLnurlMetadataItem> {
+    let Some(PaymentDetails::Lightning {
+        htlc_details,
+        lnurl_receive_metadata: Some(metadata),
+        ..
+    }) = &payment.details
+    else {
+        return None;
+    };
+    Some(SetLnurlMetadataItem {
+        payment_hash: htlc_details.payment_hash.clone(),
+        sender_comment: metadata.sender_comment.clone(),
+        nostr_zap_request: metadata.nostr_zap_request.clone(),
+        nostr_zap_receipt: metadata.nostr_zap_receipt.clone(),
+    })
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use bitcoin::{Network, bip32::Xpriv};
+
+    use super::*;
+    use crate::{
+        DisplayAmount, PaymentMethod, PaymentStatus, PaymentType, persist::sqlite::SqliteStorage,
+        signer::breez::BreezSignerImpl,
+    };
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("breez-test-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn make_service(seed: u8) -> (BackupService, Arc<dyn Storage>) {
+        let dir = create_temp_dir("backup");
+        let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::new(&dir).unwrap());
+        let master = Xpriv::new_master(Network::Regtest, &[seed; 32]).unwrap();
+        let hmac: Arc<dyn HmacSigner> = Arc::new(BreezSignerImpl::new(master));
+        (BackupService::new(Arc::clone(&storage), hmac), storage)
+    }
+
+    fn payment(id: &str) -> Payment {
+        Payment {
+            id: id.to_string(),
+            payment_type: PaymentType::Send,
+            status: PaymentStatus::Completed,
+            amount: 1000,
+            fees: 0,
+            timestamp: 1_700_000_000,
+            method: PaymentMethod::Withdraw,
+            details: Some(PaymentDetails::Withdraw {
+                tx_id: format!("tx-{id}"),
+            }),
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backup_restore_roundtrip() {
+        let (source, source_storage) = make_service(1);
+        source_storage
+            .apply_payment_update(payment("p1"))
+            .await
+            .unwrap();
+        source_storage
+            .add_deposit("txid".to_string(), 0, 5000, true)
+            .await
+            .unwrap();
+        source_storage
+            .set_cached_item(BACKUP_CACHE_KEYS[0].to_string(), "[]".to_string())
+            .await
+            .unwrap();
+        let backup = source
+            .encrypt(&source.snapshot().await.unwrap())
+            .await
+            .unwrap();
+
+        // A fresh storage of the same wallet
+        let (target, target_storage) = make_service(1);
+        let response = target.restore(&backup).await.unwrap();
+        assert_eq!(response.payments_count, 1);
+        assert_eq!(response.deposits_count, 1);
+        assert_eq!(response.contacts_count, 0);
+
+        let restored = target_storage
+            .get_payment_by_id("p1".to_string())
+            .await
+            .unwrap();
+        assert!(matches!(
+            restored.details,
+            Some(PaymentDetails::Withdraw { tx_id }) if tx_id == "tx-p1"
+        ));
+        assert_eq!(target_storage.list_deposits().await.unwrap().len(), 1);
+        assert_eq!(
+            target_storage
+                .get_cached_item(BACKUP_CACHE_KEYS[0].to_string())
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("[]")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_other_wallet_backup() {
+        let (source, _) = make_service(1);
+        let backup = source
+            .encrypt(&source.snapshot().await.unwrap())
+            .await
+            .unwrap();
+
+        let (other, _) = make_service(2);
+        assert!(matches!(
+            other.restore(&backup).await,
+            Err(SdkError::InvalidInput(_))
+        ));
+    }
+}
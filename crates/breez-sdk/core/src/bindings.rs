@@ -3,9 +3,11 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::{
-    BitcoinChainService, BreezSdk, Config, Credentials, FiatService, PaymentObserver, RestClient,
-    SdkContext, SdkError, Seed, SessionStore, Storage, StorageBackend,
-    chain::rest_client::ChainApiType,
+    BackupStore, BitcoinChainService, BreezSdk, ChainServiceEndpoint, ClaimFeeSponsor, Config,
+    Credentials, EmergencyExitStore, ExportEmergencyExitPackageRequest, FiatService,
+    PaymentApprover, PaymentObserver, Plugin, RestClient, SdkContext, SdkError, SecretStore, Seed,
+    SessionStore, Storage, StorageBackend, TravelRuleProvider, ViewerBundle,
+    chain::rest_client::ChainApiType, signer::CpfpSigner,
 };
 
 /// Builder for creating `BreezSdk` instances with customizable components.
@@ -77,6 +79,35 @@ impl SdkBuilder {
         }
     }
 
+    /// Creates a new `SdkBuilder` for a watch-only wallet, which holds no keys.
+    ///
+    /// Operations that need a signature fail with `SdkError::WatchOnly`, and
+    /// the session store must hold valid sessions for the wallet.
+    /// Arguments:
+    /// - `config`: The configuration to be used.
+    /// - `identity_public_key`: The hex-encoded identity public key of the wallet.
+    #[cfg_attr(feature = "uniffi", uniffi::constructor)]
+    pub fn new_watch_only(config: Config, identity_public_key: String) -> Self {
+        let inner = crate::sdk_builder::SdkBuilder::new_watch_only(config, identity_public_key);
+        SdkBuilder {
+            inner: Mutex::new(inner),
+        }
+    }
+
+    /// Creates a new `SdkBuilder` for a watch-only wallet viewing the wallet
+    /// of a bundle from `export_viewer_bundle`. The session store must hold
+    /// valid sessions for the wallet.
+    /// Arguments:
+    /// - `config`: The configuration to be used.
+    /// - `bundle`: The viewer bundle exported by the wallet.
+    #[cfg_attr(feature = "uniffi", uniffi::constructor)]
+    pub fn new_viewer(config: Config, bundle: ViewerBundle) -> Self {
+        let inner = crate::sdk_builder::SdkBuilder::new_viewer(config, bundle);
+        SdkBuilder {
+            inner: Mutex::new(inner),
+        }
+    }
+
     /// Sets the root storage directory to initialize the default storage with.
     /// This initializes both storage and real-time sync storage with the
     /// default implementations.
@@ -114,6 +145,17 @@ impl SdkBuilder {
         *builder = builder.clone().with_session_store(session_store);
     }
 
+    /// Sets the store for secret material, such as the Breez partner JWT,
+    /// replacing the main storage. Use it to keep secrets in the iOS Keychain
+    /// or the Android Keystore. Secrets still in the main storage are moved
+    /// into it when the SDK is built.
+    /// Arguments:
+    /// - `secret_store`: The secret store to be used.
+    pub async fn with_secret_store(&self, secret_store: Arc<dyn SecretStore>) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_secret_store(secret_store);
+    }
+
     /// **Deprecated.** Use
     /// [`with_storage_backend`](SdkBuilder::with_storage_backend) with
     /// [`custom_storage`](crate::custom_storage).
@@ -161,6 +203,15 @@ impl SdkBuilder {
             .with_rest_chain_service(url, api_type, credentials);
     }
 
+    /// Configures REST chain services to be used by the SDK, in priority order.
+    /// Requests fail over to the next endpoint while one is unreachable.
+    /// Arguments:
+    /// - `endpoints`: The REST API endpoints, highest priority first.
+    pub async fn with_rest_chain_service_endpoints(&self, endpoints: Vec<ChainServiceEndpoint>) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_rest_chain_service_endpoints(endpoints);
+    }
+
     /// Sets the fiat service to be used by the SDK.
     /// Arguments:
     /// - `fiat_service`: The fiat service to be used.
@@ -182,6 +233,91 @@ impl SdkBuilder {
         *builder = builder.clone().with_payment_observer(payment_observer);
     }
 
+    /// Sets the payment approver to be used by the SDK.
+    /// Arguments:
+    /// - `payment_approver`: The payment approver to be used.
+    pub async fn with_payment_approver(&self, payment_approver: Arc<dyn PaymentApprover>) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_payment_approver(payment_approver);
+    }
+
+    /// Sets the sponsor paying the claim fees of deposits instead of the user.
+    /// Arguments:
+    /// - `claim_fee_sponsor`: The claim fee sponsor to be used.
+    pub async fn with_claim_fee_sponsor(&self, claim_fee_sponsor: Arc<dyn ClaimFeeSponsor>) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_claim_fee_sponsor(claim_fee_sponsor);
+    }
+
+    /// Sets the store the SDK periodically saves encrypted backups to.
+    /// Arguments:
+    /// - `backup_store`: The backup store to be used.
+    pub async fn with_backup_store(&self, backup_store: Arc<dyn BackupStore>) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_backup_store(backup_store);
+    }
+
+    /// Keeps an emergency exit package up to date, saving a new one to `store`
+    /// whenever the leaves worth exiting change.
+    /// Arguments:
+    /// - `request`: The funding UTXO, fee rate and destination of the package.
+    /// - `signer`: The signer of the funding UTXO.
+    /// - `store`: The store the packages are saved to.
+    pub async fn with_emergency_exit_package(
+        &self,
+        request: ExportEmergencyExitPackageRequest,
+        signer: Arc<dyn CpfpSigner>,
+        store: Arc<dyn EmergencyExitStore>,
+    ) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder
+            .clone()
+            .with_emergency_exit_package(request, signer, store);
+    }
+
+    /// Sets the travel-rule provider to be used by the SDK.
+    /// Arguments:
+    /// - `travel_rule_provider`: The travel-rule provider to be used.
+    pub async fn with_travel_rule_provider(
+        &self,
+        travel_rule_provider: Arc<dyn TravelRuleProvider>,
+    ) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder
+            .clone()
+            .with_travel_rule_provider(travel_rule_provider);
+    }
+
+    /// Scopes the wallet's data to a namespace within the storage backend.
+    /// Arguments:
+    /// - `namespace`: A non-empty namespace name.
+    pub async fn with_storage_namespace(&self, namespace: String) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_storage_namespace(namespace);
+    }
+
+    /// Accepts an incompatible change to the network or the signing operator
+    /// pool for an existing storage, instead of failing with `ConfigMismatch`.
+    pub async fn with_config_change_accepted(&self) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_config_change_accepted();
+    }
+
+    /// Builds a headless SDK that only processes push notifications, without
+    /// background services or a sync on startup.
+    pub async fn with_headless_mode(&self) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_headless_mode();
+    }
+
+    /// Registers a plugin to be started with the SDK.
+    /// Arguments:
+    /// - `plugin`: The plugin to be registered. Its id must be unique.
+    pub async fn with_plugin(&self, plugin: Arc<dyn Plugin>) {
+        let mut builder = self.inner.lock().await;
+        *builder = builder.clone().with_plugin(plugin);
+    }
+
     /// Threads a shared [`SdkContext`](crate::SdkContext) into the builder.
     ///
     /// Construct the context once via
@@ -0,0 +1,316 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use platform_utils::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::{
+    chain::{BitcoinChainService, ChainServiceError, Outspend, RecommendedFees, TxStatus, Utxo},
+    events::{EventEmitter, SdkEvent},
+};
+
+/// How long an unreachable endpoint is only tried after the others.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(60);
+
+pub(crate) struct ChainEndpoint {
+    pub(crate) url: String,
+    pub(crate) service: Arc<dyn BitcoinChainService>,
+}
+
+struct FailoverState {
+    /// The index of the endpoint that served the last request
+    active: usize,
+    /// When each endpoint was found unreachable
+    unhealthy_since: Vec<Option<Instant>>,
+}
+
+/// A [`BitcoinChainService`] over a prioritized list of endpoints.
+///
+/// Requests go to the first endpoint that isn't known to be unreachable. When
+/// a request fails with a connectivity error, the endpoint is health checked
+/// by fetching the recommended fees. If the check fails too, the endpoint is
+/// tried last for a while and the request is retried on the next one.
+pub(crate) struct FailoverChainService {
+    endpoints: Vec<ChainEndpoint>,
+    state: Mutex<FailoverState>,
+    event_emitter: OnceLock<Arc<EventEmitter>>,
+}
+
+impl FailoverChainService {
+    pub(crate) fn new(endpoints: Vec<ChainEndpoint>) -> Self {
+        let unhealthy_since = vec![None; endpoints.len()];
+        Self {
+            endpoints,
+            state: Mutex::new(FailoverState {
+                active: 0,
+                unhealthy_since,
+            }),
+            event_emitter: OnceLock::new(),
+        }
+    }
+
+    /// Sets the emitter of the `ChainServiceFailover` events.
+    pub(crate) fn set_event_emitter(&self, event_emitter: Arc<EventEmitter>) {
+        let _ = self.event_emitter.set(event_emitter);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FailoverState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// The endpoints in the order they are tried: healthy ones by priority,
+    /// then the unreachable ones by priority.
+    fn candidates(&self) -> Vec<usize> {
+        let state = self.lock();
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) = (0..self.endpoints.len())
+            .partition(|&i| {
+                state.unhealthy_since[i].is_none_or(|since| since.elapsed() >= UNHEALTHY_COOLDOWN)
+            });
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    fn mark_unhealthy(&self, index: usize) {
+        self.lock().unhealthy_since[index] = Some(Instant::now());
+    }
+
+    /// Records that `index` served a request, emitting an event if requests
+    /// were served by another endpoint before.
+    async fn mark_served(&self, index: usize) {
+        let previous = {
+            let mut state = self.lock();
+            state.unhealthy_since[index] = None;
+            std::mem::replace(&mut state.active, index)
+        };
+        if previous == index {
+            return;
+        }
+        let from_url = self.endpoints[previous].url.clone();
+        let to_url = self.endpoints[index].url.clone();
+        info!("Chain service switched from {from_url} to {to_url}");
+        if let Some(event_emitter) = self.event_emitter.get() {
+            event_emitter
+                .emit(&SdkEvent::ChainServiceFailover { from_url, to_url })
+                .await;
+        }
+    }
+
+    async fn call<T, F, Fut>(&self, f: F) -> Result<T, ChainServiceError>
+    where
+        F: Fn(Arc<dyn BitcoinChainService>) -> Fut,
+        Fut: Future<Output = Result<T, ChainServiceError>>,
+    {
+        let mut last_error = None;
+        for index in self.candidates() {
+            let service = Arc::clone(&self.endpoints[index].service);
+            match f(Arc::clone(&service)).await {
+                Ok(value) => {
+                    self.mark_served(index).await;
+                    return Ok(value);
+                }
+                Err(e @ ChainServiceError::ServiceConnectivity(_)) => {
+                    // A reachable endpoint failed the request itself, e.g. an
+                    // unknown transaction. Other endpoints would fail it too.
+                    if service.recommended_fees().await.is_ok() {
+                        self.mark_served(index).await;
+                        return Err(e);
+                    }
+                    warn!(
+                        "Chain service endpoint {} is unreachable: {e}",
+                        self.endpoints[index].url
+                    );
+                    self.mark_unhealthy(index);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            ChainServiceError::Generic("No chain service endpoints".to_string())
+        }))
+    }
+}
+
+#[macros::async_trait]
+impl BitcoinChainService for FailoverChainService {
+    async fn get_address_utxos(&self, address: String) -> Result<Vec<Utxo>, ChainServiceError> {
+        self.call(|s| {
+            let address = address.clone();
+            async move { s.get_address_utxos(address).await }
+        })
+        .await
+    }
+
+    async fn get_address_txos(&self, address: String) -> Result<Vec<Utxo>, ChainServiceError> {
+        self.call(|s| {
+            let address = address.clone();
+            async move { s.get_address_txos(address).await }
+        })
+        .await
+    }
+
+    async fn get_transaction_status(&self, txid: String) -> Result<TxStatus, ChainServiceError> {
+        self.call(|s| {
+            let txid = txid.clone();
+            async move { s.get_transaction_status(txid).await }
+        })
+        .await
+    }
+
+    async fn get_transaction_hex(&self, txid: String) -> Result<String, ChainServiceError> {
+        self.call(|s| {
+            let txid = txid.clone();
+            async move { s.get_transaction_hex(txid).await }
+        })
+        .await
+    }
+
+    async fn get_outspend(&self, txid: String, vout: u32) -> Result<Outspend, ChainServiceError> {
+        self.call(|s| {
+            let txid = txid.clone();
+            async move { s.get_outspend(txid, vout).await }
+        })
+        .await
+    }
+
+    async fn broadcast_transaction(&self, tx: String) -> Result<(), ChainServiceError> {
+        self.call(|s| {
+            let tx = tx.clone();
+            async move { s.broadcast_transaction(tx).await }
+        })
+        .await
+    }
+
+    async fn recommended_fees(&self) -> Result<RecommendedFees, ChainServiceError> {
+        self.call(|s| async move { s.recommended_fees().await })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use macros::async_test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Fails every request while down. The transaction `missing` is unknown.
+    struct MockChainService {
+        up: AtomicBool,
+    }
+
+    impl MockChainService {
+        fn new(up: bool) -> Arc<Self> {
+            Arc::new(Self {
+                up: AtomicBool::new(up),
+            })
+        }
+
+        fn check(&self) -> Result<(), ChainServiceError> {
+            if self.up.load(Ordering::SeqCst) {
+                Ok(())
+            } else {
+                Err(ChainServiceError::ServiceConnectivity(
+                    "connection refused".to_string(),
+                ))
+            }
+        }
+    }
+
+    #[macros::async_trait]
+    impl BitcoinChainService for MockChainService {
+        async fn get_address_utxos(&self, _: String) -> Result<Vec<Utxo>, ChainServiceError> {
+            self.check().map(|()| Vec::new())
+        }
+
+        async fn get_address_txos(&self, _: String) -> Result<Vec<Utxo>, ChainServiceError> {
+            self.check().map(|()| Vec::new())
+        }
+
+        async fn get_transaction_status(&self, _: String) -> Result<TxStatus, ChainServiceError> {
+            Err(ChainServiceError::Generic("not implemented".to_string()))
+        }
+
+        async fn get_transaction_hex(&self, txid: String) -> Result<String, ChainServiceError> {
+            self.check()?;
+            if txid == "missing" {
+                return Err(ChainServiceError::ServiceConnectivity("404".to_string()));
+            }
+            Ok("00".to_string())
+        }
+
+        async fn get_outspend(&self, _: String, _: u32) -> Result<Outspend, ChainServiceError> {
+            self.check().map(|()| Outspend::Unspent)
+        }
+
+        async fn broadcast_transaction(&self, _: String) -> Result<(), ChainServiceError> {
+            self.check()
+        }
+
+        async fn recommended_fees(&self) -> Result<RecommendedFees, ChainServiceError> {
+            self.check().map(|()| RecommendedFees {
+                fastest_fee: 1,
+                half_hour_fee: 1,
+                hour_fee: 1,
+                economy_fee: 1,
+                minimum_fee: 1,
+            })
+        }
+    }
+
+    fn failover(
+        primary: &Arc<MockChainService>,
+        backup: &Arc<MockChainService>,
+    ) -> FailoverChainService {
+        FailoverChainService::new(vec![
+            ChainEndpoint {
+                url: "https://primary".to_string(),
+                service: Arc::clone(primary) as Arc<dyn BitcoinChainService>,
+            },
+            ChainEndpoint {
+                url: "https://backup".to_string(),
+                service: Arc::clone(backup) as Arc<dyn BitcoinChainService>,
+            },
+        ])
+    }
+
+    #[async_test_all]
+    async fn test_fails_over_to_next_endpoint() {
+        let primary = MockChainService::new(false);
+        let backup = MockChainService::new(true);
+        let service = failover(&primary, &backup);
+
+        assert!(service.get_address_utxos("addr".to_string()).await.is_ok());
+        assert_eq!(service.lock().active, 1);
+        assert_eq!(service.candidates(), vec![1, 0]);
+
+        // Both down: the error of the last endpoint tried is returned
+        backup.up.store(false, Ordering::SeqCst);
+        assert!(matches!(
+            service.broadcast_transaction("tx".to_string()).await,
+            Err(ChainServiceError::ServiceConnectivity(_))
+        ));
+    }
+
+    #[async_test_all]
+    async fn test_request_errors_of_reachable_endpoint_are_returned() {
+        let primary = MockChainService::new(true);
+        let backup = MockChainService::new(true);
+        let service = failover(&primary, &backup);
+
+        assert!(
+            service
+                .get_transaction_hex("missing".to_string())
+                .await
+                .is_err()
+        );
+        assert_eq!(service.lock().active, 0);
+        assert_eq!(service.candidates(), vec![0, 1]);
+    }
+}
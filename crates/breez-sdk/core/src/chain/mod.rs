@@ -9,6 +9,7 @@ use crate::{
     chain::rest_client::{BasicAuth, ChainApiType, RestClientChainService},
 };
 
+pub(crate) mod failover;
 pub mod rest_client;
 
 #[derive(Debug, Error, Clone)]
@@ -50,6 +51,17 @@ pub trait BitcoinChainService: Send + Sync {
     async fn recommended_fees(&self) -> Result<RecommendedFees, ChainServiceError>;
 }
 
+/// A REST chain API endpoint
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ChainServiceEndpoint {
+    /// The base URL of the REST API
+    pub url: String,
+    pub api_type: ChainApiType,
+    /// Optional credentials for basic authentication
+    pub credentials: Option<Credentials>,
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct TxStatus {
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use platform_utils::time::SystemTime;
+
+/// Source of wall-clock time for time-driven SDK behaviour, such as invoice expiry.
+///
+/// The SDK uses the system clock. Tests can replace it through
+/// `SdkBuilder::with_clock` (`test-utils` feature) to fast-forward expiries.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// The system wall clock
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+pub(crate) fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+/// A clock that runs with the system clock but can be moved forward.
+#[cfg(feature = "test-utils")]
+#[derive(Default)]
+pub struct TestClock {
+    offset: std::sync::Mutex<platform_utils::time::Duration>,
+}
+
+#[cfg(feature = "test-utils")]
+impl TestClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: platform_utils::time::Duration) {
+        let mut offset = self.offset.lock().unwrap_or_else(|e| e.into_inner());
+        *offset = offset.saturating_add(duration);
+    }
+}
+
+#[cfg(feature = "test-utils")]
+impl Clock for TestClock {
+    fn now(&self) -> SystemTime {
+        let offset = *self.offset.lock().unwrap_or_else(|e| e.into_inner());
+        let now = SystemTime::now();
+        now.checked_add(offset).unwrap_or(now)
+    }
+}
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use platform_utils::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_clock_advances() {
+        let clock = TestClock::new();
+        let before = clock.now();
+        clock.advance(Duration::from_secs(3600));
+        let after = clock.now();
+        assert!(after.duration_since(before).unwrap() >= Duration::from_secs(3600));
+    }
+}
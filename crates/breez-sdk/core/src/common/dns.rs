@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::ServiceConnectivityError;
+
+/// DNS resolver trait for looking up TXT records.
+///
+/// This trait provides a way for users to supply their own DNS resolution, e.g.
+/// DNS-over-HTTPS or resolution over Tor. The SDK uses it for BIP-353 lookups,
+/// including those of lightning addresses that resolve through BIP-353. The
+/// implementation is expected to validate the records with DNSSEC.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait DnsResolver: Send + Sync {
+    /// Returns the TXT records of `dns_name`
+    async fn txt_lookup(&self, dns_name: String) -> Result<Vec<String>, ServiceConnectivityError>;
+}
+
+/// Wrapper that adapts an external `DnsResolver` to `breez_sdk_common::dns::DnsResolver`
+pub(crate) struct DnsResolverWrapper {
+    inner: Arc<dyn DnsResolver>,
+}
+
+impl DnsResolverWrapper {
+    pub fn new(inner: Arc<dyn DnsResolver>) -> Self {
+        DnsResolverWrapper { inner }
+    }
+}
+
+#[macros::async_trait]
+impl breez_sdk_common::dns::DnsResolver for DnsResolverWrapper {
+    async fn txt_lookup(&self, dns_name: String) -> anyhow::Result<Vec<String>> {
+        Ok(self.inner.txt_lookup(dns_name).await?)
+    }
+}
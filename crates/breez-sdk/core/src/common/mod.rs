@@ -1,3 +1,4 @@
+pub mod dns;
 pub mod fiat;
 pub mod models;
 pub mod rest;
@@ -279,6 +279,15 @@ pub enum InputType {
     CrossChainAddress(CrossChainAddressDetails),
 }
 
+/// A payment request decoded offline by `decode_payment_request`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[macros::derive_from(breez_sdk_common::input::DecodedPaymentRequest)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DecodedPaymentRequest {
+    Bolt11Invoice(Bolt11InvoiceDetails),
+    SparkInvoice(SparkInvoiceDetails),
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[macros::derive_from(breez_sdk_common::input::CrossChainAddressFamily)]
 #[macros::derive_into(breez_sdk_common::input::CrossChainAddressFamily)]
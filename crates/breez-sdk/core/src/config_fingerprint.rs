@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use spark_wallet::SparkWalletConfig;
+use tracing::warn;
+
+use crate::{Network, SdkError, Storage, persist::ObjectCacheRepository};
+
+/// The parts of the configuration that the data in a storage is bound to.
+///
+/// Payments, leaves and deposits stored for one network or operator pool are
+/// meaningless to another, so a storage must not be reused across them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ConfigFingerprint {
+    network: String,
+    /// Hex-encoded identity public keys of the signing operators, sorted.
+    operator_identity_public_keys: Vec<String>,
+}
+
+impl ConfigFingerprint {
+    pub(crate) fn new(network: Network, spark_wallet_config: &SparkWalletConfig) -> Self {
+        let mut operator_identity_public_keys: Vec<String> = spark_wallet_config
+            .operator_pool
+            .get_all_operators()
+            .map(|op| op.identity_public_key.to_string())
+            .collect();
+        operator_identity_public_keys.sort();
+        Self {
+            network: network.to_string(),
+            operator_identity_public_keys,
+        }
+    }
+
+    /// Human-readable description of what changed from `self` to `current`.
+    fn describe_changes(&self, current: &Self) -> String {
+        let mut changes = Vec::new();
+        if self.network != current.network {
+            changes.push(format!(
+                "network changed from {} to {}",
+                self.network, current.network
+            ));
+        }
+        if self.operator_identity_public_keys != current.operator_identity_public_keys {
+            changes.push("signing operator pool changed".to_string());
+        }
+        changes.join(", ")
+    }
+}
+
+/// Compares the configuration against the fingerprint persisted in storage.
+///
+/// The first start persists the fingerprint. On later starts an incompatible
+/// change fails with [`SdkError::ConfigMismatch`], unless `accept_change` is set,
+/// in which case the new fingerprint replaces the stored one.
+pub(crate) async fn check_config_fingerprint(
+    storage: Arc<dyn Storage>,
+    current: &ConfigFingerprint,
+    accept_change: bool,
+) -> Result<(), SdkError> {
+    let cache = ObjectCacheRepository::new(storage);
+    match cache.fetch_config_fingerprint().await? {
+        Some(stored) if stored == *current => return Ok(()),
+        Some(stored) => {
+            let details = stored.describe_changes(current);
+            if !accept_change {
+                return Err(SdkError::ConfigMismatch { details });
+            }
+            warn!("Accepting incompatible config change for existing storage: {details}");
+        }
+        None => {}
+    }
+    cache.save_config_fingerprint(current).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(network: &str, keys: &[&str]) -> ConfigFingerprint {
+        ConfigFingerprint {
+            network: network.to_string(),
+            operator_identity_public_keys: keys.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_coordinator_and_user_agent() {
+        let config = SparkWalletConfig::default_config(spark_wallet::Network::Regtest);
+        let mut changed = config.clone();
+        let operators: Vec<_> = changed.operator_pool.get_all_operators().cloned().collect();
+        changed.operator_pool = spark_wallet::OperatorPoolConfig::new(1, operators)
+            .unwrap()
+            .with_user_agent(Some("test".to_string()));
+
+        assert_eq!(
+            ConfigFingerprint::new(Network::Regtest, &config),
+            ConfigFingerprint::new(Network::Regtest, &changed)
+        );
+    }
+
+    #[test]
+    fn test_describe_changes() {
+        let stored = fingerprint("Mainnet", &["a", "b"]);
+        assert_eq!(
+            stored.describe_changes(&fingerprint("Regtest", &["a", "b"])),
+            "network changed from Mainnet to Regtest"
+        );
+        assert_eq!(
+            stored.describe_changes(&fingerprint("Mainnet", &["a", "c"])),
+            "signing operator pool changed"
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_check_config_fingerprint() {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!(
+            "breez-test-config-fingerprint-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let storage: Arc<dyn Storage> =
+            Arc::new(crate::persist::sqlite::SqliteStorage::new(&dir).unwrap());
+        let mainnet = fingerprint("Mainnet", &["a"]);
+        let regtest = fingerprint("Regtest", &["a"]);
+
+        // First start persists the fingerprint, a restart with the same config passes.
+        check_config_fingerprint(Arc::clone(&storage), &mainnet, false)
+            .await
+            .unwrap();
+        check_config_fingerprint(Arc::clone(&storage), &mainnet, false)
+            .await
+            .unwrap();
+
+        let err = check_config_fingerprint(Arc::clone(&storage), &regtest, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SdkError::ConfigMismatch { .. }), "{err:?}");
+
+        // Accepting the change replaces the stored fingerprint.
+        check_config_fingerprint(Arc::clone(&storage), &regtest, true)
+            .await
+            .unwrap();
+        check_config_fingerprint(Arc::clone(&storage), &regtest, false)
+            .await
+            .unwrap();
+    }
+}
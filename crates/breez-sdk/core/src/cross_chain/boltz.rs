@@ -33,6 +33,7 @@ use super::{
 use crate::{
     ConversionInfo, ConversionStatus, CrossChainAddressDetails, Network, PaymentMetadata,
     PaymentStatus, Storage,
+    clock::Clock,
     error::SdkError,
     sdk::LightningSender,
     utils::{
@@ -60,6 +61,7 @@ pub(crate) struct BoltzService {
     /// by this provider so Boltz hold-invoice pays behave identically to
     /// direct LN sends.
     lightning_sender: Arc<LightningSender>,
+    clock: Arc<dyn Clock>,
 }
 
 impl BoltzService {
@@ -86,6 +88,7 @@ impl BoltzService {
         ecies: Option<Arc<dyn crate::signer::EciesSigner>>,
         fiat_service: Arc<dyn FiatService>,
         lightning_sender: Arc<LightningSender>,
+        clock: Arc<dyn Clock>,
         shutdown_receiver: watch::Receiver<()>,
     ) -> Result<Option<Arc<dyn CrossChainService>>, SdkError> {
         let Some(config) = Self::default_client_config(network) else {
@@ -112,6 +115,7 @@ impl BoltzService {
             storage,
             fiat_service,
             lightning_sender,
+            clock,
         });
         info!("Boltz service initialized");
         service.spawn_resume_monitor(shutdown_receiver);
@@ -625,7 +629,7 @@ impl CrossChainService for BoltzService {
         // display value (token base units on the conversion path) instead of sats.
         let invoice_amount_sats = *invoice_amount_sats;
 
-        validate_quote_expiry(&prepared.expires_at)?;
+        validate_quote_expiry(&prepared.expires_at, self.clock.now())?;
 
         let transfer_id = Some(derive_btc_leg_transfer_id(
             idempotency_key.as_deref(),
@@ -821,11 +825,11 @@ async fn poll_to_terminal_or_fallback(
 /// at send time if the wall clock has passed it so the user sees a clean
 /// "quote expired, re-prepare" rather than a server-side error after the LN
 /// pay attempt.
-fn validate_quote_expiry(expires_at: &str) -> Result<(), SdkError> {
+fn validate_quote_expiry(expires_at: &str, now: SystemTime) -> Result<(), SdkError> {
     let exp_secs: u64 = expires_at
         .parse()
         .map_err(|e| SdkError::Generic(format!("Boltz: invalid expires_at {expires_at:?}: {e}")))?;
-    let now_secs = SystemTime::now()
+    let now_secs = now
         .duration_since(UNIX_EPOCH)
         .map_err(|_| SdkError::Generic("Failed to read current time".to_string()))?
         .as_secs();
@@ -1203,6 +1207,10 @@ mod tests {
                 method: crate::PaymentMethod::Lightning,
                 details: None,
                 conversion_details: None,
+                imported: false,
+                display_amount: crate::DisplayAmount::default(),
+                group_id: None,
+                dispute: None,
             }
         }
 
@@ -1312,18 +1320,18 @@ mod tests {
             .unwrap()
             .as_secs()
             .saturating_add(600);
-        assert!(validate_quote_expiry(&future.to_string()).is_ok());
+        assert!(validate_quote_expiry(&future.to_string(), SystemTime::now()).is_ok());
     }
 
     #[test_all]
     fn validate_quote_expiry_rejects_past_unix_secs() {
-        let err = validate_quote_expiry("1000000000").unwrap_err();
+        let err = validate_quote_expiry("1000000000", SystemTime::now()).unwrap_err();
         assert!(matches!(err, SdkError::InvalidInput(ref m) if m.contains("expired")));
     }
 
     #[test_all]
     fn validate_quote_expiry_rejects_malformed() {
-        let err = validate_quote_expiry("not-a-number").unwrap_err();
+        let err = validate_quote_expiry("not-a-number", SystemTime::now()).unwrap_err();
         assert!(matches!(err, SdkError::Generic(ref m) if m.contains("invalid expires_at")));
     }
 }
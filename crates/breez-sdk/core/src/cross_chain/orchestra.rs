@@ -25,6 +25,7 @@ use tokio::{
 };
 use tracing::{Instrument, debug, error, info};
 
+use crate::clock::Clock;
 use crate::error::SdkError;
 use crate::persist::{ConversionFilter, StorageListPaymentsRequest, StoragePaymentDetailsFilter};
 use crate::{ConversionInfo, ConversionStatus, PaymentDetails, Storage};
@@ -98,6 +99,7 @@ pub(crate) struct OrchestraService {
     storage: Arc<dyn Storage>,
     fiat_service: Arc<dyn FiatService>,
     monitor_trigger: broadcast::Sender<()>,
+    clock: Arc<dyn Clock>,
 }
 
 impl OrchestraService {
@@ -106,6 +108,7 @@ impl OrchestraService {
         spark_wallet: Arc<SparkWallet>,
         storage: Arc<dyn Storage>,
         fiat_service: Arc<dyn FiatService>,
+        clock: Arc<dyn Clock>,
         shutdown_receiver: watch::Receiver<()>,
     ) -> Self {
         let client = Arc::new(OrchestraClient::new(
@@ -120,6 +123,7 @@ impl OrchestraService {
             storage,
             fiat_service,
             monitor_trigger: monitor_trigger.clone(),
+            clock,
         };
         info!("Orchestra service initialized");
         service.spawn_monitor(shutdown_receiver, &monitor_trigger);
@@ -606,7 +610,7 @@ impl CrossChainService for OrchestraService {
         // display value (token base units on the conversion path) instead.
         let deposit_amount = *deposit_amount;
 
-        validate_quote_expiry(&prepared.expires_at)?;
+        validate_quote_expiry(&prepared.expires_at, self.clock.now())?;
 
         let transfer_id = Some(derive_btc_leg_transfer_id(
             idempotency_key.as_deref(),
@@ -903,12 +907,12 @@ fn apply_terminal_status(
 
 /// Rejects an expired quote at send time so the caller can re-prepare
 /// instead of getting a less helpful error from `/submit`.
-fn validate_quote_expiry(expires_at: &str) -> Result<(), SdkError> {
+fn validate_quote_expiry(expires_at: &str, now: SystemTime) -> Result<(), SdkError> {
     let exp = DateTime::parse_from_rfc3339(expires_at).map_err(|e| {
         SdkError::Generic(format!("Orchestra: invalid expires_at {expires_at:?}: {e}"))
     })?;
     let exp_secs = u64::try_from(exp.timestamp()).unwrap_or(0);
-    let now_secs = SystemTime::now()
+    let now_secs = now
         .duration_since(UNIX_EPOCH)
         .map_err(|_| SdkError::Generic("Failed to read current time".to_string()))?
         .as_secs();
@@ -1250,6 +1254,10 @@ mod tests {
             method,
             details: Some(details),
             conversion_details: None,
+            imported: false,
+            display_amount: crate::DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -1707,19 +1715,19 @@ mod tests {
         let dt =
             chrono::DateTime::<chrono::Utc>::from_timestamp(future_secs.cast_signed(), 0).unwrap();
         let s = dt.to_rfc3339();
-        assert!(validate_quote_expiry(&s).is_ok());
+        assert!(validate_quote_expiry(&s, SystemTime::now()).is_ok());
     }
 
     #[test_all]
     fn validate_quote_expiry_rejects_past_rfc3339() {
         // 2001-09-09 — well in the past.
-        let err = validate_quote_expiry("2001-09-09T01:46:40Z").unwrap_err();
+        let err = validate_quote_expiry("2001-09-09T01:46:40Z", SystemTime::now()).unwrap_err();
         assert!(matches!(err, SdkError::InvalidInput(ref m) if m.contains("expired")));
     }
 
     #[test_all]
     fn validate_quote_expiry_rejects_malformed() {
-        let err = validate_quote_expiry("not-a-timestamp").unwrap_err();
+        let err = validate_quote_expiry("not-a-timestamp", SystemTime::now()).unwrap_err();
         assert!(matches!(err, SdkError::Generic(ref m) if m.contains("invalid expires_at")));
     }
 
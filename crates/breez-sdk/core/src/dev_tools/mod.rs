@@ -0,0 +1,5 @@
+mod models;
+mod sdk;
+
+pub use models::*;
+pub use sdk::DevTools;
@@ -0,0 +1,93 @@
+use serde::Serialize;
+
+/// Configuration of the regtest services driven by [`DevTools`](crate::DevTools)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DevToolsConfig {
+    /// GraphQL endpoint of the regtest faucet. Defaults to the Lightspark regtest faucet.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub faucet_url: Option<String>,
+    /// Optional basic authentication username for the faucet
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub faucet_username: Option<String>,
+    /// Optional basic authentication password for the faucet
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub faucet_password: Option<String>,
+    /// Lightning node paying invoices in [`DevTools::simulate_incoming_payment`](crate::DevTools::simulate_incoming_payment)
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub lightning_node: Option<RegtestLightningNode>,
+    /// Bitcoin Core node mining blocks in [`DevTools::mine_blocks`](crate::DevTools::mine_blocks)
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub bitcoind: Option<RegtestBitcoind>,
+}
+
+/// A pre-funded LND node exposing its REST API
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RegtestLightningNode {
+    /// REST endpoint, e.g. `http://127.0.0.1:8080`
+    pub url: String,
+    /// Optional hex-encoded macaroon authorizing payments
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub macaroon: Option<String>,
+}
+
+/// A Bitcoin Core node reachable over JSON-RPC
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RegtestBitcoind {
+    /// JSON-RPC endpoint, e.g. `http://127.0.0.1:18443`
+    pub rpc_url: String,
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub rpc_username: Option<String>,
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub rpc_password: Option<String>,
+    /// Address receiving the block rewards. If not set, a new address of the
+    /// node wallet is used.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub mining_address: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FaucetFundRequest {
+    pub amount_sats: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FaucetFundResponse {
+    /// The deposit address funded by the faucet
+    pub address: String,
+    /// The id of the funding transaction
+    pub txid: String,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct MineBlocksRequest {
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct MineBlocksResponse {
+    pub block_hashes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SimulateIncomingPaymentRequest {
+    pub amount_sats: u64,
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SimulateIncomingPaymentResponse {
+    /// The bolt11 invoice paid by the lightning node
+    pub invoice: String,
+    /// The hex-encoded payment preimage
+    pub preimage: String,
+}
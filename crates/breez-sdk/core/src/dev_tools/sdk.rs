@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use base64::Engine;
+use platform_utils::{
+    ContentType, HttpClient, HttpResponse, add_basic_auth_header, add_content_type_header,
+    create_http_client,
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::{
+    BreezSdk, DevToolsConfig, FaucetFundRequest, FaucetFundResponse, MineBlocksRequest,
+    MineBlocksResponse, Network, ReceivePaymentMethod, ReceivePaymentRequest, RegtestBitcoind,
+    SdkError, SimulateIncomingPaymentRequest, SimulateIncomingPaymentResponse,
+};
+
+const DEFAULT_FAUCET_URL: &str = "https://api.lightspark.com/graphql/spark/rc";
+const FAUCET_MUTATION: &str = "mutation RequestRegtestFunds($address: String!, $amount_sats: Long!) { request_regtest_funds(input: {address: $address, amount_sats: $amount_sats}) { transaction_hash}}";
+
+#[derive(Deserialize)]
+struct GraphQLResponse {
+    data: Option<FaucetResponseData>,
+    errors: Option<Vec<GraphQLError>>,
+}
+
+#[derive(Deserialize)]
+struct FaucetResponseData {
+    request_regtest_funds: RequestRegtestFunds,
+}
+
+#[derive(Deserialize)]
+struct RequestRegtestFunds {
+    transaction_hash: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct LndSendPaymentResponse {
+    #[serde(default)]
+    payment_error: String,
+    /// Base64-encoded preimage
+    #[serde(default)]
+    payment_preimage: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+/// Helpers driving regtest flows from app code during development.
+///
+/// Only available on regtest, see [`BreezSdk::get_dev_tools`].
+#[cfg_attr(feature = "uniffi", derive(uniffi::Object))]
+pub struct DevTools {
+    sdk: BreezSdk,
+    config: DevToolsConfig,
+    http_client: Arc<dyn HttpClient>,
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Returns an instance of the [`DevTools`] for driving regtest flows.
+    ///
+    /// Fails on networks other than regtest.
+    pub fn get_dev_tools(&self, config: DevToolsConfig) -> Result<DevTools, SdkError> {
+        if self.config.get().network != Network::Regtest {
+            return Err(SdkError::InvalidInput(
+                "Dev tools are only available on regtest".to_string(),
+            ));
+        }
+        Ok(DevTools::new(self.clone(), config))
+    }
+}
+
+impl DevTools {
+    fn new(sdk: BreezSdk, config: DevToolsConfig) -> Self {
+        Self {
+            sdk,
+            config,
+            http_client: create_http_client(Some("breez-sdk-spark-dev-tools")),
+        }
+    }
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl DevTools {
+    /// Funds a deposit address of the wallet from the regtest faucet
+    ///
+    /// The deposit is claimed like any other once it confirms, which may
+    /// require mining blocks.
+    pub async fn faucet_fund(
+        &self,
+        request: FaucetFundRequest,
+    ) -> Result<FaucetFundResponse, SdkError> {
+        let address = self
+            .receive(ReceivePaymentMethod::BitcoinAddress { new_address: None })
+            .await?;
+        info!(
+            "Requesting {} sats from the faucet to {address}",
+            request.amount_sats
+        );
+
+        let body = json!({
+            "operationName": "RequestRegtestFunds",
+            "variables": { "address": address, "amount_sats": request.amount_sats },
+            "query": FAUCET_MUTATION,
+        });
+        let mut headers = HashMap::new();
+        if let (Some(username), Some(password)) =
+            (&self.config.faucet_username, &self.config.faucet_password)
+        {
+            add_basic_auth_header(&mut headers, username, password);
+        }
+        let url = self
+            .config
+            .faucet_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_FAUCET_URL.to_string());
+        let response: GraphQLResponse = self.post_json(url, headers, &body).await?;
+
+        if let Some(errors) = response.errors {
+            let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+            return Err(SdkError::Generic(format!(
+                "Faucet returned errors: {}",
+                messages.join(", ")
+            )));
+        }
+        let txid = response
+            .data
+            .ok_or_else(|| SdkError::Generic("Faucet response missing data".to_string()))?
+            .request_regtest_funds
+            .transaction_hash;
+        Ok(FaucetFundResponse { address, txid })
+    }
+
+    /// Mines blocks on the configured Bitcoin Core node
+    pub async fn mine_blocks(
+        &self,
+        request: MineBlocksRequest,
+    ) -> Result<MineBlocksResponse, SdkError> {
+        let Some(bitcoind) = &self.config.bitcoind else {
+            return Err(SdkError::InvalidInput(
+                "Mining blocks requires a bitcoind node in the dev tools config".to_string(),
+            ));
+        };
+        let address = match &bitcoind.mining_address {
+            Some(address) => address.clone(),
+            None => self.rpc(bitcoind, "getnewaddress", json!([])).await?,
+        };
+        let block_hashes = self
+            .rpc(
+                bitcoind,
+                "generatetoaddress",
+                json!([request.count, address]),
+            )
+            .await?;
+        Ok(MineBlocksResponse { block_hashes })
+    }
+
+    /// Pays a new bolt11 invoice of the wallet from the configured lightning node
+    pub async fn simulate_incoming_payment(
+        &self,
+        request: SimulateIncomingPaymentRequest,
+    ) -> Result<SimulateIncomingPaymentResponse, SdkError> {
+        let Some(node) = &self.config.lightning_node else {
+            return Err(SdkError::InvalidInput(
+                "Simulating payments requires a lightning node in the dev tools config".to_string(),
+            ));
+        };
+        let invoice = self
+            .receive(ReceivePaymentMethod::Bolt11Invoice {
+                description: request
+                    .description
+                    .unwrap_or_else(|| "Simulated payment".to_string()),
+                amount_sats: Some(request.amount_sats),
+                expiry_secs: None,
+                payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
+            })
+            .await?;
+
+        let mut headers = HashMap::new();
+        if let Some(macaroon) = &node.macaroon {
+            headers.insert("Grpc-Metadata-macaroon".to_string(), macaroon.clone());
+        }
+        let url = format!(
+            "{}/v1/channels/transactions",
+            node.url.trim_end_matches('/')
+        );
+        let payment: LndSendPaymentResponse = self
+            .post_json(url, headers, &json!({ "payment_request": invoice }))
+            .await?;
+        if !payment.payment_error.is_empty() {
+            return Err(SdkError::Generic(format!(
+                "Lightning node failed to pay: {}",
+                payment.payment_error
+            )));
+        }
+        let preimage = base64::engine::general_purpose::STANDARD
+            .decode(&payment.payment_preimage)
+            .map_err(|e| SdkError::Generic(format!("Invalid preimage: {e}")))?;
+        Ok(SimulateIncomingPaymentResponse {
+            invoice,
+            preimage: hex::encode(preimage),
+        })
+    }
+}
+
+impl DevTools {
+    async fn receive(&self, payment_method: ReceivePaymentMethod) -> Result<String, SdkError> {
+        Ok(self
+            .sdk
+            .receive_payment(ReceivePaymentRequest {
+                payment_method,
+                order_ref: None,
+            })
+            .await?
+            .payment_request)
+    }
+
+    async fn rpc<T: DeserializeOwned>(
+        &self,
+        bitcoind: &RegtestBitcoind,
+        method: &str,
+        params: Value,
+    ) -> Result<T, SdkError> {
+        let mut headers = HashMap::new();
+        if let (Some(username), Some(password)) = (&bitcoind.rpc_username, &bitcoind.rpc_password) {
+            add_basic_auth_header(&mut headers, username, password);
+        }
+        let body = json!({
+            "jsonrpc": "1.0",
+            "id": "breez-sdk",
+            "method": method,
+            "params": params,
+        });
+        // bitcoind reports RPC errors with an error status and a JSON body
+        let response: RpcResponse<T> = self
+            .post(bitcoind.rpc_url.clone(), headers, &body)
+            .await?
+            .json()
+            .map_err(|e| SdkError::Generic(format!("Invalid bitcoind response: {e}")))?;
+        if let Some(error) = response.error.filter(|e| !e.is_null()) {
+            return Err(SdkError::Generic(format!(
+                "bitcoind {method} failed: {error}"
+            )));
+        }
+        response
+            .result
+            .ok_or_else(|| SdkError::Generic(format!("bitcoind {method} returned no result")))
+    }
+
+    async fn post_json<T: DeserializeOwned>(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: &Value,
+    ) -> Result<T, SdkError> {
+        let response = self.post(url, headers, body).await?;
+        if !response.is_success() {
+            return Err(SdkError::NetworkError(format!(
+                "Request failed with status {}: {}",
+                response.status, response.body
+            )));
+        }
+        response
+            .json()
+            .map_err(|e| SdkError::Generic(format!("Invalid response: {e}")))
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        mut headers: HashMap<String, String>,
+        body: &Value,
+    ) -> Result<HttpResponse, SdkError> {
+        add_content_type_header(&mut headers, ContentType::Json);
+        self.http_client
+            .post(url, Some(headers), Some(body.to_string()))
+            .await
+            .map_err(|e| SdkError::NetworkError(e.to_string()))
+    }
+}
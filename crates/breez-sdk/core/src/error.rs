@@ -54,6 +54,11 @@ pub enum SdkError {
     #[error("Missing utxo: {tx}:{vout}")]
     MissingUtxo { tx: String, vout: u32 },
 
+    /// The deposit was not sent to one of this wallet's static deposit addresses,
+    /// so it can't be claimed with this seed.
+    #[error("Deposit {tx}:{vout} was not sent to an address of this wallet")]
+    NotOurDeposit { tx: String, vout: u32 },
+
     #[error("Lnurl error: {0}")]
     LnurlError(String),
 
@@ -79,6 +84,46 @@ pub enum SdkError {
     #[error("Funding UTXO {txid}:{vout} was spent by an unrelated transaction")]
     FundingUtxoConflict { txid: String, vout: u32 },
 
+    /// The configuration is incompatible with the one the storage was created
+    /// with, e.g. the network or the signing operator pool changed.
+    #[error(
+        "Config mismatch: {details}. Use a separate storage directory for this configuration, or accept the change with `SdkBuilder::with_config_change_accepted`"
+    )]
+    ConfigMismatch { details: String },
+
+    /// The destination is not on the withdrawal allowlist and the payment was
+    /// not approved by the payment approver.
+    #[error("Destination is not on the withdrawal allowlist: {destination}")]
+    DestinationNotAllowed { destination: String },
+
+    /// `update_config` was asked to change a setting that is fixed for the
+    /// lifetime of the SDK instance.
+    #[error("Config field {field} can't be changed at runtime")]
+    ImmutableConfig { field: String },
+
+    /// The `SdkBuilder` is misconfigured. Lists every problem found, so they
+    /// can all be fixed at once.
+    #[error("Invalid SDK builder configuration: {}", problems.join("; "))]
+    BuilderError { problems: Vec<String> },
+
+    /// The request failed during a maintenance window announced by the
+    /// service status feed. `until` is the end of the window, in seconds
+    /// since the Unix epoch.
+    #[error("Service is under maintenance until {until}")]
+    ServiceMaintenance { until: u64 },
+
+    /// The SDK was built with [`SdkBuilder::new_watch_only`](crate::SdkBuilder::new_watch_only)
+    /// and can't sign, so `operation` is unavailable.
+    #[error("{operation} is not available in watch-only mode")]
+    WatchOnly { operation: String },
+
+    /// The network was unreachable, so the payment was queued as requested
+    /// with `SendPaymentRequest::queue_if_offline`. It is sent once the
+    /// connection is restored, and a `QueuedPaymentResolved` event reports
+    /// the outcome.
+    #[error("Payment {queued_payment_id} was queued until the network is reachable")]
+    PaymentQueued { queued_payment_id: String },
+
     #[error("Error: {0}")]
     Generic(String),
 }
@@ -301,6 +346,11 @@ pub enum DepositClaimError {
     #[error("Missing utxo: {tx}:{vout}")]
     MissingUtxo { tx: String, vout: u32 },
 
+    /// The deposit was not sent to one of this wallet's static deposit addresses,
+    /// so it can't be claimed with this seed.
+    #[error("Deposit {tx}:{vout} was not sent to an address of this wallet")]
+    NotOurDeposit { tx: String, vout: u32 },
+
     #[error("Generic error: {message}")]
     Generic { message: String },
 }
@@ -322,6 +372,7 @@ impl From<SdkError> for DepositClaimError {
                 required_fee_rate_sat_per_vbyte,
             },
             SdkError::MissingUtxo { tx, vout } => DepositClaimError::MissingUtxo { tx, vout },
+            SdkError::NotOurDeposit { tx, vout } => DepositClaimError::NotOurDeposit { tx, vout },
             SdkError::Generic(e) => DepositClaimError::Generic { message: e },
             _ => DepositClaimError::Generic {
                 message: value.to_string(),
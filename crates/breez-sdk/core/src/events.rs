@@ -1,16 +1,19 @@
 use core::fmt;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     sync::atomic::{AtomicBool, AtomicU64, Ordering},
 };
 
 use platform_utils::time::Instant;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, RwLock};
 use tracing::info;
 use uuid::Uuid;
 
-use crate::{DepositInfo, LightningAddressInfo, Payment, sdk::RuntimeEvent};
+use crate::{
+    DepositInfo, LightningAddressInfo, Payment, PaymentDetails, PaymentMethod, PaymentStatus,
+    PaymentType, ReceiveStageUpdate, StuckPaymentRecommendation, sdk::RuntimeEvent,
+};
 
 /// Events emitted by the SDK
 #[allow(clippy::large_enum_variant)]
@@ -32,8 +35,15 @@ pub enum SdkEvent {
     PaymentPending {
         payment: Payment,
     },
+    /// Emitted instead of `PaymentPending` for a payment waiting for an
+    /// on-chain transaction to confirm, such as a withdrawal
+    PaymentWaitingConfirmation {
+        payment: Payment,
+    },
     PaymentFailed {
         payment: Payment,
+        /// Why the payment failed, when known
+        reason: Option<String>,
     },
     /// Emitted while the background auto-optimizer is running.
     ///
@@ -51,14 +61,281 @@ pub enum SdkEvent {
     NewDeposits {
         new_deposits: Vec<DepositInfo>,
     },
+    /// Emitted each time the SDK re-evaluates the claim fee of a deposit that
+    /// couldn't be claimed because the fee exceeded the maximum. The deposit is
+    /// claimed automatically once the required fee drops to the maximum.
+    DepositClaimFeeEvaluated {
+        deposit: DepositInfo,
+        /// The fee currently required to claim the deposit
+        required_fee_sats: u64,
+        /// The maximum fee the deposit is claimed with, if any
+        max_fee_sats: Option<u64>,
+        /// Whether the deposit was claimed in this evaluation
+        claimed: bool,
+    },
+    /// Emitted after each batch of payments is synced, e.g. to show the
+    /// progress of the first sync of a wallet with a long history.
+    SyncProgress {
+        /// The number of transfers synced so far
+        synced: u64,
+    },
+    /// Emitted when a sync finds the balance of a token changed, once per token
+    TokenBalanceChanged {
+        token_identifier: String,
+        /// The balance in token base units before the change
+        old_balance: u128,
+        /// The balance in token base units after the change
+        new_balance: u128,
+    },
+    /// Emitted when a payment has been pending for longer than expected for
+    /// its method, and again if the recommendation escalates
+    PaymentStuck {
+        payment_id: String,
+        recommendation: StuckPaymentRecommendation,
+    },
+    /// Emitted once the exit transaction of a withdrawal is confirmed
+    WithdrawConfirmed {
+        txid: String,
+        height: u32,
+    },
+    /// Emitted after `PaymentSucceeded` for a payment to the lightning
+    /// address that carries a sender comment or a Nostr zap request
+    LightningAddressPaymentReceived {
+        payment: Payment,
+        /// The comment of the sender, or the content of the zap request
+        sender_comment: Option<String>,
+        /// The Nostr public key of the zap sender, in hex
+        zap_sender_pubkey: Option<String>,
+        /// The zap request, a Nostr event of kind 9734 as JSON
+        nostr_zap_request: Option<String>,
+    },
+    /// Emitted when chain API requests switch to another endpoint of the list
+    /// set with `with_rest_chain_service_endpoints`, e.g. because the previous
+    /// one is unreachable
+    ChainServiceFailover {
+        from_url: String,
+        to_url: String,
+    },
+    /// Emitted when a Lightning receive for an invoice created by this wallet
+    /// reaches a new stage
+    ReceiveStageChanged {
+        payment_hash: String,
+        update: ReceiveStageUpdate,
+    },
+    /// Emitted when a payment queued with `SendPaymentRequest::queue_if_offline`
+    /// is sent, fails or expires
+    QueuedPaymentResolved {
+        queued_payment_id: String,
+        outcome: QueuedPaymentOutcome,
+    },
+    /// Emitted after each batch of older payments is backfilled following a
+    /// fast sync, and once more when the whole history is synced
+    HistoryBackfillProgress {
+        /// The number of transfers synced so far
+        synced: u64,
+        /// Whether the whole history is synced
+        completed: bool,
+    },
 }
 
 impl SdkEvent {
     pub(crate) fn from_payment(payment: Payment) -> Self {
+        Self::from_payment_with_reason(payment, None)
+    }
+
+    /// The event for the status of a payment, with the `reason` of a failure.
+    pub(crate) fn from_payment_with_reason(payment: Payment, reason: Option<String>) -> Self {
         match payment.status {
-            crate::PaymentStatus::Completed => SdkEvent::PaymentSucceeded { payment },
-            crate::PaymentStatus::Pending => SdkEvent::PaymentPending { payment },
-            crate::PaymentStatus::Failed => SdkEvent::PaymentFailed { payment },
+            PaymentStatus::Completed => SdkEvent::PaymentSucceeded { payment },
+            PaymentStatus::Pending if payment.method == PaymentMethod::Withdraw => {
+                SdkEvent::PaymentWaitingConfirmation { payment }
+            }
+            PaymentStatus::Pending => SdkEvent::PaymentPending { payment },
+            PaymentStatus::Failed => SdkEvent::PaymentFailed { payment, reason },
+        }
+    }
+
+    /// One `TokenBalanceChanged` event per token whose balance differs,
+    /// ordered by token identifier. Missing tokens have a zero balance.
+    pub(crate) fn token_balance_changes(
+        old: &HashMap<String, u128>,
+        new: &HashMap<String, u128>,
+    ) -> Vec<Self> {
+        let identifiers: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        identifiers
+            .into_iter()
+            .filter_map(|identifier| {
+                let old_balance = old.get(identifier).copied().unwrap_or_default();
+                let new_balance = new.get(identifier).copied().unwrap_or_default();
+                (old_balance != new_balance).then(|| SdkEvent::TokenBalanceChanged {
+                    token_identifier: identifier.clone(),
+                    old_balance,
+                    new_balance,
+                })
+            })
+            .collect()
+    }
+
+    /// A `LightningAddressPaymentReceived` event for a completed lightning
+    /// address payment with a sender comment or a zap request.
+    pub(crate) fn lightning_address_payment(payment: &Payment) -> Option<Self> {
+        if payment.status != PaymentStatus::Completed
+            || payment.payment_type != PaymentType::Receive
+        {
+            return None;
+        }
+        let Some(PaymentDetails::Lightning {
+            lnurl_receive_metadata: Some(metadata),
+            ..
+        }) = &payment.details
+        else {
+            return None;
+        };
+        let zap_request = metadata
+            .nostr_zap_request
+            .as_deref()
+            .and_then(|request| serde_json::from_str::<ZapRequest>(request).ok());
+        let sender_comment = metadata.sender_comment.clone().or_else(|| {
+            zap_request
+                .as_ref()
+                .map(|request| request.content.clone())
+                .filter(|content| !content.is_empty())
+        });
+        if sender_comment.is_none() && metadata.nostr_zap_request.is_none() {
+            return None;
+        }
+        Some(SdkEvent::LightningAddressPaymentReceived {
+            payment: payment.clone(),
+            sender_comment,
+            zap_sender_pubkey: zap_request.map(|request| request.pubkey),
+            nostr_zap_request: metadata.nostr_zap_request.clone(),
+        })
+    }
+}
+
+/// The fields of a Nostr zap request the events expose
+#[derive(Deserialize)]
+struct ZapRequest {
+    pubkey: String,
+    #[serde(default)]
+    content: String,
+}
+
+impl SdkEvent {
+    /// The kind of the event, without its data
+    pub fn kind(&self) -> SdkEventKind {
+        match self {
+            SdkEvent::Synced => SdkEventKind::Synced,
+            SdkEvent::UnclaimedDeposits { .. } => SdkEventKind::UnclaimedDeposits,
+            SdkEvent::ClaimedDeposits { .. } => SdkEventKind::ClaimedDeposits,
+            SdkEvent::PaymentSucceeded { .. } => SdkEventKind::PaymentSucceeded,
+            SdkEvent::PaymentPending { .. } => SdkEventKind::PaymentPending,
+            SdkEvent::PaymentWaitingConfirmation { .. } => SdkEventKind::PaymentWaitingConfirmation,
+            SdkEvent::PaymentFailed { .. } => SdkEventKind::PaymentFailed,
+            SdkEvent::AutoOptimization { .. } => SdkEventKind::AutoOptimization,
+            SdkEvent::LightningAddressChanged { .. } => SdkEventKind::LightningAddressChanged,
+            SdkEvent::NewDeposits { .. } => SdkEventKind::NewDeposits,
+            SdkEvent::DepositClaimFeeEvaluated { .. } => SdkEventKind::DepositClaimFeeEvaluated,
+            SdkEvent::SyncProgress { .. } => SdkEventKind::SyncProgress,
+            SdkEvent::TokenBalanceChanged { .. } => SdkEventKind::TokenBalanceChanged,
+            SdkEvent::PaymentStuck { .. } => SdkEventKind::PaymentStuck,
+            SdkEvent::WithdrawConfirmed { .. } => SdkEventKind::WithdrawConfirmed,
+            SdkEvent::LightningAddressPaymentReceived { .. } => {
+                SdkEventKind::LightningAddressPaymentReceived
+            }
+            SdkEvent::ChainServiceFailover { .. } => SdkEventKind::ChainServiceFailover,
+            SdkEvent::ReceiveStageChanged { .. } => SdkEventKind::ReceiveStageChanged,
+            SdkEvent::QueuedPaymentResolved { .. } => SdkEventKind::QueuedPaymentResolved,
+            SdkEvent::HistoryBackfillProgress { .. } => SdkEventKind::HistoryBackfillProgress,
+        }
+    }
+
+    fn payment_id(&self) -> Option<&str> {
+        match self {
+            SdkEvent::PaymentSucceeded { payment }
+            | SdkEvent::PaymentPending { payment }
+            | SdkEvent::PaymentWaitingConfirmation { payment }
+            | SdkEvent::PaymentFailed { payment, .. }
+            | SdkEvent::LightningAddressPaymentReceived { payment, .. } => Some(&payment.id),
+            SdkEvent::PaymentStuck { payment_id, .. } => Some(payment_id),
+            SdkEvent::QueuedPaymentResolved {
+                outcome: QueuedPaymentOutcome::Sent { payment },
+                ..
+            } => Some(&payment.id),
+            _ => None,
+        }
+    }
+}
+
+/// The kinds of [`SdkEvent`], used to filter the events a listener receives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum SdkEventKind {
+    Synced,
+    UnclaimedDeposits,
+    ClaimedDeposits,
+    PaymentSucceeded,
+    PaymentPending,
+    PaymentWaitingConfirmation,
+    PaymentFailed,
+    AutoOptimization,
+    LightningAddressChanged,
+    NewDeposits,
+    DepositClaimFeeEvaluated,
+    SyncProgress,
+    TokenBalanceChanged,
+    PaymentStuck,
+    WithdrawConfirmed,
+    LightningAddressPaymentReceived,
+    ChainServiceFailover,
+    ReceiveStageChanged,
+    QueuedPaymentResolved,
+    HistoryBackfillProgress,
+}
+
+/// Selects the events delivered to a listener added with
+/// `add_filtered_event_listener`. An event is delivered when it matches all
+/// the set criteria.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct EventFilter {
+    /// The event kinds to deliver. All kinds are delivered when empty.
+    #[cfg_attr(feature = "uniffi", uniffi(default = []))]
+    pub kinds: Vec<SdkEventKind>,
+    /// Only deliver payment events for the payment with this id
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub payment_id: Option<String>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &SdkEvent) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind()) {
+            return false;
+        }
+        match &self.payment_id {
+            Some(payment_id) => event.payment_id() == Some(payment_id.as_str()),
+            None => true,
+        }
+    }
+}
+
+/// Forwards the events matching `filter` to the wrapped listener.
+pub(crate) struct FilteredEventListener {
+    listener: Box<dyn EventListener>,
+    filter: EventFilter,
+}
+
+impl FilteredEventListener {
+    pub(crate) fn new(listener: Box<dyn EventListener>, filter: EventFilter) -> Self {
+        Self { listener, filter }
+    }
+}
+
+#[macros::async_trait]
+impl EventListener for FilteredEventListener {
+    async fn on_event(&self, event: SdkEvent) {
+        if self.filter.matches(&event) {
+            self.listener.on_event(event).await;
         }
     }
 }
@@ -79,8 +356,11 @@ impl fmt::Display for SdkEvent {
             SdkEvent::PaymentPending { payment } => {
                 write!(f, "PaymentPending: {payment:?}")
             }
-            SdkEvent::PaymentFailed { payment } => {
-                write!(f, "PaymentFailed: {payment:?}")
+            SdkEvent::PaymentWaitingConfirmation { payment } => {
+                write!(f, "PaymentWaitingConfirmation: {payment:?}")
+            }
+            SdkEvent::PaymentFailed { payment, reason } => {
+                write!(f, "PaymentFailed: {payment:?}, reason: {reason:?}")
             }
             SdkEvent::AutoOptimization {
                 optimization_event: event,
@@ -93,6 +373,61 @@ impl fmt::Display for SdkEvent {
             SdkEvent::NewDeposits { new_deposits } => {
                 write!(f, "NewDeposits: {new_deposits:?}")
             }
+            SdkEvent::DepositClaimFeeEvaluated {
+                deposit,
+                required_fee_sats,
+                max_fee_sats,
+                claimed,
+            } => write!(
+                f,
+                "DepositClaimFeeEvaluated: {}:{} required fee {required_fee_sats} sats, max fee {max_fee_sats:?} sats, claimed: {claimed}",
+                deposit.txid, deposit.vout
+            ),
+            SdkEvent::SyncProgress { synced } => write!(f, "SyncProgress: {synced} synced"),
+            SdkEvent::TokenBalanceChanged {
+                token_identifier,
+                old_balance,
+                new_balance,
+            } => write!(
+                f,
+                "TokenBalanceChanged: {token_identifier} {old_balance} -> {new_balance}"
+            ),
+            SdkEvent::PaymentStuck {
+                payment_id,
+                recommendation,
+            } => write!(f, "PaymentStuck: {payment_id} {recommendation:?}"),
+            SdkEvent::WithdrawConfirmed { txid, height } => {
+                write!(f, "WithdrawConfirmed: {txid} at height {height}")
+            }
+            SdkEvent::LightningAddressPaymentReceived {
+                payment,
+                sender_comment,
+                zap_sender_pubkey,
+                ..
+            } => write!(
+                f,
+                "LightningAddressPaymentReceived: {} comment {sender_comment:?} zap sender {zap_sender_pubkey:?}",
+                payment.id
+            ),
+            SdkEvent::ChainServiceFailover { from_url, to_url } => {
+                write!(f, "ChainServiceFailover: {from_url} -> {to_url}")
+            }
+            SdkEvent::ReceiveStageChanged {
+                payment_hash,
+                update,
+            } => write!(
+                f,
+                "ReceiveStageChanged: {payment_hash} {:?} SSP status {:?}",
+                update.stage, update.ssp_status
+            ),
+            SdkEvent::QueuedPaymentResolved {
+                queued_payment_id,
+                outcome,
+            } => write!(f, "QueuedPaymentResolved: {queued_payment_id} {outcome:?}"),
+            SdkEvent::HistoryBackfillProgress { synced, completed } => write!(
+                f,
+                "HistoryBackfillProgress: {synced} synced, completed: {completed}"
+            ),
         }
     }
 }
@@ -117,6 +452,19 @@ pub enum AutoOptimizationEvent {
     Skipped,
 }
 
+/// How a payment queued while offline was resolved
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum QueuedPaymentOutcome {
+    /// The payment was sent
+    Sent { payment: Payment },
+    /// Sending the payment failed with an error other than a network error
+    Failed { error: String },
+    /// The network stayed unreachable until the queued payment expired
+    Expired,
+}
+
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Default)]
 pub struct InternalSyncedEvent {
@@ -446,7 +794,7 @@ mod tests {
     use std::sync::Arc;
     use std::sync::atomic::{AtomicBool, Ordering};
 
-    use macros::async_test_all;
+    use macros::{async_test_all, test_all};
 
     #[cfg(feature = "browser-tests")]
     wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
@@ -1041,9 +1389,95 @@ mod tests {
             method: crate::PaymentMethod::Spark,
             details: None,
             conversion_details: None,
+            imported: false,
+            display_amount: crate::DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
+    // ── Filtered listener tests ──
+
+    #[async_test_all]
+    async fn test_filtered_listener_by_kind() {
+        let emitter = EventEmitter::new(false);
+        let (listener, events) = RecordingListener::new();
+        let filter = EventFilter {
+            kinds: vec![SdkEventKind::PaymentSucceeded, SdkEventKind::PaymentFailed],
+            payment_id: None,
+        };
+        emitter
+            .add_external_listener(Box::new(FilteredEventListener::new(
+                Box::new(listener),
+                filter,
+            )))
+            .await;
+
+        emitter.emit(&SdkEvent::Synced).await;
+        emitter
+            .emit(&SdkEvent::PaymentPending {
+                payment: test_payment(),
+            })
+            .await;
+        emitter
+            .emit(&SdkEvent::PaymentSucceeded {
+                payment: test_payment(),
+            })
+            .await;
+
+        let events = events.lock().await;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].starts_with("PaymentSucceeded"));
+    }
+
+    #[async_test_all]
+    async fn test_filtered_listener_by_payment_id() {
+        let emitter = EventEmitter::new(false);
+        let (listener, events) = RecordingListener::new();
+        let filter = EventFilter {
+            kinds: vec![],
+            payment_id: Some("test-id".to_string()),
+        };
+        emitter
+            .add_external_listener(Box::new(FilteredEventListener::new(
+                Box::new(listener),
+                filter,
+            )))
+            .await;
+
+        let mut other_payment = test_payment();
+        other_payment.id = "other-id".to_string();
+        emitter.emit(&SdkEvent::Synced).await;
+        emitter
+            .emit(&SdkEvent::PaymentSucceeded {
+                payment: other_payment,
+            })
+            .await;
+        emitter
+            .emit(&SdkEvent::PaymentPending {
+                payment: test_payment(),
+            })
+            .await;
+        emitter
+            .emit(&SdkEvent::PaymentStuck {
+                payment_id: "test-id".to_string(),
+                recommendation: StuckPaymentRecommendation::Sync,
+            })
+            .await;
+
+        let events = events.lock().await;
+        assert_eq!(events.len(), 2);
+        assert!(events[0].starts_with("PaymentPending"));
+        assert!(events[1].starts_with("PaymentStuck"));
+    }
+
+    #[async_test_all]
+    async fn test_empty_filter_matches_all_events() {
+        let filter = EventFilter::default();
+        assert!(filter.matches(&SdkEvent::Synced));
+        assert!(filter.matches(&SdkEvent::SyncProgress { synced: 1 }));
+    }
+
     // ── Internal listener tests ──
 
     #[async_test_all]
@@ -1316,4 +1750,112 @@ mod tests {
 
         assert_eq!(count.load(Ordering::Relaxed), 2); // Now count should be 2
     }
+
+    #[test_all]
+    fn test_from_payment() {
+        let payment = |status, method| Payment {
+            status,
+            method,
+            ..test_payment()
+        };
+        assert!(matches!(
+            SdkEvent::from_payment(payment(PaymentStatus::Pending, PaymentMethod::Spark)),
+            SdkEvent::PaymentPending { .. }
+        ));
+        assert!(matches!(
+            SdkEvent::from_payment(payment(PaymentStatus::Pending, PaymentMethod::Withdraw)),
+            SdkEvent::PaymentWaitingConfirmation { .. }
+        ));
+        assert!(matches!(
+            SdkEvent::from_payment(payment(PaymentStatus::Completed, PaymentMethod::Withdraw)),
+            SdkEvent::PaymentSucceeded { .. }
+        ));
+        assert!(matches!(
+            SdkEvent::from_payment_with_reason(
+                payment(PaymentStatus::Failed, PaymentMethod::Lightning),
+                Some("Lightning payment failed".to_string()),
+            ),
+            SdkEvent::PaymentFailed { reason: Some(reason), .. } if reason == "Lightning payment failed"
+        ));
+    }
+
+    #[test_all]
+    fn test_token_balance_changes() {
+        let old = HashMap::from([("a".to_string(), 10), ("b".to_string(), 5)]);
+        let new = HashMap::from([("a".to_string(), 10), ("c".to_string(), 7)]);
+
+        let events = SdkEvent::token_balance_changes(&old, &new);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            SdkEvent::TokenBalanceChanged { token_identifier, old_balance: 5, new_balance: 0 }
+                if token_identifier == "b"
+        ));
+        assert!(matches!(
+            &events[1],
+            SdkEvent::TokenBalanceChanged { token_identifier, old_balance: 0, new_balance: 7 }
+                if token_identifier == "c"
+        ));
+        assert!(SdkEvent::token_balance_changes(&new, &new).is_empty());
+    }
+
+    fn lnurl_payment(metadata: crate::LnurlReceiveMetadata) -> Payment {
+        Payment {
+            method: crate::PaymentMethod::Lightning,
+            details: Some(PaymentDetails::Lightning {
+                description: None,
+                invoice: "lnbc".to_string(),
+                destination_pubkey: String::new(),
+                htlc_details: crate::SparkHtlcDetails {
+                    payment_hash: "hash".to_string(),
+                    preimage: None,
+                    expiry_time: 0,
+                    status: crate::SparkHtlcStatus::PreimageShared,
+                },
+                lnurl_pay_info: None,
+                lnurl_withdraw_info: None,
+                lnurl_receive_metadata: Some(metadata),
+                conversion_info: None,
+            }),
+            ..test_payment()
+        }
+    }
+
+    #[test_all]
+    fn test_lightning_address_payment() {
+        let zap = lnurl_payment(crate::LnurlReceiveMetadata {
+            nostr_zap_request: Some(r#"{"kind":9734,"pubkey":"abcd","content":"gm"}"#.to_string()),
+            nostr_zap_receipt: None,
+            sender_comment: None,
+        });
+        assert!(matches!(
+            SdkEvent::lightning_address_payment(&zap),
+            Some(SdkEvent::LightningAddressPaymentReceived {
+                sender_comment: Some(comment),
+                zap_sender_pubkey: Some(pubkey),
+                ..
+            }) if comment == "gm" && pubkey == "abcd"
+        ));
+
+        let commented = lnurl_payment(crate::LnurlReceiveMetadata {
+            nostr_zap_request: None,
+            nostr_zap_receipt: None,
+            sender_comment: Some("thanks".to_string()),
+        });
+        assert!(matches!(
+            SdkEvent::lightning_address_payment(&commented),
+            Some(SdkEvent::LightningAddressPaymentReceived {
+                sender_comment: Some(comment),
+                zap_sender_pubkey: None,
+                ..
+            }) if comment == "thanks"
+        ));
+
+        let pending = Payment {
+            status: PaymentStatus::Pending,
+            ..commented
+        };
+        assert!(SdkEvent::lightning_address_payment(&pending).is_none());
+        assert!(SdkEvent::lightning_address_payment(&test_payment()).is_none());
+    }
 }
@@ -170,3 +170,32 @@ impl TokenIssuer {
             .into())
     }
 }
+
+#[cfg(feature = "token-announcements")]
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl TokenIssuer {
+    /// Publishes the issuer token metadata as a signed Nostr event, so wallets
+    /// can resolve tokens their operators don't know yet
+    ///
+    /// # Arguments
+    ///
+    /// * `request`: The request containing the optional icon URL of the token
+    ///
+    /// # Returns
+    ///
+    /// Result containing either:
+    /// * `TokenAnnouncement` - The published announcement
+    /// * `SdkError` - If no issuer token exists or no relay accepted the announcement
+    pub async fn publish_token_announcement(
+        &self,
+        request: crate::PublishTokenAnnouncementRequest,
+    ) -> Result<crate::TokenAnnouncement, SdkError> {
+        let metadata = self.get_issuer_token_metadata().await?;
+        crate::token_announcement::publish_announcement(
+            &self.spark_wallet,
+            metadata,
+            request.icon_url,
+        )
+        .await
+    }
+}
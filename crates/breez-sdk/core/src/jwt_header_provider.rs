@@ -12,10 +12,10 @@ use spark_wallet::{HeaderProvider, HeaderProviderError};
 use tokio::sync::oneshot;
 use tracing::{Instrument, warn};
 
-use crate::persist::Storage;
+use crate::secret_store::SecretStore;
 
 const PARTNER_ID_HEADER: &str = "x-partner-jwt";
-const KEY_BREEZ_JWT: &str = "breez_jwt";
+pub(crate) const KEY_BREEZ_JWT: &str = "breez_jwt";
 const JWT_EXPIRY_GRACE_PERIOD_SECS: u64 = 60 * 5;
 /// Stop serving a token this many seconds before its real `exp`, as a
 /// clock-skew guard so we never hand out a token the server may already treat
@@ -57,7 +57,7 @@ struct Inner {
     api_key: String,
     /// Bound once by [`BreezJwtHeaderProvider::start`] before the refresh task
     /// spawns; empty when the provider persists nothing (in-memory only).
-    storage: OnceLock<Arc<dyn Storage>>,
+    secret_store: OnceLock<Arc<dyn SecretStore>>,
     http_client: Arc<dyn HttpClient>,
 }
 
@@ -65,7 +65,7 @@ struct Inner {
 /// outgoing SO requests.
 ///
 /// Best-effort: construction is non-blocking. The initial JWT load (from
-/// `storage` cache if available, otherwise via HTTP) and all subsequent
+/// the secret store if available, otherwise via HTTP) and all subsequent
 /// refreshes run on a background task. Until the first successful load,
 /// [`headers`](Self::headers) returns an empty map, so calls proceed without
 /// the `x-partner-jwt` header rather than blocking on the JWT fetch.
@@ -86,8 +86,8 @@ pub struct BreezJwtHeaderProvider {
 
 impl BreezJwtHeaderProvider {
     /// Constructs the provider without starting its background refresh task.
-    /// Non-blocking. Call [`start`](Self::start) once storage is known to bind
-    /// it and begin refreshing.
+    /// Non-blocking. Call [`start`](Self::start) once the secret store is known
+    /// to bind it and begin refreshing.
     ///
     /// `http_client` is the shared client used for the JWT refresh fetch
     /// (typically supplied from the surrounding [`SdkContext`](crate::SdkContext)).
@@ -95,7 +95,7 @@ impl BreezJwtHeaderProvider {
         let inner = Arc::new(Inner {
             token: RwLock::new(None),
             api_key,
-            storage: OnceLock::new(),
+            secret_store: OnceLock::new(),
             http_client,
         });
 
@@ -108,17 +108,17 @@ impl BreezJwtHeaderProvider {
         })
     }
 
-    /// Binds `storage` (for warm-start and cross-restart persistence) and spawns
-    /// the refresh task. Idempotent: only the first call starts the task, so a
-    /// provider shared across SDK instances is started once. Pass `None` to run
-    /// in-memory only. Storage is bound before the task spawns, so its one-shot
-    /// `load_cached_token` observes it.
-    pub fn start(&self, storage: Option<Arc<dyn Storage>>) {
+    /// Binds `secret_store` (for warm-start and cross-restart persistence) and
+    /// spawns the refresh task. Idempotent: only the first call starts the task,
+    /// so a provider shared across SDK instances is started once. Pass `None` to
+    /// run in-memory only. The store is bound before the task spawns, so its
+    /// one-shot `load_cached_token` observes it.
+    pub fn start(&self, secret_store: Option<Arc<dyn SecretStore>>) {
         let Some(shutdown_rx) = self.pending_rx.lock().unwrap().take() else {
             return;
         };
-        if let Some(storage) = storage {
-            let _ = self.inner.storage.set(storage);
+        if let Some(secret_store) = secret_store {
+            let _ = self.inner.secret_store.set(secret_store);
         }
         spawn_refresh_task(Arc::clone(&self.inner), shutdown_rx);
     }
@@ -144,10 +144,10 @@ impl HeaderProvider for BreezJwtHeaderProvider {
 }
 
 async fn load_cached_token(inner: &Inner) -> bool {
-    let Some(storage) = inner.storage.get() else {
+    let Some(secret_store) = inner.secret_store.get() else {
         return false;
     };
-    let stored = match storage.get_cached_item(KEY_BREEZ_JWT.to_string()).await {
+    let stored = match secret_store.get_secret(KEY_BREEZ_JWT.to_string()).await {
         Ok(Some(token)) => token,
         Ok(None) => return false,
         Err(err) => {
@@ -181,9 +181,9 @@ async fn store_token(inner: &Inner, token: String) {
         token: token.clone(),
         exp,
     });
-    if let Some(storage) = inner.storage.get()
-        && let Err(err) = storage
-            .set_cached_item(KEY_BREEZ_JWT.to_string(), token)
+    if let Some(secret_store) = inner.secret_store.get()
+        && let Err(err) = secret_store
+            .set_secret(KEY_BREEZ_JWT.to_string(), token)
             .await
     {
         warn!("Could not persist JWT: {err}");
@@ -262,7 +262,7 @@ fn spawn_refresh_task(inner: Arc<Inner>, mut shutdown_rx: oneshot::Receiver<()>)
     let span = tracing::Span::current();
     tokio::spawn(
         async move {
-            // If a fresh token is already in storage, install it before the
+            // If a fresh token is already in the secret store, install it before the
             // first fetch so headers() can start serving it immediately.
             let has_cached = load_cached_token(&inner).await;
             if has_cached {
@@ -415,7 +415,8 @@ mod tests {
 #[cfg(all(test, not(target_family = "wasm")))]
 mod persistence_tests {
     use super::*;
-    use crate::persist::sqlite::SqliteStorage;
+    use crate::persist::{Storage, sqlite::SqliteStorage};
+    use crate::secret_store::StorageSecretStore;
     use platform_utils::create_http_client;
 
     /// Fresh on-disk `SQLite` storage in a unique temp directory.
@@ -430,12 +431,12 @@ mod persistence_tests {
     fn inner_with_storage(storage: Option<Arc<dyn Storage>>) -> Inner {
         let cell = OnceLock::new();
         if let Some(storage) = storage {
-            let _ = cell.set(storage);
+            let _ = cell.set(Arc::new(StorageSecretStore::new(storage)) as Arc<dyn SecretStore>);
         }
         Inner {
             token: RwLock::new(None),
             api_key: "test-key".to_string(),
-            storage: cell,
+            secret_store: cell,
             http_client: create_http_client(Some("jwt-test")),
         }
     }
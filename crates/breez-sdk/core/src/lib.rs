@@ -1,8 +1,13 @@
+mod backup;
 #[cfg(feature = "uniffi")]
 pub mod bindings;
 mod chain;
+mod clock;
 mod common;
+mod config_fingerprint;
 mod cross_chain;
+#[cfg(feature = "dev-tools")]
+mod dev_tools;
 mod error;
 mod events;
 mod issuer;
@@ -13,24 +18,32 @@ mod models;
 #[cfg(feature = "passkey")]
 pub mod passkey;
 mod persist;
+mod plugin;
 mod realtime_sync;
 mod sdk;
 mod sdk_builder;
 mod sdk_context;
+mod secret_store;
+mod service_status;
 mod session_store;
 pub mod signer;
 mod stable_balance;
 mod sync;
+mod sync_capture;
+#[cfg(feature = "token-announcements")]
+mod token_announcement;
 pub mod token_conversion;
 #[cfg(feature = "turnkey")]
 pub mod turnkey;
 mod utils;
+mod webhook;
 
 pub use chain::{
-    BitcoinChainService, ChainServiceError, Outspend, RecommendedFees, TxStatus, Utxo,
-    new_rest_chain_service,
+    BitcoinChainService, ChainServiceEndpoint, ChainServiceError, Outspend, RecommendedFees,
+    TxStatus, Utxo, new_rest_chain_service,
     rest_client::{ChainApiType, RestClientChainService},
 };
+pub use common::dns::DnsResolver;
 pub use common::rest::{RestClient, RestResponse};
 pub use common::{fiat::*, models::*, sync_storage};
 pub use cross_chain::{
@@ -38,29 +51,46 @@ pub use cross_chain::{
     CrossChainRoutePair, SourceAsset,
 };
 pub use error::{DepositClaimError, SdkError, SignerError};
-pub use events::{AutoOptimizationEvent, EventEmitter, EventListener, SdkEvent};
+pub use events::{
+    AutoOptimizationEvent, EventEmitter, EventFilter, EventListener, QueuedPaymentOutcome,
+    SdkEvent, SdkEventKind,
+};
 pub use issuer::*;
 pub use logger::DEFAULT_FILTER;
 pub use models::*;
 pub use persist::{
-    ConversionFilter, PaymentMetadata, SetLnurlMetadataItem, Storage, StorageError,
-    StorageListPaymentsRequest, StoragePaymentDetailsFilter, StoredCrossChainSwap,
-    UpdateDepositPayload,
+    ConversionFilter, PaymentCursor, PaymentMetadata, SetLnurlMetadataItem, Storage, StorageError,
+    StorageIntegrityIssue, StorageIntegrityIssueKind, StorageListPaymentsRequest,
+    StoragePaymentDetailsFilter, StoredCrossChainSwap, UpdateDepositPayload,
     backend::{
         PrebuiltBackend, ResolvedStores, StorageBackend, custom_storage, default_session_store,
+        storage_tenant_key,
     },
-    path::default_storage_path,
+    path::{default_storage_path, tenant_storage_path},
 };
+pub use plugin::{Plugin, PluginStorage};
 pub use sdk::{
-    BreezSdk, default_config, default_server_config, get_spark_status, init_logging, parse_input,
+    BreezSdk, decode_payment_request, default_config, default_profile_config,
+    default_server_config, discover_accounts, get_spark_status, init_logging, parse_input,
 };
 pub use sdk_builder::SdkBuilder;
 pub use sdk_context::{SdkContext, SdkContextConfig, new_shared_sdk_context};
+pub use secret_store::{SecretStore, SecretStoreError};
 pub use session_store::{Session, SessionStore, SessionStoreAdapter, SessionStoreError};
 pub use spark_wallet::{
     CombinedHeaderProvider, HeaderProvider, HeaderProviderError, PublicKey, account_master_key,
     identity_master_key, identity_public_key,
 };
+pub use sync_capture::{ReplayedSyncPass, SyncPassKind, SyncReplayReport, replay_sync_capture};
+
+#[cfg(feature = "dev-tools")]
+pub use dev_tools::*;
+
+#[cfg(feature = "token-announcements")]
+pub use token_announcement::{
+    GetTokenAnnouncementsRequest, GetTokenAnnouncementsResponse, PublishTokenAnnouncementRequest,
+    TokenAnnouncement,
+};
 
 #[cfg(feature = "postgres")]
 pub use persist::{
@@ -82,6 +112,9 @@ pub use {
 
 pub use sdk::{ExternalSigners, SigningOnlyExternalSigners, default_external_signers};
 
+#[cfg(feature = "test-utils")]
+pub use clock::{Clock, TestClock};
+
 #[cfg(feature = "test-utils")]
 pub use persist::tests as storage_tests;
 
@@ -12,10 +12,10 @@ use platform_utils::time::UNIX_EPOCH;
 use tracing::{debug, warn};
 
 use crate::{
-    AutoOptimizationEvent, Fee, Network, OnchainConfirmationSpeed, OptimizationOutcome, Payment,
-    PaymentDetails, PaymentMethod, PaymentStatus, PaymentType, SdkError, SendOnchainFeeQuote,
-    SendOnchainSpeedFeeQuote, SparkHtlcDetails, SparkHtlcStatus, SparkInvoicePaymentDetails,
-    TokenBalance, TokenMetadata,
+    AutoOptimizationEvent, DisplayAmount, Fee, Network, OnchainConfirmationSpeed,
+    OptimizationOutcome, Payment, PaymentDetails, PaymentMethod, PaymentStatus, PaymentType,
+    SdkError, SendOnchainFeeQuote, SendOnchainSpeedFeeQuote, SparkHtlcDetails, SparkHtlcStatus,
+    SparkInvoicePaymentDetails, TokenBalance, TokenMetadata,
 };
 
 /// Feb 1, 2026 00:00:00 UTC — transfers before this may lack HTLC data on the operator.
@@ -263,13 +263,22 @@ impl TryFrom<WalletTransfer> for Payment {
             TransferStatus::Expired | TransferStatus::Returned => PaymentStatus::Failed,
             _ => PaymentStatus::Pending,
         };
-        let (fees_sat, mut amount_sat) = match transfer.clone().user_request {
+        let (fees_sat, mut amount_sat) = match &transfer.user_request {
             Some(user_request) => match user_request {
                 SspUserRequest::LightningSendRequest(r) => {
                     // TODO: if we have the preimage it is not pending. This is a workaround
                     // until spark will implement incremental syncing based on updated time.
-                    if r.lightning_send_payment_preimage.is_some() {
+                    let has_preimage = r.lightning_send_payment_preimage.is_some()
+                        || transfer
+                            .htlc_preimage_request
+                            .as_ref()
+                            .is_some_and(|req| req.preimage.is_some());
+                    if has_preimage {
                         status = PaymentStatus::Completed;
+                    } else if transfer.status == TransferStatus::SenderKeyTweaked {
+                        // The SSP only claims the transfer once it has the preimage, so a
+                        // send is not completed before then
+                        status = PaymentStatus::Pending;
                     }
                     let fee_sat = r.fee.as_sats().unwrap_or(0);
                     (fee_sat, transfer.total_value_sat.saturating_sub(fee_sat))
@@ -311,6 +320,7 @@ impl TryFrom<WalletTransfer> for Payment {
             }
             amount_sat = transfer.total_value_sat;
         }
+        let display_amount = DisplayAmount::new(amount_sat.into(), details.as_ref());
 
         Ok(Payment {
             id: transfer.id.to_string(),
@@ -325,6 +335,10 @@ impl TryFrom<WalletTransfer> for Payment {
             method: PaymentMethod::from_transfer(&transfer),
             details,
             conversion_details: None,
+            imported: false,
+            display_amount,
+            group_id: None,
+            dispute: None,
         })
     }
 }
@@ -337,27 +351,31 @@ impl Payment {
     /// - The preimage is taken from `htlc_details` if present, otherwise from the payment.
     /// - If a preimage is available from either source, the HTLC status is set to
     ///   [`SparkHtlcStatus::PreimageShared`].
+    ///
+    /// The payment is only reported as completed once the preimage is known, so
+    /// completed Lightning sends always carry their preimage.
     pub fn from_lightning(
         payment: LightningSendPayment,
         amount_sat: u128,
         transfer_id: String,
         mut htlc_details: SparkHtlcDetails,
     ) -> Result<Self, SdkError> {
-        let mut status = match payment.status {
-            LightningSendStatus::LightningPaymentSucceeded => PaymentStatus::Completed,
-            LightningSendStatus::LightningPaymentFailed
-            | LightningSendStatus::TransferFailed
-            | LightningSendStatus::PreimageProvidingFailed
-            | LightningSendStatus::UserSwapReturnFailed
-            | LightningSendStatus::UserSwapReturned => PaymentStatus::Failed,
-            _ => PaymentStatus::Pending,
-        };
-        if payment.payment_preimage.is_some() {
-            status = PaymentStatus::Completed;
-        }
-
         reconcile_htlc_preimage(&mut htlc_details, payment.payment_preimage.as_deref());
 
+        let status = if htlc_details.preimage.is_some() {
+            PaymentStatus::Completed
+        } else {
+            match payment.status {
+                LightningSendStatus::LightningPaymentFailed
+                | LightningSendStatus::TransferFailed
+                | LightningSendStatus::PreimageProvidingFailed
+                | LightningSendStatus::UserSwapReturnFailed
+                | LightningSendStatus::UserSwapReturned => PaymentStatus::Failed,
+                // A succeeded send stays pending until its preimage is available
+                _ => PaymentStatus::Pending,
+            }
+        };
+
         let invoice_details = input::parse_invoice(&payment.encoded_invoice).ok_or(
             SdkError::Generic("Invalid invoice in LightnintSendPayment".to_string()),
         )?;
@@ -371,6 +389,7 @@ impl Payment {
             lnurl_receive_metadata: None,
             conversion_info: None,
         };
+        let display_amount = DisplayAmount::new(amount_sat, Some(&details));
 
         Ok(Payment {
             id: transfer_id,
@@ -382,10 +401,27 @@ impl Payment {
             method: PaymentMethod::Lightning,
             details: Some(details),
             conversion_details: None,
+            imported: false,
+            display_amount,
+            group_id: None,
+            dispute: None,
         })
     }
 }
 
+/// Why a Lightning send with `status` failed, if it did.
+pub(crate) fn lightning_send_failure_reason(status: &LightningSendStatus) -> Option<String> {
+    let reason = match status {
+        LightningSendStatus::LightningPaymentFailed => "The Lightning payment failed",
+        LightningSendStatus::TransferFailed => "The transfer to the SSP failed",
+        LightningSendStatus::PreimageProvidingFailed => "The SSP failed to provide the preimage",
+        LightningSendStatus::UserSwapReturned => "The payment failed and was returned",
+        LightningSendStatus::UserSwapReturnFailed => "The payment failed and returning it failed",
+        _ => return None,
+    };
+    Some(reason.to_string())
+}
+
 impl From<Network> for SparkNetwork {
     fn from(network: Network) -> Self {
         match network {
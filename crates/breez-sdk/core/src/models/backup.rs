@@ -0,0 +1,56 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use super::EmergencyExitPackage;
+
+#[derive(Debug, Error, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
+pub enum BackupStoreError {
+    #[error("Service connectivity: {0}")]
+    ServiceConnectivity(String),
+    #[error("Generic: {0}")]
+    Generic(String),
+}
+
+/// This interface is used to store the encrypted backups the SDK creates
+/// periodically, e.g. in the user's cloud storage.
+///
+/// `save` is called with the latest backup whenever the stored data changed.
+/// Only the latest backup needs to be kept.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait BackupStore: Send + Sync {
+    async fn save(&self, backup: Vec<u8>) -> Result<(), BackupStoreError>;
+}
+
+/// This interface is used to store the emergency exit packages the SDK
+/// refreshes whenever the wallet's leaves change.
+///
+/// Only the latest package is usable, older ones exit leaves that are gone.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait EmergencyExitStore: Send + Sync {
+    async fn save(&self, package: EmergencyExitPackage) -> Result<(), BackupStoreError>;
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct BackupResponse {
+    /// The encrypted backup, which only this wallet's seed can decrypt
+    pub backup: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RestoreRequest {
+    /// A backup returned by `backup` or passed to a `BackupStore`
+    pub backup: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RestoreResponse {
+    pub payments_count: u32,
+    pub deposits_count: u32,
+    pub contacts_count: u32,
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
+pub enum ClaimFeeSponsorError {
+    #[error("Service connectivity: {0}")]
+    ServiceConnectivity(String),
+    #[error("Generic: {0}")]
+    Generic(String),
+}
+
+/// The claim fee of a deposit a sponsor is asked to pay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ClaimFeeSponsorRequest {
+    pub deposit_txid: String,
+    pub deposit_vout: u32,
+    pub deposit_amount_sats: u64,
+    /// The claim fee the SSP deducts from the deposit
+    pub fee_sats: u64,
+    /// The Spark address of the wallet the fee is paid to
+    pub spark_address: String,
+}
+
+/// This interface is used to let a third party, such as the wallet provider,
+/// pay the claim fees of deposits.
+///
+/// The SSP still deducts the fee from the deposit. The sponsor then pays the
+/// wallet the same amount with a Spark transfer.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait ClaimFeeSponsor: Send + Sync {
+    /// Returns whether the sponsor pays the claim fee of the deposit. Only
+    /// asked for deposits whose fee the claim policy allows, as the fee is
+    /// deducted from the deposit before the sponsor reimburses it.
+    async fn will_sponsor(&self, request: ClaimFeeSponsorRequest) -> bool;
+
+    /// Pays the claim fee of a claimed deposit to the wallet. Returns the id
+    /// of the Spark transfer paying it.
+    ///
+    /// When it fails, it's called again with the same request on the next
+    /// deposit claims, until it succeeds. Sponsors should pay each deposit
+    /// only once, e.g. by keying their payments by the deposit outpoint.
+    async fn pay_claim_fee(
+        &self,
+        request: ClaimFeeSponsorRequest,
+    ) -> Result<String, ClaimFeeSponsorError>;
+}
+
+/// A claim fee paid by the sponsor, tying the deposit claim to the transfer
+/// reimbursing its fee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ClaimFeeSponsorship {
+    pub deposit_txid: String,
+    pub deposit_vout: u32,
+    /// The id of the payment claiming the deposit
+    pub claim_payment_id: String,
+    pub fee_sats: u64,
+    /// The id of the sponsor's payment of the fee
+    pub sponsor_payment_id: String,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+}
+
+/// Request to list claim fee sponsorships with optional pagination.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ListClaimFeeSponsorshipsRequest {
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub offset: Option<u32>,
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ListClaimFeeSponsorshipsResponse {
+    /// The sponsored claim fees, oldest first
+    pub sponsorships: Vec<ClaimFeeSponsorship>,
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A party to a payment, as required by travel-rule regulations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ComplianceParty {
+    /// Full name of the natural or legal person
+    pub name: String,
+    /// Account or wallet identifier of the party at its provider
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub account_id: Option<String>,
+    /// Geographic address
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub physical_address: Option<String>,
+    /// National identification number, e.g. a passport or registration number
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub national_id: Option<String>,
+    /// Date of birth in ISO 8601 format (YYYY-MM-DD)
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub date_of_birth: Option<String>,
+    /// Name of the virtual asset service provider holding the party's account
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub vasp_name: Option<String>,
+}
+
+/// Originator and beneficiary information attached to an outgoing payment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ComplianceMetadata {
+    pub originator: ComplianceParty,
+    pub beneficiary: ComplianceParty,
+    /// Reference of the transfer at the travel-rule provider, if already known
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub reference: Option<String>,
+}
+
+/// An outgoing payment carrying compliance metadata, passed to the
+/// [`TravelRuleProvider`] before it is sent.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TravelRuleTransfer {
+    /// The payment request or address being paid
+    pub destination: String,
+    /// Amount in satoshis or token base units
+    pub amount: u128,
+    /// The token being sent. Not set for Bitcoin payments.
+    pub token_identifier: Option<String>,
+    /// The idempotency key of the send, if set. A retried send is submitted again
+    /// with the same key.
+    pub idempotency_key: Option<String>,
+    pub metadata: ComplianceMetadata,
+}
+
+#[derive(Debug, Error, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
+pub enum TravelRuleProviderError {
+    #[error("Service connectivity: {0}")]
+    ServiceConnectivity(String),
+    #[error("Rejected: {0}")]
+    Rejected(String),
+    #[error("Generic: {0}")]
+    Generic(String),
+}
+
+/// This interface is used to pass compliance metadata of outgoing payments to
+/// an external travel-rule provider.
+///
+/// `submit` is called before a payment with compliance metadata is sent; if the
+/// implementation returns an error the payment is cancelled.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait TravelRuleProvider: Send + Sync {
+    async fn submit(&self, transfer: TravelRuleTransfer) -> Result<(), TravelRuleProviderError>;
+}
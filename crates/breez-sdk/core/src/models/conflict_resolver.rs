@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+/// A synced record changed on this device and on another device before the
+/// local change was pushed.
+///
+/// Field values are JSON-encoded.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SyncConflict {
+    /// The record type, such as `PaymentMetadata` or `Contact`
+    pub record_type: String,
+    /// The identifier of the record within its type
+    pub record_id: String,
+    /// The record as last synced, before either change
+    pub base_fields: HashMap<String, String>,
+    /// The fields changed on this device
+    pub local_fields: HashMap<String, String>,
+    /// The record as changed on the other device
+    pub remote_fields: HashMap<String, String>,
+}
+
+/// How real-time sync resolves records changed on several devices at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ConflictResolutionStrategy {
+    /// Fields changed on this device win. Fields changed only on the other
+    /// device are kept.
+    #[default]
+    FieldMerge,
+    /// The record as known on this device wins, including fields the other
+    /// device changed.
+    LastWriterWins,
+}
+
+#[derive(Debug, Error, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
+pub enum ConflictResolverError {
+    #[error("Generic: {0}")]
+    Generic(String),
+}
+
+/// This interface is used to resolve synced records changed on several devices
+/// at once.
+///
+/// If `resolve` returns an error, the conflict is resolved by field merge.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait ConflictResolver: Send + Sync {
+    /// Returns the JSON-encoded fields of the resolved record. Fields left out
+    /// keep their remote value.
+    async fn resolve(
+        &self,
+        conflict: SyncConflict,
+    ) -> Result<HashMap<String, String>, ConflictResolverError>;
+}
@@ -1,5 +1,15 @@
 pub(crate) mod adaptors;
+pub mod backup;
+pub mod claim_fee_sponsor;
+pub mod compliance;
+pub mod conflict_resolver;
+pub mod payment_approver;
 pub mod payment_observer;
+pub use backup::*;
+pub use claim_fee_sponsor::*;
+pub use compliance::*;
+pub use conflict_resolver::*;
+pub use payment_approver::*;
 pub use payment_observer::*;
 
 // Re-export public conversion types from the conversion module
@@ -17,16 +27,20 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
     str::FromStr,
+    time::Duration,
 };
 
 use crate::{
-    BitcoinAddressDetails, BitcoinChainService, BitcoinNetwork, Bolt11InvoiceDetails,
+    Bip21Details, BitcoinAddressDetails, BitcoinChainService, BitcoinNetwork, Bolt11InvoiceDetails,
     ExternalInputParser, FiatCurrency, LnurlPayRequestDetails, LnurlWithdrawRequestDetails, Rate,
-    SdkError, SparkInvoiceDetails, SuccessAction, SuccessActionProcessed,
+    SdkError, SparkInvoiceDetails, StorageIntegrityIssue, SuccessAction, SuccessActionProcessed,
     cross_chain::{CrossChainFeeMode, CrossChainProviderContext, CrossChainRoutePair},
     error::DepositClaimError,
 };
 
+/// How much longer background polls wait in low-data mode.
+const LOW_DATA_INTERVAL_FACTOR: u32 = 4;
+
 /// A list of external input parsers that are used by default.
 /// To opt-out, set `use_default_external_input_parsers` in [Config] to false.
 pub const DEFAULT_EXTERNAL_INPUT_PARSERS: &[(&str, &str, &str)] = &[
@@ -84,6 +98,33 @@ pub struct ConnectRequest {
     pub storage_dir: String,
 }
 
+/// Request to find the accounts derived from a seed that have Spark activity.
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DiscoverAccountsRequest {
+    pub config: Config,
+    pub seed: Seed,
+    /// The number of consecutive accounts without activity after which the
+    /// scan stops. Defaults to 5.
+    pub gap_limit: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DiscoverAccountsResponse {
+    /// The accounts with activity, by ascending account number.
+    pub accounts: Vec<AccountActivity>,
+}
+
+/// An account derived from a seed that has Spark activity.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct AccountActivity {
+    /// The account number to pass to `SdkBuilder::with_account_number`.
+    pub account_number: u32,
+    /// The identity public key of the account, hex encoded.
+    pub identity_public_key: String,
+}
+
 /// Request object for connecting to the Spark network using an external signer.
 ///
 /// This allows using a custom signer implementation instead of providing a seed directly.
@@ -253,6 +294,108 @@ pub struct Payment {
     pub details: Option<PaymentDetails>,
     /// If set, this payment involved a conversion before the payment
     pub conversion_details: Option<ConversionDetails>,
+    /// Whether the payment was imported from the history of another Breez SDK
+    #[serde(default)]
+    pub imported: bool,
+    /// The amount and the asset it is denominated in, for rendering any payment
+    /// without matching on its method
+    #[serde(default)]
+    pub display_amount: DisplayAmount,
+    /// Identifier shared by the payments making up one user action, such as a
+    /// payment and its conversions or the legs of a split payment
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Set when the payment was marked as disputed by the merchant
+    #[serde(default)]
+    pub dispute: Option<PaymentDispute>,
+}
+
+/// A merchant annotation tracking a dispute raised on a payment, such as a
+/// customer refund request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaymentDispute {
+    pub status: DisputeStatus,
+    /// Free-form note describing the dispute or its resolution
+    pub note: Option<String>,
+    /// When the payment was marked as disputed, as a UNIX timestamp in seconds
+    pub disputed_at: u64,
+    /// When the dispute was resolved, as a UNIX timestamp in seconds
+    pub resolved_at: Option<u64>,
+    /// When the dispute was last changed, as a UNIX timestamp in seconds
+    pub updated_at: u64,
+}
+
+/// The status of a [`PaymentDispute`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DisputeStatus {
+    /// The dispute is open
+    Disputed,
+    /// The dispute was settled
+    Resolved,
+}
+
+impl fmt::Display for DisputeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisputeStatus::Disputed => write!(f, "disputed"),
+            DisputeStatus::Resolved => write!(f, "resolved"),
+        }
+    }
+}
+
+impl FromStr for DisputeStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "disputed" => DisputeStatus::Disputed,
+            "resolved" => DisputeStatus::Resolved,
+            _ => return Err(format!("Invalid dispute status '{s}'")),
+        })
+    }
+}
+
+/// An amount together with the asset it is denominated in
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DisplayAmount {
+    /// Amount in the smallest unit of the asset: sats or token base units
+    pub value: u128,
+    pub asset: DisplayAsset,
+}
+
+impl DisplayAmount {
+    /// Derives the display amount of a payment from its amount and details
+    pub fn new(amount: u128, details: Option<&PaymentDetails>) -> Self {
+        let asset = match details {
+            Some(PaymentDetails::Token { metadata, .. }) => DisplayAsset::Token {
+                identifier: metadata.identifier.clone(),
+                ticker: metadata.ticker.clone(),
+                decimals: metadata.decimals,
+            },
+            _ => DisplayAsset::Bitcoin,
+        };
+        Self {
+            value: amount,
+            asset,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DisplayAsset {
+    /// Amounts are in sats
+    #[default]
+    Bitcoin,
+    Token {
+        identifier: String,
+        ticker: String,
+        /// Number of decimals of the token, to convert base units for display
+        decimals: u32,
+    },
 }
 
 impl Payment {
@@ -588,7 +731,13 @@ impl FromStr for Network {
 pub struct Config {
     pub api_key: Option<String>,
     pub network: Network,
+    /// The interval between scheduled syncs, in seconds. Set to `0` to only
+    /// sync on wallet events and [`trigger_sync`](crate::BreezSdk::trigger_sync) hints.
     pub sync_interval_secs: u32,
+    /// How long [`trigger_sync`](crate::BreezSdk::trigger_sync) waits for
+    /// further hints before syncing, in seconds, so a burst of hints runs a
+    /// single sync.
+    pub sync_trigger_debounce_secs: u32,
 
     // The maximum fee that can be paid for a static deposit claim
     // If not set then any fee is allowed
@@ -614,6 +763,15 @@ pub struct Config {
     /// Url to use for the real-time sync server. Defaults to the Breez real-time sync server.
     pub real_time_sync_server_url: Option<String>,
 
+    /// Whether to receive the Spark server events over the coordinator's
+    /// gRPC-web WebSocket endpoint.
+    ///
+    /// When enabled, the SDK subscribes over a WebSocket instead of the
+    /// gRPC(-web) stream, which some CDNs and proxies break. If the WebSocket
+    /// repeatedly fails to connect, the SDK falls back to gRPC. Requires the
+    /// `websocket-events` feature. Defaults to `false`.
+    pub event_stream_websocket: bool,
+
     /// Whether the Spark private mode is enabled by default.
     ///
     /// If set to true, the Spark private mode will be enabled on the first
@@ -653,6 +811,18 @@ pub struct Config {
     /// Default is 4. Increase for server environments with high incoming payment volume.
     pub max_concurrent_claims: u32,
 
+    /// Maximum number of pending transfers claimed per batch.
+    ///
+    /// When the wallet comes back online with many pending transfers, claims are
+    /// processed in batches and the claimed transfers are reported after each batch.
+    /// Default is 50.
+    pub claim_batch_size: u32,
+
+    /// Minimum delay in milliseconds between two claim batches.
+    ///
+    /// Limits the rate at which claims are sent to the operators. Default is 0 (no delay).
+    pub claim_batch_interval_ms: u32,
+
     /// Optional custom Spark environment configuration.
     ///
     /// When set, overrides the default Spark operator pool, service provider,
@@ -694,6 +864,170 @@ pub struct Config {
     /// run background work (e.g. web sockets), so enabling is left to the
     /// caller. Cross-chain sends are only supported on mainnet.
     pub cross_chain_config: Option<CrossChainConfig>,
+
+    /// Template for the description of the Bolt11 and Spark invoices created by
+    /// `receive_payment`. Defaults to `None`, which uses the requested description as is.
+    pub invoice_description_template: Option<InvoiceDescriptionTemplate>,
+
+    /// Endpoint the SDK events are posted to. Defaults to `None`, which
+    /// delivers events to the registered [`EventListener`](crate::EventListener)s only.
+    pub webhook_config: Option<WebhookConfig>,
+
+    /// Decides which deposits are claimed automatically. Defaults to `None`,
+    /// which claims deposits whose fee is within `max_deposit_claim_fee`.
+    pub deposit_claim_policy: Option<DepositClaimPolicy>,
+
+    /// Url of a service status feed announcing maintenance windows of the
+    /// Spark operators and service provider. Defaults to `None`, which disables it.
+    ///
+    /// The feed is a JSON document of the form
+    /// `{"maintenance_windows": [{"starts_at": 1700000000, "ends_at": 1700003600, "message": "..."}]}`
+    /// with timestamps in seconds since the Unix epoch.
+    pub service_status_url: Option<String>,
+    /// Url of the historical price API used to value payments at the time they
+    /// were made, following the mempool.space one. Defaults to the mempool.space
+    /// API. Setting it to `None` disables historical fiat values.
+    pub historical_rates_url: Option<String>,
+    /// Reduces the data used by the SDK, for metered connections. Defaults to `false`.
+    ///
+    /// When enabled, scheduled syncs and the background checks of pending
+    /// payments run four times less often, and the service status feed is
+    /// only fetched by `get_service_status`.
+    pub low_data_mode: bool,
+    /// How the payment history of a wallet is synced the first time.
+    /// Defaults to [`SyncMode::Full`].
+    pub sync_mode: SyncMode,
+}
+
+/// How the payment history of a wallet is synced the first time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum SyncMode {
+    /// The whole history is synced before the wallet is reported as synced
+    #[default]
+    Full,
+    /// Only the latest `recent_payments` payments and the balance are synced
+    /// before the wallet is reported as synced. Older payments are backfilled
+    /// in the background, reporting their progress with
+    /// [`SdkEvent::HistoryBackfillProgress`](crate::SdkEvent::HistoryBackfillProgress).
+    ///
+    /// Only applies to a wallet that has not synced its history yet, with
+    /// background tasks enabled.
+    Fast { recent_payments: u32 },
+}
+
+/// A maintenance window announced by the service status feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct MaintenanceWindow {
+    /// Start of the window, in seconds since the Unix epoch
+    pub starts_at: u64,
+    /// End of the window, in seconds since the Unix epoch
+    pub ends_at: u64,
+    /// Details about the maintenance, if announced
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Response of `get_service_status`
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetServiceStatusResponse {
+    /// The maintenance windows in progress or upcoming
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// The maintenance window in progress, if any. While it lasts, the SDK
+    /// defers its scheduled syncs and reports network failures as
+    /// [`SdkError::ServiceMaintenance`].
+    pub active_maintenance: Option<MaintenanceWindow>,
+}
+
+/// Bytes exchanged with a service since the SDK was started. Counts cover
+/// request and response payloads, not TLS or transport framing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DataUsage {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+/// Response of `get_data_usage`
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetDataUsageResponse {
+    /// Calls to the Spark operators
+    pub operator: DataUsage,
+    /// Calls to the Spark service provider
+    pub ssp: DataUsage,
+    /// Calls to the chain service. Not counted for a chain service supplied
+    /// with `SdkBuilder::with_chain_service`.
+    pub chain: DataUsage,
+    /// Calls to the real-time sync server
+    pub sync: DataUsage,
+}
+
+/// Configuration for delivering SDK events to an HTTPS endpoint.
+///
+/// Every event received by external listeners is posted as JSON. Each request
+/// carries an `X-Breez-Timestamp` header and an `X-Breez-Signature` header
+/// holding the hex encoded HMAC-SHA256 of `"{timestamp}.{body}"` keyed with
+/// the [`secret`](WebhookConfig::secret). Failed deliveries are retried with
+/// exponential backoff.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WebhookConfig {
+    /// The HTTPS URL the events are posted to
+    pub url: String,
+    /// The secret the payloads are signed with
+    pub secret: String,
+    /// Maximum number of delivery attempts per event. Defaults to 5.
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub max_attempts: Option<u32>,
+}
+
+/// Settings to change on a connected SDK with `update_config`.
+///
+/// Fields left `None` keep their current value.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ConfigPatch {
+    /// The network can't be changed at runtime. The patch is rejected with
+    /// [`SdkError::ImmutableConfig`] if this differs from the current network.
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub network: Option<Network>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub sync_interval_secs: Option<u32>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub sync_trigger_debounce_secs: Option<u32>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub max_deposit_claim_fee: Option<MaxFee>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub prefer_spark_over_lightning: Option<bool>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub invoice_description_template: Option<InvoiceDescriptionTemplate>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub webhook_config: Option<WebhookConfig>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub deposit_claim_policy: Option<DepositClaimPolicy>,
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub low_data_mode: Option<bool>,
+}
+
+/// Template for the description of generated invoices.
+///
+/// The template supports the following placeholders:
+/// - `{app_name}`: the configured [`app_name`](InvoiceDescriptionTemplate::app_name)
+/// - `{description}`: the description given in the receive request
+/// - `{order_ref}`: the [`order_ref`](ReceivePaymentRequest::order_ref) of the receive request
+///
+/// Placeholders without a value are left empty. A Bolt11 description that
+/// exceeds the invoice description limit is committed to by its hash instead.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct InvoiceDescriptionTemplate {
+    /// The template, e.g. `"{app_name}: {description} (order {order_ref})"`
+    pub template: String,
+    /// The value of the `{app_name}` placeholder
+    pub app_name: String,
 }
 
 /// Configuration for cross-chain sends.
@@ -718,6 +1052,22 @@ pub struct CrossChainConfig {
     pub default_target_overpay_bps: Option<u32>,
 }
 
+/// A named set of config defaults for a common use case, used with
+/// `default_profile_config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ConfigProfile {
+    /// Syncs more often and keeps more leaves, so payments rarely wait for a
+    /// swap or a claim.
+    LowLatency,
+    /// Prefers Spark transfers over Lightning, keeps fewer leaves to lower
+    /// the unilateral exit cost, and claims deposits at a low fee rate.
+    LowFee,
+    /// Enables the Spark private mode, prefers Lightning over Spark transfers
+    /// and disables the real-time sync server and the lightning address.
+    Privacy,
+}
+
 /// Configuration for leaf optimization.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -904,16 +1254,89 @@ pub struct SparkSspConfig {
 }
 
 impl Config {
+    /// The deposit claim policy in effect: `deposit_claim_policy` if set,
+    /// otherwise the one given by `max_deposit_claim_fee`.
+    pub(crate) fn effective_deposit_claim_policy(&self) -> DepositClaimPolicy {
+        match (&self.deposit_claim_policy, &self.max_deposit_claim_fee) {
+            (Some(policy), _) => policy.clone(),
+            (None, Some(max_fee)) => DepositClaimPolicy::MaxFee {
+                max_fee: max_fee.clone(),
+            },
+            (None, None) => DepositClaimPolicy::Manual,
+        }
+    }
+
+    /// The interval of a background poll, lengthened in low-data mode.
+    pub(crate) fn polling_interval(&self, interval: Duration) -> Duration {
+        if self.low_data_mode {
+            interval.saturating_mul(LOW_DATA_INTERVAL_FACTOR)
+        } else {
+            interval
+        }
+    }
+
     /// Validates the configuration.
     ///
     /// Returns an error if any configuration values are invalid.
     pub fn validate(&self) -> Result<(), SdkError> {
+        if let Some(webhook) = &self.webhook_config {
+            if !webhook.url.starts_with("https://") {
+                return Err(SdkError::InvalidInput(
+                    "webhook_config url must use https".to_string(),
+                ));
+            }
+            if webhook.secret.is_empty() {
+                return Err(SdkError::InvalidInput(
+                    "webhook_config secret must not be empty".to_string(),
+                ));
+            }
+            if webhook.max_attempts == Some(0) {
+                return Err(SdkError::InvalidInput(
+                    "webhook_config max_attempts must be greater than 0".to_string(),
+                ));
+            }
+        }
+
+        if self.event_stream_websocket && !cfg!(feature = "websocket-events") {
+            return Err(SdkError::InvalidInput(
+                "event_stream_websocket requires the websocket-events feature".to_string(),
+            ));
+        }
+
+        if let Some(DepositClaimPolicy::MaxFeePercent { percent }) = &self.deposit_claim_policy
+            && !(percent.is_finite() && *percent > 0.0)
+        {
+            return Err(SdkError::InvalidInput(
+                "deposit_claim_policy percent must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some(template) = &self.invoice_description_template
+            && template.template.trim().is_empty()
+        {
+            return Err(SdkError::InvalidInput(
+                "invoice_description_template must not be empty".to_string(),
+            ));
+        }
+
         if self.max_concurrent_claims == 0 {
             return Err(SdkError::InvalidInput(
                 "max_concurrent_claims must be greater than 0".to_string(),
             ));
         }
 
+        if self.claim_batch_size == 0 {
+            return Err(SdkError::InvalidInput(
+                "claim_batch_size must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.sync_mode == (SyncMode::Fast { recent_payments: 0 }) {
+            return Err(SdkError::InvalidInput(
+                "sync_mode recent_payments must be greater than 0".to_string(),
+            ));
+        }
+
         if let Some(sb) = &self.stable_balance_config {
             if sb.tokens.is_empty() {
                 return Err(SdkError::InvalidInput(
@@ -1068,6 +1491,49 @@ impl MaxFee {
     }
 }
 
+/// Which deposits the SDK claims automatically.
+///
+/// Deposits that are not claimed automatically are reported in
+/// [`SdkEvent::UnclaimedDeposits`](crate::SdkEvent::UnclaimedDeposits), with the
+/// reason in their `claim_error`, and can be claimed with `claim_deposit`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DepositClaimPolicy {
+    /// Claim every deposit, whatever the fee
+    Always,
+    /// Never claim automatically
+    Manual,
+    /// Claim deposits whose fee is within `max_fee`
+    MaxFee { max_fee: MaxFee },
+    /// Claim deposits whose fee is at most `percent` of the deposit value
+    MaxFeePercent { percent: f64 },
+}
+
+impl DepositClaimPolicy {
+    /// Returns the maximum fee allowed to claim a deposit of `value_sats`, or
+    /// `None` if the deposit can't be claimed automatically.
+    pub(crate) async fn max_fee(
+        &self,
+        value_sats: u64,
+        client: &dyn BitcoinChainService,
+    ) -> Result<Option<Fee>, SdkError> {
+        match self {
+            DepositClaimPolicy::Always => Ok(Some(Fee::Fixed { amount: value_sats })),
+            DepositClaimPolicy::Manual => Ok(None),
+            DepositClaimPolicy::MaxFee { max_fee } => Ok(Some(max_fee.to_fee(client).await?)),
+            DepositClaimPolicy::MaxFeePercent { percent } => {
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_precision_loss,
+                    clippy::cast_sign_loss
+                )]
+                let amount = (value_sats as f64 * percent / 100.0).floor() as u64;
+                Ok(Some(Fee::Fixed { amount }))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum Fee {
@@ -1096,6 +1562,11 @@ pub struct DepositInfo {
     pub refund_tx: Option<String>,
     pub refund_tx_id: Option<String>,
     pub claim_error: Option<DepositClaimError>,
+    /// The maximum fee this deposit is claimed with, overriding the claim
+    /// policy. Set with `set_deposit_claim_override` or by a `claim_deposit`
+    /// that failed on the fee.
+    #[serde(default)]
+    pub claim_max_fee: Option<MaxFee>,
 }
 
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -1106,6 +1577,18 @@ pub struct ClaimDepositRequest {
     pub max_fee: Option<MaxFee>,
 }
 
+/// Sets the maximum fee a single deposit is claimed with, overriding the
+/// claim policy for it.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SetDepositClaimOverrideRequest {
+    pub txid: String,
+    pub vout: u32,
+    /// The maximum claim fee. `None` removes the override.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub max_fee: Option<MaxFee>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct ClaimDepositResponse {
@@ -1128,6 +1611,30 @@ pub struct RefundDepositResponse {
     pub tx_hex: String,
 }
 
+/// The fee a deposit would currently be claimed with
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct EstimateClaimDepositFeeResponse {
+    /// The claim fee quoted by the SSP, deducted from the deposit
+    pub fee_sats: u64,
+    /// The fee rate the quoted fee amounts to
+    pub fee_rate_sat_per_vbyte: u64,
+    /// The maximum fee allowed by the request, or by the claim policy without
+    /// one. `None` if no fee is allowed.
+    pub max_fee_sats: Option<u64>,
+    /// Whether the quoted fee is within the maximum fee
+    pub within_max_fee: bool,
+}
+
+/// The fee a deposit would be refunded with
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct EstimateRefundDepositFeeResponse {
+    pub fee_sats: u64,
+    /// The amount sent to the destination address
+    pub refund_amount_sats: u64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct ListUnclaimedDepositsRequest {}
@@ -1216,6 +1723,32 @@ pub struct GetInfoRequest {
     pub ensure_synced: Option<bool>,
 }
 
+/// Accuracy of the Lightning fee estimates returned by `prepare_send_payment`
+/// and `prepare_lnurl_pay`, compared with the fees the payments actually paid.
+///
+/// Only the most recent Lightning sends are tracked. Errors are positive when the
+/// actual fee was higher than the estimate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FeeEstimationStats {
+    /// Number of completed payments the stats are computed from
+    pub sample_count: u32,
+    /// Number of tracked payments that are still pending
+    pub pending_count: u32,
+    /// Number of payments that paid more than estimated
+    pub underestimated_count: u32,
+    /// Number of payments that paid less than estimated
+    pub overestimated_count: u32,
+    pub mean_estimated_fee_sats: f64,
+    pub mean_actual_fee_sats: f64,
+    /// Mean of the absolute difference between the actual and the estimated fee
+    pub mean_absolute_error_sats: f64,
+    /// Mean of `(actual - estimated) / estimated`, over payments with a non-zero estimate
+    pub mean_relative_error: f64,
+    /// Largest `(actual - estimated) / estimated`, over payments with a non-zero estimate
+    pub max_relative_error: f64,
+}
+
 /// Response containing the balance of the wallet
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -1226,6 +1759,9 @@ pub struct GetInfoResponse {
     pub balance_sats: u64,
     /// The balances of the tokens in the wallet keyed by the token identifier
     pub token_balances: HashMap<String, TokenBalance>,
+    /// Whether only the latest payments are synced yet, while the older ones
+    /// are backfilled. See [`SyncMode::Fast`].
+    pub history_partially_synced: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1259,6 +1795,161 @@ pub struct SyncWalletRequest {}
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct SyncWalletResponse {}
 
+/// Request to sync the wallet in the background, hinting why a sync is due
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TriggerSyncRequest {
+    pub trigger: SyncTrigger,
+}
+
+/// Why the app hints that a sync is due
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum SyncTrigger {
+    /// The app came to the foreground. Skipped if the wallet synced within
+    /// [`Config::sync_interval_secs`].
+    AppForeground,
+    /// The app received a push notification, e.g. of an incoming payment.
+    /// Always syncs.
+    PushNotification,
+}
+
+/// Request to process a push notification received by the app
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ProcessNotificationRequest {
+    /// The JSON payload of the notification. It references the incoming
+    /// transfer as `{"type": "transfer", "transfer_id": ...}` or the deposit
+    /// as `{"type": "deposit", "txid": ..., "vout": ...}`.
+    pub payload: String,
+}
+
+/// Response from processing a push notification
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ProcessNotificationResponse {
+    /// The payment resulting from the claim, `None` if the transfer isn't
+    /// claimable yet
+    pub payment: Option<Payment>,
+}
+
+/// Response from stopping a sync capture
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct StopSyncCaptureResponse {
+    /// The captured sync passes as JSON, to be replayed with
+    /// [`crate::replay_sync_capture`]. Preimages and descriptions are redacted.
+    pub capture: String,
+}
+
+/// The recovery action recommended for a payment stuck in `Pending`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum StuckPaymentRecommendation {
+    /// The stored status may be outdated. Syncing the wallet refreshes it.
+    Sync,
+    /// The payment has been pending for much longer than expected. Contact
+    /// support with the payment id.
+    ContactSupport,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RecoverStuckPaymentRequest {
+    pub payment_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RecoverStuckPaymentResponse {
+    /// The payment after the recovery action
+    pub payment: Payment,
+    /// The recommendation if the payment is still stuck
+    pub recommendation: Option<StuckPaymentRecommendation>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetWithdrawStatusRequest {
+    /// The id of a withdrawal payment
+    pub payment_id: String,
+}
+
+/// The on-chain status of the exit transaction of a withdrawal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetWithdrawStatusResponse {
+    pub tx_id: String,
+    pub confirmed: bool,
+    /// The height of the block the transaction confirmed in
+    pub block_height: Option<u32>,
+    /// The confirmations of the transaction, estimated from its block time
+    pub estimated_confirmations: Option<u64>,
+}
+
+/// The stages of a Lightning receive, from the invoice to its settlement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ReceiveStage {
+    /// The invoice was created and is waiting to be paid
+    InvoiceIssued,
+    /// The SSP received the payer's HTLC
+    HtlcSeen,
+    /// The SDK started claiming the Spark transfer of the payment
+    ClaimAttempted,
+    /// The payment was claimed
+    Settled,
+    /// The SSP failed the payment
+    Failed,
+}
+
+/// A stage reached by a Lightning receive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ReceiveStageUpdate {
+    pub stage: ReceiveStage,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// The status reported by the SSP, e.g. `TRANSFER_CREATION_FAILED`
+    pub ssp_status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DiagnoseReceiveRequest {
+    /// A BOLT11 invoice created by `receive_payment`
+    pub invoice: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DiagnoseReceiveResponse {
+    pub payment_hash: String,
+    /// The latest stage the receive reached
+    pub stage: ReceiveStage,
+    /// The current status of the receive at the SSP
+    pub ssp_status: Option<String>,
+    /// The stages reached, oldest first
+    pub trail: Vec<ReceiveStageUpdate>,
+    pub invoice_expired: bool,
+    /// Where the receive is stuck, or how it ended
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CheckStorageIntegrityRequest {
+    /// Whether to repair the issues found and rebuild the derived state
+    #[cfg_attr(feature = "uniffi", uniffi(default = false))]
+    pub repair: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CheckStorageIntegrityResponse {
+    pub issues: Vec<StorageIntegrityIssue>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum ReceivePaymentMethod {
@@ -1278,8 +1969,9 @@ pub enum ReceivePaymentMethod {
     },
     BitcoinAddress {
         /// If true, rotate to a new deposit address. Previous ones remain valid.
-        /// If false or absent, return the existing address (creating one if none
-        /// exists yet).
+        /// If false, return the existing address (creating one if none exists yet).
+        /// If absent, return the existing address as long as it hasn't received
+        /// a deposit, and rotate to a new one otherwise.
         new_address: Option<bool>,
     },
     Bolt11Invoice {
@@ -1291,10 +1983,27 @@ pub enum ReceivePaymentMethod {
         /// The payer's HTLC will be held until the preimage is provided via
         /// `claim_htlc_payment` or the HTLC expires.
         payment_hash: Option<String>,
+        /// Minimum amount the payer can send to an amountless invoice. Payments
+        /// outside the limits aren't claimed, and the SSP refunds the payer once
+        /// their HTLC expires.
+        min_amount_sats: Option<u64>,
+        /// Maximum amount the payer can send to an amountless invoice.
+        max_amount_sats: Option<u64>,
+    },
+    /// A Spark invoice requesting a fixed amount of a token. Incoming payments
+    /// of this token and amount are matched to the invoice, even if the payer
+    /// sends to the Spark address instead of paying the invoice.
+    TokenInvoice {
+        /// The identifier of the token to receive
+        token_identifier: String,
+        /// Amount to receive, in the token base units
+        amount: u128,
+        /// A memo to embed in the invoice
+        memo: Option<String>,
     },
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum SendPaymentMethod {
     BitcoinAddress {
@@ -1386,9 +2095,45 @@ impl SendOnchainSpeedFeeQuote {
     }
 }
 
+/// The fees of a send, broken down by what they pay for.
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FeeBreakdown {
+    /// A Lightning send through the Spark Service Provider.
+    Lightning {
+        /// Fee the SSP charges to pay the invoice, in sats. It includes the
+        /// routing fee budget, which the SSP doesn't report separately.
+        ssp_fee_sats: u64,
+    },
+    /// An on-chain send, for each confirmation speed of the fee quote.
+    BitcoinAddress {
+        speed_fast: OnchainFeeBreakdown,
+        speed_medium: OnchainFeeBreakdown,
+        speed_slow: OnchainFeeBreakdown,
+    },
+}
+
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnchainFeeBreakdown {
+    /// Fee the SSP charges for the withdrawal, in sats
+    pub ssp_fee_sat: u64,
+    /// Share of the L1 transaction fee, in sats
+    pub l1_broadcast_fee_sat: u64,
+    /// Recommended network fee rate for the speed, in sats per vbyte
+    pub fee_rate_sat_per_vbyte: u64,
+    /// Vbytes of the L1 transaction covered by `l1_broadcast_fee_sat` at
+    /// `fee_rate_sat_per_vbyte`
+    pub estimated_vbytes: u64,
+}
+
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct ReceivePaymentRequest {
     pub payment_method: ReceivePaymentMethod,
+    /// Reference of the order being paid, used for the `{order_ref}` placeholder
+    /// of [`Config::invoice_description_template`]
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub order_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1458,7 +2203,12 @@ pub struct LnurlPayRequest {
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct LnurlPayResponse {
     pub payment: Payment,
+    /// The processed success action. An AES action of a payment that is still
+    /// pending is processed once the payment completes, and can then be read
+    /// from the payment's `lnurl_pay_info`.
     pub success_action: Option<SuccessActionProcessed>,
+    /// The comment sent to the LNURL service
+    pub comment: Option<String>,
 }
 
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -1726,7 +2476,7 @@ pub struct PrepareSendPaymentRequest {
     pub fee_policy: Option<FeePolicy>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct PrepareSendPaymentResponse {
     pub payment_method: SendPaymentMethod,
@@ -1743,8 +2493,15 @@ pub struct PrepareSendPaymentResponse {
     /// The fee policy actually applied. May differ from the request — e.g.,
     /// cross-chain AMM-conversion sends are always `FeesIncluded`.
     pub fee_policy: FeePolicy,
+    /// Set when paying a BIP21 URI. `payment_method` is the method selected
+    /// from the ones it contains.
+    pub bip21_details: Option<Bip21Details>,
+    /// The fees broken down by what they pay for. Set for Lightning sends
+    /// through the SSP and for on-chain sends when fee rates are available.
+    pub fee_breakdown: Option<FeeBreakdown>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum SendPaymentOptions {
     BitcoinAddress {
@@ -1765,6 +2522,7 @@ pub enum SendPaymentOptions {
     },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct SparkHtlcOptions {
     /// The payment hash of the HTLC. The receiver will need to provide the associated preimage to claim it.
@@ -1774,6 +2532,7 @@ pub struct SparkHtlcOptions {
     pub expiry_duration_secs: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct SendPaymentRequest {
     pub prepare_response: PrepareSendPaymentResponse,
@@ -1782,10 +2541,22 @@ pub struct SendPaymentRequest {
     /// The optional idempotency key for all Spark based transfers (excludes token payments
     /// and cross-chain sends).
     /// If set, providing the same idempotency key for multiple requests will ensure that only one
-    /// payment is made. If an idempotency key is re-used, the same payment will be returned.
+    /// payment is made. If an idempotency key is re-used, the same payment will be returned,
+    /// also when the earlier call failed after the payment was made, e.g. on a network error.
     /// The idempotency key must be a valid UUID.
     #[cfg_attr(feature = "uniffi", uniffi(default=None))]
     pub idempotency_key: Option<String>,
+    /// Travel-rule information about the originator and the beneficiary. It is passed
+    /// to the registered `TravelRuleProvider` before the payment is sent and persisted
+    /// with the payment.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub compliance_metadata: Option<ComplianceMetadata>,
+    /// If true, the payment is queued when it fails because the network is
+    /// unreachable, and sent once the connection is restored. The call then
+    /// returns [`SdkError::PaymentQueued`](crate::SdkError::PaymentQueued).
+    /// Not supported for payments with a token transfer leg and cross-chain sends.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub queue_if_offline: Option<bool>,
 }
 
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -1807,6 +2578,66 @@ pub struct SendPaymentResponse {
     pub payment: Payment,
 }
 
+/// How the payments of a [`PaySplitRequest`] are executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum SplitExecutionMode {
+    /// Pays the invoices one after the other and stops at the first failure
+    #[default]
+    Sequential,
+    /// Pays all invoices concurrently
+    Parallel,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaySplitRequest {
+    /// BOLT11 or Spark invoices with embedded amounts
+    pub invoices: Vec<String>,
+    /// The most the invoices may cost in total, fees included, in satoshis
+    pub total_budget_sats: u64,
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub execution_mode: Option<SplitExecutionMode>,
+}
+
+/// The outcome of paying one invoice of a [`PaySplitRequest`].
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum SplitPaymentStatus {
+    Sent {
+        payment: Payment,
+    },
+    Failed {
+        error: String,
+    },
+    /// Not attempted because an earlier payment failed in sequential mode
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SplitPaymentResult {
+    pub invoice: String,
+    /// The amount of the invoice in satoshis
+    pub amount_sats: u64,
+    /// The fee estimated when the invoices were prepared, in satoshis
+    pub estimated_fee_sats: u64,
+    pub status: SplitPaymentStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaySplitResponse {
+    /// The result of each invoice, in the order of the request
+    pub results: Vec<SplitPaymentResult>,
+    /// Total amount of the sent payments in satoshis
+    pub total_sent_sats: u64,
+    /// Total fees of the sent payments in satoshis
+    pub total_fees_sats: u64,
+    /// Whether all invoices were paid
+    pub all_succeeded: bool,
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
 pub enum PaymentDetailsFilter {
@@ -1857,6 +2688,35 @@ pub struct ListPaymentsRequest {
     pub limit: Option<u32>,
     #[cfg_attr(feature = "uniffi", uniffi(default=None))]
     pub sort_ascending: Option<bool>,
+    /// Only include payments with this counterparty
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub counterparty_filter: Option<CounterpartyFilter>,
+    /// Only include payments whose description, invoice, transaction id,
+    /// Lightning address or comment contains this text, ignoring case
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub search: Option<String>,
+    /// Resume listing after the previous page, as returned in
+    /// [`ListPaymentsResponse::next_cursor`]. Unlike `offset`, pages stay
+    /// consistent when payments are added while paginating.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub cursor: Option<String>,
+    /// Also return the payments aggregated by group in
+    /// [`ListPaymentsResponse::groups`]
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub grouped: Option<bool>,
+    /// Only include payments with a dispute in one of these statuses
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub dispute_status_filter: Option<Vec<DisputeStatus>>,
+}
+
+/// A field of [`ListPaymentsRequest`] when listing payments filtered by counterparty
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum CounterpartyFilter {
+    /// Lightning payments whose invoice destination is this node public key
+    LightningDestination { destination_pubkey: String },
+    /// Payments sent to this Lightning address. Matched case-insensitively.
+    LightningAddress { address: String },
 }
 
 /// A field of [`ListPaymentsRequest`] when listing payments filtered by asset
@@ -1898,6 +2758,33 @@ impl FromStr for AssetFilter {
 pub struct ListPaymentsResponse {
     /// The list of payments
     pub payments: Vec<Payment>,
+    /// Cursor of the next page, set when the page is full. Pass it as
+    /// [`ListPaymentsRequest::cursor`] with the same filters to continue.
+    pub next_cursor: Option<String>,
+    /// The payments of the page aggregated by group, in list order. Only set
+    /// when [`ListPaymentsRequest::grouped`] is true. A group whose payments
+    /// span several pages is returned with each of them.
+    pub groups: Option<Vec<PaymentGroup>>,
+}
+
+/// The payments making up one user action, listed as a single entry
+///
+/// A payment without a group forms a group of its own. Every payment of the
+/// group is included, even those not matching the filters of the request.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaymentGroup {
+    /// The shared group id, or the payment id for a payment without a group
+    pub group_id: String,
+    /// `Pending` if any payment is pending, `Failed` if any failed, and
+    /// `Completed` otherwise
+    pub status: PaymentStatus,
+    /// Timestamp of the latest payment of the group
+    pub timestamp: u64,
+    /// Sum of the fees of the payments
+    pub fees: u128,
+    /// The payments of the group, oldest first
+    pub payments: Vec<Payment>,
 }
 
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
@@ -1909,6 +2796,95 @@ pub struct GetPaymentRequest {
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct GetPaymentResponse {
     pub payment: Payment,
+    /// Compliance metadata the payment was sent with, if any
+    pub compliance_metadata: Option<ComplianceMetadata>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetPaymentFiatValueRequest {
+    pub payment_id: String,
+    /// The fiat currency code, e.g. "USD"
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetPaymentFiatValueResponse {
+    /// The fiat value of the payment amount at the time of the payment
+    pub fiat_value_at_payment: f64,
+    /// The BTC rate of the currency at the time of the payment
+    pub rate: f64,
+}
+
+/// The Breez SDK a payment history export was produced by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum PaymentImportSource {
+    /// The Greenlight based Breez SDK
+    Greenlight,
+    /// The Breez SDK - Nodeless (Liquid implementation)
+    Liquid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ImportPaymentsRequest {
+    /// The SDK the export was produced by
+    pub source: PaymentImportSource,
+    /// The JSON serialized list of payments, as returned by the source SDK's `list_payments`
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ImportPaymentsResponse {
+    /// The number of payments imported
+    pub imported_count: u32,
+    /// The number of entries skipped, either pending or not convertible
+    pub skipped_count: u32,
+}
+
+/// The format of a payment history export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ExportFormat {
+    /// Comma separated values, with a header row in the first chunk
+    Csv,
+    /// One JSON object per line (JSON Lines)
+    Json,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ExportPaymentsRequest {
+    pub format: ExportFormat,
+    /// Only export payments at or after this timestamp
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub from_timestamp: Option<u64>,
+    /// Only export payments before this timestamp
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub to_timestamp: Option<u64>,
+    /// Adds the description, the invoice or transaction id and the dispute
+    /// note of each payment
+    #[cfg_attr(feature = "uniffi", uniffi(default = false))]
+    pub include_metadata: bool,
+    /// Adds the value of Bitcoin payments in this fiat currency at the time
+    /// of the payment, when a historical rate is available
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub fiat_currency: Option<String>,
+    /// The [`ExportPaymentsResponse::next_cursor`] of the previous chunk
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ExportPaymentsResponse {
+    /// The exported chunk. Chunks can be concatenated in order.
+    pub data: String,
+    /// Cursor of the next chunk, unset once the export is complete
+    pub next_cursor: Option<String>,
 }
 
 #[cfg_attr(feature = "uniffi", uniffi::export(callback_interface))]
@@ -2059,6 +3035,34 @@ pub(crate) enum WaitForPaymentIdentifier {
     LightningReceive { invoice: String, ssp_id: String },
 }
 
+/// Identifies the payment to wait for in [`WaitForPaymentRequest`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum PaymentIdentifier {
+    /// The payment id, which for Spark payments is the transfer id
+    PaymentId { payment_id: String },
+    /// The payment hash of a Lightning payment
+    PaymentHash { payment_hash: String },
+    /// The BOLT11 invoice of a Lightning payment
+    Invoice { invoice: String },
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WaitForPaymentRequest {
+    pub identifier: PaymentIdentifier,
+    /// How long to wait for a terminal status, in seconds. Defaults to 60.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub timeout_secs: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WaitForPaymentResponse {
+    /// The payment, either completed or failed
+    pub payment: Payment,
+}
+
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct GetTokensMetadataRequest {
     pub token_identifiers: Vec<String>,
@@ -2099,6 +3103,41 @@ pub struct CheckMessageResponse {
     pub is_valid: bool,
 }
 
+/// What a secret derived with `derive_child_secret` is used for. Each purpose
+/// yields independent secrets.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ChildSecretPurpose {
+    /// Key of a Nostr Wallet Connect connection
+    NwcKey,
+    /// Key signing webhook payloads
+    WebhookHmacKey,
+    /// Key encrypting wallet backups
+    BackupEncryptionKey,
+    /// An application-defined purpose. `application` must be at least 1000,
+    /// lower numbers being reserved for the SDK.
+    Custom { application: u32 },
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DeriveChildSecretRequest {
+    pub purpose: ChildSecretPurpose,
+    /// Index of the secret within the purpose, e.g. to rotate it. Defaults to 0.
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub index: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DeriveChildSecretResponse {
+    /// The hex encoded 32 byte secret
+    pub secret: String,
+    /// The derivation path of the key the secret is derived from, relative
+    /// to the wallet identity key
+    pub derivation_path: String,
+}
+
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 #[derive(Debug, Clone, Serialize)]
 pub struct UserSettings {
@@ -2127,6 +3166,69 @@ pub struct ClaimHtlcPaymentResponse {
     pub payment: Payment,
 }
 
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CreateHtlcReceiveRequest {
+    /// The hex-encoded payment hash the HTLC must lock to, supplied by the
+    /// coordinator that holds the preimage
+    pub payment_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CreateHtlcReceiveResponse {
+    /// The Spark address the sender should create the HTLC transfer to
+    pub spark_address: String,
+    pub payment_hash: String,
+}
+
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetHtlcReceiveRequest {
+    /// The hex-encoded payment hash of the HTLC receive
+    pub payment_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetHtlcReceiveResponse {
+    pub htlc_receive: HtlcReceive,
+}
+
+/// The state of an HTLC receive on Spark
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct HtlcReceive {
+    pub payment_hash: String,
+    pub status: HtlcReceiveStatus,
+    /// The HTLC details, once the sender has created the HTLC. Spark HTLCs
+    /// time out at `expiry_time` rather than at a block height.
+    pub htlc_details: Option<SparkHtlcDetails>,
+    /// The incoming payment, once the sender has created the HTLC
+    pub payment: Option<Payment>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum HtlcReceiveStatus {
+    /// The sender hasn't created the HTLC yet
+    AwaitingHtlc,
+    /// The HTLC is waiting for the preimage to be shared
+    WaitingForPreimage,
+    /// The preimage has been shared and the HTLC can be or has been claimed
+    PreimageShared,
+    /// The HTLC has been returned to the sender due to expiry
+    Returned,
+}
+
+impl From<SparkHtlcStatus> for HtlcReceiveStatus {
+    fn from(status: SparkHtlcStatus) -> Self {
+        match status {
+            SparkHtlcStatus::WaitingForPreimage => HtlcReceiveStatus::WaitingForPreimage,
+            SparkHtlcStatus::PreimageShared => HtlcReceiveStatus::PreimageShared,
+            SparkHtlcStatus::Returned => HtlcReceiveStatus::Returned,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct LnurlReceiveMetadata {
@@ -2499,7 +3601,7 @@ pub struct UnilateralExitRequest {
 
 /// Result of `unilateral_exit`: a cost summary plus the complete, signed exit
 /// path.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct UnilateralExitResponse {
     /// Total value of the selected leaves, in satoshis.
@@ -2513,3 +3615,114 @@ pub struct UnilateralExitResponse {
     /// with shared ancestors appearing once and the sweep last.
     pub transactions: Vec<UnilateralExitTransaction>,
 }
+
+/// Request for `export_emergency_exit_package`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ExportEmergencyExitPackageRequest {
+    /// A UTXO set aside to pay the exit fees. The package stays usable while it
+    /// is unspent and holds at least the quoted `single_utxo_funding_sat`.
+    pub funding_input: CpfpInput,
+    /// Fee rate in sat/vByte the package is signed at. It can't be raised once
+    /// the operators are unavailable, so pick a rate that confirms during a fee
+    /// spike.
+    pub fee_rate_sat_per_vbyte: u64,
+    /// The Bitcoin address the swept funds are sent to.
+    pub destination: String,
+}
+
+/// Signed unilateral exit transactions for every leaf worth exiting, which
+/// recover the funds without the SDK or the operators.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct EmergencyExitPackage {
+    /// When the package was created, as a UNIX timestamp in seconds.
+    pub created_at: u64,
+    pub exit: UnilateralExitResponse,
+}
+
+/// What a watch-only SDK on another machine needs to view a wallet. It holds
+/// no keys and no credentials. See `SdkBuilder::new_viewer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ViewerBundle {
+    /// The hex-encoded identity public key of the wallet
+    pub identity_public_key: String,
+    /// When the bundle was created, as a UNIX timestamp in seconds.
+    pub created_at: u64,
+}
+
+/// Request for `sweep_private_key`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SweepPrivateKeyRequest {
+    /// The private key holding the funds, in WIF format.
+    pub wif: String,
+    /// Fee rate in sat/vByte of the sweep transaction.
+    pub fee_rate_sat_per_vbyte: u64,
+}
+
+/// The broadcast sweep transaction. Its output is a deposit to the wallet,
+/// claimed once it confirms.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SweepPrivateKeyResponse {
+    pub tx_id: String,
+    pub tx_hex: String,
+    /// The static deposit address the funds were sent to.
+    pub deposit_address: String,
+    /// The amount deposited, in satoshis.
+    pub amount_sats: u64,
+    /// The fee paid by the sweep transaction, in satoshis.
+    pub fee_sats: u64,
+}
+
+/// Request to mark a payment as disputed or resolve its dispute
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SetPaymentDisputeRequest {
+    pub payment_id: String,
+    pub status: DisputeStatus,
+    /// Replaces the note of the dispute. When `None`, the existing note is kept.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SetPaymentDisputeResponse {
+    /// The payment with its updated dispute
+    pub payment: Payment,
+}
+
+/// Request to acknowledge that a consumer has processed a received payment
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct AcknowledgePaymentRequest {
+    pub payment_id: String,
+    /// Identifies the consumer, e.g. an order fulfillment service. Each
+    /// consumer acknowledges the payments separately.
+    pub consumer_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct AcknowledgePaymentResponse {}
+
+/// Request to list the received payments a consumer has not acknowledged yet
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ListUnacknowledgedPaymentsRequest {
+    pub consumer_id: String,
+    /// The maximum number of payments to return. Defaults to, and is capped
+    /// at, 100.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ListUnacknowledgedPaymentsResponse {
+    /// The completed received payments not acknowledged yet, oldest first
+    pub payments: Vec<Payment>,
+}
@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use bitcoin::secp256k1::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::SdkError;
+
+/// An approved destination for outgoing payments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum AllowlistEntry {
+    SparkIdentity {
+        /// Hex-encoded identity public key of the recipient wallet
+        identity_public_key: String,
+    },
+    LightningAddress {
+        /// Lightning address (user@domain)
+        address: String,
+    },
+    BitcoinAddress {
+        /// Onchain Bitcoin address
+        address: String,
+    },
+}
+
+impl AllowlistEntry {
+    /// Validates the entry and brings it into the form it is matched in.
+    pub(crate) fn normalize(self) -> Result<Self, SdkError> {
+        match self {
+            AllowlistEntry::SparkIdentity {
+                identity_public_key,
+            } => {
+                let key = PublicKey::from_str(identity_public_key.trim()).map_err(|_| {
+                    SdkError::InvalidInput(format!(
+                        "Invalid spark identity public key: {identity_public_key}"
+                    ))
+                })?;
+                Ok(AllowlistEntry::SparkIdentity {
+                    identity_public_key: key.to_string(),
+                })
+            }
+            AllowlistEntry::LightningAddress { address } => {
+                let address = address.trim().to_lowercase();
+                match address.split_once('@') {
+                    Some((user, domain)) if !user.is_empty() && !domain.is_empty() => {
+                        Ok(AllowlistEntry::LightningAddress { address })
+                    }
+                    _ => Err(SdkError::InvalidInput(format!(
+                        "Invalid lightning address: {address}"
+                    ))),
+                }
+            }
+            AllowlistEntry::BitcoinAddress { address } => {
+                let parsed = bitcoin::Address::from_str(address.trim()).map_err(|_| {
+                    SdkError::InvalidInput(format!("Invalid bitcoin address: {address}"))
+                })?;
+                Ok(AllowlistEntry::BitcoinAddress {
+                    address: parsed.assume_checked().to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// The persisted withdrawal allowlist.
+///
+/// While enabled, `send_payment` and `lnurl_pay` only pay destinations on the
+/// list. Other destinations need the approval of the [`PaymentApprover`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WithdrawalAllowlist {
+    pub enabled: bool,
+    pub entries: Vec<AllowlistEntry>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct UpdateWithdrawalAllowlistRequest {
+    /// Enables or disables enforcement. Left unchanged if not set.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub enabled: Option<bool>,
+    /// Entries to add to the allowlist
+    #[cfg_attr(feature = "uniffi", uniffi(default=[]))]
+    pub add: Vec<AllowlistEntry>,
+    /// Entries to remove from the allowlist
+    #[cfg_attr(feature = "uniffi", uniffi(default=[]))]
+    pub remove: Vec<AllowlistEntry>,
+}
+
+/// An outgoing payment to a destination that is not on the withdrawal allowlist.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaymentApprovalRequest {
+    /// The payment request or address being paid
+    pub destination: String,
+    /// The allowlist entry that would allow the destination. Not set for
+    /// destinations that can't be allowlisted, such as a BOLT11 invoice paid directly.
+    pub allowlist_entry: Option<AllowlistEntry>,
+    /// Amount in satoshis or token base units
+    pub amount: u128,
+    /// The token being sent. Not set for Bitcoin payments.
+    pub token_identifier: Option<String>,
+}
+
+/// This interface is used to approve outgoing payments to destinations that are
+/// not on the withdrawal allowlist.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait PaymentApprover: Send + Sync {
+    /// Returns whether the payment may be sent. If it returns `false`, the payment
+    /// fails with `SdkError::DestinationNotAllowed`.
+    async fn approve(&self, request: PaymentApprovalRequest) -> bool;
+}
@@ -12,6 +12,7 @@
 
 use std::sync::Arc;
 
+use bitcoin::hashes::{Hash, sha256};
 use macros::async_trait;
 use spark_wallet::{SessionStore, TokenOutputStore, TreeStore};
 
@@ -46,8 +47,9 @@ pub struct ResolvedStores {
 ///
 /// A single backend may back many SDK instances; each
 /// [`create_stores`](Self::create_stores) call yields the store set scoped to
-/// one tenant `identity` (a serialized public key). `network` lets file-based
-/// backends segregate tenants by network; database backends ignore it.
+/// one tenant `identity`, a 33-byte key from [`storage_tenant_key`]. `network`
+/// lets file-based backends segregate tenants by network; database backends
+/// ignore it.
 #[cfg_attr(feature = "uniffi", uniffi::export)]
 #[async_trait]
 pub trait StorageBackend: Send + Sync {
@@ -58,6 +60,40 @@ pub trait StorageBackend: Send + Sync {
     ) -> Result<Arc<ResolvedStores>, SdkError>;
 }
 
+/// Tag prefixed to the hash a namespaced tenant key is derived from.
+const NAMESPACE_TENANT_TAG: &[u8] = b"breez-sdk/storage-namespace";
+
+/// Derives the key a wallet's data is stored under in a [`StorageBackend`].
+///
+/// Without a namespace this is the wallet identity public key itself. With a
+/// namespace it is a 0x00 byte followed by a hash of the identity and the
+/// namespace, so the same wallet gets an isolated store per namespace. The
+/// leading byte can never start a compressed public key (0x02 or 0x03), so a
+/// namespaced tenant can't collide with a plain one.
+#[must_use]
+pub fn storage_tenant_key(identity: &[u8], namespace: Option<&str>) -> Vec<u8> {
+    let Some(namespace) = namespace else {
+        return identity.to_vec();
+    };
+    let mut preimage = Vec::with_capacity(
+        NAMESPACE_TENANT_TAG
+            .len()
+            .saturating_add(1)
+            .saturating_add(identity.len())
+            .saturating_add(namespace.len()),
+    );
+    preimage.extend_from_slice(NAMESPACE_TENANT_TAG);
+    // The identity length disambiguates where the identity ends and the namespace starts.
+    preimage.push(u8::try_from(identity.len()).unwrap_or(u8::MAX));
+    preimage.extend_from_slice(identity);
+    preimage.extend_from_slice(namespace.as_bytes());
+
+    let mut key = Vec::with_capacity(33);
+    key.push(0x00);
+    key.extend_from_slice(sha256::Hash::hash(&preimage).as_byte_array());
+    key
+}
+
 /// Wraps a caller-supplied [`Storage`] implementation as a [`StorageBackend`].
 /// The tree, token-output and session stores use the in-memory defaults.
 #[cfg_attr(feature = "uniffi", uniffi::export)]
@@ -75,7 +111,8 @@ pub fn custom_storage(storage: Arc<dyn Storage>) -> Arc<dyn StorageBackend> {
 /// keeping the backend's persistence. A typical use is at-rest encryption (the
 /// SDK does not encrypt tokens itself): wrap it in a store that encrypts on
 /// write and decrypts on read. `identity` is the wallet identity public key
-/// bytes (the same value the SDK derives from the signer).
+/// bytes (the same value the SDK derives from the signer), or the
+/// [`storage_tenant_key`] of it when the SDK is built with a storage namespace.
 #[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
 pub async fn default_session_store(
     backend: Arc<dyn StorageBackend>,
@@ -135,3 +172,92 @@ pub fn mysql_storage(
         foreign_key_mode,
     )))
 }
+
+#[cfg(test)]
+pub(super) mod tests {
+    use spark_wallet::{PublicKey, Session, SessionStoreError};
+
+    use super::*;
+
+    const IDENTITY: [u8; 33] = [0x02; 33];
+
+    #[test]
+    fn test_storage_tenant_key() {
+        assert_eq!(storage_tenant_key(&IDENTITY, None), IDENTITY.to_vec());
+
+        let a = storage_tenant_key(&IDENTITY, Some("a"));
+        let b = storage_tenant_key(&IDENTITY, Some("b"));
+        assert_eq!(a.len(), 33);
+        assert_eq!(a[0], 0x00);
+        assert_ne!(a, b);
+        assert_ne!(a, storage_tenant_key(&[0x03; 33], Some("a")));
+        assert_eq!(a, storage_tenant_key(&IDENTITY, Some("a")));
+    }
+
+    /// Asserts that the same identity under different namespaces, and without a
+    /// namespace, gets stores that never see each other's data.
+    pub(crate) async fn assert_namespaces_isolated(backend: &dyn StorageBackend) {
+        let tenants = [
+            storage_tenant_key(&IDENTITY, None),
+            storage_tenant_key(&IDENTITY, Some("a")),
+            storage_tenant_key(&IDENTITY, Some("b")),
+        ];
+        let mut stores = Vec::new();
+        for tenant in &tenants {
+            stores.push(
+                backend
+                    .create_stores(Network::Regtest, tenant.clone())
+                    .await
+                    .expect("stores created"),
+            );
+        }
+
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let service_key = PublicKey::from_secret_key(
+            &secp,
+            &bitcoin::secp256k1::SecretKey::from_slice(&[0x11; 32]).expect("valid secret key"),
+        );
+        stores[1]
+            .storage
+            .set_cached_item("key".to_string(), "a".to_string())
+            .await
+            .expect("cached item written");
+        if let Some(session_store) = &stores[1].session_store {
+            session_store
+                .set_session(
+                    &service_key,
+                    Session {
+                        token: "token-a".to_string(),
+                        expiration: u64::MAX,
+                    },
+                )
+                .await
+                .expect("session written");
+        }
+
+        for (i, store) in stores.iter().enumerate() {
+            let item = store
+                .storage
+                .get_cached_item("key".to_string())
+                .await
+                .expect("cached item read");
+            let session = match &store.session_store {
+                Some(session_store) => match session_store.get_session(&service_key).await {
+                    Ok(session) => Some(session.token),
+                    Err(SessionStoreError::NotFound) => None,
+                    Err(e) => panic!("unexpected session store error: {e:?}"),
+                },
+                None => None,
+            };
+            if i == 1 {
+                assert_eq!(item.as_deref(), Some("a"));
+                if store.session_store.is_some() {
+                    assert_eq!(session.as_deref(), Some("token-a"));
+                }
+            } else {
+                assert_eq!(item, None, "tenant {i} sees another namespace's data");
+                assert_eq!(session, None, "tenant {i} sees another namespace's session");
+            }
+        }
+    }
+}
@@ -208,4 +208,23 @@ mod tests {
         assert_all_stores_usable(&a).await;
         assert_all_stores_usable(&b).await;
     }
+
+    /// Namespaces of one identity share the database but never each other's rows.
+    #[tokio::test]
+    async fn test_namespaces_are_isolated() {
+        let container = Mysql::default()
+            .start()
+            .await
+            .expect("Failed to start MySQL container");
+        let host_port = container
+            .get_host_port_ipv4(3306)
+            .await
+            .expect("Failed to get host port");
+        let connection_string = format!("mysql://root@127.0.0.1:{host_port}/test");
+        let pool = create_pool(&MysqlStorageConfig::with_defaults(connection_string))
+            .expect("Failed to create pool");
+
+        let backend = MysqlBackend::new(pool, true, MysqlForeignKeyMode::Enforced);
+        crate::persist::backend::tests::assert_namespaces_isolated(&backend).await;
+    }
 }
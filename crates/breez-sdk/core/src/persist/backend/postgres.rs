@@ -202,4 +202,25 @@ mod tests {
         assert_all_stores_usable(&a).await;
         assert_all_stores_usable(&b).await;
     }
+
+    /// Namespaces of one identity share the database but never each other's rows.
+    #[tokio::test]
+    async fn test_namespaces_are_isolated() {
+        let container = Postgres::default()
+            .start()
+            .await
+            .expect("Failed to start PostgreSQL container");
+        let host_port = container
+            .get_host_port_ipv4(5432)
+            .await
+            .expect("Failed to get host port");
+        let connection_string = format!(
+            "host=127.0.0.1 port={host_port} user=postgres password=postgres dbname=postgres"
+        );
+        let pool = create_pool(&PostgresStorageConfig::with_defaults(connection_string))
+            .expect("Failed to create pool");
+
+        let backend = PostgresBackend::new(pool, true);
+        crate::persist::backend::tests::assert_namespaces_isolated(&backend).await;
+    }
 }
@@ -3,15 +3,13 @@
 use std::sync::Arc;
 
 use macros::async_trait;
-use spark_wallet::PublicKey;
 
-use crate::{Network, SdkError, SqliteStorage};
+use crate::{Network, SdkError, SqliteStorage, persist::path::tenant_storage_path};
 
 use super::{ResolvedStores, StorageBackend};
 
 /// `SQLite` backend. The database path is derived per-tenant from the network
-/// and identity public key, so one `storage_dir` can hold many tenants'
-/// databases.
+/// and tenant key, so one `storage_dir` can hold many tenants' databases.
 pub(super) struct SqliteBackend {
     storage_dir: String,
 }
@@ -29,9 +27,7 @@ impl StorageBackend for SqliteBackend {
         network: Network,
         identity: Vec<u8>,
     ) -> Result<Arc<ResolvedStores>, SdkError> {
-        let identity =
-            PublicKey::from_slice(&identity).map_err(|e| SdkError::Generic(e.to_string()))?;
-        let db_path = crate::default_storage_path(&self.storage_dir, &network, &identity)?;
+        let db_path = tenant_storage_path(&self.storage_dir, &network, &identity)?;
         let storage = Arc::new(SqliteStorage::new(&db_path)?);
         Ok(Arc::new(ResolvedStores {
             storage,
@@ -41,3 +37,19 @@ impl StorageBackend for SqliteBackend {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persist::backend::tests::assert_namespaces_isolated;
+
+    #[tokio::test]
+    async fn test_namespaces_are_isolated() {
+        let dir = std::env::temp_dir().join(format!(
+            "breez-test-sqlite-backend-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let backend = SqliteBackend::new(dir.to_string_lossy().into_owned());
+        assert_namespaces_isolated(&backend).await;
+    }
+}
@@ -20,34 +20,95 @@ compile_error!(
      cannot be enabled for the wasm32 target"
 );
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
 
 use macros::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    AssetFilter, Contact, ConversionInfo, ConversionStatus, DepositClaimError, DepositInfo,
-    LightningAddressInfo, ListContactsRequest, ListPaymentsRequest, LnurlPayInfo,
-    LnurlWithdrawInfo, PaymentDetailsFilter, PaymentStatus, PaymentType, SparkHtlcStatus,
-    TokenBalance, TokenMetadata, TokenTransactionType,
+    AssetFilter, ClaimFeeSponsorRequest, ClaimFeeSponsorship, ComplianceMetadata, Contact,
+    ConversionInfo, ConversionStatus, CounterpartyFilter, DepositClaimError, DepositInfo,
+    DisputeStatus, LightningAddressInfo, ListContactsRequest, ListPaymentsRequest, LnurlPayInfo,
+    LnurlWithdrawInfo, MaxFee, PaymentDetailsFilter, PaymentDispute, PaymentStatus, PaymentType,
+    ReceiveStageUpdate, SendPaymentRequest, SparkHtlcStatus, TokenBalance, TokenMetadata,
+    TokenTransactionType, WithdrawalAllowlist,
+    config_fingerprint::ConfigFingerprint,
+    error::SdkError,
     models::Payment,
     sync_storage::{IncomingChange, OutgoingChange, Record, UnversionedRecordChange},
 };
 
 const ACCOUNT_INFO_KEY: &str = "account_info";
+const AMOUNT_RANGE_RECEIVES_KEY: &str = "amount_range_receives";
+const CLAIM_FEE_SPONSORSHIPS_KEY: &str = "claim_fee_sponsorships";
+/// Number of claim fee sponsorships stored per cached item
+pub(crate) const CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE: usize = 100;
+const COMPLIANCE_METADATA_KEY_PREFIX: &str = "compliance_metadata";
+const CONFIG_FINGERPRINT_KEY: &str = "config_fingerprint";
+const CONFIRMED_WITHDRAWALS_KEY: &str = "confirmed_withdrawals";
+const DEPOSIT_CLAIM_FEE_TARGET_KEY_PREFIX: &str = "deposit_claim_fee_target";
+const FEE_ESTIMATES_KEY: &str = "fee_estimates";
+const FIAT_RATE_AT_PAYMENT_KEY_PREFIX: &str = "fiat_rate_at_payment";
+const HTLC_RECEIVE_KEY_PREFIX: &str = "htlc_receive_";
 const LAST_SYNC_TIME_KEY: &str = "last_sync_time";
 pub(crate) const LIGHTNING_ADDRESS_KEY: &str = "lightning_address";
+const LIGHTNING_RECEIVES_KEY: &str = "lightning_receives";
 const LNURL_METADATA_UPDATED_AFTER_KEY: &str = "lnurl_metadata_updated_after";
+const MONITORED_DEPOSIT_ADDRESSES_KEY: &str = "monitored_deposit_addresses";
 const SYNC_OFFSET_KEY: &str = "sync_offset";
 const TX_CACHE_KEY: &str = "tx_cache";
 // Note: the key "static_deposit_address" may still exist in storage from older versions.
+const TOKEN_INVOICES_KEY: &str = "token_invoices";
 const TOKEN_METADATA_KEY_PREFIX: &str = "token_metadata_";
+const REPORTED_TOKEN_BALANCES_KEY: &str = "reported_token_balances";
+const PAYMENT_CONSUMER_KEY_PREFIX: &str = "payment_consumer_";
 const PAYMENT_METADATA_KEY_PREFIX: &str = "payment_metadata";
 const PUBLISHED_PACKAGE_KEY_PREFIX: &str = "published_package_";
+const QUEUED_SENDS_KEY: &str = "queued_sends";
+const IN_FLIGHT_SEND_KEY_PREFIX: &str = "in_flight_send_";
 const SPARK_PRIVATE_MODE_INITIALIZED_KEY: &str = "spark_private_mode_initialized";
 pub(crate) const STABLE_BALANCE_ACTIVE_LABEL_KEY: &str = "stable_balance_active_label";
+const PENDING_CLAIM_FEE_SPONSORSHIPS_KEY: &str = "pending_claim_fee_sponsorships";
 const PENDING_CONVERSIONS_KEY: &str = "pending_conversions";
+const WITHDRAWAL_ALLOWLIST_KEY: &str = "withdrawal_allowlist";
+
+/// Cached items kept in backups, as they can't be re-derived from the
+/// operators. The withdrawal allowlist is left out, so a crafted backup can't
+/// widen it. The pages of claim fee sponsorships are kept too, see
+/// [`ObjectCacheRepository::claim_fee_sponsorships_backup_cache_keys`].
+pub(crate) const BACKUP_CACHE_KEYS: &[&str] = &[
+    AMOUNT_RANGE_RECEIVES_KEY,
+    CONFIRMED_WITHDRAWALS_KEY,
+    LIGHTNING_ADDRESS_KEY,
+    MONITORED_DEPOSIT_ADDRESSES_KEY,
+    PENDING_CLAIM_FEE_SPONSORSHIPS_KEY,
+    TOKEN_INVOICES_KEY,
+];
+
+/// The cached items of a payment kept in backups.
+pub(crate) fn payment_backup_cache_keys(payment_id: &str) -> Vec<String> {
+    vec![format!("{COMPLIANCE_METADATA_KEY_PREFIX}-{payment_id}")]
+}
+
+/// The cached item holding the `page`th page of claim fee sponsorships. The
+/// first page keeps the key the sponsorships were stored under before paging.
+fn claim_fee_sponsorships_key(page: usize) -> String {
+    match page {
+        0 => CLAIM_FEE_SPONSORSHIPS_KEY.to_string(),
+        page => format!("{CLAIM_FEE_SPONSORSHIPS_KEY}-{page}"),
+    }
+}
+
+/// The cached items of a deposit kept in backups.
+pub(crate) fn deposit_backup_cache_keys(txid: &str, vout: u32) -> Vec<String> {
+    vec![format!(
+        "{DEPOSIT_CLAIM_FEE_TARGET_KEY_PREFIX}-{txid}:{vout}"
+    )]
+}
 
 /// Wrapper stored in the cache that carries context about whether the value
 /// was written as part of a recovery or a client-initiated change.
@@ -244,6 +305,57 @@ pub struct StorageListPaymentsRequest {
     pub limit: Option<u32>,
     #[cfg_attr(feature = "uniffi", uniffi(default=None))]
     pub sort_ascending: Option<bool>,
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub counterparty_filter: Option<CounterpartyFilter>,
+    /// Only include payments whose text matches this term. Empty terms are
+    /// normalized to `None`.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub search: Option<String>,
+    /// Only include payments after this one in the requested sort order.
+    /// Payments are sorted by timestamp, then by id.
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub after: Option<PaymentCursor>,
+    /// Only include payments in one of these groups
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub group_id_filter: Option<Vec<String>>,
+    /// Only include payments with a dispute in one of these statuses
+    #[cfg_attr(feature = "uniffi", uniffi(default=None))]
+    pub dispute_status_filter: Option<Vec<DisputeStatus>>,
+}
+
+/// Position of a payment in the payment list, resumed from with
+/// [`StorageListPaymentsRequest::after`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaymentCursor {
+    pub timestamp: u64,
+    pub id: String,
+}
+
+impl PaymentCursor {
+    pub(crate) fn from_payment(payment: &Payment) -> Self {
+        Self {
+            timestamp: payment.timestamp,
+            id: payment.id.clone(),
+        }
+    }
+
+    /// Encodes the cursor as the opaque [`ListPaymentsRequest::cursor`] string.
+    pub(crate) fn encode(&self) -> String {
+        format!("{}:{}", self.timestamp, self.id)
+    }
+
+    pub(crate) fn decode(cursor: &str) -> Result<Self, SdkError> {
+        cursor
+            .split_once(':')
+            .and_then(|(timestamp, id)| {
+                Some(Self {
+                    timestamp: timestamp.parse().ok()?,
+                    id: id.to_string(),
+                })
+            })
+            .ok_or_else(|| SdkError::InvalidInput(format!("Invalid payment cursor: {cursor}")))
+    }
 }
 
 impl From<ListPaymentsRequest> for StorageListPaymentsRequest {
@@ -260,6 +372,15 @@ impl From<ListPaymentsRequest> for StorageListPaymentsRequest {
             offset: request.offset,
             limit: request.limit,
             sort_ascending: request.sort_ascending,
+            counterparty_filter: request.counterparty_filter,
+            search: request
+                .search
+                .map(|search| search.trim().to_string())
+                .filter(|search| !search.is_empty()),
+            // The cursor is decoded by the caller, as decoding can fail
+            after: None,
+            group_id_filter: None,
+            dispute_status_filter: request.dispute_status_filter,
         }
     }
 }
@@ -278,6 +399,11 @@ impl From<StorageListPaymentsRequest> for ListPaymentsRequest {
             offset: request.offset,
             limit: request.limit,
             sort_ascending: request.sort_ascending,
+            counterparty_filter: request.counterparty_filter,
+            search: request.search,
+            cursor: request.after.as_ref().map(PaymentCursor::encode),
+            grouped: None,
+            dispute_status_filter: request.dispute_status_filter,
         }
     }
 }
@@ -304,6 +430,10 @@ pub struct PaymentMetadata {
     pub conversion_info: Option<ConversionInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub conversion_status: Option<ConversionStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dispute: Option<PaymentDispute>,
 }
 
 #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
@@ -313,6 +443,16 @@ pub(crate) fn parse_payment_status(value: &str) -> Result<PaymentStatus, Storage
         .map_err(|e| StorageError::Implementation(format!("invalid payment status: {e}")))
 }
 
+/// A `LIKE` pattern matching text containing `search`, escaping its wildcards.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(crate) fn search_like_pattern(search: &str) -> String {
+    let escaped = search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
 /// Deserializes `ConversionInfo` leniently — ensures the `"type"` tag exists
 /// (defaulting to `"amm"` for pre-migration sync records), then deserializes.
 /// The `entry().or_insert_with()` is a no-op hash lookup when the tag already
@@ -370,6 +510,69 @@ pub struct StoredCrossChainSwap {
     pub secrets: String,
 }
 
+/// The kind of a problem found by [`Storage::check_integrity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum StorageIntegrityIssueKind {
+    /// The database engine reported corruption
+    Corruption,
+    /// A stored value isn't valid JSON
+    InvalidJson,
+    /// A record belongs to a payment that doesn't exist
+    OrphanedRecord,
+}
+
+/// A problem found by [`Storage::check_integrity`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct StorageIntegrityIssue {
+    pub kind: StorageIntegrityIssueKind,
+    /// The table or store of the affected record, if any
+    pub table: Option<String>,
+    /// The id of the affected record, if any
+    pub record_id: Option<String>,
+    pub description: String,
+    /// Whether the issue was repaired
+    pub repaired: bool,
+}
+
+impl StorageIntegrityIssue {
+    pub(crate) fn corruption(description: String) -> Self {
+        Self {
+            kind: StorageIntegrityIssueKind::Corruption,
+            table: None,
+            record_id: None,
+            description,
+            repaired: false,
+        }
+    }
+
+    pub(crate) fn invalid_json(
+        table: &str,
+        record_id: String,
+        column: &str,
+        repaired: bool,
+    ) -> Self {
+        Self {
+            kind: StorageIntegrityIssueKind::InvalidJson,
+            table: Some(table.to_string()),
+            description: format!("Invalid JSON in {column} of {record_id}"),
+            record_id: Some(record_id),
+            repaired,
+        }
+    }
+
+    pub(crate) fn orphaned_record(table: &str, payment_id: String, repaired: bool) -> Self {
+        Self {
+            kind: StorageIntegrityIssueKind::OrphanedRecord,
+            table: Some(table.to_string()),
+            description: format!("Payment {payment_id} doesn't exist"),
+            record_id: Some(payment_id),
+            repaired,
+        }
+    }
+}
+
 /// Trait for persistent storage
 #[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
 #[async_trait]
@@ -379,6 +582,9 @@ pub trait Storage: Send + Sync {
     async fn set_cached_item(&self, key: String, value: String) -> Result<(), StorageError>;
     /// Lists payments with optional filters and pagination
     ///
+    /// Payments are sorted by timestamp, then by id, so that
+    /// [`StorageListPaymentsRequest::after`] resumes at a stable position.
+    ///
     /// # Arguments
     ///
     /// * `list_payments_request` - The request to list payments
@@ -582,6 +788,16 @@ pub trait Storage: Send + Sync {
 
     /// Update the sync state record from an incoming record
     async fn update_record_from_incoming(&self, record: Record) -> Result<(), StorageError>;
+
+    /// Checks the stored data for corruption, invalid JSON values and payment
+    /// details whose payment doesn't exist.
+    ///
+    /// With `repair`, orphaned records are deleted, invalid optional values
+    /// are cleared and derived indexes are rebuilt. Returns the issues found.
+    async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<StorageIntegrityIssue>, StorageError>;
 }
 
 pub(crate) struct ObjectCacheRepository {
@@ -616,6 +832,86 @@ impl ObjectCacheRepository {
         }
     }
 
+    pub(crate) async fn save_monitored_deposit_addresses(
+        &self,
+        addresses: &[CachedDepositAddress],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                MONITORED_DEPOSIT_ADDRESSES_KEY.to_string(),
+                serde_json::to_string(addresses)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_monitored_deposit_addresses(
+        &self,
+    ) -> Result<Vec<CachedDepositAddress>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(MONITORED_DEPOSIT_ADDRESSES_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The token balances last reported through `TokenBalanceChanged` events
+    pub(crate) async fn save_reported_token_balances(
+        &self,
+        balances: &HashMap<String, u128>,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                REPORTED_TOKEN_BALANCES_KEY.to_string(),
+                serde_json::to_string(balances)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_reported_token_balances(
+        &self,
+    ) -> Result<Option<HashMap<String, u128>>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(REPORTED_TOKEN_BALANCES_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The withdrawal transactions already reported through `WithdrawConfirmed` events
+    pub(crate) async fn save_confirmed_withdrawals(
+        &self,
+        txids: &BTreeSet<String>,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                CONFIRMED_WITHDRAWALS_KEY.to_string(),
+                serde_json::to_string(txids)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_confirmed_withdrawals(
+        &self,
+    ) -> Result<BTreeSet<String>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(CONFIRMED_WITHDRAWALS_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(BTreeSet::new()),
+        }
+    }
+
     pub(crate) async fn save_sync_info(&self, value: &CachedSyncInfo) -> Result<(), StorageError> {
         self.storage
             .set_cached_item(SYNC_OFFSET_KEY.to_string(), serde_json::to_string(value)?)
@@ -661,6 +957,79 @@ impl ObjectCacheRepository {
             .await
     }
 
+    /// Marks the send with `idempotency_key` as started, until it succeeds.
+    pub(crate) async fn save_in_flight_send(
+        &self,
+        idempotency_key: &str,
+        started_at: u64,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                format!("{IN_FLIGHT_SEND_KEY_PREFIX}{idempotency_key}"),
+                serde_json::to_string(&started_at)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_in_flight_send(
+        &self,
+        idempotency_key: &str,
+    ) -> Result<Option<u64>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(format!("{IN_FLIGHT_SEND_KEY_PREFIX}{idempotency_key}"))
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn delete_in_flight_send(
+        &self,
+        idempotency_key: &str,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .delete_cached_item(format!("{IN_FLIGHT_SEND_KEY_PREFIX}{idempotency_key}"))
+            .await?;
+        Ok(())
+    }
+
+    /// Records the BTC rate of `currency` at the time of a payment, so its
+    /// fiat value is only looked up once.
+    pub(crate) async fn save_fiat_rate_at_payment(
+        &self,
+        payment_id: &str,
+        currency: &str,
+        rate: f64,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                format!("{FIAT_RATE_AT_PAYMENT_KEY_PREFIX}-{payment_id}-{currency}"),
+                serde_json::to_string(&rate)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_fiat_rate_at_payment(
+        &self,
+        payment_id: &str,
+        currency: &str,
+    ) -> Result<Option<f64>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(format!(
+                "{FIAT_RATE_AT_PAYMENT_KEY_PREFIX}-{payment_id}-{currency}"
+            ))
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
     pub(crate) async fn save_tx(&self, txid: &str, value: &CachedTx) -> Result<(), StorageError> {
         self.storage
             .set_cached_item(
@@ -738,6 +1107,34 @@ impl ObjectCacheRepository {
         }
     }
 
+    pub(crate) async fn save_htlc_receive(
+        &self,
+        payment_hash: &str,
+        value: &CachedHtlcReceive,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                format!("{HTLC_RECEIVE_KEY_PREFIX}{payment_hash}"),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_htlc_receive(
+        &self,
+        payment_hash: &str,
+    ) -> Result<Option<CachedHtlcReceive>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(format!("{HTLC_RECEIVE_KEY_PREFIX}{payment_hash}"))
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
     pub(crate) async fn save_token_metadata(
         &self,
         value: &TokenMetadata,
@@ -803,6 +1200,388 @@ impl ObjectCacheRepository {
         Ok(())
     }
 
+    pub(crate) async fn save_compliance_metadata(
+        &self,
+        payment_id: &str,
+        value: &ComplianceMetadata,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                format!("{COMPLIANCE_METADATA_KEY_PREFIX}-{payment_id}"),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_compliance_metadata(
+        &self,
+        payment_id: &str,
+    ) -> Result<Option<ComplianceMetadata>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(format!("{COMPLIANCE_METADATA_KEY_PREFIX}-{payment_id}"))
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn save_deposit_claim_fee_target(
+        &self,
+        txid: &str,
+        vout: u32,
+        value: &MaxFee,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                format!("{DEPOSIT_CLAIM_FEE_TARGET_KEY_PREFIX}-{txid}:{vout}"),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_deposit_claim_fee_target(
+        &self,
+        txid: &str,
+        vout: u32,
+    ) -> Result<Option<MaxFee>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(format!(
+                "{DEPOSIT_CLAIM_FEE_TARGET_KEY_PREFIX}-{txid}:{vout}"
+            ))
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn delete_deposit_claim_fee_target(
+        &self,
+        txid: &str,
+        vout: u32,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .delete_cached_item(format!(
+                "{DEPOSIT_CLAIM_FEE_TARGET_KEY_PREFIX}-{txid}:{vout}"
+            ))
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn save_fee_estimates(
+        &self,
+        value: &[CachedFeeEstimate],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(FEE_ESTIMATES_KEY.to_string(), serde_json::to_string(value)?)
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_fee_estimates(&self) -> Result<Vec<CachedFeeEstimate>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(FEE_ESTIMATES_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub(crate) async fn save_token_invoices(
+        &self,
+        value: &[CachedTokenInvoice],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                TOKEN_INVOICES_KEY.to_string(),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_token_invoices(
+        &self,
+    ) -> Result<Vec<CachedTokenInvoice>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(TOKEN_INVOICES_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub(crate) async fn save_amount_range_receives(
+        &self,
+        value: &[CachedAmountRangeReceive],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                AMOUNT_RANGE_RECEIVES_KEY.to_string(),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_amount_range_receives(
+        &self,
+    ) -> Result<Vec<CachedAmountRangeReceive>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(AMOUNT_RANGE_RECEIVES_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Stores `sponsorship` after the others, starting a new page when the
+    /// last one is full.
+    pub(crate) async fn append_claim_fee_sponsorship(
+        &self,
+        sponsorship: ClaimFeeSponsorship,
+    ) -> Result<(), StorageError> {
+        let page_count = self.fetch_claim_fee_sponsorships_page_count().await?;
+        let mut page = page_count.saturating_sub(1);
+        let mut sponsorships = self.fetch_claim_fee_sponsorships_page(page).await?;
+        if sponsorships.len() >= CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE {
+            page = page_count;
+            sponsorships.clear();
+        }
+        sponsorships.push(sponsorship);
+        self.storage
+            .set_cached_item(
+                claim_fee_sponsorships_key(page),
+                serde_json::to_string(&sponsorships)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the claim fee sponsorships, oldest first, skipping `offset`
+    /// of them and returning at most `limit`. Only the pages in range are read.
+    pub(crate) async fn fetch_claim_fee_sponsorships(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ClaimFeeSponsorship>, StorageError> {
+        let mut page = offset.div_euclid(CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE);
+        let mut skip = offset.rem_euclid(CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE);
+        let mut sponsorships = Vec::new();
+        while sponsorships.len() < limit {
+            let records = self.fetch_claim_fee_sponsorships_page(page).await?;
+            if records.is_empty() {
+                break;
+            }
+            let remaining = limit.saturating_sub(sponsorships.len());
+            sponsorships.extend(records.into_iter().skip(skip).take(remaining));
+            skip = 0;
+            page = page.saturating_add(1);
+        }
+        Ok(sponsorships)
+    }
+
+    /// The keys of the stored pages of claim fee sponsorships
+    pub(crate) async fn claim_fee_sponsorships_backup_cache_keys(
+        &self,
+    ) -> Result<Vec<String>, StorageError> {
+        let page_count = self.fetch_claim_fee_sponsorships_page_count().await?;
+        Ok((0..page_count).map(claim_fee_sponsorships_key).collect())
+    }
+
+    async fn fetch_claim_fee_sponsorships_page(
+        &self,
+        page: usize,
+    ) -> Result<Vec<ClaimFeeSponsorship>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(claim_fee_sponsorships_key(page))
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The number of stored pages of claim fee sponsorships. Pages are only
+    /// added after the last one, so the first missing page ends them.
+    async fn fetch_claim_fee_sponsorships_page_count(&self) -> Result<usize, StorageError> {
+        let mut page_count = 0;
+        while self
+            .storage
+            .get_cached_item(claim_fee_sponsorships_key(page_count))
+            .await?
+            .is_some()
+        {
+            page_count = page_count.saturating_add(1);
+        }
+        Ok(page_count)
+    }
+
+    pub(crate) async fn save_pending_claim_fee_sponsorships(
+        &self,
+        value: &[CachedPendingClaimFeeSponsorship],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                PENDING_CLAIM_FEE_SPONSORSHIPS_KEY.to_string(),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_pending_claim_fee_sponsorships(
+        &self,
+    ) -> Result<Vec<CachedPendingClaimFeeSponsorship>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(PENDING_CLAIM_FEE_SPONSORSHIPS_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub(crate) async fn save_lightning_receives(
+        &self,
+        value: &[CachedLightningReceive],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                LIGHTNING_RECEIVES_KEY.to_string(),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_lightning_receives(
+        &self,
+    ) -> Result<Vec<CachedLightningReceive>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(LIGHTNING_RECEIVES_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub(crate) async fn save_payment_consumer(
+        &self,
+        consumer_id: &str,
+        value: &CachedPaymentConsumer,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                format!("{PAYMENT_CONSUMER_KEY_PREFIX}{consumer_id}"),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_payment_consumer(
+        &self,
+        consumer_id: &str,
+    ) -> Result<CachedPaymentConsumer, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(format!("{PAYMENT_CONSUMER_KEY_PREFIX}{consumer_id}"))
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(CachedPaymentConsumer::default()),
+        }
+    }
+
+    pub(crate) async fn save_queued_sends(
+        &self,
+        value: &[CachedQueuedSend],
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(QUEUED_SENDS_KEY.to_string(), serde_json::to_string(value)?)
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_queued_sends(&self) -> Result<Vec<CachedQueuedSend>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(QUEUED_SENDS_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(serde_json::from_str(&value)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub(crate) async fn save_config_fingerprint(
+        &self,
+        fingerprint: &ConfigFingerprint,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                CONFIG_FINGERPRINT_KEY.to_string(),
+                serde_json::to_string(fingerprint)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_config_fingerprint(
+        &self,
+    ) -> Result<Option<ConfigFingerprint>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(CONFIG_FINGERPRINT_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn save_withdrawal_allowlist(
+        &self,
+        value: &WithdrawalAllowlist,
+    ) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(
+                WITHDRAWAL_ALLOWLIST_KEY.to_string(),
+                serde_json::to_string(value)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn fetch_withdrawal_allowlist(
+        &self,
+    ) -> Result<Option<WithdrawalAllowlist>, StorageError> {
+        let value = self
+            .storage
+            .get_cached_item(WITHDRAWAL_ALLOWLIST_KEY.to_string())
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
     pub(crate) async fn save_spark_private_mode_initialized(&self) -> Result<(), StorageError> {
         self.storage
             .set_cached_item(
@@ -926,6 +1705,80 @@ impl ObjectCacheRepository {
             .set_cached_item(LAST_SYNC_TIME_KEY.to_string(), time.to_string())
             .await
     }
+
+    /// Checks that the cached values derived from the wallet state can be
+    /// read. With `repair`, unreadable values are deleted, to be rebuilt on
+    /// the next sync.
+    pub(crate) async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<StorageIntegrityIssue>, StorageError> {
+        let results = [
+            (ACCOUNT_INFO_KEY, self.fetch_account_info().await.err()),
+            (
+                MONITORED_DEPOSIT_ADDRESSES_KEY,
+                self.fetch_monitored_deposit_addresses().await.err(),
+            ),
+            (
+                REPORTED_TOKEN_BALANCES_KEY,
+                self.fetch_reported_token_balances().await.err(),
+            ),
+            (
+                CONFIRMED_WITHDRAWALS_KEY,
+                self.fetch_confirmed_withdrawals().await.err(),
+            ),
+            (SYNC_OFFSET_KEY, self.fetch_sync_info().await.err()),
+            (FEE_ESTIMATES_KEY, self.fetch_fee_estimates().await.err()),
+            (TOKEN_INVOICES_KEY, self.fetch_token_invoices().await.err()),
+            (
+                AMOUNT_RANGE_RECEIVES_KEY,
+                self.fetch_amount_range_receives().await.err(),
+            ),
+            (
+                CLAIM_FEE_SPONSORSHIPS_KEY,
+                self.fetch_claim_fee_sponsorships_page(0).await.err(),
+            ),
+            (
+                PENDING_CLAIM_FEE_SPONSORSHIPS_KEY,
+                self.fetch_pending_claim_fee_sponsorships().await.err(),
+            ),
+            (
+                LIGHTNING_RECEIVES_KEY,
+                self.fetch_lightning_receives().await.err(),
+            ),
+            (LAST_SYNC_TIME_KEY, self.get_last_sync_time().await.err()),
+        ];
+        let mut issues = Vec::new();
+        for (key, error) in results {
+            match error {
+                None => {}
+                Some(StorageError::Serialization(_)) => {
+                    if repair {
+                        self.storage.delete_cached_item(key.to_string()).await?;
+                    }
+                    issues.push(StorageIntegrityIssue::invalid_json(
+                        "cache",
+                        key.to_string(),
+                        "value",
+                        repair,
+                    ));
+                }
+                Some(e) => return Err(e),
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// A deposit address handed out by `receive_payment`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct CachedDepositAddress {
+    pub(crate) address: String,
+    /// Whether a deposit to the address was seen
+    pub(crate) used: bool,
+    /// Whether the address is the one currently handed out
+    #[serde(default)]
+    pub(crate) current: bool,
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -939,6 +1792,19 @@ pub(crate) struct CachedAccountInfo {
 pub(crate) struct CachedSyncInfo {
     pub(crate) offset: u64,
     pub(crate) last_synced_final_token_payment_id: Option<String>,
+    /// Set while only the latest payments are synced, until the older ones
+    /// are backfilled
+    #[serde(default)]
+    pub(crate) history_partial: bool,
+}
+
+/// The received payments a consumer has acknowledged. The payments up to
+/// `cursor` are all acknowledged, or failed; only the ones acknowledged past
+/// it are listed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct CachedPaymentConsumer {
+    pub(crate) cursor: Option<PaymentCursor>,
+    pub(crate) acknowledged: BTreeSet<String>,
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -946,5 +1812,77 @@ pub(crate) struct CachedTx {
     pub(crate) raw_tx: String,
 }
 
+/// The fee estimated when a payment was prepared, compared against the fee it
+/// actually paid once it completes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedFeeEstimate {
+    pub(crate) payment_id: String,
+    pub(crate) estimated_fee_sats: u64,
+}
+
+/// A token invoice created by `receive_payment`, and the payment that paid it
+/// once one is matched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedTokenInvoice {
+    pub(crate) invoice: String,
+    pub(crate) token_identifier: String,
+    pub(crate) amount: u128,
+    pub(crate) payment_id: Option<String>,
+}
+
+/// An amountless BOLT11 invoice created by `receive_payment` with amount
+/// limits. It is a HODL invoice, claimed by the SDK only if the amount paid is
+/// within the limits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedAmountRangeReceive {
+    pub(crate) payment_hash: String,
+    /// Hex-encoded preimage
+    pub(crate) preimage: String,
+    pub(crate) min_amount_sats: Option<u64>,
+    pub(crate) max_amount_sats: Option<u64>,
+    /// Unix timestamp in seconds after which the invoice can't be paid
+    pub(crate) expires_at: u64,
+    /// Transfers paying an amount outside the limits, left unclaimed
+    pub(crate) rejected_transfer_ids: Vec<String>,
+}
+
+/// A BOLT11 invoice created by the SDK, with the stages its payment reached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedLightningReceive {
+    pub(crate) payment_hash: String,
+    /// The id of the receive request at the SSP
+    pub(crate) ssp_id: String,
+    /// Unix timestamp in seconds after which the invoice can't be paid
+    pub(crate) expires_at: u64,
+    pub(crate) trail: Vec<ReceiveStageUpdate>,
+}
+
+/// A payment queued by `send_payment` because the network was unreachable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedQueuedSend {
+    /// The idempotency key the payment is sent with
+    pub(crate) id: String,
+    pub(crate) request: SendPaymentRequest,
+    /// Unix timestamp in seconds
+    pub(crate) queued_at: u64,
+    /// Unix timestamp in seconds after which the payment isn't sent anymore
+    pub(crate) expires_at: u64,
+}
+
+/// A claim fee the sponsor failed to pay, retried on the next deposit claims.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedPendingClaimFeeSponsorship {
+    pub(crate) request: ClaimFeeSponsorRequest,
+    /// The id of the payment claiming the deposit
+    pub(crate) claim_payment_id: String,
+}
+
+/// An HTLC receive registered by `create_htlc_receive`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedHtlcReceive {
+    /// Unix timestamp in seconds
+    pub(crate) created_at: u64,
+}
+
 #[cfg(feature = "test-utils")]
 pub mod tests;
@@ -14,14 +14,15 @@ use spark_mysql::{mysql_async, tx_opts};
 use tracing::warn;
 
 use crate::{
-    AssetFilter, Contact, ConversionDetails, ConversionInfo, ConversionStatus, DepositInfo,
-    ListContactsRequest, LnurlPayInfo, LnurlReceiveMetadata, LnurlWithdrawInfo, PaymentDetails,
-    PaymentMethod, PaymentStatus, SparkHtlcDetails, SparkHtlcStatus,
+    AssetFilter, Contact, ConversionDetails, ConversionInfo, ConversionStatus, CounterpartyFilter,
+    DepositInfo, DisplayAmount, ListContactsRequest, LnurlPayInfo, LnurlReceiveMetadata,
+    LnurlWithdrawInfo, PaymentDetails, PaymentMethod, PaymentStatus, SparkHtlcDetails,
+    SparkHtlcStatus,
     error::DepositClaimError,
     persist::{
         Payment, PaymentMetadata, SetLnurlMetadataItem, Storage, StorageError,
-        StorageListPaymentsRequest, StoragePaymentDetailsFilter, StoredCrossChainSwap,
-        UpdateDepositPayload, parse_payment_status,
+        StorageIntegrityIssue, StorageListPaymentsRequest, StoragePaymentDetailsFilter,
+        StoredCrossChainSwap, UpdateDepositPayload, parse_payment_status, search_like_pattern,
     },
     sync_storage::{
         IncomingChange, OutgoingChange, Record, RecordChange, RecordId, UnversionedRecordChange,
@@ -530,6 +531,49 @@ impl MysqlStorage {
                         (user_id, provider, is_terminal)
                 )",
             )],
+            // Migration 21: Index backing the list_payments counterparty filter
+            vec![Migration::CreateIndex {
+                name: "brz_idx_payment_details_lightning_user_destination_pubkey",
+                table: "brz_payment_details_lightning",
+                columns: "(user_id, destination_pubkey)",
+            }],
+            // Migration 22: Flag for payments imported from another Breez SDK
+            vec![Migration::AddColumn {
+                table: "brz_payments",
+                column: "imported",
+                definition: "TINYINT(1) NOT NULL DEFAULT 0",
+            }],
+            // Migration 23: Group id linking the payments of one user action
+            vec![
+                Migration::AddColumn {
+                    table: "brz_payment_metadata",
+                    column: "group_id",
+                    definition: "VARCHAR(255) NULL",
+                },
+                Migration::CreateIndex {
+                    name: "brz_idx_payment_metadata_user_group",
+                    table: "brz_payment_metadata",
+                    columns: "(user_id, group_id)",
+                },
+            ],
+            // Migration 24: Merchant dispute annotations
+            vec![
+                Migration::AddColumn {
+                    table: "brz_payment_metadata",
+                    column: "dispute",
+                    definition: "JSON NULL",
+                },
+                Migration::AddColumn {
+                    table: "brz_payment_metadata",
+                    column: "dispute_status",
+                    definition: "VARCHAR(64) NULL",
+                },
+                Migration::CreateIndex {
+                    name: "brz_idx_payment_metadata_user_dispute_status",
+                    table: "brz_payment_metadata",
+                    columns: "(user_id, dispute_status)",
+                },
+            ],
         ]
     }
 }
@@ -780,8 +824,8 @@ impl MysqlStorage {
         };
 
         tx.exec_drop(
-            "INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark, imported)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                  ON DUPLICATE KEY UPDATE
                     payment_type = VALUES(payment_type),
                     status = VALUES(status),
@@ -790,7 +834,8 @@ impl MysqlStorage {
                     timestamp = VALUES(timestamp),
                     method = VALUES(method),
                     withdraw_tx_id = VALUES(withdraw_tx_id),
-                    spark = VALUES(spark)",
+                    spark = VALUES(spark),
+                    imported = VALUES(imported)",
             (
                 identity.to_vec(),
                 &payment.id,
@@ -802,6 +847,7 @@ impl MysqlStorage {
                 Some(payment.method.to_string()),
                 withdraw_tx_id.map(str::to_string),
                 spark,
+                payment.imported,
             ),
         )
         .await
@@ -957,6 +1003,20 @@ impl Storage for MysqlStorage {
             params.push(Value::from(i64::try_from(to_timestamp)?));
         }
 
+        let sort_ascending = request.sort_ascending.unwrap_or(false);
+
+        // Resume after the cursor, in the sort order
+        if let Some(ref after) = request.after {
+            let operator = if sort_ascending { ">" } else { "<" };
+            where_clauses.push(format!(
+                "(p.timestamp {operator} ? OR (p.timestamp = ? AND p.id {operator} ?))"
+            ));
+            let timestamp = i64::try_from(after.timestamp)?;
+            params.push(Value::from(timestamp));
+            params.push(Value::from(timestamp));
+            params.push(Value::from(after.id.clone()));
+        }
+
         if let Some(ref asset_filter) = request.asset_filter {
             match asset_filter {
                 AssetFilter::Bitcoin => {
@@ -1080,22 +1140,60 @@ impl Storage for MysqlStorage {
             }
         }
 
+        if let Some(ref counterparty_filter) = request.counterparty_filter {
+            match counterparty_filter {
+                CounterpartyFilter::LightningDestination { destination_pubkey } => {
+                    where_clauses.push("l.destination_pubkey = ?".to_string());
+                    params.push(Value::from(destination_pubkey.clone()));
+                }
+                CounterpartyFilter::LightningAddress { address } => {
+                    where_clauses.push(
+                        "LOWER(JSON_UNQUOTE(JSON_EXTRACT(pm.lnurl_pay_info, '$.ln_address'))) = ?"
+                            .to_string(),
+                    );
+                    params.push(Value::from(address.to_lowercase()));
+                }
+            }
+        }
+
+        if let Some(ref search) = request.search {
+            where_clauses.push(format!("LOWER({PAYMENT_SEARCH_SQL}) LIKE ?"));
+            params.push(Value::from(search_like_pattern(&search.to_lowercase())));
+        }
+
+        if let Some(ref group_ids) = request.group_id_filter
+            && !group_ids.is_empty()
+        {
+            let placeholders = build_placeholders(group_ids.len());
+            where_clauses.push(format!("pm.group_id IN ({placeholders})"));
+            for group_id in group_ids {
+                params.push(Value::from(group_id.clone()));
+            }
+        }
+
+        if let Some(ref dispute_statuses) = request.dispute_status_filter
+            && !dispute_statuses.is_empty()
+        {
+            let placeholders = build_placeholders(dispute_statuses.len());
+            where_clauses.push(format!("pm.dispute_status IN ({placeholders})"));
+            for status in dispute_statuses {
+                params.push(Value::from(status.to_string()));
+            }
+        }
+
         where_clauses.push("pm.parent_payment_id IS NULL".to_string());
 
         // Build the WHERE clause (always non-empty: tenant scoping is the first clause).
         let where_sql = format!("WHERE {}", where_clauses.join(" AND "));
 
-        let order_direction = if request.sort_ascending.unwrap_or(false) {
-            "ASC"
-        } else {
-            "DESC"
-        };
+        let order_direction = if sort_ascending { "ASC" } else { "DESC" };
 
         let limit = i64::from(request.limit.unwrap_or(u32::MAX));
         let offset = i64::from(request.offset.unwrap_or(0));
 
+        // The id breaks timestamp ties, so cursors resume at a stable position
         let query = format!(
-            "{SELECT_PAYMENT_SQL} {where_sql} ORDER BY p.timestamp {order_direction} LIMIT ? OFFSET ?"
+            "{SELECT_PAYMENT_SQL} {where_sql} ORDER BY p.timestamp {order_direction}, p.id {order_direction} LIMIT ? OFFSET ?"
         );
 
         params.push(Value::from(limit));
@@ -1188,17 +1286,25 @@ impl Storage for MysqlStorage {
             .conversion_status
             .as_ref()
             .map(std::string::ToString::to_string);
+        let dispute_json = to_json_string_opt(metadata.dispute.as_ref())?;
+        let dispute_status_str = metadata
+            .dispute
+            .as_ref()
+            .map(|dispute| dispute.status.to_string());
 
         conn.exec_drop(
-            "INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status, group_id, dispute, dispute_status)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
              ON DUPLICATE KEY UPDATE
                 parent_payment_id = COALESCE(VALUES(parent_payment_id), parent_payment_id),
                 lnurl_pay_info = COALESCE(VALUES(lnurl_pay_info), lnurl_pay_info),
                 lnurl_withdraw_info = COALESCE(VALUES(lnurl_withdraw_info), lnurl_withdraw_info),
                 lnurl_description = COALESCE(VALUES(lnurl_description), lnurl_description),
                 conversion_info = COALESCE(VALUES(conversion_info), conversion_info),
-                conversion_status = COALESCE(VALUES(conversion_status), conversion_status)",
+                conversion_status = COALESCE(VALUES(conversion_status), conversion_status),
+                group_id = COALESCE(VALUES(group_id), group_id),
+                dispute = COALESCE(VALUES(dispute), dispute),
+                dispute_status = COALESCE(VALUES(dispute_status), dispute_status)",
             (
                 self.identity.clone(),
                 payment_id,
@@ -1208,6 +1314,9 @@ impl Storage for MysqlStorage {
                 metadata.lnurl_description,
                 conversion_info_json,
                 conversion_status_str,
+                metadata.group_id,
+                dispute_json,
+                dispute_status_str,
             ),
         )
         .await
@@ -1401,6 +1510,7 @@ impl Storage for MysqlStorage {
                 claim_error,
                 refund_tx: get_opt_str(row, 5),
                 refund_tx_id: get_opt_str(row, 6),
+                claim_max_fee: None,
             });
         }
         Ok(deposits)
@@ -1984,8 +2094,56 @@ impl Storage for MysqlStorage {
 
         Ok(())
     }
+
+    /// `MySQL` validates the JSON columns and guards its own consistency, so
+    /// only orphaned payment details are looked for.
+    async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<StorageIntegrityIssue>, StorageError> {
+        let mut conn = self.pool.get_conn().await.map_err(map_db_error)?;
+        let mut tx = conn
+            .start_transaction(tx_opts())
+            .await
+            .map_err(map_db_error)?;
+
+        let mut issues = Vec::new();
+        for table in PAYMENT_DETAILS_TABLES {
+            let filter = "d.user_id = ? AND NOT EXISTS (
+                SELECT 1 FROM brz_payments p WHERE p.user_id = d.user_id AND p.id = d.payment_id)";
+            let payment_ids: Vec<String> = tx
+                .exec(
+                    format!("SELECT d.payment_id FROM {table} d WHERE {filter}"),
+                    (self.identity.clone(),),
+                )
+                .await
+                .map_err(map_db_error)?;
+            if repair && !payment_ids.is_empty() {
+                tx.exec_drop(
+                    format!("DELETE d FROM {table} d WHERE {filter}"),
+                    (self.identity.clone(),),
+                )
+                .await
+                .map_err(map_db_error)?;
+            }
+            issues.extend(payment_ids.into_iter().map(|payment_id| {
+                StorageIntegrityIssue::orphaned_record(table, payment_id, repair)
+            }));
+        }
+
+        tx.commit().await.map_err(map_db_error)?;
+        Ok(issues)
+    }
 }
 
+/// Tables holding the details of a payment, keyed by its id.
+const PAYMENT_DETAILS_TABLES: [&str; 4] = [
+    "brz_payment_details_lightning",
+    "brz_payment_details_token",
+    "brz_payment_details_spark",
+    "brz_payment_details_deposit",
+];
+
 /// Base query for payment lookups. Indices 0-31 are used by `map_payment`,
 /// index 32 (`parent_payment_id`) is only used by `get_payments_by_parent_ids`.
 const SELECT_PAYMENT_SQL: &str = "
@@ -2021,7 +2179,10 @@ const SELECT_PAYMENT_SQL: &str = "
            lrm.sender_comment AS lnurl_sender_comment,
            lrm.payment_hash AS lnurl_payment_hash,
            pm.conversion_status,
-           pm.parent_payment_id
+           pm.parent_payment_id,
+           p.imported,
+           pm.group_id,
+           pm.dispute
       FROM brz_payments p
       LEFT JOIN brz_payment_details_lightning l ON p.id = l.payment_id AND p.user_id = l.user_id
       LEFT JOIN brz_payment_details_deposit pd ON p.id = pd.payment_id AND p.user_id = pd.user_id
@@ -2030,6 +2191,23 @@ const SELECT_PAYMENT_SQL: &str = "
       LEFT JOIN brz_payment_metadata pm ON p.id = pm.payment_id AND p.user_id = pm.user_id
       LEFT JOIN brz_lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash AND l.user_id = lrm.user_id";
 
+/// The text a payment is searched by with [`StorageListPaymentsRequest::search`].
+const PAYMENT_SEARCH_SQL: &str = "CONCAT_WS(' ',
+    p.id,
+    p.withdraw_tx_id,
+    pd.tx_id,
+    l.invoice,
+    l.description,
+    pm.lnurl_description,
+    JSON_UNQUOTE(JSON_EXTRACT(pm.lnurl_pay_info, '$.ln_address')),
+    JSON_UNQUOTE(JSON_EXTRACT(pm.lnurl_pay_info, '$.comment')),
+    lrm.sender_comment,
+    t.tx_hash,
+    JSON_UNQUOTE(JSON_EXTRACT(t.invoice_details, '$.invoice')),
+    JSON_UNQUOTE(JSON_EXTRACT(t.invoice_details, '$.description')),
+    JSON_UNQUOTE(JSON_EXTRACT(s.invoice_details, '$.invoice')),
+    JSON_UNQUOTE(JSON_EXTRACT(s.invoice_details, '$.description')))";
+
 #[allow(clippy::too_many_lines)]
 fn map_payment(row: &Row) -> Result<Payment, StorageError> {
     let withdraw_tx_id: Option<String> = get_opt_str(row, 7);
@@ -2151,6 +2329,11 @@ fn map_payment(row: &Row) -> Result<Payment, StorageError> {
     let fees_str: String = get_str(row, 4)?;
     let method_str: Option<String> = get_opt_str(row, 6);
 
+    let amount: u128 = amount_str
+        .parse()
+        .map_err(|_| StorageError::Serialization("invalid amount".to_string()))?;
+    let display_amount = DisplayAmount::new(amount, details.as_ref());
+
     Ok(Payment {
         id: get_str(row, 0)?,
         payment_type: payment_type_str
@@ -2159,9 +2342,7 @@ fn map_payment(row: &Row) -> Result<Payment, StorageError> {
         status: status_str
             .parse()
             .map_err(|e: String| StorageError::Serialization(e))?,
-        amount: amount_str
-            .parse()
-            .map_err(|_| StorageError::Serialization("invalid amount".to_string()))?,
+        amount,
         fees: fees_str
             .parse()
             .map_err(|_| StorageError::Serialization("invalid fees".to_string()))?,
@@ -2186,6 +2367,10 @@ fn map_payment(row: &Row) -> Result<Payment, StorageError> {
                 })
                 .transpose()?
         },
+        imported: get_opt_bool(row, 33).unwrap_or(false),
+        display_amount,
+        group_id: get_opt_str(row, 34),
+        dispute: from_json_string_opt(get_opt_str(row, 35))?,
     })
 }
 
@@ -2344,6 +2529,54 @@ mod tests {
         crate::persist::tests::test_asset_filtering(Box::new(fixture.storage)).await;
     }
 
+    #[tokio::test]
+    async fn test_counterparty_filtering() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_counterparty_filtering(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_imported_payment_flag() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_imported_payment_flag(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_display_amount() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_payment_display_amount(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_group_id() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_payment_group_id(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_dispute() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_payment_dispute(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_list_payments_cursor() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_list_payments_cursor(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_search() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_payment_search(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity() {
+        let fixture = MysqlTestFixture::new().await;
+        crate::persist::tests::test_check_integrity(Box::new(fixture.storage)).await;
+    }
+
     #[tokio::test]
     async fn test_timestamp_filtering() {
         let fixture = MysqlTestFixture::new().await;
@@ -2687,6 +2920,10 @@ mod tests {
                 conversion_info: None,
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         };
         let mut pmt_b = pmt_a.clone();
         if let Some(PaymentDetails::Lightning {
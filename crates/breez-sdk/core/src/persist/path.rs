@@ -9,9 +9,19 @@ pub fn default_storage_path(
     data_dir: &str,
     network: &Network,
     identity_pub_key: &PublicKey,
+) -> Result<PathBuf, SdkError> {
+    tenant_storage_path(data_dir, network, &identity_pub_key.serialize())
+}
+
+/// Storage path of a tenant key, see [`storage_tenant_key`](super::backend::storage_tenant_key).
+/// For an identity public key this is [`default_storage_path`].
+pub fn tenant_storage_path(
+    data_dir: &str,
+    network: &Network,
+    tenant_key: &[u8],
 ) -> Result<PathBuf, SdkError> {
     let storage_dir = std::path::PathBuf::from_str(data_dir)?;
-    let path_suffix = sha256::Hash::hash(&identity_pub_key.serialize())
+    let path_suffix = sha256::Hash::hash(tenant_key)
         .to_string()
         .chars()
         .take(8)
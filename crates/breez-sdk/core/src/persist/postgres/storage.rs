@@ -15,14 +15,15 @@ use tokio_postgres::{Row, Transaction, types::ToSql};
 use tracing::warn;
 
 use crate::{
-    AssetFilter, Contact, ConversionDetails, ConversionInfo, ConversionStatus, DepositInfo,
-    ListContactsRequest, LnurlPayInfo, LnurlReceiveMetadata, LnurlWithdrawInfo, PaymentDetails,
-    PaymentMethod, PaymentStatus, SparkHtlcDetails, SparkHtlcStatus,
+    AssetFilter, Contact, ConversionDetails, ConversionInfo, ConversionStatus, CounterpartyFilter,
+    DepositInfo, DisplayAmount, ListContactsRequest, LnurlPayInfo, LnurlReceiveMetadata,
+    LnurlWithdrawInfo, PaymentDetails, PaymentMethod, PaymentStatus, SparkHtlcDetails,
+    SparkHtlcStatus,
     error::DepositClaimError,
     persist::{
         Payment, PaymentMetadata, SetLnurlMetadataItem, Storage, StorageError,
-        StorageListPaymentsRequest, StoragePaymentDetailsFilter, StoredCrossChainSwap,
-        UpdateDepositPayload, parse_payment_status,
+        StorageIntegrityIssue, StorageListPaymentsRequest, StoragePaymentDetailsFilter,
+        StoredCrossChainSwap, UpdateDepositPayload, parse_payment_status, search_like_pattern,
     },
     sync_storage::{
         IncomingChange, OutgoingChange, Record, RecordChange, RecordId, UnversionedRecordChange,
@@ -467,6 +468,32 @@ impl PostgresStorage {
                 "CREATE INDEX IF NOT EXISTS brz_idx_cross_chain_swaps_user_provider_is_terminal
                     ON brz_cross_chain_swaps (user_id, provider, is_terminal)".to_string(),
             ],
+            // Migration 20: Indexes backing the list_payments counterparty filter
+            vec![
+                "CREATE INDEX IF NOT EXISTS brz_idx_payment_details_lightning_user_destination_pubkey
+                    ON brz_payment_details_lightning (user_id, destination_pubkey)".to_string(),
+                "CREATE INDEX IF NOT EXISTS brz_idx_payment_metadata_user_ln_address
+                    ON brz_payment_metadata (user_id, (lower(lnurl_pay_info->>'ln_address')))".to_string(),
+            ],
+            // Migration 21: Flag for payments imported from another Breez SDK
+            vec![
+                "ALTER TABLE brz_payments ADD COLUMN IF NOT EXISTS imported BOOLEAN NOT NULL DEFAULT FALSE"
+                    .to_string(),
+            ],
+            // Migration 22: Group id linking the payments of one user action
+            vec![
+                "ALTER TABLE brz_payment_metadata ADD COLUMN IF NOT EXISTS group_id TEXT".to_string(),
+                "CREATE INDEX IF NOT EXISTS brz_idx_payment_metadata_user_group
+                    ON brz_payment_metadata (user_id, group_id)".to_string(),
+            ],
+            // Migration 23: Merchant dispute annotations
+            vec![
+                "ALTER TABLE brz_payment_metadata ADD COLUMN IF NOT EXISTS dispute JSONB".to_string(),
+                "ALTER TABLE brz_payment_metadata ADD COLUMN IF NOT EXISTS dispute_status TEXT"
+                    .to_string(),
+                "CREATE INDEX IF NOT EXISTS brz_idx_payment_metadata_user_dispute_status
+                    ON brz_payment_metadata (user_id, dispute_status)".to_string(),
+            ],
         ]
     }
 }
@@ -658,8 +685,8 @@ impl PostgresStorage {
 
         // Insert or update main payment record (including detail columns atomically)
         tx.execute(
-            "INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark, imported)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                  ON CONFLICT(user_id, id) DO UPDATE SET
                     payment_type = EXCLUDED.payment_type,
                     status = EXCLUDED.status,
@@ -668,7 +695,8 @@ impl PostgresStorage {
                     timestamp = EXCLUDED.timestamp,
                     method = EXCLUDED.method,
                     withdraw_tx_id = EXCLUDED.withdraw_tx_id,
-                    spark = EXCLUDED.spark",
+                    spark = EXCLUDED.spark,
+                    imported = EXCLUDED.imported",
             &[
                 &identity,
                 &payment.id,
@@ -680,6 +708,7 @@ impl PostgresStorage {
                 &Some(payment.method.to_string()),
                 &withdraw_tx_id,
                 &spark,
+                &payment.imported,
             ],
         )
         .await
@@ -840,6 +869,20 @@ impl Storage for PostgresStorage {
             params.push(Box::new(i64::try_from(to_timestamp)?));
         }
 
+        let sort_ascending = request.sort_ascending.unwrap_or(false);
+
+        // Resume after the cursor, in the sort order
+        if let Some(ref after) = request.after {
+            let operator = if sort_ascending { ">" } else { "<" };
+            let id_idx = param_idx + 1;
+            where_clauses.push(format!(
+                "(p.timestamp {operator} ${param_idx} OR (p.timestamp = ${param_idx} AND p.id {operator} ${id_idx}))"
+            ));
+            param_idx += 2;
+            params.push(Box::new(i64::try_from(after.timestamp)?));
+            params.push(Box::new(after.id.clone()));
+        }
+
         // Filter by asset
         if let Some(ref asset_filter) = request.asset_filter {
             match asset_filter {
@@ -977,6 +1020,70 @@ impl Storage for PostgresStorage {
             }
         }
 
+        // Filter by counterparty
+        if let Some(ref counterparty_filter) = request.counterparty_filter {
+            match counterparty_filter {
+                CounterpartyFilter::LightningDestination { destination_pubkey } => {
+                    where_clauses.push(format!("l.destination_pubkey = ${param_idx}"));
+                    param_idx += 1;
+                    params.push(Box::new(destination_pubkey.clone()));
+                }
+                CounterpartyFilter::LightningAddress { address } => {
+                    where_clauses.push(format!(
+                        "lower(pm.lnurl_pay_info->>'ln_address') = ${param_idx}"
+                    ));
+                    param_idx += 1;
+                    params.push(Box::new(address.to_lowercase()));
+                }
+            }
+        }
+
+        // Filter by free text
+        if let Some(ref search) = request.search {
+            where_clauses.push(format!("{PAYMENT_SEARCH_SQL} ILIKE ${param_idx}"));
+            param_idx += 1;
+            params.push(Box::new(search_like_pattern(search)));
+        }
+
+        // Filter by group
+        if let Some(ref group_ids) = request.group_id_filter
+            && !group_ids.is_empty()
+        {
+            let placeholders: Vec<String> = group_ids
+                .iter()
+                .map(|_| {
+                    let placeholder = format!("${param_idx}");
+                    param_idx += 1;
+                    placeholder
+                })
+                .collect();
+            where_clauses.push(format!("pm.group_id IN ({})", placeholders.join(", ")));
+            for group_id in group_ids {
+                params.push(Box::new(group_id.clone()));
+            }
+        }
+
+        // Filter by dispute status
+        if let Some(ref dispute_statuses) = request.dispute_status_filter
+            && !dispute_statuses.is_empty()
+        {
+            let placeholders: Vec<String> = dispute_statuses
+                .iter()
+                .map(|_| {
+                    let placeholder = format!("${param_idx}");
+                    param_idx += 1;
+                    placeholder
+                })
+                .collect();
+            where_clauses.push(format!(
+                "pm.dispute_status IN ({})",
+                placeholders.join(", ")
+            ));
+            for status in dispute_statuses {
+                params.push(Box::new(status.to_string()));
+            }
+        }
+
         // Exclude child payments
         where_clauses.push("pm.parent_payment_id IS NULL".to_string());
 
@@ -984,18 +1091,15 @@ impl Storage for PostgresStorage {
         let where_sql = format!("WHERE {}", where_clauses.join(" AND "));
 
         // Determine sort order
-        let order_direction = if request.sort_ascending.unwrap_or(false) {
-            "ASC"
-        } else {
-            "DESC"
-        };
+        let order_direction = if sort_ascending { "ASC" } else { "DESC" };
 
         let limit = i64::from(request.limit.unwrap_or(u32::MAX));
         let offset = i64::from(request.offset.unwrap_or(0));
 
         let offset_idx = param_idx + 1;
+        // The id breaks timestamp ties, so cursors resume at a stable position
         let query = format!(
-            "{SELECT_PAYMENT_SQL} {where_sql} ORDER BY p.timestamp {order_direction} LIMIT ${param_idx} OFFSET ${offset_idx}"
+            "{SELECT_PAYMENT_SQL} {where_sql} ORDER BY p.timestamp {order_direction}, p.id {order_direction} LIMIT ${param_idx} OFFSET ${offset_idx}"
         );
 
         params.push(Box::new(limit));
@@ -1068,18 +1172,26 @@ impl Storage for PostgresStorage {
             .conversion_status
             .as_ref()
             .map(std::string::ToString::to_string);
+        let dispute_json = to_json_opt(metadata.dispute.as_ref())?;
+        let dispute_status_str = metadata
+            .dispute
+            .as_ref()
+            .map(|dispute| dispute.status.to_string());
 
         client
             .execute(
-                "INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status, group_id, dispute, dispute_status)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                  ON CONFLICT(user_id, payment_id) DO UPDATE SET
                     parent_payment_id = COALESCE(EXCLUDED.parent_payment_id, brz_payment_metadata.parent_payment_id),
                     lnurl_pay_info = COALESCE(EXCLUDED.lnurl_pay_info, brz_payment_metadata.lnurl_pay_info),
                     lnurl_withdraw_info = COALESCE(EXCLUDED.lnurl_withdraw_info, brz_payment_metadata.lnurl_withdraw_info),
                     lnurl_description = COALESCE(EXCLUDED.lnurl_description, brz_payment_metadata.lnurl_description),
                     conversion_info = COALESCE(EXCLUDED.conversion_info, brz_payment_metadata.conversion_info),
-                    conversion_status = COALESCE(EXCLUDED.conversion_status, brz_payment_metadata.conversion_status)",
+                    conversion_status = COALESCE(EXCLUDED.conversion_status, brz_payment_metadata.conversion_status),
+                    group_id = COALESCE(EXCLUDED.group_id, brz_payment_metadata.group_id),
+                    dispute = COALESCE(EXCLUDED.dispute, brz_payment_metadata.dispute),
+                    dispute_status = COALESCE(EXCLUDED.dispute_status, brz_payment_metadata.dispute_status)",
                 &[
                     &self.identity,
                     &payment_id,
@@ -1089,6 +1201,9 @@ impl Storage for PostgresStorage {
                     &metadata.lnurl_description,
                     &conversion_info_json,
                     &conversion_status_str,
+                    &metadata.group_id,
+                    &dispute_json,
+                    &dispute_status_str,
                 ],
             )
             .await?;
@@ -1280,6 +1395,7 @@ impl Storage for PostgresStorage {
                 claim_error,
                 refund_tx: row.get(5),
                 refund_tx_id: row.get(6),
+                claim_max_fee: None,
             });
         }
         Ok(deposits)
@@ -1874,10 +1990,59 @@ impl Storage for PostgresStorage {
 
         Ok(())
     }
+
+    /// `PostgreSQL` validates the JSONB columns and guards its own
+    /// consistency, so only orphaned payment details are looked for.
+    async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<StorageIntegrityIssue>, StorageError> {
+        let mut client = self.pool.get().await.map_err(map_pool_error)?;
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+
+        let mut issues = Vec::new();
+        for table in PAYMENT_DETAILS_TABLES {
+            let filter = "d.user_id = $1 AND NOT EXISTS (
+                SELECT 1 FROM brz_payments p WHERE p.user_id = d.user_id AND p.id = d.payment_id)";
+            let rows = tx
+                .query(
+                    &format!("SELECT d.payment_id FROM {table} d WHERE {filter}"),
+                    &[&self.identity],
+                )
+                .await?;
+            if repair && !rows.is_empty() {
+                tx.execute(
+                    &format!("DELETE FROM {table} d WHERE {filter}"),
+                    &[&self.identity],
+                )
+                .await?;
+            }
+            issues.extend(
+                rows.iter()
+                    .map(|row| StorageIntegrityIssue::orphaned_record(table, row.get(0), repair)),
+            );
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+        Ok(issues)
+    }
 }
 
+/// Tables holding the details of a payment, keyed by its id.
+const PAYMENT_DETAILS_TABLES: [&str; 4] = [
+    "brz_payment_details_lightning",
+    "brz_payment_details_token",
+    "brz_payment_details_spark",
+    "brz_payment_details_deposit",
+];
+
 /// Base query for payment lookups.
-/// Column indices 0-31 are used by `map_payment`, index 32 (`parent_payment_id`) is only used by `get_payments_by_parent_ids`.
+/// Column index 32 (`parent_payment_id`) is only used by `get_payments_by_parent_ids`, all others by `map_payment`.
 const SELECT_PAYMENT_SQL: &str = "
     SELECT p.id,
            p.payment_type,
@@ -1911,7 +2076,10 @@ const SELECT_PAYMENT_SQL: &str = "
            lrm.sender_comment AS lnurl_sender_comment,
            lrm.payment_hash AS lnurl_payment_hash,
            pm.conversion_status,
-           pm.parent_payment_id
+           pm.parent_payment_id,
+           p.imported,
+           pm.group_id,
+           pm.dispute
       FROM brz_payments p
       LEFT JOIN brz_payment_details_lightning l ON p.id = l.payment_id AND p.user_id = l.user_id
       LEFT JOIN brz_payment_details_token t ON p.id = t.payment_id AND p.user_id = t.user_id
@@ -1920,6 +2088,23 @@ const SELECT_PAYMENT_SQL: &str = "
       LEFT JOIN brz_payment_metadata pm ON p.id = pm.payment_id AND p.user_id = pm.user_id
       LEFT JOIN brz_lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash AND l.user_id = lrm.user_id";
 
+/// The text a payment is searched by with [`StorageListPaymentsRequest::search`].
+const PAYMENT_SEARCH_SQL: &str = "concat_ws(' ',
+    p.id,
+    p.withdraw_tx_id,
+    pd.tx_id,
+    l.invoice,
+    l.description,
+    pm.lnurl_description,
+    pm.lnurl_pay_info->>'ln_address',
+    pm.lnurl_pay_info->>'comment',
+    lrm.sender_comment,
+    t.tx_hash,
+    t.invoice_details->>'invoice',
+    t.invoice_details->>'description',
+    s.invoice_details->>'invoice',
+    s.invoice_details->>'description')";
+
 #[allow(clippy::too_many_lines)]
 fn map_payment(row: &Row) -> Result<Payment, StorageError> {
     let withdraw_tx_id: Option<String> = row.get(7);
@@ -2043,6 +2228,11 @@ fn map_payment(row: &Row) -> Result<Payment, StorageError> {
     let fees_str: String = row.get(4);
     let method_str: Option<String> = row.get(6);
 
+    let amount: u128 = amount_str
+        .parse()
+        .map_err(|_| StorageError::Serialization("invalid amount".to_string()))?;
+    let display_amount = DisplayAmount::new(amount, details.as_ref());
+
     Ok(Payment {
         id: row.get(0),
         payment_type: payment_type_str
@@ -2051,9 +2241,7 @@ fn map_payment(row: &Row) -> Result<Payment, StorageError> {
         status: status_str
             .parse()
             .map_err(|e: String| StorageError::Serialization(e))?,
-        amount: amount_str
-            .parse()
-            .map_err(|_| StorageError::Serialization("invalid amount".to_string()))?,
+        amount,
         fees: fees_str
             .parse()
             .map_err(|_| StorageError::Serialization("invalid fees".to_string()))?,
@@ -2078,6 +2266,10 @@ fn map_payment(row: &Row) -> Result<Payment, StorageError> {
                 })
                 .transpose()?
         },
+        imported: row.get(33),
+        display_amount,
+        group_id: row.get(34),
+        dispute: from_json_opt(row.get(35))?,
     })
 }
 
@@ -2173,6 +2365,54 @@ mod tests {
         crate::persist::tests::test_asset_filtering(Box::new(fixture.storage)).await;
     }
 
+    #[tokio::test]
+    async fn test_counterparty_filtering() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_counterparty_filtering(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_imported_payment_flag() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_imported_payment_flag(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_display_amount() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_payment_display_amount(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_group_id() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_payment_group_id(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_dispute() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_payment_dispute(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_list_payments_cursor() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_list_payments_cursor(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_search() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_payment_search(Box::new(fixture.storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity() {
+        let fixture = PostgresTestFixture::new().await;
+        crate::persist::tests::test_check_integrity(Box::new(fixture.storage)).await;
+    }
+
     #[tokio::test]
     async fn test_timestamp_filtering() {
         let fixture = PostgresTestFixture::new().await;
@@ -2415,6 +2655,10 @@ mod tests {
                 conversion_info: None,
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         };
         let mut pmt_b = pmt_a.clone();
         if let Some(PaymentDetails::Lightning {
@@ -8,12 +8,13 @@ use rusqlite::{
 use rusqlite_migration::{M, Migrations, SchemaVersion};
 
 use crate::{
-    AssetFilter, Contact, ConversionDetails, ConversionInfo, ConversionStatus, DepositInfo,
-    ListContactsRequest, LnurlPayInfo, LnurlReceiveMetadata, LnurlWithdrawInfo, PaymentDetails,
-    PaymentMethod, PaymentStatus, SparkHtlcDetails, SparkHtlcStatus, TokenTransactionType,
+    AssetFilter, Contact, ConversionDetails, ConversionInfo, ConversionStatus, CounterpartyFilter,
+    DepositInfo, DisplayAmount, ListContactsRequest, LnurlPayInfo, LnurlReceiveMetadata,
+    LnurlWithdrawInfo, PaymentDetails, PaymentDispute, PaymentMethod, PaymentStatus,
+    SparkHtlcDetails, SparkHtlcStatus, TokenTransactionType,
     error::DepositClaimError,
     persist::{
-        PaymentMetadata, SetLnurlMetadataItem, StorageListPaymentsRequest,
+        PaymentMetadata, SetLnurlMetadataItem, StorageIntegrityIssue, StorageListPaymentsRequest,
         StoragePaymentDetailsFilter, StoredCrossChainSwap, UpdateDepositPayload,
         parse_payment_status,
     },
@@ -79,6 +80,13 @@ impl SqliteStorage {
             Self::migrate_lnurl_metadata_description(&mut conn)?;
         }
 
+        if previous_version < 45 {
+            conn.execute(
+                &format!("INSERT INTO payment_search (payment_id, content) {PAYMENT_SEARCH_SQL}"),
+                [],
+            )?;
+        }
+
         Ok(())
     }
 
@@ -370,6 +378,26 @@ impl SqliteStorage {
             );
             CREATE INDEX idx_cross_chain_swaps_provider_is_terminal
                 ON cross_chain_swaps(provider, is_terminal);",
+            // Indexes backing the list_payments counterparty filter
+            "CREATE INDEX idx_payment_details_lightning_destination_pubkey
+                ON payment_details_lightning(destination_pubkey);
+            CREATE INDEX idx_payment_metadata_ln_address
+                ON payment_metadata(lower(json_extract(lnurl_pay_info, '$.ln_address')));",
+            "ALTER TABLE payments ADD COLUMN imported INTEGER NOT NULL DEFAULT 0;",
+            // Index backing the list_payments ordering and cursors
+            "CREATE INDEX idx_payments_timestamp_id ON payments(timestamp, id);",
+            // Full-text index backing the list_payments search. The trigram
+            // tokenizer matches substrings, e.g. part of an invoice.
+            "CREATE VIRTUAL TABLE payment_search USING fts5(
+                payment_id UNINDEXED,
+                content,
+                tokenize = 'trigram'
+            );",
+            "ALTER TABLE payment_metadata ADD COLUMN group_id TEXT;
+            CREATE INDEX idx_payment_metadata_group_id ON payment_metadata(group_id);",
+            "ALTER TABLE payment_metadata ADD COLUMN dispute TEXT;
+            ALTER TABLE payment_metadata ADD COLUMN dispute_status TEXT;
+            CREATE INDEX idx_payment_metadata_dispute_status ON payment_metadata(dispute_status);",
         ]
     }
 }
@@ -441,8 +469,8 @@ impl SqliteStorage {
 
         // Insert or update main payment record (including detail columns atomically)
         tx.execute(
-            "INSERT INTO payments (id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "INSERT INTO payments (id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark, imported)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
              ON CONFLICT(id) DO UPDATE SET
                 payment_type=excluded.payment_type,
                 status=excluded.status,
@@ -451,7 +479,8 @@ impl SqliteStorage {
                 timestamp=excluded.timestamp,
                 method=excluded.method,
                 withdraw_tx_id=excluded.withdraw_tx_id,
-                spark=excluded.spark",
+                spark=excluded.spark,
+                imported=excluded.imported",
             params![
                 payment.id,
                 payment.payment_type.to_string(),
@@ -462,6 +491,7 @@ impl SqliteStorage {
                 payment.method,
                 withdraw_tx_id,
                 spark,
+                payment.imported,
             ],
         )?;
 
@@ -554,6 +584,29 @@ impl SqliteStorage {
             Some(PaymentDetails::Withdraw { .. }) | None => {}
         }
 
+        Self::index_payment_search(tx, &payment.id)?;
+        Ok(())
+    }
+
+    fn query_ids(conn: &Connection, sql: &str) -> Result<Vec<String>, StorageError> {
+        Ok(conn
+            .prepare(sql)?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Refreshes the searched text of a payment, after any of its details changed.
+    fn index_payment_search(conn: &Connection, payment_id: &str) -> Result<(), StorageError> {
+        conn.execute(
+            "DELETE FROM payment_search WHERE payment_id = ?",
+            params![payment_id],
+        )?;
+        conn.execute(
+            &format!(
+                "INSERT INTO payment_search (payment_id, content) {PAYMENT_SEARCH_SQL} WHERE p.id = ?"
+            ),
+            params![payment_id],
+        )?;
         Ok(())
     }
 }
@@ -612,6 +665,19 @@ impl Storage for SqliteStorage {
             params.push(Box::new(to_timestamp));
         }
 
+        let sort_ascending = request.sort_ascending.unwrap_or(false);
+
+        // Resume after the cursor, in the sort order
+        if let Some(ref after) = request.after {
+            let operator = if sort_ascending { ">" } else { "<" };
+            where_clauses.push(format!(
+                "(p.timestamp {operator} ? OR (p.timestamp = ? AND p.id {operator} ?))"
+            ));
+            params.push(Box::new(after.timestamp));
+            params.push(Box::new(after.timestamp));
+            params.push(Box::new(after.id.clone()));
+        }
+
         // Filter by asset
         if let Some(ref asset_filter) = request.asset_filter {
             match asset_filter {
@@ -745,6 +811,66 @@ impl Storage for SqliteStorage {
             }
         }
 
+        // Filter by counterparty
+        if let Some(ref counterparty_filter) = request.counterparty_filter {
+            match counterparty_filter {
+                CounterpartyFilter::LightningDestination { destination_pubkey } => {
+                    where_clauses.push("l.destination_pubkey = ?".to_string());
+                    params.push(Box::new(destination_pubkey.clone()));
+                }
+                CounterpartyFilter::LightningAddress { address } => {
+                    where_clauses.push(
+                        "lower(json_extract(pm.lnurl_pay_info, '$.ln_address')) = ?".to_string(),
+                    );
+                    params.push(Box::new(address.to_lowercase()));
+                }
+            }
+        }
+
+        // Filter by free text
+        if let Some(ref search) = request.search {
+            // The trigram index only serves terms of at least 3 characters
+            if search.chars().count() >= 3 {
+                where_clauses.push(
+                    "p.id IN (SELECT payment_id FROM payment_search WHERE payment_search MATCH ?)"
+                        .to_string(),
+                );
+                params.push(Box::new(format!("\"{}\"", search.replace('"', "\"\""))));
+            } else {
+                where_clauses.push(
+                    "p.id IN (SELECT payment_id FROM payment_search WHERE instr(lower(content), ?) > 0)"
+                        .to_string(),
+                );
+                params.push(Box::new(search.to_lowercase()));
+            }
+        }
+
+        // Filter by group
+        if let Some(ref group_ids) = request.group_id_filter
+            && !group_ids.is_empty()
+        {
+            let placeholders = group_ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+            where_clauses.push(format!("pm.group_id IN ({placeholders})"));
+            for group_id in group_ids {
+                params.push(Box::new(group_id.clone()));
+            }
+        }
+
+        // Filter by dispute status
+        if let Some(ref dispute_statuses) = request.dispute_status_filter
+            && !dispute_statuses.is_empty()
+        {
+            let placeholders = dispute_statuses
+                .iter()
+                .map(|_| "?")
+                .collect::<Vec<_>>()
+                .join(", ");
+            where_clauses.push(format!("pm.dispute_status IN ({placeholders})"));
+            for status in dispute_statuses {
+                params.push(Box::new(status.to_string()));
+            }
+        }
+
         // Exclude child payments (those with a parent_payment_id)
         // Child payments are accessed via the parent's related_payments field
         where_clauses.push("pm.parent_payment_id IS NULL".to_string());
@@ -757,14 +883,11 @@ impl Storage for SqliteStorage {
         };
 
         // Determine sort order
-        let order_direction = if request.sort_ascending.unwrap_or(false) {
-            "ASC"
-        } else {
-            "DESC"
-        };
+        let order_direction = if sort_ascending { "ASC" } else { "DESC" };
 
+        // The id breaks timestamp ties, so cursors resume at a stable position
         let query = format!(
-            "{SELECT_PAYMENT_SQL} {where_sql} ORDER BY p.timestamp {order_direction} LIMIT {} OFFSET {}",
+            "{SELECT_PAYMENT_SQL} {where_sql} ORDER BY p.timestamp {order_direction}, p.id {order_direction} LIMIT {} OFFSET {}",
             request.limit.unwrap_or(u32::MAX),
             request.offset.unwrap_or(0)
         );
@@ -816,15 +939,18 @@ impl Storage for SqliteStorage {
         let connection = self.get_connection()?;
 
         connection.execute(
-            "INSERT INTO payment_metadata (payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status)
-             VALUES (?, ?, ?, ?, ?, ?, ?)
+            "INSERT INTO payment_metadata (payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status, group_id, dispute, dispute_status)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
              ON CONFLICT(payment_id) DO UPDATE SET
                 parent_payment_id = COALESCE(excluded.parent_payment_id, parent_payment_id),
                 lnurl_pay_info = COALESCE(excluded.lnurl_pay_info, lnurl_pay_info),
                 lnurl_withdraw_info = COALESCE(excluded.lnurl_withdraw_info, lnurl_withdraw_info),
                 lnurl_description = COALESCE(excluded.lnurl_description, lnurl_description),
                 conversion_info = COALESCE(excluded.conversion_info, conversion_info),
-                conversion_status = COALESCE(excluded.conversion_status, conversion_status)",
+                conversion_status = COALESCE(excluded.conversion_status, conversion_status),
+                group_id = COALESCE(excluded.group_id, group_id),
+                dispute = COALESCE(excluded.dispute, dispute),
+                dispute_status = COALESCE(excluded.dispute_status, dispute_status)",
             params![
                 payment_id,
                 metadata.parent_payment_id,
@@ -833,8 +959,12 @@ impl Storage for SqliteStorage {
                 metadata.lnurl_description,
                 metadata.conversion_info.as_ref().map(serde_json::to_string).transpose()?,
                 metadata.conversion_status.as_ref().map(std::string::ToString::to_string),
+                metadata.group_id,
+                metadata.dispute,
+                metadata.dispute.as_ref().map(|dispute| dispute.status.to_string()),
             ],
         )?;
+        Self::index_payment_search(&connection, &payment_id)?;
 
         Ok(())
     }
@@ -985,6 +1115,7 @@ impl Storage for SqliteStorage {
                 claim_error: row.get(4)?,
                 refund_tx: row.get(5)?,
                 refund_tx_id: row.get(6)?,
+                claim_max_fee: None,
             })
         })?;
         let mut deposits = Vec::new();
@@ -1037,6 +1168,16 @@ impl Storage for SqliteStorage {
                     metadata.sender_comment,
                 ],
             )?;
+            // The sender comment is searched
+            let payment_ids = connection
+                .prepare("SELECT payment_id FROM payment_details_lightning WHERE payment_hash = ?")?
+                .query_map(params![metadata.payment_hash], |row| {
+                    row.get::<_, String>(0)
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            for payment_id in payment_ids {
+                Self::index_payment_search(&connection, &payment_id)?;
+            }
         }
         Ok(())
     }
@@ -1531,10 +1672,70 @@ impl Storage for SqliteStorage {
         tx.commit().map_err(map_sqlite_error)?;
         Ok(())
     }
+
+    async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<StorageIntegrityIssue>, StorageError> {
+        let mut connection = self.get_connection()?;
+        let mut issues: Vec<StorageIntegrityIssue> = connection
+            .prepare("PRAGMA integrity_check")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .filter(|message| !matches!(message.as_deref(), Ok("ok")))
+            .map(|message| message.map(StorageIntegrityIssue::corruption))
+            .collect::<Result<_, _>>()?;
+
+        let tx = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(map_sqlite_error)?;
+        for table in PAYMENT_DETAILS_TABLES {
+            let filter = "payment_id NOT IN (SELECT id FROM payments)";
+            let payment_ids = Self::query_ids(
+                &tx,
+                &format!("SELECT payment_id FROM {table} WHERE {filter}"),
+            )?;
+            if repair && !payment_ids.is_empty() {
+                tx.execute(&format!("DELETE FROM {table} WHERE {filter}"), [])?;
+            }
+            issues.extend(payment_ids.into_iter().map(|payment_id| {
+                StorageIntegrityIssue::orphaned_record(table, payment_id, repair)
+            }));
+        }
+        for (columns, optional) in [
+            (OPTIONAL_JSON_COLUMNS.as_slice(), true),
+            (REQUIRED_JSON_COLUMNS.as_slice(), false),
+        ] {
+            for &(table, id, column) in columns {
+                let filter = format!("{column} IS NOT NULL AND NOT json_valid({column})");
+                let record_ids =
+                    Self::query_ids(&tx, &format!("SELECT {id} FROM {table} WHERE {filter}"))?;
+                let repaired = repair && optional;
+                if repaired && !record_ids.is_empty() {
+                    tx.execute(
+                        &format!("UPDATE {table} SET {column} = NULL WHERE {filter}"),
+                        [],
+                    )?;
+                }
+                issues.extend(record_ids.into_iter().map(|record_id| {
+                    StorageIntegrityIssue::invalid_json(table, record_id, column, repaired)
+                }));
+            }
+        }
+        if repair {
+            // The search index is derived from the payments, so it is rebuilt
+            tx.execute("DELETE FROM payment_search", [])?;
+            tx.execute(
+                &format!("INSERT INTO payment_search (payment_id, content) {PAYMENT_SEARCH_SQL}"),
+                [],
+            )?;
+        }
+        tx.commit().map_err(map_sqlite_error)?;
+        Ok(issues)
+    }
 }
 
 /// Base query for payment lookups.
-/// Column indices 0-31 are used by `map_payment`, index 32 (`parent_payment_id`) is only used by `get_payments_by_parent_ids`.
+/// Column index 32 (`parent_payment_id`) is only used by `get_payments_by_parent_ids`, all others by `map_payment`.
 const SELECT_PAYMENT_SQL: &str = "
     SELECT p.id,
            p.payment_type,
@@ -1568,7 +1769,10 @@ const SELECT_PAYMENT_SQL: &str = "
            lrm.sender_comment AS lnurl_sender_comment,
            lrm.payment_hash AS lnurl_payment_hash,
            pm.conversion_status,
-           pm.parent_payment_id
+           pm.parent_payment_id,
+           p.imported,
+           pm.group_id,
+           pm.dispute
       FROM payments p
       LEFT JOIN payment_details_lightning l ON p.id = l.payment_id
       LEFT JOIN payment_details_token t ON p.id = t.payment_id
@@ -1577,6 +1781,61 @@ const SELECT_PAYMENT_SQL: &str = "
       LEFT JOIN payment_metadata pm ON p.id = pm.payment_id
       LEFT JOIN lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash";
 
+/// Selects the text a payment is searched by with [`StorageListPaymentsRequest::search`].
+const PAYMENT_SEARCH_SQL: &str = "
+    SELECT p.id,
+           concat_ws(' ',
+               p.id,
+               p.withdraw_tx_id,
+               pd.tx_id,
+               l.invoice,
+               l.description,
+               pm.lnurl_description,
+               json_extract(pm.lnurl_pay_info, '$.ln_address'),
+               json_extract(pm.lnurl_pay_info, '$.comment'),
+               lrm.sender_comment,
+               t.tx_hash,
+               json_extract(t.invoice_details, '$.invoice'),
+               json_extract(t.invoice_details, '$.description'),
+               json_extract(s.invoice_details, '$.invoice'),
+               json_extract(s.invoice_details, '$.description'))
+      FROM payments p
+      LEFT JOIN payment_details_lightning l ON p.id = l.payment_id
+      LEFT JOIN payment_details_token t ON p.id = t.payment_id
+      LEFT JOIN payment_details_spark s ON p.id = s.payment_id
+      LEFT JOIN payment_details_deposit pd ON p.id = pd.payment_id
+      LEFT JOIN payment_metadata pm ON p.id = pm.payment_id
+      LEFT JOIN lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash";
+
+/// Tables holding the details of a payment, keyed by its id.
+const PAYMENT_DETAILS_TABLES: [&str; 4] = [
+    "payment_details_lightning",
+    "payment_details_token",
+    "payment_details_spark",
+    "payment_details_deposit",
+];
+
+/// Nullable columns holding JSON, as `(table, record id, column)`. Invalid
+/// values are cleared on repair, as their record stays usable without them.
+const OPTIONAL_JSON_COLUMNS: [(&str, &str, &str); 7] = [
+    ("payment_metadata", "payment_id", "lnurl_pay_info"),
+    ("payment_metadata", "payment_id", "lnurl_withdraw_info"),
+    ("payment_metadata", "payment_id", "conversion_info"),
+    ("payment_details_token", "payment_id", "invoice_details"),
+    ("payment_details_spark", "payment_id", "invoice_details"),
+    ("payment_details_spark", "payment_id", "htlc_details"),
+    ("unclaimed_deposits", "txid", "claim_error"),
+];
+
+/// Required columns holding JSON, as `(table, record id, column)`.
+const REQUIRED_JSON_COLUMNS: [(&str, &str, &str); 5] = [
+    ("payment_details_token", "payment_id", "metadata"),
+    ("cross_chain_swaps", "id", "data"),
+    ("sync_outgoing", "data_id", "updated_fields_json"),
+    ("sync_state", "data_id", "data"),
+    ("sync_incoming", "data_id", "data"),
+];
+
 #[allow(clippy::too_many_lines)]
 fn map_payment(row: &Row<'_>) -> Result<Payment, rusqlite::Error> {
     let withdraw_tx_id: Option<String> = row.get(7)?;
@@ -1697,6 +1956,8 @@ fn map_payment(row: &Row<'_>) -> Result<Payment, rusqlite::Error> {
         status,
         conversions: vec![],
     });
+    let amount = row.get::<_, U128SqlWrapper>(3)?.0;
+    let display_amount = DisplayAmount::new(amount, details.as_ref());
 
     Ok(Payment {
         id: row.get(0)?,
@@ -1706,12 +1967,16 @@ fn map_payment(row: &Row<'_>) -> Result<Payment, rusqlite::Error> {
         status: row.get::<_, String>(2)?.parse().map_err(|e: String| {
             rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, e.into())
         })?,
-        amount: row.get::<_, U128SqlWrapper>(3)?.0,
+        amount,
         fees: row.get::<_, U128SqlWrapper>(4)?.0,
         timestamp: row.get(5)?,
         details,
         method: row.get(6)?,
         conversion_details,
+        imported: row.get::<_, Option<bool>>(33)?.unwrap_or(false),
+        display_amount,
+        group_id: row.get(34)?,
+        dispute: row.get(35)?,
     })
 }
 
@@ -1847,6 +2112,18 @@ impl FromSql for LnurlWithdrawInfo {
     }
 }
 
+impl ToSql for PaymentDispute {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        to_sql_json(self)
+    }
+}
+
+impl FromSql for PaymentDispute {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        from_sql_json(value)
+    }
+}
+
 fn to_sql_json<T>(value: T) -> rusqlite::Result<ToSqlOutput<'static>>
 where
     T: serde::Serialize,
@@ -1965,6 +2242,132 @@ mod tests {
         crate::persist::tests::test_asset_filtering(Box::new(storage)).await;
     }
 
+    #[tokio::test]
+    async fn test_counterparty_filtering() {
+        let temp_dir = create_temp_dir("sqlite_storage_counterparty_filter");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_counterparty_filtering(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_imported_payment_flag() {
+        let temp_dir = create_temp_dir("sqlite_storage_imported_payment_flag");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_imported_payment_flag(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_display_amount() {
+        let temp_dir = create_temp_dir("sqlite_storage_payment_display_amount");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_payment_display_amount(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_group_id() {
+        let temp_dir = create_temp_dir("sqlite_storage_payment_group_id");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_payment_group_id(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_dispute() {
+        let temp_dir = create_temp_dir("sqlite_storage_payment_dispute");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_payment_dispute(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_list_payments_cursor() {
+        let temp_dir = create_temp_dir("sqlite_storage_list_payments_cursor");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_list_payments_cursor(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_search() {
+        let temp_dir = create_temp_dir("sqlite_storage_payment_search");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_payment_search(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity() {
+        let temp_dir = create_temp_dir("sqlite_storage_check_integrity");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        crate::persist::tests::test_check_integrity(Box::new(storage)).await;
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity_repairs_orphans_and_invalid_json() {
+        use crate::{Storage, StorageIntegrityIssueKind};
+
+        let temp_dir = create_temp_dir("sqlite_storage_check_integrity_repair");
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+        let conn = storage.get_connection().unwrap();
+        conn.execute(
+            "INSERT INTO payment_details_deposit (payment_id, tx_id, vout) VALUES ('gone', 'tx', 0)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO payment_metadata (payment_id, lnurl_pay_info) VALUES ('meta', '{broken')",
+            [],
+        )
+        .unwrap();
+
+        let issues = storage.check_integrity(false).await.unwrap();
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|issue| !issue.repaired));
+        assert!(issues.iter().any(|issue| {
+            issue.kind == StorageIntegrityIssueKind::OrphanedRecord
+                && issue.record_id.as_deref() == Some("gone")
+        }));
+        assert!(issues.iter().any(|issue| {
+            issue.kind == StorageIntegrityIssueKind::InvalidJson
+                && issue.record_id.as_deref() == Some("meta")
+        }));
+
+        let issues = storage.check_integrity(true).await.unwrap();
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|issue| issue.repaired));
+        assert!(storage.check_integrity(false).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cache_check_integrity_deletes_invalid_values() {
+        use std::sync::Arc;
+
+        use crate::Storage;
+        use crate::persist::ObjectCacheRepository;
+
+        let temp_dir = create_temp_dir("sqlite_storage_cache_check_integrity");
+        let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::new(&temp_dir).unwrap());
+        storage
+            .set_cached_item("account_info".to_string(), "{broken".to_string())
+            .await
+            .unwrap();
+        let cache = ObjectCacheRepository::new(storage.clone());
+
+        let issues = cache.check_integrity(false).await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].record_id.as_deref(), Some("account_info"));
+        assert!(!issues[0].repaired);
+
+        let issues = cache.check_integrity(true).await.unwrap();
+        assert!(issues[0].repaired);
+        assert!(cache.fetch_account_info().await.unwrap().is_none());
+        assert!(cache.check_integrity(false).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_timestamp_filtering() {
         let temp_dir = create_temp_dir("sqlite_storage_timestamp_filter");
@@ -2196,6 +2599,10 @@ mod tests {
                 conversion_info: None,
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: crate::DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         };
 
         storage.apply_payment_update(new_payment).await.unwrap();
@@ -2211,6 +2618,11 @@ mod tests {
             offset: None,
             limit: None,
             sort_ascending: Some(true),
+            counterparty_filter: None,
+            search: None,
+            after: None,
+            group_id_filter: None,
+            dispute_status_filter: None,
         };
 
         let payments = storage.list_payments(request).await.unwrap();
@@ -2255,6 +2667,11 @@ mod tests {
             offset: None,
             limit: None,
             sort_ascending: Some(true),
+            counterparty_filter: None,
+            search: None,
+            after: None,
+            group_id_filter: None,
+            dispute_status_filter: None,
         };
 
         let transfer_payments = storage.list_payments(transfer_filter).await.unwrap();
@@ -2266,6 +2683,56 @@ mod tests {
         assert_eq!(transfer_payments[0].id, "token-migration-test");
     }
 
+    #[tokio::test]
+    async fn test_migration_backfills_payment_search() {
+        use crate::{Storage, persist::StorageListPaymentsRequest};
+        use rusqlite::{Connection, params};
+        use rusqlite_migration::{M, Migrations};
+
+        let temp_dir = create_temp_dir("sqlite_migration_payment_search");
+        let db_path = temp_dir.join(super::DEFAULT_DB_FILENAME);
+
+        // Step 1: Create database at version 44 (before the payment_search migration)
+        {
+            let mut conn = Connection::open(&db_path).unwrap();
+            let migrations_before_search: Vec<_> = SqliteStorage::current_migrations()
+                .iter()
+                .take(44) // Migrations 0-43 (index 44 creates payment_search)
+                .map(|s| M::up(s))
+                .collect();
+            let migrations = Migrations::new(migrations_before_search);
+            migrations.to_latest(&mut conn).unwrap();
+            conn.execute(
+                "INSERT INTO payments (id, payment_type, status, amount, fees, timestamp, method)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    "search-backfill-test",
+                    "receive",
+                    "completed",
+                    "1000",
+                    "0",
+                    1_234_567_890_i64,
+                    "\"spark\""
+                ],
+            )
+            .unwrap();
+        }
+
+        // Step 2: Open with SqliteStorage (triggers migration to latest)
+        let storage = SqliteStorage::new(&temp_dir).unwrap();
+
+        // Step 3: The payment stored before the migration is searchable
+        let payments = storage
+            .list_payments(StorageListPaymentsRequest {
+                search: Some("backfill".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].id, "search-backfill-test");
+    }
+
     #[tokio::test]
     #[allow(clippy::too_many_lines)]
     async fn test_migration_htlc_details() {
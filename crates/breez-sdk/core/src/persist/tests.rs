@@ -3,10 +3,11 @@ use std::collections::HashMap;
 use chrono::Utc;
 
 use crate::{
-    DepositClaimError, LnurlWithdrawInfo, Payment, PaymentDetails, PaymentMetadata, PaymentMethod,
-    PaymentStatus, PaymentType, SparkHtlcDetails, SparkHtlcStatus, Storage, TokenMetadata,
-    TokenTransactionType, UpdateDepositPayload,
-    persist::{ObjectCacheRepository, StorageListPaymentsRequest},
+    CounterpartyFilter, DepositClaimError, DisplayAmount, DisplayAsset, DisputeStatus,
+    LnurlPayInfo, LnurlWithdrawInfo, Payment, PaymentDetails, PaymentDispute, PaymentMetadata,
+    PaymentMethod, PaymentStatus, PaymentType, SparkHtlcDetails, SparkHtlcStatus, Storage,
+    TokenMetadata, TokenTransactionType, UpdateDepositPayload,
+    persist::{ObjectCacheRepository, PaymentCursor, StorageListPaymentsRequest},
     sync_storage::{Record, RecordId, UnversionedRecordChange},
 };
 
@@ -358,6 +359,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 2: Spark HTLC payment
@@ -380,6 +385,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 3: Transfer token payment with invoice
@@ -412,6 +421,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 4: Mint token payment
@@ -431,6 +444,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 5: Burn token payment
@@ -450,6 +467,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 6: Lightning payment with full details
@@ -483,7 +504,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             lnurl_receive_metadata: None,
             conversion_info: None,
         }),
-        conversion_details: None,
+        conversion_details: None, imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 7: Lightning payment with full details
@@ -511,7 +535,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             lnurl_receive_metadata: None,
             conversion_info: None,
         }),
-        conversion_details: None,
+        conversion_details: None, imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 8: Lightning HODL payment with HTLC details
@@ -541,6 +568,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 9: Lightning payment with minimal details
@@ -562,7 +593,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             lnurl_receive_metadata: None,
             conversion_info: None,
         }),
-        conversion_details: None,
+        conversion_details: None, imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 9: Lightning payment with LNURL receive metadata
@@ -591,7 +625,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             lnurl_receive_metadata: Some(lnurl_receive_metadata.clone()),
             conversion_info: None,
         }),
-        conversion_details: None,
+        conversion_details: None, imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 10: Withdraw payment
@@ -607,6 +644,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             tx_id: "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef12".to_string(),
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 11: Deposit payment
@@ -623,6 +664,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             vout: 2,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 12: Payment with no details
@@ -636,6 +681,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
         method: PaymentMethod::Unknown,
         details: None,
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 13: Successful conversion payment
@@ -667,6 +716,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
                 .clone(),
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     let successful_received_conversion_payment_metadata = PaymentMetadata {
         parent_payment_id: Some("after_conversion_pmt124".to_string()),
@@ -688,6 +741,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     let after_conversion_payment = Payment {
         id: "after_conversion_pmt124".to_string(),
@@ -705,6 +762,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 14: Failed conversion payment with refund info
@@ -735,6 +796,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
                 .clone(),
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Test 15: Failed conversion payment with no refund info
@@ -765,6 +830,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
                 .clone(),
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let test_payments = vec![
@@ -1101,7 +1170,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             lnurl_receive_metadata: None,
             conversion_info: None,
         }),
-        conversion_details: None,
+        conversion_details: None, imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage
@@ -1175,6 +1247,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let lightning_zap_payment3 = Payment {
@@ -1196,6 +1272,10 @@ pub async fn test_storage(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage
@@ -1429,6 +1509,10 @@ pub async fn test_payment_type_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let receive_payment = Payment {
@@ -1450,6 +1534,10 @@ pub async fn test_payment_type_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage.apply_payment_update(send_payment).await.unwrap();
@@ -1511,6 +1599,10 @@ pub async fn test_payment_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let pending_payment = Payment {
@@ -1527,6 +1619,10 @@ pub async fn test_payment_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let failed_payment = Payment {
@@ -1543,6 +1639,10 @@ pub async fn test_payment_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage
@@ -1604,6 +1704,10 @@ pub async fn test_asset_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let lightning_payment = Payment {
@@ -1625,6 +1729,10 @@ pub async fn test_asset_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let token_payment = Payment {
@@ -1651,6 +1759,10 @@ pub async fn test_asset_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let withdraw_payment = Payment {
@@ -1665,6 +1777,10 @@ pub async fn test_asset_filtering(storage: Box<dyn Storage>) {
             tx_id: "withdraw_tx_1".to_string(),
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let deposit_payment = Payment {
@@ -1680,6 +1796,10 @@ pub async fn test_asset_filtering(storage: Box<dyn Storage>) {
             vout: 0,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage.apply_payment_update(spark_payment).await.unwrap();
@@ -1765,6 +1885,10 @@ pub async fn test_spark_htlc_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let htlc_shared = Payment {
@@ -1786,6 +1910,10 @@ pub async fn test_spark_htlc_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let htlc_returned = Payment {
@@ -1807,6 +1935,10 @@ pub async fn test_spark_htlc_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Create a payment that is not HTLC-related
@@ -1827,6 +1959,10 @@ pub async fn test_spark_htlc_status_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Insert all payments
@@ -1955,6 +2091,10 @@ pub async fn test_conversion_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let successful_conversion_metadata = PaymentMetadata {
@@ -1982,6 +2122,10 @@ pub async fn test_conversion_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment_without_refund_metadata = PaymentMetadata {
@@ -2009,6 +2153,10 @@ pub async fn test_conversion_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage
@@ -2178,6 +2326,10 @@ pub async fn test_conversion_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     storage
         .apply_payment_update(orchestra_payment)
@@ -2224,6 +2376,10 @@ pub async fn test_conversion_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     storage
         .apply_payment_update(orchestra_completed_payment)
@@ -2329,6 +2485,10 @@ pub async fn test_conversion_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Pending Boltz conversion → should match BoltzPending.
@@ -2414,6 +2574,10 @@ pub async fn test_token_transaction_type_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     let payment2 = Payment {
         id: "mint_2".to_string(),
@@ -2431,6 +2595,10 @@ pub async fn test_token_transaction_type_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     let payment3 = Payment {
         id: "burn_3".to_string(),
@@ -2448,6 +2616,10 @@ pub async fn test_token_transaction_type_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     storage.apply_payment_update(payment1).await.unwrap();
     storage.apply_payment_update(payment2).await.unwrap();
@@ -2516,6 +2688,10 @@ pub async fn test_timestamp_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment2 = Payment {
@@ -2532,6 +2708,10 @@ pub async fn test_timestamp_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment3 = Payment {
@@ -2548,6 +2728,10 @@ pub async fn test_timestamp_filtering(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage.apply_payment_update(payment1).await.unwrap();
@@ -2606,6 +2790,10 @@ pub async fn test_combined_filters(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment2 = Payment {
@@ -2627,6 +2815,10 @@ pub async fn test_combined_filters(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment3 = Payment {
@@ -2648,6 +2840,10 @@ pub async fn test_combined_filters(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage.apply_payment_update(payment1).await.unwrap();
@@ -2708,6 +2904,10 @@ pub async fn test_sort_order(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment2 = Payment {
@@ -2724,6 +2924,10 @@ pub async fn test_sort_order(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let payment3 = Payment {
@@ -2740,6 +2944,10 @@ pub async fn test_sort_order(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage.apply_payment_update(payment1).await.unwrap();
@@ -2783,6 +2991,101 @@ pub async fn test_sort_order(storage: Box<dyn Storage>) {
     assert_eq!(desc_explicit[2].id, "sort_1");
 }
 
+pub async fn test_counterparty_filtering(storage: Box<dyn Storage>) {
+    let lightning_payment = |id: &str, destination_pubkey: &str, timestamp: u64| Payment {
+        id: id.to_string(),
+        payment_type: PaymentType::Send,
+        status: PaymentStatus::Completed,
+        amount: 1_000,
+        fees: 10,
+        timestamp,
+        method: PaymentMethod::Lightning,
+        details: Some(PaymentDetails::Lightning {
+            invoice: format!("lnbc_{id}"),
+            destination_pubkey: destination_pubkey.to_string(),
+            htlc_details: test_lightning_htlc(&format!("hash_{id}")),
+            description: None,
+            lnurl_pay_info: None,
+            lnurl_withdraw_info: None,
+            lnurl_receive_metadata: None,
+            conversion_info: None,
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+
+    storage
+        .apply_payment_update(lightning_payment("ln_alice_1", "pubkey_alice", 1000))
+        .await
+        .unwrap();
+    storage
+        .apply_payment_update(lightning_payment("ln_alice_2", "pubkey_alice", 2000))
+        .await
+        .unwrap();
+    storage
+        .apply_payment_update(lightning_payment("ln_bob", "pubkey_bob", 3000))
+        .await
+        .unwrap();
+    storage
+        .insert_payment_metadata(
+            "ln_alice_2".to_string(),
+            PaymentMetadata {
+                lnurl_pay_info: Some(LnurlPayInfo {
+                    ln_address: Some("Alice@Example.com".to_string()),
+                    comment: None,
+                    domain: Some("example.com".to_string()),
+                    metadata: None,
+                    processed_success_action: None,
+                    raw_success_action: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    // Filter by lightning destination
+    let payments = storage
+        .list_payments(StorageListPaymentsRequest {
+            counterparty_filter: Some(CounterpartyFilter::LightningDestination {
+                destination_pubkey: "pubkey_alice".to_string(),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(payments.len(), 2);
+    assert!(payments.iter().all(|p| p.id.starts_with("ln_alice")));
+
+    // Filter by lightning address, matched case-insensitively
+    let payments = storage
+        .list_payments(StorageListPaymentsRequest {
+            counterparty_filter: Some(CounterpartyFilter::LightningAddress {
+                address: "alice@example.com".to_string(),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(payments.len(), 1);
+    assert_eq!(payments[0].id, "ln_alice_2");
+
+    // Unknown counterparty
+    let payments = storage
+        .list_payments(StorageListPaymentsRequest {
+            counterparty_filter: Some(CounterpartyFilter::LightningDestination {
+                destination_pubkey: "pubkey_carol".to_string(),
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(payments.is_empty());
+}
+
 pub async fn test_payment_metadata(storage: Box<dyn Storage>) {
     let cache = ObjectCacheRepository::new(storage.into());
 
@@ -2867,6 +3170,10 @@ pub async fn test_payment_details_update_persistence(storage: Box<dyn Storage>)
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Insert the payment into storage
@@ -2942,6 +3249,10 @@ pub async fn test_payment_terminal_status_is_not_replaced(storage: Box<dyn Stora
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     let should_emit = storage.apply_payment_update(payment.clone()).await.unwrap();
@@ -2996,6 +3307,10 @@ pub async fn test_payment_metadata_merge(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     storage.apply_payment_update(payment).await.unwrap();
 
@@ -3010,6 +3325,10 @@ pub async fn test_payment_metadata_merge(storage: Box<dyn Storage>) {
         method: PaymentMethod::Spark,
         details: None,
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
     storage.apply_payment_update(parent_payment).await.unwrap();
 
@@ -3111,6 +3430,10 @@ pub async fn test_lightning_htlc_details_and_status_filtering(storage: Box<dyn S
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Lightning payment with htlc_details PreimageShared (claimed)
@@ -3138,6 +3461,10 @@ pub async fn test_lightning_htlc_details_and_status_filtering(storage: Box<dyn S
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Regular Lightning payment
@@ -3165,6 +3492,10 @@ pub async fn test_lightning_htlc_details_and_status_filtering(storage: Box<dyn S
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // Non-Lightning payment (should never appear in Lightning filters)
@@ -3182,6 +3513,10 @@ pub async fn test_lightning_htlc_details_and_status_filtering(storage: Box<dyn S
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     storage
@@ -3529,6 +3864,10 @@ pub async fn test_conversion_status_persistence(storage: Box<dyn Storage>) {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     // --- Test 1: All ConversionStatus variants round-trip ---
@@ -3705,6 +4044,10 @@ fn boltz_payment(id: &str) -> Payment {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     }
 }
 
@@ -3836,3 +4179,531 @@ pub async fn test_update_boltz_status_to_completed(storage: Box<dyn Storage>) {
     assert_eq!(bridge_ref, Some("0xabc123".to_string()));
     assert!(fetched.conversion_details.is_none());
 }
+
+pub async fn test_imported_payment_flag(storage: Box<dyn Storage>) {
+    let payment = |id: &str, imported: bool| Payment {
+        id: id.to_string(),
+        payment_type: PaymentType::Receive,
+        status: PaymentStatus::Completed,
+        amount: 1_000,
+        fees: 0,
+        timestamp: 1_600_000_000,
+        method: PaymentMethod::Lightning,
+        details: Some(PaymentDetails::Lightning {
+            invoice: format!("lnbc_{id}"),
+            destination_pubkey: "pubkey".to_string(),
+            htlc_details: test_lightning_htlc(&format!("hash_{id}")),
+            description: None,
+            lnurl_pay_info: None,
+            lnurl_withdraw_info: None,
+            lnurl_receive_metadata: None,
+            conversion_info: None,
+        }),
+        conversion_details: None,
+        imported,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+
+    storage
+        .apply_payment_update(payment("imported:greenlight:abc", true))
+        .await
+        .unwrap();
+    storage
+        .apply_payment_update(payment("native", false))
+        .await
+        .unwrap();
+
+    let imported = storage
+        .get_payment_by_id("imported:greenlight:abc".to_string())
+        .await
+        .unwrap();
+    assert!(imported.imported);
+    assert_eq!(imported.timestamp, 1_600_000_000);
+    assert!(
+        !storage
+            .get_payment_by_id("native".to_string())
+            .await
+            .unwrap()
+            .imported
+    );
+
+    let listed = storage
+        .list_payments(StorageListPaymentsRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(listed.iter().filter(|p| p.imported).count(), 1);
+}
+
+pub async fn test_payment_display_amount(storage: Box<dyn Storage>) {
+    let token_payment = Payment {
+        id: "token".to_string(),
+        payment_type: PaymentType::Receive,
+        status: PaymentStatus::Completed,
+        amount: 2_500_000,
+        fees: 0,
+        timestamp: 1_600_000_000,
+        method: PaymentMethod::Token,
+        details: Some(PaymentDetails::Token {
+            metadata: TokenMetadata {
+                identifier: "token-id".to_string(),
+                issuer_public_key: format!("02{}", "a".repeat(64)),
+                name: "Test Token".to_string(),
+                ticker: "TST".to_string(),
+                decimals: 6,
+                max_supply: 1_000_000_000,
+                is_freezable: false,
+            },
+            tx_hash: "0xdisplay".to_string(),
+            tx_type: TokenTransactionType::Transfer,
+            invoice_details: None,
+            conversion_info: None,
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+    let sats_payment = Payment {
+        id: "sats".to_string(),
+        payment_type: PaymentType::Send,
+        status: PaymentStatus::Completed,
+        amount: 1_000,
+        fees: 5,
+        timestamp: 1_600_000_001,
+        method: PaymentMethod::Withdraw,
+        details: Some(PaymentDetails::Withdraw {
+            tx_id: "display_withdraw_tx".to_string(),
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+    storage.apply_payment_update(token_payment).await.unwrap();
+    storage.apply_payment_update(sats_payment).await.unwrap();
+
+    let token = storage
+        .get_payment_by_id("token".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        token.display_amount,
+        DisplayAmount {
+            value: 2_500_000,
+            asset: DisplayAsset::Token {
+                identifier: "token-id".to_string(),
+                ticker: "TST".to_string(),
+                decimals: 6,
+            },
+        }
+    );
+
+    let listed = storage
+        .list_payments(StorageListPaymentsRequest::default())
+        .await
+        .unwrap();
+    let sats = listed.iter().find(|p| p.id == "sats").unwrap();
+    assert_eq!(
+        sats.display_amount,
+        DisplayAmount {
+            value: 1_000,
+            asset: DisplayAsset::Bitcoin,
+        }
+    );
+}
+
+pub async fn test_list_payments_cursor(storage: Box<dyn Storage>) {
+    let payment = |id: &str, timestamp: u64| Payment {
+        id: id.to_string(),
+        payment_type: PaymentType::Receive,
+        status: PaymentStatus::Completed,
+        amount: 1_000,
+        fees: 0,
+        timestamp,
+        method: PaymentMethod::Spark,
+        details: Some(PaymentDetails::Spark {
+            invoice_details: None,
+            htlc_details: None,
+            conversion_info: None,
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+    // Payments sharing a timestamp are ordered by id
+    for (id, timestamp) in [("a", 100), ("b", 200), ("c", 200), ("d", 200), ("e", 300)] {
+        storage
+            .apply_payment_update(payment(id, timestamp))
+            .await
+            .unwrap();
+    }
+
+    let list_page = |after: Option<PaymentCursor>, sort_ascending: bool| {
+        storage.list_payments(StorageListPaymentsRequest {
+            limit: Some(2),
+            sort_ascending: Some(sort_ascending),
+            after,
+            ..Default::default()
+        })
+    };
+    let ids = |payments: &[Payment]| payments.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+
+    let first = list_page(None, false).await.unwrap();
+    assert_eq!(ids(&first), vec!["e", "d"]);
+
+    // A payment arriving mid-pagination doesn't shift the next page
+    storage
+        .apply_payment_update(payment("f", 400))
+        .await
+        .unwrap();
+    let second = list_page(first.last().map(PaymentCursor::from_payment), false)
+        .await
+        .unwrap();
+    assert_eq!(ids(&second), vec!["c", "b"]);
+    let third = list_page(second.last().map(PaymentCursor::from_payment), false)
+        .await
+        .unwrap();
+    assert_eq!(ids(&third), vec!["a"]);
+
+    let ascending = list_page(
+        Some(PaymentCursor {
+            timestamp: 200,
+            id: "b".to_string(),
+        }),
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(ids(&ascending), vec!["c", "d"]);
+}
+
+pub async fn test_payment_search(storage: Box<dyn Storage>) {
+    let lightning_payment = |id: &str, description: Option<&str>| Payment {
+        id: id.to_string(),
+        payment_type: PaymentType::Send,
+        status: PaymentStatus::Completed,
+        amount: 1_000,
+        fees: 10,
+        timestamp: 1000,
+        method: PaymentMethod::Lightning,
+        details: Some(PaymentDetails::Lightning {
+            invoice: format!("lnbc_{id}"),
+            destination_pubkey: "pubkey".to_string(),
+            htlc_details: test_lightning_htlc(&format!("hash_{id}")),
+            description: description.map(ToString::to_string),
+            lnurl_pay_info: None,
+            lnurl_withdraw_info: None,
+            lnurl_receive_metadata: None,
+            conversion_info: None,
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+
+    storage
+        .apply_payment_update(lightning_payment("coffee", Some("Coffee at the corner")))
+        .await
+        .unwrap();
+    storage
+        .apply_payment_update(lightning_payment("lnurl", None))
+        .await
+        .unwrap();
+    storage
+        .insert_payment_metadata(
+            "lnurl".to_string(),
+            PaymentMetadata {
+                lnurl_pay_info: Some(LnurlPayInfo {
+                    ln_address: Some("alice@example.com".to_string()),
+                    comment: Some("Thanks for lunch".to_string()),
+                    domain: Some("example.com".to_string()),
+                    metadata: None,
+                    processed_success_action: None,
+                    raw_success_action: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    storage
+        .apply_payment_update(Payment {
+            id: "withdraw".to_string(),
+            payment_type: PaymentType::Send,
+            status: PaymentStatus::Completed,
+            amount: 50_000,
+            fees: 500,
+            timestamp: 2000,
+            method: PaymentMethod::Withdraw,
+            details: Some(PaymentDetails::Withdraw {
+                tx_id: "f00dcafe".to_string(),
+            }),
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        })
+        .await
+        .unwrap();
+
+    let search = |search: &str| {
+        storage.list_payments(StorageListPaymentsRequest {
+            search: Some(search.to_string()),
+            ..Default::default()
+        })
+    };
+    let ids = |payments: Vec<Payment>| payments.into_iter().map(|p| p.id).collect::<Vec<_>>();
+
+    assert_eq!(ids(search("coffee").await.unwrap()), vec!["coffee"]);
+    assert_eq!(ids(search("ALICE@EXAMPLE").await.unwrap()), vec!["lnurl"]);
+    assert_eq!(ids(search("lunch").await.unwrap()), vec!["lnurl"]);
+    assert_eq!(ids(search("dcaf").await.unwrap()), vec!["withdraw"]);
+    assert_eq!(ids(search("lnbc_coff").await.unwrap()), vec!["coffee"]);
+    // Short terms and wildcards are matched literally
+    assert_eq!(ids(search("0d").await.unwrap()), vec!["withdraw"]);
+    assert!(search("%").await.unwrap().is_empty());
+    assert!(search("tea").await.unwrap().is_empty());
+}
+
+pub async fn test_payment_group_id(storage: Box<dyn Storage>) {
+    let payment = |id: &str, timestamp: u64| Payment {
+        id: id.to_string(),
+        payment_type: PaymentType::Send,
+        status: PaymentStatus::Completed,
+        amount: 1_000,
+        fees: 2,
+        timestamp,
+        method: PaymentMethod::Withdraw,
+        details: Some(PaymentDetails::Withdraw {
+            tx_id: format!("tx_{id}"),
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+    for (id, timestamp) in [("leg1", 1_000), ("leg2", 1_001), ("other", 1_002)] {
+        storage
+            .apply_payment_update(payment(id, timestamp))
+            .await
+            .unwrap();
+    }
+    for id in ["leg1", "leg2"] {
+        storage
+            .insert_payment_metadata(
+                id.to_string(),
+                PaymentMetadata {
+                    group_id: Some("group".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+    }
+    // A later metadata update keeps the group
+    storage
+        .insert_payment_metadata(
+            "leg1".to_string(),
+            PaymentMetadata {
+                lnurl_description: Some("description".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let leg = storage.get_payment_by_id("leg1".to_string()).await.unwrap();
+    assert_eq!(leg.group_id.as_deref(), Some("group"));
+    let other = storage
+        .get_payment_by_id("other".to_string())
+        .await
+        .unwrap();
+    assert!(other.group_id.is_none());
+
+    let grouped = storage
+        .list_payments(StorageListPaymentsRequest {
+            group_id_filter: Some(vec!["group".to_string()]),
+            sort_ascending: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let ids: Vec<_> = grouped.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(ids, vec!["leg1", "leg2"]);
+    assert!(
+        grouped
+            .iter()
+            .all(|p| p.group_id.as_deref() == Some("group"))
+    );
+
+    let unknown = storage
+        .list_payments(StorageListPaymentsRequest {
+            group_id_filter: Some(vec!["unknown".to_string()]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(unknown.is_empty());
+}
+
+pub async fn test_payment_dispute(storage: Box<dyn Storage>) {
+    let payment = |id: &str, timestamp: u64| Payment {
+        id: id.to_string(),
+        payment_type: PaymentType::Receive,
+        status: PaymentStatus::Completed,
+        amount: 5_000,
+        fees: 0,
+        timestamp,
+        method: PaymentMethod::Withdraw,
+        details: Some(PaymentDetails::Withdraw {
+            tx_id: format!("tx_{id}"),
+        }),
+        conversion_details: None,
+        imported: false,
+        display_amount: DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
+    };
+    for (id, timestamp) in [("open", 1_000), ("closed", 1_001), ("clean", 1_002)] {
+        storage
+            .apply_payment_update(payment(id, timestamp))
+            .await
+            .unwrap();
+    }
+    let disputed = PaymentDispute {
+        status: DisputeStatus::Disputed,
+        note: Some("customer claims double charge".to_string()),
+        disputed_at: 2_000,
+        resolved_at: None,
+        updated_at: 2_000,
+    };
+    for id in ["open", "closed"] {
+        storage
+            .insert_payment_metadata(
+                id.to_string(),
+                PaymentMetadata {
+                    dispute: Some(disputed.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+    }
+    let resolved = PaymentDispute {
+        status: DisputeStatus::Resolved,
+        resolved_at: Some(3_000),
+        updated_at: 3_000,
+        ..disputed.clone()
+    };
+    storage
+        .insert_payment_metadata(
+            "closed".to_string(),
+            PaymentMetadata {
+                dispute: Some(resolved.clone()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    // A later metadata update keeps the dispute
+    storage
+        .insert_payment_metadata(
+            "open".to_string(),
+            PaymentMetadata {
+                lnurl_description: Some("description".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let open = storage.get_payment_by_id("open".to_string()).await.unwrap();
+    assert_eq!(open.dispute, Some(disputed));
+    let closed = storage
+        .get_payment_by_id("closed".to_string())
+        .await
+        .unwrap();
+    assert_eq!(closed.dispute, Some(resolved));
+    let clean = storage
+        .get_payment_by_id("clean".to_string())
+        .await
+        .unwrap();
+    assert!(clean.dispute.is_none());
+
+    let list = |filter: Vec<DisputeStatus>| {
+        storage.list_payments(StorageListPaymentsRequest {
+            dispute_status_filter: Some(filter),
+            sort_ascending: Some(true),
+            ..Default::default()
+        })
+    };
+    let ids = |payments: Vec<Payment>| payments.into_iter().map(|p| p.id).collect::<Vec<_>>();
+    assert_eq!(
+        ids(list(vec![DisputeStatus::Disputed]).await.unwrap()),
+        vec!["open"]
+    );
+    assert_eq!(
+        ids(list(vec![DisputeStatus::Resolved]).await.unwrap()),
+        vec!["closed"]
+    );
+    assert_eq!(
+        ids(list(vec![DisputeStatus::Disputed, DisputeStatus::Resolved])
+            .await
+            .unwrap()),
+        vec!["open", "closed"]
+    );
+}
+
+pub async fn test_check_integrity(storage: Box<dyn Storage>) {
+    storage
+        .apply_payment_update(Payment {
+            id: "payment".to_string(),
+            payment_type: PaymentType::Send,
+            status: PaymentStatus::Completed,
+            amount: 1_000,
+            fees: 10,
+            timestamp: 1000,
+            method: PaymentMethod::Lightning,
+            details: Some(PaymentDetails::Lightning {
+                invoice: "lnbc_payment".to_string(),
+                destination_pubkey: "pubkey".to_string(),
+                htlc_details: test_lightning_htlc("hash_payment"),
+                description: Some("coffee".to_string()),
+                lnurl_pay_info: None,
+                lnurl_withdraw_info: None,
+                lnurl_receive_metadata: None,
+                conversion_info: None,
+            }),
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        })
+        .await
+        .unwrap();
+
+    assert!(storage.check_integrity(false).await.unwrap().is_empty());
+    assert!(storage.check_integrity(true).await.unwrap().is_empty());
+
+    // Repairing a healthy storage keeps its payments searchable
+    let found = storage
+        .list_payments(StorageListPaymentsRequest {
+            search: Some("coffee".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, "payment");
+}
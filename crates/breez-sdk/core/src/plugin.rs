@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::{
+    BreezSdk, EventListener, SdkEvent,
+    error::SdkError,
+    persist::{Storage, StorageError},
+};
+
+const PLUGIN_STORAGE_KEY_PREFIX: &str = "plugin_";
+
+/// A plugin extends the SDK with custom behaviour driven by the SDK lifecycle.
+///
+/// Plugins are registered on the [`SdkBuilder`](crate::SdkBuilder) and are started once the
+/// SDK is built. Each plugin receives every [`SdkEvent`] emitted to external listeners and is
+/// notified when the SDK is disconnected.
+///
+/// A plugin that keeps the [`BreezSdk`] handed to it in `on_start` must release it in
+/// `on_shutdown`, otherwise the SDK and the plugin keep each other alive.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait Plugin: Send + Sync {
+    /// Unique identifier of the plugin. Also used to namespace the plugin storage.
+    fn id(&self) -> String;
+
+    /// Called once after the SDK has been started
+    async fn on_start(&self, sdk: Arc<BreezSdk>, storage: Arc<PluginStorage>);
+
+    /// Called for every event emitted by the SDK
+    async fn on_event(&self, event: SdkEvent);
+
+    /// Called when the SDK is disconnected
+    async fn on_shutdown(&self);
+}
+
+/// Key-value storage scoped to a single [`Plugin`].
+///
+/// Keys are namespaced by the plugin id, so plugins can't read or overwrite each other's
+/// items nor the SDK's own cached items.
+#[cfg_attr(feature = "uniffi", derive(uniffi::Object))]
+pub struct PluginStorage {
+    plugin_id: String,
+    storage: Arc<dyn Storage>,
+}
+
+impl PluginStorage {
+    pub(crate) fn new(plugin_id: String, storage: Arc<dyn Storage>) -> Self {
+        Self { plugin_id, storage }
+    }
+
+    /// The plugin id is length-prefixed, so no two plugin id and key pairs
+    /// map to the same storage key.
+    fn scoped_key(&self, key: &str) -> String {
+        format!(
+            "{PLUGIN_STORAGE_KEY_PREFIX}{}_{}_{key}",
+            self.plugin_id.len(),
+            self.plugin_id
+        )
+    }
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl PluginStorage {
+    pub async fn get_item(&self, key: String) -> Result<Option<String>, StorageError> {
+        self.storage.get_cached_item(self.scoped_key(&key)).await
+    }
+
+    pub async fn set_item(&self, key: String, value: String) -> Result<(), StorageError> {
+        self.storage
+            .set_cached_item(self.scoped_key(&key), value)
+            .await
+    }
+
+    pub async fn delete_item(&self, key: String) -> Result<(), StorageError> {
+        self.storage.delete_cached_item(self.scoped_key(&key)).await
+    }
+}
+
+/// Forwards SDK events to a plugin
+struct PluginEventListener {
+    plugin: Arc<dyn Plugin>,
+}
+
+#[macros::async_trait]
+impl EventListener for PluginEventListener {
+    async fn on_event(&self, event: SdkEvent) {
+        self.plugin.on_event(event).await;
+    }
+}
+
+/// Drives the lifecycle of the plugins registered on the builder.
+#[derive(Clone, Default)]
+pub(crate) struct PluginManager {
+    plugins: Arc<Vec<Arc<dyn Plugin>>>,
+}
+
+impl PluginManager {
+    pub(crate) fn new(plugins: Vec<Arc<dyn Plugin>>) -> Result<Self, SdkError> {
+        let mut ids = HashSet::new();
+        for plugin in &plugins {
+            let id = plugin.id();
+            if id.is_empty() {
+                return Err(SdkError::InvalidInput(
+                    "Plugin id must not be empty".to_string(),
+                ));
+            }
+            if !ids.insert(id.clone()) {
+                return Err(SdkError::InvalidInput(format!("Duplicate plugin id: {id}")));
+            }
+        }
+        Ok(Self {
+            plugins: Arc::new(plugins),
+        })
+    }
+
+    /// Starts every plugin and subscribes it to SDK events.
+    pub(crate) async fn start(&self, sdk: &BreezSdk) {
+        for plugin in self.plugins.iter() {
+            let id = plugin.id();
+            debug!("Starting plugin {id}");
+            let storage = Arc::new(PluginStorage::new(id, Arc::clone(&sdk.storage)));
+            plugin.on_start(Arc::new(sdk.clone()), storage).await;
+            sdk.event_emitter
+                .add_external_listener(Box::new(PluginEventListener {
+                    plugin: Arc::clone(plugin),
+                }))
+                .await;
+        }
+    }
+
+    /// Notifies every plugin that the SDK is shutting down.
+    pub(crate) async fn shutdown(&self) {
+        for plugin in self.plugins.iter() {
+            debug!("Shutting down plugin {}", plugin.id());
+            plugin.on_shutdown().await;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::persist::sqlite::SqliteStorage;
+
+    struct NoopPlugin(&'static str);
+
+    #[macros::async_trait]
+    impl Plugin for NoopPlugin {
+        fn id(&self) -> String {
+            self.0.to_string()
+        }
+
+        async fn on_start(&self, _sdk: Arc<BreezSdk>, _storage: Arc<PluginStorage>) {}
+
+        async fn on_event(&self, _event: SdkEvent) {}
+
+        async fn on_shutdown(&self) {}
+    }
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("breez-test-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn plugin_storage_is_namespaced() {
+        let dir = create_temp_dir("plugin_storage");
+        let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::new(&dir).unwrap());
+        let first = PluginStorage::new("first".to_string(), Arc::clone(&storage));
+        let second = PluginStorage::new("second".to_string(), Arc::clone(&storage));
+
+        first
+            .set_item("key".to_string(), "value".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            first.get_item("key".to_string()).await.unwrap().as_deref(),
+            Some("value")
+        );
+        assert_eq!(second.get_item("key".to_string()).await.unwrap(), None);
+        assert_eq!(
+            storage.get_cached_item("key".to_string()).await.unwrap(),
+            None
+        );
+
+        first.delete_item("key".to_string()).await.unwrap();
+        assert_eq!(first.get_item("key".to_string()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn plugin_storage_keys_do_not_collide() {
+        let dir = create_temp_dir("plugin_storage_collision");
+        let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::new(&dir).unwrap());
+        let first = PluginStorage::new("a_b".to_string(), Arc::clone(&storage));
+        let second = PluginStorage::new("a".to_string(), Arc::clone(&storage));
+        assert_ne!(first.scoped_key("c"), second.scoped_key("b_c"));
+
+        first
+            .set_item("c".to_string(), "first".to_string())
+            .await
+            .unwrap();
+        second
+            .set_item("b_c".to_string(), "second".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            first.get_item("c".to_string()).await.unwrap().as_deref(),
+            Some("first")
+        );
+        assert_eq!(
+            second.get_item("b_c".to_string()).await.unwrap().as_deref(),
+            Some("second")
+        );
+    }
+
+    #[test]
+    fn plugin_ids_must_be_unique() {
+        let plugins: Vec<Arc<dyn Plugin>> =
+            vec![Arc::new(NoopPlugin("a")), Arc::new(NoopPlugin("a"))];
+        assert!(matches!(
+            PluginManager::new(plugins),
+            Err(SdkError::InvalidInput(_))
+        ));
+
+        let plugins: Vec<Arc<dyn Plugin>> =
+            vec![Arc::new(NoopPlugin("a")), Arc::new(NoopPlugin("b"))];
+        assert!(PluginManager::new(plugins).is_ok());
+    }
+}
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, sync::Arc};
+
+use breez_sdk_common::sync::RecordConflict;
+use serde_json::Value;
+
+use crate::{ConflictResolutionStrategy, ConflictResolver, SyncConflict};
+
+/// Returns the resolver for the sync processor, if any. A custom resolver
+/// takes precedence over the strategy. Field merge is the processor default.
+pub(crate) fn sync_conflict_resolver(
+    strategy: ConflictResolutionStrategy,
+    custom: Option<Arc<dyn ConflictResolver>>,
+) -> Option<Arc<dyn breez_sdk_common::sync::ConflictResolver>> {
+    if let Some(resolver) = custom {
+        return Some(Arc::new(CustomConflictResolver { resolver }));
+    }
+    match strategy {
+        ConflictResolutionStrategy::FieldMerge => None,
+        ConflictResolutionStrategy::LastWriterWins => Some(Arc::new(LastWriterWinsResolver)),
+    }
+}
+
+struct LastWriterWinsResolver;
+
+#[macros::async_trait]
+impl breez_sdk_common::sync::ConflictResolver for LastWriterWinsResolver {
+    async fn resolve(&self, conflict: RecordConflict) -> anyhow::Result<HashMap<String, Value>> {
+        let mut resolved = conflict.base;
+        resolved.extend(conflict.local);
+        Ok(resolved)
+    }
+}
+
+struct CustomConflictResolver {
+    resolver: Arc<dyn ConflictResolver>,
+}
+
+#[macros::async_trait]
+impl breez_sdk_common::sync::ConflictResolver for CustomConflictResolver {
+    async fn resolve(&self, conflict: RecordConflict) -> anyhow::Result<HashMap<String, Value>> {
+        let encode = |fields: HashMap<String, Value>| {
+            fields
+                .into_iter()
+                .map(|(k, v)| Ok((k, serde_json::to_string(&v)?)))
+                .collect::<Result<HashMap<String, String>, serde_json::Error>>()
+        };
+        let resolved = self
+            .resolver
+            .resolve(SyncConflict {
+                record_type: conflict.id.r#type,
+                record_id: conflict.id.data_id,
+                base_fields: encode(conflict.base)?,
+                local_fields: encode(conflict.local)?,
+                remote_fields: encode(conflict.remote)?,
+            })
+            .await?;
+        Ok(resolved
+            .into_iter()
+            .map(|(k, v)| Ok((k, serde_json::from_str(&v)?)))
+            .collect::<Result<HashMap<String, Value>, serde_json::Error>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use breez_sdk_common::sync::RecordId;
+    use macros::{async_test_all, test_all};
+    use serde_json::json;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[async_test_all]
+    async fn test_last_writer_wins_keeps_local_record() {
+        let resolver =
+            sync_conflict_resolver(ConflictResolutionStrategy::LastWriterWins, None).unwrap();
+        let resolved = resolver
+            .resolve(RecordConflict {
+                id: RecordId::new("Contact", "c1"),
+                base: HashMap::from([
+                    ("name".to_string(), json!("Alice")),
+                    ("note".to_string(), json!("old")),
+                ]),
+                local: HashMap::from([("name".to_string(), json!("Bob"))]),
+                remote: HashMap::from([
+                    ("name".to_string(), json!("Carol")),
+                    ("note".to_string(), json!("new")),
+                ]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(resolved.get("name"), Some(&json!("Bob")));
+        assert_eq!(resolved.get("note"), Some(&json!("old")));
+    }
+
+    #[test_all]
+    fn test_field_merge_uses_processor_default() {
+        assert!(sync_conflict_resolver(ConflictResolutionStrategy::FieldMerge, None).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use breez_sdk_common::sync::{BreezSyncerClient, SigningClient, SyncProcessor, SyncService};
+use platform_utils::ByteCounter;
 use tracing::debug;
 use uuid::Uuid;
 
@@ -22,6 +23,8 @@ pub struct RealTimeSyncParams {
     pub shutdown_receiver: tokio::sync::watch::Receiver<()>,
     pub event_emitter: Arc<EventEmitter>,
     pub lnurl_server_client: Option<Arc<dyn LnurlServerClient>>,
+    pub conflict_resolver: Option<Arc<dyn breez_sdk_common::sync::ConflictResolver>>,
+    pub byte_counter: Arc<ByteCounter>,
 }
 
 pub async fn init_and_start_real_time_sync(
@@ -52,7 +55,8 @@ pub async fn init_and_start_real_time_sync(
             params.api_key.as_deref(),
             &params.user_agent,
         )
-        .map_err(|e| SdkError::Generic(e.to_string()))?,
+        .map_err(|e| SdkError::Generic(e.to_string()))?
+        .with_byte_counter(params.byte_counter),
     );
 
     let signing_client = SigningClient::new(
@@ -61,12 +65,16 @@ pub async fn init_and_start_real_time_sync(
         Uuid::now_v7().to_string(),
     );
 
-    let sync_processor = Arc::new(SyncProcessor::new(
+    let mut sync_processor = SyncProcessor::new(
         signing_client.clone(),
         sync_service.get_sync_trigger(),
         record_handler,
         Arc::clone(&sync_storage),
-    ));
+    );
+    if let Some(conflict_resolver) = params.conflict_resolver {
+        sync_processor = sync_processor.with_conflict_resolver(conflict_resolver);
+    }
+    let sync_processor = Arc::new(sync_processor);
 
     sync_processor
         .start(params.shutdown_receiver)
@@ -1,4 +1,6 @@
+mod conflict;
 mod init;
 mod storage;
 
+pub(crate) use conflict::sync_conflict_resolver;
 pub use {init::*, storage::*};
@@ -19,8 +19,8 @@ use crate::{
     events::{InternalSyncedEvent, SdkEvent},
     lnurl::LnurlServerClient,
     persist::{
-        LIGHTNING_ADDRESS_KEY, ObjectCacheRepository, StorageListPaymentsRequest,
-        StoredCrossChainSwap, parse_cached_lightning_address,
+        LIGHTNING_ADDRESS_KEY, ObjectCacheRepository, StorageIntegrityIssue,
+        StorageListPaymentsRequest, StoredCrossChainSwap, parse_cached_lightning_address,
     },
     sync_storage::{IncomingChange, OutgoingChange, Record, UnversionedRecordChange},
 };
@@ -743,6 +743,13 @@ impl Storage for SyncedStorage {
     async fn update_record_from_incoming(&self, record: Record) -> Result<(), StorageError> {
         self.inner.update_record_from_incoming(record).await
     }
+
+    async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<StorageIntegrityIssue>, StorageError> {
+        self.inner.check_integrity(repair).await
+    }
 }
 
 #[cfg(all(test, feature = "sqlite"))]
@@ -833,6 +840,10 @@ mod tests {
                 conversion_info: None,
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: crate::DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
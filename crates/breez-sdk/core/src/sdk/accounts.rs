@@ -0,0 +1,180 @@
+use std::{future::Future, sync::Arc};
+
+use spark_wallet::{
+    DefaultSigner, ListTokenTransactionsRequest, ListTransfersRequest, PagingFilter,
+    SparkSignerAdapter, SparkWallet,
+};
+use tracing::{debug, info};
+
+use crate::{
+    AccountActivity, DiscoverAccountsRequest, DiscoverAccountsResponse, error::SdkError,
+    models::Config, sdk_builder::finalize_spark_wallet_config,
+};
+
+/// The default number of consecutive accounts without activity after which
+/// the discovery stops
+const DEFAULT_GAP_LIMIT: u32 = 5;
+
+/// Finds the accounts derived from a seed that have Spark activity, to know
+/// which account numbers to connect with `SdkBuilder::with_account_number`.
+///
+/// Account numbers are checked from 0 upwards, until `gap_limit` consecutive
+/// accounts have neither transfers nor token transactions. Each account keeps
+/// its own storage, so the same `storage_dir` can be used to connect to all of
+/// them.
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+pub async fn discover_accounts(
+    request: DiscoverAccountsRequest,
+) -> Result<DiscoverAccountsResponse, SdkError> {
+    let gap_limit = request.gap_limit.unwrap_or(DEFAULT_GAP_LIMIT);
+    if gap_limit == 0 {
+        return Err(SdkError::InvalidInput(
+            "Gap limit must be greater than 0".to_string(),
+        ));
+    }
+    let seed = request.seed.to_bytes()?;
+    let config = &request.config;
+    let accounts = scan_accounts(gap_limit, |account_number| {
+        check_account(config, &seed, account_number)
+    })
+    .await?;
+    info!("Discovered {} accounts with activity", accounts.len());
+    Ok(DiscoverAccountsResponse { accounts })
+}
+
+/// Connects to the account `account_number` of `seed` and returns it if it
+/// has any transfer or token transaction.
+async fn check_account(
+    config: &Config,
+    seed: &[u8],
+    account_number: u32,
+) -> Result<Option<AccountActivity>, SdkError> {
+    let master =
+        spark_wallet::account_master_key(seed, config.network.into(), Some(account_number))
+            .map_err(|e| SdkError::Generic(e.to_string()))?;
+    let signer = Arc::new(SparkSignerAdapter::new(Arc::new(
+        DefaultSigner::from_master(master),
+    )));
+    let spark_wallet_config =
+        finalize_spark_wallet_config(config, &crate::default_user_agent(), false)?;
+    let wallet = SparkWallet::connect(spark_wallet_config, signer).await?;
+    let identity_public_key = wallet.get_identity_public_key().to_string();
+
+    let paging = Some(PagingFilter::new(None, Some(1), None));
+    let has_transfers = !wallet
+        .list_transfers(ListTransfersRequest {
+            paging: paging.clone(),
+            ..Default::default()
+        })
+        .await?
+        .items
+        .is_empty();
+    let active = has_transfers
+        || !wallet
+            .list_token_transactions(ListTokenTransactionsRequest {
+                paging,
+                ..Default::default()
+            })
+            .await?
+            .items
+            .is_empty();
+    debug!("Account {account_number} ({identity_public_key}) active: {active}");
+    Ok(active.then_some(AccountActivity {
+        account_number,
+        identity_public_key,
+    }))
+}
+
+/// Checks the accounts from 0 upwards with `check`, until `gap_limit`
+/// consecutive accounts have no activity. Returns the accounts with activity.
+async fn scan_accounts<F, Fut>(
+    gap_limit: u32,
+    mut check: F,
+) -> Result<Vec<AccountActivity>, SdkError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<Option<AccountActivity>, SdkError>>,
+{
+    let mut accounts = Vec::new();
+    let mut gap = 0;
+    let mut account_number: u32 = 0;
+    while gap < gap_limit {
+        match check(account_number).await? {
+            Some(account) => {
+                accounts.push(account);
+                gap = 0;
+            }
+            None => gap = gap.saturating_add(1),
+        }
+        let Some(next) = account_number.checked_add(1) else {
+            break;
+        };
+        account_number = next;
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::async_test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn account(account_number: u32) -> AccountActivity {
+        AccountActivity {
+            account_number,
+            identity_public_key: format!("key{account_number}"),
+        }
+    }
+
+    async fn scan(gap_limit: u32, active: &[u32]) -> (Vec<AccountActivity>, Vec<u32>) {
+        let mut checked = Vec::new();
+        let accounts = scan_accounts(gap_limit, |account_number| {
+            checked.push(account_number);
+            let result = active
+                .contains(&account_number)
+                .then(|| account(account_number));
+            async move { Ok(result) }
+        })
+        .await
+        .unwrap();
+        (accounts, checked)
+    }
+
+    #[async_test_all]
+    async fn test_scan_accounts_without_activity() {
+        let (accounts, checked) = scan(3, &[]).await;
+        assert!(accounts.is_empty());
+        assert_eq!(checked, vec![0, 1, 2]);
+    }
+
+    #[async_test_all]
+    async fn test_scan_accounts_resets_gap_on_activity() {
+        let (accounts, checked) = scan(2, &[1, 3]).await;
+        assert_eq!(accounts, vec![account(1), account(3)]);
+        assert_eq!(checked, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[async_test_all]
+    async fn test_scan_accounts_stops_at_gap() {
+        let (accounts, checked) = scan(2, &[0, 4]).await;
+        assert_eq!(accounts, vec![account(0)]);
+        assert_eq!(checked, vec![0, 1, 2]);
+    }
+
+    #[async_test_all]
+    async fn test_scan_accounts_propagates_errors() {
+        let result = scan_accounts(5, |account_number| async move {
+            if account_number == 1 {
+                Err(SdkError::NetworkError("unreachable".to_string()))
+            } else {
+                Ok(Some(account(account_number)))
+            }
+        })
+        .await;
+        assert!(matches!(result, Err(SdkError::NetworkError(_))));
+    }
+}
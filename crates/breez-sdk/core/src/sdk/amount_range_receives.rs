@@ -0,0 +1,270 @@
+use bitcoin::secp256k1::rand::{Rng, thread_rng};
+use breez_sdk_common::input::parse_invoice;
+use platform_utils::time::Duration;
+use platform_utils::tokio;
+use spark_wallet::{Preimage, WalletTransfer};
+use tokio::{select, time::sleep};
+use tracing::{Instrument, debug, info, warn};
+
+use crate::{
+    Payment,
+    error::SdkError,
+    models::ReceivePaymentResponse,
+    persist::{CachedAmountRangeReceive, ObjectCacheRepository},
+    utils::{invoice_description::bolt11_invoice_description, payments::record_payment_update},
+};
+
+use super::BreezSdk;
+
+const AMOUNT_RANGE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+/// Receives are tracked this long after their invoice expires, for HTLCs that
+/// arrived just before it.
+const AMOUNT_RANGE_GRACE_SECS: u64 = 24 * 60 * 60;
+
+/// Validates the amount limits of a BOLT11 invoice receive.
+pub(super) fn validate_amount_range(
+    amount_sats: Option<u64>,
+    payment_hash: Option<&String>,
+    min_amount_sats: Option<u64>,
+    max_amount_sats: Option<u64>,
+) -> Result<(), SdkError> {
+    if amount_sats.is_some() {
+        return Err(SdkError::InvalidInput(
+            "Amount limits are only supported for amountless invoices".to_string(),
+        ));
+    }
+    if payment_hash.is_some() {
+        return Err(SdkError::InvalidInput(
+            "Amount limits can't be combined with a payment hash".to_string(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (min_amount_sats, max_amount_sats)
+        && min > max
+    {
+        return Err(SdkError::InvalidInput(
+            "Minimum amount can't be greater than the maximum amount".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl BreezSdk {
+    /// Creates an amountless HODL invoice whose payment is claimed only if its
+    /// amount is within the limits.
+    pub(super) async fn receive_amount_range_invoice(
+        &self,
+        description: String,
+        expiry_secs: Option<u32>,
+        min_amount_sats: Option<u64>,
+        max_amount_sats: Option<u64>,
+    ) -> Result<ReceivePaymentResponse, SdkError> {
+        let mut bytes = [0u8; 32];
+        thread_rng().fill(&mut bytes);
+        let preimage =
+            Preimage::try_from(bytes.to_vec()).map_err(|e| SdkError::Generic(e.to_string()))?;
+        let payment_hash = preimage.compute_hash();
+        let receive = self
+            .spark_wallet
+            .create_hodl_lightning_invoice(
+                0,
+                Some(bolt11_invoice_description(description)),
+                payment_hash,
+                None,
+                expiry_secs,
+            )
+            .await?;
+        let invoice = parse_invoice(&receive.invoice)
+            .ok_or_else(|| SdkError::Generic("Failed to parse the created invoice".to_string()))?;
+
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut receives = cache.fetch_amount_range_receives().await?;
+        receives.push(CachedAmountRangeReceive {
+            payment_hash: payment_hash.to_string(),
+            preimage: preimage.encode_hex(),
+            min_amount_sats,
+            max_amount_sats,
+            expires_at: invoice.timestamp.saturating_add(invoice.expiry),
+            rejected_transfer_ids: Vec::new(),
+        });
+        cache.save_amount_range_receives(&receives).await?;
+        self.track_lightning_receive(&receive).await;
+        Ok(ReceivePaymentResponse {
+            payment_request: receive.invoice,
+            fee: 0,
+        })
+    }
+
+    /// Periodically claims the payments of invoices created with amount
+    /// limits until shutdown. Payments outside the limits are left unclaimed,
+    /// so the SSP refunds the payer once their HTLC expires.
+    pub(crate) fn spawn_amount_range_receive_tracker(&self) {
+        let sdk = self.clone();
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                loop {
+                    select! {
+                        _ = shutdown_receiver.changed() => {
+                            debug!("Amount range receive tracker stopped on shutdown");
+                            return;
+                        }
+                        () = sleep(sdk.config.get().polling_interval(AMOUNT_RANGE_CHECK_INTERVAL)) => {}
+                    }
+                    if let Err(e) = sdk.check_amount_range_receives().await {
+                        warn!("Failed to check amount range receives: {e}");
+                    }
+                }
+            }
+            .instrument(span),
+        );
+    }
+
+    async fn check_amount_range_receives(&self) -> Result<(), SdkError> {
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let receives = cache.fetch_amount_range_receives().await?;
+        if receives.is_empty() {
+            return Ok(());
+        }
+        let transfers = self
+            .spark_wallet
+            .list_claimable_htlc_transfers(None)
+            .await?;
+
+        let mut claimed = Vec::new();
+        let mut rejected = Vec::new();
+        for transfer in transfers {
+            let Some((receive, transfer_id)) = match_receive(&receives, &transfer) else {
+                continue;
+            };
+            let amount_sats = transfer.total_value_sat;
+            if !in_range(receive, amount_sats) {
+                warn!(
+                    "Not claiming payment of {amount_sats} sats to invoice {} outside its amount limits",
+                    receive.payment_hash
+                );
+                rejected.push((receive.payment_hash.clone(), transfer_id));
+                continue;
+            }
+            if let Err(e) = self.claim_amount_range_receive(receive).await {
+                warn!(
+                    "Failed to claim payment to invoice {}: {e}",
+                    receive.payment_hash
+                );
+                continue;
+            }
+            claimed.push(receive.payment_hash.clone());
+        }
+
+        // Fetched again to keep receives created during the check
+        let mut receives = cache.fetch_amount_range_receives().await?;
+        let before = receives.clone();
+        let now = self.now_secs();
+        receives.retain(|r| {
+            !claimed.contains(&r.payment_hash)
+                && r.expires_at.saturating_add(AMOUNT_RANGE_GRACE_SECS) > now
+        });
+        for (payment_hash, transfer_id) in rejected {
+            if let Some(receive) = receives.iter_mut().find(|r| r.payment_hash == payment_hash) {
+                receive.rejected_transfer_ids.push(transfer_id);
+            }
+        }
+        if receives != before {
+            cache.save_amount_range_receives(&receives).await?;
+        }
+        Ok(())
+    }
+
+    async fn claim_amount_range_receive(
+        &self,
+        receive: &CachedAmountRangeReceive,
+    ) -> Result<(), SdkError> {
+        let preimage = Preimage::from_hex(&receive.preimage)
+            .map_err(|e| SdkError::Generic(format!("Invalid stored preimage: {e}")))?;
+        let transfer = self.spark_wallet.claim_htlc(&preimage).await?;
+        let payment: Payment = transfer.try_into()?;
+        info!(
+            "Claimed payment {} to invoice {}",
+            payment.id, receive.payment_hash
+        );
+        record_payment_update(&self.storage, &self.event_emitter, payment, true).await;
+        Ok(())
+    }
+}
+
+/// Returns the tracked receive a claimable HTLC transfer pays, with the id of
+/// the transfer, unless the transfer was already rejected.
+fn match_receive<'a>(
+    receives: &'a [CachedAmountRangeReceive],
+    transfer: &WalletTransfer,
+) -> Option<(&'a CachedAmountRangeReceive, String)> {
+    let payment_hash = transfer.htlc_preimage_request.as_ref()?.payment_hash;
+    let receive = receives
+        .iter()
+        .find(|r| r.payment_hash == payment_hash.to_string())?;
+    let transfer_id = transfer.id.to_string();
+    if receive.rejected_transfer_ids.contains(&transfer_id) {
+        return None;
+    }
+    Some((receive, transfer_id))
+}
+
+fn in_range(receive: &CachedAmountRangeReceive, amount_sats: u64) -> bool {
+    receive.min_amount_sats.is_none_or(|min| amount_sats >= min)
+        && receive.max_amount_sats.is_none_or(|max| amount_sats <= max)
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn receive(
+        min_amount_sats: Option<u64>,
+        max_amount_sats: Option<u64>,
+    ) -> CachedAmountRangeReceive {
+        CachedAmountRangeReceive {
+            payment_hash: "hash".to_string(),
+            preimage: "00".repeat(32),
+            min_amount_sats,
+            max_amount_sats,
+            expires_at: 0,
+            rejected_transfer_ids: Vec::new(),
+        }
+    }
+
+    #[test_all]
+    fn test_in_range() {
+        let bounded = receive(Some(1_000), Some(5_000));
+        assert!(!in_range(&bounded, 999));
+        assert!(in_range(&bounded, 1_000));
+        assert!(in_range(&bounded, 5_000));
+        assert!(!in_range(&bounded, 5_001));
+
+        let min_only = receive(Some(1_000), None);
+        assert!(in_range(&min_only, u64::MAX));
+        assert!(!in_range(&min_only, 1));
+    }
+
+    #[test_all]
+    fn test_validate_amount_range() {
+        assert!(validate_amount_range(None, None, Some(1_000), Some(5_000)).is_ok());
+        assert!(validate_amount_range(None, None, None, Some(5_000)).is_ok());
+        assert!(matches!(
+            validate_amount_range(None, None, Some(5_000), Some(1_000)),
+            Err(SdkError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            validate_amount_range(Some(2_000), None, Some(1_000), None),
+            Err(SdkError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            validate_amount_range(None, Some(&"hash".to_string()), Some(1_000), None),
+            Err(SdkError::InvalidInput(_))
+        ));
+    }
+}
@@ -1,25 +1,25 @@
 use bitcoin::secp256k1::{PublicKey, ecdsa::Signature};
 use breez_sdk_common::buy::cashapp::CashAppProvider;
-use std::str::FromStr;
-use tracing::{debug, info};
+use std::{str::FromStr, sync::Arc};
+use tracing::{debug, info, warn};
 
 use crate::{
     BuyBitcoinRequest, BuyBitcoinResponse, CheckMessageRequest, CheckMessageResponse,
-    CrossChainRouteFilter, CrossChainRoutePair, GetTokensMetadataRequest,
+    CrossChainRouteFilter, CrossChainRoutePair, GetServiceStatusResponse, GetTokensMetadataRequest,
     GetTokensMetadataResponse, InputType, ListFiatCurrenciesResponse, ListFiatRatesResponse,
     Network, OptimizationMode, OptimizeLeavesRequest, OptimizeLeavesResponse,
     RegisterWebhookRequest, RegisterWebhookResponse, SignMessageRequest, SignMessageResponse,
     UnregisterWebhookRequest, UpdateUserSettingsRequest, UserSettings, Webhook,
     chain::RecommendedFees,
     error::SdkError,
-    events::EventListener,
+    events::{EventFilter, EventListener, FilteredEventListener},
     issuer::TokenIssuer,
     models::{GetInfoRequest, GetInfoResponse, StableBalanceActiveLabel},
     persist::ObjectCacheRepository,
     utils::token::get_tokens_metadata_cached_or_query,
 };
 
-use super::{BreezSdk, helpers::get_deposit_address, parse_input};
+use super::{BreezSdk, helpers::get_deposit_address, parse_input_with_dns_resolver};
 
 #[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
 #[allow(clippy::needless_pass_by_value)]
@@ -42,6 +42,28 @@ impl BreezSdk {
         self.event_emitter.add_external_listener(listener).await
     }
 
+    /// Registers a listener to receive only the SDK events matching `filter`
+    ///
+    /// The listener is held and removed like one added with `add_event_listener`.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - An implementation of the `EventListener` trait
+    /// * `filter` - The events to deliver to the listener
+    ///
+    /// # Returns
+    ///
+    /// A unique identifier for the listener, which can be used to remove it later
+    pub async fn add_filtered_event_listener(
+        &self,
+        listener: Box<dyn EventListener>,
+        filter: EventFilter,
+    ) -> String {
+        self.event_emitter
+            .add_external_listener(Box::new(FilteredEventListener::new(listener, filter)))
+            .await
+    }
+
     /// Removes a previously registered event listener
     ///
     /// # Arguments
@@ -69,6 +91,7 @@ impl BreezSdk {
     pub async fn disconnect(&self) -> Result<(), SdkError> {
         info!("Disconnecting Breez SDK");
         self.event_emitter.clear_external_listeners().await;
+        self.plugins.shutdown().await;
         if self.shutdown_sender.send(()).is_err() {
             // A `watch::Sender::send` error means every receiver has been
             // dropped, i.e. no background task is listening. This is the
@@ -85,7 +108,12 @@ impl BreezSdk {
     }
 
     pub async fn parse(&self, input: &str) -> Result<InputType, SdkError> {
-        parse_input(input, Some(self.external_input_parsers.clone())).await
+        parse_input_with_dns_resolver(
+            input,
+            Some(self.external_input_parsers.clone()),
+            Arc::clone(&self.dns_resolver),
+        )
+        .await
     }
 
     /// Returns the available cross-chain routes.
@@ -149,6 +177,18 @@ impl BreezSdk {
         Ok(ListFiatRatesResponse { rates })
     }
 
+    /// Returns the maintenance windows announced by the service status feed
+    /// configured with `service_status_url`.
+    ///
+    /// The feed is fetched again, falling back to the last known status when
+    /// it can't be reached.
+    pub async fn get_service_status(&self) -> Result<GetServiceStatusResponse, SdkError> {
+        if let Err(e) = self.service_status.refresh().await {
+            warn!("Failed to refresh the service status: {e}");
+        }
+        Ok(self.service_status.status())
+    }
+
     /// Get the recommended BTC fees based on the configured chain service.
     pub async fn recommended_fees(&self) -> Result<RecommendedFees, SdkError> {
         Ok(self.chain_service.recommended_fees().await?)
@@ -160,6 +200,8 @@ impl BreezSdk {
     ///
     /// If the metadata is not found locally in cache, it will be queried from
     /// the Spark network and then cached.
+    /// With the `token-announcements` feature, tokens unknown to the Spark
+    /// network are resolved from their issuer announcements on Nostr.
     pub async fn get_tokens_metadata(
         &self,
         request: GetTokensMetadataRequest,
@@ -174,6 +216,10 @@ impl BreezSdk {
                 .collect::<Vec<_>>(),
         )
         .await?;
+        #[cfg(feature = "token-announcements")]
+        let metadata = self
+            .enrich_tokens_metadata(&request.token_identifiers, metadata)
+            .await;
         Ok(GetTokensMetadataResponse {
             tokens_metadata: metadata,
         })
@@ -186,6 +232,7 @@ impl BreezSdk {
         &self,
         request: SignMessageRequest,
     ) -> Result<SignMessageResponse, SdkError> {
+        self.ensure_not_watch_only("sign_message")?;
         use bitcoin::hex::DisplayHex;
 
         let pubkey = self.spark_wallet.get_identity_public_key().to_string();
@@ -302,6 +349,7 @@ impl BreezSdk {
         &self,
         request: OptimizeLeavesRequest,
     ) -> Result<OptimizeLeavesResponse, SdkError> {
+        self.ensure_not_watch_only("optimize_leaves")?;
         let max_rounds = match request.mode {
             OptimizationMode::Full => None,
             OptimizationMode::SingleRound => Some(1),
@@ -385,7 +433,9 @@ impl BreezSdk {
                 locked_amount_sat,
                 redirect_url,
             } => {
-                let address = get_deposit_address(&self.spark_wallet, true).await?;
+                let address =
+                    get_deposit_address(&self.spark_wallet, self.storage.clone(), Some(true))
+                        .await?;
                 self.buy_bitcoin_provider
                     .buy_bitcoin(address, locked_amount_sat, redirect_url)
                     .await
@@ -394,7 +444,7 @@ impl BreezSdk {
                     })?
             }
             BuyBitcoinRequest::CashApp { amount_sats } => {
-                if !matches!(self.config.network, Network::Mainnet) {
+                if !matches!(self.config.get().network, Network::Mainnet) {
                     return Err(SdkError::Generic(
                         "CashApp is only available on mainnet".to_string(),
                     ));
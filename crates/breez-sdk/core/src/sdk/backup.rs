@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use bitcoin::hashes::{Hash, sha256};
+use platform_utils::time::Duration;
+use platform_utils::tokio;
+use tokio::{select, time::sleep};
+use tracing::{Instrument, debug, info, warn};
+
+use crate::{
+    BackupResponse, BackupStore, RestoreRequest, RestoreResponse, backup::BackupService,
+    error::SdkError,
+};
+
+use super::BreezSdk;
+
+const BACKUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Creates an encrypted backup of the data that can't be recovered from
+    /// the Spark operators, such as LNURL descriptions, deposit refunds and
+    /// contacts. Only this wallet's seed can decrypt it.
+    pub async fn backup(&self) -> Result<BackupResponse, SdkError> {
+        let service = self.backup_service()?;
+        let snapshot = service.snapshot().await?;
+        Ok(BackupResponse {
+            backup: service.encrypt(&snapshot).await?,
+        })
+    }
+
+    /// Restores a backup created by this wallet. Data already stored is
+    /// kept, so a backup can be restored into a wallet in use.
+    pub async fn restore(&self, request: RestoreRequest) -> Result<RestoreResponse, SdkError> {
+        self.backup_service()?.restore(&request.backup).await
+    }
+}
+
+impl BreezSdk {
+    fn backup_service(&self) -> Result<&Arc<BackupService>, SdkError> {
+        self.backup_service.as_ref().ok_or_else(|| {
+            SdkError::Generic("Backups require a signer that supports HMAC".to_string())
+        })
+    }
+
+    /// Periodically passes a new backup to the backup store until shutdown,
+    /// when the backed up data changed.
+    pub(crate) fn spawn_backup_task(&self) {
+        let (Some(service), Some(store)) = (&self.backup_service, &self.backup_store) else {
+            return;
+        };
+        let service = Arc::clone(service);
+        let store = Arc::clone(store);
+        let config = self.config.clone();
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                let mut last_saved = None;
+                loop {
+                    match save_backup(&service, store.as_ref(), last_saved).await {
+                        Ok(hash) => last_saved = Some(hash),
+                        Err(e) => warn!("Failed to back up: {e}"),
+                    }
+                    select! {
+                        _ = shutdown_receiver.changed() => {
+                            debug!("Backup task stopped on shutdown");
+                            return;
+                        }
+                        () = sleep(config.get().polling_interval(BACKUP_INTERVAL)) => {}
+                    }
+                }
+            }
+            .instrument(span),
+        );
+    }
+}
+
+/// Saves a backup unless the data is unchanged since the backup with hash
+/// `last_saved`. Returns the hash of the saved data.
+async fn save_backup(
+    service: &BackupService,
+    store: &dyn BackupStore,
+    last_saved: Option<sha256::Hash>,
+) -> Result<sha256::Hash, SdkError> {
+    let snapshot = service.snapshot().await?;
+    let hash = sha256::Hash::hash(&snapshot);
+    if last_saved == Some(hash) {
+        return Ok(hash);
+    }
+    let backup = service.encrypt(&snapshot).await?;
+    store
+        .save(backup)
+        .await
+        .map_err(|e| SdkError::Generic(format!("Backup store failed: {e}")))?;
+    info!("Saved a new backup");
+    Ok(hash)
+}
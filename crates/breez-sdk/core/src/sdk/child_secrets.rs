@@ -0,0 +1,150 @@
+use bitcoin::bip32::{ChildNumber, DerivationPath};
+use bitcoin::hashes::Hash;
+
+use crate::{
+    ChildSecretPurpose, DeriveChildSecretRequest, DeriveChildSecretResponse, error::SdkError,
+    signer::HmacSigner,
+};
+
+use super::BreezSdk;
+
+/// Purpose of the derivation path, as in BIP-85.
+const CHILD_SECRET_PURPOSE: u32 = 83_696_968;
+/// Applications below this number are reserved for the SDK.
+const MIN_CUSTOM_APPLICATION: u32 = 1000;
+/// The message HMAC'ed with the derived key, as in BIP-85.
+const CHILD_SECRET_MESSAGE: &[u8] = b"bip-entropy-from-k";
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+#[allow(clippy::needless_pass_by_value)]
+impl BreezSdk {
+    /// Derives a secret for the given purpose from the wallet seed.
+    ///
+    /// Secrets are HMAC-SHA256 of `bip-entropy-from-k`, keyed with the private
+    /// key at `83696968'/{application}'/{index}'` under the wallet identity key.
+    /// They can be derived again from the seed, so they don't need to be backed up.
+    pub async fn derive_child_secret(
+        &self,
+        request: DeriveChildSecretRequest,
+    ) -> Result<DeriveChildSecretResponse, SdkError> {
+        // The HMAC step is absent on a signing-only signer
+        let Some(hmac_signer) = self.hmac_signer.as_ref() else {
+            return Err(SdkError::Generic(
+                "Deriving secrets requires a signer that supports HMAC".to_string(),
+            ));
+        };
+        derive_child_secret(
+            hmac_signer.as_ref(),
+            &request.purpose,
+            request.index.unwrap_or_default(),
+        )
+        .await
+    }
+}
+
+fn application_number(purpose: &ChildSecretPurpose) -> Result<u32, SdkError> {
+    match purpose {
+        ChildSecretPurpose::NwcKey => Ok(1),
+        ChildSecretPurpose::WebhookHmacKey => Ok(2),
+        ChildSecretPurpose::BackupEncryptionKey => Ok(3),
+        ChildSecretPurpose::Custom { application } if *application >= MIN_CUSTOM_APPLICATION => {
+            Ok(*application)
+        }
+        ChildSecretPurpose::Custom { .. } => Err(SdkError::InvalidInput(format!(
+            "Custom application must be at least {MIN_CUSTOM_APPLICATION}"
+        ))),
+    }
+}
+
+fn child_secret_path(purpose: &ChildSecretPurpose, index: u32) -> Result<DerivationPath, SdkError> {
+    let hardened = |i| {
+        ChildNumber::from_hardened_idx(i)
+            .map_err(|_| SdkError::InvalidInput(format!("Invalid derivation index: {i}")))
+    };
+    Ok(DerivationPath::from(vec![
+        hardened(CHILD_SECRET_PURPOSE)?,
+        hardened(application_number(purpose)?)?,
+        hardened(index)?,
+    ]))
+}
+
+pub(crate) async fn derive_child_secret(
+    hmac_signer: &dyn HmacSigner,
+    purpose: &ChildSecretPurpose,
+    index: u32,
+) -> Result<DeriveChildSecretResponse, SdkError> {
+    let path = child_secret_path(purpose, index)?;
+    let secret = hmac_signer.hmac_sha256(&path, CHILD_SECRET_MESSAGE).await?;
+    Ok(DeriveChildSecretResponse {
+        secret: hex::encode(secret.as_byte_array()),
+        derivation_path: path.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::bip32::Xpriv;
+    use macros::async_test_all;
+
+    use super::*;
+    use crate::signer::breez::BreezSignerImpl;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn path(path: &str) -> String {
+        DerivationPath::from_str(path).unwrap().to_string()
+    }
+
+    fn signer() -> BreezSignerImpl {
+        BreezSignerImpl::new(Xpriv::new_master(bitcoin::Network::Regtest, &[7; 32]).unwrap())
+    }
+
+    #[async_test_all]
+    async fn test_derive_child_secret_is_deterministic() {
+        let signer = signer();
+        let first = derive_child_secret(&signer, &ChildSecretPurpose::NwcKey, 0)
+            .await
+            .unwrap();
+        let again = derive_child_secret(&signer, &ChildSecretPurpose::NwcKey, 0)
+            .await
+            .unwrap();
+        assert_eq!(first.secret, again.secret);
+        assert_eq!(first.secret.len(), 64);
+        assert_eq!(first.derivation_path, path("m/83696968'/1'/0'"));
+    }
+
+    #[async_test_all]
+    async fn test_derive_child_secret_differs_per_purpose_and_index() {
+        let signer = signer();
+        let nwc = derive_child_secret(&signer, &ChildSecretPurpose::NwcKey, 0)
+            .await
+            .unwrap();
+        let backup = derive_child_secret(&signer, &ChildSecretPurpose::BackupEncryptionKey, 0)
+            .await
+            .unwrap();
+        let rotated = derive_child_secret(&signer, &ChildSecretPurpose::NwcKey, 1)
+            .await
+            .unwrap();
+        assert_ne!(nwc.secret, backup.secret);
+        assert_ne!(nwc.secret, rotated.secret);
+    }
+
+    #[async_test_all]
+    async fn test_derive_child_secret_rejects_reserved_application() {
+        let result =
+            derive_child_secret(&signer(), &ChildSecretPurpose::Custom { application: 3 }, 0).await;
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+
+        let custom = derive_child_secret(
+            &signer(),
+            &ChildSecretPurpose::Custom { application: 1000 },
+            0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(custom.derivation_path, path("m/83696968'/1000'/0'"));
+    }
+}
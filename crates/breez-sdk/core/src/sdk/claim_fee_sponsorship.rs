@@ -0,0 +1,444 @@
+use platform_utils::tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::{
+    ClaimFeeSponsor, ClaimFeeSponsorRequest, ClaimFeeSponsorship, ListClaimFeeSponsorshipsRequest,
+    ListClaimFeeSponsorshipsResponse,
+    error::SdkError,
+    persist::{CachedPendingClaimFeeSponsorship, ObjectCacheRepository},
+    utils::utxo_fetcher::DetailedUtxo,
+};
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Lists the deposit claim fees paid by the `ClaimFeeSponsor`, oldest
+    /// first, each tied to the payment claiming the deposit and the sponsor's
+    /// payment of the fee.
+    pub async fn list_claim_fee_sponsorships(
+        &self,
+        request: ListClaimFeeSponsorshipsRequest,
+    ) -> Result<ListClaimFeeSponsorshipsResponse, SdkError> {
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let sponsorships = cache
+            .fetch_claim_fee_sponsorships(
+                request.offset.unwrap_or(0) as usize,
+                request.limit.map_or(usize::MAX, |limit| limit as usize),
+            )
+            .await?;
+        Ok(ListClaimFeeSponsorshipsResponse { sponsorships })
+    }
+}
+
+impl BreezSdk {
+    /// The request for the sponsor to pay the claim fee of `utxo`, if a
+    /// sponsor is set and agrees to pay it. Only asked for fees the claim
+    /// policy allows, as the fee is deducted from the deposit either way.
+    pub(super) async fn sponsored_claim_fee(
+        &self,
+        utxo: &DetailedUtxo,
+        fee_sats: u64,
+    ) -> Result<Option<ClaimFeeSponsorRequest>, SdkError> {
+        let Some(sponsor) = &self.claim_fee_sponsor else {
+            return Ok(None);
+        };
+        if fee_sats == 0 {
+            return Ok(None);
+        }
+        let spark_address = self
+            .spark_wallet
+            .get_spark_address()?
+            .to_address_string()
+            .map_err(|e| {
+                SdkError::Generic(format!("Failed to convert Spark address to string: {e}"))
+            })?;
+        let request = ClaimFeeSponsorRequest {
+            deposit_txid: utxo.txid.to_string(),
+            deposit_vout: utxo.vout,
+            deposit_amount_sats: utxo.value,
+            fee_sats,
+            spark_address,
+        };
+        if !sponsor.will_sponsor(request.clone()).await {
+            info!(
+                "Sponsor declined the claim fee of deposit {}:{}",
+                utxo.txid, utxo.vout
+            );
+            return Ok(None);
+        }
+        Ok(Some(request))
+    }
+
+    /// Asks the sponsor to pay the fee of a sponsored claim and records the
+    /// sponsorship. If the sponsor fails, the sponsorship is kept pending and
+    /// retried by [`retry_claim_fee_sponsorships`](Self::retry_claim_fee_sponsorships),
+    /// as the deposit is already claimed.
+    pub(super) async fn pay_sponsored_claim_fee(
+        &self,
+        request: ClaimFeeSponsorRequest,
+        claim_payment_id: &str,
+    ) {
+        let Some(sponsor) = &self.claim_fee_sponsor else {
+            return;
+        };
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let pending = CachedPendingClaimFeeSponsorship {
+            request,
+            claim_payment_id: claim_payment_id.to_string(),
+        };
+        if let Err(e) = pay_claim_fee(
+            &cache,
+            sponsor.as_ref(),
+            &self.claim_fee_sponsorships_lock,
+            pending,
+            self.now_secs(),
+        )
+        .await
+        {
+            warn!("Failed to keep the claim fee sponsorship pending: {e}");
+        }
+    }
+
+    /// Asks the sponsor again to pay the claim fees it failed to pay.
+    pub(super) async fn retry_claim_fee_sponsorships(&self) -> Result<(), SdkError> {
+        let Some(sponsor) = &self.claim_fee_sponsor else {
+            return Ok(());
+        };
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        retry_claim_fees(
+            &cache,
+            sponsor.as_ref(),
+            &self.claim_fee_sponsorships_lock,
+            self.now_secs(),
+        )
+        .await
+    }
+}
+
+/// Asks the sponsor to pay the claim fee of `pending` and records the
+/// sponsorship, or keeps it pending if the sponsor fails.
+async fn pay_claim_fee(
+    cache: &ObjectCacheRepository,
+    sponsor: &dyn ClaimFeeSponsor,
+    lock: &Mutex<()>,
+    pending: CachedPendingClaimFeeSponsorship,
+    now: u64,
+) -> Result<(), SdkError> {
+    if try_pay_claim_fee(cache, sponsor, lock, &pending, now).await {
+        return Ok(());
+    }
+    let _guard = lock.lock().await;
+    let mut all_pending = cache.fetch_pending_claim_fee_sponsorships().await?;
+    all_pending.retain(|p| p.claim_payment_id != pending.claim_payment_id);
+    all_pending.push(pending);
+    cache
+        .save_pending_claim_fee_sponsorships(&all_pending)
+        .await?;
+    Ok(())
+}
+
+/// Asks the sponsor to pay the pending claim fees, in order. The ones it
+/// fails to pay again stay pending.
+async fn retry_claim_fees(
+    cache: &ObjectCacheRepository,
+    sponsor: &dyn ClaimFeeSponsor,
+    lock: &Mutex<()>,
+    now: u64,
+) -> Result<(), SdkError> {
+    let pending = cache.fetch_pending_claim_fee_sponsorships().await?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+    info!("Retrying {} pending claim fee sponsorships", pending.len());
+    for pending in pending {
+        if !try_pay_claim_fee(cache, sponsor, lock, &pending, now).await {
+            continue;
+        }
+        // Sponsorships may have been added while paying, so only this one is removed
+        let _guard = lock.lock().await;
+        let mut all_pending = cache.fetch_pending_claim_fee_sponsorships().await?;
+        all_pending.retain(|p| p.claim_payment_id != pending.claim_payment_id);
+        cache
+            .save_pending_claim_fee_sponsorships(&all_pending)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Asks the sponsor to pay the claim fee of `pending` and records the
+/// sponsorship. Returns whether the sponsor paid it.
+async fn try_pay_claim_fee(
+    cache: &ObjectCacheRepository,
+    sponsor: &dyn ClaimFeeSponsor,
+    lock: &Mutex<()>,
+    pending: &CachedPendingClaimFeeSponsorship,
+    now: u64,
+) -> bool {
+    let request = &pending.request;
+    let (txid, vout) = (&request.deposit_txid, request.deposit_vout);
+    let sponsor_payment_id = match sponsor.pay_claim_fee(request.clone()).await {
+        Ok(id) => id,
+        Err(e) => {
+            warn!("Sponsor failed to pay the claim fee of deposit {txid}:{vout}: {e}");
+            return false;
+        }
+    };
+    info!("Sponsor paid the claim fee of deposit {txid}:{vout} with {sponsor_payment_id}");
+    let sponsorship = ClaimFeeSponsorship {
+        deposit_txid: txid.clone(),
+        deposit_vout: vout,
+        claim_payment_id: pending.claim_payment_id.clone(),
+        fee_sats: request.fee_sats,
+        sponsor_payment_id,
+        created_at: now,
+    };
+    let _guard = lock.lock().await;
+    if let Err(e) = cache.append_claim_fee_sponsorship(sponsorship).await {
+        warn!("Failed to record claim fee sponsorship: {e}");
+    }
+    true
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use std::{path::PathBuf, sync::Arc};
+
+    use super::*;
+    use crate::{
+        ClaimFeeSponsorError,
+        persist::{CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE, Storage, sqlite::SqliteStorage},
+    };
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("breez-test-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn create_cache() -> ObjectCacheRepository {
+        let storage: Arc<dyn Storage> =
+            Arc::new(SqliteStorage::new(&create_temp_dir("claim_fee_sponsorship")).unwrap());
+        ObjectCacheRepository::new(storage)
+    }
+
+    /// Pays the claim fees while `fails` is false.
+    struct MockSponsor {
+        fails: std::sync::Mutex<bool>,
+        payments: std::sync::Mutex<u32>,
+    }
+
+    impl MockSponsor {
+        fn new(fails: bool) -> Self {
+            Self {
+                fails: std::sync::Mutex::new(fails),
+                payments: std::sync::Mutex::new(0),
+            }
+        }
+
+        fn set_fails(&self, fails: bool) {
+            *self.fails.lock().unwrap() = fails;
+        }
+
+        fn payments(&self) -> u32 {
+            *self.payments.lock().unwrap()
+        }
+    }
+
+    #[macros::async_trait]
+    impl ClaimFeeSponsor for MockSponsor {
+        async fn will_sponsor(&self, _request: ClaimFeeSponsorRequest) -> bool {
+            true
+        }
+
+        async fn pay_claim_fee(
+            &self,
+            request: ClaimFeeSponsorRequest,
+        ) -> Result<String, ClaimFeeSponsorError> {
+            if *self.fails.lock().unwrap() {
+                return Err(ClaimFeeSponsorError::ServiceConnectivity(
+                    "unreachable".to_string(),
+                ));
+            }
+            *self.payments.lock().unwrap() += 1;
+            Ok(format!("sponsor-{}", request.deposit_txid))
+        }
+    }
+
+    fn pending(txid: &str) -> CachedPendingClaimFeeSponsorship {
+        CachedPendingClaimFeeSponsorship {
+            request: ClaimFeeSponsorRequest {
+                deposit_txid: txid.to_string(),
+                deposit_vout: 0,
+                deposit_amount_sats: 10_000,
+                fee_sats: 300,
+                spark_address: "spark_address".to_string(),
+            },
+            claim_payment_id: format!("claim-{txid}"),
+        }
+    }
+
+    fn sponsorship(index: usize) -> ClaimFeeSponsorship {
+        ClaimFeeSponsorship {
+            deposit_txid: format!("tx{index}"),
+            deposit_vout: 0,
+            claim_payment_id: format!("claim-tx{index}"),
+            fee_sats: 300,
+            sponsor_payment_id: format!("sponsor-tx{index}"),
+            created_at: index as u64,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paid_claim_fee_recorded() {
+        let cache = create_cache();
+        let sponsor = MockSponsor::new(false);
+        let lock = Mutex::new(());
+        pay_claim_fee(&cache, &sponsor, &lock, pending("tx1"), 100)
+            .await
+            .unwrap();
+
+        let recorded = cache
+            .fetch_claim_fee_sponsorships(0, usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].claim_payment_id, "claim-tx1");
+        assert_eq!(recorded[0].sponsor_payment_id, "sponsor-tx1");
+        assert_eq!(recorded[0].created_at, 100);
+        assert!(
+            cache
+                .fetch_pending_claim_fee_sponsorships()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_claim_fee_retried() {
+        let cache = create_cache();
+        let sponsor = MockSponsor::new(true);
+        let lock = Mutex::new(());
+        pay_claim_fee(&cache, &sponsor, &lock, pending("tx1"), 100)
+            .await
+            .unwrap();
+        pay_claim_fee(&cache, &sponsor, &lock, pending("tx2"), 100)
+            .await
+            .unwrap();
+        assert_eq!(
+            cache
+                .fetch_pending_claim_fee_sponsorships()
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Still failing, the sponsorships stay pending
+        retry_claim_fees(&cache, &sponsor, &lock, 200)
+            .await
+            .unwrap();
+        assert_eq!(
+            cache
+                .fetch_pending_claim_fee_sponsorships()
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(
+            cache
+                .fetch_claim_fee_sponsorships(0, usize::MAX)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        sponsor.set_fails(false);
+        retry_claim_fees(&cache, &sponsor, &lock, 300)
+            .await
+            .unwrap();
+        assert_eq!(sponsor.payments(), 2);
+        assert!(
+            cache
+                .fetch_pending_claim_fee_sponsorships()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let recorded = cache
+            .fetch_claim_fee_sponsorships(0, usize::MAX)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = recorded
+            .iter()
+            .map(|s| s.claim_payment_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["claim-tx1", "claim-tx2"]);
+        assert!(recorded.iter().all(|s| s.created_at == 300));
+
+        // Nothing is paid again once settled
+        retry_claim_fees(&cache, &sponsor, &lock, 400)
+            .await
+            .unwrap();
+        assert_eq!(sponsor.payments(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_claim_fee_sponsorships_paged() {
+        let cache = create_cache();
+        let count = CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE * 2 + 5;
+        for index in 0..count {
+            cache
+                .append_claim_fee_sponsorship(sponsorship(index))
+                .await
+                .unwrap();
+        }
+
+        // None are dropped, and they're stored over three pages
+        let all = cache
+            .fetch_claim_fee_sponsorships(0, usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), count);
+        assert!(
+            all.iter()
+                .enumerate()
+                .all(|(i, s)| s.created_at == i as u64)
+        );
+        assert_eq!(
+            cache
+                .claim_fee_sponsorships_backup_cache_keys()
+                .await
+                .unwrap(),
+            vec![
+                "claim_fee_sponsorships",
+                "claim_fee_sponsorships-1",
+                "claim_fee_sponsorships-2"
+            ]
+        );
+
+        // A range across a page boundary
+        let offset = CLAIM_FEE_SPONSORSHIPS_PAGE_SIZE - 2;
+        let range = cache.fetch_claim_fee_sponsorships(offset, 5).await.unwrap();
+        let created: Vec<u64> = range.iter().map(|s| s.created_at).collect();
+        let expected: Vec<u64> = (offset..offset + 5).map(|i| i as u64).collect();
+        assert_eq!(created, expected);
+
+        // A range past the end
+        let tail = cache
+            .fetch_claim_fee_sponsorships(count - 3, 10)
+            .await
+            .unwrap();
+        assert_eq!(tail.len(), 3);
+        assert!(
+            cache
+                .fetch_claim_fee_sponsorships(count, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tracing::info;
+
+use crate::{Config, ConfigPatch, error::SdkError};
+
+use super::BreezSdk;
+
+/// The SDK config, shared with the components that read the settings that
+/// can be changed at runtime.
+#[derive(Clone)]
+pub(crate) struct SharedConfig(Arc<RwLock<Arc<Config>>>);
+
+impl SharedConfig {
+    pub(crate) fn new(config: Config) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(config))))
+    }
+
+    /// Returns a snapshot of the current config
+    pub(crate) fn get(&self) -> Arc<Config> {
+        Arc::clone(&self.0.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn update(&self, patch: ConfigPatch) -> Result<(), SdkError> {
+        let mut config = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        *config = Arc::new(apply_patch(&config, patch)?);
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+#[allow(clippy::needless_pass_by_value)]
+impl BreezSdk {
+    /// Changes the settings that can be changed without reconnecting.
+    ///
+    /// The patch is applied as a whole: if it changes an immutable setting or
+    /// results in an invalid config, nothing is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `patch` - The settings to change
+    pub async fn update_config(&self, patch: ConfigPatch) -> Result<(), SdkError> {
+        self.config.update(patch)?;
+        info!("Config updated");
+        Ok(())
+    }
+}
+
+fn apply_patch(config: &Config, patch: ConfigPatch) -> Result<Config, SdkError> {
+    if let Some(network) = patch.network
+        && network != config.network
+    {
+        return Err(SdkError::ImmutableConfig {
+            field: "network".to_string(),
+        });
+    }
+
+    let mut config = config.clone();
+    if let Some(sync_interval_secs) = patch.sync_interval_secs {
+        config.sync_interval_secs = sync_interval_secs;
+    }
+    if let Some(sync_trigger_debounce_secs) = patch.sync_trigger_debounce_secs {
+        config.sync_trigger_debounce_secs = sync_trigger_debounce_secs;
+    }
+    if let Some(max_deposit_claim_fee) = patch.max_deposit_claim_fee {
+        config.max_deposit_claim_fee = Some(max_deposit_claim_fee);
+    }
+    if let Some(prefer_spark_over_lightning) = patch.prefer_spark_over_lightning {
+        config.prefer_spark_over_lightning = prefer_spark_over_lightning;
+    }
+    if let Some(template) = patch.invoice_description_template {
+        config.invoice_description_template = Some(template);
+    }
+    if let Some(webhook_config) = patch.webhook_config {
+        config.webhook_config = Some(webhook_config);
+    }
+    if let Some(deposit_claim_policy) = patch.deposit_claim_policy {
+        config.deposit_claim_policy = Some(deposit_claim_policy);
+    }
+    if let Some(low_data_mode) = patch.low_data_mode {
+        config.low_data_mode = low_data_mode;
+    }
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use platform_utils::time::Duration;
+
+    use crate::{DepositClaimPolicy, MaxFee, Network, WebhookConfig, default_config};
+
+    use super::*;
+
+    #[test]
+    fn test_apply_patch() {
+        let config = default_config(Network::Regtest);
+        let patched = apply_patch(
+            &config,
+            ConfigPatch {
+                network: Some(Network::Regtest),
+                sync_interval_secs: Some(30),
+                sync_trigger_debounce_secs: Some(5),
+                max_deposit_claim_fee: Some(MaxFee::Fixed { amount: 500 }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(patched.sync_interval_secs, 30);
+        assert_eq!(patched.sync_trigger_debounce_secs, 5);
+        assert_eq!(
+            patched.max_deposit_claim_fee,
+            Some(MaxFee::Fixed { amount: 500 })
+        );
+        assert_eq!(
+            patched.prefer_spark_over_lightning,
+            config.prefer_spark_over_lightning
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_rejects_network_change() {
+        let result = apply_patch(
+            &default_config(Network::Regtest),
+            ConfigPatch {
+                network: Some(Network::Mainnet),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(SdkError::ImmutableConfig { field }) if field == "network"
+        ));
+    }
+
+    #[test]
+    fn test_apply_patch_rejects_invalid_config() {
+        let result = apply_patch(
+            &default_config(Network::Regtest),
+            ConfigPatch {
+                webhook_config: Some(WebhookConfig {
+                    url: "http://example.com".to_string(),
+                    secret: "secret".to_string(),
+                    max_attempts: None,
+                }),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_apply_patch_deposit_claim_policy() {
+        let config = default_config(Network::Regtest);
+        assert_eq!(
+            config.effective_deposit_claim_policy(),
+            DepositClaimPolicy::MaxFee {
+                max_fee: MaxFee::Rate { sat_per_vbyte: 1 }
+            }
+        );
+
+        let patched = apply_patch(
+            &config,
+            ConfigPatch {
+                deposit_claim_policy: Some(DepositClaimPolicy::Manual),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            patched.effective_deposit_claim_policy(),
+            DepositClaimPolicy::Manual
+        );
+
+        let result = apply_patch(
+            &config,
+            ConfigPatch {
+                deposit_claim_policy: Some(DepositClaimPolicy::MaxFeePercent { percent: 0.0 }),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_apply_patch_low_data_mode() {
+        let config = default_config(Network::Regtest);
+        let interval = Duration::from_secs(60);
+        assert_eq!(config.polling_interval(interval), interval);
+
+        let patched = apply_patch(
+            &config,
+            ConfigPatch {
+                low_data_mode: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(patched.low_data_mode);
+        assert_eq!(patched.polling_interval(interval), Duration::from_secs(240));
+    }
+
+    #[test]
+    fn test_shared_config_update() {
+        let shared = SharedConfig::new(default_config(Network::Regtest));
+        let snapshot = shared.get();
+        shared
+            .update(ConfigPatch {
+                prefer_spark_over_lightning: Some(!snapshot.prefer_spark_over_lightning),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_ne!(
+            shared.get().prefer_spark_over_lightning,
+            snapshot.prefer_spark_over_lightning
+        );
+    }
+}
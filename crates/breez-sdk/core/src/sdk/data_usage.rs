@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use platform_utils::{ByteCounter, HttpClient, MeteredHttpClient};
+
+use crate::{DataUsage, GetDataUsageResponse};
+
+use super::BreezSdk;
+
+/// Counts the bytes exchanged with each service since the SDK was built.
+#[derive(Default)]
+pub(crate) struct DataUsageTracker {
+    pub(crate) operator: Arc<ByteCounter>,
+    pub(crate) ssp: Arc<ByteCounter>,
+    pub(crate) chain: Arc<ByteCounter>,
+    pub(crate) sync: Arc<ByteCounter>,
+}
+
+impl DataUsageTracker {
+    /// Wraps `client` so its calls are counted by `counter`.
+    pub(crate) fn metered(
+        client: Arc<dyn HttpClient>,
+        counter: &Arc<ByteCounter>,
+    ) -> Arc<dyn HttpClient> {
+        Arc::new(MeteredHttpClient::new(client, Arc::clone(counter)))
+    }
+
+    fn usage(&self) -> GetDataUsageResponse {
+        GetDataUsageResponse {
+            operator: data_usage(&self.operator),
+            ssp: data_usage(&self.ssp),
+            chain: data_usage(&self.chain),
+            sync: data_usage(&self.sync),
+        }
+    }
+}
+
+fn data_usage(counter: &ByteCounter) -> DataUsage {
+    DataUsage {
+        bytes_sent: counter.sent(),
+        bytes_received: counter.received(),
+    }
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export)]
+impl BreezSdk {
+    /// Returns the bytes exchanged with each service since the SDK was
+    /// connected.
+    pub fn get_data_usage(&self) -> GetDataUsageResponse {
+        self.data_usage.usage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_usage() {
+        let tracker = DataUsageTracker::default();
+        tracker.operator.add_sent(10);
+        tracker.operator.add_received(20);
+        tracker.sync.add_received(5);
+
+        let usage = tracker.usage();
+        assert_eq!(
+            usage.operator,
+            DataUsage {
+                bytes_sent: 10,
+                bytes_received: 20
+            }
+        );
+        assert_eq!(usage.ssp, DataUsage::default());
+        assert_eq!(usage.sync.bytes_received, 5);
+    }
+}
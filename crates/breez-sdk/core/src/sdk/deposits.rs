@@ -6,13 +6,17 @@ use spark_wallet::{ListTransfersRequest, TransferId, WalletTransfer};
 use tracing::{error, trace};
 
 use crate::{
-    ClaimDepositRequest, ClaimDepositResponse, ListUnclaimedDepositsRequest,
-    ListUnclaimedDepositsResponse, RefundDepositRequest, RefundDepositResponse, error::SdkError,
-    models::Payment, persist::UpdateDepositPayload, sdk::RuntimeEvent,
+    ClaimDepositRequest, ClaimDepositResponse, DepositClaimPolicy, EstimateClaimDepositFeeResponse,
+    EstimateRefundDepositFeeResponse, ListUnclaimedDepositsRequest, ListUnclaimedDepositsResponse,
+    MaxFee, RefundDepositRequest, RefundDepositResponse, SetDepositClaimOverrideRequest,
+    error::SdkError,
+    models::Payment,
+    persist::{ObjectCacheRepository, UpdateDepositPayload},
+    sdk::RuntimeEvent,
     utils::utxo_fetcher::CachedUtxoFetcher,
 };
 
-use super::BreezSdk;
+use super::{BreezSdk, CLAIM_TX_SIZE_VBYTES};
 
 // Retry parameters for looking up the transfer created by a static deposit
 // claim while it propagates across Spark operators.
@@ -26,18 +30,24 @@ impl BreezSdk {
         &self,
         request: ClaimDepositRequest,
     ) -> Result<ClaimDepositResponse, SdkError> {
+        self.ensure_not_watch_only("claim_deposit")?;
         self.maybe_ensure_spark_private_mode_initialized().await?;
         let detailed_utxo =
             CachedUtxoFetcher::new(self.chain_service.clone(), self.storage.clone())
                 .fetch_detailed_utxo(&request.txid, request.vout)
                 .await?;
 
-        let max_fee = request
-            .max_fee
-            .or(self.config.max_deposit_claim_fee.clone());
-        match self.claim_utxo(&detailed_utxo, max_fee).await {
-            Ok(transfer_id) => {
-                let transfer = self.lookup_claim_transfer_with_retry(transfer_id).await?;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let txid = detailed_utxo.txid.to_string();
+        let policy = self.claim_policy(request.max_fee.clone());
+        match self.claim_utxo(&detailed_utxo, &policy).await {
+            Ok(claim) => {
+                cache
+                    .delete_deposit_claim_fee_target(&txid, detailed_utxo.vout)
+                    .await?;
+                let transfer = self
+                    .lookup_claim_transfer_with_retry(claim.transfer_id)
+                    .await?;
                 let payment: Payment = transfer.try_into()?;
                 // Insert the payment before returning so callers that
                 // immediately list payments see the claim.
@@ -55,24 +65,89 @@ impl BreezSdk {
             }
             Err(e) => {
                 error!("Failed to claim deposit: {e:?}");
+                // Keep the requested maximum so the deposit is claimed in the
+                // background once the fee drops to it.
+                if let (SdkError::MaxDepositClaimFeeExceeded { .. }, Some(max_fee)) =
+                    (&e, &request.max_fee)
+                {
+                    cache
+                        .save_deposit_claim_fee_target(&txid, detailed_utxo.vout, max_fee)
+                        .await?;
+                }
                 self.storage
                     .update_deposit(
-                        detailed_utxo.txid.to_string(),
+                        txid,
                         detailed_utxo.vout,
                         UpdateDepositPayload::ClaimError {
                             error: e.clone().into(),
                         },
                     )
                     .await?;
-                Err(e)
+                Err(self.service_status.annotate(e))
             }
         }
     }
 
+    /// Estimates the fee `claim_deposit` would claim the deposit with, from
+    /// the current claim quote, without claiming it.
+    pub async fn estimate_claim_deposit_fee(
+        &self,
+        request: ClaimDepositRequest,
+    ) -> Result<EstimateClaimDepositFeeResponse, SdkError> {
+        let detailed_utxo =
+            CachedUtxoFetcher::new(self.chain_service.clone(), self.storage.clone())
+                .fetch_detailed_utxo(&request.txid, request.vout)
+                .await?;
+        self.ensure_own_deposit(&detailed_utxo).await?;
+        let quote = self
+            .spark_wallet
+            .fetch_static_deposit_claim_quote(detailed_utxo.tx.clone(), Some(detailed_utxo.vout))
+            .await?;
+        let fee_sats = detailed_utxo.value.saturating_sub(quote.credit_amount_sats);
+        let policy = self.claim_policy(request.max_fee);
+        let (max_fee_sats, within_max_fee) = match self
+            .allowed_claim_fee_sats(&detailed_utxo, &policy, fee_sats)
+            .await
+        {
+            Ok(max_fee_sats) => (Some(max_fee_sats), true),
+            Err(SdkError::MaxDepositClaimFeeExceeded { max_fee, .. }) => (
+                max_fee.map(|max_fee| max_fee.to_sats(CLAIM_TX_SIZE_VBYTES)),
+                false,
+            ),
+            Err(e) => return Err(e),
+        };
+        Ok(EstimateClaimDepositFeeResponse {
+            fee_sats,
+            fee_rate_sat_per_vbyte: fee_sats.div_ceil(CLAIM_TX_SIZE_VBYTES),
+            max_fee_sats,
+            within_max_fee,
+        })
+    }
+
+    /// Estimates the fee `refund_deposit` would refund the deposit with,
+    /// without refunding it.
+    pub async fn estimate_refund_deposit_fee(
+        &self,
+        request: RefundDepositRequest,
+    ) -> Result<EstimateRefundDepositFeeResponse, SdkError> {
+        let detailed_utxo =
+            CachedUtxoFetcher::new(self.chain_service.clone(), self.storage.clone())
+                .fetch_detailed_utxo(&request.txid, request.vout)
+                .await?;
+        let fee_sats = self
+            .spark_wallet
+            .static_deposit_refund_fee(&request.destination_address, request.fee.into())?;
+        Ok(EstimateRefundDepositFeeResponse {
+            fee_sats,
+            refund_amount_sats: detailed_utxo.value.saturating_sub(fee_sats),
+        })
+    }
+
     pub async fn refund_deposit(
         &self,
         request: RefundDepositRequest,
     ) -> Result<RefundDepositResponse, SdkError> {
+        self.ensure_not_watch_only("refund_deposit")?;
         let detailed_utxo =
             CachedUtxoFetcher::new(self.chain_service.clone(), self.storage.clone())
                 .fetch_detailed_utxo(&request.txid, request.vout)
@@ -112,12 +187,50 @@ impl BreezSdk {
         &self,
         request: ListUnclaimedDepositsRequest,
     ) -> Result<ListUnclaimedDepositsResponse, SdkError> {
-        let deposits = self.storage.list_deposits().await?;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut deposits = self.storage.list_deposits().await?;
+        for deposit in &mut deposits {
+            deposit.claim_max_fee = cache
+                .fetch_deposit_claim_fee_target(&deposit.txid, deposit.vout)
+                .await?;
+        }
         Ok(ListUnclaimedDepositsResponse { deposits })
     }
+
+    /// Sets the maximum fee a deposit is claimed with, overriding the claim
+    /// policy for that deposit. The override applies from the next automatic
+    /// claim and is removed once the deposit is claimed.
+    pub async fn set_deposit_claim_override(
+        &self,
+        request: SetDepositClaimOverrideRequest,
+    ) -> Result<(), SdkError> {
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        match &request.max_fee {
+            Some(max_fee) => {
+                cache
+                    .save_deposit_claim_fee_target(&request.txid, request.vout, max_fee)
+                    .await?;
+            }
+            None => {
+                cache
+                    .delete_deposit_claim_fee_target(&request.txid, request.vout)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl BreezSdk {
+    /// The claim policy of a manual claim: `max_fee` if set, otherwise the
+    /// configured policy.
+    fn claim_policy(&self, max_fee: Option<MaxFee>) -> DepositClaimPolicy {
+        max_fee.map_or_else(
+            || self.config.get().effective_deposit_claim_policy(),
+            |max_fee| DepositClaimPolicy::MaxFee { max_fee },
+        )
+    }
+
     /// Looks up the transfer produced by a static deposit claim, retrying
     /// while the Spark operators have not yet indexed it. The SSP commits
     /// the claim synchronously, but there is a brief window before the
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use platform_utils::time::Duration;
+use platform_utils::tokio;
+use tokio::{select, time::sleep};
+use tracing::{Instrument, debug, info, warn};
+
+use crate::{
+    CpfpFundingKind, CpfpInput, EmergencyExitPackage, EmergencyExitStore, ExitLeafSelection,
+    ExportEmergencyExitPackageRequest, PrepareUnilateralExitRequest, PrepareUnilateralExitResponse,
+    UnilateralExitRequest, error::SdkError, signer::CpfpSigner,
+};
+
+use super::BreezSdk;
+
+const EMERGENCY_EXIT_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// What the SDK needs to keep an emergency exit package up to date.
+#[derive(Clone)]
+pub(crate) struct EmergencyExitSettings {
+    pub request: ExportEmergencyExitPackageRequest,
+    pub signer: Arc<dyn CpfpSigner>,
+    pub store: Arc<dyn EmergencyExitStore>,
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Builds signed unilateral exit transactions for every leaf worth exiting
+    /// at the request's fee rate, paying their fees from the set aside UTXO.
+    ///
+    /// The package recovers the funds with any Bitcoin wallet that can
+    /// broadcast transaction packages, even when the SDK and the operators are
+    /// unavailable. It covers the leaves at the time it is created, so export
+    /// a new one whenever the balance changes.
+    pub async fn export_emergency_exit_package(
+        &self,
+        request: ExportEmergencyExitPackageRequest,
+        signer: Arc<dyn CpfpSigner>,
+    ) -> Result<EmergencyExitPackage, SdkError> {
+        self.ensure_not_watch_only("export_emergency_exit_package")?;
+        let quote = self.quote_emergency_exit(&request).await?;
+        self.build_emergency_exit_package(request, quote, signer)
+            .await
+    }
+}
+
+impl BreezSdk {
+    async fn quote_emergency_exit(
+        &self,
+        request: &ExportEmergencyExitPackageRequest,
+    ) -> Result<PrepareUnilateralExitResponse, SdkError> {
+        self.prepare_unilateral_exit(PrepareUnilateralExitRequest {
+            fee_rate_sat_per_vbyte: request.fee_rate_sat_per_vbyte,
+            funding_kind: funding_kind(&request.funding_input),
+            destination: request.destination.clone(),
+            selection: ExitLeafSelection::Auto,
+        })
+        .await
+    }
+
+    async fn build_emergency_exit_package(
+        &self,
+        request: ExportEmergencyExitPackageRequest,
+        quote: PrepareUnilateralExitResponse,
+        signer: Arc<dyn CpfpSigner>,
+    ) -> Result<EmergencyExitPackage, SdkError> {
+        let exit = self
+            .unilateral_exit(
+                UnilateralExitRequest {
+                    prepared: quote,
+                    funding_inputs: vec![request.funding_input],
+                },
+                signer,
+            )
+            .await?;
+        Ok(EmergencyExitPackage {
+            created_at: self.now_secs(),
+            exit,
+        })
+    }
+
+    /// Periodically saves a new emergency exit package to the configured store
+    /// until shutdown, when the leaves worth exiting changed.
+    pub(crate) fn spawn_emergency_exit_refresh(&self) {
+        let Some(settings) = self.emergency_exit.clone() else {
+            return;
+        };
+        let sdk = self.clone();
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                let mut last_leaves = None;
+                loop {
+                    select! {
+                        _ = shutdown_receiver.changed() => {
+                            debug!("Emergency exit refresh stopped on shutdown");
+                            return;
+                        }
+                        () = sleep(sdk.config.get().polling_interval(EMERGENCY_EXIT_REFRESH_INTERVAL)) => {}
+                    }
+                    match sdk
+                        .refresh_emergency_exit_package(&settings, last_leaves.as_ref())
+                        .await
+                    {
+                        Ok(leaves) => last_leaves = Some(leaves),
+                        Err(e) => warn!("Failed to refresh the emergency exit package: {e}"),
+                    }
+                }
+            }
+            .instrument(span),
+        );
+    }
+
+    /// Saves a new package unless the leaves worth exiting are `last_leaves`.
+    /// Returns the leaves of the current package.
+    async fn refresh_emergency_exit_package(
+        &self,
+        settings: &EmergencyExitSettings,
+        last_leaves: Option<&Vec<(String, u64)>>,
+    ) -> Result<Vec<(String, u64)>, SdkError> {
+        let quote = self.quote_emergency_exit(&settings.request).await?;
+        let mut leaves: Vec<(String, u64)> = quote
+            .leaves
+            .iter()
+            .map(|l| (l.leaf_id.clone(), l.value))
+            .collect();
+        leaves.sort();
+        if last_leaves == Some(&leaves) {
+            return Ok(leaves);
+        }
+        let package = self
+            .build_emergency_exit_package(
+                settings.request.clone(),
+                quote,
+                Arc::clone(&settings.signer),
+            )
+            .await?;
+        settings
+            .store
+            .save(package)
+            .await
+            .map_err(|e| SdkError::Generic(format!("Emergency exit store failed: {e}")))?;
+        info!(
+            "Saved a new emergency exit package for {} leaves",
+            leaves.len()
+        );
+        Ok(leaves)
+    }
+}
+
+/// The funding kind a quote for `input` is computed with.
+fn funding_kind(input: &CpfpInput) -> CpfpFundingKind {
+    match input {
+        CpfpInput::P2wpkh { .. } => CpfpFundingKind::P2wpkh,
+        CpfpInput::P2tr { .. } => CpfpFundingKind::P2tr,
+        CpfpInput::Custom {
+            script_pubkey_hex,
+            signed_input_weight,
+            ..
+        } => CpfpFundingKind::Custom {
+            script_pubkey_hex: script_pubkey_hex.clone(),
+            signed_input_weight: *signed_input_weight,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_funding_kind() {
+        let p2wpkh = CpfpInput::P2wpkh {
+            txid: "00".repeat(32),
+            vout: 0,
+            value: 10_000,
+            pubkey: "02".repeat(33),
+        };
+        assert!(matches!(funding_kind(&p2wpkh), CpfpFundingKind::P2wpkh));
+
+        let custom = CpfpInput::Custom {
+            txid: "00".repeat(32),
+            vout: 1,
+            value: 10_000,
+            script_pubkey_hex: "0020".to_string(),
+            signed_input_weight: 300,
+        };
+        assert!(matches!(
+            funding_kind(&custom),
+            CpfpFundingKind::Custom {
+                script_pubkey_hex,
+                signed_input_weight: 300,
+            } if script_pubkey_hex == "0020"
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+use tracing::warn;
+
+use crate::{
+    FeeEstimationStats, Payment, PaymentMethod, PaymentStatus, SendPaymentMethod,
+    error::SdkError,
+    models::SendPaymentRequest,
+    persist::{CachedFeeEstimate, ObjectCacheRepository},
+};
+
+use super::BreezSdk;
+
+/// Number of most recent fee estimates kept for the stats.
+const MAX_FEE_ESTIMATES: usize = 500;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Returns how accurate the Lightning fee estimates of recent payments were.
+    ///
+    /// Drift between the estimated and the actual fees indicates that the fee
+    /// estimates of the Spark Service Provider changed.
+    pub async fn get_fee_estimation_stats(&self) -> Result<FeeEstimationStats, SdkError> {
+        let estimates = ObjectCacheRepository::new(self.storage.clone())
+            .fetch_fee_estimates()
+            .await?;
+        let mut samples = Vec::with_capacity(estimates.len());
+        for estimate in estimates {
+            // Estimates of payments that were never persisted are skipped
+            if let Ok(payment) = self.storage.get_payment_by_id(estimate.payment_id).await {
+                samples.push((estimate.estimated_fee_sats, payment));
+            }
+        }
+        Ok(compute_stats(&samples))
+    }
+}
+
+/// Records the prepare-time fee of a Lightning send so it can be compared with
+/// the fee the payment actually paid. Failures are logged and don't fail the send.
+pub(super) async fn record_fee_estimate(
+    sdk: &BreezSdk,
+    request: &SendPaymentRequest,
+    payment: &Payment,
+) {
+    let SendPaymentMethod::Bolt11Invoice {
+        lightning_fee_sats, ..
+    } = &request.prepare_response.payment_method
+    else {
+        return;
+    };
+    // Invoices paid over Spark and conversions have no Lightning fee to compare
+    if payment.method != PaymentMethod::Lightning
+        || request.prepare_response.conversion_estimate.is_some()
+    {
+        return;
+    }
+
+    let cache = ObjectCacheRepository::new(sdk.storage.clone());
+    let result = async {
+        let mut estimates = cache.fetch_fee_estimates().await?;
+        if estimates.iter().any(|e| e.payment_id == payment.id) {
+            return Ok(());
+        }
+        estimates.push(CachedFeeEstimate {
+            payment_id: payment.id.clone(),
+            estimated_fee_sats: *lightning_fee_sats,
+        });
+        let excess = estimates.len().saturating_sub(MAX_FEE_ESTIMATES);
+        estimates.drain(..excess);
+        cache.save_fee_estimates(&estimates).await
+    }
+    .await;
+    if let Err(e) = result {
+        warn!(
+            "Failed to record fee estimate for payment {}: {e}",
+            payment.id
+        );
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn compute_stats(samples: &[(u64, Payment)]) -> FeeEstimationStats {
+    let mut stats = FeeEstimationStats::default();
+    let mut relative_errors = Vec::new();
+    let mut total_estimated = 0f64;
+    let mut total_actual = 0f64;
+    let mut total_absolute_error = 0f64;
+
+    for (estimated, payment) in samples {
+        match payment.status {
+            PaymentStatus::Pending => {
+                stats.pending_count = stats.pending_count.saturating_add(1);
+                continue;
+            }
+            PaymentStatus::Failed => continue,
+            PaymentStatus::Completed => {}
+        }
+        let estimated = *estimated as f64;
+        let actual = payment.fees as f64;
+        stats.sample_count = stats.sample_count.saturating_add(1);
+        total_estimated += estimated;
+        total_actual += actual;
+        total_absolute_error += (actual - estimated).abs();
+        if actual > estimated {
+            stats.underestimated_count = stats.underestimated_count.saturating_add(1);
+        } else if actual < estimated {
+            stats.overestimated_count = stats.overestimated_count.saturating_add(1);
+        }
+        if estimated > 0.0 {
+            relative_errors.push((actual - estimated) / estimated);
+        }
+    }
+
+    if stats.sample_count > 0 {
+        let count = f64::from(stats.sample_count);
+        stats.mean_estimated_fee_sats = total_estimated / count;
+        stats.mean_actual_fee_sats = total_actual / count;
+        stats.mean_absolute_error_sats = total_absolute_error / count;
+    }
+    if !relative_errors.is_empty() {
+        stats.mean_relative_error =
+            relative_errors.iter().sum::<f64>() / relative_errors.len() as f64;
+        stats.max_relative_error = relative_errors
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PaymentType;
+
+    use super::*;
+
+    fn payment(status: PaymentStatus, fees: u128) -> Payment {
+        Payment {
+            id: uuid::Uuid::new_v4().to_string(),
+            payment_type: PaymentType::Send,
+            status,
+            amount: 10_000,
+            fees,
+            timestamp: 0,
+            method: PaymentMethod::Lightning,
+            details: None,
+            conversion_details: None,
+            imported: false,
+            display_amount: crate::DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let stats = compute_stats(&[
+            (10, payment(PaymentStatus::Completed, 12)),
+            (10, payment(PaymentStatus::Completed, 8)),
+            (4, payment(PaymentStatus::Completed, 4)),
+            (10, payment(PaymentStatus::Pending, 0)),
+            (10, payment(PaymentStatus::Failed, 0)),
+        ]);
+
+        assert_eq!(stats.sample_count, 3);
+        assert_eq!(stats.pending_count, 1);
+        assert_eq!(stats.underestimated_count, 1);
+        assert_eq!(stats.overestimated_count, 1);
+        assert!((stats.mean_estimated_fee_sats - 8.0).abs() < f64::EPSILON);
+        assert!((stats.mean_actual_fee_sats - 8.0).abs() < f64::EPSILON);
+        assert!((stats.mean_absolute_error_sats - 4.0 / 3.0).abs() < 1e-9);
+        assert!(stats.mean_relative_error.abs() < 1e-9);
+        assert!((stats.max_relative_error - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_stats_without_samples() {
+        assert_eq!(compute_stats(&[]), FeeEstimationStats::default());
+    }
+}
@@ -16,8 +16,11 @@ use crate::{
     error::SdkError,
     events::{EventListener, SdkEvent},
     models::Payment,
-    persist::Storage,
-    utils::payments::update_balances,
+    persist::{ObjectCacheRepository, Storage},
+    utils::{
+        deposit_addresses::{is_used, track_current_address},
+        payments::update_balances,
+    },
 };
 
 /// Looks up the payment matching `identifier` from storage, if present.
@@ -180,19 +183,38 @@ pub(crate) fn validate_breez_api_key(api_key: &str) -> Result<(), SdkError> {
 ///
 /// When `new_address` is `false`, returns the existing address via
 /// generate (which creates one on first call).
+///
+/// When `new_address` is unset, returns the existing address as long as no
+/// deposit to it was seen, and rotates otherwise.
+///
+/// The returned address is tracked as the current one, see
+/// [`track_current_address`].
 pub(crate) async fn get_deposit_address(
     spark_wallet: &SparkWallet,
-    new_address: bool,
+    storage: Arc<dyn Storage>,
+    new_address: Option<bool>,
 ) -> Result<String, SdkError> {
-    if new_address {
-        Ok(spark_wallet
-            .rotate_static_deposit_address()
-            .await?
-            .to_string())
-    } else {
-        Ok(spark_wallet
-            .generate_static_deposit_address()
-            .await?
-            .to_string())
+    let object_repository = ObjectCacheRepository::new(storage);
+    let mut monitored = object_repository
+        .fetch_monitored_deposit_addresses()
+        .await?;
+    let address = match new_address {
+        Some(true) => spark_wallet.rotate_static_deposit_address().await?,
+        Some(false) => spark_wallet.generate_static_deposit_address().await?,
+        None => {
+            let current = spark_wallet.generate_static_deposit_address().await?;
+            if is_used(&monitored, &current.to_string()) {
+                spark_wallet.rotate_static_deposit_address().await?
+            } else {
+                current
+            }
+        }
     }
+    .to_string();
+
+    track_current_address(&mut monitored, &address);
+    object_repository
+        .save_monitored_deposit_addresses(&monitored)
+        .await?;
+    Ok(address)
 }
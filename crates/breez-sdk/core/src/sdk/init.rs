@@ -1,6 +1,6 @@
 use platform_utils::tokio;
-use std::sync::Arc;
-use tokio::sync::{OnceCell, watch};
+use std::sync::{Arc, atomic::AtomicBool};
+use tokio::sync::{Mutex, OnceCell, watch};
 use tracing::{Instrument, error, info};
 
 use crate::{Network, error::SdkError, persist::ObjectCacheRepository};
@@ -12,14 +12,15 @@ impl BreezSdk {
     pub(crate) async fn init_and_start(params: BreezSdkParams) -> Result<Self, SdkError> {
         // In Regtest we allow running without a Breez API key to facilitate local
         // integration tests. For non-regtest networks, a valid API key is required.
-        if !matches!(params.config.network, Network::Regtest) {
-            match &params.config.api_key {
+        let config = params.config.get();
+        if !matches!(config.network, Network::Regtest) {
+            match &config.api_key {
                 Some(api_key) => validate_breez_api_key(api_key)?,
                 None => return Err(SdkError::Generic("Missing Breez API key".to_string())),
             }
         }
         let (initial_synced_sender, initial_synced_watcher) = watch::channel(false);
-        let external_input_parsers = params.config.get_all_external_input_parsers();
+        let external_input_parsers = config.get_all_external_input_parsers();
 
         let sdk = Self {
             config: params.config,
@@ -27,14 +28,24 @@ impl BreezSdk {
             storage: params.storage,
             chain_service: params.chain_service,
             fiat_service: params.fiat_service,
+            historical_rates: params.historical_rates,
             lnurl_client: params.lnurl_client,
             lnurl_server_client: params.lnurl_server_client,
+            dns_resolver: params.dns_resolver,
             lnurl_auth_signer: params.lnurl_auth_signer,
+            hmac_signer: params.hmac_signer,
             event_emitter: params.event_emitter,
             shutdown_sender: params.shutdown_sender,
             runtime: params.runtime,
             sync_coordinator: params.sync_coordinator,
             initial_synced_watcher,
+            history_backfill_running: Arc::new(AtomicBool::new(false)),
+            sync_info_lock: Arc::new(Mutex::new(())),
+            payment_consumers_lock: Arc::new(Mutex::new(())),
+            queued_sends_lock: Arc::new(Mutex::new(())),
+            claim_fee_sponsorships_lock: Arc::new(Mutex::new(())),
+            queued_sends_retry_running: Arc::new(AtomicBool::new(false)),
+            sync_capture: Arc::new(Mutex::new(None)),
             external_input_parsers,
             spark_private_mode_initialized: Arc::new(OnceCell::new()),
             token_converter: params.token_converter,
@@ -42,9 +53,21 @@ impl BreezSdk {
             buy_bitcoin_provider: params.buy_bitcoin_provider,
             cross_chain_context: params.cross_chain_context,
             lightning_sender: params.lightning_sender,
+            plugins: params.plugins,
+            clock: params.clock,
+            payment_approver: params.payment_approver,
+            claim_fee_sponsor: params.claim_fee_sponsor,
+            travel_rule_provider: params.travel_rule_provider,
+            service_status: params.service_status,
+            data_usage: params.data_usage,
+            backup_service: params.backup_service,
+            backup_store: params.backup_store,
+            emergency_exit: params.emergency_exit,
+            watch_only: params.watch_only,
         };
 
         sdk.start(initial_synced_sender).await;
+        sdk.plugins.start(&sdk).await;
         Ok(sdk)
     }
 
@@ -73,7 +96,7 @@ impl BreezSdk {
         let sdk = self.clone();
         let span = tracing::Span::current();
         tokio::spawn(async move {
-            if sdk.config.lnurl_domain.is_none() {
+            if sdk.config.get().lnurl_domain.is_none() {
                 return;
             }
 
@@ -94,7 +117,21 @@ impl BreezSdk {
             .await
     }
 
+    /// Fails with [`SdkError::WatchOnly`] if the wallet can't sign `operation`.
+    pub(crate) fn ensure_not_watch_only(&self, operation: &str) -> Result<(), SdkError> {
+        if self.watch_only {
+            return Err(SdkError::WatchOnly {
+                operation: operation.to_string(),
+            });
+        }
+        Ok(())
+    }
+
     pub(super) async fn ensure_spark_private_mode_initialized_inner(&self) -> Result<(), SdkError> {
+        // A watch-only wallet leaves the wallet's settings as they are
+        if self.watch_only {
+            return Ok(());
+        }
         self.spark_private_mode_initialized
             .get_or_try_init(|| async {
                 // Check if already initialized in storage
@@ -114,7 +151,7 @@ impl BreezSdk {
     }
 
     async fn initialize_spark_private_mode(&self) -> Result<(), SdkError> {
-        if !self.config.private_enabled_default {
+        if !self.config.get().private_enabled_default {
             ObjectCacheRepository::new(self.storage.clone())
                 .save_spark_private_mode_initialized()
                 .await?;
@@ -40,6 +40,7 @@ impl BreezSdk {
         &self,
         request: RegisterLightningAddressRequest,
     ) -> Result<LightningAddressInfo, SdkError> {
+        self.ensure_not_watch_only("register_lightning_address")?;
         let cache = ObjectCacheRepository::new(self.storage.clone());
         let Some(client) = &self.lnurl_server_client else {
             return Err(SdkError::Generic(
@@ -79,6 +80,7 @@ impl BreezSdk {
         &self,
         request: AuthorizeTransferRequest,
     ) -> Result<TransferAuthorization, SdkError> {
+        self.ensure_not_watch_only("authorize_lightning_address_transfer")?;
         let cache = ObjectCacheRepository::new(self.storage.clone());
         let Some(address_info) = cache.fetch_lightning_address().await?.flatten() else {
             return Err(SdkError::Generic(
@@ -106,6 +108,7 @@ impl BreezSdk {
         &self,
         request: ClaimTransferRequest,
     ) -> Result<LightningAddressInfo, SdkError> {
+        self.ensure_not_watch_only("claim_lightning_address_transfer")?;
         let cache = ObjectCacheRepository::new(self.storage.clone());
         let Some(client) = &self.lnurl_server_client else {
             return Err(SdkError::Generic(
@@ -18,7 +18,8 @@ use tracing::{Instrument, error, info};
 
 use crate::{
     Payment, PaymentDetails, PaymentStatus, Storage, error::SdkError, events::EventEmitter,
-    utils::payments::record_payment_update,
+    models::adaptors::lightning_send_failure_reason,
+    utils::payments::record_payment_update_with_reason,
 };
 
 /// Reusable helper that owns the dependencies needed to pay a BOLT11
@@ -171,7 +172,7 @@ impl LightningSender {
             async move {
                 // Drive the poll loop until we either reach a terminal status,
                 // hit the attempt cap, or get a shutdown signal.
-                let terminal_payment: Option<Payment> = 'poll: {
+                let terminal_payment: Option<(Payment, Option<String>)> = 'poll: {
                     for i in 0..MAX_POLL_ATTEMPTS {
                         info!(
                             "Polling lightning send payment {} attempt {}",
@@ -187,7 +188,7 @@ impl LightningSender {
                                     info!("Polling payment status = {} {:?}", payment.status, p.status);
                                     if payment.status != PaymentStatus::Pending {
                                         info!("Polling payment completed status = {}", payment.status);
-                                        break 'poll Some(payment);
+                                        break 'poll Some((payment, lightning_send_failure_reason(&p.status)));
                                     }
                                 }
 
@@ -203,12 +204,19 @@ impl LightningSender {
                     None
                 };
 
-                let Some(payment) = terminal_payment else {
+                let Some((payment, failure_reason)) = terminal_payment else {
                     return;
                 };
 
                 let _ = tx.send(payment.clone());
-                record_payment_update(&storage, &event_emitter, payment, true).await;
+                record_payment_update_with_reason(
+                    &storage,
+                    &event_emitter,
+                    payment,
+                    true,
+                    failure_reason,
+                )
+                .await;
             }
             .instrument(span),
         );
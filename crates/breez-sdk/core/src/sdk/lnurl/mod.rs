@@ -26,6 +26,7 @@ impl BreezSdk {
     }
 
     pub async fn lnurl_pay(&self, request: LnurlPayRequest) -> Result<LnurlPayResponse, SdkError> {
+        self.ensure_not_watch_only("lnurl_pay")?;
         pay::send(self, request).await
     }
 
@@ -73,6 +74,7 @@ impl BreezSdk {
         &self,
         request: LnurlWithdrawRequest,
     ) -> Result<LnurlWithdrawResponse, SdkError> {
+        self.ensure_not_watch_only("lnurl_withdraw")?;
         self.maybe_ensure_spark_private_mode_initialized().await?;
         let LnurlWithdrawRequest {
             amount_sats,
@@ -164,6 +166,7 @@ impl BreezSdk {
         &self,
         request_data: LnurlAuthRequestDetails,
     ) -> Result<LnurlCallbackStatus, SdkError> {
+        self.ensure_not_watch_only("lnurl_auth")?;
         // LNURL-auth needs the HMAC step, absent on a signing-only signer.
         let Some(lnurl_auth_signer) = self.lnurl_auth_signer.as_ref() else {
             return Err(SdkError::Generic(
@@ -17,6 +17,7 @@ use crate::{
         BreezSdk,
         helpers::process_success_action,
         payments::{client_signing, conversion, send, validation},
+        withdrawal_allowlist,
     },
 };
 
@@ -234,6 +235,11 @@ pub(super) async fn send(
     request: LnurlPayRequest,
 ) -> Result<LnurlPayResponse, SdkError> {
     sdk.maybe_ensure_spark_private_mode_initialized().await?;
+    withdrawal_allowlist::ensure_destination_allowed(
+        sdk,
+        withdrawal_allowlist::lnurl_pay_approval_request(&request.prepare_response),
+    )
+    .await?;
 
     let is_fees_included = request.prepare_response.fee_policy == FeePolicy::FeesIncluded;
 
@@ -314,9 +320,13 @@ pub(super) async fn send(
                 token_identifier: None,
                 conversion_estimate: request.prepare_response.conversion_estimate,
                 fee_policy: internal_fee_policy,
+                bip21_details: None,
+                fee_breakdown: None,
             },
             options: None,
             idempotency_key: request.idempotency_key,
+            compliance_metadata: None,
+            queue_if_offline: None,
         },
         true,
         // For conversions, don't pass amount_override — let
@@ -355,7 +365,7 @@ async fn finalize_lnurl_pay(
 
     let lnurl_info = LnurlPayInfo {
         ln_address: pay_request.address,
-        comment,
+        comment: comment.clone(),
         domain: Some(pay_request.domain),
         metadata: Some(pay_request.metadata_str),
         processed_success_action: processed_success_action.clone().map(From::from),
@@ -406,6 +416,7 @@ async fn finalize_lnurl_pay(
     Ok(LnurlPayResponse {
         payment,
         success_action: processed_success_action.map(From::from),
+        comment,
     })
 }
 
@@ -439,6 +450,8 @@ pub(super) async fn build_package(
         token_identifier: None,
         conversion_estimate: None,
         fee_policy: prepare_response.fee_policy,
+        bip21_details: None,
+        fee_breakdown: None,
     };
 
     let mut package = client_signing::build_unsigned_transfer_package(sdk, &internal, None).await?;
@@ -516,7 +529,7 @@ async fn query_lnurl_invoice(
         amount_msat,
         &request.comment,
         &request.pay_request.clone().into(),
-        sdk.config.network.into(),
+        sdk.config.get().network.into(),
         request.validate_success_action_url,
     )
     .await?;
@@ -1,34 +1,81 @@
+mod accounts;
+mod amount_range_receives;
 mod api;
+mod backup;
+mod child_secrets;
+mod claim_fee_sponsorship;
+mod config_update;
 mod contacts;
+mod data_usage;
 mod deposits;
+mod emergency_exit;
+mod fee_estimation;
 mod helpers;
 mod init;
 mod lightning_address;
 mod lightning_sender;
 mod lnurl;
+mod notifications;
+mod payment_consumers;
+mod payment_disputes;
+mod payment_export;
+mod payment_import;
 mod payments;
+mod private_key_sweep;
+mod queued_sends;
+mod receive_diagnostics;
 mod runtime;
+mod storage_integrity;
+mod stuck_payments;
 mod sync;
+mod sync_capture;
 mod sync_coordinator;
+#[cfg(feature = "token-announcements")]
+mod token_announcements;
 mod unilateral_exit;
-
+mod viewer;
+mod withdrawal_allowlist;
+mod withdrawals;
+
+pub use accounts::discover_accounts;
+pub(crate) use child_secrets::derive_child_secret;
+pub(crate) use config_update::SharedConfig;
+pub(crate) use data_usage::DataUsageTracker;
+pub(crate) use emergency_exit::EmergencyExitSettings;
+pub(crate) use helpers::process_success_action;
 pub(crate) use lightning_sender::LightningSender;
 pub(crate) use runtime::{RuntimeEvent, SdkRuntime, runtime_from_config};
 pub(crate) use sync_coordinator::SyncCoordinator;
 
 use bitflags::bitflags;
-use breez_sdk_common::{buy::moonpay::MoonpayProvider, fiat::FiatService};
+use breez_sdk_common::{
+    buy::moonpay::MoonpayProvider,
+    dns::DnsResolver,
+    fiat::{FiatService, HistoricalRatesService, MEMPOOL_HISTORICAL_PRICE_URL},
+};
 use platform_utils::HttpClient;
 use platform_utils::tokio;
 use spark_wallet::SparkWallet;
-use std::sync::Arc;
+use std::sync::{Arc, atomic::AtomicBool};
 use tokio::sync::{Mutex, OnceCell, oneshot, watch};
 
 use crate::{
-    BitcoinChainService, ExternalInputParser, InputType, LeafOptimizationConfig, Logger, Network,
-    TokenOptimizationConfig, error::SdkError, events::EventEmitter, lnurl::LnurlServerClient,
-    logger, models::Config, persist::Storage, signer::lnurl_auth::LnurlAuthSignerAdapter,
-    stable_balance::StableBalance, token_conversion::TokenConverter,
+    BackupStore, BitcoinChainService, ClaimFeeSponsor, DecodedPaymentRequest, ExternalInputParser,
+    InputType, LeafOptimizationConfig, Logger, Network, PaymentApprover, TokenOptimizationConfig,
+    backup::BackupService,
+    clock::Clock,
+    error::SdkError,
+    events::EventEmitter,
+    lnurl::LnurlServerClient,
+    logger,
+    models::{Config, ConfigProfile, SyncMode},
+    persist::Storage,
+    plugin::PluginManager,
+    service_status::ServiceStatusMonitor,
+    signer::{HmacSigner, lnurl_auth::LnurlAuthSignerAdapter},
+    stable_balance::StableBalance,
+    sync_capture::SyncCapture,
+    token_conversion::TokenConverter,
 };
 
 #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
@@ -80,20 +127,38 @@ impl SyncRequest {
 #[derive(Clone)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Object))]
 pub struct BreezSdk {
-    pub(crate) config: Config,
+    pub(crate) config: SharedConfig,
     pub(crate) spark_wallet: Arc<SparkWallet>,
     pub(crate) storage: Arc<dyn Storage>,
     pub(crate) chain_service: Arc<dyn BitcoinChainService>,
     pub(crate) fiat_service: Arc<dyn FiatService>,
+    pub(crate) historical_rates: Option<Arc<dyn HistoricalRatesService>>,
     pub(crate) lnurl_client: Arc<dyn HttpClient>,
     pub(crate) lnurl_server_client: Option<Arc<dyn LnurlServerClient>>,
+    pub(crate) dns_resolver: Arc<dyn DnsResolver + Send + Sync>,
     pub(crate) lnurl_auth_signer: Option<Arc<LnurlAuthSignerAdapter>>,
+    pub(crate) hmac_signer: Option<Arc<dyn HmacSigner>>,
     pub(crate) event_emitter: Arc<EventEmitter>,
     pub(crate) shutdown_sender: watch::Sender<()>,
     pub(crate) runtime: SdkRuntime,
     /// Coordinator for coalescing duplicate sync requests
     pub(crate) sync_coordinator: SyncCoordinator,
     pub(crate) initial_synced_watcher: watch::Receiver<bool>,
+    /// Set while the history older than the payments loaded by a fast sync
+    /// is being backfilled
+    pub(crate) history_backfill_running: Arc<AtomicBool>,
+    /// Serializes the sync info updates of the syncs and the history backfill
+    pub(crate) sync_info_lock: Arc<Mutex<()>>,
+    /// Serializes the acknowledgements of payment consumers
+    pub(crate) payment_consumers_lock: Arc<Mutex<()>>,
+    /// Serializes the updates of the queue of payments sent once online
+    pub(crate) queued_sends_lock: Arc<Mutex<()>>,
+    /// Serializes the updates of the claim fee sponsorships
+    pub(crate) claim_fee_sponsorships_lock: Arc<Mutex<()>>,
+    /// Set while the queued payments are being retried
+    pub(crate) queued_sends_retry_running: Arc<AtomicBool>,
+    /// Set while the sync passes are captured for debugging
+    pub(crate) sync_capture: Arc<Mutex<Option<Arc<SyncCapture>>>>,
     pub(crate) external_input_parsers: Vec<ExternalInputParser>,
     pub(crate) spark_private_mode_initialized: Arc<OnceCell<()>>,
     pub(crate) token_converter: Arc<dyn TokenConverter>,
@@ -105,16 +170,32 @@ pub struct BreezSdk {
     /// need to pay an LN invoice as part of a larger flow.
     #[allow(dead_code)]
     pub(crate) lightning_sender: Arc<LightningSender>,
+    pub(crate) plugins: PluginManager,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) payment_approver: Option<Arc<dyn PaymentApprover>>,
+    pub(crate) claim_fee_sponsor: Option<Arc<dyn ClaimFeeSponsor>>,
+    pub(crate) travel_rule_provider: Option<Arc<dyn TravelRuleProvider>>,
+    pub(crate) service_status: Arc<ServiceStatusMonitor>,
+    pub(crate) data_usage: Arc<DataUsageTracker>,
+    /// Set when the signer supports ECIES
+    pub(crate) backup_service: Option<Arc<BackupService>>,
+    pub(crate) backup_store: Option<Arc<dyn BackupStore>>,
+    pub(crate) emergency_exit: Option<EmergencyExitSettings>,
+    /// Set when built with `SdkBuilder::new_watch_only`, so nothing can be signed
+    pub(crate) watch_only: bool,
 }
 
 pub(crate) struct BreezSdkParams {
-    pub config: Config,
+    pub config: SharedConfig,
     pub storage: Arc<dyn Storage>,
     pub chain_service: Arc<dyn BitcoinChainService>,
     pub fiat_service: Arc<dyn FiatService>,
+    pub historical_rates: Option<Arc<dyn HistoricalRatesService>>,
     pub lnurl_client: Arc<dyn HttpClient>,
     pub lnurl_server_client: Option<Arc<dyn LnurlServerClient>>,
+    pub dns_resolver: Arc<dyn DnsResolver + Send + Sync>,
     pub lnurl_auth_signer: Option<Arc<LnurlAuthSignerAdapter>>,
+    pub hmac_signer: Option<Arc<dyn HmacSigner>>,
     pub shutdown_sender: watch::Sender<()>,
     pub runtime: SdkRuntime,
     pub spark_wallet: Arc<SparkWallet>,
@@ -125,6 +206,17 @@ pub(crate) struct BreezSdkParams {
     pub sync_coordinator: SyncCoordinator,
     pub cross_chain_context: crate::cross_chain::CrossChainContext,
     pub lightning_sender: Arc<LightningSender>,
+    pub plugins: PluginManager,
+    pub clock: Arc<dyn Clock>,
+    pub payment_approver: Option<Arc<dyn PaymentApprover>>,
+    pub claim_fee_sponsor: Option<Arc<dyn ClaimFeeSponsor>>,
+    pub travel_rule_provider: Option<Arc<dyn TravelRuleProvider>>,
+    pub service_status: Arc<ServiceStatusMonitor>,
+    pub data_usage: Arc<DataUsageTracker>,
+    pub backup_service: Option<Arc<BackupService>>,
+    pub backup_store: Option<Arc<dyn BackupStore>>,
+    pub emergency_exit: Option<EmergencyExitSettings>,
+    pub watch_only: bool,
 }
 
 pub async fn parse_input(
@@ -139,6 +231,32 @@ pub async fn parse_input(
     .into())
 }
 
+pub(crate) async fn parse_input_with_dns_resolver(
+    input: &str,
+    external_input_parsers: Option<Vec<ExternalInputParser>>,
+    dns_resolver: Arc<dyn DnsResolver + Send + Sync>,
+) -> Result<InputType, SdkError> {
+    Ok(breez_sdk_common::input::parse_with_dns_resolver(
+        input,
+        external_input_parsers.map(|parsers| parsers.into_iter().map(From::from).collect()),
+        dns_resolver,
+    )
+    .await?
+    .into())
+}
+
+/// Decodes a BOLT11 invoice or a Spark invoice without any network access.
+///
+/// Use it to display the details of a payment request, such as its amount,
+/// payment hash, expiry and route hints, without preparing a payment. Other
+/// inputs, including LNURLs and lightning addresses, are rejected; use
+/// `BreezSdk::parse` for them.
+#[allow(clippy::needless_pass_by_value)]
+#[cfg_attr(feature = "uniffi", uniffi::export)]
+pub fn decode_payment_request(input: String) -> Result<DecodedPaymentRequest, SdkError> {
+    Ok(breez_sdk_common::input::decode_payment_request(&input)?.into())
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[cfg_attr(feature = "uniffi", uniffi::export)]
 pub fn init_logging(
@@ -233,12 +351,14 @@ pub fn default_config(network: Network) -> Config {
         api_key: None,
         network,
         sync_interval_secs: 60, // every 1 minute
+        sync_trigger_debounce_secs: 2,
         max_deposit_claim_fee: Some(crate::MaxFee::Rate { sat_per_vbyte: 1 }),
         lnurl_domain,
         prefer_spark_over_lightning: false,
         external_input_parsers: None,
         use_default_external_input_parsers: true,
         real_time_sync_server_url: Some(BREEZ_SYNC_SERVICE_URL.to_string()),
+        event_stream_websocket: false,
         private_enabled_default: true,
         leaf_optimization_config: LeafOptimizationConfig {
             auto_enabled: true,
@@ -251,9 +371,18 @@ pub fn default_config(network: Network) -> Config {
         },
         stable_balance_config: None,
         max_concurrent_claims: 4,
+        claim_batch_size: 50,
+        claim_batch_interval_ms: 0,
         spark_config: Some(default_spark_config(network)),
         background_tasks_enabled: true,
         cross_chain_config: None,
+        invoice_description_template: None,
+        webhook_config: None,
+        deposit_claim_policy: None,
+        service_status_url: None,
+        historical_rates_url: Some(MEMPOOL_HISTORICAL_PRICE_URL.to_string()),
+        low_data_mode: false,
+        sync_mode: SyncMode::Full,
     }
 }
 
@@ -293,11 +422,45 @@ pub fn default_config(network: Network) -> Config {
 #[cfg_attr(feature = "uniffi", uniffi::export)]
 pub fn default_server_config(network: Network) -> Config {
     let mut config = default_config(network);
+    disable_background_tasks(&mut config);
+    config
+}
+
+/// Turns the background tasks off, resetting the config fields whose
+/// background services are gated off to their inactive shape.
+pub(crate) fn disable_background_tasks(config: &mut Config) {
     config.background_tasks_enabled = false;
     config.real_time_sync_server_url = None;
     config.leaf_optimization_config.auto_enabled = false;
     config.token_optimization_config.auto_enabled = false;
     config.cross_chain_config = None;
+}
+
+/// Builds a [`Config`] with coherent defaults for a [`ConfigProfile`].
+///
+/// Starts from [`default_config`] and adjusts the settings the profile is
+/// about. The result can be customized further like any other config.
+#[cfg_attr(feature = "uniffi", uniffi::export)]
+pub fn default_profile_config(network: Network, profile: ConfigProfile) -> Config {
+    let mut config = default_config(network);
+    match profile {
+        ConfigProfile::LowLatency => {
+            config.sync_interval_secs = 30;
+            config.leaf_optimization_config.multiplicity = 3;
+            config.max_concurrent_claims = 8;
+        }
+        ConfigProfile::LowFee => {
+            config.prefer_spark_over_lightning = true;
+            config.leaf_optimization_config.multiplicity = 0;
+            config.max_deposit_claim_fee = Some(crate::MaxFee::Rate { sat_per_vbyte: 1 });
+        }
+        ConfigProfile::Privacy => {
+            config.prefer_spark_over_lightning = false;
+            config.private_enabled_default = true;
+            config.real_time_sync_server_url = None;
+            config.lnurl_domain = None;
+        }
+    }
     config
 }
 
@@ -497,4 +660,33 @@ mod tests {
         assert!(default_config(Network::Mainnet).background_tasks_enabled);
         assert!(default_config(Network::Regtest).background_tasks_enabled);
     }
+
+    #[test]
+    fn default_profile_config_applies_profile() {
+        let cfg = default_profile_config(Network::Mainnet, ConfigProfile::LowLatency);
+        assert_eq!(cfg.sync_interval_secs, 30);
+        assert_eq!(cfg.leaf_optimization_config.multiplicity, 3);
+
+        let cfg = default_profile_config(Network::Mainnet, ConfigProfile::LowFee);
+        assert!(cfg.prefer_spark_over_lightning);
+        assert_eq!(cfg.leaf_optimization_config.multiplicity, 0);
+
+        let cfg = default_profile_config(Network::Mainnet, ConfigProfile::Privacy);
+        assert!(cfg.private_enabled_default);
+        assert!(cfg.real_time_sync_server_url.is_none());
+        assert!(cfg.lnurl_domain.is_none());
+        assert!(cfg.background_tasks_enabled);
+    }
+
+    #[test]
+    fn validate_fast_sync_mode() {
+        let mut cfg = default_config(Network::Mainnet);
+        assert_eq!(cfg.sync_mode, SyncMode::Full);
+        cfg.sync_mode = SyncMode::Fast {
+            recent_payments: 100,
+        };
+        assert!(cfg.validate().is_ok());
+        cfg.sync_mode = SyncMode::Fast { recent_payments: 0 };
+        assert!(matches!(cfg.validate(), Err(SdkError::InvalidInput(_))));
+    }
 }
@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use spark_wallet::TransferId;
+use tracing::info;
+
+use crate::{
+    ClaimDepositRequest, ProcessNotificationRequest, ProcessNotificationResponse,
+    error::SdkError,
+    utils::payments::{fetch_and_process_payment, insert_payment_with_metadata},
+};
+
+use super::BreezSdk;
+
+/// What a push notification asks the SDK to claim
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NotificationPayload {
+    Transfer { transfer_id: String },
+    Deposit { txid: String, vout: u32 },
+}
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Claims the incoming transfer or deposit referenced by a push
+    /// notification and returns the resulting payment, without syncing the
+    /// wallet.
+    ///
+    /// Meant for a mobile app woken by a push, with an SDK built with
+    /// [`SdkBuilder::with_headless_mode`](crate::SdkBuilder::with_headless_mode).
+    /// The rest of the wallet is synced when the app next runs.
+    pub async fn process_notification(
+        &self,
+        request: ProcessNotificationRequest,
+    ) -> Result<ProcessNotificationResponse, SdkError> {
+        self.ensure_not_watch_only("process_notification")?;
+        let payment = match parse_notification_payload(&request.payload)? {
+            NotificationPayload::Transfer { transfer_id } => {
+                info!("Processing notification of transfer {transfer_id}");
+                let payment = fetch_and_process_payment(
+                    &self.spark_wallet,
+                    self.storage.clone(),
+                    &transfer_id,
+                    false,
+                )
+                .await?;
+                if let Some(payment) = &payment {
+                    insert_payment_with_metadata(
+                        self.spark_wallet.clone(),
+                        self.storage.clone(),
+                        self.event_emitter.clone(),
+                        payment.clone(),
+                    )
+                    .await;
+                }
+                payment
+            }
+            NotificationPayload::Deposit { txid, vout } => {
+                info!("Processing notification of deposit {txid}:{vout}");
+                let response = self
+                    .claim_deposit(ClaimDepositRequest {
+                        txid,
+                        vout,
+                        max_fee: None,
+                    })
+                    .await?;
+                Some(response.payment)
+            }
+        };
+        Ok(ProcessNotificationResponse { payment })
+    }
+}
+
+fn parse_notification_payload(payload: &str) -> Result<NotificationPayload, SdkError> {
+    let payload: NotificationPayload = serde_json::from_str(payload)
+        .map_err(|e| SdkError::InvalidInput(format!("Invalid notification payload: {e}")))?;
+    if let NotificationPayload::Transfer { transfer_id } = &payload
+        && TransferId::from_str(transfer_id).is_err()
+    {
+        return Err(SdkError::InvalidInput(format!(
+            "Invalid transfer id in notification: {transfer_id}"
+        )));
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_parse_transfer_notification() {
+        let transfer_id = "0195b2b5-7d3c-7c6e-9a41-3f2d8c1e5b7a";
+        let payload = format!(r#"{{"type":"transfer","transfer_id":"{transfer_id}"}}"#);
+        assert_eq!(
+            parse_notification_payload(&payload).unwrap(),
+            NotificationPayload::Transfer {
+                transfer_id: transfer_id.to_string(),
+            }
+        );
+    }
+
+    #[test_all]
+    fn test_parse_deposit_notification() {
+        let payload = r#"{"type":"deposit","txid":"abcd","vout":1}"#;
+        assert_eq!(
+            parse_notification_payload(payload).unwrap(),
+            NotificationPayload::Deposit {
+                txid: "abcd".to_string(),
+                vout: 1,
+            }
+        );
+    }
+
+    #[test_all]
+    fn test_parse_invalid_notification() {
+        for payload in [
+            r#"{"type":"transfer","transfer_id":"not-a-transfer-id"}"#,
+            r#"{"type":"lnurl_pay_info"}"#,
+            "not json",
+        ] {
+            assert!(matches!(
+                parse_notification_payload(payload),
+                Err(SdkError::InvalidInput(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    AcknowledgePaymentRequest, AcknowledgePaymentResponse, ListUnacknowledgedPaymentsRequest,
+    ListUnacknowledgedPaymentsResponse, Payment, PaymentStatus, PaymentType,
+    error::SdkError,
+    persist::{
+        CachedPaymentConsumer, ObjectCacheRepository, PaymentCursor, StorageListPaymentsRequest,
+    },
+};
+
+use super::BreezSdk;
+
+/// The default and maximum number of unacknowledged payments listed at once
+const UNACKNOWLEDGED_PAYMENTS_LIMIT: u32 = 100;
+/// A consumer's cursor only moves past payments at least this old, so a
+/// payment synced late with an earlier timestamp is still listed.
+const CONSUMER_CURSOR_LAG_SECS: u64 = 24 * 60 * 60;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Lists the completed received payments a consumer has not acknowledged
+    /// yet with [`BreezSdk::acknowledge_payment`], oldest first.
+    ///
+    /// A payment is listed until it is acknowledged, also across restarts,
+    /// so each consumer processes every received payment at least once.
+    pub async fn list_unacknowledged_payments(
+        &self,
+        request: ListUnacknowledgedPaymentsRequest,
+    ) -> Result<ListUnacknowledgedPaymentsResponse, SdkError> {
+        validate_consumer_id(&request.consumer_id)?;
+        let limit = request
+            .limit
+            .unwrap_or(UNACKNOWLEDGED_PAYMENTS_LIMIT)
+            .min(UNACKNOWLEDGED_PAYMENTS_LIMIT);
+        let consumer = ObjectCacheRepository::new(self.storage.clone())
+            .fetch_payment_consumer(&request.consumer_id)
+            .await?;
+
+        let mut payments = Vec::new();
+        let mut after = consumer.cursor.clone();
+        loop {
+            let page = self
+                .storage
+                .list_payments(StorageListPaymentsRequest {
+                    type_filter: Some(vec![PaymentType::Receive]),
+                    status_filter: Some(vec![PaymentStatus::Completed]),
+                    after,
+                    limit: Some(limit),
+                    sort_ascending: Some(true),
+                    ..Default::default()
+                })
+                .await?;
+            let page_complete = u32::try_from(page.len()).is_ok_and(|len| len >= limit);
+            after = page.last().map(PaymentCursor::from_payment);
+            payments.extend(
+                page.into_iter()
+                    .filter(|p| !consumer.acknowledged.contains(&p.id)),
+            );
+            if !page_complete || u32::try_from(payments.len()).is_ok_and(|len| len >= limit) {
+                break;
+            }
+        }
+        payments.truncate(usize::try_from(limit)?);
+        Ok(ListUnacknowledgedPaymentsResponse { payments })
+    }
+
+    /// Acknowledges that a consumer has processed a received payment, so it
+    /// is no longer listed by [`BreezSdk::list_unacknowledged_payments`] for
+    /// that consumer. Acknowledging a payment again has no effect.
+    pub async fn acknowledge_payment(
+        &self,
+        request: AcknowledgePaymentRequest,
+    ) -> Result<AcknowledgePaymentResponse, SdkError> {
+        validate_consumer_id(&request.consumer_id)?;
+        let payment = self
+            .storage
+            .get_payment_by_id(request.payment_id.clone())
+            .await?;
+        if payment.payment_type != PaymentType::Receive
+            || payment.status != PaymentStatus::Completed
+        {
+            return Err(SdkError::InvalidInput(
+                "Only completed received payments can be acknowledged".to_string(),
+            ));
+        }
+
+        let _guard = self.payment_consumers_lock.lock().await;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut consumer = cache.fetch_payment_consumer(&request.consumer_id).await?;
+        let passed = consumer.cursor.as_ref().is_some_and(|cursor| {
+            (cursor.timestamp, cursor.id.as_str()) >= (payment.timestamp, payment.id.as_str())
+        });
+        if passed || !consumer.acknowledged.insert(payment.id) {
+            return Ok(AcknowledgePaymentResponse {});
+        }
+        self.advance_consumer_cursor(&mut consumer).await?;
+        cache
+            .save_payment_consumer(&request.consumer_id, &consumer)
+            .await?;
+        Ok(AcknowledgePaymentResponse {})
+    }
+}
+
+impl BreezSdk {
+    /// Moves the cursor of `consumer` past its oldest acknowledged payments,
+    /// so only the payments acknowledged out of order are kept.
+    async fn advance_consumer_cursor(
+        &self,
+        consumer: &mut CachedPaymentConsumer,
+    ) -> Result<(), SdkError> {
+        let to_timestamp = self.now_secs().saturating_sub(CONSUMER_CURSOR_LAG_SECS);
+        loop {
+            let page = self
+                .storage
+                .list_payments(StorageListPaymentsRequest {
+                    type_filter: Some(vec![PaymentType::Receive]),
+                    to_timestamp: Some(to_timestamp),
+                    after: consumer.cursor.clone(),
+                    limit: Some(UNACKNOWLEDGED_PAYMENTS_LIMIT),
+                    sort_ascending: Some(true),
+                    ..Default::default()
+                })
+                .await?;
+            let page_complete =
+                u32::try_from(page.len()).is_ok_and(|len| len >= UNACKNOWLEDGED_PAYMENTS_LIMIT);
+            if !advance_cursor(consumer, &page) || !page_complete {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Moves the cursor of `consumer` over `page`, as long as its payments are
+/// acknowledged or failed. Returns whether the whole page was passed.
+fn advance_cursor(consumer: &mut CachedPaymentConsumer, page: &[Payment]) -> bool {
+    for payment in page {
+        let passable = match payment.status {
+            PaymentStatus::Completed => consumer.acknowledged.remove(&payment.id),
+            PaymentStatus::Failed => true,
+            // A pending payment may still complete and has to be listed then
+            PaymentStatus::Pending => false,
+        };
+        if !passable {
+            return false;
+        }
+        consumer.cursor = Some(PaymentCursor::from_payment(payment));
+    }
+    true
+}
+
+fn validate_consumer_id(consumer_id: &str) -> Result<(), SdkError> {
+    if consumer_id.trim().is_empty() {
+        return Err(SdkError::InvalidInput(
+            "Consumer id must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+    use crate::PaymentMethod;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn payment(id: &str, status: PaymentStatus, timestamp: u64) -> Payment {
+        Payment {
+            id: id.to_string(),
+            payment_type: PaymentType::Receive,
+            status,
+            amount: 1_000,
+            fees: 0,
+            timestamp,
+            method: PaymentMethod::Spark,
+            details: None,
+            conversion_details: None,
+            imported: false,
+            display_amount: crate::DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    fn consumer(acknowledged: &[&str]) -> CachedPaymentConsumer {
+        CachedPaymentConsumer {
+            cursor: None,
+            acknowledged: acknowledged.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test_all]
+    fn test_advance_cursor_over_acknowledged_and_failed() {
+        let page = [
+            payment("a", PaymentStatus::Completed, 10),
+            payment("b", PaymentStatus::Failed, 20),
+            payment("c", PaymentStatus::Completed, 30),
+        ];
+        let mut consumer = consumer(&["a", "c"]);
+        assert!(advance_cursor(&mut consumer, &page));
+        assert_eq!(
+            consumer.cursor,
+            Some(PaymentCursor {
+                timestamp: 30,
+                id: "c".to_string(),
+            })
+        );
+        assert!(consumer.acknowledged.is_empty());
+    }
+
+    #[test_all]
+    fn test_advance_cursor_stops_at_unacknowledged() {
+        let page = [
+            payment("a", PaymentStatus::Completed, 10),
+            payment("b", PaymentStatus::Completed, 20),
+            payment("c", PaymentStatus::Completed, 30),
+        ];
+        let mut consumer = consumer(&["a", "c"]);
+        assert!(!advance_cursor(&mut consumer, &page));
+        assert_eq!(
+            consumer.cursor,
+            Some(PaymentCursor {
+                timestamp: 10,
+                id: "a".to_string(),
+            })
+        );
+        // The payment acknowledged out of order is kept
+        assert_eq!(consumer.acknowledged, ["c".to_string()].into());
+    }
+
+    #[test_all]
+    fn test_advance_cursor_stops_at_pending() {
+        let page = [
+            payment("a", PaymentStatus::Pending, 10),
+            payment("b", PaymentStatus::Completed, 20),
+        ];
+        let mut consumer = consumer(&["b"]);
+        assert!(!advance_cursor(&mut consumer, &page));
+        assert_eq!(consumer.cursor, None);
+        assert_eq!(consumer.acknowledged, ["b".to_string()].into());
+    }
+
+    #[test_all]
+    fn test_validate_consumer_id() {
+        assert!(validate_consumer_id("fulfillment").is_ok());
+        assert!(matches!(
+            validate_consumer_id(" "),
+            Err(SdkError::InvalidInput(_))
+        ));
+    }
+}
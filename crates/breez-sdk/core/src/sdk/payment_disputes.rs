@@ -0,0 +1,154 @@
+use crate::{
+    DisputeStatus, PaymentDispute, PaymentMetadata, SetPaymentDisputeRequest,
+    SetPaymentDisputeResponse, error::SdkError,
+    utils::payments::get_payment_with_conversion_details,
+};
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Marks a payment as disputed, or resolves its dispute.
+    ///
+    /// The dispute is stored with the payment metadata, so it is synced to
+    /// the other instances of the wallet and included in payment exports.
+    /// Payments can be listed by dispute status with
+    /// [`ListPaymentsRequest::dispute_status_filter`](crate::ListPaymentsRequest::dispute_status_filter).
+    pub async fn set_payment_dispute(
+        &self,
+        request: SetPaymentDisputeRequest,
+    ) -> Result<SetPaymentDisputeResponse, SdkError> {
+        let payment = self
+            .storage
+            .get_payment_by_id(request.payment_id.clone())
+            .await?;
+        let dispute = update_dispute(
+            payment.dispute,
+            request.status,
+            request.note,
+            self.now_secs(),
+        )?;
+        self.storage
+            .insert_payment_metadata(
+                request.payment_id.clone(),
+                PaymentMetadata {
+                    dispute: Some(dispute),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let payment =
+            get_payment_with_conversion_details(request.payment_id, self.storage.clone()).await?;
+        Ok(SetPaymentDisputeResponse { payment })
+    }
+}
+
+/// Applies a dispute status change to the existing dispute of a payment.
+/// Disputing a resolved payment opens a new dispute.
+fn update_dispute(
+    existing: Option<PaymentDispute>,
+    status: DisputeStatus,
+    note: Option<String>,
+    now: u64,
+) -> Result<PaymentDispute, SdkError> {
+    let note = note.map(|note| note.trim().to_string());
+    Ok(match (existing, status) {
+        (Some(existing), DisputeStatus::Disputed) if existing.status == DisputeStatus::Disputed => {
+            PaymentDispute {
+                note: note.or(existing.note),
+                updated_at: now,
+                ..existing
+            }
+        }
+        (Some(existing), DisputeStatus::Resolved) => PaymentDispute {
+            status: DisputeStatus::Resolved,
+            note: note.or(existing.note),
+            resolved_at: Some(existing.resolved_at.unwrap_or(now)),
+            updated_at: now,
+            ..existing
+        },
+        (_, DisputeStatus::Disputed) => PaymentDispute {
+            status: DisputeStatus::Disputed,
+            note,
+            disputed_at: now,
+            resolved_at: None,
+            updated_at: now,
+        },
+        (None, DisputeStatus::Resolved) => {
+            return Err(SdkError::InvalidInput(
+                "Payment is not disputed".to_string(),
+            ));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_dispute_then_resolve() {
+        let disputed = update_dispute(
+            None,
+            DisputeStatus::Disputed,
+            Some(" double charge ".to_string()),
+            100,
+        )
+        .unwrap();
+        assert_eq!(disputed.status, DisputeStatus::Disputed);
+        assert_eq!(disputed.note.as_deref(), Some("double charge"));
+        assert_eq!(disputed.disputed_at, 100);
+        assert_eq!(disputed.resolved_at, None);
+
+        // Updating an open dispute keeps its note and opening time
+        let updated = update_dispute(Some(disputed), DisputeStatus::Disputed, None, 150).unwrap();
+        assert_eq!(updated.note.as_deref(), Some("double charge"));
+        assert_eq!(updated.disputed_at, 100);
+        assert_eq!(updated.updated_at, 150);
+
+        let resolved = update_dispute(
+            Some(updated),
+            DisputeStatus::Resolved,
+            Some("refunded".to_string()),
+            200,
+        )
+        .unwrap();
+        assert_eq!(resolved.status, DisputeStatus::Resolved);
+        assert_eq!(resolved.note.as_deref(), Some("refunded"));
+        assert_eq!(resolved.disputed_at, 100);
+        assert_eq!(resolved.resolved_at, Some(200));
+
+        // Resolving again keeps the resolution time
+        let resolved = update_dispute(Some(resolved), DisputeStatus::Resolved, None, 300).unwrap();
+        assert_eq!(resolved.resolved_at, Some(200));
+        assert_eq!(resolved.updated_at, 300);
+    }
+
+    #[test_all]
+    fn test_reopen_resolved_dispute() {
+        let resolved = PaymentDispute {
+            status: DisputeStatus::Resolved,
+            note: Some("refunded".to_string()),
+            disputed_at: 100,
+            resolved_at: Some(200),
+            updated_at: 200,
+        };
+        let reopened = update_dispute(Some(resolved), DisputeStatus::Disputed, None, 300).unwrap();
+        assert_eq!(reopened.status, DisputeStatus::Disputed);
+        assert_eq!(reopened.note, None);
+        assert_eq!(reopened.disputed_at, 300);
+        assert_eq!(reopened.resolved_at, None);
+    }
+
+    #[test_all]
+    fn test_resolve_undisputed_payment() {
+        let result = update_dispute(None, DisputeStatus::Resolved, None, 100);
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,395 @@
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    DisplayAsset, ExportFormat, ExportPaymentsRequest, ExportPaymentsResponse, Payment,
+    PaymentDetails, PaymentMethod, StorageListPaymentsRequest, error::SdkError,
+    persist::PaymentCursor,
+};
+
+use super::BreezSdk;
+
+/// The number of payments exported per chunk.
+const EXPORT_CHUNK_SIZE: u32 = 500;
+
+const CSV_HEADER: &[&str] = &[
+    "id",
+    "timestamp",
+    "type",
+    "status",
+    "method",
+    "asset",
+    "amount",
+    "fees",
+    "dispute_status",
+];
+const CSV_FIAT_HEADER: &[&str] = &["fiat_currency", "fiat_rate", "fiat_value"];
+const CSV_METADATA_HEADER: &[&str] = &["description", "reference", "dispute_note"];
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Exports the payment history, oldest first, as CSV or JSON for
+    /// accounting.
+    ///
+    /// The history is exported in chunks of a bounded number of payments.
+    /// Call this again with the returned `next_cursor` and the same
+    /// parameters until it is unset, appending each chunk's `data`.
+    ///
+    /// Amounts are in sats or token base units. Fiat values are only set for
+    /// Bitcoin payments, and left empty when no historical rate is available.
+    pub async fn export_payments(
+        &self,
+        request: ExportPaymentsRequest,
+    ) -> Result<ExportPaymentsResponse, SdkError> {
+        let after = request
+            .cursor
+            .as_deref()
+            .map(PaymentCursor::decode)
+            .transpose()?;
+        let first_chunk = after.is_none();
+        let payments = self
+            .storage
+            .list_payments(StorageListPaymentsRequest {
+                from_timestamp: request.from_timestamp,
+                to_timestamp: request.to_timestamp,
+                limit: Some(EXPORT_CHUNK_SIZE),
+                sort_ascending: Some(true),
+                after,
+                ..Default::default()
+            })
+            .await?;
+        let next_cursor = match payments.last() {
+            Some(last) if payments.len() >= EXPORT_CHUNK_SIZE as usize => {
+                Some(PaymentCursor::from_payment(last).encode())
+            }
+            _ => None,
+        };
+
+        let fiat_currency = request.fiat_currency.map(|c| c.to_uppercase());
+        let mut records = Vec::with_capacity(payments.len());
+        for payment in &payments {
+            let fiat_rate = match &fiat_currency {
+                Some(currency) if payment.method != PaymentMethod::Token => self
+                    .fiat_rate_at_payment(payment, currency)
+                    .await
+                    .inspect_err(|e| debug!("No fiat rate for payment {}: {e}", payment.id))
+                    .ok(),
+                _ => None,
+            };
+            records.push(ExportRecord::new(
+                payment,
+                fiat_currency.as_deref(),
+                fiat_rate,
+                request.include_metadata,
+            ));
+        }
+
+        let data = match request.format {
+            ExportFormat::Csv => write_csv(
+                &records,
+                CsvColumns {
+                    header: first_chunk,
+                    fiat: fiat_currency.is_some(),
+                    metadata: request.include_metadata,
+                },
+            ),
+            ExportFormat::Json => write_json_lines(&records)?,
+        };
+        Ok(ExportPaymentsResponse { data, next_cursor })
+    }
+}
+
+/// A payment as written to an export.
+#[derive(Serialize)]
+struct ExportRecord {
+    id: String,
+    timestamp: u64,
+    #[serde(rename = "type")]
+    payment_type: String,
+    status: String,
+    method: String,
+    asset: String,
+    amount: String,
+    fees: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dispute_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fiat_currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fiat_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fiat_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dispute_note: Option<String>,
+}
+
+impl ExportRecord {
+    fn new(
+        payment: &Payment,
+        fiat_currency: Option<&str>,
+        fiat_rate: Option<f64>,
+        include_metadata: bool,
+    ) -> Self {
+        let asset = match &payment.display_amount.asset {
+            DisplayAsset::Bitcoin => "BTC".to_string(),
+            DisplayAsset::Token { ticker, .. } => ticker.clone(),
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let fiat_value = fiat_rate.map(|rate| payment.amount as f64 * rate / 100_000_000f64);
+        let (description, reference) = if include_metadata {
+            payment_metadata(payment)
+        } else {
+            (None, None)
+        };
+        let dispute_note = payment
+            .dispute
+            .as_ref()
+            .and_then(|dispute| dispute.note.clone())
+            .filter(|_| include_metadata);
+        Self {
+            id: payment.id.clone(),
+            timestamp: payment.timestamp,
+            payment_type: payment.payment_type.to_string(),
+            status: payment.status.to_string(),
+            method: payment.method.to_string(),
+            asset,
+            amount: payment.amount.to_string(),
+            fees: payment.fees.to_string(),
+            dispute_status: payment
+                .dispute
+                .as_ref()
+                .map(|dispute| dispute.status.to_string()),
+            fiat_currency: fiat_currency.map(ToString::to_string),
+            fiat_rate,
+            fiat_value,
+            description,
+            reference,
+            dispute_note,
+        }
+    }
+
+    fn csv_row(&self, columns: CsvColumns) -> Vec<String> {
+        let mut row = vec![
+            self.id.clone(),
+            self.timestamp.to_string(),
+            self.payment_type.clone(),
+            self.status.clone(),
+            self.method.clone(),
+            self.asset.clone(),
+            self.amount.clone(),
+            self.fees.clone(),
+            self.dispute_status.clone().unwrap_or_default(),
+        ];
+        if columns.fiat {
+            row.push(self.fiat_currency.clone().unwrap_or_default());
+            row.push(self.fiat_rate.map(|r| r.to_string()).unwrap_or_default());
+            row.push(self.fiat_value.map(|v| v.to_string()).unwrap_or_default());
+        }
+        if columns.metadata {
+            row.push(self.description.clone().unwrap_or_default());
+            row.push(self.reference.clone().unwrap_or_default());
+            row.push(self.dispute_note.clone().unwrap_or_default());
+        }
+        row
+    }
+}
+
+/// The description of a payment and the invoice or transaction identifying it.
+fn payment_metadata(payment: &Payment) -> (Option<String>, Option<String>) {
+    match &payment.details {
+        Some(PaymentDetails::Lightning {
+            description,
+            invoice,
+            lnurl_pay_info,
+            ..
+        }) => (
+            description
+                .clone()
+                .or_else(|| lnurl_pay_info.as_ref()?.extract_description()),
+            Some(invoice.clone()),
+        ),
+        Some(PaymentDetails::Spark {
+            invoice_details, ..
+        }) => (
+            invoice_details.as_ref().and_then(|i| i.description.clone()),
+            invoice_details.as_ref().map(|i| i.invoice.clone()),
+        ),
+        Some(PaymentDetails::Token {
+            tx_hash,
+            invoice_details,
+            ..
+        }) => (
+            invoice_details.as_ref().and_then(|i| i.description.clone()),
+            Some(tx_hash.clone()),
+        ),
+        Some(PaymentDetails::Withdraw { tx_id } | PaymentDetails::Deposit { tx_id, .. }) => {
+            (None, Some(tx_id.clone()))
+        }
+        None => (None, None),
+    }
+}
+
+/// The optional parts of a CSV chunk.
+#[derive(Clone, Copy)]
+struct CsvColumns {
+    header: bool,
+    fiat: bool,
+    metadata: bool,
+}
+
+fn write_csv(records: &[ExportRecord], columns: CsvColumns) -> String {
+    let mut out = String::new();
+    if columns.header {
+        let mut header = CSV_HEADER.to_vec();
+        if columns.fiat {
+            header.extend_from_slice(CSV_FIAT_HEADER);
+        }
+        if columns.metadata {
+            header.extend_from_slice(CSV_METADATA_HEADER);
+        }
+        let header: Vec<String> = header.into_iter().map(ToString::to_string).collect();
+        push_csv_line(&mut out, &header);
+    }
+    for record in records {
+        push_csv_line(&mut out, &record.csv_row(columns));
+    }
+    out
+}
+
+fn push_csv_line(out: &mut String, fields: &[String]) {
+    let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    out.push_str(&line.join(","));
+    out.push('\n');
+}
+
+/// Quotes a field containing a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn write_json_lines(records: &[ExportRecord]) -> Result<String, SdkError> {
+    let mut out = String::new();
+    for record in records {
+        out.push_str(&serde_json::to_string(record)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+    use crate::{
+        DisplayAmount, DisputeStatus, PaymentDispute, PaymentStatus, PaymentType,
+        SparkInvoicePaymentDetails,
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn payment(details: Option<PaymentDetails>) -> Payment {
+        Payment {
+            id: "payment".to_string(),
+            payment_type: PaymentType::Receive,
+            status: PaymentStatus::Completed,
+            amount: 50_000,
+            fees: 10,
+            timestamp: 1_700_000_000,
+            method: PaymentMethod::Spark,
+            details,
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    #[test_all]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test_all]
+    fn test_write_csv() {
+        let mut payment = payment(Some(PaymentDetails::Spark {
+            invoice_details: Some(SparkInvoicePaymentDetails {
+                description: Some("coffee, large".to_string()),
+                invoice: "spark1invoice".to_string(),
+            }),
+            htlc_details: None,
+            conversion_info: None,
+        }));
+        payment.dispute = Some(PaymentDispute {
+            status: DisputeStatus::Disputed,
+            note: Some("refund requested".to_string()),
+            disputed_at: 1_700_000_100,
+            resolved_at: None,
+            updated_at: 1_700_000_100,
+        });
+        let records = [ExportRecord::new(
+            &payment,
+            Some("USD"),
+            Some(60_000.0),
+            true,
+        )];
+
+        let csv = write_csv(
+            &records,
+            CsvColumns {
+                header: true,
+                fiat: true,
+                metadata: true,
+            },
+        );
+        assert_eq!(
+            csv,
+            "id,timestamp,type,status,method,asset,amount,fees,dispute_status,fiat_currency,fiat_rate,fiat_value,description,reference,dispute_note\n\
+             payment,1700000000,receive,completed,spark,BTC,50000,10,disputed,USD,60000,30,\"coffee, large\",spark1invoice,refund requested\n"
+        );
+
+        let csv = write_csv(
+            &records,
+            CsvColumns {
+                header: false,
+                fiat: false,
+                metadata: false,
+            },
+        );
+        assert_eq!(
+            csv,
+            "payment,1700000000,receive,completed,spark,BTC,50000,10,disputed\n"
+        );
+    }
+
+    #[test_all]
+    fn test_write_json_lines() {
+        let records = [
+            ExportRecord::new(&payment(None), None, None, false),
+            ExportRecord::new(&payment(None), None, None, false),
+        ];
+        let json = write_json_lines(&records).unwrap();
+        let lines: Vec<serde_json::Value> = json
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "receive");
+        assert_eq!(lines[0]["amount"], "50000");
+        assert!(lines[0].get("fiat_value").is_none());
+        assert!(lines[0].get("dispute_status").is_none());
+    }
+}
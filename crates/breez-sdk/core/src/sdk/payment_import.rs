@@ -0,0 +1,437 @@
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::{
+    DisplayAmount, ImportPaymentsRequest, ImportPaymentsResponse, Payment, PaymentDetails,
+    PaymentImportSource, PaymentMethod, PaymentStatus, PaymentType, SparkHtlcDetails,
+    SparkHtlcStatus, error::SdkError,
+};
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+#[allow(clippy::needless_pass_by_value)]
+impl BreezSdk {
+    /// Imports the payment history exported from another Breez SDK.
+    ///
+    /// Imported payments are marked with `imported` and keep their original
+    /// timestamps and payment hashes. Importing the same export again is a no-op.
+    /// Pending entries are skipped, as their outcome is only known to the
+    /// source SDK.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The source SDK and the JSON list of payments it returned
+    ///
+    /// # Returns
+    ///
+    /// The number of imported and skipped payments or an error
+    pub async fn import_payments(
+        &self,
+        request: ImportPaymentsRequest,
+    ) -> Result<ImportPaymentsResponse, SdkError> {
+        let entries: Vec<Value> = serde_json::from_str(&request.data)
+            .map_err(|e| SdkError::InvalidInput(format!("Invalid payment export: {e}")))?;
+
+        let mut imported_count: u32 = 0;
+        let mut skipped_count: u32 = 0;
+        for entry in entries {
+            let Some(payment) = convert_entry(request.source, entry) else {
+                skipped_count = skipped_count.saturating_add(1);
+                continue;
+            };
+            self.storage.apply_payment_update(payment).await?;
+            imported_count = imported_count.saturating_add(1);
+        }
+        info!(
+            "Imported {imported_count} payments from {:?}, skipped {skipped_count}",
+            request.source
+        );
+        Ok(ImportPaymentsResponse {
+            imported_count,
+            skipped_count,
+        })
+    }
+}
+
+fn convert_entry(source: PaymentImportSource, entry: Value) -> Option<Payment> {
+    let payment = match source {
+        PaymentImportSource::Greenlight => serde_json::from_value::<GreenlightPayment>(entry)
+            .map_err(|e| e.to_string())
+            .and_then(GreenlightPayment::into_payment),
+        PaymentImportSource::Liquid => serde_json::from_value::<LiquidPayment>(entry)
+            .map_err(|e| e.to_string())
+            .and_then(LiquidPayment::into_payment),
+    };
+    match payment {
+        Ok(payment) if payment.status == PaymentStatus::Pending => None,
+        Ok(payment) => Some(payment),
+        Err(e) => {
+            warn!("Skipping payment import entry: {e}");
+            None
+        }
+    }
+}
+
+fn imported_id(source: &str, id: &str) -> String {
+    format!("imported:{source}:{id}")
+}
+
+fn parse_status(status: &str) -> PaymentStatus {
+    match status.to_lowercase().as_str() {
+        "complete" | "completed" => PaymentStatus::Completed,
+        "failed" | "timedout" | "timed_out" | "refundable" | "refunded" => PaymentStatus::Failed,
+        _ => PaymentStatus::Pending,
+    }
+}
+
+fn parse_payment_type(payment_type: &str) -> Result<PaymentType, String> {
+    match payment_type.to_lowercase().as_str() {
+        "sent" | "send" | "closed_channel" | "closedchannel" => Ok(PaymentType::Send),
+        "received" | "receive" => Ok(PaymentType::Receive),
+        _ => Err(format!("Unknown payment type '{payment_type}'")),
+    }
+}
+
+fn lightning_details(
+    status: PaymentStatus,
+    payment_hash: String,
+    preimage: Option<String>,
+    invoice: Option<String>,
+    description: Option<String>,
+    destination_pubkey: Option<String>,
+) -> PaymentDetails {
+    let htlc_status = match status {
+        PaymentStatus::Completed => SparkHtlcStatus::PreimageShared,
+        PaymentStatus::Failed => SparkHtlcStatus::Returned,
+        PaymentStatus::Pending => SparkHtlcStatus::WaitingForPreimage,
+    };
+    PaymentDetails::Lightning {
+        description,
+        invoice: invoice.unwrap_or_default(),
+        destination_pubkey: destination_pubkey.unwrap_or_default(),
+        htlc_details: SparkHtlcDetails {
+            payment_hash,
+            preimage: preimage.filter(|p| !p.is_empty()),
+            expiry_time: 0,
+            status: htlc_status,
+        },
+        lnurl_pay_info: None,
+        lnurl_withdraw_info: None,
+        lnurl_receive_metadata: None,
+        conversion_info: None,
+    }
+}
+
+/// A payment as serialized by the Greenlight based Breez SDK
+#[derive(Deserialize)]
+struct GreenlightPayment {
+    id: String,
+    payment_type: String,
+    payment_time: i64,
+    amount_msat: u64,
+    fee_msat: u64,
+    status: String,
+    description: Option<String>,
+    #[serde(default)]
+    details: GreenlightPaymentDetails,
+}
+
+/// The union of the Greenlight SDK's untagged Lightning and closed channel details
+#[derive(Default, Deserialize)]
+struct GreenlightPaymentDetails {
+    payment_hash: Option<String>,
+    payment_preimage: Option<String>,
+    bolt11: Option<String>,
+    destination_pubkey: Option<String>,
+    closing_txid: Option<String>,
+}
+
+impl GreenlightPayment {
+    fn into_payment(self) -> Result<Payment, String> {
+        let status = parse_status(&self.status);
+        let timestamp = u64::try_from(self.payment_time)
+            .map_err(|_| format!("Invalid payment time {}", self.payment_time))?;
+        let (method, details) = match self.details.payment_hash {
+            Some(payment_hash) => (
+                PaymentMethod::Lightning,
+                Some(lightning_details(
+                    status,
+                    payment_hash,
+                    self.details.payment_preimage,
+                    self.details.bolt11,
+                    self.description,
+                    self.details.destination_pubkey,
+                )),
+            ),
+            None => (
+                PaymentMethod::Withdraw,
+                self.details
+                    .closing_txid
+                    .map(|tx_id| PaymentDetails::Withdraw { tx_id }),
+            ),
+        };
+        let amount = u128::from(self.amount_msat / 1000);
+        let display_amount = DisplayAmount::new(amount, details.as_ref());
+        Ok(Payment {
+            id: imported_id("greenlight", &self.id),
+            payment_type: parse_payment_type(&self.payment_type)?,
+            status,
+            amount,
+            fees: u128::from(self.fee_msat / 1000),
+            timestamp,
+            method,
+            details,
+            conversion_details: None,
+            imported: true,
+            display_amount,
+            group_id: None,
+            dispute: None,
+        })
+    }
+}
+
+/// A payment as serialized by the Breez SDK - Nodeless (Liquid implementation)
+#[derive(Deserialize)]
+struct LiquidPayment {
+    tx_id: Option<String>,
+    timestamp: u32,
+    amount_sat: u64,
+    fees_sat: u64,
+    payment_type: String,
+    status: String,
+    details: LiquidPaymentDetails,
+}
+
+#[derive(Deserialize)]
+enum LiquidPaymentDetails {
+    #[serde(alias = "lightning")]
+    Lightning {
+        swap_id: String,
+        description: Option<String>,
+        preimage: Option<String>,
+        invoice: Option<String>,
+        payment_hash: Option<String>,
+        destination_pubkey: Option<String>,
+    },
+    #[serde(alias = "liquid")]
+    Liquid {},
+    #[serde(alias = "bitcoin")]
+    Bitcoin {
+        swap_id: String,
+        lockup_tx_id: Option<String>,
+        claim_tx_id: Option<String>,
+    },
+}
+
+impl LiquidPayment {
+    fn into_payment(self) -> Result<Payment, String> {
+        let status = parse_status(&self.status);
+        let payment_type = parse_payment_type(&self.payment_type)?;
+        let (id, method, details) = match self.details {
+            LiquidPaymentDetails::Lightning {
+                swap_id,
+                description,
+                preimage,
+                invoice,
+                payment_hash,
+                destination_pubkey,
+            } => {
+                let payment_hash = payment_hash
+                    .ok_or_else(|| format!("Missing payment hash for swap {swap_id}"))?;
+                (
+                    swap_id,
+                    PaymentMethod::Lightning,
+                    Some(lightning_details(
+                        status,
+                        payment_hash,
+                        preimage,
+                        invoice,
+                        description,
+                        destination_pubkey,
+                    )),
+                )
+            }
+            LiquidPaymentDetails::Liquid {} => (
+                self.tx_id
+                    .ok_or_else(|| "Missing tx id for Liquid payment".to_string())?,
+                PaymentMethod::Unknown,
+                None,
+            ),
+            LiquidPaymentDetails::Bitcoin {
+                swap_id,
+                lockup_tx_id,
+                claim_tx_id,
+            } => match payment_type {
+                PaymentType::Send => (
+                    swap_id,
+                    PaymentMethod::Withdraw,
+                    claim_tx_id.map(|tx_id| PaymentDetails::Withdraw { tx_id }),
+                ),
+                PaymentType::Receive => (
+                    swap_id,
+                    PaymentMethod::Deposit,
+                    lockup_tx_id.map(|tx_id| PaymentDetails::Deposit { tx_id, vout: 0 }),
+                ),
+            },
+        };
+        let amount = u128::from(self.amount_sat);
+        let display_amount = DisplayAmount::new(amount, details.as_ref());
+        Ok(Payment {
+            id: imported_id("liquid", &id),
+            payment_type,
+            status,
+            amount,
+            fees: u128::from(self.fees_sat),
+            timestamp: u64::from(self.timestamp),
+            method,
+            details,
+            conversion_details: None,
+            imported: true,
+            display_amount,
+            group_id: None,
+            dispute: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const PAYMENT_HASH: &str = "4a2b4d5e7a1c9b3f8e6d0c2a5b7e9f1d3c5a7b9e1f3d5c7a9b1e3f5d7c9a1b3e";
+
+    #[test]
+    fn test_convert_greenlight_lightning_payment() {
+        let entry = json!({
+            "id": PAYMENT_HASH,
+            "payment_type": "Sent",
+            "payment_time": 1_700_000_000,
+            "amount_msat": 21_000_500,
+            "fee_msat": 3_000,
+            "status": "Complete",
+            "error": null,
+            "description": "coffee",
+            "details": {
+                "payment_hash": PAYMENT_HASH,
+                "label": "",
+                "destination_pubkey": "02abc",
+                "payment_preimage": "00ff",
+                "keysend": false,
+                "bolt11": "lnbc210n1",
+            },
+        });
+        let payment = convert_entry(PaymentImportSource::Greenlight, entry).unwrap();
+        assert_eq!(payment.id, format!("imported:greenlight:{PAYMENT_HASH}"));
+        assert_eq!(payment.payment_type, PaymentType::Send);
+        assert_eq!(payment.status, PaymentStatus::Completed);
+        assert_eq!(payment.amount, 21_000);
+        assert_eq!(payment.fees, 3);
+        assert_eq!(payment.timestamp, 1_700_000_000);
+        assert_eq!(payment.method, PaymentMethod::Lightning);
+        assert!(payment.imported);
+        let Some(PaymentDetails::Lightning {
+            description,
+            invoice,
+            htlc_details,
+            ..
+        }) = payment.details
+        else {
+            panic!("Expected Lightning details");
+        };
+        assert_eq!(description.as_deref(), Some("coffee"));
+        assert_eq!(invoice, "lnbc210n1");
+        assert_eq!(htlc_details.payment_hash, PAYMENT_HASH);
+        assert_eq!(htlc_details.preimage.as_deref(), Some("00ff"));
+        assert_eq!(htlc_details.status, SparkHtlcStatus::PreimageShared);
+    }
+
+    #[test]
+    fn test_convert_greenlight_closed_channel() {
+        let entry = json!({
+            "id": "closed",
+            "payment_type": "ClosedChannel",
+            "payment_time": 1_700_000_000,
+            "amount_msat": 50_000_000,
+            "fee_msat": 0,
+            "status": "Complete",
+            "details": {
+                "state": "Closed",
+                "funding_txid": "aa",
+                "closing_txid": "bb",
+            },
+        });
+        let payment = convert_entry(PaymentImportSource::Greenlight, entry).unwrap();
+        assert_eq!(payment.method, PaymentMethod::Withdraw);
+        assert!(matches!(
+            payment.details,
+            Some(PaymentDetails::Withdraw { ref tx_id }) if tx_id == "bb"
+        ));
+    }
+
+    #[test]
+    fn test_convert_liquid_payments() {
+        let lightning = json!({
+            "tx_id": "cc",
+            "timestamp": 1_700_000_100,
+            "amount_sat": 1_000,
+            "fees_sat": 25,
+            "payment_type": "receive",
+            "status": "complete",
+            "details": {
+                "Lightning": {
+                    "swap_id": "swap1",
+                    "description": "tip",
+                    "payment_hash": PAYMENT_HASH,
+                    "preimage": "00ff",
+                    "invoice": "lnbc10u1",
+                },
+            },
+        });
+        let payment = convert_entry(PaymentImportSource::Liquid, lightning).unwrap();
+        assert_eq!(payment.id, "imported:liquid:swap1");
+        assert_eq!(payment.payment_type, PaymentType::Receive);
+        assert_eq!(payment.timestamp, 1_700_000_100);
+        assert_eq!(payment.amount, 1_000);
+        assert_eq!(payment.fees, 25);
+        assert!(matches!(
+            payment.details,
+            Some(PaymentDetails::Lightning { ref htlc_details, .. })
+                if htlc_details.payment_hash == PAYMENT_HASH
+        ));
+
+        let bitcoin = json!({
+            "timestamp": 1_700_000_200,
+            "amount_sat": 5_000,
+            "fees_sat": 100,
+            "payment_type": "send",
+            "status": "failed",
+            "details": {
+                "Bitcoin": { "swap_id": "swap2", "claim_tx_id": null },
+            },
+        });
+        let payment = convert_entry(PaymentImportSource::Liquid, bitcoin).unwrap();
+        assert_eq!(payment.id, "imported:liquid:swap2");
+        assert_eq!(payment.status, PaymentStatus::Failed);
+        assert_eq!(payment.method, PaymentMethod::Withdraw);
+        assert!(payment.details.is_none());
+    }
+
+    #[test]
+    fn test_skip_pending_and_invalid_entries() {
+        let pending = json!({
+            "id": "pending",
+            "payment_type": "Received",
+            "payment_time": 1_700_000_000,
+            "amount_msat": 1_000,
+            "fee_msat": 0,
+            "status": "Pending",
+            "details": { "payment_hash": PAYMENT_HASH },
+        });
+        assert!(convert_entry(PaymentImportSource::Greenlight, pending).is_none());
+        assert!(convert_entry(PaymentImportSource::Liquid, json!({ "id": 1 })).is_none());
+    }
+}
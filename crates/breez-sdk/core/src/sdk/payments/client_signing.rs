@@ -99,7 +99,7 @@ pub(in crate::sdk) async fn build_unsigned_transfer_package(
                     prefer_spark,
                     completion_timeout_secs,
                 }) => (*prefer_spark, *completion_timeout_secs),
-                _ => (sdk.config.prefer_spark_over_lightning, None),
+                _ => (sdk.config.get().prefer_spark_over_lightning, None),
             };
             if prefers_bolt11_spark_route(prefer_spark, prepare_response) {
                 let spark_address = sdk
@@ -389,6 +389,7 @@ async fn pre_link_conversion_children(
                 conversion_response.sent_payment_id.clone(),
                 PaymentMetadata {
                     parent_payment_id: Some(conversion_response.received_payment_id.clone()),
+                    group_id: Some(conversion_response.received_payment_id.clone()),
                     ..Default::default()
                 },
             )
@@ -483,6 +484,7 @@ async fn complete_conversion_and_send(
             conversion_response.sent_payment_id.clone(),
             PaymentMetadata {
                 parent_payment_id: Some(response.payment.id.clone()),
+                group_id: Some(response.payment.id.clone()),
                 ..Default::default()
             },
         )
@@ -492,6 +494,7 @@ async fn complete_conversion_and_send(
             conversion_response.received_payment_id.clone(),
             PaymentMetadata {
                 parent_payment_id: Some(response.payment.id.clone()),
+                group_id: Some(response.payment.id.clone()),
                 ..Default::default()
             },
         )
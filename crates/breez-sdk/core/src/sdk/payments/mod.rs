@@ -1,9 +1,13 @@
 use spark_wallet::LightningReceivePayment;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 use crate::{
-    ClaimHtlcPaymentRequest, ClaimHtlcPaymentResponse, FetchConversionLimitsRequest,
-    FetchConversionLimitsResponse, GetPaymentRequest, GetPaymentResponse, WaitForPaymentIdentifier,
+    ClaimHtlcPaymentRequest, ClaimHtlcPaymentResponse, CreateHtlcReceiveRequest,
+    CreateHtlcReceiveResponse, FetchConversionLimitsRequest, FetchConversionLimitsResponse,
+    GetHtlcReceiveRequest, GetHtlcReceiveResponse, GetPaymentFiatValueRequest,
+    GetPaymentFiatValueResponse, GetPaymentRequest, GetPaymentResponse, PaySplitRequest,
+    PaySplitResponse, PaymentMethod, TravelRuleTransfer, WaitForPaymentIdentifier,
+    WaitForPaymentRequest, WaitForPaymentResponse,
     error::SdkError,
     models::{
         BuildUnsignedTransferPackageRequest, ListPaymentsRequest, ListPaymentsResponse, Payment,
@@ -12,10 +16,11 @@ use crate::{
         ReceivePaymentRequest, ReceivePaymentResponse, SendPaymentRequest, SendPaymentResponse,
         UnsignedTransferPackage,
     },
-    utils::payments::get_payment_with_conversion_details,
+    persist::{ObjectCacheRepository, PaymentCursor, StorageListPaymentsRequest},
+    utils::payments::{complete_lnurl_success_action, get_payment_with_conversion_details},
 };
 
-use super::BreezSdk;
+use super::{BreezSdk, queued_sends, withdrawal_allowlist};
 
 pub(in crate::sdk) mod client_signing;
 pub(in crate::sdk) mod conversion;
@@ -23,6 +28,7 @@ mod polling;
 pub(in crate::sdk) mod prepare;
 mod receive;
 pub(in crate::sdk) mod send;
+mod split;
 pub(in crate::sdk) mod validation;
 
 #[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
@@ -32,16 +38,39 @@ impl BreezSdk {
         &self,
         request: ReceivePaymentRequest,
     ) -> Result<ReceivePaymentResponse, SdkError> {
-        receive::receive_payment(self, request).await
+        receive::receive_payment(self, request)
+            .await
+            .map_err(|e| self.service_status.annotate(e))
     }
 
     pub async fn claim_htlc_payment(
         &self,
         request: ClaimHtlcPaymentRequest,
     ) -> Result<ClaimHtlcPaymentResponse, SdkError> {
+        self.ensure_not_watch_only("claim_htlc_payment")?;
         receive::claim_htlc_payment(self, request).await
     }
 
+    /// Registers a receive for a Spark HTLC locked to an externally supplied
+    /// payment hash, e.g. for atomic swaps or escrow. Returns the Spark
+    /// address the sender should create the HTLC to.
+    ///
+    /// Once the HTLC has arrived, it is claimed with `claim_htlc_payment`.
+    pub async fn create_htlc_receive(
+        &self,
+        request: CreateHtlcReceiveRequest,
+    ) -> Result<CreateHtlcReceiveResponse, SdkError> {
+        receive::create_htlc_receive(self, request).await
+    }
+
+    /// Returns the current state of an HTLC receive, queried from Spark.
+    pub async fn get_htlc_receive(
+        &self,
+        request: GetHtlcReceiveRequest,
+    ) -> Result<GetHtlcReceiveResponse, SdkError> {
+        receive::get_htlc_receive(self, request).await
+    }
+
     pub async fn prepare_send_payment(
         &self,
         request: PrepareSendPaymentRequest,
@@ -71,7 +100,9 @@ impl BreezSdk {
             )
             .await;
         }
-        prepare::prepare(self, request).await
+        prepare::prepare(self, request)
+            .await
+            .map_err(|e| self.service_status.annotate(e))
     }
 
     #[instrument(
@@ -84,11 +115,57 @@ impl BreezSdk {
         &self,
         request: SendPaymentRequest,
     ) -> Result<SendPaymentResponse, SdkError> {
+        self.ensure_not_watch_only("send_payment")?;
         self.maybe_ensure_spark_private_mode_initialized().await?;
+        let mut request = request;
+        let queue_if_offline = request.queue_if_offline.unwrap_or(false);
+        if queue_if_offline {
+            queued_sends::ensure_queueable(&mut request)?;
+        }
         if let Some(key) = request.idempotency_key.as_deref() {
             tracing::Span::current().record("payment_id", key);
         }
-        Box::pin(send::orchestrate_send(self, request, false, None)).await
+        let approval_request =
+            withdrawal_allowlist::send_payment_approval_request(&request.prepare_response);
+        let compliance_metadata = request.compliance_metadata.clone();
+        if let Some(metadata) = &compliance_metadata {
+            self.submit_travel_rule_transfer(TravelRuleTransfer {
+                destination: approval_request.destination.clone(),
+                amount: approval_request.amount,
+                token_identifier: approval_request.token_identifier.clone(),
+                idempotency_key: request.idempotency_key.clone(),
+                metadata: metadata.clone(),
+            })
+            .await?;
+        }
+        withdrawal_allowlist::ensure_destination_allowed(self, approval_request).await?;
+        let queued_request = queue_if_offline.then(|| request.clone());
+        let result = Box::pin(send::orchestrate_send(self, request, false, None)).await;
+        if let (Err(e), Some(request)) = (&result, queued_request)
+            && self.is_offline_error(e).await
+        {
+            warn!("Send failed while offline, queuing it: {e}");
+            let queued_payment_id = self.queue_send(request).await?;
+            return Err(SdkError::PaymentQueued { queued_payment_id });
+        }
+        let response = result.map_err(|e| self.service_status.annotate(e))?;
+        if let Some(metadata) = compliance_metadata {
+            ObjectCacheRepository::new(self.storage.clone())
+                .save_compliance_metadata(&response.payment.id, &metadata)
+                .await?;
+        }
+        Ok(response)
+    }
+
+    /// Pays several invoices that share a total budget, e.g. to split a bill.
+    ///
+    /// All invoices are prepared first. If their amounts plus the estimated fees
+    /// exceed `total_budget_sats`, nothing is paid and an error is returned.
+    /// Otherwise the invoices are paid sequentially, stopping at the first failure,
+    /// or in parallel, and the outcome of each invoice is returned.
+    pub async fn pay_split(&self, request: PaySplitRequest) -> Result<PaySplitResponse, SdkError> {
+        self.ensure_not_watch_only("pay_split")?;
+        Box::pin(split::pay_split(self, request)).await
     }
 
     pub async fn build_unsigned_transfer_package(
@@ -140,6 +217,7 @@ impl BreezSdk {
     /// explicit entry point for driving the pass; when background tasks are
     /// enabled, it can be called to force an immediate refund pass.
     pub async fn refund_pending_conversions(&self) -> Result<(), SdkError> {
+        self.ensure_not_watch_only("refund_pending_conversions")?;
         self.token_converter
             .refund_pending()
             .await
@@ -150,10 +228,11 @@ impl BreezSdk {
     ///
     /// This method provides direct access to the payment history stored in the database.
     /// It returns payments in reverse chronological order (newest first).
+    /// When `grouped` is set, it also aggregates them by group.
     ///
     /// # Arguments
     ///
-    /// * `request` - Contains pagination parameters (offset and limit)
+    /// * `request` - Contains pagination parameters (a cursor, or offset and limit)
     ///
     /// # Returns
     ///
@@ -164,9 +243,30 @@ impl BreezSdk {
         request: ListPaymentsRequest,
     ) -> Result<ListPaymentsResponse, SdkError> {
         use crate::utils::conversions::extract_conversion_info;
-        use crate::utils::payments::build_conversions;
+        use crate::utils::payments::{build_conversions, build_payment_groups};
 
-        let mut payments = self.storage.list_payments(request.into()).await?;
+        let after = request
+            .cursor
+            .as_deref()
+            .map(PaymentCursor::decode)
+            .transpose()?;
+        let limit = request.limit;
+        let grouped = request.grouped.unwrap_or(false);
+        let mut payments = self
+            .storage
+            .list_payments(StorageListPaymentsRequest {
+                after,
+                ..request.into()
+            })
+            .await?;
+        let next_cursor = match (limit, payments.last()) {
+            (Some(limit), Some(last))
+                if u32::try_from(payments.len()).is_ok_and(|len| len >= limit) =>
+            {
+                Some(PaymentCursor::from_payment(last).encode())
+            }
+            _ => None,
+        };
 
         // Query child payments for payments that have conversion_details set (AMM)
         let parent_ids: Vec<String> = payments
@@ -182,6 +282,8 @@ impl BreezSdk {
         };
 
         for payment in &mut payments {
+            complete_lnurl_success_action(&self.storage, payment).await;
+
             let has_conversion_details = payment.conversion_details.is_some();
             let has_crosschain_info = extract_conversion_info(payment.details.clone())
                 .is_some_and(|info| !matches!(info, crate::ConversionInfo::Amm { .. }));
@@ -214,22 +316,139 @@ impl BreezSdk {
             }
         }
 
-        Ok(ListPaymentsResponse { payments })
+        let groups = if grouped {
+            // Fetch the payments of the groups that fall outside the page
+            let mut group_ids: Vec<String> =
+                payments.iter().filter_map(|p| p.group_id.clone()).collect();
+            group_ids.sort();
+            group_ids.dedup();
+            let members = if group_ids.is_empty() {
+                Vec::new()
+            } else {
+                self.storage
+                    .list_payments(StorageListPaymentsRequest {
+                        group_id_filter: Some(group_ids),
+                        ..Default::default()
+                    })
+                    .await?
+            };
+            Some(build_payment_groups(&payments, members))
+        } else {
+            None
+        };
+
+        Ok(ListPaymentsResponse {
+            payments,
+            next_cursor,
+            groups,
+        })
     }
 
     pub async fn get_payment(
         &self,
         request: GetPaymentRequest,
     ) -> Result<GetPaymentResponse, SdkError> {
-        let payment =
+        let mut payment =
             get_payment_with_conversion_details(request.payment_id, self.storage.clone()).await?;
+        complete_lnurl_success_action(&self.storage, &mut payment).await;
+        let compliance_metadata = ObjectCacheRepository::new(self.storage.clone())
+            .fetch_compliance_metadata(&payment.id)
+            .await?;
+
+        Ok(GetPaymentResponse {
+            payment,
+            compliance_metadata,
+        })
+    }
+
+    /// Waits for a payment to reach a terminal status, checking local storage
+    /// and the operators. Use it to reconcile a payment whose outcome is
+    /// unknown, e.g. after a network error while sending.
+    ///
+    /// Fails if the payment is still pending or unknown when the timeout
+    /// elapses.
+    pub async fn wait_for_payment(
+        &self,
+        request: WaitForPaymentRequest,
+    ) -> Result<WaitForPaymentResponse, SdkError> {
+        polling::wait_for_payment(self, request).await
+    }
+
+    /// Returns the fiat value of a Bitcoin payment at the time it was made,
+    /// using the historical BTC rate of the currency. The rate is looked up
+    /// on first use and stored with the payment.
+    pub async fn get_payment_fiat_value(
+        &self,
+        request: GetPaymentFiatValueRequest,
+    ) -> Result<GetPaymentFiatValueResponse, SdkError> {
+        let payment = self.storage.get_payment_by_id(request.payment_id).await?;
+        if payment.method == PaymentMethod::Token {
+            return Err(SdkError::InvalidInput(
+                "Fiat values are only available for Bitcoin payments".to_string(),
+            ));
+        }
+
+        let rate = self
+            .fiat_rate_at_payment(&payment, &request.currency.to_uppercase())
+            .await?;
 
-        Ok(GetPaymentResponse { payment })
+        #[allow(clippy::cast_precision_loss)]
+        let fiat_value_at_payment = payment.amount as f64 * rate / 100_000_000f64;
+        Ok(GetPaymentFiatValueResponse {
+            fiat_value_at_payment,
+            rate,
+        })
     }
 }
 
 // Private payment methods
 impl BreezSdk {
+    /// Returns the BTC rate of `currency` at the time of the payment. The
+    /// rate is looked up on first use and stored with the payment.
+    pub(super) async fn fiat_rate_at_payment(
+        &self,
+        payment: &Payment,
+        currency: &str,
+    ) -> Result<f64, SdkError> {
+        let object_repository = ObjectCacheRepository::new(self.storage.clone());
+        if let Some(rate) = object_repository
+            .fetch_fiat_rate_at_payment(&payment.id, currency)
+            .await?
+        {
+            return Ok(rate);
+        }
+        let historical_rates = self
+            .historical_rates
+            .as_ref()
+            .ok_or_else(|| SdkError::InvalidInput("Historical rates are disabled".to_string()))?;
+        let rate = historical_rates
+            .fetch_historical_rate(currency, payment.timestamp)
+            .await?
+            .ok_or_else(|| {
+                SdkError::InvalidInput(format!("No historical rate available for {currency}"))
+            })?;
+        object_repository
+            .save_fiat_rate_at_payment(&payment.id, currency, rate)
+            .await?;
+        Ok(rate)
+    }
+
+    /// Passes the compliance metadata of an outgoing payment to the travel-rule
+    /// provider. Without a provider the metadata is only persisted.
+    async fn submit_travel_rule_transfer(
+        &self,
+        transfer: TravelRuleTransfer,
+    ) -> Result<(), SdkError> {
+        let Some(provider) = &self.travel_rule_provider else {
+            return Ok(());
+        };
+        provider.submit(transfer).await.map_err(|e| {
+            SdkError::Generic(format!(
+                "Travel rule provider failed to accept the payment: {e}"
+            ))
+        })
+    }
+
     pub(crate) async fn receive_bolt11_invoice(
         &self,
         description: String,
@@ -2,21 +2,114 @@ use platform_utils::time::Duration;
 use tracing::debug;
 
 use crate::{
-    PaymentStatus, WaitForPaymentIdentifier,
+    PaymentDetails, PaymentIdentifier, PaymentStatus, WaitForPaymentIdentifier,
+    WaitForPaymentRequest, WaitForPaymentResponse,
     error::SdkError,
+    events::SdkEvent,
     models::Payment,
+    persist::{StorageListPaymentsRequest, StoragePaymentDetailsFilter},
     utils::{
         payments::{fetch_and_process_payment, insert_payment_with_metadata},
         polling::{PollSchedule, poll_until},
     },
 };
 
-use super::super::{BreezSdk, helpers::maybe_get_payment_from_storage};
+use super::super::{BreezSdk, SyncType, helpers::maybe_get_payment_from_storage};
 
 // Polling cadence for wait_for_incoming_payment.
 const WAIT_FOR_INCOMING_PAYMENT_INITIAL_DELAY_MS: u64 = 500;
 const WAIT_FOR_INCOMING_PAYMENT_MAX_DELAY_MS: u64 = 2000;
 
+// Polling cadence for wait_for_payment.
+const WAIT_FOR_PAYMENT_INITIAL_DELAY_MS: u64 = 1000;
+const WAIT_FOR_PAYMENT_MAX_DELAY_MS: u64 = 5000;
+const WAIT_FOR_PAYMENT_DEFAULT_TIMEOUT_SECS: u32 = 60;
+
+pub(super) async fn wait_for_payment(
+    sdk: &BreezSdk,
+    request: WaitForPaymentRequest,
+) -> Result<WaitForPaymentResponse, SdkError> {
+    let schedule = PollSchedule {
+        initial_delay: Duration::from_millis(WAIT_FOR_PAYMENT_INITIAL_DELAY_MS),
+        max_delay: Duration::from_millis(WAIT_FOR_PAYMENT_MAX_DELAY_MS),
+        timeout: Duration::from_secs(
+            request
+                .timeout_secs
+                .unwrap_or(WAIT_FOR_PAYMENT_DEFAULT_TIMEOUT_SECS)
+                .into(),
+        ),
+    };
+    let shutdown = Some(sdk.shutdown_sender.subscribe());
+    let payment = poll_until(schedule, shutdown, || {
+        probe_payment(sdk, &request.identifier)
+    })
+    .await?;
+    Ok(WaitForPaymentResponse { payment })
+}
+
+/// Returns the payment once it is terminal, either from storage or from the
+/// operators.
+async fn probe_payment(
+    sdk: &BreezSdk,
+    identifier: &PaymentIdentifier,
+) -> Result<Option<Payment>, SdkError> {
+    let stored = find_stored_payment(sdk, identifier).await?;
+    let payment_id = match (&stored, identifier) {
+        (Some(payment), _) if payment.status != PaymentStatus::Pending => return Ok(stored),
+        (Some(payment), _) => payment.id.clone(),
+        (None, PaymentIdentifier::PaymentId { payment_id }) => payment_id.clone(),
+        (None, _) => {
+            // A payment unknown locally can only be found by syncing
+            sdk.sync_coordinator
+                .trigger_sync_no_wait(SyncType::Wallet, false)
+                .await;
+            return Ok(None);
+        }
+    };
+
+    let Some(payment) =
+        fetch_and_process_payment(&sdk.spark_wallet, sdk.storage.clone(), &payment_id, false)
+            .await?
+    else {
+        return Ok(None);
+    };
+    finalize_payment(sdk, payment.clone()).await;
+    Ok(Some(payment))
+}
+
+async fn find_stored_payment(
+    sdk: &BreezSdk,
+    identifier: &PaymentIdentifier,
+) -> Result<Option<Payment>, SdkError> {
+    match identifier {
+        PaymentIdentifier::PaymentId { payment_id } => {
+            Ok(sdk.storage.get_payment_by_id(payment_id.clone()).await.ok())
+        }
+        PaymentIdentifier::Invoice { invoice } => {
+            Ok(sdk.storage.get_payment_by_invoice(invoice.clone()).await?)
+        }
+        PaymentIdentifier::PaymentHash { payment_hash } => {
+            let payments = sdk
+                .storage
+                .list_payments(StorageListPaymentsRequest {
+                    payment_details_filter: Some(vec![StoragePaymentDetailsFilter::Lightning {
+                        htlc_status: None,
+                        conversion_filter: None,
+                    }]),
+                    ..Default::default()
+                })
+                .await?;
+            Ok(payments.into_iter().find(|payment| {
+                matches!(
+                    &payment.details,
+                    Some(PaymentDetails::Lightning { htlc_details, .. })
+                        if htlc_details.payment_hash.eq_ignore_ascii_case(payment_hash)
+                )
+            }))
+        }
+    }
+}
+
 pub(super) async fn wait_for_incoming_payment(
     sdk: &BreezSdk,
     identifier: WaitForPaymentIdentifier,
@@ -61,14 +154,19 @@ pub(super) async fn finalize_payment(sdk: &BreezSdk, mut payment: Payment) -> bo
     // No-op for non-Lightning-receive payments; for LNURL receives
     // this pulls the LNURL metadata into the payment record.
     sdk.sync_single_lnurl_metadata(&mut payment).await;
+    let lightning_address_event = SdkEvent::lightning_address_payment(&payment);
 
-    insert_payment_with_metadata(
+    let emitted = insert_payment_with_metadata(
         sdk.spark_wallet.clone(),
         sdk.storage.clone(),
         sdk.event_emitter.clone(),
         payment,
     )
-    .await
+    .await;
+    if emitted && let Some(event) = lightning_address_event {
+        sdk.event_emitter.emit(&event).await;
+    }
+    emitted
 }
 
 /// Polls an inbound Lightning payment by SSP id. The receive object
@@ -0,0 +1,175 @@
+use crate::{Bip21Details, InputType, error::SdkError, models::PrepareSendPaymentRequest};
+
+/// Selects the payment method to use from the ones contained in a BIP21 URI:
+/// a BOLT11 invoice, then a Spark address or invoice, then the on-chain
+/// address. Spark comes first when `prefer_spark` is set.
+pub(super) fn select_payment_method(
+    details: &Bip21Details,
+    prefer_spark: bool,
+) -> Result<InputType, SdkError> {
+    let rank = |method: &InputType| match method {
+        InputType::SparkAddress(_) | InputType::SparkInvoice(_) if prefer_spark => Some(0),
+        InputType::Bolt11Invoice(_) => Some(1),
+        InputType::SparkAddress(_) | InputType::SparkInvoice(_) => Some(2),
+        InputType::BitcoinAddress(_) => Some(3),
+        _ => None,
+    };
+    details
+        .payment_methods
+        .iter()
+        .filter_map(|method| rank(method).map(|rank| (rank, method)))
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, method)| method.clone())
+        .ok_or_else(|| {
+            SdkError::InvalidInput("BIP21 URI contains no supported payment method".to_string())
+        })
+}
+
+/// Uses the BIP21 amount as the amount to send. An amount set on the request
+/// must match it.
+///
+/// The amount is left to the selected method when it is an invoice with a
+/// fixed amount, or when paying from a token balance.
+pub(super) fn apply_amount(
+    details: &Bip21Details,
+    method: &InputType,
+    request: &mut PrepareSendPaymentRequest,
+) -> Result<(), SdkError> {
+    let Some(amount_sat) = details.amount_sat else {
+        return Ok(());
+    };
+    let has_fixed_amount = match method {
+        InputType::Bolt11Invoice(invoice) => invoice.amount_msat.is_some(),
+        InputType::SparkInvoice(invoice) => invoice.amount.is_some(),
+        _ => false,
+    };
+    if has_fixed_amount || request.token_identifier.is_some() {
+        return Ok(());
+    }
+
+    let amount = u128::from(amount_sat);
+    match request.amount {
+        None => request.amount = Some(amount),
+        Some(requested) if requested == amount => {}
+        Some(_) => {
+            return Err(SdkError::InvalidInput(format!(
+                "Amount does not match the BIP21 amount of {amount_sat} sats"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::*;
+    use super::{apply_amount, select_payment_method};
+    use crate::{
+        Bip21Details, BitcoinAddressDetails, BitcoinNetwork, InputType, PaymentRequestSource,
+        SparkAddressDetails, error::SdkError,
+    };
+    use macros::test_all;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn bitcoin_address() -> InputType {
+        InputType::BitcoinAddress(BitcoinAddressDetails {
+            address: "bcrt1qtest".to_string(),
+            network: BitcoinNetwork::Regtest,
+            source: PaymentRequestSource::default(),
+        })
+    }
+
+    fn spark_address() -> InputType {
+        InputType::SparkAddress(SparkAddressDetails {
+            address: "sparkrt1test".to_string(),
+            identity_public_key: "test_identity_key".to_string(),
+            network: BitcoinNetwork::Regtest,
+            source: PaymentRequestSource::default(),
+        })
+    }
+
+    fn bip21(amount_sat: Option<u64>, payment_methods: Vec<InputType>) -> Bip21Details {
+        Bip21Details {
+            amount_sat,
+            payment_methods,
+            ..Default::default()
+        }
+    }
+
+    // ---- Payment method selection ----
+
+    #[test_all]
+    fn test_select_prefers_bolt11() {
+        let details = bip21(
+            None,
+            vec![
+                bitcoin_address(),
+                spark_address(),
+                InputType::Bolt11Invoice(create_test_bolt11_invoice()),
+            ],
+        );
+        let method = select_payment_method(&details, false).unwrap();
+        assert!(matches!(method, InputType::Bolt11Invoice(_)));
+    }
+
+    #[test_all]
+    fn test_select_prefers_spark_when_configured() {
+        let details = bip21(
+            None,
+            vec![
+                InputType::Bolt11Invoice(create_test_bolt11_invoice()),
+                spark_address(),
+            ],
+        );
+        let method = select_payment_method(&details, true).unwrap();
+        assert!(matches!(method, InputType::SparkAddress(_)));
+    }
+
+    #[test_all]
+    fn test_select_falls_back_to_bitcoin_address() {
+        let details = bip21(None, vec![bitcoin_address()]);
+        let method = select_payment_method(&details, false).unwrap();
+        assert!(matches!(method, InputType::BitcoinAddress(_)));
+    }
+
+    #[test_all]
+    fn test_select_without_supported_method() {
+        let details = bip21(
+            None,
+            vec![InputType::Url("https://example.com".to_string())],
+        );
+        let result = select_payment_method(&details, false);
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+    }
+
+    // ---- Amount ----
+
+    #[test_all]
+    fn test_apply_amount_sets_missing_amount() {
+        let details = bip21(Some(1000), vec![bitcoin_address()]);
+        let mut request = create_test_request();
+        apply_amount(&details, &bitcoin_address(), &mut request).unwrap();
+        assert_eq!(request.amount, Some(1000));
+    }
+
+    #[test_all]
+    fn test_apply_amount_rejects_mismatch() {
+        let details = bip21(Some(1000), vec![bitcoin_address()]);
+        let mut request = create_bitcoin_amount_request(2000);
+        let result = apply_amount(&details, &bitcoin_address(), &mut request);
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+    }
+
+    #[test_all]
+    fn test_apply_amount_keeps_invoice_amount() {
+        let mut invoice = create_test_bolt11_invoice();
+        invoice.amount_msat = Some(1_000_000);
+        let method = InputType::Bolt11Invoice(invoice);
+        let details = bip21(Some(1000), vec![method.clone()]);
+        let mut request = create_test_request();
+        apply_amount(&details, &method, &mut request).unwrap();
+        assert_eq!(request.amount, None);
+    }
+}
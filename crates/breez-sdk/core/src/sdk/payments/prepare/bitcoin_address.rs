@@ -1,6 +1,9 @@
+use tracing::warn;
+
 use crate::{
-    BitcoinAddressDetails, ConversionOptions, ConversionType, FeePolicy, SendOnchainFeeQuote,
-    SendPaymentMethod,
+    BitcoinAddressDetails, ConversionOptions, ConversionType, FeeBreakdown, FeePolicy,
+    OnchainFeeBreakdown, SendOnchainFeeQuote, SendOnchainSpeedFeeQuote, SendPaymentMethod,
+    chain::RecommendedFees,
     error::SdkError,
     models::{PrepareSendPaymentRequest, PrepareSendPaymentResponse},
     sdk::BreezSdk,
@@ -153,6 +156,7 @@ async fn prepare_sats_denominated(
     )
     .await?;
 
+    let fee_breakdown = onchain_fee_breakdown(sdk, &fee_quote).await;
     Ok(PrepareSendPaymentResponse {
         payment_method: SendPaymentMethod::BitcoinAddress {
             address: withdrawal_address.clone(),
@@ -162,6 +166,8 @@ async fn prepare_sats_denominated(
         token_identifier,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown,
     })
 }
 
@@ -224,6 +230,7 @@ async fn prepare_token_denominated(
         fee_quote.speed_slow.total_fee_sat(),
     )?;
 
+    let fee_breakdown = onchain_fee_breakdown(sdk, &fee_quote).await;
     Ok(PrepareSendPaymentResponse {
         payment_method: SendPaymentMethod::BitcoinAddress {
             address: withdrawal_address.clone(),
@@ -234,9 +241,48 @@ async fn prepare_token_denominated(
         token_identifier: None,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown,
     })
 }
 
+/// Breaks down the fee quote using the recommended network fee rates. The
+/// breakdown is omitted if the fee rates can't be fetched.
+async fn onchain_fee_breakdown(
+    sdk: &BreezSdk,
+    fee_quote: &SendOnchainFeeQuote,
+) -> Option<FeeBreakdown> {
+    let fees = sdk
+        .chain_service
+        .recommended_fees()
+        .await
+        .inspect_err(|e| warn!("Failed to get recommended fees for the fee breakdown: {e}"))
+        .ok()?;
+    Some(fee_breakdown(fee_quote, &fees))
+}
+
+fn fee_breakdown(fee_quote: &SendOnchainFeeQuote, fees: &RecommendedFees) -> FeeBreakdown {
+    FeeBreakdown::BitcoinAddress {
+        speed_fast: speed_fee_breakdown(&fee_quote.speed_fast, fees.fastest_fee),
+        speed_medium: speed_fee_breakdown(&fee_quote.speed_medium, fees.half_hour_fee),
+        speed_slow: speed_fee_breakdown(&fee_quote.speed_slow, fees.hour_fee),
+    }
+}
+
+fn speed_fee_breakdown(
+    quote: &SendOnchainSpeedFeeQuote,
+    fee_rate_sat_per_vbyte: u64,
+) -> OnchainFeeBreakdown {
+    OnchainFeeBreakdown {
+        ssp_fee_sat: quote.user_fee_sat,
+        l1_broadcast_fee_sat: quote.l1_broadcast_fee_sat,
+        fee_rate_sat_per_vbyte,
+        estimated_vbytes: quote
+            .l1_broadcast_fee_sat
+            .div_ceil(fee_rate_sat_per_vbyte.max(1)),
+    }
+}
+
 /// Validates a Bitcoin send amount against the address dust limit.
 ///
 /// Always rejects amounts below the dust limit. For `FeesIncluded`, also rejects
@@ -269,8 +315,11 @@ fn validate_dust(
 #[cfg(test)]
 mod tests {
     use super::super::test_helpers::*;
-    use super::{validate_dust, validate_request};
-    use crate::{ConversionOptions, ConversionType, FeePolicy, error::SdkError};
+    use super::{fee_breakdown, validate_dust, validate_request};
+    use crate::{
+        ConversionOptions, ConversionType, FeeBreakdown, FeePolicy, SendOnchainFeeQuote,
+        SendOnchainSpeedFeeQuote, chain::RecommendedFees, error::SdkError,
+    };
     use macros::test_all;
 
     #[cfg(feature = "browser-tests")]
@@ -449,4 +498,43 @@ mod tests {
             panic!("Expected InvalidInput error");
         }
     }
+
+    // ============ fee_breakdown ============
+
+    #[test_all]
+    fn test_fee_breakdown_uses_rate_per_speed() {
+        let speed = |user_fee_sat, l1_broadcast_fee_sat| SendOnchainSpeedFeeQuote {
+            user_fee_sat,
+            l1_broadcast_fee_sat,
+        };
+        let fee_quote = SendOnchainFeeQuote {
+            id: "quote".to_string(),
+            expires_at: 0,
+            speed_fast: speed(300, 2000),
+            speed_medium: speed(200, 1000),
+            speed_slow: speed(100, 0),
+        };
+        let fees = RecommendedFees {
+            fastest_fee: 20,
+            half_hour_fee: 10,
+            hour_fee: 0,
+            economy_fee: 0,
+            minimum_fee: 0,
+        };
+
+        let FeeBreakdown::BitcoinAddress {
+            speed_fast,
+            speed_medium,
+            speed_slow,
+        } = fee_breakdown(&fee_quote, &fees)
+        else {
+            panic!("Expected an on-chain breakdown");
+        };
+        assert_eq!(speed_fast.ssp_fee_sat, 300);
+        assert_eq!(speed_fast.fee_rate_sat_per_vbyte, 20);
+        assert_eq!(speed_fast.estimated_vbytes, 100);
+        assert_eq!(speed_medium.estimated_vbytes, 100);
+        // A zero fee rate doesn't divide by zero
+        assert_eq!(speed_slow.estimated_vbytes, 0);
+    }
 }
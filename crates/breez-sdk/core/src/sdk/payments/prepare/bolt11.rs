@@ -1,7 +1,8 @@
 use spark_wallet::SparkAddress;
 
 use crate::{
-    Bolt11InvoiceDetails, ConversionOptions, ConversionType, FeePolicy, SendPaymentMethod,
+    Bolt11InvoiceDetails, ConversionOptions, ConversionType, FeeBreakdown, FeePolicy,
+    SendPaymentMethod,
     error::SdkError,
     models::{PrepareSendPaymentRequest, PrepareSendPaymentResponse},
     sdk::BreezSdk,
@@ -175,6 +176,10 @@ async fn prepare_sats_denominated(
         token_identifier,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown: Some(FeeBreakdown::Lightning {
+            ssp_fee_sats: lightning_fee_sats,
+        }),
     };
 
     Ok(response)
@@ -248,6 +253,10 @@ async fn prepare_token_denominated(
         token_identifier: None,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown: Some(FeeBreakdown::Lightning {
+            ssp_fee_sats: lightning_fee_sats,
+        }),
     })
 }
 
@@ -268,16 +268,17 @@ pub(crate) async fn prepare(
     })?;
     validate_address_family_against_route(address_family, route)?;
 
+    let config = sdk.config.get();
     let provider_slippage_bps = resolve_slippage_bps(
         max_slippage_bps,
-        sdk.config
+        config
             .cross_chain_config
             .as_ref()
             .and_then(|c| c.default_slippage_bps),
     )?;
     let overpay_bps = resolve_target_overpay_bps(
         target_overpay_bps,
-        sdk.config
+        config
             .cross_chain_config
             .as_ref()
             .and_then(|c| c.default_target_overpay_bps),
@@ -778,6 +779,8 @@ fn build_response(
         token_identifier: response_token_identifier,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown: None,
     }
 }
 
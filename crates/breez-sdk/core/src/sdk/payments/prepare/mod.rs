@@ -1,3 +1,4 @@
+mod bip21;
 mod bitcoin_address;
 mod bolt11;
 pub(in crate::sdk::payments) mod cross_chain;
@@ -13,9 +14,9 @@ use crate::{
 
 pub(super) async fn prepare(
     sdk: &BreezSdk,
-    request: PrepareSendPaymentRequest,
+    mut request: PrepareSendPaymentRequest,
 ) -> Result<PrepareSendPaymentResponse, SdkError> {
-    let input = match &request.payment_request {
+    let mut input = match &request.payment_request {
         PaymentRequest::Input { input } => input.clone(),
         PaymentRequest::CrossChain { .. } => {
             return Err(SdkError::Generic(
@@ -25,12 +26,25 @@ pub(super) async fn prepare(
             ));
         }
     };
-    let parsed_input = sdk.parse(&input).await?;
+    let (parsed_input, bip21_details) = match sdk.parse(&input).await? {
+        InputType::Bip21(details) => {
+            let method = bip21::select_payment_method(
+                &details,
+                sdk.config.get().prefer_spark_over_lightning,
+            )?;
+            bip21::apply_amount(&details, &method, &mut request)?;
+            if let InputType::Bolt11Invoice(invoice) = &method {
+                input.clone_from(&invoice.invoice.bolt11);
+            }
+            (method, Some(details))
+        }
+        parsed_input => (parsed_input, None),
+    };
 
     let fee_policy = request.fee_policy.unwrap_or_default();
     let token_identifier = request.token_identifier.clone();
 
-    match &parsed_input {
+    let mut response = match &parsed_input {
         InputType::SparkAddress(details) => {
             spark_address::prepare(sdk, &request, details, fee_policy, token_identifier).await
         }
@@ -48,10 +62,15 @@ pub(super) async fn prepare(
              routes, then PaymentRequest::CrossChain { address, route }."
                 .to_string(),
         )),
+        InputType::Bolt12Offer(_) | InputType::Bolt12Invoice(_) => Err(SdkError::InvalidInput(
+            "BOLT12 is not supported. Spark can only pay Lightning BOLT11 invoices".to_string(),
+        )),
         _ => Err(SdkError::InvalidInput(
             "Unsupported payment method".to_string(),
         )),
-    }
+    }?;
+    response.bip21_details = bip21_details;
+    Ok(response)
 }
 
 #[cfg(test)]
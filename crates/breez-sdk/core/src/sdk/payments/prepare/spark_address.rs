@@ -71,6 +71,8 @@ pub(super) async fn prepare(
         token_identifier: response_token_identifier,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown: None,
     };
 
     Ok(response)
@@ -13,6 +13,7 @@ fn validate_request(
     spark_invoice_details: &SparkInvoiceDetails,
     request: &PrepareSendPaymentRequest,
     identity_public_key: &str,
+    now: SystemTime,
 ) -> Result<(), SdkError> {
     validation::validate_amount(request.amount)?;
     validation::validate_fee_policy_for_conversion(
@@ -73,7 +74,7 @@ fn validate_request(
 
     // Validate expiry time
     if let Some(expiry_time) = spark_invoice_details.expiry_time {
-        let current_time = SystemTime::now()
+        let current_time = now
             .duration_since(UNIX_EPOCH)
             .map_err(|_| SdkError::Generic("Failed to get current time".to_string()))?;
         if current_time > Duration::from_secs(expiry_time) {
@@ -122,6 +123,7 @@ pub(super) async fn prepare(
         details,
         request,
         &sdk.spark_wallet.get_identity_public_key().to_string(),
+        sdk.clock.now(),
     )?;
 
     // Use request's token_identifier if provided, otherwise fall back to invoice's
@@ -156,6 +158,8 @@ pub(super) async fn prepare(
         token_identifier: response_token_identifier,
         conversion_estimate,
         fee_policy,
+        bip21_details: None,
+        fee_breakdown: None,
     };
 
     Ok(response)
@@ -182,7 +186,7 @@ mod tests {
         let request = create_token_amount_request(1000, "token123");
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when token identifiers match"
@@ -197,7 +201,7 @@ mod tests {
         let request = create_token_amount_request(1000, "token456");
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when token identifiers don't match"
@@ -221,7 +225,7 @@ mod tests {
         let request = create_test_request(); // No pay_amount - defers to invoice
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when pay_amount is None for token invoice (defers to invoice)"
@@ -235,7 +239,7 @@ mod tests {
         let request = create_token_amount_request(1000, "token123");
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when token identifier is provided for non-token invoice"
@@ -258,7 +262,7 @@ mod tests {
         let request = create_fees_included_request(1000);
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when FeesIncluded is used for amountless Spark invoice"
@@ -272,7 +276,7 @@ mod tests {
         let request = create_fees_included_request(1000);
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when FeesIncluded is used for Spark invoice with fixed amount"
@@ -295,7 +299,7 @@ mod tests {
         request.token_identifier = Some("token123".to_string());
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when FeesIncluded is used for token Spark invoice"
@@ -316,7 +320,7 @@ mod tests {
 
         let request = create_test_request();
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(result.is_err(), "Should fail when invoice has expired");
         if let Err(SdkError::InvalidInput(msg)) = result {
             assert!(
@@ -342,7 +346,7 @@ mod tests {
 
         let request = create_test_request();
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(result.is_ok(), "Should succeed when invoice hasn't expired");
     }
 
@@ -356,7 +360,7 @@ mod tests {
 
         let request = create_test_request();
         let identity_key = "sender_key123".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when sender public key matches"
@@ -370,7 +374,7 @@ mod tests {
 
         let request = create_test_request();
         let identity_key = "different_key".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when sender public key doesn't match"
@@ -395,7 +399,7 @@ mod tests {
         let request = create_bitcoin_amount_request(1000);
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(result.is_ok(), "Should succeed when amounts match");
     }
 
@@ -407,7 +411,7 @@ mod tests {
         let request = create_bitcoin_amount_request(2000);
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(result.is_err(), "Should fail when amounts don't match");
         if let Err(SdkError::InvalidInput(msg)) = result {
             assert!(
@@ -427,7 +431,7 @@ mod tests {
         let request = create_test_request(); // No amount in request
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when only invoice has amount"
@@ -441,7 +445,7 @@ mod tests {
         let request = create_test_request(); // No pay_amount
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when neither invoice nor request has amount"
@@ -465,7 +469,7 @@ mod tests {
         let request = create_test_request(); // No pay_amount
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when neither token invoice nor request has amount"
@@ -499,7 +503,7 @@ mod tests {
         let request = create_token_amount_request(1000, "token123");
 
         let identity_key = "sender_key123".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(result.is_ok(), "Should succeed when all validations pass");
     }
 
@@ -520,7 +524,7 @@ mod tests {
         });
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when conversion to Bitcoin is provided"
@@ -540,7 +544,7 @@ mod tests {
         });
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_ok(),
             "Should succeed when conversion from Bitcoin is provided"
@@ -559,7 +563,7 @@ mod tests {
         });
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when conversion from Bitcoin is provided"
@@ -581,7 +585,7 @@ mod tests {
         });
 
         let identity_key = "test_identity".to_string();
-        let result = validate_request(&invoice, &request, &identity_key);
+        let result = validate_request(&invoice, &request, &identity_key, SystemTime::now());
         assert!(
             result.is_err(),
             "Should fail when conversion to Bitcoin is provided"
@@ -3,21 +3,42 @@ use std::str::FromStr;
 use bitcoin::hashes::sha256;
 use bitcoin::secp256k1::PublicKey;
 use platform_utils::time::{Duration, SystemTime};
-use spark_wallet::{InvoiceDescription, LightningReceivePayment, Preimage};
+use spark_wallet::{LightningReceivePayment, Preimage};
 
 use crate::{
-    ClaimHtlcPaymentRequest, ClaimHtlcPaymentResponse,
+    ClaimHtlcPaymentRequest, ClaimHtlcPaymentResponse, CreateHtlcReceiveRequest,
+    CreateHtlcReceiveResponse, GetHtlcReceiveRequest, GetHtlcReceiveResponse, HtlcReceive,
+    HtlcReceiveStatus, PaymentDetails, SparkHtlcDetails, SparkHtlcStatus,
     error::SdkError,
     models::{Payment, ReceivePaymentMethod, ReceivePaymentRequest, ReceivePaymentResponse},
+    persist::{CachedHtlcReceive, ObjectCacheRepository},
+    utils::{
+        invoice_description::{bolt11_invoice_description, render_invoice_description},
+        token::{get_tokens_metadata_cached_or_query, track_token_invoice},
+    },
 };
 
-use super::super::{BreezSdk, helpers::get_deposit_address};
+use super::super::{
+    BreezSdk, amount_range_receives::validate_amount_range, helpers::get_deposit_address,
+};
 
 pub(super) async fn receive_payment(
     sdk: &BreezSdk,
     request: ReceivePaymentRequest,
 ) -> Result<ReceivePaymentResponse, SdkError> {
+    // The Spark address derives from the identity key, the rest needs signing
+    if !matches!(request.payment_method, ReceivePaymentMethod::SparkAddress) {
+        sdk.ensure_not_watch_only("receive_payment")?;
+    }
     sdk.maybe_ensure_spark_private_mode_initialized().await?;
+    let config = sdk.config.get();
+    let render_description = |description: Option<&str>| {
+        render_invoice_description(
+            config.invoice_description_template.as_ref(),
+            description,
+            request.order_ref.as_deref(),
+        )
+    };
     match request.payment_method {
         ReceivePaymentMethod::SparkAddress => Ok(ReceivePaymentResponse {
             fee: 0,
@@ -52,7 +73,7 @@ pub(super) async fn receive_payment(
                                 .ok_or(SdkError::Generic("Invalid expiry time".to_string()))
                         })
                         .transpose()?,
-                    description,
+                    render_description(description.as_deref()),
                     sender_public_key,
                 )
                 .await?;
@@ -61,9 +82,54 @@ pub(super) async fn receive_payment(
                 payment_request: invoice,
             })
         }
+        ReceivePaymentMethod::TokenInvoice {
+            token_identifier,
+            amount,
+            memo,
+        } => {
+            if amount == 0 {
+                return Err(SdkError::InvalidInput(
+                    "Amount must be greater than 0".to_string(),
+                ));
+            }
+            let object_repository = ObjectCacheRepository::new(sdk.storage.clone());
+            if get_tokens_metadata_cached_or_query(
+                &sdk.spark_wallet,
+                &object_repository,
+                &[&token_identifier],
+            )
+            .await?
+            .is_empty()
+            {
+                return Err(SdkError::InvalidInput(format!(
+                    "Unknown token: {token_identifier}"
+                )));
+            }
+            let invoice = sdk
+                .spark_wallet
+                .create_spark_invoice(
+                    Some(amount),
+                    Some(token_identifier.clone()),
+                    None,
+                    render_description(memo.as_deref()),
+                    None,
+                )
+                .await?;
+            track_token_invoice(
+                &object_repository,
+                invoice.clone(),
+                token_identifier,
+                amount,
+            )
+            .await?;
+            Ok(ReceivePaymentResponse {
+                fee: 0,
+                payment_request: invoice,
+            })
+        }
         ReceivePaymentMethod::BitcoinAddress { new_address } => {
             let address =
-                get_deposit_address(&sdk.spark_wallet, new_address.unwrap_or(false)).await?;
+                get_deposit_address(&sdk.spark_wallet, sdk.storage.clone(), new_address).await?;
             Ok(ReceivePaymentResponse {
                 payment_request: address,
                 fee: 0,
@@ -74,7 +140,28 @@ pub(super) async fn receive_payment(
             amount_sats,
             expiry_secs,
             payment_hash,
-        } => receive_bolt11_invoice(sdk, description, amount_sats, expiry_secs, payment_hash).await,
+            min_amount_sats,
+            max_amount_sats,
+        } => {
+            let description = render_description(Some(&description)).unwrap_or_default();
+            if min_amount_sats.is_some() || max_amount_sats.is_some() {
+                validate_amount_range(
+                    amount_sats,
+                    payment_hash.as_ref(),
+                    min_amount_sats,
+                    max_amount_sats,
+                )?;
+                return sdk
+                    .receive_amount_range_invoice(
+                        description,
+                        expiry_secs,
+                        min_amount_sats,
+                        max_amount_sats,
+                    )
+                    .await;
+            }
+            receive_bolt11_invoice(sdk, description, amount_sats, expiry_secs, payment_hash).await
+        }
     }
 }
 
@@ -107,6 +194,97 @@ pub(super) async fn claim_htlc_payment(
     Ok(ClaimHtlcPaymentResponse { payment })
 }
 
+pub(super) async fn create_htlc_receive(
+    sdk: &BreezSdk,
+    request: CreateHtlcReceiveRequest,
+) -> Result<CreateHtlcReceiveResponse, SdkError> {
+    let payment_hash = parse_payment_hash(&request.payment_hash)?.to_string();
+    let spark_address = sdk
+        .spark_wallet
+        .get_spark_address()?
+        .to_address_string()
+        .map_err(|e| {
+            SdkError::Generic(format!("Failed to convert Spark address to string: {e}"))
+        })?;
+
+    let object_repository = ObjectCacheRepository::new(sdk.storage.clone());
+    if object_repository
+        .fetch_htlc_receive(&payment_hash)
+        .await?
+        .is_none()
+    {
+        object_repository
+            .save_htlc_receive(
+                &payment_hash,
+                &CachedHtlcReceive {
+                    created_at: sdk.now_secs(),
+                },
+            )
+            .await?;
+    }
+
+    Ok(CreateHtlcReceiveResponse {
+        spark_address,
+        payment_hash,
+    })
+}
+
+pub(super) async fn get_htlc_receive(
+    sdk: &BreezSdk,
+    request: GetHtlcReceiveRequest,
+) -> Result<GetHtlcReceiveResponse, SdkError> {
+    let hash = parse_payment_hash(&request.payment_hash)?;
+    let payment_hash = hash.to_string();
+    let transfers = sdk.spark_wallet.list_received_htlc_transfers(hash).await?;
+    let mut payments = transfers
+        .into_iter()
+        .map(Payment::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    // A returned HTLC may have been followed by a new one for the same hash
+    payments.sort_by_key(|payment| {
+        htlc_details(payment).map(|d| d.status) == Some(SparkHtlcStatus::Returned)
+    });
+    let payment = payments.into_iter().next();
+
+    if payment.is_none()
+        && ObjectCacheRepository::new(sdk.storage.clone())
+            .fetch_htlc_receive(&payment_hash)
+            .await?
+            .is_none()
+    {
+        return Err(SdkError::InvalidInput(
+            "No HTLC receive with the given payment hash".to_string(),
+        ));
+    }
+
+    let htlc_details = payment.as_ref().and_then(htlc_details).cloned();
+    Ok(GetHtlcReceiveResponse {
+        htlc_receive: HtlcReceive {
+            payment_hash,
+            status: htlc_details
+                .as_ref()
+                .map_or(HtlcReceiveStatus::AwaitingHtlc, |d| d.status.into()),
+            htlc_details,
+            payment,
+        },
+    })
+}
+
+fn parse_payment_hash(payment_hash: &str) -> Result<sha256::Hash, SdkError> {
+    sha256::Hash::from_str(payment_hash)
+        .map_err(|e| SdkError::InvalidInput(format!("Invalid payment hash: {e}")))
+}
+
+fn htlc_details(payment: &Payment) -> Option<&SparkHtlcDetails> {
+    match &payment.details {
+        Some(PaymentDetails::Spark {
+            htlc_details: Some(htlc_details),
+            ..
+        }) => Some(htlc_details),
+        _ => None,
+    }
+}
+
 pub(super) async fn receive_bolt11_invoice(
     sdk: &BreezSdk,
     description: String,
@@ -140,7 +318,7 @@ pub(super) async fn receive_bolt11_invoice_inner(
         sdk.spark_wallet
             .create_hodl_lightning_invoice(
                 amount_sats.unwrap_or_default(),
-                Some(InvoiceDescription::Memo(description.clone())),
+                Some(bolt11_invoice_description(description)),
                 hash,
                 None,
                 expiry_secs,
@@ -150,12 +328,13 @@ pub(super) async fn receive_bolt11_invoice_inner(
         sdk.spark_wallet
             .create_lightning_invoice(
                 amount_sats.unwrap_or_default(),
-                Some(InvoiceDescription::Memo(description.clone())),
+                Some(bolt11_invoice_description(description)),
                 None,
                 expiry_secs,
-                sdk.config.prefer_spark_over_lightning,
+                sdk.config.get().prefer_spark_over_lightning,
             )
             .await?
     };
+    sdk.track_lightning_receive(&receive).await;
     Ok(receive)
 }
@@ -29,7 +29,7 @@ pub(super) async fn send(
             prefer_spark,
             completion_timeout_secs,
         }) => (prefer_spark, completion_timeout_secs),
-        _ => (sdk.config.prefer_spark_over_lightning, None),
+        _ => (sdk.config.get().prefer_spark_over_lightning, None),
     };
     let is_spark_route = prefer_spark && spark_transfer_fee_sats.is_some();
     let fee_sats = if is_spark_route {
@@ -4,14 +4,17 @@ pub(in crate::sdk::payments) mod cross_chain;
 pub(super) mod spark_address;
 pub(super) mod spark_invoice;
 
-use tracing::warn;
+use std::{future::Future, str::FromStr};
+
+use spark_wallet::{ListTransfersRequest, TransferId};
+use tracing::{info, warn};
 
 use crate::{
     ConversionEstimate, SendPaymentMethod,
     error::SdkError,
     events::SdkEvent,
     models::{
-        PublishSignedTransferPackageResponse, SendPaymentRequest, SendPaymentResponse,
+        Payment, PublishSignedTransferPackageResponse, SendPaymentRequest, SendPaymentResponse,
         SignedTransferPackage, TransferSignature, TransferTarget, UnsignedTransferPackage,
     },
     persist::ObjectCacheRepository,
@@ -237,6 +240,14 @@ pub(in crate::sdk) async fn orchestrate_send(
         if let Ok(payment) = sdk.storage.get_payment_by_id(idempotency_key.clone()).await {
             return Ok(SendPaymentResponse { payment });
         }
+        let cache = ObjectCacheRepository::new(sdk.storage.clone());
+        let started = begin_in_flight_send(&cache, idempotency_key, sdk.now_secs(), || {
+            lookup_transfer(sdk, idempotency_key)
+        })
+        .await?;
+        if let InFlightSend::Recovered(payment) = started {
+            return Ok(SendPaymentResponse { payment });
+        }
     }
     let conversion_estimate = request.prepare_response.conversion_estimate.clone();
     // Perform the send payment, with conversion if requested
@@ -266,9 +277,95 @@ pub(in crate::sdk) async fn orchestrate_send(
             .emit(&SdkEvent::from_payment(response.payment.clone()))
             .await;
     }
+    if let Ok(response) = &res {
+        crate::sdk::fee_estimation::record_fee_estimate(sdk, &request, &response.payment).await;
+    }
+    if let Some(idempotency_key) = &request.idempotency_key {
+        let cache = ObjectCacheRepository::new(sdk.storage.clone());
+        finish_in_flight_send(&cache, idempotency_key, &res).await;
+    }
     res
 }
 
+enum InFlightSend {
+    /// An earlier send with the idempotency key made the transfer
+    Recovered(Payment),
+    /// The send can go ahead, its marker is saved
+    Started,
+}
+
+/// Starts the send with `idempotency_key`, or returns the payment of an
+/// earlier send with the same key that didn't return successfully, e.g.
+/// because the connection dropped after the transfer was made.
+///
+/// The marker of an earlier send is only kept until the send succeeds, so if
+/// there is one the transfer is looked up with `lookup`. When it doesn't
+/// exist the send can safely be made again.
+async fn begin_in_flight_send<F, Fut>(
+    cache: &ObjectCacheRepository,
+    idempotency_key: &str,
+    started_at: u64,
+    lookup: F,
+) -> Result<InFlightSend, SdkError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<Option<Payment>, SdkError>>,
+{
+    if cache.fetch_in_flight_send(idempotency_key).await?.is_some() {
+        if let Some(payment) = lookup().await? {
+            info!("Recovered in-flight send {idempotency_key}");
+            cache.delete_in_flight_send(idempotency_key).await?;
+            return Ok(InFlightSend::Recovered(payment));
+        }
+        info!("No transfer found for in-flight send {idempotency_key}, sending again");
+    }
+    cache
+        .save_in_flight_send(idempotency_key, started_at)
+        .await?;
+    Ok(InFlightSend::Started)
+}
+
+/// Clears the marker of the send with `idempotency_key` once it succeeded.
+/// A failed send keeps it, as the transfer may have been made anyway, so the
+/// next send with the key looks it up first.
+async fn finish_in_flight_send<T>(
+    cache: &ObjectCacheRepository,
+    idempotency_key: &str,
+    res: &Result<T, SdkError>,
+) {
+    if res.is_err() {
+        return;
+    }
+    if let Err(e) = cache.delete_in_flight_send(idempotency_key).await {
+        warn!("Failed to clear the in-flight send {idempotency_key}: {e:?}");
+    }
+}
+
+/// Looks up the transfer of the send with `idempotency_key` from the
+/// operators, as the idempotency key is the transfer id, and stores its
+/// payment if it exists.
+async fn lookup_transfer(
+    sdk: &BreezSdk,
+    idempotency_key: &str,
+) -> Result<Option<Payment>, SdkError> {
+    let transfer_id = TransferId::from_str(idempotency_key)?;
+    let transfer = sdk
+        .spark_wallet
+        .list_transfers(ListTransfersRequest {
+            transfer_ids: vec![transfer_id],
+            paging: None,
+        })
+        .await?
+        .items
+        .pop();
+    let Some(transfer) = transfer else {
+        return Ok(None);
+    };
+    let payment: Payment = transfer.try_into()?;
+    sdk.storage.apply_payment_update(payment.clone()).await?;
+    Ok(Some(payment))
+}
+
 pub(super) async fn send_internal(
     sdk: &BreezSdk,
     request: &SendPaymentRequest,
@@ -332,3 +429,190 @@ pub(super) async fn send_internal(
         }
     }
 }
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use std::{path::PathBuf, sync::Arc};
+
+    use super::*;
+    use crate::{
+        DisplayAmount, FeePolicy, PaymentDetails, PaymentMethod, PaymentStatus, PaymentType,
+        PrepareSendPaymentResponse,
+        persist::{CachedQueuedSend, Storage, sqlite::SqliteStorage},
+        sdk::queued_sends::ensure_queueable,
+    };
+
+    const KEY: &str = "019a3b1c-2d4e-7f60-8a9b-0c1d2e3f4a5b";
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("breez-test-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn create_cache() -> ObjectCacheRepository {
+        let storage: Arc<dyn Storage> =
+            Arc::new(SqliteStorage::new(&create_temp_dir("in_flight_send")).unwrap());
+        ObjectCacheRepository::new(storage)
+    }
+
+    fn payment() -> Payment {
+        Payment {
+            id: KEY.to_string(),
+            payment_type: PaymentType::Send,
+            status: PaymentStatus::Completed,
+            amount: 1000,
+            fees: 0,
+            timestamp: 1_700_000_000,
+            method: PaymentMethod::Spark,
+            details: Some(PaymentDetails::Spark {
+                invoice_details: None,
+                htlc_details: None,
+                conversion_info: None,
+            }),
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    /// Begins the send, with `found` as the transfer the operators return.
+    async fn begin(
+        cache: &ObjectCacheRepository,
+        found: Option<Payment>,
+        lookups: &mut u32,
+    ) -> InFlightSend {
+        begin_in_flight_send(cache, KEY, 100, || {
+            *lookups += 1;
+            async move { Ok(found) }
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn has_marker(cache: &ObjectCacheRepository) -> bool {
+        cache.fetch_in_flight_send(KEY).await.unwrap().is_some()
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_send_recovered_after_crash() {
+        let cache = create_cache();
+        // The process stopped while sending, before the send returned
+        let mut lookups = 0;
+        assert!(matches!(
+            begin(&cache, None, &mut lookups).await,
+            InFlightSend::Started
+        ));
+
+        let result = begin(&cache, Some(payment()), &mut lookups).await;
+        assert!(matches!(result, InFlightSend::Recovered(p) if p.id == KEY));
+        assert_eq!(lookups, 1);
+        assert!(!has_marker(&cache).await);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_send_recovered_after_network_error() {
+        let cache = create_cache();
+        let mut lookups = 0;
+        begin(&cache, None, &mut lookups).await;
+        // The transfer was made, but the response was lost
+        let res: Result<(), SdkError> = Err(SdkError::NetworkError("timeout".to_string()));
+        finish_in_flight_send(&cache, KEY, &res).await;
+        assert!(has_marker(&cache).await);
+
+        let result = begin(&cache, Some(payment()), &mut lookups).await;
+        assert!(matches!(result, InFlightSend::Recovered(p) if p.id == KEY));
+        assert_eq!(lookups, 1);
+        assert!(!has_marker(&cache).await);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_send_retried_when_transfer_missing() {
+        let cache = create_cache();
+        let mut lookups = 0;
+        begin(&cache, None, &mut lookups).await;
+        let res: Result<(), SdkError> = Err(SdkError::NetworkError("unreachable".to_string()));
+        finish_in_flight_send(&cache, KEY, &res).await;
+
+        // The transfer wasn't made, so the send is made again
+        let result = begin(&cache, None, &mut lookups).await;
+        assert!(matches!(result, InFlightSend::Started));
+        assert_eq!(lookups, 1);
+        assert!(has_marker(&cache).await);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_send_cleared_on_success() {
+        let cache = create_cache();
+        let mut lookups = 0;
+        begin(&cache, None, &mut lookups).await;
+        finish_in_flight_send(&cache, KEY, &Ok::<_, SdkError>(())).await;
+        assert!(!has_marker(&cache).await);
+
+        // A later send with the key doesn't look up the operators
+        let result = begin(&cache, None, &mut lookups).await;
+        assert!(matches!(result, InFlightSend::Started));
+        assert_eq!(lookups, 0);
+    }
+
+    #[tokio::test]
+    async fn test_queued_send_not_sent_twice_after_transport_error() {
+        let cache = create_cache();
+        let mut request = SendPaymentRequest {
+            prepare_response: PrepareSendPaymentResponse {
+                payment_method: SendPaymentMethod::SparkAddress {
+                    address: "spark_address".to_string(),
+                    fee: 0,
+                    token_identifier: None,
+                },
+                amount: 1_000,
+                token_identifier: None,
+                conversion_estimate: None,
+                fee_policy: FeePolicy::FeesExcluded,
+                bip21_details: None,
+                fee_breakdown: None,
+            },
+            options: None,
+            idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: Some(true),
+        };
+        ensure_queueable(&mut request).unwrap();
+        let key = request.idempotency_key.clone().unwrap();
+
+        // The transfer is made, but the transport fails before the response
+        let started = begin_in_flight_send(&cache, &key, 100, || async { Ok(None) })
+            .await
+            .unwrap();
+        assert!(matches!(started, InFlightSend::Started));
+        let res: Result<(), SdkError> = Err(SdkError::SparkError("transport error".to_string()));
+        finish_in_flight_send(&cache, &key, &res).await;
+
+        // The send is queued and retried with the same key
+        cache
+            .save_queued_sends(&[CachedQueuedSend {
+                id: key.clone(),
+                request,
+                queued_at: 100,
+                expires_at: 86_500,
+            }])
+            .await
+            .unwrap();
+        let queued = cache.fetch_queued_sends().await.unwrap().remove(0);
+        let retry_key = queued.request.idempotency_key.unwrap();
+        assert_eq!(retry_key, key);
+
+        // The retry finds the transfer instead of sending again
+        let mut sent = payment();
+        sent.id.clone_from(&key);
+        let retried =
+            begin_in_flight_send(&cache, &retry_key, 200, || async move { Ok(Some(sent)) })
+                .await
+                .unwrap();
+        assert!(matches!(retried, InFlightSend::Recovered(p) if p.id == key));
+        assert!(cache.fetch_in_flight_send(&key).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use tracing::{info, warn};
+
+use crate::{
+    PaySplitRequest, PaySplitResponse, PaymentRequest, PrepareSendPaymentRequest,
+    PrepareSendPaymentResponse, SendPaymentMethod, SendPaymentRequest, SplitExecutionMode,
+    SplitPaymentResult, SplitPaymentStatus, error::SdkError, persist::PaymentMetadata,
+    sdk::BreezSdk,
+};
+
+/// Prepares all invoices of the split, checks that they fit the budget and pays them.
+pub(super) async fn pay_split(
+    sdk: &BreezSdk,
+    request: PaySplitRequest,
+) -> Result<PaySplitResponse, SdkError> {
+    validate_invoices(&request.invoices)?;
+
+    let mut prepared = Vec::with_capacity(request.invoices.len());
+    for invoice in &request.invoices {
+        let prepare_response = sdk
+            .prepare_send_payment(PrepareSendPaymentRequest {
+                payment_request: PaymentRequest::Input {
+                    input: invoice.clone(),
+                },
+                amount: None,
+                token_identifier: None,
+                conversion_options: None,
+                fee_policy: None,
+            })
+            .await?;
+        let (amount_sats, fee_sats) = split_payment_cost(invoice, &prepare_response)?;
+        prepared.push((invoice.clone(), amount_sats, fee_sats, prepare_response));
+    }
+    let costs: Vec<_> = prepared
+        .iter()
+        .map(|(_, amount_sats, fee_sats, _)| (*amount_sats, *fee_sats))
+        .collect();
+    check_budget(&costs, request.total_budget_sats)?;
+
+    let mut results: Vec<SplitPaymentResult> = prepared
+        .iter()
+        .map(|(invoice, amount_sats, fee_sats, _)| SplitPaymentResult {
+            invoice: invoice.clone(),
+            amount_sats: *amount_sats,
+            estimated_fee_sats: *fee_sats,
+            status: SplitPaymentStatus::Skipped,
+        })
+        .collect();
+    // Links the payments of the split in the payment list
+    let group_id = uuid::Uuid::new_v4().to_string();
+    let group_id = &group_id;
+    let send = |prepare_response: PrepareSendPaymentResponse| async move {
+        match sdk
+            .send_payment(SendPaymentRequest {
+                prepare_response,
+                options: None,
+                idempotency_key: None,
+                compliance_metadata: None,
+                queue_if_offline: None,
+            })
+            .await
+        {
+            Ok(response) => {
+                let mut payment = response.payment;
+                if let Err(e) = sdk
+                    .storage
+                    .insert_payment_metadata(
+                        payment.id.clone(),
+                        PaymentMetadata {
+                            group_id: Some(group_id.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                {
+                    warn!(
+                        "Failed to set the group of split payment {}: {e}",
+                        payment.id
+                    );
+                }
+                payment.group_id = Some(group_id.clone());
+                SplitPaymentStatus::Sent { payment }
+            }
+            Err(e) => SplitPaymentStatus::Failed {
+                error: e.to_string(),
+            },
+        }
+    };
+
+    match request.execution_mode.unwrap_or_default() {
+        SplitExecutionMode::Sequential => {
+            for (result, (_, _, _, prepare_response)) in results.iter_mut().zip(prepared) {
+                result.status = send(prepare_response).await;
+                if let SplitPaymentStatus::Failed { error } = &result.status {
+                    warn!("Split payment of {} failed: {error}", result.invoice);
+                    break;
+                }
+            }
+        }
+        SplitExecutionMode::Parallel => {
+            let statuses = futures::future::join_all(
+                prepared
+                    .into_iter()
+                    .map(|(_, _, _, prepare_response)| send(prepare_response)),
+            )
+            .await;
+            for (result, status) in results.iter_mut().zip(statuses) {
+                result.status = status;
+            }
+        }
+    }
+
+    let response = summarize(results);
+    info!(
+        "Split payment completed: sent {} sats with {} sats fees, all succeeded: {}",
+        response.total_sent_sats, response.total_fees_sats, response.all_succeeded
+    );
+    Ok(response)
+}
+
+fn validate_invoices(invoices: &[String]) -> Result<(), SdkError> {
+    if invoices.is_empty() {
+        return Err(SdkError::InvalidInput(
+            "At least one invoice is required".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    for invoice in invoices {
+        if !seen.insert(invoice.trim()) {
+            return Err(SdkError::InvalidInput(format!(
+                "Invoice is included more than once: {invoice}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the amount and the estimated fee of a prepared invoice, in satoshis.
+fn split_payment_cost(
+    invoice: &str,
+    prepare_response: &PrepareSendPaymentResponse,
+) -> Result<(u64, u64), SdkError> {
+    if prepare_response.token_identifier.is_some() || prepare_response.conversion_estimate.is_some()
+    {
+        return Err(SdkError::InvalidInput(format!(
+            "Only Bitcoin invoices can be split paid: {invoice}"
+        )));
+    }
+    let fee = match &prepare_response.payment_method {
+        SendPaymentMethod::Bolt11Invoice {
+            lightning_fee_sats, ..
+        } => u128::from(*lightning_fee_sats),
+        SendPaymentMethod::SparkInvoice { fee, .. } => *fee,
+        _ => {
+            return Err(SdkError::InvalidInput(format!(
+                "Only BOLT11 and Spark invoices can be split paid: {invoice}"
+            )));
+        }
+    };
+    Ok((u64::try_from(prepare_response.amount)?, u64::try_from(fee)?))
+}
+
+/// Fails if the invoices together with their fees exceed the budget.
+fn check_budget(costs: &[(u64, u64)], total_budget_sats: u64) -> Result<(), SdkError> {
+    let total = costs
+        .iter()
+        .try_fold(0u64, |total, (amount, fee)| {
+            total.checked_add(*amount)?.checked_add(*fee)
+        })
+        .ok_or(SdkError::InvalidInput("Total amount overflows".to_string()))?;
+    if total > total_budget_sats {
+        return Err(SdkError::InvalidInput(format!(
+            "Invoices cost {total} sats including fees, which exceeds the budget of {total_budget_sats} sats"
+        )));
+    }
+    Ok(())
+}
+
+fn summarize(results: Vec<SplitPaymentResult>) -> PaySplitResponse {
+    let mut total_sent_sats: u64 = 0;
+    let mut total_fees_sats: u64 = 0;
+    let mut all_succeeded = true;
+    for result in &results {
+        match &result.status {
+            SplitPaymentStatus::Sent { payment } => {
+                total_sent_sats =
+                    total_sent_sats.saturating_add(u64::try_from(payment.amount).unwrap_or(0));
+                total_fees_sats =
+                    total_fees_sats.saturating_add(u64::try_from(payment.fees).unwrap_or(0));
+            }
+            SplitPaymentStatus::Failed { .. } | SplitPaymentStatus::Skipped => {
+                all_succeeded = false;
+            }
+        }
+    }
+    PaySplitResponse {
+        results,
+        total_sent_sats,
+        total_fees_sats,
+        all_succeeded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macros::test_all;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_validate_invoices() {
+        assert!(validate_invoices(&[]).is_err());
+        assert!(validate_invoices(&["lnbc1".to_string(), "lnbc2".to_string()]).is_ok());
+        assert!(validate_invoices(&["lnbc1".to_string(), " lnbc1 ".to_string()]).is_err());
+    }
+
+    #[test_all]
+    fn test_check_budget() {
+        let costs = [(1_000, 10), (2_000, 0), (500, 5)];
+        assert!(check_budget(&costs, 3_515).is_ok());
+        assert!(check_budget(&costs, 4_000).is_ok());
+
+        let Err(SdkError::InvalidInput(msg)) = check_budget(&costs, 3_514) else {
+            panic!("Expected InvalidInput error");
+        };
+        assert!(msg.contains("3515 sats"), "{msg}");
+
+        assert!(check_budget(&[(u64::MAX, 1)], u64::MAX).is_err());
+    }
+}
@@ -0,0 +1,402 @@
+use std::str::FromStr;
+
+use bitcoin::{
+    Address, Amount, CompressedPublicKey, EcdsaSighashType, NetworkKind, OutPoint, PrivateKey,
+    ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+    absolute::LockTime,
+    consensus::serialize,
+    ecdsa,
+    hashes::Hash as _,
+    hex::DisplayHex,
+    key::{Keypair, Secp256k1, TapTweak as _},
+    script::{Builder, PushBytesBuf},
+    secp256k1::{All, Message},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot,
+    transaction::{InputWeightPrediction, Version, predict_weight},
+};
+use tracing::info;
+
+use crate::{
+    SweepPrivateKeyRequest, SweepPrivateKeyResponse, error::SdkError,
+    utils::bitcoin_dust::get_dust_limit_sats,
+};
+
+use super::{BreezSdk, SyncType, helpers::get_deposit_address};
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Moves the funds held by a single private key, such as a paper wallet,
+    /// into the wallet.
+    ///
+    /// The UTXOs of the key's P2PKH, P2SH-P2WPKH, P2WPKH and P2TR addresses
+    /// are spent in one transaction paying the wallet's static deposit
+    /// address, which is then claimed like any other deposit once confirmed.
+    pub async fn sweep_private_key(
+        &self,
+        request: SweepPrivateKeyRequest,
+    ) -> Result<SweepPrivateKeyResponse, SdkError> {
+        self.ensure_not_watch_only("sweep_private_key")?;
+        let network: bitcoin::Network = self.config.get().network.into();
+        let key = PrivateKey::from_wif(&request.wif)
+            .map_err(|e| SdkError::InvalidInput(format!("Invalid private key: {e}")))?;
+        if key.network != NetworkKind::from(network) {
+            return Err(SdkError::InvalidInput(
+                "Private key is for a different network".to_string(),
+            ));
+        }
+
+        let secp = Secp256k1::new();
+        let mut utxos = Vec::new();
+        for (kind, address) in swept_addresses(&secp, &key, network) {
+            for utxo in self
+                .chain_service
+                .get_address_utxos(address.to_string())
+                .await?
+            {
+                utxos.push(SweptUtxo {
+                    outpoint: OutPoint {
+                        txid: Txid::from_str(&utxo.txid)
+                            .map_err(|e| SdkError::Generic(format!("Invalid txid: {e}")))?,
+                        vout: utxo.vout,
+                    },
+                    value: utxo.value,
+                    kind,
+                    script_pubkey: address.script_pubkey(),
+                });
+            }
+        }
+        if utxos.is_empty() {
+            return Err(SdkError::InvalidInput(
+                "No funds found for the private key".to_string(),
+            ));
+        }
+
+        let deposit_address =
+            get_deposit_address(&self.spark_wallet, self.storage.clone(), None).await?;
+        let destination = Address::from_str(&deposit_address)
+            .and_then(|address| address.require_network(network))
+            .map_err(|e| SdkError::Generic(format!("Invalid deposit address: {e}")))?
+            .script_pubkey();
+        let tx = build_sweep_tx(
+            &secp,
+            &key,
+            &utxos,
+            destination,
+            request.fee_rate_sat_per_vbyte,
+            get_dust_limit_sats(&deposit_address)?,
+        )?;
+
+        let tx_hex = serialize(&tx).as_hex().to_string();
+        let tx_id = tx.compute_txid().to_string();
+        let amount_sats = tx.output[0].value.to_sat();
+        let fee_sats = utxos
+            .iter()
+            .map(|utxo| utxo.value)
+            .sum::<u64>()
+            .saturating_sub(amount_sats);
+        self.chain_service
+            .broadcast_transaction(tx_hex.clone())
+            .await?;
+        info!(
+            "Swept {} UTXOs of a private key to {deposit_address} in {tx_id}",
+            utxos.len()
+        );
+
+        // The deposit shows up as unclaimed until it confirms and is claimed
+        // by the regular deposit sync.
+        self.sync_coordinator
+            .trigger_sync_no_wait(SyncType::Deposits, true)
+            .await;
+        Ok(SweepPrivateKeyResponse {
+            tx_id,
+            tx_hex,
+            deposit_address,
+            amount_sats,
+            fee_sats,
+        })
+    }
+}
+
+/// The address types a single key receives to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SweptKind {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2tr,
+}
+
+/// A UTXO held by the swept key.
+#[derive(Clone, Debug)]
+struct SweptUtxo {
+    outpoint: OutPoint,
+    value: u64,
+    kind: SweptKind,
+    script_pubkey: ScriptBuf,
+}
+
+/// The addresses of `key`. Uncompressed keys only have a P2PKH address.
+fn swept_addresses(
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    network: bitcoin::Network,
+) -> Vec<(SweptKind, Address)> {
+    let public_key = key.public_key(secp);
+    let mut addresses = vec![(SweptKind::P2pkh, Address::p2pkh(public_key, network))];
+    if let Ok(compressed) = CompressedPublicKey::try_from(public_key) {
+        addresses.extend([
+            (
+                SweptKind::P2shP2wpkh,
+                Address::p2shwpkh(&compressed, network),
+            ),
+            (SweptKind::P2wpkh, Address::p2wpkh(&compressed, network)),
+            (
+                SweptKind::P2tr,
+                Address::p2tr(secp, compressed.0.x_only_public_key().0, None, network),
+            ),
+        ]);
+    }
+    addresses
+}
+
+/// Builds and signs a transaction spending all `utxos` to `destination`,
+/// paying `fee_rate_sat_per_vbyte`.
+fn build_sweep_tx(
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    utxos: &[SweptUtxo],
+    destination: ScriptBuf,
+    fee_rate_sat_per_vbyte: u64,
+    dust_limit_sats: u64,
+) -> Result<Transaction, SdkError> {
+    let weight = predict_weight(
+        utxos.iter().map(|utxo| match utxo.kind {
+            SweptKind::P2pkh if key.compressed => InputWeightPrediction::P2PKH_COMPRESSED_MAX,
+            SweptKind::P2pkh => InputWeightPrediction::P2PKH_UNCOMPRESSED_MAX,
+            SweptKind::P2shP2wpkh => InputWeightPrediction::NESTED_P2WPKH_MAX,
+            SweptKind::P2wpkh => InputWeightPrediction::P2WPKH_MAX,
+            SweptKind::P2tr => InputWeightPrediction::P2TR_KEY_DEFAULT_SIGHASH,
+        }),
+        [destination.len()],
+    );
+    let fee_sats = fee_rate_sat_per_vbyte.saturating_mul(weight.to_vbytes_ceil());
+    let total_sats: u64 = utxos.iter().map(|utxo| utxo.value).sum();
+    let amount_sats = total_sats.saturating_sub(fee_sats);
+    if amount_sats < dust_limit_sats {
+        return Err(SdkError::InsufficientFunds);
+    }
+
+    let mut tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: utxos
+            .iter()
+            .map(|utxo| TxIn {
+                previous_output: utxo.outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            })
+            .collect(),
+        output: vec![TxOut {
+            value: Amount::from_sat(amount_sats),
+            script_pubkey: destination,
+        }],
+    };
+    let signatures = sign_sweep_inputs(secp, key, utxos, &tx)?;
+    for (input, (script_sig, witness)) in tx.input.iter_mut().zip(signatures) {
+        input.script_sig = script_sig;
+        input.witness = witness;
+    }
+    Ok(tx)
+}
+
+/// Returns the script sig and witness of each input of `tx`.
+fn sign_sweep_inputs(
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    utxos: &[SweptUtxo],
+    tx: &Transaction,
+) -> Result<Vec<(ScriptBuf, Witness)>, SdkError> {
+    let public_key = key.public_key(secp);
+    let prevouts: Vec<TxOut> = utxos
+        .iter()
+        .map(|utxo| TxOut {
+            value: Amount::from_sat(utxo.value),
+            script_pubkey: utxo.script_pubkey.clone(),
+        })
+        .collect();
+    let signing_error = |e: String| SdkError::Generic(format!("Failed to sign sweep: {e}"));
+    let sign_ecdsa = |digest: [u8; 32]| ecdsa::Signature {
+        signature: secp.sign_ecdsa(&Message::from_digest(digest), &key.inner),
+        sighash_type: EcdsaSighashType::All,
+    };
+
+    let mut cache = SighashCache::new(tx);
+    let mut signatures = Vec::with_capacity(utxos.len());
+    for (index, utxo) in utxos.iter().enumerate() {
+        let signature = match utxo.kind {
+            SweptKind::P2pkh => {
+                let sighash = cache
+                    .legacy_signature_hash(
+                        index,
+                        &utxo.script_pubkey,
+                        EcdsaSighashType::All.to_u32(),
+                    )
+                    .map_err(|e| signing_error(e.to_string()))?;
+                let signature = sign_ecdsa(sighash.to_byte_array());
+                let script_sig = Builder::new()
+                    .push_slice(signature.serialize())
+                    .push_key(&public_key)
+                    .into_script();
+                (script_sig, Witness::new())
+            }
+            SweptKind::P2shP2wpkh | SweptKind::P2wpkh => {
+                let compressed = CompressedPublicKey::try_from(public_key)
+                    .map_err(|e| signing_error(e.to_string()))?;
+                let wpkh_script = ScriptBuf::new_p2wpkh(&compressed.wpubkey_hash());
+                let sighash = cache
+                    .p2wpkh_signature_hash(
+                        index,
+                        &wpkh_script,
+                        Amount::from_sat(utxo.value),
+                        EcdsaSighashType::All,
+                    )
+                    .map_err(|e| signing_error(e.to_string()))?;
+                let witness = Witness::p2wpkh(&sign_ecdsa(sighash.to_byte_array()), &compressed.0);
+                let script_sig = if utxo.kind == SweptKind::P2shP2wpkh {
+                    let redeem_script = PushBytesBuf::try_from(wpkh_script.into_bytes())
+                        .map_err(|e| signing_error(e.to_string()))?;
+                    Builder::new().push_slice(redeem_script).into_script()
+                } else {
+                    ScriptBuf::new()
+                };
+                (script_sig, witness)
+            }
+            SweptKind::P2tr => {
+                let keypair = Keypair::from_secret_key(secp, &key.inner)
+                    .tap_tweak(secp, None)
+                    .to_keypair();
+                let sighash = cache
+                    .taproot_key_spend_signature_hash(
+                        index,
+                        &Prevouts::All(&prevouts),
+                        TapSighashType::Default,
+                    )
+                    .map_err(|e| signing_error(e.to_string()))?;
+                let signature = taproot::Signature {
+                    signature: secp.sign_schnorr_no_aux_rand(
+                        &Message::from_digest(sighash.to_byte_array()),
+                        &keypair,
+                    ),
+                    sighash_type: TapSighashType::Default,
+                };
+                (ScriptBuf::new(), Witness::p2tr_key_spend(&signature))
+            }
+        };
+        signatures.push(signature);
+    }
+    Ok(signatures)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::secp256k1::SecretKey;
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn key(compressed: bool) -> PrivateKey {
+        let secret_key = SecretKey::from_slice(&[7u8; 32]).unwrap();
+        if compressed {
+            PrivateKey::new(secret_key, bitcoin::Network::Regtest)
+        } else {
+            PrivateKey::new_uncompressed(secret_key, bitcoin::Network::Regtest)
+        }
+    }
+
+    fn utxos(secp: &Secp256k1<All>, key: &PrivateKey) -> Vec<SweptUtxo> {
+        swept_addresses(secp, key, bitcoin::Network::Regtest)
+            .into_iter()
+            .zip(0..)
+            .map(|((kind, address), vout)| SweptUtxo {
+                outpoint: OutPoint {
+                    txid: Txid::all_zeros(),
+                    vout,
+                },
+                value: 10_000,
+                kind,
+                script_pubkey: address.script_pubkey(),
+            })
+            .collect()
+    }
+
+    fn destination() -> ScriptBuf {
+        let secp = Secp256k1::new();
+        let public_key = key(true).public_key(&secp);
+        Address::p2wpkh(
+            &CompressedPublicKey::try_from(public_key).unwrap(),
+            bitcoin::Network::Regtest,
+        )
+        .script_pubkey()
+    }
+
+    #[test_all]
+    fn test_swept_addresses() {
+        let secp = Secp256k1::new();
+        let kinds = |compressed| {
+            swept_addresses(&secp, &key(compressed), bitcoin::Network::Regtest)
+                .into_iter()
+                .map(|(kind, _)| kind)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(true),
+            vec![
+                SweptKind::P2pkh,
+                SweptKind::P2shP2wpkh,
+                SweptKind::P2wpkh,
+                SweptKind::P2tr
+            ]
+        );
+        assert_eq!(kinds(false), vec![SweptKind::P2pkh]);
+    }
+
+    #[test_all]
+    fn test_build_sweep_tx_signs_every_input() {
+        let secp = Secp256k1::new();
+        let key = key(true);
+        let utxos = utxos(&secp, &key);
+
+        let tx = build_sweep_tx(&secp, &key, &utxos, destination(), 2, 294).unwrap();
+
+        assert_eq!(tx.input.len(), 4);
+        assert_eq!(tx.output.len(), 1);
+        let fee_sats = 40_000u64.saturating_sub(tx.output[0].value.to_sat());
+        assert!(fee_sats >= u64::try_from(tx.vsize()).unwrap().saturating_mul(2));
+        assert!(!tx.input[0].script_sig.is_empty());
+        assert!(tx.input[0].witness.is_empty());
+        assert!(!tx.input[1].script_sig.is_empty());
+        assert_eq!(tx.input[1].witness.len(), 2);
+        assert!(tx.input[2].script_sig.is_empty());
+        assert_eq!(tx.input[2].witness.len(), 2);
+        assert!(tx.input[3].script_sig.is_empty());
+        assert_eq!(tx.input[3].witness.len(), 1);
+    }
+
+    #[test_all]
+    fn test_build_sweep_tx_rejects_dust() {
+        let secp = Secp256k1::new();
+        let key = key(false);
+        let utxos = utxos(&secp, &key);
+
+        let result = build_sweep_tx(&secp, &key, &utxos, destination(), 100, 294);
+
+        assert!(matches!(result, Err(SdkError::InsufficientFunds)));
+    }
+}
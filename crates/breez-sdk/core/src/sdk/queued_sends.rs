@@ -0,0 +1,304 @@
+use std::sync::atomic::Ordering;
+
+use platform_utils::tokio;
+use tracing::{Instrument, info, warn};
+
+use crate::{
+    SendPaymentMethod, SendPaymentRequest,
+    error::SdkError,
+    events::{QueuedPaymentOutcome, SdkEvent},
+    persist::{CachedQueuedSend, ObjectCacheRepository},
+};
+
+use super::{BreezSdk, payments::send};
+
+/// How long a queued payment is retried before it expires
+const QUEUED_SEND_EXPIRY_SECS: u64 = 24 * 60 * 60;
+
+/// Checks that `request` can be queued and sets its idempotency key if
+/// missing. Queued payments are retried with the same key, so a send that
+/// failed after the transfer was made is never paid twice.
+pub(super) fn ensure_queueable(request: &mut SendPaymentRequest) -> Result<(), SdkError> {
+    let prepare_response = &request.prepare_response;
+    if prepare_response.token_identifier.is_some()
+        || prepare_response.conversion_estimate.is_some()
+        || matches!(
+            prepare_response.payment_method,
+            SendPaymentMethod::CrossChainAddress { .. }
+        )
+    {
+        return Err(SdkError::InvalidInput(
+            "Payments with a token transfer leg and cross-chain sends can't be queued".to_string(),
+        ));
+    }
+    if request.idempotency_key.is_none() {
+        request.idempotency_key = Some(uuid::Uuid::new_v4().to_string());
+    }
+    Ok(())
+}
+
+impl BreezSdk {
+    /// Persists `request` to be sent once the network is reachable again.
+    /// Returns the id of the queued payment.
+    pub(super) async fn queue_send(&self, request: SendPaymentRequest) -> Result<String, SdkError> {
+        let id = request.idempotency_key.clone().ok_or(SdkError::Generic(
+            "Queued send has no idempotency key".to_string(),
+        ))?;
+        let now = self.now_secs();
+        let queued = CachedQueuedSend {
+            id: id.clone(),
+            expires_at: queued_send_expiry(&request, now),
+            request,
+            queued_at: now,
+        };
+        let _guard = self.queued_sends_lock.lock().await;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut queue = cache.fetch_queued_sends().await?;
+        queue.retain(|q| q.id != id);
+        queue.push(queued);
+        cache.save_queued_sends(&queue).await?;
+        info!("Queued send {id} until the network is reachable");
+        Ok(id)
+    }
+
+    /// Whether a send failed because the operators are unreachable. Spark
+    /// errors don't tell transport failures apart, so the coordinator is
+    /// probed to find out.
+    pub(super) async fn is_offline_error(&self, error: &SdkError) -> bool {
+        match error {
+            SdkError::NetworkError(_) => true,
+            SdkError::SparkError(_) => self
+                .spark_wallet
+                .query_wallet_settings()
+                .await
+                .is_err_and(|e| e.is_transport_error()),
+            _ => false,
+        }
+    }
+
+    /// Retries the queued payments in the background, e.g. once the
+    /// connection to the operators is restored. Does nothing if a retry is
+    /// already running, so a payment is never sent twice.
+    pub(crate) fn spawn_queued_sends_retry(&self) {
+        if self.queued_sends_retry_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let sdk = self.clone();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                if let Err(e) = sdk.retry_queued_sends().await {
+                    warn!("Failed to retry queued sends: {e}");
+                }
+                sdk.queued_sends_retry_running
+                    .store(false, Ordering::SeqCst);
+            }
+            .instrument(span),
+        );
+    }
+
+    /// Sends the queued payments in order, emitting a `QueuedPaymentResolved`
+    /// event for each one that is sent, fails or expires. Payments that fail
+    /// with a network error again stay queued.
+    async fn retry_queued_sends(&self) -> Result<(), SdkError> {
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let queue = cache.fetch_queued_sends().await?;
+        if queue.is_empty() {
+            return Ok(());
+        }
+        info!("Retrying {} queued sends", queue.len());
+        for queued in queue {
+            let outcome = if queued.expires_at <= self.now_secs() {
+                Some(QueuedPaymentOutcome::Expired)
+            } else {
+                self.send_queued(&queued).await
+            };
+            let Some(outcome) = outcome else {
+                continue;
+            };
+            // The queue may have changed while sending, so only this entry is removed
+            {
+                let _guard = self.queued_sends_lock.lock().await;
+                let mut queue = cache.fetch_queued_sends().await?;
+                queue.retain(|q| q.id != queued.id);
+                cache.save_queued_sends(&queue).await?;
+            }
+            info!("Queued send {} resolved: {outcome:?}", queued.id);
+            self.event_emitter
+                .emit(&SdkEvent::QueuedPaymentResolved {
+                    queued_payment_id: queued.id,
+                    outcome,
+                })
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Sends a queued payment. Returns `None` if the network is still
+    /// unreachable.
+    async fn send_queued(&self, queued: &CachedQueuedSend) -> Option<QueuedPaymentOutcome> {
+        let compliance_metadata = queued.request.compliance_metadata.clone();
+        match Box::pin(send::orchestrate_send(
+            self,
+            queued.request.clone(),
+            false,
+            None,
+        ))
+        .await
+        {
+            Ok(response) => {
+                if let Some(metadata) = compliance_metadata
+                    && let Err(e) = ObjectCacheRepository::new(self.storage.clone())
+                        .save_compliance_metadata(&response.payment.id, &metadata)
+                        .await
+                {
+                    warn!("Failed to save the compliance metadata of queued send: {e}");
+                }
+                Some(QueuedPaymentOutcome::Sent {
+                    payment: response.payment,
+                })
+            }
+            Err(e) => {
+                if self.is_offline_error(&e).await {
+                    info!("Queued send {} still offline: {e}", queued.id);
+                    return None;
+                }
+                Some(QueuedPaymentOutcome::Failed {
+                    error: e.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// The time a payment queued at `now` expires: after
+/// [`QUEUED_SEND_EXPIRY_SECS`], or earlier when the invoice it pays expires.
+fn queued_send_expiry(request: &SendPaymentRequest, now: u64) -> u64 {
+    let expiry = now.saturating_add(QUEUED_SEND_EXPIRY_SECS);
+    let invoice_expiry = match &request.prepare_response.payment_method {
+        SendPaymentMethod::Bolt11Invoice {
+            invoice_details, ..
+        } => Some(
+            invoice_details
+                .timestamp
+                .saturating_add(invoice_details.expiry),
+        ),
+        SendPaymentMethod::SparkInvoice {
+            spark_invoice_details,
+            ..
+        } => spark_invoice_details.expiry_time,
+        _ => None,
+    };
+    invoice_expiry.map_or(expiry, |invoice_expiry| invoice_expiry.min(expiry))
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+    use crate::{FeePolicy, PrepareSendPaymentResponse, sdk::payments::prepare::test_helpers};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn request(payment_method: SendPaymentMethod) -> SendPaymentRequest {
+        SendPaymentRequest {
+            prepare_response: PrepareSendPaymentResponse {
+                payment_method,
+                amount: 1_000,
+                token_identifier: None,
+                conversion_estimate: None,
+                fee_policy: FeePolicy::FeesExcluded,
+                bip21_details: None,
+                fee_breakdown: None,
+            },
+            options: None,
+            idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: Some(true),
+        }
+    }
+
+    fn bolt11_request(timestamp: u64, expiry: u64) -> SendPaymentRequest {
+        let mut invoice_details = test_helpers::create_test_bolt11_invoice();
+        invoice_details.timestamp = timestamp;
+        invoice_details.expiry = expiry;
+        request(SendPaymentMethod::Bolt11Invoice {
+            invoice_details,
+            spark_transfer_fee_sats: None,
+            lightning_fee_sats: 5,
+        })
+    }
+
+    fn spark_address_request(token_identifier: Option<String>) -> SendPaymentRequest {
+        let mut request = request(SendPaymentMethod::SparkAddress {
+            address: "spark_address".to_string(),
+            fee: 0,
+            token_identifier: token_identifier.clone(),
+        });
+        request.prepare_response.token_identifier = token_identifier;
+        request
+    }
+
+    #[test_all]
+    fn test_ensure_queueable_sets_idempotency_key() {
+        let mut request = spark_address_request(None);
+        ensure_queueable(&mut request).unwrap();
+        let key = request.idempotency_key.clone().unwrap();
+        assert!(uuid::Uuid::parse_str(&key).is_ok());
+
+        // An idempotency key set by the caller is kept
+        ensure_queueable(&mut request).unwrap();
+        assert_eq!(request.idempotency_key, Some(key));
+    }
+
+    #[test_all]
+    fn test_token_payment_not_queueable() {
+        let mut request = spark_address_request(Some("token".to_string()));
+        let result = ensure_queueable(&mut request);
+        assert!(matches!(result, Err(SdkError::InvalidInput(_))));
+        assert_eq!(request.idempotency_key, None);
+    }
+
+    #[test_all]
+    fn test_queued_send_expiry() {
+        let now = 1_000_000;
+        // Payments without an expiring invoice expire after a day
+        let request = spark_address_request(None);
+        assert_eq!(queued_send_expiry(&request, now), 1_086_400);
+
+        // Payments to an invoice expire with it
+        let request = bolt11_request(999_900, 3600);
+        assert_eq!(queued_send_expiry(&request, now), 1_003_500);
+
+        // Long-lived invoices are capped
+        let request = bolt11_request(now, 7 * 24 * 3600);
+        assert_eq!(queued_send_expiry(&request, now), 1_086_400);
+    }
+
+    #[test_all]
+    fn test_queued_send_roundtrip() {
+        let mut request = bolt11_request(100, 3600);
+        ensure_queueable(&mut request).unwrap();
+        let queued = CachedQueuedSend {
+            id: request.idempotency_key.clone().unwrap(),
+            request,
+            queued_at: 100,
+            expires_at: 3700,
+        };
+        let json = serde_json::to_string(&[queued.clone()]).unwrap();
+        let restored: Vec<CachedQueuedSend> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, queued.id);
+        assert_eq!(restored[0].request.idempotency_key, Some(queued.id));
+        assert_eq!(restored[0].request.queue_if_offline, Some(true));
+        assert!(matches!(
+            restored[0].request.prepare_response.payment_method,
+            SendPaymentMethod::Bolt11Invoice {
+                lightning_fee_sats: 5,
+                ..
+            }
+        ));
+    }
+}
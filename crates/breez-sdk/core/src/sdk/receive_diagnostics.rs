@@ -0,0 +1,282 @@
+use breez_sdk_common::input::parse_invoice;
+use spark_wallet::{LightningReceivePayment, LightningReceiveRequestStatus};
+use tracing::{debug, warn};
+
+use crate::{
+    DiagnoseReceiveRequest, DiagnoseReceiveResponse, Payment, PaymentDetails, PaymentType,
+    ReceiveStage, ReceiveStageUpdate,
+    error::SdkError,
+    events::SdkEvent,
+    persist::{CachedLightningReceive, ObjectCacheRepository},
+};
+
+use super::BreezSdk;
+
+/// Receives are kept this long after their invoice expires, to diagnose
+/// payments that arrived just before it.
+const RECEIVE_RETENTION_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Reports the stage the payment of a BOLT11 invoice created by this
+    /// wallet reached, combining the stages the SDK saw with the status of
+    /// the receive at the SSP.
+    pub async fn diagnose_receive(
+        &self,
+        request: DiagnoseReceiveRequest,
+    ) -> Result<DiagnoseReceiveResponse, SdkError> {
+        let invoice = parse_invoice(&request.invoice)
+            .ok_or_else(|| SdkError::InvalidInput("Invalid BOLT11 invoice".to_string()))?;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let Some(receive) = cache
+            .fetch_lightning_receives()
+            .await?
+            .into_iter()
+            .find(|r| r.payment_hash == invoice.payment_hash)
+        else {
+            return Err(SdkError::InvalidInput(
+                "The invoice wasn't created by this wallet or is too old".to_string(),
+            ));
+        };
+
+        let ssp_status = self
+            .spark_wallet
+            .fetch_lightning_receive_payment(&receive.ssp_id)
+            .await?
+            .map(|r| r.status);
+        let ssp_code = ssp_status.map(ssp_status_code);
+        let receive = match ssp_status.and_then(ssp_stage) {
+            Some(stage) => self
+                .advance_receive_stage(&receive.payment_hash, stage, ssp_code.clone())
+                .await?
+                .unwrap_or(receive),
+            None => receive,
+        };
+
+        let stage = receive
+            .trail
+            .last()
+            .map_or(ReceiveStage::InvoiceIssued, |u| u.stage);
+        let invoice_expired = self.now_secs() >= receive.expires_at;
+        Ok(DiagnoseReceiveResponse {
+            summary: summary(stage, ssp_code.as_deref(), invoice_expired),
+            payment_hash: receive.payment_hash,
+            stage,
+            ssp_status: ssp_code,
+            trail: receive.trail,
+            invoice_expired,
+        })
+    }
+}
+
+impl BreezSdk {
+    /// Starts tracking the stages of a receive whose invoice was just issued.
+    pub(super) async fn track_lightning_receive(&self, receive: &LightningReceivePayment) {
+        if let Err(e) = self.try_track_lightning_receive(receive).await {
+            warn!("Failed to track Lightning receive {}: {e}", receive.id);
+        }
+    }
+
+    async fn try_track_lightning_receive(
+        &self,
+        receive: &LightningReceivePayment,
+    ) -> Result<(), SdkError> {
+        let invoice = parse_invoice(&receive.invoice)
+            .ok_or_else(|| SdkError::Generic("Failed to parse the created invoice".to_string()))?;
+        let now = self.now_secs();
+        let update = ReceiveStageUpdate {
+            stage: ReceiveStage::InvoiceIssued,
+            timestamp: now,
+            ssp_status: Some(ssp_status_code(receive.status)),
+        };
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut receives = cache.fetch_lightning_receives().await?;
+        receives.retain(|r| r.expires_at.saturating_add(RECEIVE_RETENTION_SECS) > now);
+        receives.push(CachedLightningReceive {
+            payment_hash: invoice.payment_hash.clone(),
+            ssp_id: receive.id.clone(),
+            expires_at: invoice.timestamp.saturating_add(invoice.expiry),
+            trail: vec![update.clone()],
+        });
+        cache.save_lightning_receives(&receives).await?;
+        self.event_emitter
+            .emit(&SdkEvent::ReceiveStageChanged {
+                payment_hash: invoice.payment_hash,
+                update,
+            })
+            .await;
+        Ok(())
+    }
+
+    /// Records the stage a Lightning receive payment reached, if it is a
+    /// tracked receive.
+    pub(crate) async fn record_payment_receive_stage(
+        &self,
+        payment: &Payment,
+        stage: ReceiveStage,
+    ) {
+        let Some(PaymentDetails::Lightning { htlc_details, .. }) = &payment.details else {
+            return;
+        };
+        if payment.payment_type != PaymentType::Receive {
+            return;
+        }
+        if let Err(e) = self
+            .advance_receive_stage(&htlc_details.payment_hash, stage, None)
+            .await
+        {
+            warn!(
+                "Failed to record stage of receive {}: {e}",
+                htlc_details.payment_hash
+            );
+        }
+    }
+
+    /// Appends `stage` to the trail of a tracked receive and emits a
+    /// `ReceiveStageChanged` event, unless the receive already reached it.
+    /// Returns the receive, if tracked.
+    async fn advance_receive_stage(
+        &self,
+        payment_hash: &str,
+        stage: ReceiveStage,
+        ssp_status: Option<String>,
+    ) -> Result<Option<CachedLightningReceive>, SdkError> {
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut receives = cache.fetch_lightning_receives().await?;
+        let Some(receive) = receives.iter_mut().find(|r| r.payment_hash == payment_hash) else {
+            return Ok(None);
+        };
+        let reached = receive.trail.last().map(|u| u.stage);
+        if !advances(reached, stage) {
+            return Ok(Some(receive.clone()));
+        }
+        debug!("Receive {payment_hash} reached stage {stage:?}");
+        let update = ReceiveStageUpdate {
+            stage,
+            timestamp: self.now_secs(),
+            ssp_status,
+        };
+        receive.trail.push(update.clone());
+        let receive = receive.clone();
+        cache.save_lightning_receives(&receives).await?;
+        self.event_emitter
+            .emit(&SdkEvent::ReceiveStageChanged {
+                payment_hash: payment_hash.to_string(),
+                update,
+            })
+            .await;
+        Ok(Some(receive))
+    }
+}
+
+/// The position of a stage in the order receives go through.
+fn rank(stage: ReceiveStage) -> u8 {
+    match stage {
+        ReceiveStage::InvoiceIssued => 0,
+        ReceiveStage::HtlcSeen => 1,
+        ReceiveStage::ClaimAttempted => 2,
+        ReceiveStage::Settled | ReceiveStage::Failed => 3,
+    }
+}
+
+/// Whether a receive that `reached` a stage moves on to `stage`. Settled and
+/// failed receives don't move on.
+fn advances(reached: Option<ReceiveStage>, stage: ReceiveStage) -> bool {
+    reached.is_none_or(|reached| rank(stage) > rank(reached))
+}
+
+/// The status code of the SSP, as reported by its API.
+fn ssp_status_code(status: LightningReceiveRequestStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(ToString::to_string))
+        .unwrap_or_else(|| "UNKNOWN".to_string())
+}
+
+/// The stage a receive with the SSP `status` reached.
+fn ssp_stage(status: LightningReceiveRequestStatus) -> Option<ReceiveStage> {
+    match status {
+        LightningReceiveRequestStatus::InvoiceCreated => Some(ReceiveStage::InvoiceIssued),
+        LightningReceiveRequestStatus::HtlcReceived
+        | LightningReceiveRequestStatus::TransferCreated
+        | LightningReceiveRequestStatus::PaymentPreimagePending
+        | LightningReceiveRequestStatus::PaymentPreimageRecovered
+        | LightningReceiveRequestStatus::LightningPaymentReceived => Some(ReceiveStage::HtlcSeen),
+        LightningReceiveRequestStatus::TransferCompleted => Some(ReceiveStage::Settled),
+        LightningReceiveRequestStatus::TransferCreationFailed
+        | LightningReceiveRequestStatus::PaymentPreimageQueryingFailed
+        | LightningReceiveRequestStatus::PaymentPreimageRecoveringFailed
+        | LightningReceiveRequestStatus::TransferCanceled
+        | LightningReceiveRequestStatus::HtlcFailed
+        | LightningReceiveRequestStatus::TransferFailed
+        | LightningReceiveRequestStatus::RefundSigningCommitmentsQueryingFailed
+        | LightningReceiveRequestStatus::RefundSigningFailed => Some(ReceiveStage::Failed),
+        LightningReceiveRequestStatus::Unknown => None,
+    }
+}
+
+fn summary(stage: ReceiveStage, ssp_status: Option<&str>, invoice_expired: bool) -> String {
+    let ssp_status = ssp_status.unwrap_or("unknown");
+    match stage {
+        ReceiveStage::InvoiceIssued if invoice_expired => {
+            "The invoice expired without being paid".to_string()
+        }
+        ReceiveStage::InvoiceIssued => {
+            "Waiting for the payer, the SSP hasn't received an HTLC for the invoice".to_string()
+        }
+        ReceiveStage::HtlcSeen => format!(
+            "The SSP received the HTLC and hasn't completed the Spark transfer yet (SSP status {ssp_status})"
+        ),
+        ReceiveStage::ClaimAttempted => {
+            "The SDK is claiming the Spark transfer, syncing the wallet retries the claim"
+                .to_string()
+        }
+        ReceiveStage::Settled => "The payment was received".to_string(),
+        ReceiveStage::Failed => format!("The SSP failed the payment (SSP status {ssp_status})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_advances() {
+        assert!(advances(None, ReceiveStage::InvoiceIssued));
+        assert!(advances(
+            Some(ReceiveStage::InvoiceIssued),
+            ReceiveStage::ClaimAttempted
+        ));
+        assert!(!advances(
+            Some(ReceiveStage::ClaimAttempted),
+            ReceiveStage::HtlcSeen
+        ));
+        assert!(!advances(Some(ReceiveStage::Settled), ReceiveStage::Failed));
+    }
+
+    #[test_all]
+    fn test_ssp_status() {
+        assert_eq!(
+            ssp_status_code(LightningReceiveRequestStatus::TransferCreationFailed),
+            "TRANSFER_CREATION_FAILED"
+        );
+        assert_eq!(
+            ssp_status_code(LightningReceiveRequestStatus::Unknown),
+            "UNKNOWN"
+        );
+        assert_eq!(
+            ssp_stage(LightningReceiveRequestStatus::PaymentPreimagePending),
+            Some(ReceiveStage::HtlcSeen)
+        );
+        assert_eq!(
+            ssp_stage(LightningReceiveRequestStatus::HtlcFailed),
+            Some(ReceiveStage::Failed)
+        );
+        assert_eq!(ssp_stage(LightningReceiveRequestStatus::Unknown), None);
+    }
+}
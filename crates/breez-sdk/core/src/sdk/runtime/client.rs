@@ -11,7 +11,7 @@ use tracing::{Instrument, debug, error, info, trace};
 
 use crate::utils::token::{token_transaction_to_payments, token_tx_inputs_are_ours};
 use crate::{
-    GetInfoRequest, GetInfoResponse, Payment,
+    GetInfoRequest, GetInfoResponse, Payment, ReceiveStage,
     error::SdkError,
     events::{EventListener, SdkEvent},
     persist::ObjectCacheRepository,
@@ -45,7 +45,18 @@ impl RuntimeProfile for ClientRuntime {
         // connects) lands in the runtime loop and drives the initial Full sync.
         sdk.spark_wallet.start_background_processing().await;
 
+        sdk.spawn_stuck_payment_watchdog();
+        sdk.spawn_withdraw_tracker();
+        sdk.spawn_backup_task();
+        sdk.service_status
+            .spawn_polling(sdk.shutdown_sender.subscribe());
+        // The remaining services sign on their own
+        if sdk.watch_only {
+            return;
+        }
         sdk.try_recover_lightning_address();
+        sdk.spawn_amount_range_receive_tracker();
+        sdk.spawn_emergency_exit_refresh();
         spawn_conversion_refunder(
             Arc::clone(&sdk.token_converter),
             sdk.shutdown_sender.subscribe(),
@@ -81,15 +92,15 @@ impl RuntimeProfile for ClientRuntime {
                 })?;
         }
 
-        let account_info = ObjectCacheRepository::new(sdk.storage.clone())
-            .fetch_account_info()
-            .await?
-            .unwrap_or_default();
+        let cache = ObjectCacheRepository::new(sdk.storage.clone());
+        let account_info = cache.fetch_account_info().await?.unwrap_or_default();
+        let sync_info = cache.fetch_sync_info().await?.unwrap_or_default();
 
         Ok(GetInfoResponse {
             identity_pubkey: sdk.spark_wallet.get_identity_public_key().to_string(),
             balance_sats: account_info.balance_sats,
             token_balances: account_info.token_balances,
+            history_partially_synced: sync_info.history_partial,
         })
     }
 
@@ -107,7 +118,6 @@ fn spawn_client_runtime_loop(sdk: &BreezSdk, initial_synced_sender: watch::Sende
     let mut wallet_events = sdk.spark_wallet.subscribe_events();
     let mut sync_requests = sdk.sync_coordinator.subscribe();
     let mut last_sync_time = SystemTime::now();
-    let sync_interval = u64::from(sdk.config.sync_interval_secs);
     let span = tracing::Span::current();
 
     tokio::spawn(
@@ -145,8 +155,17 @@ fn spawn_client_runtime_loop(sdk: &BreezSdk, initial_synced_sender: watch::Sende
 
                     () = tokio::time::sleep(Duration::from_secs(10)) => {
                         let now = SystemTime::now();
-                        if let Ok(elapsed) = now.duration_since(last_sync_time) && elapsed.as_secs() >= sync_interval {
-                            sdk.sync_coordinator.trigger_sync_no_wait(SyncType::Full, false).await;
+                        let config = sdk.config.get();
+                        let sync_interval = Duration::from_secs(u64::from(config.sync_interval_secs));
+                        let sync_interval = config.polling_interval(sync_interval).as_secs();
+                        // An interval of 0 turns the scheduled syncs off
+                        if sync_interval > 0 && let Ok(elapsed) = now.duration_since(last_sync_time) && elapsed.as_secs() >= sync_interval {
+                            // Scheduled syncs can wait for the maintenance to end
+                            if let Some(window) = sdk.service_status.active_maintenance() {
+                                debug!("Deferring scheduled sync until maintenance ends at {}", window.ends_at);
+                            } else {
+                                sdk.sync_coordinator.trigger_sync_no_wait(SyncType::Full, false).await;
+                            }
                         }
                     }
                 }
@@ -268,6 +287,9 @@ async fn handle_wallet_event(sdk: &BreezSdk, event: WalletEvent) -> bool {
         }
         WalletEvent::StreamConnected => {
             info!("Stream connected");
+            // The operators are reachable again, so payments queued while
+            // offline can be sent
+            sdk.spawn_queued_sends_retry();
             false
         }
         WalletEvent::StreamDisconnected => {
@@ -286,6 +308,8 @@ async fn handle_wallet_event(sdk: &BreezSdk, event: WalletEvent) -> bool {
                 cleanup_claimed_deposit(sdk, &tx_id, vout).await;
             }
             if let Ok(payment) = Payment::try_from(transfer) {
+                sdk.record_payment_receive_stage(&payment, ReceiveStage::Settled)
+                    .await;
                 sdk.finalize_payment(payment).await
             } else {
                 false
@@ -295,6 +319,8 @@ async fn handle_wallet_event(sdk: &BreezSdk, event: WalletEvent) -> bool {
             info!("Transfer claim starting");
             let mut payment_emitted = false;
             if let Ok(mut payment) = Payment::try_from(transfer) {
+                sdk.record_payment_receive_stage(&payment, ReceiveStage::ClaimAttempted)
+                    .await;
                 // Persist before syncing metadata so the Pending payment is not
                 // delayed by the metadata fetch.
                 let should_emit = match sdk.storage.apply_payment_update(payment.clone()).await {
@@ -309,7 +335,8 @@ async fn handle_wallet_event(sdk: &BreezSdk, event: WalletEvent) -> bool {
 
                 // Drop this Pending event if sync already saw the transfer Completed.
                 if should_emit {
-                    get_payment_and_emit_event(&sdk.storage, &sdk.event_emitter, payment).await;
+                    get_payment_and_emit_event(&sdk.storage, &sdk.event_emitter, payment, None)
+                        .await;
                     payment_emitted = true;
                 }
             }
@@ -447,6 +474,7 @@ impl EventListener for ClientSyncListener {
         match event {
             SdkEvent::PaymentSucceeded { .. }
             | SdkEvent::PaymentPending { .. }
+            | SdkEvent::PaymentWaitingConfirmation { .. }
             | SdkEvent::PaymentFailed { .. }
             | SdkEvent::ClaimedDeposits { .. } => {
                 self.sync_coordinator
@@ -63,6 +63,8 @@ impl RuntimeProfile for ServerRuntime {
             identity_pubkey: sdk.spark_wallet.get_identity_public_key().to_string(),
             balance_sats,
             token_balances,
+            // Fast sync needs background tasks, so the history is always fully synced
+            history_partially_synced: false,
         })
     }
 
@@ -87,7 +89,7 @@ impl crate::events::RuntimeEventHandler for ServerRuntimeEventHandler {
                 should_emit_event,
             } => {
                 if should_emit_event {
-                    get_payment_and_emit_event(&self.storage, emitter, *payment).await;
+                    get_payment_and_emit_event(&self.storage, emitter, *payment, None).await;
                 }
             }
             RuntimeEvent::StableBalanceConversionCompleted => {}
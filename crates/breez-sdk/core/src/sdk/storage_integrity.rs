@@ -0,0 +1,40 @@
+use tracing::{info, warn};
+
+use crate::{
+    CheckStorageIntegrityRequest, CheckStorageIntegrityResponse,
+    error::SdkError,
+    persist::{CachedSyncInfo, ObjectCacheRepository},
+};
+
+use super::{BreezSdk, SyncType};
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Checks the storage for corruption, invalid JSON values and orphaned
+    /// records.
+    ///
+    /// With `repair`, orphaned records and unreadable cached values are
+    /// deleted, and the sync cursor is reset so a full sync rebuilds the
+    /// balances and payment history from the wallet state. Corruption
+    /// reported by the database engine can't be repaired.
+    pub async fn check_storage_integrity(
+        &self,
+        request: CheckStorageIntegrityRequest,
+    ) -> Result<CheckStorageIntegrityResponse, SdkError> {
+        let mut issues = self.storage.check_integrity(request.repair).await?;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        issues.extend(cache.check_integrity(request.repair).await?);
+        for issue in &issues {
+            warn!("Storage integrity issue: {issue:?}");
+        }
+
+        if request.repair {
+            info!("Resetting the sync cursor to rebuild the derived state");
+            cache.save_sync_info(&CachedSyncInfo::default()).await?;
+            self.sync_coordinator
+                .trigger_sync_no_wait(SyncType::Full, true)
+                .await;
+        }
+        Ok(CheckStorageIntegrityResponse { issues })
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use platform_utils::time::{Duration, UNIX_EPOCH};
+use platform_utils::tokio;
+use tokio::{select, time::sleep};
+use tracing::{Instrument, debug, info, warn};
+
+use crate::{
+    Payment, PaymentDetails, PaymentMethod, PaymentStatus, PaymentType, RecoverStuckPaymentRequest,
+    RecoverStuckPaymentResponse, StorageListPaymentsRequest, StuckPaymentRecommendation,
+    SyncWalletRequest, error::SdkError, events::SdkEvent,
+};
+
+use super::BreezSdk;
+
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(300);
+/// Lightning sends usually settle within seconds.
+const LIGHTNING_STUCK_SECS: u64 = 10 * 60;
+/// Deposits are claimed once their transaction has 3 confirmations.
+const ONCHAIN_STUCK_CONFIRMATIONS: u64 = 6;
+/// A payment still pending after this long needs support.
+const ESCALATION_SECS: u64 = 24 * 60 * 60;
+const BLOCK_INTERVAL_SECS: u64 = 600;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Runs the recovery action for a payment stuck in `Pending`.
+    ///
+    /// Every stuck payment is recovered by syncing the wallet, which refreshes
+    /// its status. The response carries the recommendation if the payment is
+    /// still stuck afterwards.
+    pub async fn recover_stuck_payment(
+        &self,
+        request: RecoverStuckPaymentRequest,
+    ) -> Result<RecoverStuckPaymentResponse, SdkError> {
+        let payment = self
+            .storage
+            .get_payment_by_id(request.payment_id.clone())
+            .await?;
+        if payment.status == PaymentStatus::Pending {
+            info!("Syncing to recover stuck payment {}", payment.id);
+            self.sync_wallet(SyncWalletRequest {}).await?;
+        }
+
+        let payment = self.storage.get_payment_by_id(request.payment_id).await?;
+        let recommendation = self.stuck_payment_recommendation(&payment).await;
+        Ok(RecoverStuckPaymentResponse {
+            payment,
+            recommendation,
+        })
+    }
+}
+
+impl BreezSdk {
+    /// Periodically checks the pending payments until shutdown, emitting
+    /// `PaymentStuck` when a recommendation is new or escalates.
+    pub(crate) fn spawn_stuck_payment_watchdog(&self) {
+        let sdk = self.clone();
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                let mut reported = HashMap::new();
+                loop {
+                    select! {
+                        _ = shutdown_receiver.changed() => {
+                            debug!("Stuck payment watchdog stopped on shutdown");
+                            return;
+                        }
+                        () = sleep(sdk.config.get().polling_interval(WATCHDOG_INTERVAL)) => {}
+                    }
+                    if let Err(e) = sdk.check_stuck_payments(&mut reported).await {
+                        warn!("Failed to check for stuck payments: {e}");
+                    }
+                }
+            }
+            .instrument(span),
+        );
+    }
+
+    async fn check_stuck_payments(
+        &self,
+        reported: &mut HashMap<String, StuckPaymentRecommendation>,
+    ) -> Result<(), SdkError> {
+        let pending = self
+            .storage
+            .list_payments(StorageListPaymentsRequest {
+                status_filter: Some(vec![PaymentStatus::Pending]),
+                ..Default::default()
+            })
+            .await?;
+        // Forget payments that settled, so they are reported again if needed
+        reported.retain(|id, _| pending.iter().any(|p| &p.id == id));
+
+        for payment in pending {
+            let Some(recommendation) = self.stuck_payment_recommendation(&payment).await else {
+                continue;
+            };
+            if reported.get(&payment.id) == Some(&recommendation) {
+                continue;
+            }
+            info!(
+                "Payment {} is stuck, recommending {recommendation:?}",
+                payment.id
+            );
+            self.event_emitter
+                .emit(&SdkEvent::PaymentStuck {
+                    payment_id: payment.id.clone(),
+                    recommendation,
+                })
+                .await;
+            reported.insert(payment.id, recommendation);
+        }
+        Ok(())
+    }
+
+    async fn stuck_payment_recommendation(
+        &self,
+        payment: &Payment,
+    ) -> Option<StuckPaymentRecommendation> {
+        if payment.status != PaymentStatus::Pending {
+            return None;
+        }
+        let now = self
+            .clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let confirmations = match &payment.details {
+            Some(PaymentDetails::Deposit { tx_id, .. } | PaymentDetails::Withdraw { tx_id }) => {
+                self.estimated_confirmations(tx_id, now).await
+            }
+            _ => None,
+        };
+        stuck_recommendation(payment, now, confirmations)
+    }
+
+    /// Estimates the confirmations of a transaction from its block time, as
+    /// the chain service doesn't report the chain tip.
+    async fn estimated_confirmations(&self, tx_id: &str, now: u64) -> Option<u64> {
+        let status = self
+            .chain_service
+            .get_transaction_status(tx_id.to_string())
+            .await
+            .inspect_err(|e| debug!("Failed to get the status of transaction {tx_id}: {e}"))
+            .ok()?;
+        let block_time = status.block_time.filter(|_| status.confirmed)?;
+        Some(confirmations_since(block_time, now))
+    }
+}
+
+/// Estimates the confirmations of a transaction confirmed at `block_time`.
+pub(super) fn confirmations_since(block_time: u64, now: u64) -> u64 {
+    now.saturating_sub(block_time) / BLOCK_INTERVAL_SECS + 1
+}
+
+/// The recommendation for a pending payment, if it is stuck.
+///
+/// `confirmations` are those of the transaction of an on-chain payment, if it
+/// is confirmed.
+fn stuck_recommendation(
+    payment: &Payment,
+    now: u64,
+    confirmations: Option<u64>,
+) -> Option<StuckPaymentRecommendation> {
+    let age = now.saturating_sub(payment.timestamp);
+    let stuck = match payment.method {
+        // Lightning receives stay pending until a held HTLC is claimed
+        PaymentMethod::Lightning => {
+            payment.payment_type == PaymentType::Send && age >= LIGHTNING_STUCK_SECS
+        }
+        PaymentMethod::Deposit => confirmations.is_some_and(|c| c >= ONCHAIN_STUCK_CONFIRMATIONS),
+        // A withdrawal that never confirms is stuck too
+        PaymentMethod::Withdraw => {
+            confirmations.is_some_and(|c| c >= ONCHAIN_STUCK_CONFIRMATIONS)
+                || age >= ESCALATION_SECS
+        }
+        // Spark and token sends stay pending until the receiver claims them
+        PaymentMethod::Spark | PaymentMethod::Token | PaymentMethod::Unknown => false,
+    };
+    if !stuck {
+        return None;
+    }
+    if age >= ESCALATION_SECS {
+        Some(StuckPaymentRecommendation::ContactSupport)
+    } else {
+        Some(StuckPaymentRecommendation::Sync)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+    use crate::DisplayAmount;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn pending_payment(method: PaymentMethod, payment_type: PaymentType, age: u64) -> Payment {
+        Payment {
+            id: "payment".to_string(),
+            payment_type,
+            status: PaymentStatus::Pending,
+            amount: 1000,
+            fees: 0,
+            timestamp: NOW - age,
+            method,
+            details: None,
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    #[test_all]
+    fn test_lightning_send_stuck_after_threshold() {
+        let fresh = pending_payment(PaymentMethod::Lightning, PaymentType::Send, 60);
+        assert_eq!(stuck_recommendation(&fresh, NOW, None), None);
+
+        let stuck = pending_payment(PaymentMethod::Lightning, PaymentType::Send, 3600);
+        assert_eq!(
+            stuck_recommendation(&stuck, NOW, None),
+            Some(StuckPaymentRecommendation::Sync)
+        );
+
+        let escalated =
+            pending_payment(PaymentMethod::Lightning, PaymentType::Send, ESCALATION_SECS);
+        assert_eq!(
+            stuck_recommendation(&escalated, NOW, None),
+            Some(StuckPaymentRecommendation::ContactSupport)
+        );
+
+        let receive = pending_payment(PaymentMethod::Lightning, PaymentType::Receive, 3600);
+        assert_eq!(stuck_recommendation(&receive, NOW, None), None);
+    }
+
+    #[test_all]
+    fn test_deposit_stuck_by_confirmations() {
+        let deposit = pending_payment(PaymentMethod::Deposit, PaymentType::Receive, 3600);
+        assert_eq!(stuck_recommendation(&deposit, NOW, None), None);
+        assert_eq!(stuck_recommendation(&deposit, NOW, Some(3)), None);
+        assert_eq!(
+            stuck_recommendation(&deposit, NOW, Some(ONCHAIN_STUCK_CONFIRMATIONS)),
+            Some(StuckPaymentRecommendation::Sync)
+        );
+    }
+
+    #[test_all]
+    fn test_unconfirmed_withdrawal_escalates() {
+        let recent = pending_payment(PaymentMethod::Withdraw, PaymentType::Send, 3600);
+        assert_eq!(stuck_recommendation(&recent, NOW, None), None);
+
+        let old = pending_payment(PaymentMethod::Withdraw, PaymentType::Send, ESCALATION_SECS);
+        assert_eq!(
+            stuck_recommendation(&old, NOW, None),
+            Some(StuckPaymentRecommendation::ContactSupport)
+        );
+    }
+
+    #[test_all]
+    fn test_spark_send_never_stuck() {
+        let spark = pending_payment(PaymentMethod::Spark, PaymentType::Send, ESCALATION_SECS);
+        assert_eq!(stuck_recommendation(&spark, NOW, None), None);
+    }
+}
@@ -1,24 +1,40 @@
-use platform_utils::time::{Instant, SystemTime};
+use platform_utils::time::{Duration, Instant};
 use platform_utils::tokio;
-use std::sync::Arc;
-use tracing::{debug, error, info, trace, warn};
+use std::{
+    collections::HashMap,
+    sync::{Arc, atomic::Ordering},
+};
+use tokio::select;
+use tracing::{Instrument, debug, error, info, trace, warn};
 
 use super::{BreezSdk, CLAIM_TX_SIZE_VBYTES, SYNC_PAGING_LIMIT, SyncType, parse_input};
 use crate::{
-    DepositInfo, InputType, MaxFee, PaymentDetails, PaymentType,
+    DepositClaimError, DepositClaimPolicy, DepositInfo, InputType, PaymentDetails, PaymentType,
     error::SdkError,
     events::{InternalSyncedEvent, SdkEvent},
     lnurl::ListMetadataRequest,
-    models::{Payment, SyncWalletRequest, SyncWalletResponse},
+    models::{
+        Payment, SyncMode, SyncTrigger, SyncWalletRequest, SyncWalletResponse, TriggerSyncRequest,
+    },
     persist::{ObjectCacheRepository, UpdateDepositPayload},
     sync::SparkSyncService,
     utils::{
+        deposit_addresses::update_monitored_addresses,
         deposit_chain_syncer::{DepositChainSyncer, TxOutput},
         payments::update_balances,
-        utxo_fetcher::DetailedUtxo,
+        utxo_fetcher::{CachedUtxoFetcher, DetailedUtxo},
     },
 };
 
+/// A submitted static deposit claim.
+pub(super) struct StaticDepositClaim {
+    pub(super) transfer_id: String,
+    /// The fee paid for the claim
+    pub(super) fee_sats: u64,
+    /// The maximum fee the claim was allowed to pay
+    pub(super) max_fee_sats: u64,
+}
+
 impl BreezSdk {
     pub(in crate::sdk) async fn sync_single_lnurl_metadata(&self, payment: &mut Payment) {
         if payment.payment_type != PaymentType::Receive {
@@ -111,11 +127,9 @@ impl BreezSdk {
         force: bool,
     ) -> Result<(), SdkError> {
         let cache = ObjectCacheRepository::new(self.storage.clone());
-        let sync_interval_secs = u64::from(self.config.sync_interval_secs);
+        let sync_interval_secs = u64::from(self.config.get().sync_interval_secs);
 
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map_or(0, |d| d.as_secs());
+        let now = self.now_secs();
 
         // Skip if we synced recently (unless forced).
         if !force
@@ -262,20 +276,133 @@ impl BreezSdk {
     /// Synchronizes wallet state to persistent storage, making sure we have the latest balances and payments.
     pub(super) async fn sync_wallet_state_to_storage(&self) -> Result<(), SdkError> {
         update_balances(self.spark_wallet.clone(), self.storage.clone()).await?;
+        if let Err(e) = self.emit_token_balance_changes().await {
+            error!("Failed to emit token balance changes: {e:?}");
+        }
 
         let initial_sync_complete = *self.initial_synced_watcher.borrow();
+        // The backfill runs in the background, so a fast sync needs background tasks
+        let config = self.config.get();
+        let fast_start_payments = match config.sync_mode {
+            SyncMode::Fast { recent_payments } if config.background_tasks_enabled => {
+                Some(u64::from(recent_payments))
+            }
+            _ => None,
+        };
+        let capture = self.sync_capture.lock().await.clone();
         let sync_service = SparkSyncService::new(
             self.spark_wallet.clone(),
             self.storage.clone(),
             self.event_emitter.clone(),
+        )
+        .with_capture(capture.as_ref())
+        .with_sync_info_lock(self.sync_info_lock.clone());
+        let history_partial = sync_service
+            .sync_payments(initial_sync_complete, fast_start_payments)
+            .await?;
+        if history_partial {
+            self.spawn_history_backfill();
+        }
+
+        Ok(())
+    }
+
+    /// Backfills the payments older than the ones loaded by a fast sync,
+    /// unless a backfill is already running. A failed backfill is resumed by
+    /// the next sync.
+    fn spawn_history_backfill(&self) {
+        if self.history_backfill_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let sdk = self.clone();
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                let capture = sdk.sync_capture.lock().await.clone();
+                let sync_service = SparkSyncService::new(
+                    sdk.spark_wallet.clone(),
+                    sdk.storage.clone(),
+                    sdk.event_emitter.clone(),
+                )
+                .with_capture(capture.as_ref())
+                .with_sync_info_lock(sdk.sync_info_lock.clone());
+                let initial_sync_complete = *sdk.initial_synced_watcher.borrow();
+                select! {
+                    _ = shutdown_receiver.changed() => {
+                        debug!("History backfill stopped on shutdown");
+                    }
+                    res = sync_service.backfill_history(initial_sync_complete) => {
+                        if let Err(e) = res {
+                            warn!("Failed to backfill the payment history: {e}");
+                        }
+                    }
+                }
+                sdk.history_backfill_running.store(false, Ordering::SeqCst);
+            }
+            .instrument(span),
         );
-        sync_service.sync_payments(initial_sync_complete).await?;
+    }
+
+    /// Updates the deposit addresses monitored for rotation with the deposits
+    /// in progress: the ones `seen` in this sync and the ones being refunded.
+    async fn update_monitored_deposit_addresses(
+        &self,
+        seen: Vec<DetailedUtxo>,
+    ) -> Result<(), SdkError> {
+        let object_repository = ObjectCacheRepository::new(self.storage.clone());
+        let monitored = object_repository
+            .fetch_monitored_deposit_addresses()
+            .await?;
+        if monitored.is_empty() {
+            return Ok(());
+        }
+
+        let mut deposits = seen;
+        let utxo_fetcher = CachedUtxoFetcher::new(self.chain_service.clone(), self.storage.clone());
+        for deposit in self.storage.list_deposits().await? {
+            if deposit.refund_tx_id.is_some() {
+                deposits.push(
+                    utxo_fetcher
+                        .fetch_detailed_utxo(&deposit.txid, deposit.vout)
+                        .await?,
+                );
+            }
+        }
+        object_repository
+            .save_monitored_deposit_addresses(&update_monitored_addresses(monitored, &deposits))
+            .await?;
+        Ok(())
+    }
 
+    /// Emits `TokenBalanceChanged` events for the token balances that changed
+    /// since the last report. The first report only records the balances.
+    async fn emit_token_balance_changes(&self) -> Result<(), SdkError> {
+        let object_repository = ObjectCacheRepository::new(self.storage.clone());
+        let Some(account_info) = object_repository.fetch_account_info().await? else {
+            return Ok(());
+        };
+        let balances: HashMap<String, u128> = account_info
+            .token_balances
+            .into_iter()
+            .map(|(identifier, balance)| (identifier, balance.balance))
+            .collect();
+        if let Some(reported) = object_repository.fetch_reported_token_balances().await? {
+            for event in SdkEvent::token_balance_changes(&reported, &balances) {
+                self.event_emitter.emit(&event).await;
+            }
+        }
+        object_repository
+            .save_reported_token_balances(&balances)
+            .await?;
         Ok(())
     }
 
     pub(super) async fn check_and_claim_static_deposits(&self) -> Result<(), SdkError> {
         self.maybe_ensure_spark_private_mode_initialized().await?;
+        if let Err(e) = self.retry_claim_fee_sponsorships().await {
+            warn!("Failed to retry claim fee sponsorships: {e}");
+        }
         let existing_deposits = self.storage.list_deposits().await?;
         let existing_keys: std::collections::HashSet<TxOutput> = existing_deposits
             .iter()
@@ -293,6 +420,11 @@ impl BreezSdk {
         .sync()
         .await?;
 
+        let seen: Vec<DetailedUtxo> = all_utxos.iter().map(|(u, _)| u.clone()).collect();
+        if let Err(e) = self.update_monitored_deposit_addresses(seen).await {
+            warn!("Failed to update the monitored deposit addresses: {e:?}");
+        }
+
         // Emit NewDeposits for any deposits not previously known
         let new_deposits: Vec<DepositInfo> = all_utxos
             .iter()
@@ -309,6 +441,10 @@ impl BreezSdk {
                 .emit(&SdkEvent::NewDeposits { new_deposits })
                 .await;
         }
+        // A watch-only wallet monitors its deposits but can't claim them
+        if self.watch_only {
+            return Ok(());
+        }
 
         // Only claim UTXOs with sufficient confirmations
         let to_claim: Vec<_> = all_utxos
@@ -317,27 +453,107 @@ impl BreezSdk {
             .map(|(u, _)| u)
             .collect();
 
+        // Deposits whose last claim failed on the fee are monitored: every
+        // evaluation of their claim fee is reported with an event, up to the
+        // one that claims them.
+        let fee_blocked_keys: std::collections::HashSet<TxOutput> = existing_deposits
+            .iter()
+            .filter(|d| {
+                matches!(
+                    d.claim_error,
+                    Some(DepositClaimError::MaxDepositClaimFeeExceeded { .. })
+                )
+            })
+            .map(|d| TxOutput {
+                txid: d.txid.clone(),
+                vout: d.vout,
+            })
+            .collect();
+
+        // Deposits that weren't sent to this wallet can never be claimed, so
+        // they are reported once and not retried.
+        let foreign_keys: std::collections::HashSet<TxOutput> = existing_deposits
+            .iter()
+            .filter(|d| matches!(d.claim_error, Some(DepositClaimError::NotOurDeposit { .. })))
+            .map(|d| TxOutput {
+                txid: d.txid.clone(),
+                vout: d.vout,
+            })
+            .collect();
+
+        let cache = ObjectCacheRepository::new(Arc::clone(&self.storage));
         let mut claimed_deposits: Vec<DepositInfo> = Vec::new();
         let mut unclaimed_deposits: Vec<DepositInfo> = Vec::new();
         for detailed_utxo in to_claim {
-            match self
-                .claim_utxo(&detailed_utxo, self.config.max_deposit_claim_fee.clone())
-                .await
-            {
-                Ok(_) => {
+            let txid = detailed_utxo.txid.to_string();
+            if foreign_keys.contains(&TxOutput {
+                txid: txid.clone(),
+                vout: detailed_utxo.vout,
+            }) {
+                debug!("Skipping foreign deposit {txid}:{}", detailed_utxo.vout);
+                continue;
+            }
+            // A fee target set with `set_deposit_claim_override`, or when a
+            // manual claim failed on the fee, takes precedence over the policy.
+            let fee_target = cache
+                .fetch_deposit_claim_fee_target(&txid, detailed_utxo.vout)
+                .await?;
+            let monitored = fee_target.is_some()
+                || fee_blocked_keys.contains(&TxOutput {
+                    txid: txid.clone(),
+                    vout: detailed_utxo.vout,
+                });
+            let policy = fee_target.clone().map_or_else(
+                || self.config.get().effective_deposit_claim_policy(),
+                |max_fee| DepositClaimPolicy::MaxFee { max_fee },
+            );
+            match self.claim_utxo(&detailed_utxo, &policy).await {
+                Ok(claim) => {
                     info!("Claimed utxo {}:{}", detailed_utxo.txid, detailed_utxo.vout);
                     self.storage
-                        .delete_deposit(detailed_utxo.txid.to_string(), detailed_utxo.vout)
+                        .delete_deposit(txid.clone(), detailed_utxo.vout)
+                        .await?;
+                    cache
+                        .delete_deposit_claim_fee_target(&txid, detailed_utxo.vout)
                         .await?;
-                    claimed_deposits.push(detailed_utxo.into_deposit_info(true));
+                    let deposit = detailed_utxo.into_deposit_info(true);
+                    if monitored {
+                        self.event_emitter
+                            .emit(&SdkEvent::DepositClaimFeeEvaluated {
+                                deposit: deposit.clone(),
+                                required_fee_sats: claim.fee_sats,
+                                max_fee_sats: Some(claim.max_fee_sats),
+                                claimed: true,
+                            })
+                            .await;
+                    }
+                    claimed_deposits.push(deposit);
                 }
                 Err(e) => {
                     warn!(
                         "Failed to claim utxo {}:{}: {e}",
                         detailed_utxo.txid, detailed_utxo.vout
                     );
-                    unclaimed_deposits
-                        .push(self.record_unclaimed_deposit(&detailed_utxo, e).await?);
+                    let mut deposit = self.record_unclaimed_deposit(&detailed_utxo, e).await?;
+                    deposit.claim_max_fee = fee_target;
+                    if let Some(DepositClaimError::MaxDepositClaimFeeExceeded {
+                        max_fee,
+                        required_fee_sats,
+                        ..
+                    }) = &deposit.claim_error
+                    {
+                        self.event_emitter
+                            .emit(&SdkEvent::DepositClaimFeeEvaluated {
+                                deposit: deposit.clone(),
+                                required_fee_sats: *required_fee_sats,
+                                max_fee_sats: max_fee
+                                    .as_ref()
+                                    .map(|fee| fee.to_sats(CLAIM_TX_SIZE_VBYTES)),
+                                claimed: false,
+                            })
+                            .await;
+                    }
+                    unclaimed_deposits.push(deposit);
                 }
             }
         }
@@ -425,27 +641,94 @@ impl BreezSdk {
         Ok(())
     }
 
-    /// Submits a static deposit claim for `detailed_utxo` and returns the
-    /// resulting transfer id.
+    /// Fails with [`SdkError::NotOurDeposit`] if the deposit wasn't sent to one
+    /// of this wallet's static deposit addresses, e.g. because another
+    /// wallet's address was used by mistake.
+    pub(super) async fn ensure_own_deposit(
+        &self,
+        detailed_utxo: &DetailedUtxo,
+    ) -> Result<(), SdkError> {
+        let addresses = self
+            .spark_wallet
+            .list_static_deposit_addresses(None)
+            .await?;
+        if detailed_utxo.pays_to(&addresses.items) {
+            return Ok(());
+        }
+        warn!(
+            "Deposit {}:{} was not sent to an address of this wallet",
+            detailed_utxo.txid, detailed_utxo.vout
+        );
+        Err(SdkError::NotOurDeposit {
+            tx: detailed_utxo.txid.to_string(),
+            vout: detailed_utxo.vout,
+        })
+    }
+
+    /// Submits a static deposit claim for `detailed_utxo` if `policy` allows
+    /// its fee.
     pub(super) async fn claim_utxo(
         &self,
         detailed_utxo: &DetailedUtxo,
-        max_claim_fee: Option<MaxFee>,
-    ) -> Result<String, SdkError> {
+        policy: &DepositClaimPolicy,
+    ) -> Result<StaticDepositClaim, SdkError> {
         info!(
             "Fetching static deposit claim quote for deposit tx {}:{} and amount: {}",
             detailed_utxo.txid, detailed_utxo.vout, detailed_utxo.value
         );
+        self.ensure_own_deposit(detailed_utxo).await?;
         let quote = self
             .spark_wallet
             .fetch_static_deposit_claim_quote(detailed_utxo.tx.clone(), Some(detailed_utxo.vout))
             .await?;
 
         let spark_requested_fee_sats = detailed_utxo.value.saturating_sub(quote.credit_amount_sats);
+        // The fee is deducted from the deposit even when sponsored, and the
+        // sponsor only reimburses it after the claim, so the policy still applies.
+        let max_fee_sats = self
+            .allowed_claim_fee_sats(detailed_utxo, policy, spark_requested_fee_sats)
+            .await?;
+        let sponsorship = self
+            .sponsored_claim_fee(detailed_utxo, spark_requested_fee_sats)
+            .await?;
+        if sponsorship.is_some() {
+            info!("Claim fee of {spark_requested_fee_sats} sats is sponsored");
+        }
 
+        info!(
+            "Claiming static deposit for utxo {}:{}",
+            detailed_utxo.txid, detailed_utxo.vout
+        );
+        let credit_amount_sats = quote.credit_amount_sats;
+        let transfer_id = self.spark_wallet.claim_static_deposit(quote).await?;
+        info!(
+            "Claimed static deposit for utxo {}:{} (deposit value {}, credit {}), transfer {transfer_id}",
+            detailed_utxo.txid, detailed_utxo.vout, detailed_utxo.value, credit_amount_sats,
+        );
+        if let Some(request) = sponsorship {
+            self.pay_sponsored_claim_fee(request, &transfer_id).await;
+        }
+        Ok(StaticDepositClaim {
+            transfer_id,
+            fee_sats: spark_requested_fee_sats,
+            max_fee_sats,
+        })
+    }
+
+    /// The maximum claim fee `policy` allows for `detailed_utxo`. Fails if the
+    /// claim fee requested by Spark exceeds it.
+    pub(super) async fn allowed_claim_fee_sats(
+        &self,
+        detailed_utxo: &DetailedUtxo,
+        policy: &DepositClaimPolicy,
+        spark_requested_fee_sats: u64,
+    ) -> Result<u64, SdkError> {
         let spark_requested_fee_rate = spark_requested_fee_sats.div_ceil(CLAIM_TX_SIZE_VBYTES);
 
-        let Some(max_deposit_claim_fee) = max_claim_fee else {
+        let Some(max_fee) = policy
+            .max_fee(detailed_utxo.value, self.chain_service.as_ref())
+            .await?
+        else {
             return Err(SdkError::MaxDepositClaimFeeExceeded {
                 tx: detailed_utxo.txid.to_string(),
                 vout: detailed_utxo.vout,
@@ -454,9 +737,6 @@ impl BreezSdk {
                 required_fee_rate_sat_per_vbyte: spark_requested_fee_rate,
             });
         };
-        let max_fee = max_deposit_claim_fee
-            .to_fee(self.chain_service.as_ref())
-            .await?;
         let max_fee_sats = max_fee.to_sats(CLAIM_TX_SIZE_VBYTES);
         info!(
             "User max fee: {} spark requested fee: {}",
@@ -471,18 +751,7 @@ impl BreezSdk {
                 required_fee_rate_sat_per_vbyte: spark_requested_fee_rate,
             });
         }
-
-        info!(
-            "Claiming static deposit for utxo {}:{}",
-            detailed_utxo.txid, detailed_utxo.vout
-        );
-        let credit_amount_sats = quote.credit_amount_sats;
-        let transfer_id = self.spark_wallet.claim_static_deposit(quote).await?;
-        info!(
-            "Claimed static deposit for utxo {}:{} (deposit value {}, credit {}), transfer {transfer_id}",
-            detailed_utxo.txid, detailed_utxo.vout, detailed_utxo.value, credit_amount_sats,
-        );
-        Ok(transfer_id)
+        Ok(max_fee_sats)
     }
 }
 
@@ -497,7 +766,43 @@ impl BreezSdk {
     ) -> Result<SyncWalletResponse, SdkError> {
         self.runtime
             .run_user_sync(self, super::SyncType::Full, true)
-            .await?;
+            .await
+            .map_err(|e| self.service_status.annotate(e))?;
         Ok(SyncWalletResponse {})
     }
+
+    /// Hints that a sync is due, e.g. when the app comes to the foreground
+    /// or receives a push notification, without waiting for it.
+    ///
+    /// Hints arriving within [`Config::sync_trigger_debounce_secs`](crate::Config::sync_trigger_debounce_secs)
+    /// of each other run a single sync. Requires background tasks; otherwise
+    /// call [`BreezSdk::sync_wallet`].
+    pub async fn trigger_sync(&self, request: TriggerSyncRequest) -> Result<(), SdkError> {
+        let config = self.config.get();
+        if !config.background_tasks_enabled {
+            return Err(SdkError::InvalidInput(
+                "trigger_sync is not supported when background_tasks_enabled is false; call sync_wallet explicitly instead".to_string(),
+            ));
+        }
+        info!("Sync triggered: {:?}", request.trigger);
+        let force = match request.trigger {
+            SyncTrigger::AppForeground => false,
+            SyncTrigger::PushNotification => true,
+        };
+        let delay = Duration::from_secs(u64::from(config.sync_trigger_debounce_secs));
+        self.sync_coordinator
+            .trigger_sync_debounced(super::SyncType::Full, force, delay)
+            .await;
+        Ok(())
+    }
+
+    /// Prepares the SDK for a fast first payment after app launch.
+    ///
+    /// Connects to and authenticates with the Spark operators and the Spark
+    /// Service Provider, and pre-fetches fee estimates. Call it early, for
+    /// example when the payment screen is opened.
+    pub async fn warm_up(&self) -> Result<(), SdkError> {
+        self.spark_wallet.warm_up().await?;
+        Ok(())
+    }
 }
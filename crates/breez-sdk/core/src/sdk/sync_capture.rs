@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::{StopSyncCaptureResponse, error::SdkError, sync_capture::SyncCapture};
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Starts capturing the payment sync passes for debugging, until
+    /// [`BreezSdk::stop_sync_capture`] is called.
+    ///
+    /// The capture holds the operator responses the passes received, so a
+    /// sync issue can be reproduced with [`crate::replay_sync_capture`].
+    pub async fn start_sync_capture(&self) -> Result<(), SdkError> {
+        let mut capture = self.sync_capture.lock().await;
+        if capture.is_some() {
+            return Err(SdkError::InvalidInput(
+                "A sync capture is already running".to_string(),
+            ));
+        }
+        info!("Starting sync capture");
+        *capture = Some(Arc::new(SyncCapture::default()));
+        Ok(())
+    }
+
+    /// Stops the running sync capture and returns it. Responses received
+    /// by passes still running are left out, so stop the capture once the
+    /// sync to debug has completed.
+    pub async fn stop_sync_capture(&self) -> Result<StopSyncCaptureResponse, SdkError> {
+        let Some(capture) = self.sync_capture.lock().await.take() else {
+            return Err(SdkError::InvalidInput(
+                "No sync capture is running".to_string(),
+            ));
+        };
+        info!("Stopped sync capture");
+        Ok(StopSyncCaptureResponse {
+            capture: capture.to_json()?,
+        })
+    }
+}
@@ -4,6 +4,7 @@
 //! a sync is running, they share a single NEW sync that starts after the current
 //! one completes. Different sync types are processed in order.
 
+use platform_utils::time::Duration;
 use platform_utils::tokio;
 use std::sync::Arc;
 use tokio::sync::{Mutex, broadcast, oneshot};
@@ -23,6 +24,7 @@ struct Waiter {
 pub(crate) struct SyncCoordinator {
     sender: broadcast::Sender<SyncRequest>,
     inner: Arc<Mutex<Inner>>,
+    debounce: Arc<Mutex<Debounce>>,
 }
 
 struct Inner {
@@ -30,6 +32,15 @@ struct Inner {
     waiters: Vec<Waiter>,
 }
 
+/// The burst of debounced triggers in progress
+#[derive(Default)]
+struct Debounce {
+    /// Incremented by every trigger, so only the last one of a burst syncs
+    generation: u64,
+    /// Whether any trigger of the burst was forced
+    force: bool,
+}
+
 impl SyncCoordinator {
     pub fn new() -> Self {
         let (sender, _) = broadcast::channel(10);
@@ -39,6 +50,7 @@ impl SyncCoordinator {
                 sync_running: false,
                 waiters: Vec::new(),
             })),
+            debounce: Arc::new(Mutex::new(Debounce::default())),
         }
     }
 
@@ -82,6 +94,34 @@ impl SyncCoordinator {
         }
     }
 
+    /// Trigger a sync once no other debounced trigger arrived for `delay`
+    /// (fire-and-forget).
+    ///
+    /// A burst of triggers runs a single sync, forced if any trigger of the
+    /// burst was.
+    pub async fn trigger_sync_debounced(&self, sync_type: SyncType, force: bool, delay: Duration) {
+        let generation = {
+            let mut debounce = self.debounce.lock().await;
+            debounce.generation = debounce.generation.wrapping_add(1);
+            debounce.force |= force;
+            debounce.generation
+        };
+
+        let coordinator = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let force = {
+                let mut debounce = coordinator.debounce.lock().await;
+                if debounce.generation != generation {
+                    debug!("Debounced sync trigger superseded");
+                    return;
+                }
+                std::mem::take(&mut debounce.force)
+            };
+            coordinator.trigger_sync_no_wait(sync_type, force).await;
+        });
+    }
+
     /// Add a waiter and return whether this caller should run the sync loop.
     async fn add_waiter(
         &self,
@@ -170,3 +210,31 @@ impl SyncCoordinator {
             .map_err(|_| SdkError::Generic("Sync reply channel closed".to_string()))?
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[macros::async_test_all]
+    async fn test_debounced_triggers_run_single_sync() {
+        let coordinator = SyncCoordinator::new();
+        let mut requests = coordinator.subscribe();
+        for force in [false, true, false] {
+            coordinator
+                .trigger_sync_debounced(SyncType::Full, force, Duration::from_millis(50))
+                .await;
+        }
+
+        // The burst runs one sync, forced as one of its triggers was
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.sync_type, SyncType::Full);
+        assert!(request.force);
+        request.reply(None).await;
+
+        let next = tokio::time::timeout(Duration::from_millis(200), requests.recv()).await;
+        assert!(next.is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use tracing::warn;
+
+use crate::{
+    GetTokenAnnouncementsRequest, GetTokenAnnouncementsResponse, TokenMetadata, error::SdkError,
+    token_announcement::fetch_announcements,
+};
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Fetches the latest announcement of each token from Nostr relays.
+    ///
+    /// Only announcements signed by the issuer key they name are returned.
+    pub async fn get_token_announcements(
+        &self,
+        request: GetTokenAnnouncementsRequest,
+    ) -> Result<GetTokenAnnouncementsResponse, SdkError> {
+        let announcements =
+            fetch_announcements(&self.spark_wallet, &request.token_identifiers).await?;
+        Ok(GetTokenAnnouncementsResponse { announcements })
+    }
+}
+
+impl BreezSdk {
+    /// Adds the announced metadata of the tokens missing from `metadata`.
+    /// Announced metadata isn't cached, as the operators may learn the token
+    /// later.
+    pub(super) async fn enrich_tokens_metadata(
+        &self,
+        token_identifiers: &[String],
+        mut metadata: Vec<TokenMetadata>,
+    ) -> Vec<TokenMetadata> {
+        let unknown: Vec<String> = token_identifiers
+            .iter()
+            .filter(|id| !metadata.iter().any(|m| &m.identifier == *id))
+            .cloned()
+            .collect();
+        if unknown.is_empty() {
+            return metadata;
+        }
+        match fetch_announcements(&self.spark_wallet, &unknown).await {
+            Ok(announcements) => metadata.extend(announcements.into_iter().map(|a| a.metadata)),
+            Err(e) => warn!("Failed to fetch token announcements: {e}"),
+        }
+        metadata
+    }
+}
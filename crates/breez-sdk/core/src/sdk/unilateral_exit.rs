@@ -47,7 +47,7 @@ impl BreezSdk {
             selection = ?request.selection,
             "prepare_unilateral_exit: quoting"
         );
-        let btc_network: bitcoin::Network = self.config.network.into();
+        let btc_network: bitcoin::Network = self.config.get().network.into();
 
         let destination = request
             .destination
@@ -149,6 +149,7 @@ impl BreezSdk {
         request: UnilateralExitRequest,
         signer: Arc<dyn CpfpSigner>,
     ) -> Result<UnilateralExitResponse, SdkError> {
+        self.ensure_not_watch_only("unilateral_exit")?;
         let UnilateralExitRequest {
             prepared,
             funding_inputs,
@@ -159,7 +160,7 @@ impl BreezSdk {
             fee_rate_sat_per_vbyte = prepared.fee_rate_sat_per_vbyte,
             "unilateral_exit: building"
         );
-        let btc_network: bitcoin::Network = self.config.network.into();
+        let btc_network: bitcoin::Network = self.config.get().network.into();
         let chain = self.chain_service.as_ref();
 
         let destination = prepared
@@ -0,0 +1,21 @@
+use crate::ViewerBundle;
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Exports what a watch-only SDK needs to view the wallet, e.g. for an
+    /// accountant. Pass the bundle to `SdkBuilder::new_viewer` on another
+    /// machine to sync the wallet and list its payments without its keys.
+    ///
+    /// The bundle holds no session tokens, as the Spark services only issue
+    /// tokens with the full privileges of the wallet. The viewer reads with
+    /// the sessions of its session store instead, and loses access once they
+    /// expire until it's given fresh ones.
+    pub fn export_viewer_bundle(&self) -> ViewerBundle {
+        ViewerBundle {
+            identity_public_key: self.spark_wallet.get_identity_public_key().to_string(),
+            created_at: self.now_secs(),
+        }
+    }
+}
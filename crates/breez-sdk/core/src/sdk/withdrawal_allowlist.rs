@@ -0,0 +1,296 @@
+use spark_wallet::SparkAddress;
+use tracing::info;
+
+use crate::{
+    AllowlistEntry, PaymentApprovalRequest, PrepareLnurlPayResponse, PrepareSendPaymentResponse,
+    SendPaymentMethod, UpdateWithdrawalAllowlistRequest, WithdrawalAllowlist, error::SdkError,
+    persist::ObjectCacheRepository,
+};
+
+use super::BreezSdk;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+#[allow(clippy::needless_pass_by_value)]
+impl BreezSdk {
+    /// Returns the withdrawal allowlist.
+    pub async fn get_withdrawal_allowlist(&self) -> Result<WithdrawalAllowlist, SdkError> {
+        Ok(ObjectCacheRepository::new(self.storage.clone())
+            .fetch_withdrawal_allowlist()
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Updates the withdrawal allowlist.
+    ///
+    /// While the allowlist is enabled, payments to destinations that are not on
+    /// it are only sent if the registered `PaymentApprover` approves them.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The entries to add and remove, and optionally whether the allowlist is enforced
+    ///
+    /// # Returns
+    ///
+    /// The updated allowlist or an error
+    pub async fn update_withdrawal_allowlist(
+        &self,
+        request: UpdateWithdrawalAllowlistRequest,
+    ) -> Result<WithdrawalAllowlist, SdkError> {
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let mut allowlist = cache
+            .fetch_withdrawal_allowlist()
+            .await?
+            .unwrap_or_default();
+        apply_update(&mut allowlist, request)?;
+        cache.save_withdrawal_allowlist(&allowlist).await?;
+        Ok(allowlist)
+    }
+}
+
+fn apply_update(
+    allowlist: &mut WithdrawalAllowlist,
+    request: UpdateWithdrawalAllowlistRequest,
+) -> Result<(), SdkError> {
+    let remove = request
+        .remove
+        .into_iter()
+        .map(AllowlistEntry::normalize)
+        .collect::<Result<Vec<_>, _>>()?;
+    allowlist.entries.retain(|entry| !remove.contains(entry));
+    for entry in request.add {
+        let entry = entry.normalize()?;
+        if !allowlist.entries.contains(&entry) {
+            allowlist.entries.push(entry);
+        }
+    }
+    if let Some(enabled) = request.enabled {
+        allowlist.enabled = enabled;
+    }
+    Ok(())
+}
+
+/// Builds the approval request for a prepared `send_payment`.
+pub(super) fn send_payment_approval_request(
+    prepare_response: &PrepareSendPaymentResponse,
+) -> PaymentApprovalRequest {
+    let (destination, allowlist_entry) = match &prepare_response.payment_method {
+        SendPaymentMethod::SparkAddress { address, .. } => (
+            address.clone(),
+            address
+                .parse::<SparkAddress>()
+                .ok()
+                .map(|address| AllowlistEntry::SparkIdentity {
+                    identity_public_key: address.identity_public_key.to_string(),
+                }),
+        ),
+        SendPaymentMethod::SparkInvoice {
+            spark_invoice_details,
+            ..
+        } => (
+            spark_invoice_details.invoice.clone(),
+            AllowlistEntry::SparkIdentity {
+                identity_public_key: spark_invoice_details.identity_public_key.clone(),
+            }
+            .normalize()
+            .ok(),
+        ),
+        SendPaymentMethod::Bolt11Invoice {
+            invoice_details, ..
+        } => (invoice_details.invoice.bolt11.clone(), None),
+        SendPaymentMethod::BitcoinAddress { address, .. } => (
+            address.address.clone(),
+            AllowlistEntry::BitcoinAddress {
+                address: address.address.clone(),
+            }
+            .normalize()
+            .ok(),
+        ),
+        SendPaymentMethod::CrossChainAddress {
+            recipient_address, ..
+        } => (recipient_address.clone(), None),
+    };
+    PaymentApprovalRequest {
+        destination,
+        allowlist_entry,
+        amount: prepare_response.amount,
+        token_identifier: prepare_response.token_identifier.clone(),
+    }
+}
+
+/// Builds the approval request for a prepared `lnurl_pay`. Only payments to a
+/// lightning address can be allowlisted.
+pub(super) fn lnurl_pay_approval_request(
+    prepare_response: &PrepareLnurlPayResponse,
+) -> PaymentApprovalRequest {
+    let pay_request = &prepare_response.pay_request;
+    let allowlist_entry = pay_request.address.as_ref().and_then(|address| {
+        AllowlistEntry::LightningAddress {
+            address: address.clone(),
+        }
+        .normalize()
+        .ok()
+    });
+    PaymentApprovalRequest {
+        destination: pay_request
+            .address
+            .clone()
+            .unwrap_or_else(|| pay_request.url.clone()),
+        allowlist_entry,
+        amount: u128::from(prepare_response.amount_sats),
+        token_identifier: None,
+    }
+}
+
+/// Fails with [`SdkError::DestinationNotAllowed`] if the allowlist is enabled,
+/// the destination is not on it and the payment approver doesn't approve it.
+pub(super) async fn ensure_destination_allowed(
+    sdk: &BreezSdk,
+    request: PaymentApprovalRequest,
+) -> Result<(), SdkError> {
+    let allowlist = sdk.get_withdrawal_allowlist().await?;
+    if is_allowed(&allowlist, &request) {
+        return Ok(());
+    }
+    if let Some(approver) = &sdk.payment_approver
+        && approver.approve(request.clone()).await
+    {
+        info!(
+            "Payment to {} approved outside the withdrawal allowlist",
+            request.destination
+        );
+        return Ok(());
+    }
+    Err(SdkError::DestinationNotAllowed {
+        destination: request.destination,
+    })
+}
+
+fn is_allowed(allowlist: &WithdrawalAllowlist, request: &PaymentApprovalRequest) -> bool {
+    !allowlist.enabled
+        || request
+            .allowlist_entry
+            .as_ref()
+            .is_some_and(|entry| allowlist.entries.contains(entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn request(allowlist_entry: Option<AllowlistEntry>) -> PaymentApprovalRequest {
+        PaymentApprovalRequest {
+            destination: "destination".to_string(),
+            allowlist_entry,
+            amount: 1_000,
+            token_identifier: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_entries() {
+        assert_eq!(
+            AllowlistEntry::LightningAddress {
+                address: " Alice@Example.com ".to_string(),
+            }
+            .normalize()
+            .unwrap(),
+            AllowlistEntry::LightningAddress {
+                address: "alice@example.com".to_string(),
+            }
+        );
+        assert_eq!(
+            AllowlistEntry::SparkIdentity {
+                identity_public_key: IDENTITY.to_uppercase(),
+            }
+            .normalize()
+            .unwrap(),
+            AllowlistEntry::SparkIdentity {
+                identity_public_key: IDENTITY.to_string(),
+            }
+        );
+        assert_eq!(
+            AllowlistEntry::BitcoinAddress {
+                address: "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4".to_string(),
+            }
+            .normalize()
+            .unwrap(),
+            AllowlistEntry::BitcoinAddress {
+                address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+            }
+        );
+        assert!(
+            AllowlistEntry::LightningAddress {
+                address: "alice".to_string(),
+            }
+            .normalize()
+            .is_err()
+        );
+        assert!(
+            AllowlistEntry::SparkIdentity {
+                identity_public_key: "not a key".to_string(),
+            }
+            .normalize()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_apply_update() {
+        let alice = AllowlistEntry::LightningAddress {
+            address: "alice@example.com".to_string(),
+        };
+        let bob = AllowlistEntry::LightningAddress {
+            address: "bob@example.com".to_string(),
+        };
+        let mut allowlist = WithdrawalAllowlist::default();
+        apply_update(
+            &mut allowlist,
+            UpdateWithdrawalAllowlistRequest {
+                enabled: Some(true),
+                add: vec![
+                    alice.clone(),
+                    bob.clone(),
+                    AllowlistEntry::LightningAddress {
+                        address: "ALICE@example.com".to_string(),
+                    },
+                ],
+                remove: vec![],
+            },
+        )
+        .unwrap();
+        assert!(allowlist.enabled);
+        assert_eq!(allowlist.entries, vec![alice.clone(), bob.clone()]);
+
+        apply_update(
+            &mut allowlist,
+            UpdateWithdrawalAllowlistRequest {
+                enabled: None,
+                add: vec![],
+                remove: vec![alice],
+            },
+        )
+        .unwrap();
+        assert!(allowlist.enabled);
+        assert_eq!(allowlist.entries, vec![bob]);
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        let entry = AllowlistEntry::SparkIdentity {
+            identity_public_key: IDENTITY.to_string(),
+        };
+        let mut allowlist = WithdrawalAllowlist::default();
+        // Nothing is enforced while the allowlist is disabled
+        assert!(is_allowed(&allowlist, &request(None)));
+
+        allowlist.enabled = true;
+        assert!(!is_allowed(&allowlist, &request(None)));
+        assert!(!is_allowed(&allowlist, &request(Some(entry.clone()))));
+
+        allowlist.entries.push(entry.clone());
+        assert!(is_allowed(&allowlist, &request(Some(entry))));
+        assert!(!is_allowed(&allowlist, &request(None)));
+    }
+}
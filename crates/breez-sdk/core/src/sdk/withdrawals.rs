@@ -0,0 +1,223 @@
+use std::collections::BTreeSet;
+
+use platform_utils::time::{Duration, UNIX_EPOCH};
+use platform_utils::tokio;
+use tokio::{select, time::sleep};
+use tracing::{Instrument, debug, info, warn};
+
+use crate::{
+    GetWithdrawStatusRequest, GetWithdrawStatusResponse, Payment, PaymentDetails, PaymentStatus,
+    PaymentType, StorageListPaymentsRequest, error::SdkError, events::SdkEvent,
+    persist::ObjectCacheRepository, utils::payments::record_payment_update,
+};
+
+use super::{BreezSdk, stuck_payments::confirmations_since};
+
+const WITHDRAW_TRACKING_INTERVAL: Duration = Duration::from_secs(120);
+/// Withdrawals older than this are no longer tracked.
+const WITHDRAW_TRACKING_WINDOW_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[cfg_attr(feature = "uniffi", uniffi::export(async_runtime = "tokio"))]
+impl BreezSdk {
+    /// Returns the on-chain status of the exit transaction of a withdrawal.
+    pub async fn get_withdraw_status(
+        &self,
+        request: GetWithdrawStatusRequest,
+    ) -> Result<GetWithdrawStatusResponse, SdkError> {
+        let payment = self.storage.get_payment_by_id(request.payment_id).await?;
+        let Some(tx_id) = withdraw_tx_id(&payment) else {
+            return Err(SdkError::InvalidInput(
+                "Payment is not a withdrawal".to_string(),
+            ));
+        };
+        let status = self
+            .chain_service
+            .get_transaction_status(tx_id.to_string())
+            .await?;
+        let estimated_confirmations = status
+            .block_time
+            .filter(|_| status.confirmed)
+            .map(|block_time| confirmations_since(block_time, self.now_secs()));
+        Ok(GetWithdrawStatusResponse {
+            tx_id: tx_id.to_string(),
+            confirmed: status.confirmed,
+            block_height: status.block_height.filter(|_| status.confirmed),
+            estimated_confirmations,
+        })
+    }
+}
+
+impl BreezSdk {
+    /// Periodically checks the exit transactions of recent withdrawals until
+    /// shutdown, emitting `WithdrawConfirmed` once each confirms.
+    pub(crate) fn spawn_withdraw_tracker(&self) {
+        let sdk = self.clone();
+        let mut shutdown_receiver = self.shutdown_sender.subscribe();
+        let span = tracing::Span::current();
+        tokio::spawn(
+            async move {
+                loop {
+                    select! {
+                        _ = shutdown_receiver.changed() => {
+                            debug!("Withdraw tracker stopped on shutdown");
+                            return;
+                        }
+                        () = sleep(sdk.config.get().polling_interval(WITHDRAW_TRACKING_INTERVAL)) => {}
+                    }
+                    if let Err(e) = sdk.check_withdrawals().await {
+                        warn!("Failed to check withdrawals: {e}");
+                    }
+                }
+            }
+            .instrument(span),
+        );
+    }
+
+    async fn check_withdrawals(&self) -> Result<(), SdkError> {
+        let now = self.now_secs();
+        let payments = self
+            .storage
+            .list_payments(StorageListPaymentsRequest {
+                type_filter: Some(vec![PaymentType::Send]),
+                status_filter: Some(vec![PaymentStatus::Pending, PaymentStatus::Completed]),
+                from_timestamp: Some(now.saturating_sub(WITHDRAW_TRACKING_WINDOW_SECS)),
+                ..Default::default()
+            })
+            .await?;
+        let cache = ObjectCacheRepository::new(self.storage.clone());
+        let reported = cache.fetch_confirmed_withdrawals().await?;
+        let (pending, mut confirmed) = split_withdrawals(payments, &reported);
+        // Withdrawals that left the tracking window are forgotten
+        let mut changed = confirmed.len() != reported.len();
+
+        for (tx_id, mut payment) in pending {
+            let status = match self
+                .chain_service
+                .get_transaction_status(tx_id.clone())
+                .await
+            {
+                Ok(status) => status,
+                Err(e) => {
+                    debug!("Failed to get the status of withdrawal {tx_id}: {e}");
+                    continue;
+                }
+            };
+            let Some(height) = status.block_height.filter(|_| status.confirmed) else {
+                continue;
+            };
+            info!("Withdrawal {} confirmed at height {height}", payment.id);
+            if payment.status == PaymentStatus::Pending {
+                payment.status = PaymentStatus::Completed;
+                record_payment_update(&self.storage, &self.event_emitter, payment, true).await;
+            }
+            self.event_emitter
+                .emit(&SdkEvent::WithdrawConfirmed {
+                    txid: tx_id.clone(),
+                    height,
+                })
+                .await;
+            confirmed.insert(tx_id);
+            changed = true;
+        }
+        if changed {
+            cache.save_confirmed_withdrawals(&confirmed).await?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn now_secs(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+fn withdraw_tx_id(payment: &Payment) -> Option<&str> {
+    match &payment.details {
+        Some(PaymentDetails::Withdraw { tx_id }) => Some(tx_id),
+        _ => None,
+    }
+}
+
+/// Splits the withdrawals into those whose exit transaction isn't known to
+/// be confirmed, keyed by transaction id, and the ids of those reported as
+/// confirmed.
+fn split_withdrawals(
+    payments: Vec<Payment>,
+    reported: &BTreeSet<String>,
+) -> (Vec<(String, Payment)>, BTreeSet<String>) {
+    let mut confirmed = BTreeSet::new();
+    let mut pending = Vec::new();
+    for payment in payments {
+        let Some(tx_id) = withdraw_tx_id(&payment).map(ToString::to_string) else {
+            continue;
+        };
+        if reported.contains(&tx_id) {
+            confirmed.insert(tx_id);
+        } else {
+            pending.push((tx_id, payment));
+        }
+    }
+    (pending, confirmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+    use crate::{DisplayAmount, PaymentMethod};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn payment(id: &str, method: PaymentMethod, details: Option<PaymentDetails>) -> Payment {
+        Payment {
+            id: id.to_string(),
+            payment_type: PaymentType::Send,
+            status: PaymentStatus::Completed,
+            amount: 1000,
+            fees: 0,
+            timestamp: 0,
+            method,
+            details,
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    fn withdrawal(id: &str, tx_id: &str) -> Payment {
+        payment(
+            id,
+            PaymentMethod::Withdraw,
+            Some(PaymentDetails::Withdraw {
+                tx_id: tx_id.to_string(),
+            }),
+        )
+    }
+
+    #[test_all]
+    fn test_split_withdrawals() {
+        let payments = vec![
+            withdrawal("a", "tx-a"),
+            withdrawal("b", "tx-b"),
+            payment("c", PaymentMethod::Spark, None),
+        ];
+        let reported = BTreeSet::from(["tx-a".to_string(), "tx-old".to_string()]);
+
+        let (pending, confirmed) = split_withdrawals(payments, &reported);
+
+        let pending: Vec<_> = pending
+            .iter()
+            .map(|(tx_id, p)| (tx_id.as_str(), p.id.as_str()))
+            .collect();
+        assert_eq!(pending, vec![("tx-b", "b")]);
+        // Withdrawals outside the tracking window are dropped
+        assert_eq!(confirmed, BTreeSet::from(["tx-a".to_string()]));
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use breez_sdk_common::breez_server::BreezServer;
 use breez_sdk_common::buy::moonpay::MoonpayProvider;
 
+use platform_utils::ByteCounter;
 use spark_wallet::{
     InMemorySessionStore, SessionStore, SparkSigner, SparkWallet, SparkWalletConfig,
 };
@@ -16,38 +17,60 @@ use tracing::{debug, info};
 use flashnet::{FlashnetConfig, IntegratorConfig};
 
 use crate::{
-    Credentials, EventEmitter, FiatService, FiatServiceWrapper, Network, Seed,
+    BackupStore, Credentials, EmergencyExitStore, EventEmitter, ExportEmergencyExitPackageRequest,
+    FiatService, FiatServiceWrapper, Network, Seed, ViewerBundle,
+    backup::BackupService,
     chain::{
-        BitcoinChainService,
+        BitcoinChainService, ChainServiceEndpoint,
+        failover::{ChainEndpoint, FailoverChainService},
         rest_client::{BasicAuth, ChainApiType, RestClientChainService},
     },
+    claim_fee_sponsor::ClaimFeeSponsor,
+    clock::{Clock, system_clock},
+    compliance::TravelRuleProvider,
+    config_fingerprint::{ConfigFingerprint, check_config_fingerprint},
+    conflict_resolver::{ConflictResolutionStrategy, ConflictResolver},
     error::SdkError,
     lnurl::{DefaultLnurlServerClient, LnurlServerClient},
     models::Config,
+    payment_approver::PaymentApprover,
     payment_observer::{PaymentObserver, SparkTransferObserver},
-    persist::backend::{ResolvedStores, StorageBackend},
-    realtime_sync::{RealTimeSyncParams, init_and_start_real_time_sync},
-    sdk::{BreezSdk, BreezSdkParams, SyncCoordinator, runtime_from_config},
+    persist::{
+        Storage,
+        backend::{ResolvedStores, StorageBackend, storage_tenant_key},
+    },
+    plugin::{Plugin, PluginManager},
+    realtime_sync::{RealTimeSyncParams, init_and_start_real_time_sync, sync_conflict_resolver},
+    sdk::{
+        BreezSdk, BreezSdkParams, DataUsageTracker, EmergencyExitSettings, SharedConfig,
+        SyncCoordinator, disable_background_tasks, runtime_from_config,
+    },
     sdk_context::{SdkContext, SdkContextConfig, new_shared_sdk_context},
-    signer::{breez::BreezSignerImpl, lnurl_auth::LnurlAuthSignerAdapter, rtsync::RTSyncSigner},
+    secret_store::{SecretStore, StorageSecretStore, migrate_secrets},
+    service_status::ServiceStatusMonitor,
+    signer::{
+        CpfpSigner, breez::BreezSignerImpl, lnurl_auth::LnurlAuthSignerAdapter,
+        rtsync::RTSyncSigner,
+    },
     stable_balance::StableBalance,
     token_conversion::TokenConversionMiddleware,
     token_conversion::{
         DEFAULT_INTEGRATOR_FEE_BPS, DEFAULT_INTEGRATOR_PUBKEY, FlashnetTokenConverter,
         TokenConverter,
     },
+    webhook::WebhookEventListener,
 };
 
-/// Configuration captured by [`SdkBuilder::with_rest_chain_service`].
+/// Configuration captured by [`SdkBuilder::with_rest_chain_service`] and
+/// [`SdkBuilder::with_rest_chain_service_endpoints`].
 ///
 /// Stored on the builder and resolved during `build()` so the resulting
-/// `RestClientChainService` reuses the shared HTTP client from the
+/// `RestClientChainService`s reuse the shared HTTP client from the
 /// [`SdkContext`](crate::SdkContext).
 #[derive(Clone)]
 struct RestChainServiceConfig {
-    url: String,
-    api_type: ChainApiType,
-    credentials: Option<Credentials>,
+    /// The endpoints in priority order
+    endpoints: Vec<ChainServiceEndpoint>,
 }
 
 /// Source for the signer - either a seed or an external signer implementation
@@ -71,6 +94,8 @@ enum SignerSource {
         breez: ExternalBreez,
         spark: Arc<dyn crate::signer::ExternalSparkSigner>,
     },
+    /// No signer: only the hex-encoded identity public key is known.
+    WatchOnly { identity_public_key: String },
 }
 
 /// The signers derived from a single signer source. `ecies` is absent for a
@@ -81,6 +106,7 @@ struct Signers {
     spark: Arc<dyn SparkSigner>,
     rtsync: Option<Arc<RTSyncSigner>>,
     lnurl_auth: Option<Arc<LnurlAuthSignerAdapter>>,
+    hmac: Option<Arc<dyn crate::signer::HmacSigner>>,
 }
 
 /// Inputs to [`build_spark_wallet`] — bundled to avoid an >8-argument helper.
@@ -97,6 +123,7 @@ struct BuildSparkWalletParams {
     token_output_store: Option<Arc<dyn spark_wallet::TokenOutputStore>>,
     payment_observer: Option<Arc<dyn PaymentObserver>>,
     context: Arc<SdkContext>,
+    data_usage: Arc<DataUsageTracker>,
 }
 
 /// Builder for creating `BreezSdk` instances with customizable components.
@@ -106,13 +133,26 @@ pub struct SdkBuilder {
     signer_source: SignerSource,
 
     storage: Option<Arc<dyn StorageBackend>>,
+    storage_namespace: Option<String>,
     session_store: Option<Arc<dyn crate::session_store::SessionStore>>,
+    secret_store: Option<Arc<dyn SecretStore>>,
     chain_service: Option<Arc<dyn BitcoinChainService>>,
     rest_chain_service_config: Option<RestChainServiceConfig>,
     fiat_service: Option<Arc<dyn FiatService>>,
     lnurl_client: Option<Arc<dyn platform_utils::HttpClient>>,
     lnurl_server_client: Option<Arc<dyn LnurlServerClient>>,
+    dns_resolver: Option<Arc<dyn breez_sdk_common::dns::DnsResolver + Send + Sync>>,
     payment_observer: Option<Arc<dyn PaymentObserver>>,
+    payment_approver: Option<Arc<dyn PaymentApprover>>,
+    claim_fee_sponsor: Option<Arc<dyn ClaimFeeSponsor>>,
+    backup_store: Option<Arc<dyn BackupStore>>,
+    emergency_exit: Option<EmergencyExitSettings>,
+    travel_rule_provider: Option<Arc<dyn TravelRuleProvider>>,
+    conflict_resolver: Option<Arc<dyn ConflictResolver>>,
+    conflict_resolution: ConflictResolutionStrategy,
+    plugins: Vec<Arc<dyn Plugin>>,
+    clock: Arc<dyn Clock>,
+    config_change_accepted: bool,
     context: Option<Arc<SdkContext>>,
 }
 
@@ -126,22 +166,13 @@ impl SdkBuilder {
     /// - `seed`: The seed for wallet generation.
     #[allow(clippy::needless_pass_by_value)]
     pub fn new(config: Config, seed: Seed) -> Self {
-        SdkBuilder {
+        Self::from_signer_source(
             config,
-            signer_source: SignerSource::Seed {
+            SignerSource::Seed {
                 seed,
                 account_number: None,
             },
-            storage: None,
-            session_store: None,
-            chain_service: None,
-            rest_chain_service_config: None,
-            fiat_service: None,
-            lnurl_client: None,
-            lnurl_server_client: None,
-            payment_observer: None,
-            context: None,
-        }
+        )
     }
 
     /// Creates a new `SdkBuilder` with the provided configuration and external signers.
@@ -184,32 +215,92 @@ impl SdkBuilder {
         )
     }
 
+    /// Creates a new `SdkBuilder` for a watch-only wallet, which holds no keys.
+    ///
+    /// The SDK syncs the wallet, reports its balance and payments, and monitors
+    /// its deposits. Operations that need a signature, like sending, receiving
+    /// over Lightning or claiming deposits, fail with [`SdkError::WatchOnly`].
+    ///
+    /// Authenticating with the Spark operators requires a signature too, so the
+    /// session store (see [`with_session_store`](Self::with_session_store))
+    /// must hold valid sessions for the wallet.
+    ///
+    /// # Arguments
+    /// - `config`: The configuration to be used.
+    /// - `identity_public_key`: The hex-encoded identity public key of the wallet.
+    pub fn new_watch_only(config: Config, identity_public_key: String) -> Self {
+        Self::from_signer_source(
+            config,
+            SignerSource::WatchOnly {
+                identity_public_key,
+            },
+        )
+    }
+
+    /// Creates a new `SdkBuilder` for a watch-only wallet viewing the wallet
+    /// of a bundle from
+    /// [`BreezSdk::export_viewer_bundle`](crate::BreezSdk::export_viewer_bundle).
+    ///
+    /// As with [`new_watch_only`](Self::new_watch_only), the session store
+    /// must hold valid sessions for the wallet. Once they expire, requests to
+    /// the Spark services fail until fresh sessions are stored.
+    ///
+    /// # Arguments
+    /// - `config`: The configuration to be used.
+    /// - `bundle`: The viewer bundle exported by the wallet.
+    pub fn new_viewer(config: Config, bundle: ViewerBundle) -> Self {
+        Self::new_watch_only(config, bundle.identity_public_key)
+    }
+
     fn with_external_signer(
         config: Config,
         breez: ExternalBreez,
         spark_signer: Arc<dyn crate::signer::ExternalSparkSigner>,
     ) -> Self {
-        SdkBuilder {
+        Self::from_signer_source(
             config,
-            signer_source: SignerSource::External {
+            SignerSource::External {
                 breez,
                 spark: spark_signer,
             },
+        )
+    }
+
+    fn from_signer_source(config: Config, signer_source: SignerSource) -> Self {
+        SdkBuilder {
+            config,
+            signer_source,
             storage: None,
+            storage_namespace: None,
             session_store: None,
+            secret_store: None,
             chain_service: None,
             rest_chain_service_config: None,
             fiat_service: None,
             lnurl_client: None,
+            dns_resolver: None,
             lnurl_server_client: None,
             payment_observer: None,
+            payment_approver: None,
+            claim_fee_sponsor: None,
+            backup_store: None,
+            emergency_exit: None,
+            travel_rule_provider: None,
+            conflict_resolver: None,
+            conflict_resolution: ConflictResolutionStrategy::default(),
+            plugins: Vec::new(),
+            clock: system_clock(),
+            config_change_accepted: false,
             context: None,
         }
     }
 
     /// Sets the account number for key derivation. All wallet keys derive from
     /// the seed at `m/8797555'/<account number>'`, so each account number
-    /// yields an independent wallet from the same seed.
+    /// yields an independent wallet from the same seed. Each account keeps its
+    /// own storage within the storage directory, and
+    /// [`discover_accounts`](crate::discover_accounts) finds the accounts that
+    /// have activity.
     ///
     /// When unset, the account number defaults to 0 on Regtest and 1 on all
     /// other networks.
@@ -257,6 +348,23 @@ impl SdkBuilder {
         self
     }
 
+    /// Scopes the wallet's data to a namespace within the storage backend.
+    ///
+    /// Backends already keep each wallet identity apart. A namespace further
+    /// splits a single identity, e.g. when several services share one database
+    /// and the same wallet. Data written under one namespace is invisible to
+    /// the others and to the unnamespaced wallet.
+    ///
+    /// Note: This has no effect on a [`custom_storage`](crate::custom_storage)
+    /// backend, which returns the caller's stores as-is.
+    /// Arguments:
+    /// - `namespace`: A non-empty namespace name.
+    #[must_use]
+    pub fn with_storage_namespace(mut self, namespace: String) -> Self {
+        self.storage_namespace = Some(namespace);
+        self
+    }
+
     /// Overrides the session store used to cache Spark operator and SSP auth
     /// tokens, replacing the one the [`StorageBackend`] provides.
     ///
@@ -279,6 +387,21 @@ impl SdkBuilder {
         self
     }
 
+    /// Sets the store for secret material, such as the Breez partner JWT,
+    /// replacing the main storage. Use it to keep secrets in a platform
+    /// keystore like the iOS Keychain or the Android Keystore.
+    ///
+    /// Secrets that are still in the main storage are moved into this store
+    /// when the SDK is built.
+    ///
+    /// Arguments:
+    /// - `secret_store`: The secret store to be used.
+    #[must_use]
+    pub fn with_secret_store(mut self, secret_store: Arc<dyn SecretStore>) -> Self {
+        self.secret_store = Some(secret_store);
+        self
+    }
+
     #[must_use]
     /// **Deprecated.** Use
     /// [`with_storage_backend`](Self::with_storage_backend) with
@@ -357,13 +480,34 @@ impl SdkBuilder {
     ) -> Self {
         self.chain_service = None;
         self.rest_chain_service_config = Some(RestChainServiceConfig {
-            url,
-            api_type,
-            credentials,
+            endpoints: vec![ChainServiceEndpoint {
+                url,
+                api_type,
+                credentials,
+            }],
         });
         self
     }
 
+    /// Configures REST chain services to be used by the SDK, in priority order.
+    ///
+    /// Requests go to the first reachable endpoint. An endpoint failing with a
+    /// connectivity error is health checked, and requests fail over to the
+    /// next endpoint while it is unreachable. Each switch of endpoint emits a
+    /// `ChainServiceFailover` event.
+    ///
+    /// Arguments:
+    /// - `endpoints`: The REST API endpoints, highest priority first.
+    #[must_use]
+    pub fn with_rest_chain_service_endpoints(
+        mut self,
+        endpoints: Vec<ChainServiceEndpoint>,
+    ) -> Self {
+        self.chain_service = None;
+        self.rest_chain_service_config = Some(RestChainServiceConfig { endpoints });
+        self
+    }
+
     /// Sets the fiat service to be used by the SDK.
     /// Arguments:
     /// - `fiat_service`: The fiat service to be used.
@@ -381,6 +525,29 @@ impl SdkBuilder {
         self
     }
 
+    /// Sets the DNS resolver used for BIP-353 lookups.
+    /// # Arguments
+    /// - `dns_resolver`: The DNS resolver to be used.
+    #[must_use]
+    pub fn with_dns_resolver(mut self, dns_resolver: Arc<dyn crate::DnsResolver>) -> Self {
+        self.dns_resolver = Some(Arc::new(crate::common::dns::DnsResolverWrapper::new(
+            dns_resolver,
+        )));
+        self
+    }
+
+    /// Resolves BIP-353 lookups over DNS-over-HTTPS.
+    /// # Arguments
+    /// - `url`: The DoH endpoint, e.g. `https://dns.google/dns-query`. Defaults to Cloudflare's.
+    #[must_use]
+    pub fn with_doh_dns_resolver(mut self, url: Option<String>) -> Self {
+        let url = url.unwrap_or_else(|| breez_sdk_common::dns::DEFAULT_DOH_ENDPOINT.to_string());
+        self.dns_resolver = Some(Arc::new(breez_sdk_common::dns::DohResolver::with_endpoint(
+            url,
+        )));
+        self
+    }
+
     #[must_use]
     #[allow(unused)]
     pub fn with_lnurl_server_client(
@@ -402,6 +569,143 @@ impl SdkBuilder {
         self
     }
 
+    /// Sets the payment approver to be used by the SDK.
+    /// While the withdrawal allowlist is enabled, the approver is asked to approve
+    /// payments to destinations that are not on it. Without an approver these
+    /// payments are rejected.
+    /// Arguments:
+    /// - `payment_approver`: The payment approver to be used.
+    #[must_use]
+    pub fn with_payment_approver(mut self, payment_approver: Arc<dyn PaymentApprover>) -> Self {
+        self.payment_approver = Some(payment_approver);
+        self
+    }
+
+    /// Sets the sponsor paying the claim fees of deposits instead of the user.
+    /// Arguments:
+    /// - `claim_fee_sponsor`: The claim fee sponsor to be used.
+    #[must_use]
+    pub fn with_claim_fee_sponsor(mut self, claim_fee_sponsor: Arc<dyn ClaimFeeSponsor>) -> Self {
+        self.claim_fee_sponsor = Some(claim_fee_sponsor);
+        self
+    }
+
+    /// Sets the store the SDK periodically saves encrypted backups to.
+    /// Requires a signer that supports ECIES.
+    /// Arguments:
+    /// - `backup_store`: The backup store to be used.
+    #[must_use]
+    pub fn with_backup_store(mut self, backup_store: Arc<dyn BackupStore>) -> Self {
+        self.backup_store = Some(backup_store);
+        self
+    }
+
+    /// Keeps an emergency exit package up to date, saving a new one to `store`
+    /// whenever the leaves worth exiting change.
+    /// Arguments:
+    /// - `request`: The funding UTXO, fee rate and destination of the package.
+    /// - `signer`: The signer of the funding UTXO.
+    /// - `store`: The store the packages are saved to.
+    #[must_use]
+    pub fn with_emergency_exit_package(
+        mut self,
+        request: ExportEmergencyExitPackageRequest,
+        signer: Arc<dyn CpfpSigner>,
+        store: Arc<dyn EmergencyExitStore>,
+    ) -> Self {
+        self.emergency_exit = Some(EmergencyExitSettings {
+            request,
+            signer,
+            store,
+        });
+        self
+    }
+
+    /// Sets how real-time sync resolves records changed on several devices at
+    /// once. Defaults to [`ConflictResolutionStrategy::FieldMerge`].
+    /// Arguments:
+    /// - `strategy`: The conflict resolution strategy to be used.
+    #[must_use]
+    pub fn with_conflict_resolution(mut self, strategy: ConflictResolutionStrategy) -> Self {
+        self.conflict_resolution = strategy;
+        self
+    }
+
+    /// Sets a custom conflict resolver for real-time sync. It takes precedence
+    /// over the strategy set with `with_conflict_resolution`.
+    /// Arguments:
+    /// - `conflict_resolver`: The conflict resolver to be used.
+    #[must_use]
+    pub fn with_conflict_resolver(mut self, conflict_resolver: Arc<dyn ConflictResolver>) -> Self {
+        self.conflict_resolver = Some(conflict_resolver);
+        self
+    }
+
+    /// Sets the travel-rule provider to be used by the SDK.
+    /// The provider receives the compliance metadata of outgoing payments before
+    /// they are sent, and can cancel them by returning an error.
+    /// Arguments:
+    /// - `travel_rule_provider`: The travel-rule provider to be used.
+    #[must_use]
+    pub fn with_travel_rule_provider(
+        mut self,
+        travel_rule_provider: Arc<dyn TravelRuleProvider>,
+    ) -> Self {
+        self.travel_rule_provider = Some(travel_rule_provider);
+        self
+    }
+
+    /// Registers a plugin to be started with the SDK.
+    /// Plugins are started in registration order once the SDK is built and are
+    /// notified when the SDK is disconnected.
+    /// Arguments:
+    /// - `plugin`: The plugin to be registered. Its id must be unique.
+    #[must_use]
+    pub fn with_plugin(mut self, plugin: Arc<dyn Plugin>) -> Self {
+        self.plugins.push(plugin);
+        self
+    }
+
+    /// Sets the clock driving wall-clock expiries, such as Spark invoice and
+    /// cross-chain quote expiry.
+    /// Lets tests fast-forward time with a [`TestClock`](crate::TestClock).
+    /// Arguments:
+    /// - `clock`: The clock to be used.
+    #[cfg(feature = "test-utils")]
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Accepts an incompatible change to the network or the signing operator pool
+    /// for an existing storage.
+    ///
+    /// By default `build` fails with [`SdkError::ConfigMismatch`] when the
+    /// configuration no longer matches the one the storage was created with. Set
+    /// this only when migrating a storage on purpose: data written for the
+    /// previous configuration is kept as-is.
+    #[must_use]
+    pub fn with_config_change_accepted(mut self) -> Self {
+        self.config_change_accepted = true;
+        self
+    }
+
+    /// Builds a headless SDK that only processes push notifications with
+    /// [`BreezSdk::process_notification`], e.g. in a mobile notification
+    /// service extension.
+    ///
+    /// The SDK starts no background services and doesn't sync on startup:
+    /// the background features of the config are turned off as with
+    /// [`default_server_config`](crate::default_server_config), and Stable
+    /// Balance is disabled. It can share the storage of the app's SDK.
+    #[must_use]
+    pub fn with_headless_mode(mut self) -> Self {
+        disable_background_tasks(&mut self.config);
+        self.config.stable_balance_config = None;
+        self
+    }
+
     /// Builds a [`SparkWalletConfig`](spark_wallet::SparkWalletConfig) from a
     /// [`SparkConfig`](crate::models::SparkConfig).
     fn build_spark_wallet_config(
@@ -460,33 +764,121 @@ impl SdkBuilder {
         Ok(config)
     }
 
+    /// Checks the builder for misconfiguration before anything is connected.
+    ///
+    /// Returns [`SdkError::BuilderError`] listing every problem found.
+    fn validate(&self, background_services_enabled: bool) -> Result<(), SdkError> {
+        let config = &self.config;
+        let mut problems = Vec::new();
+        if let Err(e) = config.validate() {
+            problems.push(problem_message(e));
+        }
+        if let Err(e) = PluginManager::new(self.plugins.clone()) {
+            problems.push(problem_message(e));
+        }
+        problems.extend(server_mode_problems(config, background_services_enabled));
+
+        let has_ecies = match &self.signer_source {
+            SignerSource::Seed { seed, .. } => {
+                problems.extend(seed_problems(seed));
+                true
+            }
+            SignerSource::External { breez, .. } => matches!(breez, ExternalBreez::Full(_)),
+            SignerSource::WatchOnly {
+                identity_public_key,
+            } => {
+                problems.extend(watch_only_problems(config, identity_public_key));
+                if self.emergency_exit.is_some() {
+                    problems.push(
+                        "Emergency exit packages are not supported in watch-only mode".to_string(),
+                    );
+                }
+                false
+            }
+        };
+        problems.extend(signer_capability_problems(config, has_ecies));
+        if self.backup_store.is_some() && !has_ecies {
+            problems.push("Backups require a signer that supports ECIES".to_string());
+        }
+        if self
+            .rest_chain_service_config
+            .as_ref()
+            .is_some_and(|cfg| cfg.endpoints.is_empty())
+        {
+            problems.push("No chain service endpoints configured".to_string());
+        }
+
+        if let Some(env_config) = &config.spark_config
+            && let Err(e) = Self::build_spark_wallet_config(config.network.into(), env_config)
+        {
+            problems.push(problem_message(e));
+        }
+
+        let context_storage = self
+            .context
+            .as_ref()
+            .is_some_and(|context| context.storage_backend.is_some());
+        match (self.storage.is_some(), context_storage) {
+            (false, false) => problems.push("No storage configured".to_string()),
+            (true, true) => problems.push(
+                "storage is configured on both the SdkBuilder and the shared SdkContext"
+                    .to_string(),
+            ),
+            _ => {}
+        }
+        if self.storage_namespace.as_deref().is_some_and(str::is_empty) {
+            problems.push("storage namespace must not be empty".to_string());
+        }
+        if let Some(context) = &self.context
+            && (context.network != config.network || context.api_key != config.api_key)
+        {
+            problems.push("SdkContext network/api_key do not match SdkConfig".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SdkError::BuilderError { problems })
+        }
+    }
+
     /// Builds the `BreezSdk` instance from the configured components, reading
     /// top-to-bottom as a sequence of named assembly steps.
     #[allow(clippy::too_many_lines)]
     pub async fn build(self) -> Result<BreezSdk, SdkError> {
-        self.config.validate()?;
         let runtime = runtime_from_config(&self.config);
         let background_services_enabled = runtime.starts_background_services();
-        validate_server_mode(&self.config, background_services_enabled)?;
+        self.validate(background_services_enabled)?;
+        let plugins = PluginManager::new(self.plugins)?;
 
         let signers = build_signers(&self.config, self.signer_source)?;
-        validate_signer_capabilities(&self.config, signers.ecies.is_some())?;
+        let watch_only = signers.spark.is_watch_only();
 
         let creates_context = self.context.is_none();
         let context = resolve_context(self.context, &self.config).await?;
-        let stores = resolve_storage(self.storage, &context, &signers.spark, &self.config).await?;
+        let stores = resolve_storage(
+            self.storage,
+            self.storage_namespace.as_deref(),
+            &context,
+            &signers.spark,
+            &self.config,
+        )
+        .await?;
+        let secret_store = resolve_secret_store(self.secret_store, &stores.storage).await;
         // Start the partner-JWT provider now that storage is resolved. When the builder
-        // creates its own context (no shared context supplied), bind the resolved
-        // storage so the token warm-starts and survives restarts; a shared context
-        // starts in-memory only.
+        // creates its own context (no shared context supplied), bind the secret store
+        // so the token warm-starts and survives restarts; a shared context starts
+        // in-memory only.
         if let Some(provider) = &context.jwt_header_provider {
-            provider.start(creates_context.then(|| Arc::clone(&stores.storage)));
+            provider.start(creates_context.then_some(secret_store));
         }
-        let chain_service = resolve_chain_service(
+        let data_usage = Arc::new(DataUsageTracker::default());
+        let (chain_service, chain_failover) = resolve_chain_service(
             self.chain_service,
             self.rest_chain_service_config,
             &context,
             self.config.network,
+            &data_usage.chain,
         );
 
         let user_agent = crate::default_user_agent();
@@ -496,12 +888,33 @@ impl SdkBuilder {
             Some(service) => Arc::new(FiatServiceWrapper::new(service)),
             None => context.breez_server.clone(),
         };
+        let historical_rates = self.config.historical_rates_url.clone().map(|url| {
+            Arc::new(breez_sdk_common::fiat::CachedHistoricalRates::new(
+                Arc::new(breez_sdk_common::fiat::MempoolHistoricalRates::new(
+                    context.http_client.clone(),
+                    url,
+                )),
+            )) as Arc<dyn breez_sdk_common::fiat::HistoricalRatesService>
+        });
         let lnurl_client: Arc<dyn platform_utils::HttpClient> = self
             .lnurl_client
             .unwrap_or_else(|| context.http_client.clone());
+        let dns_resolver = self
+            .dns_resolver
+            .unwrap_or_else(|| Arc::new(breez_sdk_common::dns::Resolver::new()));
 
-        let spark_wallet_config =
-            finalize_spark_wallet_config(&self.config, &user_agent, background_services_enabled)?;
+        // A watch-only wallet can't sign the optimizations
+        let spark_wallet_config = finalize_spark_wallet_config(
+            &self.config,
+            &user_agent,
+            background_services_enabled && !watch_only,
+        )?;
+        check_config_fingerprint(
+            Arc::clone(&stores.storage),
+            &ConfigFingerprint::new(self.config.network, &spark_wallet_config),
+            self.config_change_accepted,
+        )
+        .await?;
         let shutdown_sender = watch::channel::<()>(()).0;
         // An explicit `with_session_store` override (adapted to the wallet's
         // session-store trait) wins; otherwise use the store the backend
@@ -521,6 +934,7 @@ impl SdkBuilder {
             token_output_store: stores.token_output_store.clone(),
             payment_observer: self.payment_observer,
             context: Arc::clone(&context),
+            data_usage: Arc::clone(&data_usage),
         })
         .await?;
 
@@ -534,6 +948,9 @@ impl SdkBuilder {
         let real_time_sync_active =
             background_services_enabled && self.config.real_time_sync_server_url.is_some();
         let event_emitter = Arc::new(EventEmitter::new(real_time_sync_active));
+        if let Some(chain_failover) = chain_failover {
+            chain_failover.set_event_emitter(Arc::clone(&event_emitter));
+        }
 
         let storage = maybe_wrap_storage_with_real_time_sync(
             Arc::clone(&stores.storage),
@@ -544,9 +961,16 @@ impl SdkBuilder {
             shutdown_sender.subscribe(),
             Arc::clone(&event_emitter),
             lnurl_server_client.clone(),
+            sync_conflict_resolver(self.conflict_resolution, self.conflict_resolver.clone()),
+            Arc::clone(&data_usage.sync),
         )
         .await?;
 
+        let backup_service = signers
+            .hmac
+            .clone()
+            .map(|hmac| Arc::new(BackupService::new(Arc::clone(&storage), hmac)));
+
         let buy_bitcoin_provider = Arc::new(MoonpayProvider::new(context.breez_server.clone()));
         let token_converter =
             build_token_converter(&self.config, &storage, &spark_wallet, &context);
@@ -571,6 +995,7 @@ impl SdkBuilder {
             signers.ecies.clone(),
             &lightning_sender,
             Arc::clone(&fiat_service),
+            &self.clock,
             shutdown_sender.subscribe(),
         );
 
@@ -589,14 +1014,32 @@ impl SdkBuilder {
             .add_middleware(Box::new(TokenConversionMiddleware))
             .await;
 
+        // Registered unconditionally, as the webhook can be configured at runtime
+        let config = SharedConfig::new(self.config);
+        let service_status = Arc::new(ServiceStatusMonitor::new(
+            config.clone(),
+            context.http_client.clone(),
+            Arc::clone(&self.clock),
+        ));
+        event_emitter
+            .add_external_listener(Box::new(WebhookEventListener::new(
+                config.clone(),
+                context.http_client.clone(),
+                shutdown_sender.subscribe(),
+            )))
+            .await;
+
         let sdk = BreezSdk::init_and_start(BreezSdkParams {
-            config: self.config,
+            config,
             storage,
             chain_service,
             fiat_service,
+            historical_rates,
             lnurl_client,
             lnurl_server_client,
+            dns_resolver,
             lnurl_auth_signer: signers.lnurl_auth,
+            hmac_signer: signers.hmac,
             shutdown_sender,
             runtime,
             spark_wallet,
@@ -607,6 +1050,17 @@ impl SdkBuilder {
             sync_coordinator,
             cross_chain_context,
             lightning_sender,
+            plugins,
+            clock: self.clock,
+            payment_approver: self.payment_approver,
+            claim_fee_sponsor: self.claim_fee_sponsor,
+            travel_rule_provider: self.travel_rule_provider,
+            service_status,
+            data_usage,
+            backup_service,
+            backup_store: self.backup_store,
+            emergency_exit: self.emergency_exit,
+            watch_only,
         })
         .await?;
         debug!("Initialized and started breez sdk.");
@@ -615,73 +1069,106 @@ impl SdkBuilder {
     }
 }
 
-/// Rejects server-mode configs that depend on background services.
-fn validate_server_mode(
-    config: &Config,
-    background_services_enabled: bool,
-) -> Result<(), SdkError> {
+/// Problems with server-mode configs that depend on background services.
+fn server_mode_problems(config: &Config, background_services_enabled: bool) -> Vec<String> {
+    let mut problems = Vec::new();
     if background_services_enabled {
-        return Ok(());
+        return problems;
     }
     if config.stable_balance_config.is_some() {
-        return Err(SdkError::InvalidInput(
+        problems.push(
             "stable_balance_config is not supported when background_tasks_enabled is false"
                 .to_string(),
-        ));
+        );
     }
     if config.real_time_sync_server_url.is_some() {
-        return Err(SdkError::InvalidInput(
+        problems.push(
             "real_time_sync_server_url must be None when background_tasks_enabled is false"
                 .to_string(),
-        ));
+        );
     }
     if config.leaf_optimization_config.auto_enabled {
-        return Err(SdkError::InvalidInput(
+        problems.push(
             "leaf_optimization_config.auto_enabled must be false when background_tasks_enabled is false"
                 .to_string(),
-        ));
+        );
     }
     if config.token_optimization_config.auto_enabled {
-        return Err(SdkError::InvalidInput(
+        problems.push(
             "token_optimization_config.auto_enabled must be false when background_tasks_enabled is false"
                 .to_string(),
-        ));
+        );
     }
     if config.cross_chain_config.is_some() {
-        return Err(SdkError::InvalidInput(
+        problems.push(
             "Cross-chain config must be unset when background tasks are disabled".to_string(),
-        ));
+        );
     }
-    Ok(())
+    problems
 }
 
-/// Rejects configs whose features need local encryption when the signer can't
-/// perform ECIES.
-fn validate_signer_capabilities(config: &Config, has_ecies: bool) -> Result<(), SdkError> {
+/// Problems with configs whose features need local encryption when the signer
+/// can't perform ECIES.
+fn signer_capability_problems(config: &Config, has_ecies: bool) -> Vec<String> {
+    let mut problems = Vec::new();
     if has_ecies {
-        return Ok(());
+        return problems;
     }
     if config.real_time_sync_server_url.is_some() {
-        return Err(SdkError::InvalidInput(
-            "Real-time sync requires a signer that supports ECIES".to_string(),
-        ));
+        problems.push("Real-time sync requires a signer that supports ECIES".to_string());
     }
     if config.cross_chain_config.is_some() {
-        return Err(SdkError::InvalidInput(
-            "Cross-chain payments require a signer that supports ECIES".to_string(),
-        ));
+        problems.push("Cross-chain payments require a signer that supports ECIES".to_string());
+    }
+    problems
+}
+
+/// Problems with a watch-only builder: the identity key must be valid, and
+/// features that move funds on their own are unsupported.
+fn watch_only_problems(config: &Config, identity_public_key: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    if let Err(e) = identity_public_key.parse::<bitcoin::secp256k1::PublicKey>() {
+        problems.push(format!("Invalid identity public key: {e}"));
+    }
+    if config.stable_balance_config.is_some() {
+        problems.push("stable_balance_config is not supported in watch-only mode".to_string());
+    }
+    problems
+}
+
+/// Problems with the seed, checked without deriving any key.
+fn seed_problems(seed: &Seed) -> Vec<String> {
+    match seed {
+        Seed::Mnemonic { mnemonic, .. } => match bip39::Mnemonic::parse(mnemonic) {
+            Ok(_) => Vec::new(),
+            Err(e) => vec![format!("Invalid seed mnemonic: {e}")],
+        },
+        // BIP32 seeds are 128 to 512 bits long
+        Seed::Entropy(entropy) if !(16..=64).contains(&entropy.len()) => vec![format!(
+            "Seed entropy must be 16 to 64 bytes long, got {}",
+            entropy.len()
+        )],
+        Seed::Entropy(_) => Vec::new(),
+    }
+}
+
+/// The message of an error reported as one of several builder problems.
+fn problem_message(error: SdkError) -> String {
+    match error {
+        SdkError::InvalidInput(message) | SdkError::Generic(message) => message,
+        other => other.to_string(),
     }
-    Ok(())
 }
 
 /// Derives the SDK-layer signers from one signer source: the Spark signer, and
 /// (when the signer can perform ECIES/HMAC) the `ecies` signer plus the
 /// real-time-sync and lnurl-auth signers. A signing-only external signer can do
-/// neither, so `ecies`, `rtsync`, and `lnurl_auth` are all left `None`.
+/// neither, so `ecies`, `hmac`, `rtsync`, and `lnurl_auth` are all left `None`,
+/// as they are for a watch-only wallet.
 fn build_signers(config: &Config, signer_source: SignerSource) -> Result<Signers, SdkError> {
     use crate::signer::{
         BreezSigner, EciesSigner, ExternalBreezSignerAdapter, ExternalSigningSignerAdapter,
-        ExternalSparkSignerAdapter, HmacSigner,
+        ExternalSparkSignerAdapter, HmacSigner, watch_only::WatchOnlySparkSigner,
     };
 
     // The SDK-layer `BreezSigner` (`base`) roots at the identity master
@@ -713,6 +1200,20 @@ fn build_signers(config: &Config, signer_source: SignerSource) -> Result<Signers
             ));
             (signer.clone(), Some(signer.clone()), Some(signer), spark)
         }
+        SignerSource::WatchOnly {
+            identity_public_key,
+        } => {
+            let identity_public_key = identity_public_key
+                .parse()
+                .map_err(|e| SdkError::InvalidInput(format!("Invalid identity public key: {e}")))?;
+            return Ok(Signers {
+                ecies: None,
+                spark: Arc::new(WatchOnlySparkSigner::new(identity_public_key)),
+                rtsync: None,
+                lnurl_auth: None,
+                hmac: None,
+            });
+        }
         SignerSource::External { breez, spark } => {
             let spark: Arc<dyn SparkSigner> = Arc::new(ExternalSparkSignerAdapter::new(spark));
             match breez {
@@ -747,6 +1248,7 @@ fn build_signers(config: &Config, signer_source: SignerSource) -> Result<Signers
         spark,
         rtsync,
         lnurl_auth,
+        hmac,
     })
 }
 
@@ -778,6 +1280,7 @@ async fn resolve_context(
 /// context, never both — and asks it for the per-tenant store set.
 async fn resolve_storage(
     supplied: Option<Arc<dyn StorageBackend>>,
+    namespace: Option<&str>,
     context: &SdkContext,
     spark_signer: &Arc<dyn SparkSigner>,
     config: &Config,
@@ -794,38 +1297,74 @@ async fn resolve_storage(
         }
         (None, None) => return Err(SdkError::Generic("No storage configured".to_string())),
     };
+    if namespace.is_some_and(str::is_empty) {
+        return Err(SdkError::InvalidInput(
+            "storage namespace must not be empty".to_string(),
+        ));
+    }
     let identity_public_key = spark_signer
         .get_identity_public_key()
         .await
         .map_err(|e| SdkError::Generic(e.to_string()))?;
     storage_backend
-        .create_stores(config.network, identity_public_key.serialize().to_vec())
+        .create_stores(
+            config.network,
+            storage_tenant_key(&identity_public_key.serialize(), namespace),
+        )
         .await
 }
 
 /// Resolves the chain service: caller-supplied override → REST config → network
-/// default (Esplora on mainnet, mempool.space on regtest).
+/// default (Esplora on mainnet, mempool.space on regtest). Also returns the
+/// failover service when several REST endpoints are configured.
 fn resolve_chain_service(
     supplied: Option<Arc<dyn BitcoinChainService>>,
     rest_config: Option<RestChainServiceConfig>,
     context: &SdkContext,
     network: Network,
-) -> Arc<dyn BitcoinChainService> {
+    byte_counter: &Arc<ByteCounter>,
+) -> (
+    Arc<dyn BitcoinChainService>,
+    Option<Arc<FailoverChainService>>,
+) {
     if let Some(service) = supplied {
-        return service;
+        return (service, None);
     }
     if let Some(cfg) = rest_config {
-        return Arc::new(RestClientChainService::new(
-            cfg.url,
-            network,
-            5,
-            context.http_client.clone(),
-            cfg.credentials
-                .map(|c| BasicAuth::new(c.username, c.password)),
-            cfg.api_type,
-        ));
+        let mut endpoints: Vec<ChainEndpoint> = cfg
+            .endpoints
+            .into_iter()
+            .map(|endpoint| ChainEndpoint {
+                service: Arc::new(RestClientChainService::new(
+                    endpoint.url.clone(),
+                    network,
+                    5,
+                    DataUsageTracker::metered(context.http_client.clone(), byte_counter),
+                    endpoint
+                        .credentials
+                        .map(|c| BasicAuth::new(c.username, c.password)),
+                    endpoint.api_type,
+                )),
+                url: endpoint.url,
+            })
+            .collect();
+        if endpoints.len() > 1 {
+            let failover = Arc::new(FailoverChainService::new(endpoints));
+            return (failover.clone(), Some(failover));
+        }
+        if let Some(endpoint) = endpoints.pop() {
+            return (endpoint.service, None);
+        }
     }
-    let inner_client: Arc<dyn platform_utils::HttpClient> = context.http_client.clone();
+    (default_chain_service(context, network, byte_counter), None)
+}
+
+fn default_chain_service(
+    context: &SdkContext,
+    network: Network,
+    byte_counter: &Arc<ByteCounter>,
+) -> Arc<dyn BitcoinChainService> {
+    let inner_client = DataUsageTracker::metered(context.http_client.clone(), byte_counter);
     match network {
         Network::Mainnet => Arc::new(RestClientChainService::new(
             "https://blockstream.info/api".to_string(),
@@ -857,8 +1396,9 @@ fn resolve_chain_service(
 
 /// Builds the full [`SparkWalletConfig`] with user-agent and SDK-level
 /// optimization overrides applied. `background_services_enabled` gates the
-/// auto-optimization flags so server-mode SDKs don't run background loops.
-fn finalize_spark_wallet_config(
+/// auto-optimization flags so server-mode and watch-only SDKs don't run
+/// background loops.
+pub(crate) fn finalize_spark_wallet_config(
     config: &Config,
     user_agent: &str,
     background_services_enabled: bool,
@@ -886,9 +1426,33 @@ fn finalize_spark_wallet_config(
         token_options.auto_optimize_interval = None;
     }
     spark_wallet_config.max_concurrent_claims = config.max_concurrent_claims;
+    spark_wallet_config.claim_batch_size = config.claim_batch_size;
+    spark_wallet_config.claim_batch_interval_ms = u64::from(config.claim_batch_interval_ms);
+    #[cfg(feature = "websocket-events")]
+    if config.event_stream_websocket {
+        spark_wallet_config.event_transport = spark_wallet::EventTransport::WebSocket {
+            max_failures: spark_wallet::DEFAULT_WEBSOCKET_MAX_FAILURES,
+        };
+    }
     Ok(spark_wallet_config)
 }
 
+/// Returns the secret store supplied via [`SdkBuilder::with_secret_store`],
+/// after moving the secrets left in the main storage into it, or the main
+/// storage otherwise.
+async fn resolve_secret_store(
+    secret_store: Option<Arc<dyn SecretStore>>,
+    storage: &Arc<dyn Storage>,
+) -> Arc<dyn SecretStore> {
+    match secret_store {
+        Some(secret_store) => {
+            migrate_secrets(storage.as_ref(), secret_store.as_ref()).await;
+            secret_store
+        }
+        None => Arc::new(StorageSecretStore::new(Arc::clone(storage))),
+    }
+}
+
 /// Wraps the resolved session store (or an in-memory default) in the in-memory
 /// caching layer. Tokens are stored as-is: the SDK applies no encryption (see
 /// [`SdkBuilder::with_session_store`] to layer your own).
@@ -928,7 +1492,12 @@ async fn build_spark_wallet(params: BuildSparkWalletParams) -> Result<Arc<SparkW
     if let Some(token_output_store) = params.token_output_store {
         wallet_builder = wallet_builder.with_token_output_store(token_output_store);
     }
-    wallet_builder = wallet_builder.with_ssp_http_client(params.context.http_client.clone());
+    wallet_builder = wallet_builder
+        .with_ssp_http_client(DataUsageTracker::metered(
+            params.context.http_client.clone(),
+            &params.data_usage.ssp,
+        ))
+        .with_operator_byte_counter(Arc::clone(&params.data_usage.operator));
     wallet_builder =
         wallet_builder.with_connection_manager(params.context.connection_manager.clone());
     Ok(Arc::new(wallet_builder.build().await?))
@@ -967,8 +1536,10 @@ async fn maybe_wrap_storage_with_real_time_sync(
     shutdown_receiver: watch::Receiver<()>,
     event_emitter: Arc<EventEmitter>,
     lnurl_server_client: Option<Arc<dyn LnurlServerClient>>,
+    conflict_resolver: Option<Arc<dyn breez_sdk_common::sync::ConflictResolver>>,
+    byte_counter: Arc<ByteCounter>,
 ) -> Result<Arc<dyn crate::persist::Storage>, SdkError> {
-    // `validate_signer_capabilities` rejects real-time sync without an
+    // `SdkBuilder::validate` rejects real-time sync without an
     // ECIES-capable signer, so `rtsync_signer` is present whenever the URL is
     // set; a missing signer can't be reached and falls through to the no-op arm.
     match (&config.real_time_sync_server_url, rtsync_signer) {
@@ -982,6 +1553,8 @@ async fn maybe_wrap_storage_with_real_time_sync(
                 shutdown_receiver,
                 event_emitter,
                 lnurl_server_client,
+                conflict_resolver,
+                byte_counter,
             })
             .await
         }
@@ -1048,6 +1621,7 @@ fn build_cross_chain_context(
     ecies: Option<Arc<dyn crate::signer::EciesSigner>>,
     lightning_sender: &Arc<crate::sdk::LightningSender>,
     fiat_service: Arc<dyn breez_sdk_common::fiat::FiatService>,
+    clock: &Arc<dyn Clock>,
     shutdown_receiver: watch::Receiver<()>,
 ) -> crate::cross_chain::CrossChainContext {
     // Cache scoped to cross-chain: providers + dispatcher share one TTL window.
@@ -1076,6 +1650,7 @@ fn build_cross_chain_context(
                 Arc::clone(spark_wallet),
                 Arc::clone(storage),
                 Arc::clone(&cached_fiat),
+                Arc::clone(clock),
                 shutdown_receiver.clone(),
             )),
         );
@@ -1088,6 +1663,7 @@ fn build_cross_chain_context(
         ecies,
         cached_fiat,
         Arc::clone(lightning_sender),
+        Arc::clone(clock),
         shutdown_receiver,
     ) {
         Ok(Some(service)) => {
@@ -1132,29 +1708,26 @@ mod tests {
     }
 
     #[test]
-    fn validate_signer_capabilities_gates_encryption_features() {
+    fn signer_capability_problems_gates_encryption_features() {
         // ECIES/HMAC supported: encryption-dependent features are allowed.
         let mut config = default_config(Network::Regtest);
         config.real_time_sync_server_url = Some("https://example.com".to_string());
-        assert!(super::validate_signer_capabilities(&config, true).is_ok());
+        assert!(super::signer_capability_problems(&config, true).is_empty());
 
         // No ECIES/HMAC + real-time sync: rejected with a clear error.
         let mut config = default_config(Network::Regtest);
         config.real_time_sync_server_url = Some("https://example.com".to_string());
         config.cross_chain_config = None;
-        match super::validate_signer_capabilities(&config, false) {
-            Err(SdkError::InvalidInput(m)) => {
-                assert!(m.contains("Real-time sync"), "got: {m}");
-            }
-            other => panic!("expected InvalidInput, got {other:?}"),
-        }
+        let problems = super::signer_capability_problems(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("Real-time sync"), "got: {problems:?}");
 
         // No ECIES/HMAC + no encryption-dependent feature: allowed, so a
         // payments-only wallet still builds.
         let mut config = default_config(Network::Regtest);
         config.real_time_sync_server_url = None;
         config.cross_chain_config = None;
-        assert!(super::validate_signer_capabilities(&config, false).is_ok());
+        assert!(super::signer_capability_problems(&config, false).is_empty());
     }
 
     /// A signing-only signer yields no ECIES/HMAC, so `build_signers` leaves the
@@ -1242,7 +1815,7 @@ mod tests {
     }
 
     /// End to end: an offline SDK built with a signing-only signer has no
-    /// lnurl-auth signer, and `lnurl_auth` rejects with `InvalidInput`.
+    /// lnurl-auth signer, and `lnurl_auth` rejects with `Generic`.
     #[tokio::test]
     async fn signing_only_signer_build_disables_lnurl_auth() {
         use crate::error::SignerError;
@@ -1336,7 +1909,7 @@ mod tests {
 
     /// A signing-only signer is rejected at build when a feature that needs ECIES
     /// is configured. Here real-time sync is set, so `build()` returns
-    /// `InvalidInput` before any signer method runs.
+    /// `BuilderError` before any signer method runs.
     #[tokio::test]
     async fn signing_only_signer_build_rejects_ecies_features() {
         use crate::error::SignerError;
@@ -1398,9 +1971,9 @@ mod tests {
             .build()
             .await;
         match result {
-            Err(SdkError::InvalidInput(_)) => {}
+            Err(SdkError::BuilderError { .. }) => {}
             Ok(_) => panic!("build must reject real-time sync for a signing-only signer"),
-            Err(other) => panic!("expected InvalidInput, got {other:?}"),
+            Err(other) => panic!("expected BuilderError, got {other:?}"),
         }
     }
 
@@ -1479,10 +2052,13 @@ mod tests {
         let seed = test_seed();
         let result = SdkBuilder::new(config, seed).build().await;
         match result {
-            Err(SdkError::InvalidInput(message)) => {
-                assert!(message.contains("stable_balance_config"));
+            Err(SdkError::BuilderError { problems }) => {
+                assert!(
+                    problems.iter().any(|p| p.contains("stable_balance_config")),
+                    "got: {problems:?}"
+                );
             }
-            Err(err) => panic!("expected InvalidInput error, got {err:?}"),
+            Err(err) => panic!("expected BuilderError, got {err:?}"),
             Ok(_) => panic!("expected server mode with Stable Balance config to fail"),
         }
     }
@@ -1497,10 +2073,15 @@ mod tests {
         let seed = test_seed();
         let result = SdkBuilder::new(config, seed).build().await;
         match result {
-            Err(SdkError::InvalidInput(message)) => {
-                assert!(message.contains("real_time_sync_server_url"));
+            Err(SdkError::BuilderError { problems }) => {
+                assert!(
+                    problems
+                        .iter()
+                        .any(|p| p.contains("real_time_sync_server_url")),
+                    "got: {problems:?}"
+                );
             }
-            Err(err) => panic!("expected InvalidInput error, got {err:?}"),
+            Err(err) => panic!("expected BuilderError, got {err:?}"),
             Ok(_) => panic!("expected server mode with real_time_sync_server_url to fail"),
         }
     }
@@ -1515,10 +2096,15 @@ mod tests {
         let seed = test_seed();
         let result = SdkBuilder::new(config, seed).build().await;
         match result {
-            Err(SdkError::InvalidInput(message)) => {
-                assert!(message.contains("leaf_optimization_config.auto_enabled"));
+            Err(SdkError::BuilderError { problems }) => {
+                assert!(
+                    problems
+                        .iter()
+                        .any(|p| p.contains("leaf_optimization_config.auto_enabled")),
+                    "got: {problems:?}"
+                );
             }
-            Err(err) => panic!("expected InvalidInput error, got {err:?}"),
+            Err(err) => panic!("expected BuilderError, got {err:?}"),
             Ok(_) => panic!("expected server mode with optimization auto_enabled to fail"),
         }
     }
@@ -1533,18 +2119,21 @@ mod tests {
         let seed = test_seed();
         let result = SdkBuilder::new(config, seed).build().await;
         match result {
-            Err(SdkError::InvalidInput(message)) => {
-                assert!(message.contains("token_optimization_config.auto_enabled"));
+            Err(SdkError::BuilderError { problems }) => {
+                assert!(
+                    problems
+                        .iter()
+                        .any(|p| p.contains("token_optimization_config.auto_enabled")),
+                    "got: {problems:?}"
+                );
             }
-            Err(err) => panic!("expected InvalidInput error, got {err:?}"),
+            Err(err) => panic!("expected BuilderError, got {err:?}"),
             Ok(_) => panic!("expected server mode with optimization auto_enabled to fail"),
         }
     }
 
     /// Regtest + `cross_chain_config` trips the Mainnet-only gate in
-    /// `Config::validate` before reaching the server-mode reject in
-    /// `build`. The server-mode gate is still in place (verified by the
-    /// inline check in `build`); this test pins the more specific failure.
+    /// `Config::validate`, reported along with the other builder problems.
     #[tokio::test]
     async fn build_rejects_cross_chain_config_on_regtest() {
         use crate::{CrossChainConfig, SdkError, default_config};
@@ -1554,17 +2143,153 @@ mod tests {
         let seed = test_seed();
         let result = SdkBuilder::new(config, seed).build().await;
         match result {
-            Err(SdkError::InvalidInput(m)) => {
+            Err(SdkError::BuilderError { problems }) => {
                 assert!(
-                    m.contains("only available on Mainnet"),
-                    "expected mainnet-only rejection, got: {m}"
+                    problems
+                        .iter()
+                        .any(|p| p.contains("only available on Mainnet")),
+                    "expected mainnet-only rejection, got: {problems:?}"
                 );
             }
-            Err(err) => panic!("expected InvalidInput error, got {err:?}"),
+            Err(err) => panic!("expected BuilderError, got {err:?}"),
             Ok(_) => panic!("expected regtest with cross_chain_config to fail"),
         }
     }
 
+    /// Every misconfiguration is reported at once, before anything connects.
+    #[tokio::test]
+    async fn build_reports_all_builder_problems() {
+        use crate::default_server_config;
+
+        let mut config = default_server_config(Network::Regtest);
+        config.real_time_sync_server_url = Some("https://example.com".to_string());
+        let seed = crate::Seed::Mnemonic {
+            mnemonic: "not a mnemonic".to_string(),
+            passphrase: None,
+        };
+        let result = SdkBuilder::new(config, seed)
+            .with_storage_namespace(String::new())
+            .build()
+            .await;
+        let Err(SdkError::BuilderError { problems }) = result else {
+            panic!("expected BuilderError");
+        };
+        for expected in [
+            "real_time_sync_server_url",
+            "Invalid seed mnemonic",
+            "No storage configured",
+            "storage namespace must not be empty",
+        ] {
+            assert!(
+                problems.iter().any(|p| p.contains(expected)),
+                "missing {expected:?} in {problems:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_only_rejects_invalid_identity_key() {
+        let config = default_config(Network::Regtest);
+        let result = SdkBuilder::new_watch_only(config, "not a key".to_string())
+            .build()
+            .await;
+        let Err(SdkError::BuilderError { problems }) = result else {
+            panic!("expected BuilderError");
+        };
+        assert!(
+            problems
+                .iter()
+                .any(|p| p.contains("Invalid identity public key")),
+            "got: {problems:?}"
+        );
+    }
+
+    /// A bundle written to a file and read back builds a watch-only wallet
+    /// with its identity key.
+    #[test]
+    fn viewer_bundle_roundtrip() {
+        use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+        let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let identity_public_key =
+            PublicKey::from_secret_key(&Secp256k1::new(), &secret_key).to_string();
+        let bundle = crate::ViewerBundle {
+            identity_public_key: identity_public_key.clone(),
+            created_at: 1_700_000_000,
+        };
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("token"));
+        let restored: crate::ViewerBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, bundle);
+
+        let builder = SdkBuilder::new_viewer(default_config(Network::Regtest), restored);
+        assert!(matches!(
+            builder.signer_source,
+            super::SignerSource::WatchOnly { identity_public_key: key } if key == identity_public_key
+        ));
+    }
+
+    #[tokio::test]
+    async fn viewer_rejects_invalid_identity_key() {
+        let bundle = crate::ViewerBundle {
+            identity_public_key: "not a key".to_string(),
+            created_at: 0,
+        };
+        let result = SdkBuilder::new_viewer(default_config(Network::Regtest), bundle)
+            .build()
+            .await;
+        let Err(SdkError::BuilderError { problems }) = result else {
+            panic!("expected BuilderError");
+        };
+        assert!(
+            problems
+                .iter()
+                .any(|p| p.contains("Invalid identity public key")),
+            "got: {problems:?}"
+        );
+    }
+
+    /// A watch-only source yields a Spark signer that only knows the identity
+    /// key, and none of the signers that need key material.
+    #[tokio::test]
+    async fn build_signers_watch_only_holds_identity_key_only() {
+        use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+        use spark_wallet::SparkSigner;
+
+        let secret_key = SecretKey::from_slice(&[1; 32]).unwrap();
+        let identity_public_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+        let signers = super::build_signers(
+            &default_config(Network::Regtest),
+            super::SignerSource::WatchOnly {
+                identity_public_key: identity_public_key.to_string(),
+            },
+        )
+        .unwrap();
+
+        assert!(signers.spark.is_watch_only());
+        assert_eq!(
+            signers.spark.get_identity_public_key().await.unwrap(),
+            identity_public_key
+        );
+        assert!(signers.spark.sign_message(b"message").await.is_err());
+        assert!(signers.ecies.is_none());
+        assert!(signers.hmac.is_none());
+        assert!(signers.rtsync.is_none());
+        assert!(signers.lnurl_auth.is_none());
+    }
+
+    #[test]
+    fn seed_problems_checks_entropy_length() {
+        assert!(super::seed_problems(&test_seed()).is_empty());
+        assert!(super::seed_problems(&crate::Seed::Entropy(vec![7; 32])).is_empty());
+        assert_eq!(super::seed_problems(&crate::Seed::Entropy(vec![])).len(), 1);
+        assert_eq!(
+            super::seed_problems(&crate::Seed::Entropy(vec![7; 65])).len(),
+            1
+        );
+    }
+
     /// Mainnet SDK with a caller-supplied Regtest context errors at `build()`
     /// — the context has no JWT provider so the partner JWT would be silently
     /// disabled.
@@ -1827,10 +2552,10 @@ mod tests {
         )))
     }
 
-    // ---- validate_server_mode ----
+    // ---- server_mode_problems ----
 
     #[test]
-    fn validate_server_mode_ok_when_background_enabled() {
+    fn server_mode_problems_ok_when_background_enabled() {
         use crate::{StableBalanceConfig, StableBalanceToken, default_server_config};
         let mut config = default_server_config(Network::Regtest);
         config.stable_balance_config = Some(StableBalanceConfig {
@@ -1846,18 +2571,18 @@ mod tests {
         config.leaf_optimization_config.auto_enabled = true;
         config.token_optimization_config.auto_enabled = true;
         // background_services_enabled = true → none of the gates fire.
-        assert!(super::validate_server_mode(&config, true).is_ok());
+        assert!(super::server_mode_problems(&config, true).is_empty());
     }
 
     #[test]
-    fn validate_server_mode_ok_in_server_mode_without_background_features() {
+    fn server_mode_problems_ok_in_server_mode_without_background_features() {
         use crate::default_server_config;
         let config = default_server_config(Network::Regtest);
-        assert!(super::validate_server_mode(&config, false).is_ok());
+        assert!(super::server_mode_problems(&config, false).is_empty());
     }
 
     #[test]
-    fn validate_server_mode_rejects_stable_balance_directly() {
+    fn server_mode_problems_rejects_stable_balance_directly() {
         use crate::{StableBalanceConfig, StableBalanceToken, default_server_config};
         let mut config = default_server_config(Network::Regtest);
         config.stable_balance_config = Some(StableBalanceConfig {
@@ -1869,58 +2594,64 @@ mod tests {
             threshold_sats: None,
             max_slippage_bps: None,
         });
-        match super::validate_server_mode(&config, false) {
-            Err(SdkError::InvalidInput(m)) => assert!(m.contains("stable_balance_config")),
-            other => panic!("expected InvalidInput, got {other:?}"),
-        }
+        let problems = super::server_mode_problems(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("stable_balance_config"),
+            "got: {problems:?}"
+        );
     }
 
     #[test]
-    fn validate_server_mode_rejects_real_time_sync_directly() {
+    fn server_mode_problems_rejects_real_time_sync_directly() {
         use crate::default_server_config;
         let mut config = default_server_config(Network::Regtest);
         config.real_time_sync_server_url = Some("https://example.com".to_string());
-        match super::validate_server_mode(&config, false) {
-            Err(SdkError::InvalidInput(m)) => assert!(m.contains("real_time_sync_server_url")),
-            other => panic!("expected InvalidInput, got {other:?}"),
-        }
+        let problems = super::server_mode_problems(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("real_time_sync_server_url"),
+            "got: {problems:?}"
+        );
     }
 
     #[test]
-    fn validate_server_mode_rejects_leaf_auto_optimize_directly() {
+    fn server_mode_problems_rejects_leaf_auto_optimize_directly() {
         use crate::default_server_config;
         let mut config = default_server_config(Network::Regtest);
         config.leaf_optimization_config.auto_enabled = true;
-        match super::validate_server_mode(&config, false) {
-            Err(SdkError::InvalidInput(m)) => {
-                assert!(m.contains("leaf_optimization_config.auto_enabled"));
-            }
-            other => panic!("expected InvalidInput, got {other:?}"),
-        }
+        let problems = super::server_mode_problems(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("leaf_optimization_config.auto_enabled"),
+            "got: {problems:?}"
+        );
     }
 
     #[test]
-    fn validate_server_mode_rejects_token_auto_optimize_directly() {
+    fn server_mode_problems_rejects_token_auto_optimize_directly() {
         use crate::default_server_config;
         let mut config = default_server_config(Network::Regtest);
         config.token_optimization_config.auto_enabled = true;
-        match super::validate_server_mode(&config, false) {
-            Err(SdkError::InvalidInput(m)) => {
-                assert!(m.contains("token_optimization_config.auto_enabled"));
-            }
-            other => panic!("expected InvalidInput, got {other:?}"),
-        }
+        let problems = super::server_mode_problems(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("token_optimization_config.auto_enabled"),
+            "got: {problems:?}"
+        );
     }
 
     #[test]
-    fn validate_server_mode_rejects_cross_chain_directly() {
+    fn server_mode_problems_rejects_cross_chain_directly() {
         use crate::{CrossChainConfig, default_server_config};
         let mut config = default_server_config(Network::Regtest);
         config.cross_chain_config = Some(CrossChainConfig::default());
-        match super::validate_server_mode(&config, false) {
-            Err(SdkError::InvalidInput(m)) => assert!(m.contains("Cross-chain config")),
-            other => panic!("expected InvalidInput, got {other:?}"),
-        }
+        let problems = super::server_mode_problems(&config, false);
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("Cross-chain config"),
+            "got: {problems:?}"
+        );
     }
 
     // ---- finalize_spark_wallet_config ----
@@ -2014,7 +2745,7 @@ mod tests {
             .await
             .expect("regtest context");
         let signer = test_spark_signer();
-        let err = super::resolve_storage(None, &ctx, &signer, &config)
+        let err = super::resolve_storage(None, None, &ctx, &signer, &config)
             .await
             .err()
             .expect("expected no-storage error");
@@ -2039,7 +2770,7 @@ mod tests {
         let signer = test_spark_signer();
         let builder_storage =
             default_storage("/tmp/breez-sdk-test-resolve-storage-builder".to_string());
-        let err = super::resolve_storage(Some(builder_storage), &ctx, &signer, &config)
+        let err = super::resolve_storage(Some(builder_storage), None, &ctx, &signer, &config)
             .await
             .err()
             .expect("expected duplicate-storage error");
@@ -0,0 +1,206 @@
+//! Storage for secret material, such as the Breez partner JWT.
+//!
+//! By default secrets are kept in the SDK's main [`Storage`]. Supply a
+//! [`SecretStore`] via `SdkBuilder::with_secret_store` to keep them in a
+//! platform keystore instead, such as the iOS Keychain or the Android
+//! Keystore. Secrets that are still in the main storage are moved into the
+//! supplied store when the SDK is built.
+//!
+//! Spark operator and SSP session tokens are kept in the
+//! [`SessionStore`](crate::SessionStore), which can be overridden separately.
+
+use std::sync::Arc;
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::{jwt_header_provider::KEY_BREEZ_JWT, persist::Storage};
+
+/// Keys of the secrets the SDK keeps in the secret store.
+const SECRET_KEYS: &[&str] = &[KEY_BREEZ_JWT];
+
+#[derive(Debug, Error, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
+pub enum SecretStoreError {
+    #[error("Generic error: {0}")]
+    Generic(String),
+}
+
+/// Key-value store for secret material. Implementations should be thread-safe.
+#[cfg_attr(feature = "uniffi", uniffi::export(with_foreign))]
+#[macros::async_trait]
+pub trait SecretStore: Send + Sync {
+    /// Returns the secret stored under `key`, if any
+    async fn get_secret(&self, key: String) -> Result<Option<String>, SecretStoreError>;
+
+    /// Stores `value` under `key`, replacing any previous secret
+    async fn set_secret(&self, key: String, value: String) -> Result<(), SecretStoreError>;
+
+    /// Deletes the secret stored under `key`. Deleting a missing key succeeds.
+    async fn delete_secret(&self, key: String) -> Result<(), SecretStoreError>;
+}
+
+/// The default [`SecretStore`], keeping secrets in the main storage.
+pub(crate) struct StorageSecretStore {
+    storage: Arc<dyn Storage>,
+}
+
+impl StorageSecretStore {
+    pub(crate) fn new(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[macros::async_trait]
+impl SecretStore for StorageSecretStore {
+    async fn get_secret(&self, key: String) -> Result<Option<String>, SecretStoreError> {
+        self.storage
+            .get_cached_item(key)
+            .await
+            .map_err(|e| SecretStoreError::Generic(e.to_string()))
+    }
+
+    async fn set_secret(&self, key: String, value: String) -> Result<(), SecretStoreError> {
+        self.storage
+            .set_cached_item(key, value)
+            .await
+            .map_err(|e| SecretStoreError::Generic(e.to_string()))
+    }
+
+    async fn delete_secret(&self, key: String) -> Result<(), SecretStoreError> {
+        self.storage
+            .delete_cached_item(key)
+            .await
+            .map_err(|e| SecretStoreError::Generic(e.to_string()))
+    }
+}
+
+/// Moves the secrets that are still in the main storage into `secret_store`.
+///
+/// A secret is only removed from the main storage once it is in the secret
+/// store. Failures are logged and retried on the next build.
+pub(crate) async fn migrate_secrets(storage: &dyn Storage, secret_store: &dyn SecretStore) {
+    for key in SECRET_KEYS {
+        if let Err(e) = migrate_secret(storage, secret_store, key).await {
+            warn!("Failed to move secret {key} to the secret store: {e}");
+        }
+    }
+}
+
+async fn migrate_secret(
+    storage: &dyn Storage,
+    secret_store: &dyn SecretStore,
+    key: &str,
+) -> Result<(), SecretStoreError> {
+    let to_error = |e: crate::persist::StorageError| SecretStoreError::Generic(e.to_string());
+    let Some(value) = storage
+        .get_cached_item(key.to_string())
+        .await
+        .map_err(to_error)?
+    else {
+        return Ok(());
+    };
+    // A secret already in the secret store is newer than the leftover copy
+    if secret_store.get_secret(key.to_string()).await?.is_none() {
+        secret_store.set_secret(key.to_string(), value).await?;
+    }
+    storage
+        .delete_cached_item(key.to_string())
+        .await
+        .map_err(to_error)?;
+    info!("Moved secret {key} to the secret store");
+    Ok(())
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::persist::sqlite::SqliteStorage;
+
+    #[derive(Default)]
+    struct MemorySecretStore(Mutex<HashMap<String, String>>);
+
+    #[macros::async_trait]
+    impl SecretStore for MemorySecretStore {
+        async fn get_secret(&self, key: String) -> Result<Option<String>, SecretStoreError> {
+            Ok(self.0.lock().unwrap().get(&key).cloned())
+        }
+
+        async fn set_secret(&self, key: String, value: String) -> Result<(), SecretStoreError> {
+            self.0.lock().unwrap().insert(key, value);
+            Ok(())
+        }
+
+        async fn delete_secret(&self, key: String) -> Result<(), SecretStoreError> {
+            self.0.lock().unwrap().remove(&key);
+            Ok(())
+        }
+    }
+
+    fn temp_storage() -> Arc<dyn Storage> {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("breez-secret-store-test-{}", uuid::Uuid::new_v4()));
+        Arc::new(SqliteStorage::new(&dir).expect("create sqlite storage"))
+    }
+
+    #[tokio::test]
+    async fn test_migrate_secrets_moves_secrets() {
+        let storage = temp_storage();
+        storage
+            .set_cached_item(KEY_BREEZ_JWT.to_string(), "jwt".to_string())
+            .await
+            .unwrap();
+        let secret_store = MemorySecretStore::default();
+
+        migrate_secrets(storage.as_ref(), &secret_store).await;
+
+        assert_eq!(
+            secret_store
+                .get_secret(KEY_BREEZ_JWT.to_string())
+                .await
+                .unwrap(),
+            Some("jwt".to_string())
+        );
+        assert_eq!(
+            storage
+                .get_cached_item(KEY_BREEZ_JWT.to_string())
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_secrets_keeps_existing_secret() {
+        let storage = temp_storage();
+        storage
+            .set_cached_item(KEY_BREEZ_JWT.to_string(), "old".to_string())
+            .await
+            .unwrap();
+        let secret_store = MemorySecretStore::default();
+        secret_store
+            .set_secret(KEY_BREEZ_JWT.to_string(), "new".to_string())
+            .await
+            .unwrap();
+
+        migrate_secrets(storage.as_ref(), &secret_store).await;
+
+        assert_eq!(
+            secret_store
+                .get_secret(KEY_BREEZ_JWT.to_string())
+                .await
+                .unwrap(),
+            Some("new".to_string())
+        );
+        assert_eq!(
+            storage
+                .get_cached_item(KEY_BREEZ_JWT.to_string())
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
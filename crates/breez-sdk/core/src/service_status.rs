@@ -0,0 +1,242 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use platform_utils::HttpClient;
+use platform_utils::{time::UNIX_EPOCH, tokio};
+use serde::Deserialize;
+use tokio::{select, sync::watch, time::sleep};
+use tracing::{debug, info, warn};
+
+use crate::{
+    MaintenanceWindow, clock::Clock, error::SdkError, models::GetServiceStatusResponse,
+    sdk::SharedConfig,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(300);
+
+/// The document served at [`Config::service_status_url`](crate::Config::service_status_url).
+#[derive(Debug, Default, Deserialize)]
+struct ServiceStatusFeed {
+    #[serde(default)]
+    maintenance_windows: Vec<MaintenanceWindow>,
+}
+
+/// Keeps the maintenance windows announced by the service status feed.
+pub(crate) struct ServiceStatusMonitor {
+    config: SharedConfig,
+    http_client: Arc<dyn HttpClient>,
+    clock: Arc<dyn Clock>,
+    maintenance_windows: RwLock<Vec<MaintenanceWindow>>,
+}
+
+impl ServiceStatusMonitor {
+    pub(crate) fn new(
+        config: SharedConfig,
+        http_client: Arc<dyn HttpClient>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            config,
+            http_client,
+            clock,
+            maintenance_windows: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Fetches the feed, if one is configured, and keeps its maintenance windows.
+    pub(crate) async fn refresh(&self) -> Result<(), SdkError> {
+        let Some(url) = self.config.get().service_status_url.clone() else {
+            return Ok(());
+        };
+        let response = self
+            .http_client
+            .get(url, None)
+            .await
+            .map_err(|e| SdkError::NetworkError(e.to_string()))?;
+        if !response.is_success() {
+            return Err(SdkError::NetworkError(format!(
+                "Service status feed returned status {}",
+                response.status
+            )));
+        }
+        let feed: ServiceStatusFeed = response
+            .json()
+            .map_err(|e| SdkError::Generic(format!("Invalid service status feed: {e}")))?;
+        *self
+            .maintenance_windows
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = feed.maintenance_windows;
+        Ok(())
+    }
+
+    pub(crate) fn status(&self) -> GetServiceStatusResponse {
+        let now = self.now_secs();
+        let maintenance_windows: Vec<MaintenanceWindow> = self
+            .maintenance_windows
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|window| window.ends_at > now)
+            .cloned()
+            .collect();
+        let active_maintenance = maintenance_windows
+            .iter()
+            .find(|window| window.is_active(now))
+            .cloned();
+        GetServiceStatusResponse {
+            maintenance_windows,
+            active_maintenance,
+        }
+    }
+
+    /// The maintenance window in progress, if any.
+    pub(crate) fn active_maintenance(&self) -> Option<MaintenanceWindow> {
+        let now = self.now_secs();
+        self.maintenance_windows
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|window| window.is_active(now))
+            .cloned()
+    }
+
+    /// Reports network failures during a maintenance window as
+    /// [`SdkError::ServiceMaintenance`]. Other errors are returned unchanged.
+    pub(crate) fn annotate(&self, error: SdkError) -> SdkError {
+        if !matches!(error, SdkError::NetworkError(_) | SdkError::SparkError(_)) {
+            return error;
+        }
+        match self.active_maintenance() {
+            Some(window) => SdkError::ServiceMaintenance {
+                until: window.ends_at,
+            },
+            None => error,
+        }
+    }
+
+    /// Refreshes the feed periodically until shutdown.
+    pub(crate) fn spawn_polling(self: &Arc<Self>, mut shutdown_receiver: watch::Receiver<()>) {
+        let monitor = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                // In low-data mode the feed is only fetched on demand
+                let config = monitor.config.get();
+                if !config.low_data_mode
+                    && let Err(e) = monitor.refresh().await
+                {
+                    warn!("Failed to refresh the service status: {e}");
+                }
+                if let Some(window) = monitor.active_maintenance() {
+                    info!(
+                        "Service maintenance in progress until {}: {:?}",
+                        window.ends_at, window.message
+                    );
+                }
+                select! {
+                    _ = shutdown_receiver.changed() => {
+                        debug!("Service status polling stopped on shutdown");
+                        return;
+                    }
+                    () = sleep(config.polling_interval(POLL_INTERVAL)) => {}
+                }
+            }
+        });
+    }
+
+    fn now_secs(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+impl MaintenanceWindow {
+    fn is_active(&self, now: u64) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use breez_sdk_common::test_utils::mock_rest_client::{MockResponse, MockRestClient};
+    use macros::async_test_all;
+
+    use super::*;
+    use crate::{Network, clock::system_clock, default_config};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn now_secs() -> u64 {
+        platform_utils::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn monitor(feed: &str) -> ServiceStatusMonitor {
+        let mut config = default_config(Network::Regtest);
+        config.service_status_url = Some("https://status.example.com".to_string());
+        let http_client = MockRestClient::new();
+        http_client.add_response(MockResponse::new(200, feed.to_string()));
+        ServiceStatusMonitor::new(
+            SharedConfig::new(config),
+            Arc::new(http_client),
+            system_clock(),
+        )
+    }
+
+    #[async_test_all]
+    async fn test_annotates_network_errors_during_maintenance() {
+        let now = now_secs();
+        let feed = format!(
+            r#"{{"maintenance_windows":[{{"starts_at":{},"ends_at":{},"message":"Upgrade"}}]}}"#,
+            now.saturating_sub(60),
+            now.saturating_add(600)
+        );
+        let monitor = monitor(&feed);
+        monitor.refresh().await.unwrap();
+
+        let status = monitor.status();
+        assert_eq!(status.maintenance_windows.len(), 1);
+        assert_eq!(
+            status.active_maintenance.unwrap().message.as_deref(),
+            Some("Upgrade")
+        );
+        assert!(matches!(
+            monitor.annotate(SdkError::NetworkError("timeout".to_string())),
+            SdkError::ServiceMaintenance { until } if until == now.saturating_add(600)
+        ));
+        assert!(matches!(
+            monitor.annotate(SdkError::InsufficientFunds),
+            SdkError::InsufficientFunds
+        ));
+    }
+
+    #[async_test_all]
+    async fn test_ignores_past_and_upcoming_windows() {
+        let now = now_secs();
+        let feed = format!(
+            r#"{{"maintenance_windows":[
+                {{"starts_at":{},"ends_at":{}}},
+                {{"starts_at":{},"ends_at":{}}}
+            ]}}"#,
+            now.saturating_sub(600),
+            now.saturating_sub(60),
+            now.saturating_add(60),
+            now.saturating_add(600)
+        );
+        let monitor = monitor(&feed);
+        monitor.refresh().await.unwrap();
+
+        let status = monitor.status();
+        assert_eq!(status.maintenance_windows.len(), 1);
+        assert!(status.active_maintenance.is_none());
+        assert!(matches!(
+            monitor.annotate(SdkError::NetworkError("timeout".to_string())),
+            SdkError::NetworkError(_)
+        ));
+    }
+}
@@ -101,6 +101,7 @@ pub mod cpfp;
 pub mod lnurl_auth;
 pub mod rtsync;
 pub mod single_key_signer;
+pub(crate) mod watch_only;
 
 pub use cpfp::CpfpSigner;
 pub use single_key_signer::{SingleKeySigner, single_key_cpfp_signer};
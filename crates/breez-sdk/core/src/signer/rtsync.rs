@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use bitcoin::bip32::DerivationPath;
+use bitcoin::bip32::{ChildNumber, DerivationPath};
 use breez_sdk_common::sync::SyncSigner;
 use std::sync::Arc;
 
@@ -10,14 +10,24 @@ use crate::{
 
 const SIGNING_DERIVATION_PATH: &str = "m/1220588449'/0'/0'/0/0";
 const SIGNING_DERIVATION_PATH_TEST: &str = "m/1220588449'/1'/0'/0/0";
-const ENCRYPTION_DERIVATION_PATH: &str = "m/1782705014'/0'/0'/0/0";
-const ENCRYPTION_DERIVATION_PATH_TEST: &str = "m/1782705014'/1'/0'/0/0";
+/// Record encryption keys are derived at `m/1782705014'/<coin>'/<key version>'/0/0`.
+const ENCRYPTION_PURPOSE: u32 = 1_782_705_014;
+
+/// Marks a record encrypted under an explicit key version, followed by the
+/// version byte. Unmarked records are version 0: ECIES ciphertexts start with
+/// an uncompressed public key (`0x04`), so the marker can't be confused with one.
+const KEY_VERSION_MARKER: u8 = b'v';
+
+/// The key version outgoing records are encrypted with. Version 0 records are
+/// written without a marker so clients that predate key versions can read them.
+/// Only raise this once all supported clients decrypt the new version.
+const WRITE_KEY_VERSION: u8 = 0;
 
 pub struct RTSyncSigner {
     signer: Arc<dyn BreezSigner>,
     ecies: Arc<dyn EciesSigner>,
     signing_path: DerivationPath,
-    encryption_path: DerivationPath,
+    coin_type: u32,
 }
 
 impl RTSyncSigner {
@@ -31,19 +41,28 @@ impl RTSyncSigner {
             Network::Regtest => SIGNING_DERIVATION_PATH_TEST,
         }
         .parse()?;
-        let encryption_path: DerivationPath = match network {
-            Network::Mainnet => ENCRYPTION_DERIVATION_PATH,
-            Network::Regtest => ENCRYPTION_DERIVATION_PATH_TEST,
-        }
-        .parse()?;
+        let coin_type = match network {
+            Network::Mainnet => 0,
+            Network::Regtest => 1,
+        };
 
         Ok(Self {
             signer,
             ecies,
             signing_path,
-            encryption_path,
+            coin_type,
         })
     }
+
+    fn encryption_path(&self, key_version: u8) -> anyhow::Result<DerivationPath> {
+        Ok(DerivationPath::from(vec![
+            ChildNumber::from_hardened_idx(ENCRYPTION_PURPOSE)?,
+            ChildNumber::from_hardened_idx(self.coin_type)?,
+            ChildNumber::from_hardened_idx(u32::from(key_version))?,
+            ChildNumber::from_normal_idx(0)?,
+            ChildNumber::from_normal_idx(0)?,
+        ]))
+    }
 }
 
 #[macros::async_trait]
@@ -71,16 +90,88 @@ impl SyncSigner for RTSyncSigner {
     }
 
     async fn encrypt_ecies(&self, msg: Vec<u8>) -> anyhow::Result<Vec<u8>> {
-        self.ecies
-            .encrypt_ecies(&msg, &self.encryption_path)
+        let encrypted = self
+            .ecies
+            .encrypt_ecies(&msg, &self.encryption_path(WRITE_KEY_VERSION)?)
             .await
-            .map_err(|e| anyhow!(e.to_string()))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        if WRITE_KEY_VERSION == 0 {
+            return Ok(encrypted);
+        }
+        Ok([
+            &[KEY_VERSION_MARKER, WRITE_KEY_VERSION],
+            encrypted.as_slice(),
+        ]
+        .concat())
     }
 
     async fn decrypt_ecies(&self, msg: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let (key_version, ciphertext) = match msg.as_slice() {
+            [KEY_VERSION_MARKER, version, ciphertext @ ..] => (*version, ciphertext),
+            ciphertext => (0, ciphertext),
+        };
         self.ecies
-            .decrypt_ecies(&msg, &self.encryption_path)
+            .decrypt_ecies(ciphertext, &self.encryption_path(key_version)?)
             .await
-            .map_err(|e| anyhow!(e.to_string()))
+            .map_err(|e| anyhow!("failed to decrypt record with key version {key_version}: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::bip32::Xpriv;
+    use macros::async_test_all;
+
+    use super::*;
+    use crate::signer::breez::BreezSignerImpl;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn rtsync_signer() -> (RTSyncSigner, Arc<BreezSignerImpl>) {
+        let master = Xpriv::new_master(bitcoin::Network::Regtest, &[7u8; 32]).unwrap();
+        let signer = Arc::new(BreezSignerImpl::new(master));
+        let rtsync = RTSyncSigner::new(signer.clone(), signer.clone(), Network::Regtest).unwrap();
+        (rtsync, signer)
+    }
+
+    #[async_test_all]
+    async fn test_encryption_roundtrip() {
+        let (rtsync, _) = rtsync_signer();
+        let encrypted = rtsync
+            .encrypt_ecies(b"payment note".to_vec())
+            .await
+            .unwrap();
+        assert_ne!(encrypted, b"payment note");
+        let decrypted = rtsync.decrypt_ecies(encrypted).await.unwrap();
+        assert_eq!(decrypted, b"payment note");
+    }
+
+    #[async_test_all]
+    async fn test_version_zero_matches_legacy_path() {
+        let (rtsync, signer) = rtsync_signer();
+        let legacy_path: DerivationPath = "m/1782705014'/1'/0'/0/0".parse().unwrap();
+        let legacy = signer
+            .encrypt_ecies(b"payment note", &legacy_path)
+            .await
+            .unwrap();
+        let decrypted = rtsync.decrypt_ecies(legacy).await.unwrap();
+        assert_eq!(decrypted, b"payment note");
+    }
+
+    #[async_test_all]
+    async fn test_decrypts_versioned_record() {
+        let (rtsync, signer) = rtsync_signer();
+        let encrypted = signer
+            .encrypt_ecies(b"payment note", &rtsync.encryption_path(1).unwrap())
+            .await
+            .unwrap();
+        let versioned = [&[KEY_VERSION_MARKER, 1], encrypted.as_slice()].concat();
+        let decrypted = rtsync.decrypt_ecies(versioned).await.unwrap();
+        assert_eq!(decrypted, b"payment note");
+
+        // The key version selects the key, so a wrong version fails
+        let mislabeled = [&[KEY_VERSION_MARKER, 2], encrypted.as_slice()].concat();
+        assert!(rtsync.decrypt_ecies(mislabeled).await.is_err());
     }
 }
@@ -0,0 +1,138 @@
+//! A `spark_wallet::SparkSigner` holding only the identity public key, for
+//! watch-only wallets.
+
+use bitcoin::secp256k1::{PublicKey, ecdsa, schnorr};
+use spark_wallet::{
+    FrostJob, FrostShareResult, PrepareClaimRequest, PrepareLightningReceiveRequest,
+    PrepareStaticDepositClaimRequest, PrepareStaticDepositRequest, PrepareTokenTransactionRequest,
+    PrepareTransferRequest, PreparedClaim, PreparedLightningReceive, PreparedStaticDeposit,
+    PreparedStaticDepositClaim, PreparedTokenTransaction, PreparedTransfer,
+    SignSparkInvoiceRequest, SignStaticDepositRefundRequest, SignedSparkInvoice, SignerError,
+    SparkSigner, StartStaticDepositRefundRequest, StartedStaticDepositRefund, TreeNodeId,
+};
+
+/// Returns the identity public key and fails every operation that needs a
+/// private key.
+pub(crate) struct WatchOnlySparkSigner {
+    identity_public_key: PublicKey,
+}
+
+impl WatchOnlySparkSigner {
+    pub(crate) fn new(identity_public_key: PublicKey) -> Self {
+        Self {
+            identity_public_key,
+        }
+    }
+}
+
+fn watch_only_error() -> SignerError {
+    SignerError::Generic("watch-only wallets can't sign".to_string())
+}
+
+#[macros::async_trait]
+impl SparkSigner for WatchOnlySparkSigner {
+    async fn get_identity_public_key(&self) -> Result<PublicKey, SignerError> {
+        Ok(self.identity_public_key)
+    }
+
+    async fn get_public_key_for_leaf(
+        &self,
+        _leaf_id: &TreeNodeId,
+    ) -> Result<PublicKey, SignerError> {
+        Err(watch_only_error())
+    }
+
+    fn is_watch_only(&self) -> bool {
+        true
+    }
+
+    async fn get_static_deposit_public_key(&self, _index: u32) -> Result<PublicKey, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn sign_authentication_challenge(
+        &self,
+        _challenge: &[u8],
+    ) -> Result<ecdsa::Signature, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn sign_message(&self, _message: &[u8]) -> Result<ecdsa::Signature, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn sign_leaf_refund_spend(
+        &self,
+        _leaf_id: &TreeNodeId,
+        _sighash: &[u8],
+    ) -> Result<schnorr::Signature, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn sign_frost(&self, _jobs: Vec<FrostJob>) -> Result<Vec<FrostShareResult>, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn prepare_transfer(
+        &self,
+        _request: PrepareTransferRequest,
+    ) -> Result<PreparedTransfer, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn prepare_claim(
+        &self,
+        _request: PrepareClaimRequest,
+    ) -> Result<PreparedClaim, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn prepare_lightning_receive(
+        &self,
+        _request: PrepareLightningReceiveRequest,
+    ) -> Result<PreparedLightningReceive, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn prepare_static_deposit(
+        &self,
+        _request: PrepareStaticDepositRequest,
+    ) -> Result<PreparedStaticDeposit, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn start_static_deposit_refund(
+        &self,
+        _request: StartStaticDepositRefundRequest,
+    ) -> Result<StartedStaticDepositRefund, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn sign_static_deposit_refund(
+        &self,
+        _request: SignStaticDepositRefundRequest,
+    ) -> Result<frost_secp256k1_tr::Signature, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn prepare_static_deposit_claim(
+        &self,
+        _request: PrepareStaticDepositClaimRequest,
+    ) -> Result<PreparedStaticDepositClaim, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn sign_spark_invoice(
+        &self,
+        _request: SignSparkInvoiceRequest,
+    ) -> Result<SignedSparkInvoice, SignerError> {
+        Err(watch_only_error())
+    }
+
+    async fn prepare_token_transaction(
+        &self,
+        _request: PrepareTokenTransactionRequest,
+    ) -> Result<PreparedTokenTransaction, SignerError> {
+        Err(watch_only_error())
+    }
+}
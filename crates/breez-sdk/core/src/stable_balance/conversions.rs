@@ -117,6 +117,7 @@ impl StableBalance {
                 response.sent_payment_id.clone(),
                 PaymentMetadata {
                     parent_payment_id: Some(parent_payment_id.to_string()),
+                    group_id: Some(parent_payment_id.to_string()),
                     ..Default::default()
                 },
             )
@@ -127,6 +128,7 @@ impl StableBalance {
                 response.received_payment_id.clone(),
                 PaymentMetadata {
                     parent_payment_id: Some(parent_payment_id.to_string()),
+                    group_id: Some(parent_payment_id.to_string()),
                     ..Default::default()
                 },
             )
@@ -224,6 +226,7 @@ impl StableBalance {
                 response.sent_payment_id.clone(),
                 PaymentMetadata {
                     parent_payment_id: Some(response.received_payment_id.clone()),
+                    group_id: Some(response.received_payment_id.clone()),
                     ..Default::default()
                 },
             )
@@ -322,6 +325,7 @@ impl StableBalance {
                 response.sent_payment_id.clone(),
                 PaymentMetadata {
                     parent_payment_id: Some(response.received_payment_id.clone()),
+                    group_id: Some(response.received_payment_id.clone()),
                     ..Default::default()
                 },
             )
@@ -1,14 +1,21 @@
-use std::{str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc, time::Duration};
 
+use bitcoin::secp256k1::rand::{Rng, thread_rng};
+use futures::StreamExt;
+use platform_utils::tokio::{sync::Mutex, time::sleep};
 use spark_wallet::{
-    ListTokenTransactionsRequest, ListTransfersRequest, Order, PagingFilter, SparkWallet,
-    TransferId,
+    ListTokenTransactionsRequest, ListTransfersRequest, Order, PagingFilter, PagingResult,
+    SparkWallet, TransferId, WalletTransfer,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
-    EventEmitter, Payment, PaymentDetails, PaymentStatus, SdkError, Storage,
+    EventEmitter, Payment, PaymentDetails, PaymentStatus, SdkError, SdkEvent, Storage,
     persist::{CachedSyncInfo, ObjectCacheRepository, StorageListPaymentsRequest},
+    sync_capture::{
+        CapturedTokenPage, CapturedTransfers, SyncCapture, SyncCaptureRecord, SyncPassKind,
+        SyncPassRecorder, SyncPassReplay,
+    },
     utils::{
         payments::record_payment_update,
         token::{token_transaction_to_payments, token_tx_inputs_are_ours},
@@ -16,11 +23,30 @@ use crate::{
 };
 
 const PAYMENT_SYNC_BATCH_SIZE: u64 = 50;
+/// Number of batches fetched concurrently until the initial sync completes.
+const PAYMENT_SYNC_INITIAL_CONCURRENCY: usize = 4;
+/// Attempts made to fetch a batch of transfers before the sync pass fails.
+const PAYMENT_SYNC_BATCH_MAX_ATTEMPTS: u32 = 4;
+/// Delay before the first retry of a batch, doubled on every retry.
+const PAYMENT_SYNC_BATCH_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
 
 pub(crate) struct SparkSyncService {
-    spark_wallet: Arc<SparkWallet>,
+    source: SyncSource,
     storage: Arc<dyn Storage>,
     event_emitter: Arc<EventEmitter>,
+    /// Serializes the updates of the sync info by passes running concurrently
+    sync_info_lock: Arc<Mutex<()>>,
+}
+
+/// Where the operator responses of a sync pass come from
+enum SyncSource {
+    Operators {
+        spark_wallet: Arc<SparkWallet>,
+        /// Set while the sync is captured for debugging
+        recorder: Option<SyncPassRecorder>,
+    },
+    /// The responses recorded by a sync capture
+    Replay(Arc<SyncPassReplay>),
 }
 
 impl SparkSyncService {
@@ -30,104 +56,311 @@ impl SparkSyncService {
         event_emitter: Arc<EventEmitter>,
     ) -> Self {
         Self {
-            spark_wallet,
+            source: SyncSource::Operators {
+                spark_wallet,
+                recorder: None,
+            },
+            storage,
+            event_emitter,
+            sync_info_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Records the operator responses of the pass to `capture`, if set.
+    pub fn with_capture(mut self, capture: Option<&Arc<SyncCapture>>) -> Self {
+        if let SyncSource::Operators { recorder, .. } = &mut self.source {
+            *recorder = capture.map(SyncCapture::new_pass);
+        }
+        self
+    }
+
+    /// Shares `lock` with the other passes updating the sync info, e.g. a
+    /// history backfill running along the regular syncs.
+    pub fn with_sync_info_lock(mut self, lock: Arc<Mutex<()>>) -> Self {
+        self.sync_info_lock = lock;
+        self
+    }
+
+    /// Runs a pass on the operator responses recorded by a sync capture.
+    pub(crate) fn replaying(
+        replay: Arc<SyncPassReplay>,
+        storage: Arc<dyn Storage>,
+        event_emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            source: SyncSource::Replay(replay),
             storage,
             event_emitter,
+            sync_info_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    fn recorder(&self) -> Option<&SyncPassRecorder> {
+        match &self.source {
+            SyncSource::Operators { recorder, .. } => recorder.as_ref(),
+            SyncSource::Replay(_) => None,
+        }
+    }
+
+    /// Records the start of a pass and the sync state it starts from, if
+    /// the sync is captured.
+    async fn record_pass_start(
+        &self,
+        object_repository: &ObjectCacheRepository,
+        kind: SyncPassKind,
+        initial_sync_complete: bool,
+        fast_start_payments: Option<u64>,
+    ) -> Result<(), SdkError> {
+        let Some(recorder) = self.recorder() else {
+            return Ok(());
+        };
+        let cached_sync_info = object_repository
+            .fetch_sync_info()
+            .await?
+            .unwrap_or_default();
+        recorder.record(SyncCaptureRecord::Pass {
+            kind,
+            initial_sync_complete,
+            fast_start_payments,
+            offset: cached_sync_info.offset,
+            last_synced_final_token_payment_id: cached_sync_info.last_synced_final_token_payment_id,
+            history_partial: cached_sync_info.history_partial,
+        });
+        Ok(())
+    }
+
+    /// Syncs the payments to storage. When `fast_start_payments` is set and the
+    /// wallet was never synced, only that many of the latest payments are
+    /// synced and the history is marked as partially synced until
+    /// [`Self::backfill_history`] completes.
+    ///
+    /// Returns whether the history is partially synced.
+    pub async fn sync_payments(
+        &self,
+        initial_sync_complete: bool,
+        fast_start_payments: Option<u64>,
+    ) -> Result<bool, SdkError> {
+        let object_repository = ObjectCacheRepository::new(self.storage.clone());
+        self.record_pass_start(
+            &object_repository,
+            SyncPassKind::Payments,
+            initial_sync_complete,
+            fast_start_payments,
+        )
+        .await?;
+        let cached_sync_info = object_repository
+            .fetch_sync_info()
+            .await?
+            .unwrap_or_default();
+        let mut history_partial = cached_sync_info.history_partial;
+        if !history_partial
+            && fast_start_payments.is_some()
+            && !initial_sync_complete
+            && cached_sync_info.offset == 0
+            && cached_sync_info
+                .last_synced_final_token_payment_id
+                .is_none()
+        {
+            info!("Fast sync of a new wallet, the older payments will be backfilled");
+            self.update_sync_info(&object_repository, |info| info.history_partial = true)
+                .await?;
+            history_partial = true;
+        }
+
+        if !history_partial {
+            self.sync_bitcoin_payments_to_storage(&object_repository, initial_sync_complete, false)
+                .await?;
+            self.sync_token_payments_to_storage(&object_repository, initial_sync_complete, None)
+                .await?;
+            return Ok(false);
         }
+
+        // Until the backfill completes only the latest payments are synced
+        let limit = fast_start_payments.unwrap_or(PAYMENT_SYNC_BATCH_SIZE);
+        self.sync_recent_bitcoin_payments(limit, initial_sync_complete)
+            .await?;
+        self.sync_token_payments_to_storage(&object_repository, initial_sync_complete, Some(limit))
+            .await?;
+        Ok(true)
     }
 
-    pub async fn sync_payments(&self, initial_sync_complete: bool) -> Result<(), SdkError> {
+    /// Syncs the payments older than the ones loaded by a fast sync, emitting
+    /// [`SdkEvent::HistoryBackfillProgress`] as the batches are stored. The
+    /// sync offset is saved after every batch, so an interrupted backfill
+    /// resumes where it stopped.
+    pub async fn backfill_history(&self, initial_sync_complete: bool) -> Result<(), SdkError> {
         let object_repository = ObjectCacheRepository::new(self.storage.clone());
-        self.sync_bitcoin_payments_to_storage(&object_repository, initial_sync_complete)
+        self.record_pass_start(
+            &object_repository,
+            SyncPassKind::Backfill,
+            initial_sync_complete,
+            None,
+        )
+        .await?;
+        self.sync_bitcoin_payments_to_storage(&object_repository, initial_sync_complete, true)
             .await?;
-        self.sync_token_payments_to_storage(&object_repository, initial_sync_complete)
+        if !self
+            .sync_token_payments_to_storage(&object_repository, false, None)
+            .await?
+        {
+            return Err(SdkError::Generic(
+                "Failed to backfill the token payments".to_string(),
+            ));
+        }
+        self.update_sync_info(&object_repository, |info| info.history_partial = false)
             .await?;
+
+        let synced = object_repository
+            .fetch_sync_info()
+            .await?
+            .unwrap_or_default()
+            .offset;
+        info!("History backfill completed, {synced} transfers synced");
+        self.event_emitter
+            .emit(&SdkEvent::HistoryBackfillProgress {
+                synced,
+                completed: true,
+            })
+            .await;
+        Ok(())
+    }
+
+    /// Syncs the latest `limit` transfers, without moving the sync offset.
+    async fn sync_recent_bitcoin_payments(
+        &self,
+        limit: u64,
+        initial_sync_complete: bool,
+    ) -> Result<(), SdkError> {
+        info!("Syncing the latest {limit} payments to storage");
+        let mut offset = 0;
+        while offset < limit {
+            let filter = PagingFilter {
+                offset,
+                limit: PAYMENT_SYNC_BATCH_SIZE.min(limit.saturating_sub(offset)),
+                order: Order::Descending,
+            };
+            let transfers_response = self.fetch_transfers_batch(&filter).await?;
+            let transfers_count = transfers_response.transfers;
+            for payment in transfers_response.payments {
+                self.record_synced_payment(payment, initial_sync_complete)
+                    .await;
+            }
+            offset = offset.saturating_add(transfers_count);
+            if !transfers_response.has_more || transfers_count == 0 {
+                break;
+            }
+        }
+
+        self.reconcile_pending_payments(initial_sync_complete).await;
         Ok(())
     }
 
+    /// Records a synced payment. Returns whether it is pending.
+    async fn record_synced_payment(&self, payment: Payment, initial_sync_complete: bool) -> bool {
+        // Apply any payment metadata for the payment
+        if let Err(e) = self.apply_payment_metadata(&payment).await {
+            error!(
+                "Failed to apply payment metadata for payment {}: {e:?}",
+                payment.id
+            );
+        }
+
+        // Emit events for new payment statuses after initial sync, or even before initial sync if the payment is pending
+        let is_pending = payment.status == PaymentStatus::Pending;
+        let should_emit = initial_sync_complete || is_pending;
+        info!("Synced payment: {payment:?}");
+        record_payment_update(&self.storage, &self.event_emitter, payment, should_emit).await;
+        is_pending
+    }
+
+    /// Syncs the transfers from the saved offset on. When `backfill` is set,
+    /// only pending payments emit events and progress is reported with
+    /// [`SdkEvent::HistoryBackfillProgress`].
     async fn sync_bitcoin_payments_to_storage(
         &self,
         object_repository: &ObjectCacheRepository,
         initial_sync_complete: bool,
+        backfill: bool,
     ) -> Result<(), SdkError> {
         // Get the last offset we processed from storage
-        let cached_sync_info = object_repository
+        let current_offset = object_repository
             .fetch_sync_info()
             .await?
-            .unwrap_or_default();
-        let current_offset = cached_sync_info.offset;
-        let last_synced_final_token_payment_id =
-            cached_sync_info.last_synced_final_token_payment_id;
-
-        // We'll keep querying in batches until we have all transfers
-        let mut next_filter = Some(PagingFilter {
-            offset: current_offset,
+            .unwrap_or_default()
+            .offset;
+
+        // We'll keep querying in batches until we have all transfers. During the
+        // initial sync of a wallet with a long history, several batches are
+        // fetched concurrently and processed in order as they arrive. The
+        // backfilled payments are old, so they are synced like an initial sync.
+        let emit_all = initial_sync_complete && !backfill;
+        let concurrency = if emit_all {
+            1
+        } else {
+            PAYMENT_SYNC_INITIAL_CONCURRENCY
+        };
+        info!(
+            "Syncing payments to storage, offset = {}, concurrency = {}",
+            current_offset, concurrency
+        );
+        let mut batches = futures::stream::iter((0u64..).map(|batch| PagingFilter {
+            offset: current_offset.saturating_add(batch.saturating_mul(PAYMENT_SYNC_BATCH_SIZE)),
             limit: PAYMENT_SYNC_BATCH_SIZE,
             order: Order::Ascending,
-        });
-        info!("Syncing payments to storage, offset = {}", current_offset);
+        }))
+        .map(|filter| async move {
+            let transfers_response = self.fetch_transfers_batch(&filter).await?;
+            Ok::<_, SdkError>((filter, transfers_response))
+        })
+        .buffered(concurrency);
+        // A batch that still fails after its retries ends the pass. The offset
+        // is saved after every batch, so the next pass resumes from there.
         let mut pending_payments: u64 = 0;
-        while let Some(filter) = next_filter {
-            // Get batch of transfers starting from current offset
-            let transfers_response = self
-                .spark_wallet
-                .list_transfers(ListTransfersRequest {
-                    paging: Some(filter.clone()),
-                    ..Default::default()
-                })
-                .await?;
+        while let Some(batch) = batches.next().await {
+            let (filter, transfers_response) = batch?;
+            let transfers_count = transfers_response.transfers;
 
             info!(
-                "Syncing payments to storage, offset = {}, transfers = {}",
+                "Syncing payments to storage, offset = {}, transfers = {transfers_count}",
                 filter.offset,
-                transfers_response.len()
             );
-            // Process transfers in this batch
-            for transfer in &transfers_response.items {
-                // Create a payment record
-                let payment: Payment = transfer.clone().try_into()?;
-                // Apply any payment metadata for the payment
-                if let Err(e) = self.apply_payment_metadata(&payment).await {
-                    error!(
-                        "Failed to apply payment metadata for payment {}: {e:?}",
-                        payment.id
-                    );
-                }
-
-                // Emit events for new payment statuses after initial sync, or even before initial sync if the payment is pending
-                let should_emit = initial_sync_complete || payment.status == PaymentStatus::Pending;
-                record_payment_update(
-                    &self.storage,
-                    &self.event_emitter,
-                    payment.clone(),
-                    should_emit,
-                )
-                .await;
-                if payment.status == PaymentStatus::Pending {
+            for payment in transfers_response.payments {
+                if self.record_synced_payment(payment, emit_all).await {
                     pending_payments = pending_payments.saturating_add(1);
                 }
-                info!("Synced payment: {payment:?}");
             }
 
             // Check if we have more transfers to fetch
-            let cache_offset = filter
-                .offset
-                .saturating_add(u64::try_from(transfers_response.len())?);
+            let cache_offset = filter.offset.saturating_add(transfers_count);
 
             // Update our last processed offset in the storage. We should remove pending payments
             // from the offset as they might be removed from the list later.
-            let save_res = object_repository
-                .save_sync_info(&CachedSyncInfo {
-                    offset: cache_offset.saturating_sub(pending_payments),
-                    last_synced_final_token_payment_id: last_synced_final_token_payment_id.clone(),
-                })
-                .await;
-
-            if let Err(err) = save_res {
+            let offset = cache_offset.saturating_sub(pending_payments);
+            if let Err(err) = self
+                .update_sync_info(object_repository, |info| info.offset = offset)
+                .await
+            {
                 error!("Failed to update last sync offset: {err:?}");
             }
 
-            next_filter = transfers_response.next;
+            if transfers_count > 0 {
+                let event = if backfill {
+                    SdkEvent::HistoryBackfillProgress {
+                        synced: cache_offset,
+                        completed: false,
+                    }
+                } else {
+                    SdkEvent::SyncProgress {
+                        synced: cache_offset,
+                    }
+                };
+                self.event_emitter.emit(&event).await;
+            }
+
+            // Batches past the last transfer are empty, they are dropped unprocessed
+            if !transfers_response.has_more {
+                break;
+            }
         }
 
         // Re-check all locally-stored pending payments to catch status transitions
@@ -137,6 +370,120 @@ impl SparkSyncService {
         Ok(())
     }
 
+    /// Fetches a batch of transfers as payments.
+    async fn fetch_transfers_batch(
+        &self,
+        filter: &PagingFilter,
+    ) -> Result<CapturedTransfers, SdkError> {
+        let descending = matches!(filter.order, Order::Descending);
+        let (spark_wallet, recorder) = match &self.source {
+            SyncSource::Operators {
+                spark_wallet,
+                recorder,
+            } => (spark_wallet, recorder),
+            SyncSource::Replay(replay) => {
+                let request = format!("transfers at offset {}", filter.offset);
+                return replay.take(&request, |record| match record {
+                    SyncCaptureRecord::Transfers {
+                        offset,
+                        limit,
+                        descending: record_descending,
+                        result,
+                    } if offset == filter.offset
+                        && limit == filter.limit
+                        && record_descending == descending =>
+                    {
+                        Ok(result.map_err(SdkError::Generic))
+                    }
+                    record => Err(record),
+                })?;
+            }
+        };
+
+        let result = fetch_transfers_with_retries(spark_wallet, filter)
+            .await
+            .and_then(|transfers| {
+                let has_more = transfers.next.is_some();
+                let transfers_count = u64::try_from(transfers.len())?;
+                let payments = transfers
+                    .items
+                    .into_iter()
+                    .map(Payment::try_from)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(CapturedTransfers {
+                    payments,
+                    transfers: transfers_count,
+                    has_more,
+                })
+            });
+        if let Some(recorder) = recorder {
+            recorder.record(SyncCaptureRecord::Transfers {
+                offset: filter.offset,
+                limit: filter.limit,
+                descending,
+                result: match &result {
+                    Ok(page) => Ok(page.clone()),
+                    Err(e) => Err(e.to_string()),
+                },
+            });
+        }
+        result
+    }
+
+    /// Fetches the transfers with the given ids as payments. Transfers that
+    /// can't be converted are skipped.
+    async fn fetch_transfers_by_id(
+        &self,
+        transfer_ids: Vec<TransferId>,
+    ) -> Result<Vec<Payment>, SdkError> {
+        let (spark_wallet, recorder) = match &self.source {
+            SyncSource::Operators {
+                spark_wallet,
+                recorder,
+            } => (spark_wallet, recorder),
+            SyncSource::Replay(replay) => {
+                return replay.take("reconciled transfers", |record| match record {
+                    SyncCaptureRecord::ReconciledTransfers { result } => {
+                        Ok(result.map_err(SdkError::Generic))
+                    }
+                    record => Err(record),
+                })?;
+            }
+        };
+
+        let result = spark_wallet
+            .list_transfers(ListTransfersRequest {
+                transfer_ids,
+                ..Default::default()
+            })
+            .await
+            .map_err(SdkError::from)
+            .map(|transfers| {
+                transfers
+                    .items
+                    .into_iter()
+                    .filter_map(|transfer| match Payment::try_from(transfer) {
+                        Ok(payment) => Some(payment),
+                        Err(e) => {
+                            error!(
+                                "Failed to convert transfer to payment during reconciliation: {e:?}"
+                            );
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            });
+        if let Some(recorder) = recorder {
+            recorder.record(SyncCaptureRecord::ReconciledTransfers {
+                result: match &result {
+                    Ok(payments) => Ok(payments.clone()),
+                    Err(e) => Err(e.to_string()),
+                },
+            });
+        }
+        result
+    }
+
     /// Re-fetches all locally-stored pending payments from the server and updates
     /// any whose status has changed. This catches cases where a payment transitioned to
     /// failed/completed before the current sync offset window began.
@@ -171,30 +518,15 @@ impl SparkSyncService {
             "Reconciling {} locally-pending bitcoin payments against server",
             transfer_ids.len()
         );
-        let transfers_response = match self
-            .spark_wallet
-            .list_transfers(ListTransfersRequest {
-                transfer_ids,
-                ..Default::default()
-            })
-            .await
-        {
-            Ok(r) => r,
+        let payments = match self.fetch_transfers_by_id(transfer_ids).await {
+            Ok(payments) => payments,
             Err(e) => {
                 error!("Failed to fetch pending payments for reconciliation: {e:?}");
                 return;
             }
         };
 
-        for transfer in &transfers_response.items {
-            let payment = match Payment::try_from(transfer.clone()) {
-                Ok(p) => p,
-                Err(e) => {
-                    error!("Failed to convert transfer to payment during reconciliation: {e:?}");
-                    continue;
-                }
-            };
-
+        for payment in payments {
             if payment.status == PaymentStatus::Pending {
                 continue;
             }
@@ -207,7 +539,7 @@ impl SparkSyncService {
             record_payment_update(
                 &self.storage,
                 &self.event_emitter,
-                payment.clone(),
+                payment,
                 initial_sync_complete,
             )
             .await;
@@ -237,12 +569,16 @@ impl SparkSyncService {
         Ok(())
     }
 
+    /// Syncs the token payments newer than the last synced one, or at most
+    /// about `max_payments` of the latest ones. Returns whether all of them
+    /// were synced.
     #[allow(clippy::too_many_lines)]
     async fn sync_token_payments_to_storage(
         &self,
         object_repository: &ObjectCacheRepository,
         initial_sync_complete: bool,
-    ) -> Result<(), SdkError> {
+        max_payments: Option<u64>,
+    ) -> Result<bool, SdkError> {
         info!("Syncing token payments to storage");
         // Get the last synced token payment id we processed from storage
         let cached_sync_info = object_repository
@@ -251,7 +587,6 @@ impl SparkSyncService {
             .unwrap_or_default();
         let last_synced_final_token_payment_id =
             cached_sync_info.last_synced_final_token_payment_id;
-        let our_public_key = self.spark_wallet.get_identity_public_key();
 
         // We'll keep querying in batches until we have all token tranactions
         let mut payments_to_sync = Vec::new();
@@ -261,37 +596,31 @@ impl SparkSyncService {
         // or we have fetched all transfers
         'page_loop: while has_more {
             info!("Fetching token transactions, offset = {next_offset}");
-            // Get batch of token transactions starting from current offset
-            let Ok(token_transactions) = self
-                .spark_wallet
-                .list_token_transactions(ListTokenTransactionsRequest {
-                    paging: Some(PagingFilter::new(
-                        Some(next_offset),
-                        Some(PAYMENT_SYNC_BATCH_SIZE),
-                        Some(Order::Descending),
-                    )),
-                    ..Default::default()
-                })
-                .await
+            let Some(page) = self
+                .fetch_token_page(
+                    object_repository,
+                    next_offset,
+                    last_synced_final_token_payment_id.as_deref(),
+                )
+                .await?
             else {
                 error!(
-                    "Failed to fetch address transactions, stopping sync and processing {} payments",
+                    "Failed to fetch token transactions, stopping sync and processing {} payments",
                     payments_to_sync.len()
                 );
                 break 'page_loop;
             };
             // If no token transactions to sync
-            if token_transactions.is_empty() {
+            if page.transactions == 0 {
                 break 'page_loop;
             }
             // Optimization: if the first transaction corresponds to the last synced final token payment id,
             // we can stop syncing
-            if let (Some(first_transaction), Some(last_synced_final_token_payment_id)) = (
-                token_transactions.items.first(),
-                &last_synced_final_token_payment_id,
-            ) {
+            if let (Some(first_tx_hash), Some(last_synced_final_token_payment_id)) =
+                (&page.first_tx_hash, &last_synced_final_token_payment_id)
+            {
                 // Payment ids have the format <transaction_hash>:<output_index>
-                if last_synced_final_token_payment_id.starts_with(&first_transaction.hash) {
+                if last_synced_final_token_payment_id.starts_with(first_tx_hash.as_str()) {
                     info!(
                         "Last synced token payment id found ({last_synced_final_token_payment_id:?}), stopping sync and processing {} payments",
                         payments_to_sync.len()
@@ -301,135 +630,266 @@ impl SparkSyncService {
                 }
             }
 
-            // Get prev out hashes of first input of each token transaction
-            // Assumes all inputs of a tx share the same owner public key
-            let token_transactions_prevout_hashes = token_transactions
-                .items
-                .iter()
-                .filter_map(|tx| match &tx.inputs {
-                    spark_wallet::TokenInputs::Transfer(token_transfer_input) => {
-                        token_transfer_input.outputs_to_spend.first().cloned()
-                    }
-                    spark_wallet::TokenInputs::Mint(..) | spark_wallet::TokenInputs::Create(..) => {
-                        None
-                    }
-                })
-                .map(|output| output.prev_token_tx_hash)
-                .collect::<Vec<_>>();
-
-            // Since we are trying to fetch at most 1 parent transaction per token transaction,
-            // we can fetch all in one go (get_token_transactions_by_hashes is limited to 100 items)
-            let Ok(parent_transactions) = self
-                .spark_wallet
-                .get_token_transactions_by_hashes(token_transactions_prevout_hashes)
-                .await
-            else {
-                error!(
-                    "Failed to fetch parent transactions, stopping sync and processing {} payments",
-                    payments_to_sync.len()
-                );
-                break 'page_loop;
-            };
-
             info!(
                 "Syncing token payments to storage, offset = {next_offset}, transactions = {}",
-                token_transactions.len()
+                page.transactions
             );
-            // Process transfers in this page
-            for transaction in &token_transactions.items {
-                let parent_transaction = match &transaction.inputs {
-                    spark_wallet::TokenInputs::Transfer(token_transfer_input) => {
-                        let first_input = token_transfer_input.outputs_to_spend.first().ok_or(
-                            SdkError::Generic("No input in token transfer input".to_string()),
-                        )?;
-                        Some(
-                            parent_transactions
-                                .iter()
-                                .find(|tx| tx.hash == first_input.prev_token_tx_hash)
-                                .ok_or(SdkError::Generic(
-                                    "Parent transaction not found".to_string(),
-                                ))?,
-                        )
-                    }
-                    spark_wallet::TokenInputs::Mint(_) | spark_wallet::TokenInputs::Create(_) => {
-                        None
-                    }
-                };
-                let tx_inputs_are_ours =
-                    token_tx_inputs_are_ours(transaction, parent_transaction, our_public_key)?;
-
-                // Create payment records
-                let payments = token_transaction_to_payments(
-                    &self.spark_wallet,
-                    object_repository,
-                    transaction,
-                    tx_inputs_are_ours,
-                )
-                .await?;
-
-                for payment in payments {
-                    // Apply any payment metadata for the payment
-                    if let Err(e) = self.apply_payment_metadata(&payment).await {
-                        error!(
-                            "Failed to apply payment metadata for payment {}: {e:?}",
-                            payment.id
-                        );
-                    }
-                    if last_synced_final_token_payment_id
-                        .as_ref()
-                        .is_some_and(|id| payment.id == *id)
-                    {
-                        info!(
-                            "Last synced token payment id found ({last_synced_final_token_payment_id:?}), stopping sync and processing {} payments",
-                            payments_to_sync.len()
-                        );
-                        has_more = false;
-                        break 'page_loop;
-                    }
-                    payments_to_sync.push(payment);
+            for payment in page.payments {
+                // Apply any payment metadata for the payment
+                if let Err(e) = self.apply_payment_metadata(&payment).await {
+                    error!(
+                        "Failed to apply payment metadata for payment {}: {e:?}",
+                        payment.id
+                    );
+                }
+                if last_synced_final_token_payment_id
+                    .as_ref()
+                    .is_some_and(|id| payment.id == *id)
+                {
+                    info!(
+                        "Last synced token payment id found ({last_synced_final_token_payment_id:?}), stopping sync and processing {} payments",
+                        payments_to_sync.len()
+                    );
+                    has_more = false;
+                    break 'page_loop;
                 }
+                payments_to_sync.push(payment);
             }
 
             // Check if we have more transfers to fetch
-            next_offset = next_offset.saturating_add(u64::try_from(token_transactions.len())?);
-            has_more = token_transactions.len() as u64 == PAYMENT_SYNC_BATCH_SIZE;
+            next_offset = next_offset.saturating_add(page.transactions);
+            has_more = page.transactions == PAYMENT_SYNC_BATCH_SIZE;
+            // The older payments are left to the backfill
+            if max_payments.is_some_and(|max| payments_to_sync.len() as u64 >= max) {
+                break 'page_loop;
+            }
         }
 
         // Insert what synced payments we have into storage, oldest to newest
         payments_to_sync.sort_by_key(|p| p.timestamp);
-        for payment in &payments_to_sync {
+        // The newest final payment becomes the sync checkpoint, only its id is kept
+        let newest_final_payment_id = payments_to_sync
+            .iter()
+            .rfind(|p| p.status.is_final())
+            .map(|p| p.id.clone());
+        for payment in payments_to_sync {
             // Emit events for new payment statuses after initial sync, or even before initial sync if the payment is pending
             let should_emit = initial_sync_complete || payment.status == PaymentStatus::Pending;
 
             info!("Syncing token payment: {payment:?}");
-            record_payment_update(
-                &self.storage,
-                &self.event_emitter,
-                payment.clone(),
-                should_emit,
-            )
-            .await;
+            record_payment_update(&self.storage, &self.event_emitter, payment, should_emit).await;
         }
 
         // We have synced all token transactions or found the last synced payment id.
         // If there was a failure to fetch transactions or no transactions exist,
         // we won't update the last synced token payment id
-        if !has_more
-            && let Some(last_synced_final_token_payment_id) = payments_to_sync
-                .into_iter()
-                .rfind(|p| p.status.is_final())
-                .map(|p| p.id)
-        {
+        if !has_more && let Some(last_synced_final_token_payment_id) = newest_final_payment_id {
             // Update last synced token payment id to the newest final payment we have processed
             info!("Updating last synced token payment id to {last_synced_final_token_payment_id}");
-            object_repository
-                .save_sync_info(&CachedSyncInfo {
-                    offset: cached_sync_info.offset,
-                    last_synced_final_token_payment_id: Some(last_synced_final_token_payment_id),
-                })
-                .await?;
+            self.update_sync_info(object_repository, |info| {
+                info.last_synced_final_token_payment_id = Some(last_synced_final_token_payment_id);
+            })
+            .await?;
         }
 
+        Ok(!has_more)
+    }
+
+    /// Fetches a page of token transactions as payments. Returns `None` if
+    /// fetching the page failed. The payments aren't built if the newest
+    /// transaction is the one of `last_synced_final_token_payment_id`, as the
+    /// page is already synced then.
+    async fn fetch_token_page(
+        &self,
+        object_repository: &ObjectCacheRepository,
+        offset: u64,
+        last_synced_final_token_payment_id: Option<&str>,
+    ) -> Result<Option<CapturedTokenPage>, SdkError> {
+        let (spark_wallet, recorder) = match &self.source {
+            SyncSource::Operators {
+                spark_wallet,
+                recorder,
+            } => (spark_wallet, recorder),
+            SyncSource::Replay(replay) => {
+                let request = format!("token transactions at offset {offset}");
+                return replay.take(&request, |record| match record {
+                    SyncCaptureRecord::TokenPage {
+                        offset: record_offset,
+                        page,
+                    } if record_offset == offset => Ok(page),
+                    record => Err(record),
+                });
+            }
+        };
+
+        let page = fetch_token_page(
+            spark_wallet,
+            object_repository,
+            offset,
+            last_synced_final_token_payment_id,
+        )
+        .await?;
+        if let Some(recorder) = recorder {
+            recorder.record(SyncCaptureRecord::TokenPage {
+                offset,
+                page: page.clone(),
+            });
+        }
+        Ok(page)
+    }
+
+    /// Updates the saved sync info with `update`. The info is fetched and
+    /// saved under the sync info lock, so fields saved by a concurrent
+    /// backfill are kept.
+    async fn update_sync_info(
+        &self,
+        object_repository: &ObjectCacheRepository,
+        update: impl FnOnce(&mut CachedSyncInfo),
+    ) -> Result<(), SdkError> {
+        let _guard = self.sync_info_lock.lock().await;
+        let mut cached_sync_info = object_repository
+            .fetch_sync_info()
+            .await?
+            .unwrap_or_default();
+        update(&mut cached_sync_info);
+        object_repository.save_sync_info(&cached_sync_info).await?;
         Ok(())
     }
 }
+
+/// See [`SparkSyncService::fetch_token_page`].
+async fn fetch_token_page(
+    spark_wallet: &SparkWallet,
+    object_repository: &ObjectCacheRepository,
+    offset: u64,
+    last_synced_final_token_payment_id: Option<&str>,
+) -> Result<Option<CapturedTokenPage>, SdkError> {
+    // Get batch of token transactions starting from current offset
+    let Ok(token_transactions) = spark_wallet
+        .list_token_transactions(ListTokenTransactionsRequest {
+            paging: Some(PagingFilter::new(
+                Some(offset),
+                Some(PAYMENT_SYNC_BATCH_SIZE),
+                Some(Order::Descending),
+            )),
+            ..Default::default()
+        })
+        .await
+    else {
+        return Ok(None);
+    };
+    let mut page = CapturedTokenPage {
+        payments: Vec::new(),
+        transactions: u64::try_from(token_transactions.len())?,
+        first_tx_hash: token_transactions.items.first().map(|tx| tx.hash.clone()),
+    };
+    if token_transactions.is_empty()
+        || page.first_tx_hash.as_ref().is_some_and(|hash| {
+            last_synced_final_token_payment_id.is_some_and(|id| id.starts_with(hash.as_str()))
+        })
+    {
+        return Ok(Some(page));
+    }
+
+    // Get prev out hashes of first input of each token transaction
+    // Assumes all inputs of a tx share the same owner public key
+    let token_transactions_prevout_hashes = token_transactions
+        .items
+        .iter()
+        .filter_map(|tx| match &tx.inputs {
+            spark_wallet::TokenInputs::Transfer(token_transfer_input) => {
+                token_transfer_input.outputs_to_spend.first().cloned()
+            }
+            spark_wallet::TokenInputs::Mint(..) | spark_wallet::TokenInputs::Create(..) => None,
+        })
+        .map(|output| output.prev_token_tx_hash)
+        .collect::<Vec<_>>();
+
+    // Since we are trying to fetch at most 1 parent transaction per token transaction,
+    // we can fetch all in one go (get_token_transactions_by_hashes is limited to 100 items)
+    let Ok(parent_transactions) = spark_wallet
+        .get_token_transactions_by_hashes(token_transactions_prevout_hashes)
+        .await
+    else {
+        error!("Failed to fetch parent transactions");
+        return Ok(None);
+    };
+
+    let our_public_key = spark_wallet.get_identity_public_key();
+    for transaction in &token_transactions.items {
+        let parent_transaction = match &transaction.inputs {
+            spark_wallet::TokenInputs::Transfer(token_transfer_input) => {
+                let first_input =
+                    token_transfer_input
+                        .outputs_to_spend
+                        .first()
+                        .ok_or(SdkError::Generic(
+                            "No input in token transfer input".to_string(),
+                        ))?;
+                Some(
+                    parent_transactions
+                        .iter()
+                        .find(|tx| tx.hash == first_input.prev_token_tx_hash)
+                        .ok_or(SdkError::Generic(
+                            "Parent transaction not found".to_string(),
+                        ))?,
+                )
+            }
+            spark_wallet::TokenInputs::Mint(_) | spark_wallet::TokenInputs::Create(_) => None,
+        };
+        let tx_inputs_are_ours =
+            token_tx_inputs_are_ours(transaction, parent_transaction, our_public_key)?;
+
+        // Create payment records
+        page.payments.extend(
+            token_transaction_to_payments(
+                spark_wallet,
+                object_repository,
+                transaction,
+                tx_inputs_are_ours,
+            )
+            .await?,
+        );
+    }
+    Ok(Some(page))
+}
+
+/// Fetches a batch of transfers, retrying with exponential backoff and
+/// jitter when it fails.
+async fn fetch_transfers_with_retries(
+    spark_wallet: &SparkWallet,
+    filter: &PagingFilter,
+) -> Result<PagingResult<WalletTransfer>, SdkError> {
+    let mut backoff = PAYMENT_SYNC_BATCH_INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match spark_wallet
+            .list_transfers(ListTransfersRequest {
+                paging: Some(filter.clone()),
+                ..Default::default()
+            })
+            .await
+        {
+            Ok(transfers) => return Ok(transfers),
+            Err(e) if attempt < PAYMENT_SYNC_BATCH_MAX_ATTEMPTS => {
+                let delay = with_jitter(backoff);
+                warn!(
+                    "Failed to fetch transfers at offset {} (attempt {attempt}), retrying in {delay:?}: {e}",
+                    filter.offset
+                );
+                sleep(delay).await;
+                backoff = backoff.saturating_mul(2);
+                attempt = attempt.saturating_add(1);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Adds up to half of `delay` at random, so failed syncs aren't retried in lockstep.
+fn with_jitter(delay: Duration) -> Duration {
+    let max_jitter_millis = u64::try_from(delay.as_millis() / 2).unwrap_or(u64::MAX);
+    delay.saturating_add(Duration::from_millis(
+        thread_rng().gen_range(0..=max_jitter_millis),
+    ))
+}
@@ -0,0 +1,509 @@
+//! Capture and replay of the payment sync, for debugging.
+//!
+//! While a capture is running, every sync pass records the state it started
+//! from and the operator responses it received, converted to payments with
+//! their preimages and descriptions redacted. A capture replayed against a
+//! fresh storage re-runs the sync pipeline on the recorded responses, so
+//! storage and state bugs can be reproduced deterministically.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    EventEmitter, Payment, PaymentDetails, SdkError, Storage,
+    persist::{CachedSyncInfo, ObjectCacheRepository},
+    sync::SparkSyncService,
+};
+
+/// The version of the capture format
+const SYNC_CAPTURE_VERSION: u32 = 1;
+/// Records kept in a capture, later records are dropped
+const SYNC_CAPTURE_MAX_RECORDS: usize = 10_000;
+const REDACTED: &str = "redacted";
+
+/// The kind of a captured sync pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncPassKind {
+    /// A regular sync of the payments
+    Payments,
+    /// A backfill of the history after a fast sync
+    Backfill,
+}
+
+/// The outcome of a replayed sync pass
+#[derive(Debug, Clone)]
+pub struct ReplayedSyncPass {
+    pub kind: SyncPassKind,
+    /// The error the pass failed with, if any
+    pub error: Option<String>,
+}
+
+/// The outcome of [`replay_sync_capture`]
+#[derive(Debug, Clone)]
+pub struct SyncReplayReport {
+    /// The replayed passes, in the order they were captured
+    pub passes: Vec<ReplayedSyncPass>,
+    /// Whether records were dropped as the capture was full
+    pub truncated: bool,
+}
+
+/// A page of transfers fetched by a sync pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CapturedTransfers {
+    pub(crate) payments: Vec<Payment>,
+    /// The number of transfers on the page
+    pub(crate) transfers: u64,
+    pub(crate) has_more: bool,
+}
+
+/// A page of token transactions fetched by a sync pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CapturedTokenPage {
+    pub(crate) payments: Vec<Payment>,
+    /// The number of transactions on the page
+    pub(crate) transactions: u64,
+    /// The hash of the newest transaction on the page
+    pub(crate) first_tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum SyncCaptureRecord {
+    /// A sync pass started from the given sync state
+    Pass {
+        kind: SyncPassKind,
+        initial_sync_complete: bool,
+        fast_start_payments: Option<u64>,
+        offset: u64,
+        last_synced_final_token_payment_id: Option<String>,
+        history_partial: bool,
+    },
+    Transfers {
+        offset: u64,
+        limit: u64,
+        descending: bool,
+        result: Result<CapturedTransfers, String>,
+    },
+    ReconciledTransfers {
+        result: Result<Vec<Payment>, String>,
+    },
+    /// `page` is `None` if fetching the page failed
+    TokenPage {
+        offset: u64,
+        page: Option<CapturedTokenPage>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncCaptureEntry {
+    /// The pass the record belongs to, as passes may run concurrently
+    pass: u32,
+    record: SyncCaptureRecord,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SyncCaptureFile {
+    version: u32,
+    truncated: bool,
+    entries: Vec<SyncCaptureEntry>,
+}
+
+#[derive(Default)]
+struct SyncCaptureState {
+    next_pass: u32,
+    truncated: bool,
+    entries: Vec<SyncCaptureEntry>,
+}
+
+/// A running capture of the sync passes
+#[derive(Default)]
+pub(crate) struct SyncCapture {
+    state: Mutex<SyncCaptureState>,
+}
+
+impl SyncCapture {
+    /// Returns the recorder of a new pass.
+    pub(crate) fn new_pass(self: &Arc<Self>) -> SyncPassRecorder {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let pass = state.next_pass;
+        state.next_pass = state.next_pass.saturating_add(1);
+        SyncPassRecorder {
+            capture: Arc::clone(self),
+            pass,
+        }
+    }
+
+    /// Serializes the capture to JSON.
+    pub(crate) fn to_json(&self) -> Result<String, SdkError> {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        Ok(serde_json::to_string(&SyncCaptureFile {
+            version: SYNC_CAPTURE_VERSION,
+            truncated: state.truncated,
+            entries: state.entries.clone(),
+        })?)
+    }
+}
+
+/// Records the operator responses of a single sync pass
+#[derive(Clone)]
+pub(crate) struct SyncPassRecorder {
+    capture: Arc<SyncCapture>,
+    pass: u32,
+}
+
+impl SyncPassRecorder {
+    pub(crate) fn record(&self, record: SyncCaptureRecord) {
+        let mut state = self
+            .capture
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if state.entries.len() >= SYNC_CAPTURE_MAX_RECORDS {
+            state.truncated = true;
+            return;
+        }
+        state.entries.push(SyncCaptureEntry {
+            pass: self.pass,
+            record: redact(record),
+        });
+    }
+}
+
+/// The recorded responses of a pass being replayed
+pub(crate) struct SyncPassReplay {
+    records: Mutex<VecDeque<SyncCaptureRecord>>,
+}
+
+impl SyncPassReplay {
+    /// Takes the first recorded response matching `matches`. Fails if the
+    /// replayed pass requests a response the captured one didn't.
+    pub(crate) fn take<T>(
+        &self,
+        request: &str,
+        matches: impl Fn(SyncCaptureRecord) -> Result<T, SyncCaptureRecord>,
+    ) -> Result<T, SdkError> {
+        let mut records = self
+            .records
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut remaining = VecDeque::with_capacity(records.len());
+        let mut found = None;
+        while let Some(record) = records.pop_front() {
+            if found.is_some() {
+                remaining.push_back(record);
+                continue;
+            }
+            match matches(record) {
+                Ok(value) => found = Some(value),
+                Err(record) => remaining.push_back(record),
+            }
+        }
+        *records = remaining;
+        found.ok_or_else(|| {
+            SdkError::Generic(format!(
+                "Replay diverged: no captured response for {request}"
+            ))
+        })
+    }
+}
+
+/// Replays a capture made with
+/// [`BreezSdk::stop_sync_capture`](crate::BreezSdk::stop_sync_capture)
+/// against `storage`, re-running the captured sync passes in order.
+///
+/// The sync state of the first pass is restored before replaying, so use a
+/// fresh storage. A pass that requests a response the capture doesn't hold
+/// fails, and the replay continues with the next pass.
+pub async fn replay_sync_capture(
+    capture: &str,
+    storage: Arc<dyn Storage>,
+) -> Result<SyncReplayReport, SdkError> {
+    let file: SyncCaptureFile = serde_json::from_str(capture)
+        .map_err(|e| SdkError::InvalidInput(format!("Invalid sync capture: {e}")))?;
+    if file.version != SYNC_CAPTURE_VERSION {
+        return Err(SdkError::InvalidInput(format!(
+            "Unsupported sync capture version {}",
+            file.version
+        )));
+    }
+
+    let mut passes: Vec<(u32, Vec<SyncCaptureRecord>)> = Vec::new();
+    for entry in file.entries {
+        match passes.iter_mut().find(|(pass, _)| *pass == entry.pass) {
+            Some((_, records)) => records.push(entry.record),
+            None => passes.push((entry.pass, vec![entry.record])),
+        }
+    }
+
+    let event_emitter = Arc::new(EventEmitter::new(false));
+    let object_repository = ObjectCacheRepository::new(storage.clone());
+    let mut report = SyncReplayReport {
+        passes: Vec::new(),
+        truncated: file.truncated,
+    };
+    for (index, (_, mut records)) in passes.into_iter().enumerate() {
+        let Some(SyncCaptureRecord::Pass {
+            kind,
+            initial_sync_complete,
+            fast_start_payments,
+            offset,
+            last_synced_final_token_payment_id,
+            history_partial,
+        }) = (!records.is_empty()).then(|| records.remove(0))
+        else {
+            warn!("Skipping a captured pass without its start");
+            continue;
+        };
+        if index == 0 {
+            object_repository
+                .save_sync_info(&CachedSyncInfo {
+                    offset,
+                    last_synced_final_token_payment_id,
+                    history_partial,
+                })
+                .await?;
+        }
+
+        info!(
+            "Replaying {kind:?} sync pass with {} records",
+            records.len()
+        );
+        let replay = Arc::new(SyncPassReplay {
+            records: Mutex::new(records.into()),
+        });
+        let sync_service =
+            SparkSyncService::replaying(replay, storage.clone(), event_emitter.clone());
+        let result = match kind {
+            SyncPassKind::Payments => sync_service
+                .sync_payments(initial_sync_complete, fast_start_payments)
+                .await
+                .map(|_| ()),
+            SyncPassKind::Backfill => sync_service.backfill_history(initial_sync_complete).await,
+        };
+        report.passes.push(ReplayedSyncPass {
+            kind,
+            error: result.err().map(|e| e.to_string()),
+        });
+    }
+    Ok(report)
+}
+
+fn redact(record: SyncCaptureRecord) -> SyncCaptureRecord {
+    match record {
+        SyncCaptureRecord::Transfers {
+            offset,
+            limit,
+            descending,
+            result,
+        } => SyncCaptureRecord::Transfers {
+            offset,
+            limit,
+            descending,
+            result: result.map(|page| CapturedTransfers {
+                payments: page.payments.into_iter().map(redact_payment).collect(),
+                ..page
+            }),
+        },
+        SyncCaptureRecord::ReconciledTransfers { result } => {
+            SyncCaptureRecord::ReconciledTransfers {
+                result: result.map(|payments| payments.into_iter().map(redact_payment).collect()),
+            }
+        }
+        SyncCaptureRecord::TokenPage { offset, page } => SyncCaptureRecord::TokenPage {
+            offset,
+            page: page.map(|page| CapturedTokenPage {
+                payments: page.payments.into_iter().map(redact_payment).collect(),
+                ..page
+            }),
+        },
+        record @ SyncCaptureRecord::Pass { .. } => record,
+    }
+}
+
+/// Removes the preimages and descriptions of a payment.
+fn redact_payment(mut payment: Payment) -> Payment {
+    let redacted = || Some(REDACTED.to_string());
+    match &mut payment.details {
+        Some(PaymentDetails::Spark {
+            invoice_details,
+            htlc_details,
+            ..
+        }) => {
+            if let Some(invoice_details) = invoice_details {
+                invoice_details.description = invoice_details.description.as_ref().and(redacted());
+            }
+            if let Some(htlc_details) = htlc_details {
+                htlc_details.preimage = htlc_details.preimage.as_ref().and(redacted());
+            }
+        }
+        Some(PaymentDetails::Token {
+            invoice_details: Some(invoice_details),
+            ..
+        }) => {
+            invoice_details.description = invoice_details.description.as_ref().and(redacted());
+        }
+        Some(PaymentDetails::Lightning {
+            description,
+            htlc_details,
+            ..
+        }) => {
+            *description = description.as_ref().and(redacted());
+            htlc_details.preimage = htlc_details.preimage.as_ref().and(redacted());
+        }
+        _ => {}
+    }
+    payment
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::{
+        DisplayAmount, PaymentMethod, PaymentStatus, PaymentType, SparkHtlcDetails,
+        SparkHtlcStatus, persist::sqlite::SqliteStorage,
+    };
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("breez-test-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn payment(id: &str) -> Payment {
+        Payment {
+            id: id.to_string(),
+            payment_type: PaymentType::Receive,
+            status: PaymentStatus::Completed,
+            amount: 1000,
+            fees: 0,
+            timestamp: 1_700_000_000,
+            method: PaymentMethod::Spark,
+            details: Some(PaymentDetails::Spark {
+                invoice_details: None,
+                htlc_details: Some(SparkHtlcDetails {
+                    payment_hash: "hash".to_string(),
+                    preimage: Some("preimage".to_string()),
+                    expiry_time: 1_700_003_600,
+                    status: SparkHtlcStatus::PreimageShared,
+                }),
+                conversion_info: None,
+            }),
+            conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
+        }
+    }
+
+    fn pass_start(kind: SyncPassKind) -> SyncCaptureRecord {
+        SyncCaptureRecord::Pass {
+            kind,
+            initial_sync_complete: true,
+            fast_start_payments: None,
+            offset: 0,
+            last_synced_final_token_payment_id: None,
+            history_partial: false,
+        }
+    }
+
+    fn transfers(offset: u64, payments: Vec<Payment>) -> SyncCaptureRecord {
+        SyncCaptureRecord::Transfers {
+            offset,
+            limit: 50,
+            descending: false,
+            result: Ok(CapturedTransfers {
+                transfers: payments.len() as u64,
+                payments,
+                has_more: false,
+            }),
+        }
+    }
+
+    fn empty_token_page() -> SyncCaptureRecord {
+        SyncCaptureRecord::TokenPage {
+            offset: 0,
+            page: Some(CapturedTokenPage {
+                payments: Vec::new(),
+                transactions: 0,
+                first_tx_hash: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_recorded_payments_are_redacted() {
+        let capture = Arc::new(SyncCapture::default());
+        capture
+            .new_pass()
+            .record(transfers(0, vec![payment("transfer")]));
+        let file: SyncCaptureFile = serde_json::from_str(&capture.to_json().unwrap()).unwrap();
+        let SyncCaptureRecord::Transfers {
+            result: Ok(page), ..
+        } = &file.entries[0].record
+        else {
+            panic!("Expected captured transfers");
+        };
+        let Some(PaymentDetails::Spark {
+            htlc_details: Some(htlc_details),
+            ..
+        }) = &page.payments[0].details
+        else {
+            panic!("Expected a spark htlc payment");
+        };
+        assert_eq!(htlc_details.preimage.as_deref(), Some(REDACTED));
+        assert_eq!(htlc_details.payment_hash, "hash");
+    }
+
+    #[tokio::test]
+    async fn test_replay_sync_capture() {
+        let capture = Arc::new(SyncCapture::default());
+        let recorder = capture.new_pass();
+        recorder.record(pass_start(SyncPassKind::Payments));
+        recorder.record(transfers(0, vec![payment("transfer")]));
+        recorder.record(empty_token_page());
+        // A pass requesting a response the capture doesn't hold fails
+        let recorder = capture.new_pass();
+        recorder.record(pass_start(SyncPassKind::Payments));
+
+        let storage: Arc<dyn Storage> =
+            Arc::new(SqliteStorage::new(&create_temp_dir("sync-capture")).unwrap());
+        let report = replay_sync_capture(&capture.to_json().unwrap(), storage.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(report.passes.len(), 2);
+        assert_eq!(report.passes[0].error, None);
+        assert!(
+            report.passes[1]
+                .error
+                .as_ref()
+                .is_some_and(|e| e.contains("Replay diverged"))
+        );
+        let payment = storage
+            .get_payment_by_id("transfer".to_string())
+            .await
+            .unwrap();
+        assert_eq!(payment.status, PaymentStatus::Completed);
+        let sync_info = ObjectCacheRepository::new(storage)
+            .fetch_sync_info()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sync_info.offset, 1);
+    }
+}
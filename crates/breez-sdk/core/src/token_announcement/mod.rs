@@ -0,0 +1,229 @@
+//! Publication and resolution of issued-token metadata as Nostr events.
+//!
+//! An announcement is a NIP-78 event tagged with the token identifier. Its
+//! content carries the token metadata and a signature by the issuer identity
+//! key over the token identifier and the Nostr public key of the event, so
+//! the event can be signed with an ephemeral key.
+
+mod models;
+
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use bitcoin::secp256k1::{PublicKey, ecdsa::Signature};
+use nostr::{Event, EventBuilder, Filter, Keys, Kind, Tag};
+use nostr_sdk::Client;
+use serde::{Deserialize, Serialize};
+use spark_wallet::SparkWallet;
+use tracing::{debug, warn};
+
+use crate::{SdkError, TokenMetadata};
+
+pub use models::*;
+
+/// NIP-78 application-specific data
+const ANNOUNCEMENT_KIND: u16 = 30078;
+const IDENTIFIER_PREFIX: &str = "spark-token:";
+const RELAYS: &[&str] = &[
+    "wss://relay.primal.net",
+    "wss://relay.damus.io",
+    "wss://nos.lol",
+];
+const RELAY_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AnnouncementContent {
+    metadata: TokenMetadata,
+    icon_url: Option<String>,
+    /// Compact hex signature of [`proof_message`] by the issuer identity key
+    issuer_signature: String,
+}
+
+fn identifier_tag(token_identifier: &str) -> String {
+    format!("{IDENTIFIER_PREFIX}{token_identifier}")
+}
+
+fn proof_message(token_identifier: &str, nostr_public_key: &str) -> String {
+    format!("spark-token-announcement:{token_identifier}:{nostr_public_key}")
+}
+
+/// Publishes an announcement of the issuer token to the default relays.
+pub(crate) async fn publish_announcement(
+    spark_wallet: &SparkWallet,
+    metadata: TokenMetadata,
+    icon_url: Option<String>,
+) -> Result<TokenAnnouncement, SdkError> {
+    let keys = Keys::generate();
+    let message = proof_message(&metadata.identifier, &keys.public_key().to_hex());
+    let signature = spark_wallet.sign_message(&message).await?;
+    let content = AnnouncementContent {
+        metadata,
+        icon_url,
+        issuer_signature: hex::encode(signature.serialize_compact()),
+    };
+    let event = EventBuilder::new(
+        Kind::Custom(ANNOUNCEMENT_KIND),
+        serde_json::to_string(&content)?,
+    )
+    .tag(Tag::identifier(identifier_tag(
+        &content.metadata.identifier,
+    )))
+    .sign_with_keys(&keys)
+    .map_err(|e| SdkError::Generic(format!("Failed to sign the announcement: {e}")))?;
+
+    let client = connect().await?;
+    let output = client.send_event(&event).await;
+    client.disconnect().await;
+    let output = output
+        .map_err(|e| SdkError::Generic(format!("Failed to publish the announcement: {e}")))?;
+    if output.success.is_empty() {
+        return Err(SdkError::Generic(
+            "No relay accepted the announcement".to_string(),
+        ));
+    }
+    Ok(TokenAnnouncement {
+        metadata: content.metadata,
+        icon_url: content.icon_url,
+        published_at: event.created_at.as_u64(),
+    })
+}
+
+/// Fetches the latest valid announcement of each of the tokens.
+pub(crate) async fn fetch_announcements(
+    spark_wallet: &SparkWallet,
+    token_identifiers: &[String],
+) -> Result<Vec<TokenAnnouncement>, SdkError> {
+    if token_identifiers.is_empty() {
+        return Ok(Vec::new());
+    }
+    let filter = Filter::new()
+        .kind(Kind::Custom(ANNOUNCEMENT_KIND))
+        .identifiers(token_identifiers.iter().map(|id| identifier_tag(id)));
+    let client = connect().await?;
+    let events = client
+        .fetch_events(filter, Duration::from_secs(RELAY_TIMEOUT_SECS))
+        .await;
+    client.disconnect().await;
+    let events =
+        events.map_err(|e| SdkError::Generic(format!("Failed to fetch announcements: {e}")))?;
+
+    let mut latest: HashMap<String, TokenAnnouncement> = HashMap::new();
+    for event in events {
+        let Some(content) = parse_announcement(&event) else {
+            continue;
+        };
+        if let Err(e) = verify_issuer_signature(spark_wallet, &event, &content).await {
+            warn!("Ignoring announcement {}: {e}", event.id);
+            continue;
+        }
+        let announcement = TokenAnnouncement {
+            metadata: content.metadata,
+            icon_url: content.icon_url,
+            published_at: event.created_at.as_u64(),
+        };
+        let token_identifier = announcement.metadata.identifier.clone();
+        if latest
+            .get(&token_identifier)
+            .is_none_or(|current| current.published_at < announcement.published_at)
+        {
+            latest.insert(token_identifier, announcement);
+        }
+    }
+    Ok(latest.into_values().collect())
+}
+
+async fn connect() -> Result<Client, SdkError> {
+    let client = Client::default();
+    let mut added = 0usize;
+    for relay in RELAYS {
+        match client.add_relay(*relay).await {
+            Ok(_) => added = added.saturating_add(1),
+            Err(e) => debug!("Failed to add relay {relay}: {e}"),
+        }
+    }
+    if added == 0 {
+        return Err(SdkError::Generic("Failed to add any relay".to_string()));
+    }
+    client.connect().await;
+    Ok(client)
+}
+
+/// Parses an announcement event whose identifier tag matches its token.
+fn parse_announcement(event: &Event) -> Option<AnnouncementContent> {
+    if event.kind != Kind::Custom(ANNOUNCEMENT_KIND) || event.verify().is_err() {
+        return None;
+    }
+    let content: AnnouncementContent = serde_json::from_str(&event.content).ok()?;
+    if event.tags.identifier() != Some(identifier_tag(&content.metadata.identifier).as_str()) {
+        return None;
+    }
+    Some(content)
+}
+
+async fn verify_issuer_signature(
+    spark_wallet: &SparkWallet,
+    event: &Event,
+    content: &AnnouncementContent,
+) -> Result<(), SdkError> {
+    let issuer_public_key = PublicKey::from_str(&content.metadata.issuer_public_key)
+        .map_err(|_| SdkError::Generic("Invalid issuer public key".to_string()))?;
+    let signature = hex::decode(&content.issuer_signature)
+        .ok()
+        .and_then(|bytes| Signature::from_compact(&bytes).ok())
+        .ok_or_else(|| SdkError::Generic("Invalid issuer signature".to_string()))?;
+    let message = proof_message(&content.metadata.identifier, &event.pubkey.to_hex());
+    spark_wallet
+        .verify_message(&message, &signature, &issuer_public_key)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn metadata(identifier: &str) -> TokenMetadata {
+        TokenMetadata {
+            identifier: identifier.to_string(),
+            issuer_public_key: "02".repeat(33),
+            name: "Token".to_string(),
+            ticker: "TKN".to_string(),
+            decimals: 6,
+            max_supply: 1_000_000,
+            is_freezable: false,
+        }
+    }
+
+    fn event(content: &AnnouncementContent, identifier: &str) -> Event {
+        EventBuilder::new(
+            Kind::Custom(ANNOUNCEMENT_KIND),
+            serde_json::to_string(content).unwrap(),
+        )
+        .tag(Tag::identifier(identifier_tag(identifier)))
+        .sign_with_keys(&Keys::generate())
+        .unwrap()
+    }
+
+    #[test_all]
+    fn test_parse_announcement() {
+        let content = AnnouncementContent {
+            metadata: metadata("btkn1"),
+            icon_url: Some("https://example.com/icon.png".to_string()),
+            issuer_signature: String::new(),
+        };
+
+        let parsed = parse_announcement(&event(&content, "btkn1")).unwrap();
+        assert_eq!(parsed.metadata, metadata("btkn1"));
+        assert_eq!(
+            parsed.icon_url.as_deref(),
+            Some("https://example.com/icon.png")
+        );
+
+        // The identifier tag must match the announced token
+        assert!(parse_announcement(&event(&content, "btkn2")).is_none());
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::TokenMetadata;
+
+/// The metadata of a token, announced by its issuer in a Nostr event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TokenAnnouncement {
+    pub metadata: TokenMetadata,
+    /// URL of the token icon
+    pub icon_url: Option<String>,
+    /// The unix timestamp of the announcement, in seconds
+    pub published_at: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PublishTokenAnnouncementRequest {
+    /// URL of the token icon
+    #[cfg_attr(feature = "uniffi", uniffi(default = None))]
+    pub icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetTokenAnnouncementsRequest {
+    pub token_identifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct GetTokenAnnouncementsResponse {
+    /// The latest valid announcement of each token found
+    pub announcements: Vec<TokenAnnouncement>,
+}
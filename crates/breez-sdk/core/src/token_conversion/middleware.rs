@@ -17,7 +17,8 @@ impl EventMiddleware for TokenConversionMiddleware {
         match &event {
             SdkEvent::PaymentSucceeded { payment }
             | SdkEvent::PaymentPending { payment }
-            | SdkEvent::PaymentFailed { payment }
+            | SdkEvent::PaymentWaitingConfirmation { payment }
+            | SdkEvent::PaymentFailed { payment, .. }
                 if payment.is_conversion_child() =>
             {
                 info!(
@@ -472,7 +472,9 @@ mod tests {
     use super::*;
     use crate::{
         AmountAdjustmentReason, SparkHtlcDetails, SparkHtlcStatus,
-        models::{PaymentMethod, PaymentStatus, TokenMetadata, TokenTransactionType},
+        models::{
+            DisplayAmount, PaymentMethod, PaymentStatus, TokenMetadata, TokenTransactionType,
+        },
     };
 
     fn test_token_metadata() -> TokenMetadata {
@@ -541,6 +543,10 @@ mod tests {
                 conversion_info: Some(info),
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -565,6 +571,10 @@ mod tests {
                 conversion_info: Some(info),
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -593,6 +603,10 @@ mod tests {
                 conversion_info: Some(info),
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -664,6 +678,10 @@ mod tests {
                 status: ConversionStatus::Completed,
                 conversions: vec![],
             }),
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -690,6 +708,10 @@ mod tests {
                 status: ConversionStatus::Completed,
                 conversions: vec![],
             }),
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -711,6 +733,10 @@ mod tests {
                 status: ConversionStatus::Completed,
                 conversions: vec![],
             }),
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use bitcoin::{Address, address::NetworkUnchecked};
+
+use crate::{persist::CachedDepositAddress, utils::utxo_fetcher::DetailedUtxo};
+
+/// Adds `address` to the monitored addresses as the current one.
+///
+/// Other unused addresses are dropped: they stay valid for deposits, but
+/// aren't handed out again.
+pub(crate) fn track_current_address(monitored: &mut Vec<CachedDepositAddress>, address: &str) {
+    monitored.retain(|a| a.used || a.address == address);
+    for a in monitored.iter_mut() {
+        a.current = a.address == address;
+    }
+    if !monitored.iter().any(|a| a.address == address) {
+        monitored.push(CachedDepositAddress {
+            address: address.to_string(),
+            used: false,
+            current: true,
+        });
+    }
+}
+
+/// Whether `address` received a deposit, so a fresh one should be handed out.
+pub(crate) fn is_used(monitored: &[CachedDepositAddress], address: &str) -> bool {
+    monitored.iter().any(|a| a.address == address && a.used)
+}
+
+/// Updates the monitored addresses with the `deposits` still in progress,
+/// i.e. unclaimed or being refunded.
+///
+/// Addresses paid by one of them are marked used. Used addresses none of them
+/// pays to anymore are no longer monitored, as their deposits completed,
+/// except the current one: it stays flagged as used until it's rotated, so it
+/// isn't handed out again after its deposit is claimed. This bounds the set to
+/// the current address and the ones with deposits in progress.
+pub(crate) fn update_monitored_addresses(
+    monitored: Vec<CachedDepositAddress>,
+    deposits: &[DetailedUtxo],
+) -> Vec<CachedDepositAddress> {
+    monitored
+        .into_iter()
+        .filter_map(|mut monitored| {
+            let Ok(address) = Address::<NetworkUnchecked>::from_str(&monitored.address) else {
+                return None;
+            };
+            let address = address.assume_checked();
+            let paid = deposits
+                .iter()
+                .any(|deposit| deposit.pays_to(std::slice::from_ref(&address)));
+            if paid {
+                monitored.used = true;
+            } else if monitored.used && !monitored.current {
+                return None;
+            }
+            Some(monitored)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::{
+        Address, Amount, Transaction, TxOut, absolute::LockTime, address::NetworkUnchecked,
+        transaction::Version,
+    };
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    const ADDRESS_A: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    const ADDRESS_B: &str = "bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297";
+
+    fn monitored(address: &str, used: bool) -> CachedDepositAddress {
+        CachedDepositAddress {
+            address: address.to_string(),
+            used,
+            current: false,
+        }
+    }
+
+    fn current(address: &str, used: bool) -> CachedDepositAddress {
+        CachedDepositAddress {
+            current: true,
+            ..monitored(address, used)
+        }
+    }
+
+    fn deposit_to(address: &str) -> DetailedUtxo {
+        let address = Address::<NetworkUnchecked>::from_str(address)
+            .unwrap()
+            .assume_checked();
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+        DetailedUtxo {
+            txid: tx.compute_txid(),
+            tx,
+            vout: 0,
+            value: 10_000,
+        }
+    }
+
+    #[test_all]
+    fn test_track_current_address_drops_other_unused() {
+        let mut addresses = vec![monitored(ADDRESS_A, false), monitored("used", true)];
+        track_current_address(&mut addresses, ADDRESS_B);
+        assert_eq!(
+            addresses,
+            vec![monitored("used", true), current(ADDRESS_B, false)]
+        );
+
+        // Tracking the current address again doesn't duplicate it
+        track_current_address(&mut addresses, ADDRESS_B);
+        assert_eq!(addresses.len(), 2);
+    }
+
+    #[test_all]
+    fn test_update_marks_paid_address_used() {
+        let addresses = vec![monitored(ADDRESS_A, false), monitored(ADDRESS_B, false)];
+        let updated = update_monitored_addresses(addresses, &[deposit_to(ADDRESS_A)]);
+        assert_eq!(
+            updated,
+            vec![monitored(ADDRESS_A, true), monitored(ADDRESS_B, false)]
+        );
+        assert!(is_used(&updated, ADDRESS_A));
+        assert!(!is_used(&updated, ADDRESS_B));
+    }
+
+    #[test_all]
+    fn test_update_drops_used_address_without_deposits() {
+        let addresses = vec![monitored(ADDRESS_A, true), monitored(ADDRESS_B, true)];
+        let updated = update_monitored_addresses(addresses, &[deposit_to(ADDRESS_B)]);
+        assert_eq!(updated, vec![monitored(ADDRESS_B, true)]);
+    }
+
+    #[test_all]
+    fn test_current_address_stays_used_after_deposit_claimed() {
+        let mut addresses = Vec::new();
+        track_current_address(&mut addresses, ADDRESS_A);
+
+        // A deposit to the current address is seen, then claimed
+        let addresses = update_monitored_addresses(addresses, &[deposit_to(ADDRESS_A)]);
+        let mut addresses = update_monitored_addresses(addresses, &[]);
+        assert_eq!(addresses, vec![current(ADDRESS_A, true)]);
+        assert!(is_used(&addresses, ADDRESS_A));
+
+        // Once rotated, the claimed address is no longer monitored
+        track_current_address(&mut addresses, ADDRESS_B);
+        let addresses = update_monitored_addresses(addresses, &[]);
+        assert_eq!(addresses, vec![current(ADDRESS_B, false)]);
+    }
+}
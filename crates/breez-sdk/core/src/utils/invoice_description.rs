@@ -0,0 +1,103 @@
+use bitcoin::hashes::{Hash, sha256};
+use spark_wallet::InvoiceDescription;
+
+use crate::InvoiceDescriptionTemplate;
+
+/// Maximum length in bytes of a Bolt11 description field
+const MAX_BOLT11_DESCRIPTION_LEN: usize = 639;
+
+/// Renders the invoice description from the configured template, if any.
+///
+/// Returns `None` when the rendered description is empty.
+pub(crate) fn render_invoice_description(
+    template: Option<&InvoiceDescriptionTemplate>,
+    description: Option<&str>,
+    order_ref: Option<&str>,
+) -> Option<String> {
+    let rendered = match template {
+        Some(template) => template
+            .template
+            .replace("{app_name}", &template.app_name)
+            .replace("{description}", description.unwrap_or_default())
+            .replace("{order_ref}", order_ref.unwrap_or_default())
+            .trim()
+            .to_string(),
+        None => description.unwrap_or_default().to_string(),
+    };
+    if rendered.is_empty() {
+        None
+    } else {
+        Some(rendered)
+    }
+}
+
+/// Returns the Bolt11 description for `description`, committing to it by hash
+/// when it is too long to be included in the invoice.
+pub(crate) fn bolt11_invoice_description(description: String) -> InvoiceDescription {
+    if description.len() > MAX_BOLT11_DESCRIPTION_LEN {
+        InvoiceDescription::DescriptionHash(
+            sha256::Hash::hash(description.as_bytes()).to_byte_array(),
+        )
+    } else {
+        InvoiceDescription::Memo(description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(template: &str) -> InvoiceDescriptionTemplate {
+        InvoiceDescriptionTemplate {
+            template: template.to_string(),
+            app_name: "Shop".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_without_template() {
+        assert_eq!(
+            render_invoice_description(None, Some("coffee"), Some("42")),
+            Some("coffee".to_string())
+        );
+        assert_eq!(render_invoice_description(None, None, Some("42")), None);
+    }
+
+    #[test]
+    fn test_render_with_template() {
+        let template = template("{app_name}: {description} (order {order_ref})");
+        assert_eq!(
+            render_invoice_description(Some(&template), Some("coffee"), Some("42")),
+            Some("Shop: coffee (order 42)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_leaves_missing_placeholders_empty() {
+        let template = template("{description} {order_ref}");
+        assert_eq!(
+            render_invoice_description(Some(&template), Some("coffee"), None),
+            Some("coffee".to_string())
+        );
+        assert_eq!(
+            render_invoice_description(Some(&template), None, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_long_bolt11_description_is_hashed() {
+        let short = "a".repeat(MAX_BOLT11_DESCRIPTION_LEN);
+        assert!(matches!(
+            bolt11_invoice_description(short.clone()),
+            InvoiceDescription::Memo(memo) if memo == short
+        ));
+
+        let long = "a".repeat(MAX_BOLT11_DESCRIPTION_LEN + 1);
+        let expected = sha256::Hash::hash(long.as_bytes()).to_byte_array();
+        assert!(matches!(
+            bolt11_invoice_description(long),
+            InvoiceDescription::DescriptionHash(hash) if hash == expected
+        ));
+    }
+}
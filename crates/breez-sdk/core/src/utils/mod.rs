@@ -1,9 +1,11 @@
 pub(crate) mod bitcoin_dust;
 pub(crate) mod contacts_validation;
 pub(crate) mod conversions;
+pub(crate) mod deposit_addresses;
 pub(crate) mod deposit_chain_syncer;
 pub(crate) mod expiring_cell;
 pub(crate) mod fees;
+pub(crate) mod invoice_description;
 pub(crate) mod payments;
 pub(crate) mod polling;
 pub mod serde_helpers;
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -8,11 +9,12 @@ use spark_wallet::{
 use tracing::{debug, error, info, warn};
 
 use crate::{
-    ConversionInfo, ConversionStatus, EventEmitter, Payment, PaymentMetadata, PaymentStatus,
-    PaymentType, Storage,
+    ConversionInfo, ConversionStatus, EventEmitter, Payment, PaymentDetails, PaymentGroup,
+    PaymentMetadata, PaymentStatus, PaymentType, Storage,
     error::SdkError,
     events::SdkEvent,
     persist::{CachedAccountInfo, ObjectCacheRepository},
+    sdk::process_success_action,
     sync::SparkSyncService,
     utils::conversions::{
         build_amm_conversion, build_crosschain_conversion, extract_conversion_info,
@@ -28,19 +30,36 @@ pub(crate) async fn record_payment_update(
     payment: Payment,
     emit_event: bool,
 ) -> bool {
-    let should_emit = match storage.apply_payment_update(payment.clone()).await {
+    record_payment_update_with_reason(storage, event_emitter, payment, emit_event, None).await
+}
+
+/// Like [`record_payment_update`], attaching `failure_reason` to the event of
+/// a failed payment.
+pub(crate) async fn record_payment_update_with_reason(
+    storage: &Arc<dyn Storage>,
+    event_emitter: &EventEmitter,
+    payment: Payment,
+    emit_event: bool,
+    failure_reason: Option<String>,
+) -> bool {
+    // The payment is only needed after the update if an event may be emitted,
+    // so it is only copied in that case.
+    let event_payment = emit_event.then(|| payment.clone());
+    let payment_id = payment.id.clone();
+    let should_emit = match storage.apply_payment_update(payment).await {
         Ok(should_emit) => should_emit,
         Err(err) => {
-            error!("Failed to apply payment update {}: {err:?}", payment.id);
+            error!("Failed to apply payment update {payment_id}: {err:?}");
             return false;
         }
     };
 
-    if emit_event && should_emit {
-        get_payment_and_emit_event(storage, event_emitter, payment).await;
-        true
-    } else {
-        false
+    match event_payment {
+        Some(payment) if should_emit => {
+            get_payment_and_emit_event(storage, event_emitter, payment, failure_reason).await;
+            true
+        }
+        _ => false,
     }
 }
 
@@ -51,8 +70,9 @@ pub(crate) async fn get_payment_and_emit_event(
     storage: &Arc<dyn Storage>,
     event_emitter: &EventEmitter,
     payment: Payment,
+    failure_reason: Option<String>,
 ) {
-    let payment =
+    let mut payment =
         match get_payment_with_conversion_details(payment.id.clone(), Arc::clone(storage)).await {
             Ok(payment) => payment,
             Err(e) => {
@@ -60,8 +80,70 @@ pub(crate) async fn get_payment_and_emit_event(
                 payment
             }
         };
+    complete_lnurl_success_action(storage, &mut payment).await;
     info!("Emitting payment event: {payment:?}");
-    event_emitter.emit(&SdkEvent::from_payment(payment)).await;
+    event_emitter
+        .emit(&SdkEvent::from_payment_with_reason(payment, failure_reason))
+        .await;
+}
+
+/// Processes and persists the LNURL success action of a payment that was
+/// still pending when it was sent, e.g. decrypting an AES action once the
+/// preimage is known.
+pub(crate) async fn complete_lnurl_success_action(
+    storage: &Arc<dyn Storage>,
+    payment: &mut Payment,
+) {
+    if let Err(e) = try_complete_lnurl_success_action(storage, payment).await {
+        warn!(
+            "Failed to process success action of payment {}: {e:?}",
+            payment.id
+        );
+    }
+}
+
+async fn try_complete_lnurl_success_action(
+    storage: &Arc<dyn Storage>,
+    payment: &mut Payment,
+) -> Result<(), SdkError> {
+    if payment.status != PaymentStatus::Completed {
+        return Ok(());
+    }
+    let Some(PaymentDetails::Lightning {
+        lnurl_pay_info: Some(lnurl_pay_info),
+        ..
+    }) = &payment.details
+    else {
+        return Ok(());
+    };
+    if lnurl_pay_info.processed_success_action.is_some() {
+        return Ok(());
+    }
+    let Some(success_action) = lnurl_pay_info.raw_success_action.clone() else {
+        return Ok(());
+    };
+    let Some(processed) = process_success_action(payment, Some(&success_action.into()))? else {
+        return Ok(());
+    };
+
+    let Some(PaymentDetails::Lightning {
+        lnurl_pay_info: Some(lnurl_pay_info),
+        ..
+    }) = &mut payment.details
+    else {
+        return Ok(());
+    };
+    lnurl_pay_info.processed_success_action = Some(processed.into());
+    storage
+        .insert_payment_metadata(
+            payment.id.clone(),
+            PaymentMetadata {
+                lnurl_pay_info: Some(lnurl_pay_info.clone()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    Ok(())
 }
 
 /// Process an already-fetched Spark transfer, claiming it locally if
@@ -387,6 +469,73 @@ pub(crate) fn build_conversions(
     conversions
 }
 
+/// Aggregates the listed payments by group, in list order.
+///
+/// `members` are all stored payments of the groups in `payments`. The listed
+/// payments take precedence over them, as they carry their conversion details.
+pub(crate) fn build_payment_groups(
+    payments: &[Payment],
+    members: Vec<Payment>,
+) -> Vec<PaymentGroup> {
+    let mut members_by_group: HashMap<String, Vec<Payment>> = HashMap::new();
+    for member in members {
+        if let Some(group_id) = member.group_id.clone() {
+            members_by_group.entry(group_id).or_default().push(member);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut groups = Vec::new();
+    for payment in payments {
+        let group_id = payment
+            .group_id
+            .clone()
+            .unwrap_or_else(|| payment.id.clone());
+        if !seen.insert(group_id.clone()) {
+            continue;
+        }
+        let mut group_payments: Vec<Payment> = payments
+            .iter()
+            .filter(|p| p.group_id.as_ref() == Some(&group_id) || p.id == group_id)
+            .cloned()
+            .collect();
+        for member in members_by_group.remove(&group_id).unwrap_or_default() {
+            if !group_payments.iter().any(|p| p.id == member.id) {
+                group_payments.push(member);
+            }
+        }
+        group_payments.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)));
+
+        let status = if group_payments
+            .iter()
+            .any(|p| p.status == PaymentStatus::Pending)
+        {
+            PaymentStatus::Pending
+        } else if group_payments
+            .iter()
+            .any(|p| p.status == PaymentStatus::Failed)
+        {
+            PaymentStatus::Failed
+        } else {
+            PaymentStatus::Completed
+        };
+        groups.push(PaymentGroup {
+            group_id,
+            status,
+            timestamp: group_payments
+                .iter()
+                .map(|p| p.timestamp)
+                .max()
+                .unwrap_or(0),
+            fees: group_payments
+                .iter()
+                .fold(0u128, |fees, p| fees.saturating_add(p.fees)),
+            payments: group_payments,
+        });
+    }
+    groups
+}
+
 /// Resolves a Spark transfer ID or token transaction hash to a payment ID.
 ///
 /// If `identifier` is a valid [`TransferId`] it is returned directly (Spark
@@ -512,8 +661,8 @@ mod tests {
     use crate::{
         ConversionInfo, ConversionStatus, SparkHtlcDetails, SparkHtlcStatus,
         models::{
-            ConversionDetails, ConversionProvider, Payment, PaymentDetails, PaymentMethod,
-            PaymentStatus, PaymentType, TokenMetadata, TokenTransactionType,
+            ConversionDetails, ConversionProvider, DisplayAmount, Payment, PaymentDetails,
+            PaymentMethod, PaymentStatus, PaymentType, TokenMetadata, TokenTransactionType,
         },
     };
 
@@ -566,6 +715,10 @@ mod tests {
                 conversion_info: Some(amm_info()),
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -584,6 +737,10 @@ mod tests {
                 conversion_info: Some(amm_info()),
             }),
             conversion_details: None,
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -655,6 +812,10 @@ mod tests {
                 status: ConversionStatus::Completed,
                 conversions: vec![],
             }),
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -681,6 +842,10 @@ mod tests {
                 status: ConversionStatus::Completed,
                 conversions: vec![],
             }),
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -702,6 +867,10 @@ mod tests {
                 status: ConversionStatus::Completed,
                 conversions: vec![],
             }),
+            imported: false,
+            display_amount: DisplayAmount::default(),
+            group_id: None,
+            dispute: None,
         }
     }
 
@@ -802,4 +971,135 @@ mod tests {
         let conversions = build_conversions(&parent, None);
         assert!(conversions.is_empty());
     }
+
+    // --- build_payment_groups tests ---
+
+    fn grouped_payment(id: &str, group_id: Option<&str>, timestamp: u64) -> Payment {
+        let mut payment = spark_child(id, PaymentType::Send);
+        payment.timestamp = timestamp;
+        payment.fees = 2;
+        payment.group_id = group_id.map(ToString::to_string);
+        payment
+    }
+
+    #[test]
+    fn groups_listed_payments_with_members() {
+        let payments = vec![
+            grouped_payment("leg2", Some("split"), 1002),
+            grouped_payment("single", None, 1001),
+        ];
+        let mut pending_leg = grouped_payment("leg1", Some("split"), 1000);
+        pending_leg.status = PaymentStatus::Pending;
+        let members = vec![pending_leg, grouped_payment("leg2", Some("split"), 1002)];
+
+        let groups = build_payment_groups(&payments, members);
+        assert_eq!(groups.len(), 2);
+
+        let split = &groups[0];
+        assert_eq!(split.group_id, "split");
+        assert_eq!(split.status, PaymentStatus::Pending);
+        assert_eq!(split.timestamp, 1002);
+        assert_eq!(split.fees, 4);
+        let ids: Vec<_> = split.payments.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["leg1", "leg2"]);
+
+        let single = &groups[1];
+        assert_eq!(single.group_id, "single");
+        assert_eq!(single.status, PaymentStatus::Completed);
+        assert_eq!(single.payments.len(), 1);
+    }
+
+    #[test]
+    fn groups_each_group_once() {
+        let payments = vec![
+            grouped_payment("leg2", Some("split"), 1002),
+            grouped_payment("leg1", Some("split"), 1000),
+        ];
+        let groups = build_payment_groups(&payments, vec![]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].payments.len(), 2);
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod success_action_tests {
+        use std::path::PathBuf;
+
+        use base64::{Engine, engine::general_purpose::STANDARD as BASE64_STANDARD};
+        use breez_sdk_common::lnurl::pay::AesSuccessActionData as CommonAesData;
+
+        use super::*;
+        use crate::{
+            AesSuccessActionData, AesSuccessActionDataResult, LnurlPayInfo, SuccessAction,
+            SuccessActionProcessed, persist::sqlite::SqliteStorage,
+        };
+
+        fn create_temp_dir(name: &str) -> PathBuf {
+            let mut path = std::env::temp_dir();
+            path.push(format!("breez-test-{}-{}", name, uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&path).unwrap();
+            path
+        }
+
+        #[tokio::test]
+        async fn test_aes_success_action_processed_after_completion() {
+            let dir = create_temp_dir("aes_success_action");
+            let storage: Arc<dyn Storage> = Arc::new(SqliteStorage::new(&dir).unwrap());
+            let preimage = [7u8; 32];
+            let iv = [0xa; 16];
+            let ciphertext = CommonAesData::encrypt(&preimage, &iv, "secret").unwrap();
+
+            let mut payment = parent_send_no_crosschain();
+            payment.conversion_details = None;
+            if let Some(PaymentDetails::Lightning { htlc_details, .. }) = &mut payment.details {
+                htlc_details.preimage = Some(hex::encode(preimage));
+            }
+            storage.apply_payment_update(payment.clone()).await.unwrap();
+            // Sent while pending, so the AES action couldn't be decrypted yet
+            storage
+                .insert_payment_metadata(
+                    payment.id.clone(),
+                    PaymentMetadata {
+                        lnurl_pay_info: Some(LnurlPayInfo {
+                            ln_address: None,
+                            comment: Some("thanks".to_string()),
+                            domain: None,
+                            metadata: None,
+                            processed_success_action: None,
+                            raw_success_action: Some(SuccessAction::Aes {
+                                data: AesSuccessActionData {
+                                    description: "Your code".to_string(),
+                                    ciphertext,
+                                    iv: BASE64_STANDARD.encode(iv),
+                                },
+                            }),
+                        }),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+
+            let mut payment = storage.get_payment_by_id(payment.id).await.unwrap();
+            complete_lnurl_success_action(&storage, &mut payment).await;
+
+            let stored = storage.get_payment_by_id(payment.id.clone()).await.unwrap();
+            for payment in [payment, stored] {
+                let Some(PaymentDetails::Lightning {
+                    lnurl_pay_info: Some(info),
+                    ..
+                }) = payment.details
+                else {
+                    panic!("Expected lnurl pay info");
+                };
+                assert_eq!(info.comment.as_deref(), Some("thanks"));
+                let Some(SuccessActionProcessed::Aes {
+                    result: AesSuccessActionDataResult::Decrypted { data },
+                }) = info.processed_success_action
+                else {
+                    panic!("Expected a decrypted AES success action");
+                };
+                assert_eq!(data.plaintext, "secret");
+            }
+        }
+    }
 }
@@ -6,10 +6,14 @@ use spark_wallet::{BURN_PUBLIC_KEY, PublicKey, SparkWallet};
 use tracing::{debug, warn};
 
 use crate::{
-    Payment, PaymentDetails, PaymentMethod, PaymentStatus, PaymentType, SdkError, Storage,
-    TokenMetadata, TokenTransactionType, persist::ObjectCacheRepository,
+    DisplayAmount, Payment, PaymentDetails, PaymentMethod, PaymentStatus, PaymentType, SdkError,
+    Storage, TokenMetadata, TokenTransactionType,
+    persist::{CachedTokenInvoice, ObjectCacheRepository},
 };
 
+/// Maximum number of token invoices kept for matching incoming payments.
+const MAX_TRACKED_TOKEN_INVOICES: usize = 200;
+
 /// Returns the metadata for the given token identifiers.
 ///
 /// Results are not guaranteed to be in the same order as the input token identifiers.
@@ -129,6 +133,9 @@ pub async fn token_transaction_to_payments(
 
     let mut payments = Vec::new();
 
+    let mut tracked_invoices = object_repository.fetch_token_invoices().await?;
+    let tracked_invoices_before = tracked_invoices.clone();
+
     let mut invoices = Vec::new();
     for invoice_str in &transaction.fulfilled_invoices {
         if let Some(InputType::SparkInvoice(invoice)) =
@@ -167,7 +174,25 @@ pub async fn token_transaction_to_payments(
                 output.owner_public_key
             );
         }
-        let invoice = invoices.first().map(|&inv| inv.clone());
+        let mut invoice = invoices.first().map(|&inv| inv.clone());
+        if payment_type == PaymentType::Receive
+            && is_transfer_transaction
+            && let Some(index) = match_token_invoice(
+                &mut tracked_invoices,
+                &id,
+                invoice.as_ref().map(|i| i.invoice.as_str()),
+                token_identifier,
+                output.token_amount,
+            )
+            && invoice.is_none()
+            && let Some(InputType::SparkInvoice(tracked)) = parse_spark_address(
+                &tracked_invoices[index].invoice,
+                &PaymentRequestSource::default(),
+            )
+        {
+            debug!("Matched token payment {id} to invoice {}", tracked.invoice);
+            invoice = Some(tracked);
+        }
 
         let mut tx_type = match transaction.inputs {
             spark_wallet::TokenInputs::Mint(..) => TokenTransactionType::Mint,
@@ -183,6 +208,14 @@ pub async fn token_transaction_to_payments(
             tx_type = TokenTransactionType::Burn;
         }
 
+        let details = PaymentDetails::Token {
+            metadata: metadata.clone(),
+            tx_hash: transaction.hash.clone(),
+            tx_type,
+            invoice_details: invoice.map(Into::into),
+            conversion_info: None,
+        };
+        let display_amount = DisplayAmount::new(output.token_amount, Some(&details));
         let payment = Payment {
             id,
             payment_type,
@@ -194,21 +227,77 @@ pub async fn token_transaction_to_payments(
             fees: 0, // TODO: calculate actual fees when they start being charged
             timestamp,
             method: PaymentMethod::Token,
-            details: Some(PaymentDetails::Token {
-                metadata: metadata.clone(),
-                tx_hash: transaction.hash.clone(),
-                tx_type,
-                invoice_details: invoice.map(Into::into),
-                conversion_info: None,
-            }),
+            details: Some(details),
             conversion_details: None,
+            imported: false,
+            display_amount,
+            group_id: None,
+            dispute: None,
         };
         payments.push(payment);
     }
 
+    if tracked_invoices != tracked_invoices_before {
+        object_repository
+            .save_token_invoices(&tracked_invoices)
+            .await?;
+    }
+
     Ok(payments)
 }
 
+/// Starts tracking a token invoice created by `receive_payment`, so the
+/// payment that pays it can be matched to it.
+pub(crate) async fn track_token_invoice(
+    object_repository: &ObjectCacheRepository,
+    invoice: String,
+    token_identifier: String,
+    amount: u128,
+) -> Result<(), SdkError> {
+    let mut invoices = object_repository.fetch_token_invoices().await?;
+    invoices.push(CachedTokenInvoice {
+        invoice,
+        token_identifier,
+        amount,
+        payment_id: None,
+    });
+    let excess = invoices.len().saturating_sub(MAX_TRACKED_TOKEN_INVOICES);
+    invoices.drain(..excess);
+    object_repository.save_token_invoices(&invoices).await?;
+    Ok(())
+}
+
+/// Returns the index of the tracked invoice paid by a received token output,
+/// recording the match on the invoice.
+///
+/// An invoice the transaction fulfills is matched by its encoded form. If the
+/// transaction fulfills no invoice, the oldest open invoice for the same token
+/// and amount is matched.
+fn match_token_invoice(
+    invoices: &mut [CachedTokenInvoice],
+    payment_id: &str,
+    fulfilled_invoice: Option<&str>,
+    token_identifier: &str,
+    amount: u128,
+) -> Option<usize> {
+    if let Some(index) = invoices
+        .iter()
+        .position(|i| i.payment_id.as_deref() == Some(payment_id))
+    {
+        return Some(index);
+    }
+    let index = match fulfilled_invoice {
+        Some(fulfilled_invoice) => invoices
+            .iter()
+            .position(|i| i.payment_id.is_none() && i.invoice == fulfilled_invoice)?,
+        None => invoices.iter().position(|i| {
+            i.payment_id.is_none() && i.token_identifier == token_identifier && i.amount == amount
+        })?,
+    };
+    invoices[index].payment_id = Some(payment_id.to_string());
+    Some(index)
+}
+
 pub(crate) async fn map_and_persist_token_transaction(
     spark_wallet: &SparkWallet,
     storage: &Arc<dyn Storage>,
@@ -365,4 +454,62 @@ mod tests {
         };
         assert!(!token_tx_inputs_are_ours(&tx, None, identity).unwrap());
     }
+
+    fn tracked_invoice(invoice: &str, amount: u128) -> CachedTokenInvoice {
+        CachedTokenInvoice {
+            invoice: invoice.to_string(),
+            token_identifier: "tk".to_string(),
+            amount,
+            payment_id: None,
+        }
+    }
+
+    #[macros::test_all]
+    fn match_token_invoice_by_token_and_amount() {
+        let mut invoices = vec![
+            tracked_invoice("inv1", 100),
+            tracked_invoice("inv2", 200),
+            tracked_invoice("inv3", 200),
+        ];
+
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx:0", None, "tk", 200),
+            Some(1)
+        );
+        assert_eq!(invoices[1].payment_id.as_deref(), Some("tx:0"));
+        // The same payment keeps its invoice, the next one gets the next open invoice
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx:0", None, "tk", 200),
+            Some(1)
+        );
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx2:0", None, "tk", 200),
+            Some(2)
+        );
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx3:0", None, "tk", 200),
+            None
+        );
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx4:0", None, "other", 100),
+            None
+        );
+    }
+
+    #[macros::test_all]
+    fn match_token_invoice_by_fulfilled_invoice() {
+        let mut invoices = vec![tracked_invoice("inv1", 100), tracked_invoice("inv2", 100)];
+
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx:0", Some("inv2"), "tk", 100),
+            Some(1)
+        );
+        assert_eq!(invoices[1].payment_id.as_deref(), Some("tx:0"));
+        // An untracked fulfilled invoice doesn't fall back to amount matching
+        assert_eq!(
+            match_token_invoice(&mut invoices, "tx2:0", Some("inv3"), "tk", 100),
+            None
+        );
+        assert_eq!(invoices[0].payment_id, None);
+    }
 }
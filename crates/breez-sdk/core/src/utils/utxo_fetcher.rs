@@ -1,6 +1,6 @@
 use std::{str::FromStr, sync::Arc};
 
-use bitcoin::{Transaction, Txid, consensus::encode::deserialize_hex};
+use bitcoin::{Address, Transaction, Txid, consensus::encode::deserialize_hex};
 
 use crate::{
     BitcoinChainService, DepositInfo, SdkError,
@@ -25,8 +25,18 @@ impl DetailedUtxo {
             refund_tx: None,
             refund_tx_id: None,
             claim_error: None,
+            claim_max_fee: None,
         }
     }
+
+    /// Whether the deposit output pays to one of `addresses`.
+    pub fn pays_to(&self, addresses: &[Address]) -> bool {
+        self.tx.output.get(self.vout as usize).is_some_and(|txout| {
+            addresses
+                .iter()
+                .any(|address| address.script_pubkey() == txout.script_pubkey)
+        })
+    }
 }
 
 pub(crate) struct CachedUtxoFetcher {
@@ -81,3 +91,49 @@ impl CachedUtxoFetcher {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::{Amount, Network, ScriptBuf, TxOut, absolute::LockTime, transaction::Version};
+
+    use super::*;
+
+    fn address(witness_script: &[u8]) -> Address {
+        Address::p2wsh(
+            &ScriptBuf::from_bytes(witness_script.to_vec()),
+            Network::Regtest,
+        )
+    }
+
+    #[test]
+    fn test_pays_to() {
+        let ours = address(&[0x51]);
+        let foreign = address(&[0x52]);
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![
+                TxOut {
+                    value: Amount::from_sat(1_000),
+                    script_pubkey: ScriptBuf::new(),
+                },
+                TxOut {
+                    value: Amount::from_sat(50_000),
+                    script_pubkey: ours.script_pubkey(),
+                },
+            ],
+        };
+        let utxo = |vout| DetailedUtxo {
+            tx: tx.clone(),
+            vout,
+            txid: tx.compute_txid(),
+            value: 50_000,
+        };
+
+        assert!(utxo(1).pays_to(&[foreign.clone(), ours.clone()]));
+        assert!(!utxo(1).pays_to(&[foreign]));
+        assert!(!utxo(0).pays_to(&[ours.clone()]));
+        assert!(!utxo(2).pays_to(&[ours]));
+    }
+}
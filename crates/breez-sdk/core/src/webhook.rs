@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bitcoin::hashes::{Hash, HashEngine, Hmac, HmacEngine, sha256};
+use platform_utils::HttpClient;
+use platform_utils::{
+    time::{SystemTime, UNIX_EPOCH},
+    tokio,
+};
+use serde::Serialize;
+use tokio::{select, sync::watch, time::sleep};
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use crate::{EventListener, SdkEvent, sdk::SharedConfig};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+const TIMESTAMP_HEADER: &str = "X-Breez-Timestamp";
+const SIGNATURE_HEADER: &str = "X-Breez-Signature";
+
+/// The body posted to the webhook endpoint
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    /// Unique identifier of the delivery, stable across retries
+    id: String,
+    event: &'a SdkEvent,
+}
+
+/// Posts every SDK event to the webhook endpoint, if one is configured.
+pub(crate) struct WebhookEventListener {
+    config: SharedConfig,
+    http_client: Arc<dyn HttpClient>,
+    shutdown_receiver: watch::Receiver<()>,
+}
+
+impl WebhookEventListener {
+    pub(crate) fn new(
+        config: SharedConfig,
+        http_client: Arc<dyn HttpClient>,
+        shutdown_receiver: watch::Receiver<()>,
+    ) -> Self {
+        Self {
+            config,
+            http_client,
+            shutdown_receiver,
+        }
+    }
+}
+
+#[macros::async_trait]
+impl EventListener for WebhookEventListener {
+    async fn on_event(&self, event: SdkEvent) {
+        let Some(webhook_config) = self.config.get().webhook_config.clone() else {
+            return;
+        };
+        let body = match serde_json::to_string(&WebhookPayload {
+            id: Uuid::new_v4().to_string(),
+            event: &event,
+        }) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("Failed to serialize webhook payload for {event}: {e}");
+                return;
+            }
+        };
+        let delivery = WebhookDelivery {
+            url: webhook_config.url,
+            secret: webhook_config.secret,
+            max_attempts: webhook_config.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS),
+            initial_backoff: INITIAL_BACKOFF,
+            http_client: Arc::clone(&self.http_client),
+        };
+        let mut shutdown_receiver = self.shutdown_receiver.clone();
+        tokio::spawn(async move {
+            select! {
+                _ = shutdown_receiver.changed() => debug!("Webhook delivery cancelled on shutdown"),
+                _ = delivery.deliver(body) => {}
+            }
+        });
+    }
+}
+
+struct WebhookDelivery {
+    url: String,
+    secret: String,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    http_client: Arc<dyn HttpClient>,
+}
+
+impl WebhookDelivery {
+    /// Posts `body`, retrying with exponential backoff until the endpoint
+    /// accepts it or the attempts are exhausted. Returns whether it was delivered.
+    async fn deliver(&self, body: String) -> bool {
+        let mut backoff = self.initial_backoff;
+        for attempt in 1..=self.max_attempts {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default()
+                .to_string();
+            let headers = HashMap::from([
+                ("Content-Type".to_string(), "application/json".to_string()),
+                (TIMESTAMP_HEADER.to_string(), timestamp.clone()),
+                (
+                    SIGNATURE_HEADER.to_string(),
+                    sign_payload(&self.secret, &timestamp, &body),
+                ),
+            ]);
+            match self
+                .http_client
+                .post(self.url.clone(), Some(headers), Some(body.clone()))
+                .await
+            {
+                Ok(response) if response.is_success() => return true,
+                Ok(response) => warn!(
+                    "Webhook delivery attempt {attempt} failed with status {}",
+                    response.status
+                ),
+                Err(e) => warn!("Webhook delivery attempt {attempt} failed: {e}"),
+            }
+            if attempt < self.max_attempts {
+                sleep(backoff).await;
+                backoff = backoff.saturating_mul(2).min(MAX_BACKOFF);
+            }
+        }
+        error!(
+            "Giving up webhook delivery after {} attempts",
+            self.max_attempts
+        );
+        false
+    }
+}
+
+/// Returns the hex encoded HMAC-SHA256 of `"{timestamp}.{body}"`.
+fn sign_payload(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut engine = HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+    engine.input(timestamp.as_bytes());
+    engine.input(b".");
+    engine.input(body.as_bytes());
+    Hmac::<sha256::Hash>::from_engine(engine).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use breez_sdk_common::test_utils::mock_rest_client::{MockResponse, MockRestClient};
+    use macros::{async_test_all, test_all};
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn delivery(http_client: Arc<MockRestClient>, max_attempts: u32) -> WebhookDelivery {
+        WebhookDelivery {
+            url: "https://example.com/webhook".to_string(),
+            secret: "secret".to_string(),
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            http_client,
+        }
+    }
+
+    #[test_all]
+    fn test_sign_payload() {
+        assert_eq!(
+            sign_payload("secret", "1700000000", "{}"),
+            "b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+        assert_ne!(
+            sign_payload("secret", "1700000001", "{}"),
+            sign_payload("secret", "1700000000", "{}")
+        );
+    }
+
+    #[async_test_all]
+    async fn test_deliver_retries_until_success() {
+        let http_client = Arc::new(MockRestClient::new());
+        http_client
+            .add_response(MockResponse::new(500, String::new()))
+            .add_response(MockResponse::new(503, String::new()))
+            .add_response(MockResponse::new(200, String::new()));
+        assert!(delivery(http_client, 3).deliver("{}".to_string()).await);
+    }
+
+    #[async_test_all]
+    async fn test_deliver_gives_up_after_max_attempts() {
+        let http_client = Arc::new(MockRestClient::new());
+        http_client
+            .add_response(MockResponse::new(500, String::new()))
+            .add_response(MockResponse::new(500, String::new()))
+            .add_response(MockResponse::new(200, String::new()));
+        assert!(!delivery(http_client, 2).deliver("{}".to_string()).await);
+    }
+}
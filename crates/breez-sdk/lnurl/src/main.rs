@@ -351,6 +351,7 @@ where
             default_jwt_provider.clone(),
             default_jwt_provider,
             None,
+            None,
         )
         .await?,
     );
@@ -61,6 +61,7 @@ impl<DB> State<DB> {
             domain_jwt_provider.clone(),
             domain_jwt_provider,
             None,
+            None,
         )
         .await;
         match built {
@@ -79,7 +79,10 @@ const SELECT_PAYMENT_SQL = `
            lrm.nostr_zap_receipt AS lnurl_nostr_zap_receipt,
            lrm.sender_comment AS lnurl_sender_comment,
            lrm.payment_hash AS lnurl_payment_hash,
-           pm.parent_payment_id
+           pm.parent_payment_id,
+           p.imported,
+           pm.group_id,
+           pm.dispute
       FROM brz_payments p
       LEFT JOIN brz_payment_details_lightning l ON p.id = l.payment_id AND p.user_id = l.user_id
       LEFT JOIN brz_payment_details_token t ON p.id = t.payment_id AND p.user_id = t.user_id
@@ -88,6 +91,36 @@ const SELECT_PAYMENT_SQL = `
       LEFT JOIN brz_payment_metadata pm ON p.id = pm.payment_id AND p.user_id = pm.user_id
       LEFT JOIN brz_lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash AND l.user_id = lrm.user_id`;
 
+// The text a payment is searched by with the `search` filter
+const PAYMENT_SEARCH_SQL = `CONCAT_WS(' ',
+    p.id,
+    p.withdraw_tx_id,
+    pd.tx_id,
+    l.invoice,
+    l.description,
+    pm.lnurl_description,
+    JSON_UNQUOTE(JSON_EXTRACT(pm.lnurl_pay_info, '$.ln_address')),
+    JSON_UNQUOTE(JSON_EXTRACT(pm.lnurl_pay_info, '$.comment')),
+    lrm.sender_comment,
+    t.tx_hash,
+    JSON_UNQUOTE(JSON_EXTRACT(t.invoice_details, '$.invoice')),
+    JSON_UNQUOTE(JSON_EXTRACT(t.invoice_details, '$.description')),
+    JSON_UNQUOTE(JSON_EXTRACT(s.invoice_details, '$.invoice')),
+    JSON_UNQUOTE(JSON_EXTRACT(s.invoice_details, '$.description')))`;
+
+// A LIKE pattern matching text containing `search`, escaping its wildcards
+function searchLikePattern(search) {
+  return `%${search.replace(/[\\%_]/g, "\\$&")}%`;
+}
+
+// Tables holding the details of a payment, keyed by its id
+const PAYMENT_DETAILS_TABLES = [
+  "brz_payment_details_lightning",
+  "brz_payment_details_token",
+  "brz_payment_details_spark",
+  "brz_payment_details_deposit",
+];
+
 /**
  * mysql2 may return JSON columns as either parsed objects or raw strings
  * depending on driver/server behavior. This helper normalizes both shapes.
@@ -267,6 +300,15 @@ class MysqlStorage {
         params.push(request.toTimestamp);
       }
 
+      // Resume after the cursor, in the sort order
+      if (request.after != null) {
+        const operator = request.sortAscending ? ">" : "<";
+        whereClauses.push(
+          `(p.timestamp ${operator} ? OR (p.timestamp = ? AND p.id ${operator} ?))`
+        );
+        params.push(request.after.timestamp, request.after.timestamp, request.after.id);
+      }
+
       if (
         request.paymentDetailsFilter &&
         request.paymentDetailsFilter.length > 0
@@ -389,13 +431,44 @@ class MysqlStorage {
         }
       }
 
+      if (request.counterpartyFilter) {
+        const counterpartyFilter = request.counterpartyFilter;
+        if (counterpartyFilter.type === "lightningDestination") {
+          whereClauses.push("l.destination_pubkey = ?");
+          params.push(counterpartyFilter.destinationPubkey);
+        } else if (counterpartyFilter.type === "lightningAddress") {
+          whereClauses.push(
+            "LOWER(JSON_UNQUOTE(JSON_EXTRACT(pm.lnurl_pay_info, '$.ln_address'))) = ?"
+          );
+          params.push(counterpartyFilter.address.toLowerCase());
+        }
+      }
+
+      if (request.search != null) {
+        whereClauses.push(`LOWER(${PAYMENT_SEARCH_SQL}) LIKE ?`);
+        params.push(searchLikePattern(request.search.toLowerCase()));
+      }
+
+      if (request.groupIdFilter && request.groupIdFilter.length > 0) {
+        const placeholders = request.groupIdFilter.map(() => "?");
+        whereClauses.push(`pm.group_id IN (${placeholders.join(", ")})`);
+        params.push(...request.groupIdFilter);
+      }
+
+      if (request.disputeStatusFilter && request.disputeStatusFilter.length > 0) {
+        const placeholders = request.disputeStatusFilter.map(() => "?");
+        whereClauses.push(`pm.dispute_status IN (${placeholders.join(", ")})`);
+        params.push(...request.disputeStatusFilter);
+      }
+
       whereClauses.push("pm.parent_payment_id IS NULL");
 
       const whereSql =
         whereClauses.length > 0 ? `WHERE ${whereClauses.join(" AND ")}` : "";
 
       const orderDirection = request.sortAscending ? "ASC" : "DESC";
-      const query = `${SELECT_PAYMENT_SQL} ${whereSql} ORDER BY p.timestamp ${orderDirection} LIMIT ? OFFSET ?`;
+      // The id breaks timestamp ties, so cursors resume at a stable position
+      const query = `${SELECT_PAYMENT_SQL} ${whereSql} ORDER BY p.timestamp ${orderDirection}, p.id ${orderDirection} LIMIT ? OFFSET ?`;
 
       params.push(actualLimit, actualOffset);
       const [rows] = await this.pool.query(query, params);
@@ -509,8 +582,8 @@ class MysqlStorage {
     const spark = payment.details?.type === "spark" ? 1 : null;
 
     await conn.query(
-      `INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark)
-       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+      `INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark, imported)
+       VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
        ON DUPLICATE KEY UPDATE
          payment_type=VALUES(payment_type),
          status=VALUES(status),
@@ -519,7 +592,8 @@ class MysqlStorage {
          timestamp=VALUES(timestamp),
          method=VALUES(method),
          withdraw_tx_id=VALUES(withdraw_tx_id),
-         spark=VALUES(spark)`,
+         spark=VALUES(spark),
+         imported=VALUES(imported)`,
       [
         this.identity,
         payment.id,
@@ -531,6 +605,7 @@ class MysqlStorage {
         payment.method ? JSON.stringify(payment.method) : null,
         withdrawTxId,
         spark,
+        payment.imported ? 1 : 0,
       ]
     );
 
@@ -732,15 +807,18 @@ class MysqlStorage {
   async insertPaymentMetadata(paymentId, metadata) {
     try {
       await this.pool.query(
-        `INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        `INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status, group_id, dispute, dispute_status)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
          ON DUPLICATE KEY UPDATE
            parent_payment_id = COALESCE(VALUES(parent_payment_id), parent_payment_id),
            lnurl_pay_info = COALESCE(VALUES(lnurl_pay_info), lnurl_pay_info),
            lnurl_withdraw_info = COALESCE(VALUES(lnurl_withdraw_info), lnurl_withdraw_info),
            lnurl_description = COALESCE(VALUES(lnurl_description), lnurl_description),
            conversion_info = COALESCE(VALUES(conversion_info), conversion_info),
-           conversion_status = COALESCE(VALUES(conversion_status), conversion_status)`,
+           conversion_status = COALESCE(VALUES(conversion_status), conversion_status),
+           group_id = COALESCE(VALUES(group_id), group_id),
+           dispute = COALESCE(VALUES(dispute), dispute),
+           dispute_status = COALESCE(VALUES(dispute_status), dispute_status)`,
         [
           this.identity,
           paymentId,
@@ -756,6 +834,9 @@ class MysqlStorage {
             ? JSON.stringify(metadata.conversionInfo)
             : null,
           metadata.conversionStatus ?? null,
+          metadata.groupId ?? null,
+          metadata.dispute ? JSON.stringify(metadata.dispute) : null,
+          metadata.dispute?.status ?? null,
         ]
       );
     } catch (error) {
@@ -977,6 +1058,9 @@ class MysqlStorage {
       conversionDetails: row.conversion_status
         ? { status: row.conversion_status, from: null, to: null }
         : null,
+      imported: toBool(row.imported),
+      groupId: row.group_id ?? null,
+      dispute: parseJson(row.dispute),
     };
   }
 
@@ -1527,6 +1611,49 @@ class MysqlStorage {
       );
     }
   }
+
+  // ===== Integrity Operations =====
+
+  /**
+   * MySQL validates the JSON columns and guards its own consistency, so only
+   * orphaned payment details are looked for.
+   */
+  async checkIntegrity(repair) {
+    try {
+      return await this._withTransaction(async (conn) => {
+        const issues = [];
+        for (const table of PAYMENT_DETAILS_TABLES) {
+          const filter = `d.user_id = ? AND NOT EXISTS (
+            SELECT 1 FROM brz_payments p WHERE p.user_id = d.user_id AND p.id = d.payment_id)`;
+          const [rows] = await conn.query(
+            `SELECT d.payment_id FROM ${table} d WHERE ${filter}`,
+            [this.identity]
+          );
+          if (repair && rows.length > 0) {
+            await conn.query(`DELETE d FROM ${table} d WHERE ${filter}`, [
+              this.identity,
+            ]);
+          }
+          for (const row of rows) {
+            issues.push({
+              kind: "orphanedRecord",
+              table,
+              recordId: row.payment_id,
+              description: `Payment ${row.payment_id} doesn't exist`,
+              repaired: repair,
+            });
+          }
+        }
+        return issues;
+      });
+    } catch (error) {
+      if (error instanceof StorageError) throw error;
+      throw new StorageError(
+        `Failed to check integrity: ${error.message}`,
+        error
+      );
+    }
+  }
 }
 
 /**
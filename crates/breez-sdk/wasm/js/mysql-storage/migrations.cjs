@@ -562,6 +562,36 @@ class MysqlMigrationManager {
           )`,
         ],
       },
+      {
+        name: "Add counterparty index",
+        sql: [
+          `CREATE INDEX brz_idx_payment_details_lightning_user_destination_pubkey
+              ON brz_payment_details_lightning(user_id, destination_pubkey)`,
+        ],
+      },
+      {
+        name: "Add imported flag to payments",
+        sql: [
+          `ALTER TABLE brz_payments ADD COLUMN imported TINYINT(1) NOT NULL DEFAULT 0`,
+        ],
+      },
+      {
+        name: "Add group id to payment metadata",
+        sql: [
+          `ALTER TABLE brz_payment_metadata ADD COLUMN group_id VARCHAR(255) NULL`,
+          `CREATE INDEX brz_idx_payment_metadata_user_group
+             ON brz_payment_metadata(user_id, group_id)`,
+        ],
+      },
+      {
+        name: "Add dispute to payment metadata",
+        sql: [
+          `ALTER TABLE brz_payment_metadata ADD COLUMN dispute JSON NULL`,
+          `ALTER TABLE brz_payment_metadata ADD COLUMN dispute_status VARCHAR(64) NULL`,
+          `CREATE INDEX brz_idx_payment_metadata_user_dispute_status
+             ON brz_payment_metadata(user_id, dispute_status)`,
+        ],
+      },
     ];
   }
 }
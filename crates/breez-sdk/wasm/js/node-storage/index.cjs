@@ -65,7 +65,10 @@ const SELECT_PAYMENT_SQL = `
            lrm.nostr_zap_receipt AS lnurl_nostr_zap_receipt,
            lrm.sender_comment AS lnurl_sender_comment,
            lrm.payment_hash AS lnurl_payment_hash,
-           pm.parent_payment_id
+           pm.parent_payment_id,
+           p.imported,
+           pm.group_id,
+           pm.dispute
       FROM payments p
       LEFT JOIN payment_details_lightning l ON p.id = l.payment_id
       LEFT JOIN payment_details_token t ON p.id = t.payment_id
@@ -74,6 +77,56 @@ const SELECT_PAYMENT_SQL = `
       LEFT JOIN payment_metadata pm ON p.id = pm.payment_id
       LEFT JOIN lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash`;
 
+// The text a payment is searched by with the `search` filter
+const PAYMENT_SEARCH_SQL = [
+  "p.id",
+  "p.withdraw_tx_id",
+  "pd.tx_id",
+  "l.invoice",
+  "l.description",
+  "pm.lnurl_description",
+  "json_extract(pm.lnurl_pay_info, '$.ln_address')",
+  "json_extract(pm.lnurl_pay_info, '$.comment')",
+  "lrm.sender_comment",
+  "t.tx_hash",
+  "json_extract(t.invoice_details, '$.invoice')",
+  "json_extract(t.invoice_details, '$.description')",
+  "json_extract(s.invoice_details, '$.invoice')",
+  "json_extract(s.invoice_details, '$.description')",
+]
+  .map((column) => `COALESCE(${column}, '')`)
+  .join(" || ' ' || ");
+
+// A LIKE pattern matching text containing `search`, escaping its wildcards
+function searchLikePattern(search) {
+  return `%${search.replace(/[\\%_]/g, "\\$&")}%`;
+}
+
+// Tables holding the details of a payment, keyed by its id
+const PAYMENT_DETAILS_TABLES = [
+  "payment_details_lightning",
+  "payment_details_token",
+  "payment_details_spark",
+  "payment_details_deposit",
+];
+
+// Columns holding JSON as [table, record id, column, optional]. Invalid
+// optional values are cleared on repair, as their record stays usable.
+const JSON_COLUMNS = [
+  ["payment_metadata", "payment_id", "lnurl_pay_info", true],
+  ["payment_metadata", "payment_id", "lnurl_withdraw_info", true],
+  ["payment_metadata", "payment_id", "conversion_info", true],
+  ["payment_details_token", "payment_id", "invoice_details", true],
+  ["payment_details_spark", "payment_id", "invoice_details", true],
+  ["payment_details_spark", "payment_id", "htlc_details", true],
+  ["unclaimed_deposits", "txid", "claim_error", true],
+  ["payment_details_token", "payment_id", "metadata", false],
+  ["cross_chain_swaps", "id", "data", false],
+  ["sync_outgoing", "data_id", "updated_fields_json", false],
+  ["sync_state", "data_id", "data", false],
+  ["sync_incoming", "data_id", "data", false],
+];
+
 class SqliteStorage {
   constructor(dbPath, logger = null) {
     this.dbPath = dbPath;
@@ -201,6 +254,15 @@ class SqliteStorage {
         params.push(request.toTimestamp);
       }
 
+      // Resume after the cursor, in the sort order
+      if (request.after != null) {
+        const operator = request.sortAscending ? ">" : "<";
+        whereClauses.push(
+          `(p.timestamp ${operator} ? OR (p.timestamp = ? AND p.id ${operator} ?))`
+        );
+        params.push(request.after.timestamp, request.after.timestamp, request.after.id);
+      }
+
       // Filter by payment details. If any filter matches, we include the payment
       if (request.paymentDetailsFilter && request.paymentDetailsFilter.length > 0) {
         const allPaymentDetailsClauses = [];
@@ -310,6 +372,40 @@ class SqliteStorage {
         }
       }
 
+      // Filter by counterparty
+      if (request.counterpartyFilter) {
+        const counterpartyFilter = request.counterpartyFilter;
+        if (counterpartyFilter.type === "lightningDestination") {
+          whereClauses.push("l.destination_pubkey = ?");
+          params.push(counterpartyFilter.destinationPubkey);
+        } else if (counterpartyFilter.type === "lightningAddress") {
+          whereClauses.push(
+            "lower(json_extract(pm.lnurl_pay_info, '$.ln_address')) = ?"
+          );
+          params.push(counterpartyFilter.address.toLowerCase());
+        }
+      }
+
+      // Filter by free text
+      if (request.search != null) {
+        whereClauses.push(`(${PAYMENT_SEARCH_SQL}) LIKE ? ESCAPE '\\'`);
+        params.push(searchLikePattern(request.search));
+      }
+
+      // Filter by group
+      if (request.groupIdFilter && request.groupIdFilter.length > 0) {
+        const placeholders = request.groupIdFilter.map(() => "?").join(", ");
+        whereClauses.push(`pm.group_id IN (${placeholders})`);
+        params.push(...request.groupIdFilter);
+      }
+
+      // Filter by dispute status
+      if (request.disputeStatusFilter && request.disputeStatusFilter.length > 0) {
+        const placeholders = request.disputeStatusFilter.map(() => "?").join(", ");
+        whereClauses.push(`pm.dispute_status IN (${placeholders})`);
+        params.push(...request.disputeStatusFilter);
+      }
+
       // Exclude child payments (those with a parent_payment_id)
       whereClauses.push("pm.parent_payment_id IS NULL");
 
@@ -319,7 +415,8 @@ class SqliteStorage {
 
       // Determine sort order
       const orderDirection = request.sortAscending ? "ASC" : "DESC";
-      const query = `${SELECT_PAYMENT_SQL} ${whereSql} ORDER BY p.timestamp ${orderDirection} LIMIT ? OFFSET ?`;
+      // The id breaks timestamp ties, so cursors resume at a stable position
+      const query = `${SELECT_PAYMENT_SQL} ${whereSql} ORDER BY p.timestamp ${orderDirection}, p.id ${orderDirection} LIMIT ? OFFSET ?`;
 
       params.push(actualLimit, actualOffset);
       const stmt = this.db.prepare(query);
@@ -387,8 +484,8 @@ class SqliteStorage {
 
   _runPaymentUpsert(payment) {
     const paymentInsert = this.db.prepare(
-      `INSERT INTO payments (id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark)
-       VALUES (@id, @paymentType, @status, @amount, @fees, @timestamp, @method, @withdrawTxId, @spark)
+      `INSERT INTO payments (id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark, imported)
+       VALUES (@id, @paymentType, @status, @amount, @fees, @timestamp, @method, @withdrawTxId, @spark, @imported)
        ON CONFLICT(id) DO UPDATE SET
          payment_type=excluded.payment_type,
          status=excluded.status,
@@ -397,7 +494,8 @@ class SqliteStorage {
          timestamp=excluded.timestamp,
          method=excluded.method,
          withdraw_tx_id=excluded.withdraw_tx_id,
-         spark=excluded.spark`
+         spark=excluded.spark,
+         imported=excluded.imported`
     );
     const depositInsert = this.db.prepare(
       `INSERT INTO payment_details_deposit
@@ -450,6 +548,7 @@ class SqliteStorage {
       withdrawTxId:
         payment.details?.type === "withdraw" ? payment.details.txId : null,
       spark: payment.details?.type === "spark" ? 1 : null,
+      imported: payment.imported ? 1 : 0,
     });
 
     if (payment.details?.type === "deposit") {
@@ -622,15 +721,18 @@ class SqliteStorage {
   insertPaymentMetadata(paymentId, metadata) {
     try {
       const stmt = this.db.prepare(`
-                INSERT INTO payment_metadata (payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status)
-                VALUES (?, ?, ?, ?, ?, ?, ?)
+                INSERT INTO payment_metadata (payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status, group_id, dispute, dispute_status)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 ON CONFLICT(payment_id) DO UPDATE SET
                     parent_payment_id = COALESCE(excluded.parent_payment_id, parent_payment_id),
                     lnurl_pay_info = COALESCE(excluded.lnurl_pay_info, lnurl_pay_info),
                     lnurl_withdraw_info = COALESCE(excluded.lnurl_withdraw_info, lnurl_withdraw_info),
                     lnurl_description = COALESCE(excluded.lnurl_description, lnurl_description),
                     conversion_info = COALESCE(excluded.conversion_info, conversion_info),
-                    conversion_status = COALESCE(excluded.conversion_status, conversion_status)
+                    conversion_status = COALESCE(excluded.conversion_status, conversion_status),
+                    group_id = COALESCE(excluded.group_id, group_id),
+                    dispute = COALESCE(excluded.dispute, dispute),
+                    dispute_status = COALESCE(excluded.dispute_status, dispute_status)
             `);
 
       stmt.run(
@@ -644,7 +746,10 @@ class SqliteStorage {
         metadata.conversionInfo
           ? JSON.stringify(metadata.conversionInfo)
           : null,
-        metadata.conversionStatus ?? null
+        metadata.conversionStatus ?? null,
+        metadata.groupId ?? null,
+        metadata.dispute ? JSON.stringify(metadata.dispute) : null,
+        metadata.dispute?.status ?? null
       );
       return Promise.resolve();
     } catch (error) {
@@ -910,6 +1015,9 @@ class SqliteStorage {
       conversionDetails: row.conversion_status
         ? { status: row.conversion_status, from: null, to: null }
         : null,
+      imported: Boolean(row.imported),
+      groupId: row.group_id ?? null,
+      dispute: row.dispute ? JSON.parse(row.dispute) : null,
     };
   }
 
@@ -1471,6 +1579,75 @@ class SqliteStorage {
       );
     }
   }
+
+  // ===== Integrity Operations =====
+
+  checkIntegrity(repair) {
+    try {
+      const issues = this.db
+        .prepare("PRAGMA integrity_check")
+        .pluck()
+        .all()
+        .filter((message) => message !== "ok")
+        .map((message) => ({
+          kind: "corruption",
+          table: null,
+          recordId: null,
+          description: message,
+          repaired: false,
+        }));
+
+      const transaction = this.db.transaction(() => {
+        for (const table of PAYMENT_DETAILS_TABLES) {
+          const filter = "payment_id NOT IN (SELECT id FROM payments)";
+          const paymentIds = this.db
+            .prepare(`SELECT payment_id FROM ${table} WHERE ${filter}`)
+            .pluck()
+            .all();
+          if (repair && paymentIds.length > 0) {
+            this.db.prepare(`DELETE FROM ${table} WHERE ${filter}`).run();
+          }
+          for (const paymentId of paymentIds) {
+            issues.push({
+              kind: "orphanedRecord",
+              table,
+              recordId: paymentId,
+              description: `Payment ${paymentId} doesn't exist`,
+              repaired: repair,
+            });
+          }
+        }
+        for (const [table, id, column, optional] of JSON_COLUMNS) {
+          const filter = `${column} IS NOT NULL AND NOT json_valid(${column})`;
+          const recordIds = this.db
+            .prepare(`SELECT ${id} FROM ${table} WHERE ${filter}`)
+            .pluck()
+            .all()
+            .map(String);
+          const repaired = repair && optional;
+          if (repaired && recordIds.length > 0) {
+            this.db.prepare(`UPDATE ${table} SET ${column} = NULL WHERE ${filter}`).run();
+          }
+          for (const recordId of recordIds) {
+            issues.push({
+              kind: "invalidJson",
+              table,
+              recordId,
+              description: `Invalid JSON in ${column} of ${recordId}`,
+              repaired,
+            });
+          }
+        }
+      });
+
+      transaction();
+      return Promise.resolve(issues);
+    } catch (error) {
+      return Promise.reject(
+        new StorageError(`Failed to check integrity: ${error.message}`, error)
+      );
+    }
+  }
 }
 
 /// Maps a `cross_chain_swaps` row to a StoredCrossChainSwap, parsing `data` and
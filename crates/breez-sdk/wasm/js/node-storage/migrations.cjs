@@ -480,6 +480,36 @@ class MigrationManager {
             ON cross_chain_swaps(provider, is_terminal)`,
         ],
       },
+      {
+        name: "Add counterparty indexes",
+        sql: [
+          `CREATE INDEX idx_payment_details_lightning_destination_pubkey
+            ON payment_details_lightning(destination_pubkey)`,
+          `CREATE INDEX idx_payment_metadata_ln_address
+            ON payment_metadata(lower(json_extract(lnurl_pay_info, '$.ln_address')))`,
+        ],
+      },
+      {
+        name: "Add imported flag to payments",
+        sql: [
+          `ALTER TABLE payments ADD COLUMN imported INTEGER NOT NULL DEFAULT 0`,
+        ],
+      },
+      {
+        name: "Add group id to payment metadata",
+        sql: [
+          `ALTER TABLE payment_metadata ADD COLUMN group_id TEXT`,
+          `CREATE INDEX idx_payment_metadata_group_id ON payment_metadata(group_id)`,
+        ],
+      },
+      {
+        name: "Add dispute to payment metadata",
+        sql: [
+          `ALTER TABLE payment_metadata ADD COLUMN dispute TEXT`,
+          `ALTER TABLE payment_metadata ADD COLUMN dispute_status TEXT`,
+          `CREATE INDEX idx_payment_metadata_dispute_status ON payment_metadata(dispute_status)`,
+        ],
+      },
     ];
   }
 }
@@ -64,7 +64,10 @@ const SELECT_PAYMENT_SQL = `
            lrm.nostr_zap_receipt AS lnurl_nostr_zap_receipt,
            lrm.sender_comment AS lnurl_sender_comment,
            lrm.payment_hash AS lnurl_payment_hash,
-           pm.parent_payment_id
+           pm.parent_payment_id,
+           p.imported,
+           pm.group_id,
+           pm.dispute
       FROM brz_payments p
       LEFT JOIN brz_payment_details_lightning l ON p.id = l.payment_id AND p.user_id = l.user_id
       LEFT JOIN brz_payment_details_token t ON p.id = t.payment_id AND p.user_id = t.user_id
@@ -73,6 +76,36 @@ const SELECT_PAYMENT_SQL = `
       LEFT JOIN brz_payment_metadata pm ON p.id = pm.payment_id AND p.user_id = pm.user_id
       LEFT JOIN brz_lnurl_receive_metadata lrm ON l.payment_hash = lrm.payment_hash AND l.user_id = lrm.user_id`;
 
+// The text a payment is searched by with the `search` filter
+const PAYMENT_SEARCH_SQL = `concat_ws(' ',
+    p.id,
+    p.withdraw_tx_id,
+    pd.tx_id,
+    l.invoice,
+    l.description,
+    pm.lnurl_description,
+    pm.lnurl_pay_info->>'ln_address',
+    pm.lnurl_pay_info->>'comment',
+    lrm.sender_comment,
+    t.tx_hash,
+    t.invoice_details->>'invoice',
+    t.invoice_details->>'description',
+    s.invoice_details->>'invoice',
+    s.invoice_details->>'description')`;
+
+// A LIKE pattern matching text containing `search`, escaping its wildcards
+function searchLikePattern(search) {
+  return `%${search.replace(/[\\%_]/g, "\\$&")}%`;
+}
+
+// Tables holding the details of a payment, keyed by its id
+const PAYMENT_DETAILS_TABLES = [
+  "brz_payment_details_lightning",
+  "brz_payment_details_token",
+  "brz_payment_details_spark",
+  "brz_payment_details_deposit",
+];
+
 class PostgresStorage {
   /**
    * @param {import('pg').Pool} pool - Connection pool (may be shared with other tenants).
@@ -230,6 +263,17 @@ class PostgresStorage {
         params.push(request.toTimestamp);
       }
 
+      // Resume after the cursor, in the sort order
+      if (request.after != null) {
+        const operator = request.sortAscending ? ">" : "<";
+        const timestampIdx = paramIdx++;
+        const idIdx = paramIdx++;
+        whereClauses.push(
+          `(p.timestamp ${operator} $${timestampIdx} OR (p.timestamp = $${timestampIdx} AND p.id ${operator} $${idIdx}))`
+        );
+        params.push(request.after.timestamp, request.after.id);
+      }
+
       // Filter by payment details
       if (
         request.paymentDetailsFilter &&
@@ -354,6 +398,44 @@ class PostgresStorage {
         }
       }
 
+      // Filter by counterparty
+      if (request.counterpartyFilter) {
+        const counterpartyFilter = request.counterpartyFilter;
+        if (counterpartyFilter.type === "lightningDestination") {
+          whereClauses.push(`l.destination_pubkey = $${paramIdx++}`);
+          params.push(counterpartyFilter.destinationPubkey);
+        } else if (counterpartyFilter.type === "lightningAddress") {
+          whereClauses.push(
+            `lower(pm.lnurl_pay_info->>'ln_address') = $${paramIdx++}`
+          );
+          params.push(counterpartyFilter.address.toLowerCase());
+        }
+      }
+
+      // Filter by free text
+      if (request.search != null) {
+        whereClauses.push(`${PAYMENT_SEARCH_SQL} ILIKE $${paramIdx++}`);
+        params.push(searchLikePattern(request.search));
+      }
+
+      // Filter by group
+      if (request.groupIdFilter && request.groupIdFilter.length > 0) {
+        const placeholders = request.groupIdFilter.map(
+          () => `$${paramIdx++}`
+        );
+        whereClauses.push(`pm.group_id IN (${placeholders.join(", ")})`);
+        params.push(...request.groupIdFilter);
+      }
+
+      // Filter by dispute status
+      if (request.disputeStatusFilter && request.disputeStatusFilter.length > 0) {
+        const placeholders = request.disputeStatusFilter.map(
+          () => `$${paramIdx++}`
+        );
+        whereClauses.push(`pm.dispute_status IN (${placeholders.join(", ")})`);
+        params.push(...request.disputeStatusFilter);
+      }
+
       // Exclude child payments
       whereClauses.push("pm.parent_payment_id IS NULL");
 
@@ -363,7 +445,8 @@ class PostgresStorage {
           : "";
 
       const orderDirection = request.sortAscending ? "ASC" : "DESC";
-      const query = `${SELECT_PAYMENT_SQL} ${whereSql} ORDER BY p.timestamp ${orderDirection} LIMIT $${paramIdx++} OFFSET $${paramIdx++}`;
+      // The id breaks timestamp ties, so cursors resume at a stable position
+      const query = `${SELECT_PAYMENT_SQL} ${whereSql} ORDER BY p.timestamp ${orderDirection}, p.id ${orderDirection} LIMIT $${paramIdx++} OFFSET $${paramIdx++}`;
 
       params.push(actualLimit, actualOffset);
       const result = await this.pool.query(query, params);
@@ -443,8 +526,8 @@ class PostgresStorage {
     const spark = payment.details?.type === "spark" ? true : null;
 
     await client.query(
-      `INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark)
-       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+      `INSERT INTO brz_payments (user_id, id, payment_type, status, amount, fees, timestamp, method, withdraw_tx_id, spark, imported)
+       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
        ON CONFLICT(user_id, id) DO UPDATE SET
          payment_type=EXCLUDED.payment_type,
          status=EXCLUDED.status,
@@ -453,7 +536,8 @@ class PostgresStorage {
          timestamp=EXCLUDED.timestamp,
          method=EXCLUDED.method,
          withdraw_tx_id=EXCLUDED.withdraw_tx_id,
-         spark=EXCLUDED.spark`,
+         spark=EXCLUDED.spark,
+         imported=EXCLUDED.imported`,
       [
         this.identity,
         payment.id,
@@ -465,6 +549,7 @@ class PostgresStorage {
         payment.method ? JSON.stringify(payment.method) : null,
         withdrawTxId,
         spark,
+        payment.imported ?? false,
       ]
     );
 
@@ -661,15 +746,18 @@ class PostgresStorage {
   async insertPaymentMetadata(paymentId, metadata) {
     try {
       await this.pool.query(
-        `INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        `INSERT INTO brz_payment_metadata (user_id, payment_id, parent_payment_id, lnurl_pay_info, lnurl_withdraw_info, lnurl_description, conversion_info, conversion_status, group_id, dispute, dispute_status)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
          ON CONFLICT(user_id, payment_id) DO UPDATE SET
            parent_payment_id = COALESCE(EXCLUDED.parent_payment_id, brz_payment_metadata.parent_payment_id),
            lnurl_pay_info = COALESCE(EXCLUDED.lnurl_pay_info, brz_payment_metadata.lnurl_pay_info),
            lnurl_withdraw_info = COALESCE(EXCLUDED.lnurl_withdraw_info, brz_payment_metadata.lnurl_withdraw_info),
            lnurl_description = COALESCE(EXCLUDED.lnurl_description, brz_payment_metadata.lnurl_description),
            conversion_info = COALESCE(EXCLUDED.conversion_info, brz_payment_metadata.conversion_info),
-           conversion_status = COALESCE(EXCLUDED.conversion_status, brz_payment_metadata.conversion_status)`,
+           conversion_status = COALESCE(EXCLUDED.conversion_status, brz_payment_metadata.conversion_status),
+           group_id = COALESCE(EXCLUDED.group_id, brz_payment_metadata.group_id),
+           dispute = COALESCE(EXCLUDED.dispute, brz_payment_metadata.dispute),
+           dispute_status = COALESCE(EXCLUDED.dispute_status, brz_payment_metadata.dispute_status)`,
         [
           this.identity,
           paymentId,
@@ -685,6 +773,9 @@ class PostgresStorage {
             ? JSON.stringify(metadata.conversionInfo)
             : null,
           metadata.conversionStatus ?? null,
+          metadata.groupId ?? null,
+          metadata.dispute ? JSON.stringify(metadata.dispute) : null,
+          metadata.dispute?.status ?? null,
         ]
       );
     } catch (error) {
@@ -941,6 +1032,13 @@ class PostgresStorage {
       conversionDetails: row.conversion_status
         ? { status: row.conversion_status, from: null, to: null }
         : null,
+      imported: Boolean(row.imported),
+      groupId: row.group_id ?? null,
+      dispute: row.dispute
+        ? typeof row.dispute === "string"
+          ? JSON.parse(row.dispute)
+          : row.dispute
+        : null,
     };
   }
 
@@ -1536,6 +1634,49 @@ class PostgresStorage {
       );
     }
   }
+
+  // ===== Integrity Operations =====
+
+  /**
+   * PostgreSQL validates the JSONB columns and guards its own consistency,
+   * so only orphaned payment details are looked for.
+   */
+  async checkIntegrity(repair) {
+    try {
+      return await this._withTransaction(async (client) => {
+        const issues = [];
+        for (const table of PAYMENT_DETAILS_TABLES) {
+          const filter = `d.user_id = $1 AND NOT EXISTS (
+            SELECT 1 FROM brz_payments p WHERE p.user_id = d.user_id AND p.id = d.payment_id)`;
+          const result = await client.query(
+            `SELECT d.payment_id FROM ${table} d WHERE ${filter}`,
+            [this.identity]
+          );
+          if (repair && result.rows.length > 0) {
+            await client.query(`DELETE FROM ${table} d WHERE ${filter}`, [
+              this.identity,
+            ]);
+          }
+          for (const row of result.rows) {
+            issues.push({
+              kind: "orphanedRecord",
+              table,
+              recordId: row.payment_id,
+              description: `Payment ${row.payment_id} doesn't exist`,
+              repaired: repair,
+            });
+          }
+        }
+        return issues;
+      });
+    } catch (error) {
+      if (error instanceof StorageError) throw error;
+      throw new StorageError(
+        `Failed to check integrity: ${error.message}`,
+        error
+      );
+    }
+  }
 }
 
 /**
@@ -518,6 +518,38 @@ class PostgresMigrationManager {
              ON brz_cross_chain_swaps(user_id, provider, is_terminal)`,
         ],
       },
+      {
+        name: "Add counterparty indexes",
+        sql: [
+          `CREATE INDEX IF NOT EXISTS brz_idx_payment_details_lightning_user_destination_pubkey
+             ON brz_payment_details_lightning(user_id, destination_pubkey)`,
+          `CREATE INDEX IF NOT EXISTS brz_idx_payment_metadata_user_ln_address
+             ON brz_payment_metadata(user_id, (lower(lnurl_pay_info->>'ln_address')))`,
+        ],
+      },
+      {
+        name: "Add imported flag to payments",
+        sql: [
+          `ALTER TABLE brz_payments ADD COLUMN IF NOT EXISTS imported BOOLEAN NOT NULL DEFAULT FALSE`,
+        ],
+      },
+      {
+        name: "Add group id to payment metadata",
+        sql: [
+          `ALTER TABLE brz_payment_metadata ADD COLUMN IF NOT EXISTS group_id TEXT`,
+          `CREATE INDEX IF NOT EXISTS brz_idx_payment_metadata_user_group
+             ON brz_payment_metadata(user_id, group_id)`,
+        ],
+      },
+      {
+        name: "Add dispute to payment metadata",
+        sql: [
+          `ALTER TABLE brz_payment_metadata ADD COLUMN IF NOT EXISTS dispute JSONB`,
+          `ALTER TABLE brz_payment_metadata ADD COLUMN IF NOT EXISTS dispute_status TEXT`,
+          `CREATE INDEX IF NOT EXISTS brz_idx_payment_metadata_user_dispute_status
+             ON brz_payment_metadata(user_id, dispute_status)`,
+        ],
+      },
     ];
   }
 }
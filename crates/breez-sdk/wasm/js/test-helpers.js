@@ -1,9 +1,10 @@
 /**
- * Test helpers for IndexedDB storage tests
+ * Test helpers for the IndexedDB and OPFS storage tests
  * This file is ONLY used by wasm tests, not production code
  */
 
 import { MigrationManager, StorageError } from "./web-storage/index.js";
+import { createOpfsStorage } from "./web-storage/opfs.js";
 
 /**
  * Opens an IndexedDB database at a specific version, running real migrations
@@ -174,3 +175,27 @@ export async function createOldV10Database(dbName) {
     tx.onerror = () => { db.close(); reject(new Error(`Failed to create old v10 database: ${tx.error?.message}`)); };
   });
 }
+
+// The OPFS SAH pool can only be held by one worker at a time
+let testOpfsStorage = null;
+
+/**
+ * Creates an OPFS storage whose worker bundle and SQLite-wasm binary are
+ * loaded from memory, as the test server doesn't serve them. The storage
+ * created by the previous call is closed.
+ */
+export async function createTestOpfsStorage(dbName, workerSource, sqliteWasm) {
+  testOpfsStorage?.close();
+  testOpfsStorage = null;
+  const workerUrl = URL.createObjectURL(
+    new Blob([workerSource], { type: "text/javascript" })
+  );
+  const sqliteWasmUrl = URL.createObjectURL(
+    new Blob([sqliteWasm], { type: "application/wasm" })
+  );
+  testOpfsStorage = await createOpfsStorage(dbName, null, {
+    workerUrl,
+    sqliteWasmUrl,
+  });
+  return testOpfsStorage;
+}
@@ -860,10 +860,19 @@ class IndexedDBStorage {
       // Determine sort order - "prev" for descending (default), "next" for ascending
       const cursorDirection = request.sortAscending ? "next" : "prev";
 
-      // Use cursor to iterate through payments ordered by timestamp
+      // Start at the cursor's timestamp when resuming after a cursor
+      const after = request.after;
+      let range = null;
+      if (after) {
+        range = request.sortAscending
+          ? IDBKeyRange.lowerBound(after.timestamp)
+          : IDBKeyRange.upperBound(after.timestamp);
+      }
+
+      // Use cursor to iterate through payments ordered by timestamp, then id
       const cursorRequest = paymentStore
         .index("timestamp")
-        .openCursor(null, cursorDirection);
+        .openCursor(range, cursorDirection);
 
       cursorRequest.onsuccess = (event) => {
         const cursor = event.target.result;
@@ -881,6 +890,16 @@ class IndexedDBStorage {
           return;
         }
 
+        // Skip the cursor's payment and those sharing its timestamp before it
+        if (
+          after &&
+          payment.timestamp === after.timestamp &&
+          (request.sortAscending ? payment.id <= after.id : payment.id >= after.id)
+        ) {
+          cursor.continue();
+          return;
+        }
+
         if (skipped < actualOffset) {
           skipped++;
           cursor.continue();
@@ -1365,6 +1384,8 @@ class IndexedDBStorage {
             ? JSON.stringify(metadata.conversionInfo)
             : existing.conversionInfo ?? null,
           conversionStatus: metadata.conversionStatus ?? existing.conversionStatus ?? null,
+          groupId: metadata.groupId ?? existing.groupId ?? null,
+          dispute: metadata.dispute ?? existing.dispute ?? null,
         };
 
         const putRequest = store.put(metadataToStore);
@@ -2302,6 +2323,92 @@ class IndexedDBStorage {
     });
   }
 
+  // ===== Integrity Operations =====
+
+  // Payment details are stored within the payments, so there are no orphaned
+  // records, and IndexedDB has no corruption check.
+  async checkIntegrity(repair) {
+    if (!this.db) {
+      throw new StorageError("Database not initialized");
+    }
+
+    const isValidJson = (value) => {
+      if (value === null || value === undefined) {
+        return true;
+      }
+      try {
+        JSON.parse(value);
+        return true;
+      } catch {
+        return false;
+      }
+    };
+    const invalidJson = (table, recordId, column, repaired) => ({
+      kind: "invalidJson",
+      table,
+      recordId,
+      description: `Invalid JSON in ${column} of ${recordId}`,
+      repaired,
+    });
+
+    return new Promise((resolve, reject) => {
+      const transaction = this.db.transaction(
+        ["payments", "payment_metadata"],
+        repair ? "readwrite" : "readonly"
+      );
+      const issues = [];
+
+      const paymentsRequest = transaction.objectStore("payments").openCursor();
+      paymentsRequest.onsuccess = (event) => {
+        const cursor = event.target.result;
+        if (!cursor) {
+          return;
+        }
+        for (const column of ["details", "method"]) {
+          if (!isValidJson(cursor.value[column])) {
+            issues.push(invalidJson("payments", cursor.value.id, column, false));
+          }
+        }
+        cursor.continue();
+      };
+
+      const metadataRequest = transaction
+        .objectStore("payment_metadata")
+        .openCursor();
+      metadataRequest.onsuccess = (event) => {
+        const cursor = event.target.result;
+        if (!cursor) {
+          return;
+        }
+        const record = { ...cursor.value };
+        let changed = false;
+        for (const column of ["lnurlPayInfo", "lnurlWithdrawInfo", "conversionInfo"]) {
+          if (!isValidJson(record[column])) {
+            issues.push(
+              invalidJson("payment_metadata", record.paymentId, column, repair)
+            );
+            record[column] = null;
+            changed = true;
+          }
+        }
+        if (repair && changed) {
+          cursor.update(record);
+        }
+        cursor.continue();
+      };
+
+      transaction.oncomplete = () => resolve(issues);
+      transaction.onerror = () => {
+        reject(
+          new StorageError(
+            `Failed to check integrity: ${transaction.error?.message || "Unknown error"}`,
+            transaction.error
+          )
+        );
+      };
+    });
+  }
+
   // ===== Private Helper Methods =====
 
   _paymentToStore(payment) {
@@ -2338,6 +2445,20 @@ class IndexedDBStorage {
       }
     }
 
+    // Filter by group
+    if (request.groupIdFilter && request.groupIdFilter.length > 0) {
+      if (!request.groupIdFilter.includes(payment.groupId)) {
+        return false;
+      }
+    }
+
+    // Filter by dispute status
+    if (request.disputeStatusFilter && request.disputeStatusFilter.length > 0) {
+      if (!request.disputeStatusFilter.includes(payment.dispute?.status)) {
+        return false;
+      }
+    }
+
     // Filter by timestamp range
     if (request.fromTimestamp !== null && request.fromTimestamp !== undefined) {
       if (payment.timestamp < request.fromTimestamp) {
@@ -2505,9 +2626,79 @@ class IndexedDBStorage {
       }
     }
 
+    // Filter by counterparty
+    if (request.counterpartyFilter) {
+      const counterpartyFilter = request.counterpartyFilter;
+      let details = null;
+      if (payment.details && typeof payment.details === "string") {
+        try {
+          details = JSON.parse(payment.details);
+        } catch (e) {
+          details = null;
+        }
+      } else {
+        details = payment.details;
+      }
+
+      if (!details) {
+        return false;
+      }
+
+      if (
+        counterpartyFilter.type === "lightningDestination" &&
+        details.destinationPubkey !== counterpartyFilter.destinationPubkey
+      ) {
+        return false;
+      }
+
+      if (
+        counterpartyFilter.type === "lightningAddress" &&
+        details.lnurlPayInfo?.lnAddress?.toLowerCase() !==
+          counterpartyFilter.address.toLowerCase()
+      ) {
+        return false;
+      }
+    }
+
+    // Filter by free text
+    if (request.search) {
+      const searchText = this._paymentSearchText(payment);
+      if (!searchText.includes(request.search.toLowerCase())) {
+        return false;
+      }
+    }
+
     return true;
   }
 
+  // The lowercased text a payment is searched by with the `search` filter
+  _paymentSearchText(payment) {
+    let details = payment.details;
+    if (details && typeof details === "string") {
+      try {
+        details = JSON.parse(details);
+      } catch (e) {
+        details = null;
+      }
+    }
+
+    return [
+      payment.id,
+      details?.txId,
+      details?.invoice,
+      details?.description,
+      details?.lnurlPayInfo?.lnAddress,
+      details?.lnurlPayInfo?.comment,
+      details?.lnurlReceiveMetadata?.senderComment,
+      details?.txHash,
+      details?.invoiceDetails?.invoice,
+      details?.invoiceDetails?.description,
+    ]
+      .filter(Boolean)
+      .join(" ")
+      .toLowerCase();
+  }
+
   _mergePaymentMetadata(payment, metadata) {
     let details = null;
     if (payment.details) {
@@ -2599,6 +2790,9 @@ class IndexedDBStorage {
       conversionDetails: metadata?.conversionStatus
         ? { status: metadata.conversionStatus, from: null, to: null }
         : null,
+      imported: payment.imported ?? false,
+      groupId: metadata?.groupId ?? null,
+      dispute: metadata?.dispute ?? null,
     };
   }
 
@@ -0,0 +1,71 @@
+/**
+ * Web Worker hosting the OPFS-backed SQLite storage for Breez SDK.
+ *
+ * The Origin Private File System can only be accessed synchronously from a
+ * worker, so the database lives here and the main thread talks to it through
+ * `opfs.js`. The storage logic is the Node.js SQLite storage, run on top of a
+ * minimal `better-sqlite3` compatible adapter over SQLite-wasm.
+ *
+ * This file is the entry point of `opfs-worker.bundle.js`, built with
+ * `npm run build`, which bundles the SQL layer and SQLite-wasm into a single
+ * module.
+ */
+
+import sqlite3InitModule from "@sqlite.org/sqlite-wasm";
+import Database from "./sqlite-adapter.cjs";
+import { SqliteStorage } from "../node-storage/index.cjs";
+
+const VFS_NAME = "breez-sdk-spark";
+
+let storage = null;
+
+const log = (level, line) => {
+  self.postMessage({ log: { level, line } });
+};
+
+// ===== Message handling =====
+
+const logger = {
+  log: (entry) => log(entry.level, entry.line),
+};
+
+/**
+ * Opens the database. `sqliteWasmUrl` overrides the location of
+ * `sqlite3.wasm`, which is otherwise loaded from next to the bundle.
+ */
+const open = async (dbPath, sqliteWasmUrl = null) => {
+  if (storage) {
+    return { created: false };
+  }
+  const sqlite3 = await sqlite3InitModule({
+    print: (line) => log("debug", line),
+    printErr: (line) => log("error", line),
+    ...(sqliteWasmUrl && { locateFile: () => sqliteWasmUrl }),
+  });
+  const poolUtil = await sqlite3.installOpfsSAHPoolVfs({ name: VFS_NAME });
+  Database.usePool(poolUtil);
+  // Mirrors the `storage.sql` file the Node.js storage creates in its data dir
+  const path = `/${dbPath.replace(/^\.?\/+/, "")}/storage.sql`;
+  const created = !poolUtil.getFileNames().includes(path);
+  storage = new SqliteStorage(path, logger).initialize();
+  return { created };
+};
+
+self.onmessage = async (event) => {
+  const { id, method, args } = event.data;
+  try {
+    let result;
+    if (method === "open") {
+      result = await open(...args);
+    } else if (!storage) {
+      throw new Error("Database not initialized");
+    } else if (typeof storage[method] !== "function" || method.startsWith("_")) {
+      throw new Error(`Unknown storage method '${method}'`);
+    } else {
+      result = await storage[method](...args);
+    }
+    self.postMessage({ id, result });
+  } catch (error) {
+    self.postMessage({ id, error: { message: error?.message || String(error) } });
+  }
+};
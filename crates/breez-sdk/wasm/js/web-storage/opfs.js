@@ -0,0 +1,320 @@
+/**
+ * OPFS-backed SQLite storage for Breez SDK in the browser.
+ *
+ * The database runs in a dedicated worker (`opfs-worker.bundle.js`, built
+ * from `opfs-worker.js`) using SQLite-wasm and the Origin Private File System. This module forwards the
+ * storage calls to the worker and, on first use, migrates the data of the
+ * IndexedDB storage with the same name.
+ */
+
+import { IndexedDBStorage, StorageError } from "./index.js";
+
+// Settings key marking that the IndexedDB data was migrated
+const INDEXEDDB_MIGRATION_KEY = "opfs_indexeddb_migration";
+
+const STORAGE_METHODS = [
+  "getCachedItem",
+  "setCachedItem",
+  "deleteCachedItem",
+  "listPayments",
+  "applyPaymentUpdate",
+  "insertPaymentMetadata",
+  "getPaymentById",
+  "getPaymentByInvoice",
+  "getPaymentsByParentIds",
+  "addDeposit",
+  "deleteDeposit",
+  "listDeposits",
+  "updateDeposit",
+  "setLnurlMetadata",
+  "listContacts",
+  "getContact",
+  "insertContact",
+  "deleteContact",
+  "setCrossChainSwap",
+  "getCrossChainSwap",
+  "listActiveCrossChainSwaps",
+  "syncAddOutgoingChange",
+  "syncCompleteOutgoingSync",
+  "syncGetPendingOutgoingChanges",
+  "syncGetLastRevision",
+  "syncInsertIncomingRecords",
+  "syncDeleteIncomingRecord",
+  "syncGetIncomingRecords",
+  "syncGetLatestOutgoingChange",
+  "syncUpdateRecordFromIncoming",
+  "checkIntegrity",
+];
+
+class OpfsStorage {
+  /**
+   * `options.workerUrl` and `options.sqliteWasmUrl` override the locations of
+   * the worker bundle and `sqlite3.wasm`, which are otherwise loaded from next
+   * to this module.
+   */
+  constructor(dbName = "BreezSdkSpark", logger = null, options = {}) {
+    this.dbName = dbName;
+    this.logger = logger;
+    this.workerUrl =
+      options.workerUrl ?? new URL("./opfs-worker.bundle.js", import.meta.url);
+    this.sqliteWasmUrl = options.sqliteWasmUrl ?? null;
+    this.worker = null;
+    this.pending = new Map();
+    this.nextRequestId = 0;
+  }
+
+  /**
+   * Initialize the storage - must be called before using other methods
+   */
+  async initialize() {
+    if (this.worker) {
+      return this;
+    }
+
+    if (
+      typeof Worker === "undefined" ||
+      typeof navigator === "undefined" ||
+      !navigator.storage?.getDirectory
+    ) {
+      throw new StorageError("OPFS is not available in this environment");
+    }
+
+    this.worker = new Worker(this.workerUrl, { type: "module" });
+    this.worker.onmessage = (event) => this._handleMessage(event.data);
+    this.worker.onerror = (event) => {
+      this._rejectAll(
+        new StorageError(`OPFS worker failed: ${event.message || "Unknown error"}`)
+      );
+    };
+
+    try {
+      await this._call("open", [this.dbName, this.sqliteWasmUrl]);
+      if ((await this.getCachedItem(INDEXEDDB_MIGRATION_KEY)) == null) {
+        await migrateFromIndexedDB(this.dbName, this, this.logger);
+        await this.setCachedItem(INDEXEDDB_MIGRATION_KEY, "done");
+      }
+    } catch (error) {
+      this.close();
+      throw error instanceof StorageError
+        ? error
+        : new StorageError(
+          `Failed to initialize OPFS storage '${this.dbName}': ${error.message}`,
+          error
+        );
+    }
+    return this;
+  }
+
+  /**
+   * Close the database by terminating its worker, which releases the OPFS
+   * file handles
+   */
+  close() {
+    if (this.worker) {
+      this.worker.terminate();
+      this.worker = null;
+      this._rejectAll(new StorageError("Database closed"));
+    }
+  }
+
+  _call(method, args) {
+    if (!this.worker) {
+      return Promise.reject(new StorageError("Database not initialized"));
+    }
+    const id = this.nextRequestId++;
+    return new Promise((resolve, reject) => {
+      this.pending.set(id, { resolve, reject });
+      this.worker.postMessage({ id, method, args });
+    });
+  }
+
+  _handleMessage(data) {
+    if (data.log) {
+      this._log(data.log.level, data.log.line);
+      return;
+    }
+    const request = this.pending.get(data.id);
+    if (!request) {
+      return;
+    }
+    this.pending.delete(data.id);
+    if (data.error) {
+      request.reject(new StorageError(data.error.message));
+    } else {
+      request.resolve(data.result);
+    }
+  }
+
+  _rejectAll(error) {
+    for (const { reject } of this.pending.values()) {
+      reject(error);
+    }
+    this.pending.clear();
+  }
+
+  _log(level, line) {
+    if (this.logger && typeof this.logger.log === "function") {
+      this.logger.log({ line, level });
+    } else if (level === "error") {
+      console.error(`[OpfsStorage] ${line}`);
+    }
+  }
+}
+
+for (const method of STORAGE_METHODS) {
+  OpfsStorage.prototype[method] = function (...args) {
+    return this._call(method, args);
+  };
+}
+
+// ===== IndexedDB migration =====
+
+const getAllRecords = (db, storeName) => {
+  if (!db.objectStoreNames.contains(storeName)) {
+    return Promise.resolve([]);
+  }
+  return new Promise((resolve, reject) => {
+    const request = db
+      .transaction(storeName, "readonly")
+      .objectStore(storeName)
+      .getAll();
+    request.onsuccess = () => resolve(request.result || []);
+    request.onerror = () => reject(request.error);
+  });
+};
+
+const parseJson = (value) =>
+  typeof value === "string" ? JSON.parse(value) : value ?? null;
+
+const indexedDBExists = async (dbName) => {
+  if (typeof indexedDB === "undefined") {
+    return false;
+  }
+  // Without `databases()` the database is opened anyway, which creates an
+  // empty one if it didn't exist
+  if (typeof indexedDB.databases !== "function") {
+    return true;
+  }
+  const databases = await indexedDB.databases();
+  return databases.some((db) => db.name === dbName);
+};
+
+/**
+ * Copies the data of the IndexedDB storage named `dbName` into `target`,
+ * including the real-time sync state, so changes not pushed to the sync
+ * server yet aren't lost. Writes are upserts, except for the pending outgoing
+ * changes, which are only copied if `target` has none, so an interrupted
+ * migration can be run again.
+ */
+async function migrateFromIndexedDB(dbName, target, logger = null) {
+  if (!(await indexedDBExists(dbName))) {
+    return;
+  }
+
+  const source = new IndexedDBStorage(dbName, logger);
+  await source.initialize();
+  try {
+    const db = source.db;
+
+    for (const { key, value } of await getAllRecords(db, "settings")) {
+      await target.setCachedItem(key, value);
+    }
+
+    const payments = await getAllRecords(db, "payments");
+    for (const { id } of payments) {
+      await target.applyPaymentUpdate(await source.getPaymentById(id));
+    }
+
+    for (const metadata of await getAllRecords(db, "payment_metadata")) {
+      await target.insertPaymentMetadata(metadata.paymentId, {
+        parentPaymentId: metadata.parentPaymentId ?? null,
+        lnurlPayInfo: parseJson(metadata.lnurlPayInfo),
+        lnurlWithdrawInfo: parseJson(metadata.lnurlWithdrawInfo),
+        lnurlDescription: metadata.lnurlDescription ?? null,
+        conversionInfo: parseJson(metadata.conversionInfo),
+        conversionStatus: metadata.conversionStatus ?? null,
+        groupId: metadata.groupId ?? null,
+        dispute: parseJson(metadata.dispute),
+      });
+    }
+
+    const lnurlMetadata = await getAllRecords(db, "lnurl_receive_metadata");
+    if (lnurlMetadata.length > 0) {
+      await target.setLnurlMetadata(lnurlMetadata);
+    }
+
+    for (const deposit of await source.listDeposits()) {
+      await target.addDeposit(
+        deposit.txid,
+        deposit.vout,
+        deposit.amountSats,
+        deposit.isMature
+      );
+      if (deposit.claimError) {
+        await target.updateDeposit(deposit.txid, deposit.vout, {
+          type: "claimError",
+          error: deposit.claimError,
+        });
+      } else if (deposit.refundTx) {
+        await target.updateDeposit(deposit.txid, deposit.vout, {
+          type: "refund",
+          refundTx: deposit.refundTx,
+          refundTxid: deposit.refundTxId,
+        });
+      }
+    }
+
+    for (const contact of await getAllRecords(db, "contacts")) {
+      await target.insertContact(contact);
+    }
+
+    for (const swap of await getAllRecords(db, "cross_chain_swaps")) {
+      await target.setCrossChainSwap(swap);
+    }
+
+    for (const { record } of await getAllRecords(db, "sync_state")) {
+      await target.syncUpdateRecordFromIncoming(record);
+    }
+
+    const incoming = await getAllRecords(db, "sync_incoming");
+    if (incoming.length > 0) {
+      await target.syncInsertIncomingRecords(
+        incoming.map(({ record }) => record)
+      );
+    }
+
+    // Outgoing changes get new local revisions in `target`, in the same order
+    const outgoing = await getAllRecords(db, "sync_outgoing");
+    if ((await target.syncGetPendingOutgoingChanges(1)).length === 0) {
+      outgoing.sort((a, b) => a.revision - b.revision);
+      for (const { record } of outgoing) {
+        await target.syncAddOutgoingChange({
+          id: record.id,
+          schemaVersion: record.schemaVersion,
+          updatedFields: record.updatedFields,
+        });
+      }
+    }
+
+    if (logger && typeof logger.log === "function") {
+      logger.log({
+        line: `Migrated ${payments.length} payments and ${outgoing.length} pending sync changes from IndexedDB '${dbName}' to OPFS`,
+        level: "info",
+      });
+    }
+  } finally {
+    source.close();
+  }
+}
+
+export async function createOpfsStorage(
+  dbName = "BreezSdkSpark",
+  logger = null,
+  options = {}
+) {
+  const storage = new OpfsStorage(dbName, logger, options);
+  await storage.initialize();
+  return storage;
+}
+
+export { OpfsStorage, migrateFromIndexedDB };
@@ -0,0 +1,143 @@
+/**
+ * Minimal `better-sqlite3` compatible adapter over SQLite-wasm, used by the
+ * OPFS worker to run the Node.js SQLite storage. The worker bundle resolves
+ * `better-sqlite3` to this module.
+ */
+
+let poolUtil = null;
+
+const toBindable = (value) => {
+  if (value === undefined) {
+    return null;
+  }
+  if (typeof value === "boolean") {
+    return value ? 1 : 0;
+  }
+  return value;
+};
+
+const isPlainObject = (value) =>
+  value !== null &&
+  typeof value === "object" &&
+  Object.getPrototypeOf(value) === Object.prototype;
+
+/**
+ * Converts better-sqlite3 style bind arguments: positional values, an array of
+ * values, or an object of `@name` parameters. Keys of the object that are not
+ * used by the statement are dropped, as better-sqlite3 does.
+ */
+const bindParams = (sql, params) => {
+  if (params.length === 1 && isPlainObject(params[0])) {
+    const bind = {};
+    for (const [key, value] of Object.entries(params[0])) {
+      if (new RegExp(`@${key}\\b`).test(sql)) {
+        bind[`@${key}`] = toBindable(value);
+      }
+    }
+    return bind;
+  }
+  const values = params.flat();
+  return values.length > 0 ? values.map(toBindable) : undefined;
+};
+
+class Statement {
+  constructor(db, sql) {
+    this.db = db;
+    this.sql = sql;
+    this.plucked = false;
+  }
+
+  pluck(toggle = true) {
+    this.plucked = toggle;
+    return this;
+  }
+
+  run(...params) {
+    this.db.exec({ sql: this.sql, bind: bindParams(this.sql, params) });
+    return { changes: this.db.changes() };
+  }
+
+  get(...params) {
+    return this.all(...params)[0];
+  }
+
+  all(...params) {
+    return this.db.exec({
+      sql: this.sql,
+      bind: bindParams(this.sql, params),
+      rowMode: this.plucked ? 0 : "object",
+      returnValue: "resultRows",
+    });
+  }
+}
+
+class Database {
+  constructor(path) {
+    if (!poolUtil) {
+      throw new Error("The OPFS SAH pool VFS is not installed");
+    }
+    this.db = new poolUtil.OpfsSAHPoolDb(path);
+    // better-sqlite3 enforces foreign keys by default
+    this.db.exec("PRAGMA foreign_keys = ON");
+    this.savepoints = 0;
+  }
+
+  prepare(sql) {
+    return new Statement(this.db, sql);
+  }
+
+  exec(sql) {
+    this.db.exec(sql);
+    return this;
+  }
+
+  pragma(source) {
+    return this.db.exec({
+      sql: `PRAGMA ${source}`,
+      rowMode: "object",
+      returnValue: "resultRows",
+    });
+  }
+
+  /**
+   * Wraps `fn` in a transaction. Savepoints are used so transactions can be
+   * nested; the database has a single connection, so the deferred, immediate
+   * and exclusive variants behave the same.
+   */
+  transaction(fn) {
+    const run = (...args) => {
+      const name = `breez_tx_${this.savepoints}`;
+      this.savepoints += 1;
+      this.db.exec(`SAVEPOINT ${name}`);
+      try {
+        const result = fn(...args);
+        this.db.exec(`RELEASE ${name}`);
+        return result;
+      } catch (error) {
+        this.db.exec(`ROLLBACK TO ${name}`);
+        this.db.exec(`RELEASE ${name}`);
+        throw error;
+      } finally {
+        this.savepoints -= 1;
+      }
+    };
+    run.deferred = run;
+    run.immediate = run;
+    run.exclusive = run;
+    return run;
+  }
+
+  close() {
+    this.db.close();
+  }
+}
+
+/**
+ * Sets the OPFS SAH pool VFS the databases are opened in. Must be called
+ * before any database is opened.
+ */
+Database.usePool = (pool) => {
+  poolUtil = pool;
+};
+
+module.exports = Database;
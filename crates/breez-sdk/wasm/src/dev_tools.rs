@@ -0,0 +1,41 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    error::WasmResult,
+    models::dev_tools::{
+        FaucetFundRequest, FaucetFundResponse, MineBlocksRequest, MineBlocksResponse,
+        SimulateIncomingPaymentRequest, SimulateIncomingPaymentResponse,
+    },
+};
+
+#[wasm_bindgen]
+pub struct DevTools {
+    pub(crate) dev_tools: Rc<breez_sdk_spark::DevTools>,
+}
+
+#[wasm_bindgen]
+impl DevTools {
+    #[wasm_bindgen(js_name = "faucetFund")]
+    pub async fn faucet_fund(&self, request: FaucetFundRequest) -> WasmResult<FaucetFundResponse> {
+        Ok(self.dev_tools.faucet_fund(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "mineBlocks")]
+    pub async fn mine_blocks(&self, request: MineBlocksRequest) -> WasmResult<MineBlocksResponse> {
+        Ok(self.dev_tools.mine_blocks(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "simulateIncomingPayment")]
+    pub async fn simulate_incoming_payment(
+        &self,
+        request: SimulateIncomingPaymentRequest,
+    ) -> WasmResult<SimulateIncomingPaymentResponse> {
+        Ok(self
+            .dev_tools
+            .simulate_incoming_payment(request.into())
+            .await?
+            .into())
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-use breez_sdk_spark::{ParseError, SdkError, SignerError, passkey::PasskeyError};
+#[cfg(feature = "passkey")]
+use breez_sdk_spark::passkey::PasskeyError;
+use breez_sdk_spark::{ParseError, SdkError, SignerError, StorageError};
 use tracing_subscriber::util::TryInitError;
 use wasm_bindgen::{JsError, JsValue};
 
@@ -45,4 +47,7 @@ macro_rules! wasm_error_wrapper {
     }
 }
 
-wasm_error_wrapper!(SdkError, ParseError, PasskeyError, SignerError);
+wasm_error_wrapper!(SdkError, ParseError, SignerError, StorageError);
+
+#[cfg(feature = "passkey")]
+wasm_error_wrapper!(PasskeyError);
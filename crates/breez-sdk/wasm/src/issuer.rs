@@ -84,4 +84,17 @@ impl TokenIssuer {
             .await?
             .into())
     }
+
+    #[cfg(feature = "token-announcements")]
+    #[wasm_bindgen(js_name = "publishTokenAnnouncement")]
+    pub async fn publish_token_announcement(
+        &self,
+        request: crate::models::token_announcement::PublishTokenAnnouncementRequest,
+    ) -> WasmResult<crate::models::token_announcement::TokenAnnouncement> {
+        Ok(self
+            .token_issuer
+            .publish_token_announcement(request.into())
+            .await?
+            .into())
+    }
 }
@@ -1,17 +1,23 @@
 mod chain_service;
+#[cfg(feature = "dev-tools")]
+mod dev_tools;
 mod error;
 mod event;
+#[cfg(feature = "issuer")]
 mod issuer;
 mod logger;
 mod models;
+#[cfg(feature = "passkey")]
 mod passkey;
 mod persist;
+mod plugin;
 mod sdk;
 mod sdk_builder;
 mod sdk_context;
 mod signer;
 mod token_store;
 mod tree_store;
+#[cfg(feature = "turnkey")]
 mod turnkey;
 
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -0,0 +1,78 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::{EmergencyExitPackage, error::js_error_to_backup_store_error};
+
+pub struct WasmBackupStore {
+    pub backup_store: BackupStore,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmBackupStore {}
+unsafe impl Sync for WasmBackupStore {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::BackupStore for WasmBackupStore {
+    async fn save(&self, backup: Vec<u8>) -> Result<(), breez_sdk_spark::BackupStoreError> {
+        let promise = self
+            .backup_store
+            .save(backup)
+            .map_err(js_error_to_backup_store_error)?;
+        JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_backup_store_error)?;
+        Ok(())
+    }
+}
+
+pub struct WasmEmergencyExitStore {
+    pub emergency_exit_store: EmergencyExitStore,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmEmergencyExitStore {}
+unsafe impl Sync for WasmEmergencyExitStore {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::EmergencyExitStore for WasmEmergencyExitStore {
+    async fn save(
+        &self,
+        package: breez_sdk_spark::EmergencyExitPackage,
+    ) -> Result<(), breez_sdk_spark::BackupStoreError> {
+        let promise = self
+            .emergency_exit_store
+            .save(package.into())
+            .map_err(js_error_to_backup_store_error)?;
+        JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_backup_store_error)?;
+        Ok(())
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EVENT_INTERFACE: &'static str = r#"export interface BackupStore {
+    save: (backup: Uint8Array) => Promise<void>;
+}
+
+export interface EmergencyExitStore {
+    save: (package: EmergencyExitPackage) => Promise<void>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "BackupStore")]
+    pub type BackupStore;
+
+    #[wasm_bindgen(structural, method, js_name = save, catch)]
+    pub fn save(this: &BackupStore, backup: Vec<u8>) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(typescript_type = "EmergencyExitStore")]
+    pub type EmergencyExitStore;
+
+    #[wasm_bindgen(structural, method, js_name = save, catch)]
+    pub fn save(
+        this: &EmergencyExitStore,
+        package: EmergencyExitPackage,
+    ) -> Result<Promise, JsValue>;
+}
@@ -1,7 +1,7 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
 
-use crate::models::error::js_error_to_chain_service_error;
+use crate::models::{Credentials, error::js_error_to_chain_service_error};
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::TxStatus)]
 pub struct TxStatus {
@@ -43,6 +43,13 @@ pub enum ChainApiType {
     MempoolSpace,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ChainServiceEndpoint)]
+pub struct ChainServiceEndpoint {
+    pub url: String,
+    pub api_type: ChainApiType,
+    pub credentials: Option<Credentials>,
+}
+
 pub struct WasmBitcoinChainService {
     pub inner: BitcoinChainService,
 }
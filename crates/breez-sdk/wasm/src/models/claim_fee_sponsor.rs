@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::{ClaimFeeSponsorRequest, error::js_error_to_claim_fee_sponsor_error};
+
+pub struct WasmClaimFeeSponsor {
+    pub claim_fee_sponsor: ClaimFeeSponsor,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmClaimFeeSponsor {}
+unsafe impl Sync for WasmClaimFeeSponsor {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::ClaimFeeSponsor for WasmClaimFeeSponsor {
+    async fn will_sponsor(&self, request: breez_sdk_spark::ClaimFeeSponsorRequest) -> bool {
+        // A failing sponsor declines the fee
+        let Ok(promise) = self.claim_fee_sponsor.will_sponsor(request.into()) else {
+            return false;
+        };
+        JsFuture::from(promise)
+            .await
+            .is_ok_and(|sponsored| sponsored.as_bool() == Some(true))
+    }
+
+    async fn pay_claim_fee(
+        &self,
+        request: breez_sdk_spark::ClaimFeeSponsorRequest,
+    ) -> Result<String, breez_sdk_spark::ClaimFeeSponsorError> {
+        let promise = self
+            .claim_fee_sponsor
+            .pay_claim_fee(request.into())
+            .map_err(js_error_to_claim_fee_sponsor_error)?;
+        let payment_id = JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_claim_fee_sponsor_error)?;
+        payment_id.as_string().ok_or_else(|| {
+            breez_sdk_spark::ClaimFeeSponsorError::Generic(
+                "payClaimFee must resolve to a payment id".to_string(),
+            )
+        })
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EVENT_INTERFACE: &'static str = r#"export interface ClaimFeeSponsor {
+    willSponsor: (request: ClaimFeeSponsorRequest) => Promise<boolean>;
+    payClaimFee: (request: ClaimFeeSponsorRequest) => Promise<string>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ClaimFeeSponsor")]
+    pub type ClaimFeeSponsor;
+
+    #[wasm_bindgen(structural, method, js_name = willSponsor, catch)]
+    pub fn will_sponsor(
+        this: &ClaimFeeSponsor,
+        request: ClaimFeeSponsorRequest,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(structural, method, js_name = payClaimFee, catch)]
+    pub fn pay_claim_fee(
+        this: &ClaimFeeSponsor,
+        request: ClaimFeeSponsorRequest,
+    ) -> Result<Promise, JsValue>;
+}
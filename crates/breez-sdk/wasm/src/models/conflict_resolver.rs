@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::{SyncConflict, error::js_error_to_conflict_resolver_error};
+
+pub struct WasmConflictResolver {
+    pub conflict_resolver: ConflictResolver,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmConflictResolver {}
+unsafe impl Sync for WasmConflictResolver {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::ConflictResolver for WasmConflictResolver {
+    async fn resolve(
+        &self,
+        conflict: breez_sdk_spark::SyncConflict,
+    ) -> Result<HashMap<String, String>, breez_sdk_spark::ConflictResolverError> {
+        let promise = self
+            .conflict_resolver
+            .resolve(conflict.into())
+            .map_err(js_error_to_conflict_resolver_error)?;
+        let result = JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_conflict_resolver_error)?;
+        serde_wasm_bindgen::from_value(result).map_err(|e| {
+            breez_sdk_spark::ConflictResolverError::Generic(format!("Invalid resolved fields: {e}"))
+        })
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EVENT_INTERFACE: &'static str = r#"export interface ConflictResolver {
+    resolve: (conflict: SyncConflict) => Promise<{ [key: string]: string }>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ConflictResolver")]
+    pub type ConflictResolver;
+
+    #[wasm_bindgen(structural, method, js_name = resolve, catch)]
+    pub fn resolve(this: &ConflictResolver, conflict: SyncConflict) -> Result<Promise, JsValue>;
+}
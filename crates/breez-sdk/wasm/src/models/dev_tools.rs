@@ -0,0 +1,55 @@
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DevToolsConfig)]
+pub struct DevToolsConfig {
+    pub faucet_url: Option<String>,
+    pub faucet_username: Option<String>,
+    pub faucet_password: Option<String>,
+    pub lightning_node: Option<RegtestLightningNode>,
+    pub bitcoind: Option<RegtestBitcoind>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::RegtestLightningNode)]
+pub struct RegtestLightningNode {
+    pub url: String,
+    pub macaroon: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::RegtestBitcoind)]
+pub struct RegtestBitcoind {
+    pub rpc_url: String,
+    pub rpc_username: Option<String>,
+    pub rpc_password: Option<String>,
+    pub mining_address: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::FaucetFundRequest)]
+pub struct FaucetFundRequest {
+    pub amount_sats: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::FaucetFundResponse)]
+pub struct FaucetFundResponse {
+    pub address: String,
+    pub txid: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::MineBlocksRequest)]
+pub struct MineBlocksRequest {
+    pub count: u32,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::MineBlocksResponse)]
+pub struct MineBlocksResponse {
+    pub block_hashes: Vec<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SimulateIncomingPaymentRequest)]
+pub struct SimulateIncomingPaymentRequest {
+    pub amount_sats: u64,
+    pub description: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SimulateIncomingPaymentResponse)]
+pub struct SimulateIncomingPaymentResponse {
+    pub invoice: String,
+    pub preimage: String,
+}
@@ -0,0 +1,43 @@
+use breez_sdk_spark::ServiceConnectivityError;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::error::js_error_to_service_connectivity_error;
+
+pub struct WasmDnsResolver {
+    pub inner: DnsResolver,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmDnsResolver {}
+unsafe impl Sync for WasmDnsResolver {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::DnsResolver for WasmDnsResolver {
+    async fn txt_lookup(&self, dns_name: String) -> Result<Vec<String>, ServiceConnectivityError> {
+        let promise = self
+            .inner
+            .txt_lookup(dns_name)
+            .map_err(js_error_to_service_connectivity_error)?;
+        let future = JsFuture::from(promise);
+        let result = future
+            .await
+            .map_err(js_error_to_service_connectivity_error)?;
+        serde_wasm_bindgen::from_value(result)
+            .map_err(|e| ServiceConnectivityError::Other(e.to_string()))
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EVENT_INTERFACE: &'static str = r#"export interface DnsResolver {
+    txtLookup(dnsName: string): Promise<string[]>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DnsResolver")]
+    pub type DnsResolver;
+
+    #[wasm_bindgen(structural, method, js_name = "txtLookup", catch)]
+    pub fn txt_lookup(this: &DnsResolver, dns_name: String) -> Result<Promise, JsValue>;
+}
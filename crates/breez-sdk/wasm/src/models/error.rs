@@ -19,6 +19,42 @@ pub(crate) fn js_error_to_payment_observer_error(
     breez_sdk_spark::PaymentObserverError::Generic(error_message)
 }
 
+pub(crate) fn js_error_to_travel_rule_provider_error(
+    js_error: JsValue,
+) -> breez_sdk_spark::TravelRuleProviderError {
+    let error_message = js_error
+        .as_string()
+        .unwrap_or_else(|| "Travel rule provider error occurred".to_string());
+    breez_sdk_spark::TravelRuleProviderError::Generic(error_message)
+}
+
+pub(crate) fn js_error_to_backup_store_error(
+    js_error: JsValue,
+) -> breez_sdk_spark::BackupStoreError {
+    let error_message = js_error
+        .as_string()
+        .unwrap_or_else(|| "Backup store error occurred".to_string());
+    breez_sdk_spark::BackupStoreError::Generic(error_message)
+}
+
+pub(crate) fn js_error_to_claim_fee_sponsor_error(
+    js_error: JsValue,
+) -> breez_sdk_spark::ClaimFeeSponsorError {
+    let error_message = js_error
+        .as_string()
+        .unwrap_or_else(|| "Claim fee sponsor error occurred".to_string());
+    breez_sdk_spark::ClaimFeeSponsorError::Generic(error_message)
+}
+
+pub(crate) fn js_error_to_conflict_resolver_error(
+    js_error: JsValue,
+) -> breez_sdk_spark::ConflictResolverError {
+    let error_message = js_error
+        .as_string()
+        .unwrap_or_else(|| "Conflict resolver error occurred".to_string());
+    breez_sdk_spark::ConflictResolverError::Generic(error_message)
+}
+
 pub(crate) fn js_error_to_session_store_error(
     js_error: JsValue,
 ) -> breez_sdk_spark::SessionStoreError {
@@ -28,6 +64,15 @@ pub(crate) fn js_error_to_session_store_error(
     breez_sdk_spark::SessionStoreError::Generic(error_message)
 }
 
+pub(crate) fn js_error_to_secret_store_error(
+    js_error: JsValue,
+) -> breez_sdk_spark::SecretStoreError {
+    let error_message = js_error
+        .as_string()
+        .unwrap_or_else(|| "Secret store error occurred".to_string());
+    breez_sdk_spark::SecretStoreError::Generic(error_message)
+}
+
 pub(crate) fn js_error_to_service_connectivity_error(
     js_error: JsValue,
 ) -> ServiceConnectivityError {
@@ -1,11 +1,24 @@
+pub mod backup_store;
 pub mod chain_service;
+pub mod claim_fee_sponsor;
+pub mod conflict_resolver;
+#[cfg(feature = "dev-tools")]
+pub mod dev_tools;
+pub mod dns_resolver;
 mod error;
 pub mod fiat_service;
+#[cfg(feature = "issuer")]
 pub mod issuer;
+#[cfg(feature = "passkey")]
 pub mod passkey_prf_provider;
+pub mod payment_approver;
 pub mod payment_observer;
 pub mod rest_client;
+pub mod secret_store;
 pub mod session_store;
+#[cfg(feature = "token-announcements")]
+pub mod token_announcement;
+pub mod travel_rule_provider;
 
 use std::collections::HashMap;
 
@@ -74,8 +87,12 @@ pub enum SdkEvent {
     PaymentPending {
         payment: Payment,
     },
+    PaymentWaitingConfirmation {
+        payment: Payment,
+    },
     PaymentFailed {
         payment: Payment,
+        reason: Option<String>,
     },
     AutoOptimization {
         optimization_event: AutoOptimizationEvent,
@@ -86,6 +103,80 @@ pub enum SdkEvent {
     NewDeposits {
         new_deposits: Vec<DepositInfo>,
     },
+    DepositClaimFeeEvaluated {
+        deposit: DepositInfo,
+        required_fee_sats: u64,
+        max_fee_sats: Option<u64>,
+        claimed: bool,
+    },
+    SyncProgress {
+        synced: u64,
+    },
+    TokenBalanceChanged {
+        token_identifier: String,
+        old_balance: u128,
+        new_balance: u128,
+    },
+    PaymentStuck {
+        payment_id: String,
+        recommendation: StuckPaymentRecommendation,
+    },
+    WithdrawConfirmed {
+        txid: String,
+        height: u32,
+    },
+    LightningAddressPaymentReceived {
+        payment: Payment,
+        sender_comment: Option<String>,
+        zap_sender_pubkey: Option<String>,
+        nostr_zap_request: Option<String>,
+    },
+    ChainServiceFailover {
+        from_url: String,
+        to_url: String,
+    },
+    ReceiveStageChanged {
+        payment_hash: String,
+        update: ReceiveStageUpdate,
+    },
+    QueuedPaymentResolved {
+        queued_payment_id: String,
+        outcome: QueuedPaymentOutcome,
+    },
+    HistoryBackfillProgress {
+        synced: u64,
+        completed: bool,
+    },
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SdkEventKind)]
+pub enum SdkEventKind {
+    Synced,
+    UnclaimedDeposits,
+    ClaimedDeposits,
+    PaymentSucceeded,
+    PaymentPending,
+    PaymentWaitingConfirmation,
+    PaymentFailed,
+    AutoOptimization,
+    LightningAddressChanged,
+    NewDeposits,
+    DepositClaimFeeEvaluated,
+    SyncProgress,
+    TokenBalanceChanged,
+    PaymentStuck,
+    WithdrawConfirmed,
+    LightningAddressPaymentReceived,
+    ChainServiceFailover,
+    ReceiveStageChanged,
+    QueuedPaymentResolved,
+    HistoryBackfillProgress,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::EventFilter)]
+pub struct EventFilter {
+    pub kinds: Vec<SdkEventKind>,
+    pub payment_id: Option<String>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::AutoOptimizationEvent)]
@@ -105,6 +196,13 @@ pub enum AutoOptimizationEvent {
     Skipped,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::QueuedPaymentOutcome)]
+pub enum QueuedPaymentOutcome {
+    Sent { payment: Payment },
+    Failed { error: String },
+    Expired,
+}
+
 #[derive(Clone)]
 #[macros::extern_wasm_bindgen(breez_sdk_spark::Seed)]
 pub enum Seed {
@@ -126,6 +224,24 @@ pub struct ConnectRequest {
     pub storage_dir: String,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DiscoverAccountsRequest)]
+pub struct DiscoverAccountsRequest {
+    pub config: Config,
+    pub seed: Seed,
+    pub gap_limit: Option<u32>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DiscoverAccountsResponse)]
+pub struct DiscoverAccountsResponse {
+    pub accounts: Vec<AccountActivity>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::AccountActivity)]
+pub struct AccountActivity {
+    pub account_number: u32,
+    pub identity_public_key: String,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::DepositInfo)]
 pub struct DepositInfo {
     pub txid: String,
@@ -135,6 +251,7 @@ pub struct DepositInfo {
     pub refund_tx: Option<String>,
     pub refund_tx_id: Option<String>,
     pub claim_error: Option<DepositClaimError>,
+    pub claim_max_fee: Option<MaxFee>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ClaimDepositRequest)]
@@ -144,6 +261,13 @@ pub struct ClaimDepositRequest {
     pub max_fee: Option<MaxFee>,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SetDepositClaimOverrideRequest)]
+pub struct SetDepositClaimOverrideRequest {
+    pub txid: String,
+    pub vout: u32,
+    pub max_fee: Option<MaxFee>,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ClaimDepositResponse)]
 pub struct ClaimDepositResponse {
     pub payment: Payment,
@@ -163,6 +287,20 @@ pub struct RefundDepositResponse {
     pub tx_hex: String,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::EstimateClaimDepositFeeResponse)]
+pub struct EstimateClaimDepositFeeResponse {
+    pub fee_sats: u64,
+    pub fee_rate_sat_per_vbyte: u64,
+    pub max_fee_sats: Option<u64>,
+    pub within_max_fee: bool,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::EstimateRefundDepositFeeResponse)]
+pub struct EstimateRefundDepositFeeResponse {
+    pub fee_sats: u64,
+    pub refund_amount_sats: u64,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ListUnclaimedDepositsRequest)]
 pub struct ListUnclaimedDepositsRequest {}
 
@@ -184,11 +322,21 @@ pub enum DepositClaimError {
         tx: String,
         vout: u32,
     },
+    NotOurDeposit {
+        tx: String,
+        vout: u32,
+    },
     Generic {
         message: String,
     },
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DecodedPaymentRequest)]
+pub enum DecodedPaymentRequest {
+    Bolt11Invoice(Bolt11InvoiceDetails),
+    SparkInvoice(SparkInvoiceDetails),
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::InputType)]
 pub enum InputType {
     BitcoinAddress(BitcoinAddressDetails),
@@ -447,6 +595,45 @@ pub struct Payment {
     pub method: PaymentMethod,
     pub details: Option<PaymentDetails>,
     pub conversion_details: Option<ConversionDetails>,
+    pub imported: bool,
+    #[serde(default)]
+    pub display_amount: DisplayAmount,
+    pub group_id: Option<String>,
+    pub dispute: Option<PaymentDispute>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentDispute)]
+pub struct PaymentDispute {
+    pub status: DisputeStatus,
+    pub note: Option<String>,
+    pub disputed_at: u64,
+    pub resolved_at: Option<u64>,
+    pub updated_at: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DisputeStatus)]
+pub enum DisputeStatus {
+    Disputed,
+    Resolved,
+}
+
+#[derive(Default)]
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DisplayAmount)]
+pub struct DisplayAmount {
+    pub value: u128,
+    pub asset: DisplayAsset,
+}
+
+#[derive(Default)]
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DisplayAsset)]
+pub enum DisplayAsset {
+    #[default]
+    Bitcoin,
+    Token {
+        identifier: String,
+        ticker: String,
+        decimals: u32,
+    },
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ConversionDetails)]
@@ -656,12 +843,14 @@ pub struct Config {
     pub api_key: Option<String>,
     pub network: Network,
     pub sync_interval_secs: u32,
+    pub sync_trigger_debounce_secs: u32,
     pub max_deposit_claim_fee: Option<MaxFee>,
     pub lnurl_domain: Option<String>,
     pub prefer_spark_over_lightning: bool,
     pub external_input_parsers: Option<Vec<ExternalInputParser>>,
     pub use_default_external_input_parsers: bool,
     pub real_time_sync_server_url: Option<String>,
+    pub event_stream_websocket: bool,
     pub private_enabled_default: bool,
     pub leaf_optimization_config: LeafOptimizationConfig,
     pub token_optimization_config: TokenOptimizationConfig,
@@ -672,9 +861,18 @@ pub struct Config {
     /// Default is 4. Increase for server environments with high incoming
     /// payment volume to improve throughput.
     pub max_concurrent_claims: u32,
+    pub claim_batch_size: u32,
+    pub claim_batch_interval_ms: u32,
     pub spark_config: Option<SparkConfig>,
     pub background_tasks_enabled: bool,
     pub cross_chain_config: Option<CrossChainConfig>,
+    pub invoice_description_template: Option<InvoiceDescriptionTemplate>,
+    pub webhook_config: Option<WebhookConfig>,
+    pub deposit_claim_policy: Option<DepositClaimPolicy>,
+    pub service_status_url: Option<String>,
+    pub historical_rates_url: Option<String>,
+    pub low_data_mode: bool,
+    pub sync_mode: SyncMode,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::CrossChainConfig)]
@@ -683,6 +881,59 @@ pub struct CrossChainConfig {
     pub default_target_overpay_bps: Option<u32>,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::InvoiceDescriptionTemplate)]
+pub struct InvoiceDescriptionTemplate {
+    pub template: String,
+    pub app_name: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::WebhookConfig)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub secret: String,
+    pub max_attempts: Option<u32>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::MaintenanceWindow)]
+pub struct MaintenanceWindow {
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub message: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetServiceStatusResponse)]
+pub struct GetServiceStatusResponse {
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    pub active_maintenance: Option<MaintenanceWindow>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ConfigPatch)]
+pub struct ConfigPatch {
+    pub network: Option<Network>,
+    pub sync_interval_secs: Option<u32>,
+    pub sync_trigger_debounce_secs: Option<u32>,
+    pub max_deposit_claim_fee: Option<MaxFee>,
+    pub prefer_spark_over_lightning: Option<bool>,
+    pub invoice_description_template: Option<InvoiceDescriptionTemplate>,
+    pub webhook_config: Option<WebhookConfig>,
+    pub deposit_claim_policy: Option<DepositClaimPolicy>,
+    pub low_data_mode: Option<bool>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DataUsage)]
+pub struct DataUsage {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetDataUsageResponse)]
+pub struct GetDataUsageResponse {
+    pub operator: DataUsage,
+    pub ssp: DataUsage,
+    pub chain: DataUsage,
+    pub sync: DataUsage,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::SparkConfig)]
 pub struct SparkConfig {
     pub coordinator_identifier: String,
@@ -710,6 +961,19 @@ pub struct SparkSspConfig {
     pub schema_endpoint: Option<String>,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ConfigProfile)]
+pub enum ConfigProfile {
+    LowLatency,
+    LowFee,
+    Privacy,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SyncMode)]
+pub enum SyncMode {
+    Full,
+    Fast { recent_payments: u32 },
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::LeafOptimizationConfig)]
 pub struct LeafOptimizationConfig {
     pub auto_enabled: bool,
@@ -744,6 +1008,14 @@ pub enum MaxFee {
     NetworkRecommended { leeway_sat_per_vbyte: u64 },
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DepositClaimPolicy)]
+pub enum DepositClaimPolicy {
+    Always,
+    Manual,
+    MaxFee { max_fee: MaxFee },
+    MaxFeePercent { percent: f64 },
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::Fee)]
 pub enum Fee {
     Fixed { amount: u64 },
@@ -869,6 +1141,72 @@ pub struct UnilateralExitResponse {
     pub transactions: Vec<UnilateralExitTransaction>,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ExportEmergencyExitPackageRequest)]
+pub struct ExportEmergencyExitPackageRequest {
+    pub funding_input: CpfpInput,
+    pub fee_rate_sat_per_vbyte: u64,
+    pub destination: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::EmergencyExitPackage)]
+pub struct EmergencyExitPackage {
+    pub created_at: u64,
+    pub exit: UnilateralExitResponse,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ViewerBundle)]
+pub struct ViewerBundle {
+    pub identity_public_key: String,
+    pub created_at: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SweepPrivateKeyRequest)]
+pub struct SweepPrivateKeyRequest {
+    pub wif: String,
+    pub fee_rate_sat_per_vbyte: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SweepPrivateKeyResponse)]
+pub struct SweepPrivateKeyResponse {
+    pub tx_id: String,
+    pub tx_hex: String,
+    pub deposit_address: String,
+    pub amount_sats: u64,
+    pub fee_sats: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SetPaymentDisputeRequest)]
+pub struct SetPaymentDisputeRequest {
+    pub payment_id: String,
+    pub status: DisputeStatus,
+    pub note: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SetPaymentDisputeResponse)]
+pub struct SetPaymentDisputeResponse {
+    pub payment: Payment,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::AcknowledgePaymentRequest)]
+pub struct AcknowledgePaymentRequest {
+    pub payment_id: String,
+    pub consumer_id: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::AcknowledgePaymentResponse)]
+pub struct AcknowledgePaymentResponse {}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ListUnacknowledgedPaymentsRequest)]
+pub struct ListUnacknowledgedPaymentsRequest {
+    pub consumer_id: String,
+    pub limit: Option<u32>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ListUnacknowledgedPaymentsResponse)]
+pub struct ListUnacknowledgedPaymentsResponse {
+    pub payments: Vec<Payment>,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::Credentials)]
 pub struct Credentials {
     pub username: String,
@@ -880,11 +1218,25 @@ pub struct GetInfoRequest {
     pub ensure_synced: Option<bool>,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::FeeEstimationStats)]
+pub struct FeeEstimationStats {
+    pub sample_count: u32,
+    pub pending_count: u32,
+    pub underestimated_count: u32,
+    pub overestimated_count: u32,
+    pub mean_estimated_fee_sats: f64,
+    pub mean_actual_fee_sats: f64,
+    pub mean_absolute_error_sats: f64,
+    pub mean_relative_error: f64,
+    pub max_relative_error: f64,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::GetInfoResponse)]
 pub struct GetInfoResponse {
     pub identity_pubkey: String,
     pub balance_sats: u64,
     pub token_balances: HashMap<String, TokenBalance>,
+    pub history_partially_synced: bool,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::TokenBalance)]
@@ -915,6 +1267,103 @@ pub struct SyncWalletRequest {}
 #[macros::extern_wasm_bindgen(breez_sdk_spark::SyncWalletResponse)]
 pub struct SyncWalletResponse {}
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::TriggerSyncRequest)]
+pub struct TriggerSyncRequest {
+    pub trigger: SyncTrigger,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SyncTrigger)]
+pub enum SyncTrigger {
+    AppForeground,
+    PushNotification,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ProcessNotificationRequest)]
+pub struct ProcessNotificationRequest {
+    pub payload: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ProcessNotificationResponse)]
+pub struct ProcessNotificationResponse {
+    pub payment: Option<Payment>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::StopSyncCaptureResponse)]
+pub struct StopSyncCaptureResponse {
+    pub capture: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::StuckPaymentRecommendation)]
+pub enum StuckPaymentRecommendation {
+    Sync,
+    ContactSupport,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::RecoverStuckPaymentRequest)]
+pub struct RecoverStuckPaymentRequest {
+    pub payment_id: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::RecoverStuckPaymentResponse)]
+pub struct RecoverStuckPaymentResponse {
+    pub payment: Payment,
+    pub recommendation: Option<StuckPaymentRecommendation>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::CheckStorageIntegrityRequest)]
+pub struct CheckStorageIntegrityRequest {
+    pub repair: bool,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::CheckStorageIntegrityResponse)]
+pub struct CheckStorageIntegrityResponse {
+    pub issues: Vec<StorageIntegrityIssue>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetWithdrawStatusRequest)]
+pub struct GetWithdrawStatusRequest {
+    pub payment_id: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetWithdrawStatusResponse)]
+pub struct GetWithdrawStatusResponse {
+    pub tx_id: String,
+    pub confirmed: bool,
+    pub block_height: Option<u32>,
+    pub estimated_confirmations: Option<u64>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ReceiveStage)]
+pub enum ReceiveStage {
+    InvoiceIssued,
+    HtlcSeen,
+    ClaimAttempted,
+    Settled,
+    Failed,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ReceiveStageUpdate)]
+pub struct ReceiveStageUpdate {
+    pub stage: ReceiveStage,
+    pub timestamp: u64,
+    pub ssp_status: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DiagnoseReceiveRequest)]
+pub struct DiagnoseReceiveRequest {
+    pub invoice: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DiagnoseReceiveResponse)]
+pub struct DiagnoseReceiveResponse {
+    pub payment_hash: String,
+    pub stage: ReceiveStage,
+    pub ssp_status: Option<String>,
+    pub trail: Vec<ReceiveStageUpdate>,
+    pub invoice_expired: bool,
+    pub summary: String,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ReceivePaymentMethod)]
 pub enum ReceivePaymentMethod {
     SparkAddress,
@@ -935,6 +1384,15 @@ pub enum ReceivePaymentMethod {
         amount_sats: Option<u64>,
         expiry_secs: Option<u32>,
         payment_hash: Option<String>,
+        min_amount_sats: Option<u64>,
+        max_amount_sats: Option<u64>,
+    },
+    TokenInvoice {
+        token_identifier: String,
+        #[tsify(type = "string")]
+        #[serde(with = "serde_u128_as_string")]
+        amount: u128,
+        memo: Option<String>,
     },
 }
 
@@ -953,6 +1411,26 @@ pub struct SendOnchainSpeedFeeQuote {
     pub l1_broadcast_fee_sat: u64,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::FeeBreakdown)]
+pub enum FeeBreakdown {
+    Lightning {
+        ssp_fee_sats: u64,
+    },
+    BitcoinAddress {
+        speed_fast: OnchainFeeBreakdown,
+        speed_medium: OnchainFeeBreakdown,
+        speed_slow: OnchainFeeBreakdown,
+    },
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::OnchainFeeBreakdown)]
+pub struct OnchainFeeBreakdown {
+    pub ssp_fee_sat: u64,
+    pub l1_broadcast_fee_sat: u64,
+    pub fee_rate_sat_per_vbyte: u64,
+    pub estimated_vbytes: u64,
+}
+
 #[derive(Clone, Copy)]
 #[macros::extern_wasm_bindgen(breez_sdk_spark::CrossChainProvider)]
 pub enum CrossChainProvider {
@@ -1087,6 +1565,7 @@ pub enum SendPaymentMethod {
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ReceivePaymentRequest)]
 pub struct ReceivePaymentRequest {
     pub payment_method: ReceivePaymentMethod,
+    pub order_ref: Option<String>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ReceivePaymentResponse)]
@@ -1138,6 +1617,7 @@ pub struct LnurlPayRequest {
 pub struct LnurlPayResponse {
     pub payment: Payment,
     pub success_action: Option<SuccessActionProcessed>,
+    pub comment: Option<String>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::BuildUnsignedLnurlPayPackageRequest)]
@@ -1273,6 +1753,8 @@ pub struct PrepareSendPaymentResponse {
     pub token_identifier: Option<String>,
     pub conversion_estimate: Option<ConversionEstimate>,
     pub fee_policy: FeePolicy,
+    pub bip21_details: Option<Bip21Details>,
+    pub fee_breakdown: Option<FeeBreakdown>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::OnchainConfirmationSpeed)]
@@ -1307,6 +1789,34 @@ pub struct SendPaymentRequest {
     pub prepare_response: PrepareSendPaymentResponse,
     pub options: Option<SendPaymentOptions>,
     pub idempotency_key: Option<String>,
+    pub compliance_metadata: Option<ComplianceMetadata>,
+    pub queue_if_offline: Option<bool>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ComplianceParty)]
+pub struct ComplianceParty {
+    pub name: String,
+    pub account_id: Option<String>,
+    pub physical_address: Option<String>,
+    pub national_id: Option<String>,
+    pub date_of_birth: Option<String>,
+    pub vasp_name: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ComplianceMetadata)]
+pub struct ComplianceMetadata {
+    pub originator: ComplianceParty,
+    pub beneficiary: ComplianceParty,
+    pub reference: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::TravelRuleTransfer)]
+pub struct TravelRuleTransfer {
+    pub destination: String,
+    pub amount: u128,
+    pub token_identifier: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub metadata: ComplianceMetadata,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::PublishSignedTransferPackageRequest)]
@@ -1326,6 +1836,42 @@ pub struct SendPaymentResponse {
     pub payment: Payment,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SplitExecutionMode)]
+pub enum SplitExecutionMode {
+    Sequential,
+    Parallel,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaySplitRequest)]
+pub struct PaySplitRequest {
+    pub invoices: Vec<String>,
+    pub total_budget_sats: u64,
+    pub execution_mode: Option<SplitExecutionMode>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SplitPaymentStatus)]
+pub enum SplitPaymentStatus {
+    Sent { payment: Payment },
+    Failed { error: String },
+    Skipped,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SplitPaymentResult)]
+pub struct SplitPaymentResult {
+    pub invoice: String,
+    pub amount_sats: u64,
+    pub estimated_fee_sats: u64,
+    pub status: SplitPaymentStatus,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaySplitResponse)]
+pub struct PaySplitResponse {
+    pub results: Vec<SplitPaymentResult>,
+    pub total_sent_sats: u64,
+    pub total_fees_sats: u64,
+    pub all_succeeded: bool,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentDetailsFilter)]
 pub enum PaymentDetailsFilter {
     Spark {
@@ -1377,6 +1923,11 @@ pub struct ListPaymentsRequest {
     pub offset: Option<u32>,
     pub limit: Option<u32>,
     pub sort_ascending: Option<bool>,
+    pub counterparty_filter: Option<CounterpartyFilter>,
+    pub search: Option<String>,
+    pub cursor: Option<String>,
+    pub grouped: Option<bool>,
+    pub dispute_status_filter: Option<Vec<DisputeStatus>>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::StorageListPaymentsRequest)]
@@ -1390,6 +1941,17 @@ pub struct StorageListPaymentsRequest {
     pub offset: Option<u32>,
     pub limit: Option<u32>,
     pub sort_ascending: Option<bool>,
+    pub counterparty_filter: Option<CounterpartyFilter>,
+    pub search: Option<String>,
+    pub after: Option<PaymentCursor>,
+    pub group_id_filter: Option<Vec<String>>,
+    pub dispute_status_filter: Option<Vec<DisputeStatus>>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentCursor)]
+pub struct PaymentCursor {
+    pub timestamp: u64,
+    pub id: String,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::AssetFilter)]
@@ -1398,9 +1960,26 @@ pub enum AssetFilter {
     Token { token_identifier: Option<String> },
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::CounterpartyFilter)]
+pub enum CounterpartyFilter {
+    LightningDestination { destination_pubkey: String },
+    LightningAddress { address: String },
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::ListPaymentsResponse)]
 pub struct ListPaymentsResponse {
     pub payments: Vec<Payment>,
+    pub next_cursor: Option<String>,
+    pub groups: Option<Vec<PaymentGroup>>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentGroup)]
+pub struct PaymentGroup {
+    pub group_id: String,
+    pub status: PaymentStatus,
+    pub timestamp: u64,
+    pub fees: u128,
+    pub payments: Vec<Payment>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::GetPaymentRequest)]
@@ -1411,6 +1990,124 @@ pub struct GetPaymentRequest {
 #[macros::extern_wasm_bindgen(breez_sdk_spark::GetPaymentResponse)]
 pub struct GetPaymentResponse {
     pub payment: Payment,
+    pub compliance_metadata: Option<ComplianceMetadata>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetPaymentFiatValueRequest)]
+pub struct GetPaymentFiatValueRequest {
+    pub payment_id: String,
+    pub currency: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetPaymentFiatValueResponse)]
+pub struct GetPaymentFiatValueResponse {
+    pub fiat_value_at_payment: f64,
+    pub rate: f64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentIdentifier)]
+pub enum PaymentIdentifier {
+    PaymentId { payment_id: String },
+    PaymentHash { payment_hash: String },
+    Invoice { invoice: String },
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::WaitForPaymentRequest)]
+pub struct WaitForPaymentRequest {
+    pub identifier: PaymentIdentifier,
+    pub timeout_secs: Option<u32>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::WaitForPaymentResponse)]
+pub struct WaitForPaymentResponse {
+    pub payment: Payment,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentImportSource)]
+pub enum PaymentImportSource {
+    Greenlight,
+    Liquid,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ImportPaymentsRequest)]
+pub struct ImportPaymentsRequest {
+    pub source: PaymentImportSource,
+    pub data: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ImportPaymentsResponse)]
+pub struct ImportPaymentsResponse {
+    pub imported_count: u32,
+    pub skipped_count: u32,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ExportFormat)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ExportPaymentsRequest)]
+pub struct ExportPaymentsRequest {
+    pub format: ExportFormat,
+    pub from_timestamp: Option<u64>,
+    pub to_timestamp: Option<u64>,
+    pub include_metadata: bool,
+    pub fiat_currency: Option<String>,
+    pub cursor: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ExportPaymentsResponse)]
+pub struct ExportPaymentsResponse {
+    pub data: String,
+    pub next_cursor: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::BackupResponse)]
+pub struct BackupResponse {
+    pub backup: Vec<u8>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ClaimFeeSponsorRequest)]
+pub struct ClaimFeeSponsorRequest {
+    pub deposit_txid: String,
+    pub deposit_vout: u32,
+    pub deposit_amount_sats: u64,
+    pub fee_sats: u64,
+    pub spark_address: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ClaimFeeSponsorship)]
+pub struct ClaimFeeSponsorship {
+    pub deposit_txid: String,
+    pub deposit_vout: u32,
+    pub claim_payment_id: String,
+    pub fee_sats: u64,
+    pub sponsor_payment_id: String,
+    pub created_at: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ListClaimFeeSponsorshipsRequest)]
+pub struct ListClaimFeeSponsorshipsRequest {
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ListClaimFeeSponsorshipsResponse)]
+pub struct ListClaimFeeSponsorshipsResponse {
+    pub sponsorships: Vec<ClaimFeeSponsorship>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::RestoreRequest)]
+pub struct RestoreRequest {
+    pub backup: Vec<u8>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::RestoreResponse)]
+pub struct RestoreResponse {
+    pub payments_count: u32,
+    pub deposits_count: u32,
+    pub contacts_count: u32,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::LogEntry)]
@@ -1427,6 +2124,8 @@ pub struct PaymentMetadata {
     pub lnurl_description: Option<String>,
     pub conversion_info: Option<ConversionInfo>,
     pub conversion_status: Option<ConversionStatus>,
+    pub group_id: Option<String>,
+    pub dispute: Option<PaymentDispute>,
 }
 
 #[macros::extern_wasm_bindgen(breez_sdk_spark::SetLnurlMetadataItem)]
@@ -1597,6 +2296,51 @@ pub struct PaymentIdUpdate {
     pub final_payment_id: String,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::AllowlistEntry)]
+pub enum AllowlistEntry {
+    SparkIdentity { identity_public_key: String },
+    LightningAddress { address: String },
+    BitcoinAddress { address: String },
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::WithdrawalAllowlist)]
+pub struct WithdrawalAllowlist {
+    pub enabled: bool,
+    pub entries: Vec<AllowlistEntry>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::UpdateWithdrawalAllowlistRequest)]
+pub struct UpdateWithdrawalAllowlistRequest {
+    pub enabled: Option<bool>,
+    pub add: Vec<AllowlistEntry>,
+    pub remove: Vec<AllowlistEntry>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PaymentApprovalRequest)]
+pub struct PaymentApprovalRequest {
+    pub destination: String,
+    pub allowlist_entry: Option<AllowlistEntry>,
+    pub amount: u128,
+    pub token_identifier: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::SyncConflict)]
+pub struct SyncConflict {
+    pub record_type: String,
+    pub record_id: String,
+    pub base_fields: HashMap<String, String>,
+    pub local_fields: HashMap<String, String>,
+    pub remote_fields: HashMap<String, String>,
+}
+
+#[derive(Clone, Copy, Default)]
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ConflictResolutionStrategy)]
+pub enum ConflictResolutionStrategy {
+    #[default]
+    FieldMerge,
+    LastWriterWins,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::SignMessageRequest)]
 pub struct SignMessageRequest {
     pub message: String,
@@ -1621,6 +2365,26 @@ pub struct CheckMessageResponse {
     pub is_valid: bool,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::ChildSecretPurpose)]
+pub enum ChildSecretPurpose {
+    NwcKey,
+    WebhookHmacKey,
+    BackupEncryptionKey,
+    Custom { application: u32 },
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DeriveChildSecretRequest)]
+pub struct DeriveChildSecretRequest {
+    pub purpose: ChildSecretPurpose,
+    pub index: Option<u32>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::DeriveChildSecretResponse)]
+pub struct DeriveChildSecretResponse {
+    pub secret: String,
+    pub derivation_path: String,
+}
+
 // Sync types
 #[macros::extern_wasm_bindgen(breez_sdk_spark::sync_storage::RecordId)]
 pub struct RecordId {
@@ -1691,6 +2455,43 @@ pub struct ClaimHtlcPaymentResponse {
     pub payment: Payment,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::CreateHtlcReceiveRequest)]
+pub struct CreateHtlcReceiveRequest {
+    pub payment_hash: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::CreateHtlcReceiveResponse)]
+pub struct CreateHtlcReceiveResponse {
+    pub spark_address: String,
+    pub payment_hash: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetHtlcReceiveRequest)]
+pub struct GetHtlcReceiveRequest {
+    pub payment_hash: String,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetHtlcReceiveResponse)]
+pub struct GetHtlcReceiveResponse {
+    pub htlc_receive: HtlcReceive,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::HtlcReceive)]
+pub struct HtlcReceive {
+    pub payment_hash: String,
+    pub status: HtlcReceiveStatus,
+    pub htlc_details: Option<SparkHtlcDetails>,
+    pub payment: Option<Payment>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::HtlcReceiveStatus)]
+pub enum HtlcReceiveStatus {
+    AwaitingHtlc,
+    WaitingForPreimage,
+    PreimageShared,
+    Returned,
+}
+
 #[macros::extern_wasm_bindgen(breez_sdk_spark::LnurlReceiveMetadata)]
 pub struct LnurlReceiveMetadata {
     pub nostr_zap_request: Option<String>,
@@ -1930,6 +2731,22 @@ pub struct StoredCrossChainSwap {
     pub secrets: String,
 }
 
+#[macros::extern_wasm_bindgen(breez_sdk_spark::StorageIntegrityIssueKind)]
+pub enum StorageIntegrityIssueKind {
+    Corruption,
+    InvalidJson,
+    OrphanedRecord,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::StorageIntegrityIssue)]
+pub struct StorageIntegrityIssue {
+    pub kind: StorageIntegrityIssueKind,
+    pub table: Option<String>,
+    pub record_id: Option<String>,
+    pub description: String,
+    pub repaired: bool,
+}
+
 #[allow(clippy::enum_variant_names)]
 #[macros::extern_wasm_bindgen(breez_sdk_spark::WebhookEventType)]
 pub enum WebhookEventType {
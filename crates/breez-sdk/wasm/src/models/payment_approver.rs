@@ -0,0 +1,42 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::PaymentApprovalRequest;
+
+pub struct WasmPaymentApprover {
+    pub payment_approver: PaymentApprover,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmPaymentApprover {}
+unsafe impl Sync for WasmPaymentApprover {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::PaymentApprover for WasmPaymentApprover {
+    async fn approve(&self, request: breez_sdk_spark::PaymentApprovalRequest) -> bool {
+        // A failing approver denies the payment
+        let Ok(promise) = self.payment_approver.approve(request.into()) else {
+            return false;
+        };
+        JsFuture::from(promise)
+            .await
+            .is_ok_and(|approved| approved.as_bool() == Some(true))
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EVENT_INTERFACE: &'static str = r#"export interface PaymentApprover {
+    approve: (request: PaymentApprovalRequest) => Promise<boolean>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "PaymentApprover")]
+    pub type PaymentApprover;
+
+    #[wasm_bindgen(structural, method, js_name = approve, catch)]
+    pub fn approve(
+        this: &PaymentApprover,
+        request: PaymentApprovalRequest,
+    ) -> Result<Promise, JsValue>;
+}
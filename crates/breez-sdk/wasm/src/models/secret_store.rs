@@ -0,0 +1,82 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::error::js_error_to_secret_store_error;
+
+pub struct WasmSecretStore {
+    pub secret_store: SecretStore,
+}
+
+// Single-threaded WASM environment makes this safe.
+unsafe impl Send for WasmSecretStore {}
+unsafe impl Sync for WasmSecretStore {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::SecretStore for WasmSecretStore {
+    async fn get_secret(
+        &self,
+        key: String,
+    ) -> Result<Option<String>, breez_sdk_spark::SecretStoreError> {
+        let promise = self
+            .secret_store
+            .get_secret(key)
+            .map_err(js_error_to_secret_store_error)?;
+        let result = JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_secret_store_error)?;
+        if result.is_null() || result.is_undefined() {
+            return Ok(None);
+        }
+        result.as_string().map(Some).ok_or_else(|| {
+            breez_sdk_spark::SecretStoreError::Generic("Secret is not a string".to_string())
+        })
+    }
+
+    async fn set_secret(
+        &self,
+        key: String,
+        value: String,
+    ) -> Result<(), breez_sdk_spark::SecretStoreError> {
+        let promise = self
+            .secret_store
+            .set_secret(key, value)
+            .map_err(js_error_to_secret_store_error)?;
+        JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_secret_store_error)?;
+        Ok(())
+    }
+
+    async fn delete_secret(&self, key: String) -> Result<(), breez_sdk_spark::SecretStoreError> {
+        let promise = self
+            .secret_store
+            .delete_secret(key)
+            .map_err(js_error_to_secret_store_error)?;
+        JsFuture::from(promise)
+            .await
+            .map_err(js_error_to_secret_store_error)?;
+        Ok(())
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const SECRET_STORE_INTERFACE: &'static str = r#"export interface SecretStore {
+    getSecret: (key: string) => Promise<string | undefined>;
+    setSecret: (key: string, value: string) => Promise<void>;
+    deleteSecret: (key: string) => Promise<void>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SecretStore")]
+    pub type SecretStore;
+
+    #[wasm_bindgen(structural, method, js_name = getSecret, catch)]
+    pub fn get_secret(this: &SecretStore, key: String) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(structural, method, js_name = setSecret, catch)]
+    pub fn set_secret(this: &SecretStore, key: String, value: String) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(structural, method, js_name = deleteSecret, catch)]
+    pub fn delete_secret(this: &SecretStore, key: String) -> Result<Promise, JsValue>;
+}
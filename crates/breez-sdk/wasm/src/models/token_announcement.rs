@@ -0,0 +1,23 @@
+use super::TokenMetadata;
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::TokenAnnouncement)]
+pub struct TokenAnnouncement {
+    pub metadata: TokenMetadata,
+    pub icon_url: Option<String>,
+    pub published_at: u64,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::PublishTokenAnnouncementRequest)]
+pub struct PublishTokenAnnouncementRequest {
+    pub icon_url: Option<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetTokenAnnouncementsRequest)]
+pub struct GetTokenAnnouncementsRequest {
+    pub token_identifiers: Vec<String>,
+}
+
+#[macros::extern_wasm_bindgen(breez_sdk_spark::GetTokenAnnouncementsResponse)]
+pub struct GetTokenAnnouncementsResponse {
+    pub announcements: Vec<TokenAnnouncement>,
+}
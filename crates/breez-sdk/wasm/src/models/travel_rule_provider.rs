@@ -0,0 +1,47 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::models::{TravelRuleTransfer, error::js_error_to_travel_rule_provider_error};
+
+pub struct WasmTravelRuleProvider {
+    pub travel_rule_provider: TravelRuleProvider,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmTravelRuleProvider {}
+unsafe impl Sync for WasmTravelRuleProvider {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::TravelRuleProvider for WasmTravelRuleProvider {
+    async fn submit(
+        &self,
+        transfer: breez_sdk_spark::TravelRuleTransfer,
+    ) -> Result<(), breez_sdk_spark::TravelRuleProviderError> {
+        let promise = self
+            .travel_rule_provider
+            .submit(transfer.into())
+            .map_err(js_error_to_travel_rule_provider_error)?;
+        let future = JsFuture::from(promise);
+        future
+            .await
+            .map_err(js_error_to_travel_rule_provider_error)?;
+        Ok(())
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EVENT_INTERFACE: &'static str = r#"export interface TravelRuleProvider {
+    submit: (transfer: TravelRuleTransfer) => Promise<void>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "TravelRuleProvider")]
+    pub type TravelRuleProvider;
+
+    #[wasm_bindgen(structural, method, js_name = submit, catch)]
+    pub fn submit(
+        this: &TravelRuleProvider,
+        transfer: TravelRuleTransfer,
+    ) -> Result<Promise, JsValue>;
+}
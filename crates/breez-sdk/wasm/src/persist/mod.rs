@@ -14,8 +14,9 @@ use std::collections::HashMap;
 
 use crate::models::{
     Contact, DepositInfo, IncomingChange, ListContactsRequest, OutgoingChange, Payment,
-    PaymentMetadata, Record, SetLnurlMetadataItem, StorageListPaymentsRequest,
-    StoredCrossChainSwap, UnversionedRecordChange, UpdateDepositPayload,
+    PaymentMetadata, Record, SetLnurlMetadataItem, StorageIntegrityIssue,
+    StorageListPaymentsRequest, StoredCrossChainSwap, UnversionedRecordChange,
+    UpdateDepositPayload,
 };
 
 pub struct WasmStorage {
@@ -84,6 +85,14 @@ fn should_emit_from_js(value: &JsValue) -> Result<bool, StorageError> {
     })
 }
 
+/// JS storages don't persist the display amount, so it is derived on read.
+fn payment_from_js(payment: Payment) -> breez_sdk_spark::Payment {
+    let mut payment: breez_sdk_spark::Payment = payment.into();
+    payment.display_amount =
+        breez_sdk_spark::DisplayAmount::new(payment.amount, payment.details.as_ref());
+    payment
+}
+
 // This assumes that we'll always be running in a single thread (true for Wasm environments)
 unsafe impl Send for WasmStorage {}
 unsafe impl Sync for WasmStorage {}
@@ -138,7 +147,7 @@ impl breez_sdk_spark::Storage for WasmStorage {
 
         let payments: Vec<Payment> = serde_wasm_bindgen::from_value(result)
             .map_err(|e| StorageError::Serialization(e.to_string()))?;
-        Ok(payments.into_iter().map(|p| p.into()).collect())
+        Ok(payments.into_iter().map(payment_from_js).collect())
     }
 
     async fn apply_payment_update(
@@ -182,7 +191,7 @@ impl breez_sdk_spark::Storage for WasmStorage {
 
         let payment: Payment = serde_wasm_bindgen::from_value(result)
             .map_err(|e| StorageError::Serialization(e.to_string()))?;
-        Ok(payment.into())
+        Ok(payment_from_js(payment))
     }
 
     async fn get_payment_by_invoice(
@@ -198,7 +207,7 @@ impl breez_sdk_spark::Storage for WasmStorage {
 
         let payment: Option<Payment> = serde_wasm_bindgen::from_value(result)
             .map_err(|e| StorageError::Serialization(e.to_string()))?;
-        Ok(payment.map(|p| p.into()))
+        Ok(payment.map(payment_from_js))
     }
 
     async fn add_deposit(
@@ -288,7 +297,10 @@ impl breez_sdk_spark::Storage for WasmStorage {
         let result_map: HashMap<String, Vec<breez_sdk_spark::Payment>> = js_map
             .into_iter()
             .map(|(parent_id, children)| {
-                (parent_id, children.into_iter().map(|c| c.into()).collect())
+                (
+                    parent_id,
+                    children.into_iter().map(payment_from_js).collect(),
+                )
             })
             .collect();
 
@@ -534,6 +546,22 @@ impl breez_sdk_spark::Storage for WasmStorage {
         future.await.map_err(js_error_to_storage_error)?;
         Ok(())
     }
+
+    async fn check_integrity(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<breez_sdk_spark::StorageIntegrityIssue>, StorageError> {
+        let promise = self
+            .storage
+            .check_integrity(repair)
+            .map_err(js_error_to_storage_error)?;
+        let future = JsFuture::from(promise);
+        let result = future.await.map_err(js_error_to_storage_error)?;
+
+        let issues: Vec<StorageIntegrityIssue> = serde_wasm_bindgen::from_value(result)
+            .map_err(|e| StorageError::Serialization(e.to_string()))?;
+        Ok(issues.into_iter().map(Into::into).collect())
+    }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -576,6 +604,12 @@ const STORAGE_INTERFACE: &'static str = r#"export interface Storage {
     syncGetIncomingRecords: (limit: number) => Promise<IncomingChange[]>;
     syncGetLatestOutgoingChange: () => Promise<OutgoingChange | null>;
     syncUpdateRecordFromIncoming: (record: Record) => Promise<void>;
+    /**
+     * Check the stored data for corruption, invalid JSON values and payment
+     * details whose payment doesn't exist. With `repair`, remove or clear what
+     * can be rebuilt. Returns the issues found.
+     */
+    checkIntegrity: (repair: boolean) => Promise<StorageIntegrityIssue[]>;
 }"#;
 
 #[wasm_bindgen]
@@ -722,4 +756,7 @@ extern "C" {
         this: &Storage,
         record: Record,
     ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(structural, method, js_name = checkIntegrity, catch)]
+    pub fn check_integrity(this: &Storage, repair: bool) -> Result<Promise, JsValue>;
 }
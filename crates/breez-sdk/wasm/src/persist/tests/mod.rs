@@ -12,3 +12,6 @@ mod mysql_foreign_keys;
 
 #[cfg(feature = "browser-tests")]
 mod web;
+
+#[cfg(feature = "browser-tests")]
+mod opfs;
@@ -84,6 +84,54 @@ async fn test_asset_filtering() {
     breez_sdk_spark::storage_tests::test_asset_filtering(Box::new(storage)).await;
 }
 
+#[wasm_bindgen_test]
+async fn test_counterparty_filtering() {
+    let storage = create_test_storage("my_counterparty_filtering").await;
+    breez_sdk_spark::storage_tests::test_counterparty_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_imported_payment_flag() {
+    let storage = create_test_storage("my_imported_payment_flag").await;
+    breez_sdk_spark::storage_tests::test_imported_payment_flag(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_display_amount() {
+    let storage = create_test_storage("my_payment_display_amount").await;
+    breez_sdk_spark::storage_tests::test_payment_display_amount(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_group_id() {
+    let storage = create_test_storage("my_payment_group_id").await;
+    breez_sdk_spark::storage_tests::test_payment_group_id(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_dispute() {
+    let storage = create_test_storage("my_payment_dispute").await;
+    breez_sdk_spark::storage_tests::test_payment_dispute(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_list_payments_cursor() {
+    let storage = create_test_storage("my_list_payments_cursor").await;
+    breez_sdk_spark::storage_tests::test_list_payments_cursor(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_search() {
+    let storage = create_test_storage("my_payment_search").await;
+    breez_sdk_spark::storage_tests::test_payment_search(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_check_integrity() {
+    let storage = create_test_storage("my_check_integrity").await;
+    breez_sdk_spark::storage_tests::test_check_integrity(Box::new(storage)).await;
+}
+
 #[wasm_bindgen_test]
 async fn test_timestamp_filtering() {
     let storage = create_test_storage("my_timestamp_filtering").await;
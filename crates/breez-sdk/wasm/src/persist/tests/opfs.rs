@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use breez_sdk_spark::sync_storage::{Record, RecordId, UnversionedRecordChange};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+use crate::persist::{Storage, WasmStorage};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Built by `npm run build` in `js/web-storage`
+const OPFS_WORKER_BUNDLE: &str = include_str!("../../../js/web-storage/opfs-worker.bundle.js");
+const SQLITE_WASM: &[u8] = include_bytes!("../../../js/web-storage/sqlite3.wasm");
+
+// Import the web-storage package
+#[wasm_bindgen(module = "/js/web-storage/index.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "createDefaultStorage", catch)]
+    async fn create_default_storage(
+        data_dir: &str,
+        logger: Option<&crate::logger::Logger>,
+    ) -> Result<Storage, JsValue>;
+}
+
+// Import test helpers
+#[wasm_bindgen(module = "/js/test-helpers.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "createTestOpfsStorage", catch)]
+    async fn create_test_opfs_storage(
+        db_name: &str,
+        worker_source: &str,
+        sqlite_wasm: &[u8],
+    ) -> Result<Storage, JsValue>;
+}
+
+// Helper to create a WasmStorage instance for testing using the OPFS storage.
+// The name is prefixed, so the IndexedDB databases of the web storage tests
+// aren't migrated into it.
+async fn create_test_storage(db_name: &str) -> WasmStorage {
+    let db_name = format!("opfs_{db_name}");
+    let storage = create_test_opfs_storage(&db_name, OPFS_WORKER_BUNDLE, SQLITE_WASM)
+        .await
+        .expect("Failed to create OPFS storage instance");
+    WasmStorage { storage }
+}
+
+#[wasm_bindgen_test]
+async fn test_storage() {
+    let storage = create_test_storage("sqlite_storage").await;
+
+    breez_sdk_spark::storage_tests::test_storage(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_unclaimed_deposits_crud() {
+    let storage = create_test_storage("unclaimed_deposits_crud").await;
+
+    breez_sdk_spark::storage_tests::test_unclaimed_deposits_crud(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_deposit_refunds() {
+    let storage = create_test_storage("deposit_refunds").await;
+
+    breez_sdk_spark::storage_tests::test_deposit_refunds(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_type_filtering() {
+    let storage = create_test_storage("payment_type_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_payment_type_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_status_filtering() {
+    let storage = create_test_storage("payment_status_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_payment_status_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_asset_filtering() {
+    let storage = create_test_storage("asset_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_asset_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_counterparty_filtering() {
+    let storage = create_test_storage("counterparty_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_counterparty_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_imported_payment_flag() {
+    let storage = create_test_storage("imported_payment_flag").await;
+
+    breez_sdk_spark::storage_tests::test_imported_payment_flag(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_display_amount() {
+    let storage = create_test_storage("payment_display_amount").await;
+
+    breez_sdk_spark::storage_tests::test_payment_display_amount(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_group_id() {
+    let storage = create_test_storage("payment_group_id").await;
+
+    breez_sdk_spark::storage_tests::test_payment_group_id(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_dispute() {
+    let storage = create_test_storage("payment_dispute").await;
+
+    breez_sdk_spark::storage_tests::test_payment_dispute(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_list_payments_cursor() {
+    let storage = create_test_storage("list_payments_cursor").await;
+
+    breez_sdk_spark::storage_tests::test_list_payments_cursor(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_search() {
+    let storage = create_test_storage("payment_search").await;
+
+    breez_sdk_spark::storage_tests::test_payment_search(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_check_integrity() {
+    let storage = create_test_storage("check_integrity").await;
+
+    breez_sdk_spark::storage_tests::test_check_integrity(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_timestamp_filtering() {
+    let storage = create_test_storage("timestamp_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_timestamp_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_combined_filters() {
+    let storage = create_test_storage("combined_filters").await;
+
+    breez_sdk_spark::storage_tests::test_combined_filters(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_sort_order() {
+    let storage = create_test_storage("sort_order").await;
+
+    breez_sdk_spark::storage_tests::test_sort_order(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_metadata() {
+    let storage = create_test_storage("test_payment_metadata").await;
+
+    breez_sdk_spark::storage_tests::test_payment_metadata(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_metadata_merge() {
+    let storage = create_test_storage("test_payment_metadata_merge").await;
+
+    breez_sdk_spark::storage_tests::test_payment_metadata_merge(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_details_update_persistence() {
+    let storage = create_test_storage("payment_details_update").await;
+
+    breez_sdk_spark::storage_tests::test_payment_details_update_persistence(Box::new(storage))
+        .await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_terminal_status_is_not_replaced() {
+    let storage = create_test_storage("payment_terminal_guard").await;
+
+    breez_sdk_spark::storage_tests::test_payment_terminal_status_is_not_replaced(Box::new(storage))
+        .await;
+}
+
+#[wasm_bindgen_test]
+async fn test_spark_htlc_status_filtering() {
+    let storage = create_test_storage("spark_htlc_status_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_spark_htlc_status_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_lightning_htlc_details_and_status_filtering() {
+    let storage = create_test_storage("lightning_htlc_details").await;
+
+    breez_sdk_spark::storage_tests::test_lightning_htlc_details_and_status_filtering(Box::new(
+        storage,
+    ))
+    .await;
+}
+
+#[wasm_bindgen_test]
+async fn test_conversion_filtering() {
+    let storage = create_test_storage("test_conversion_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_conversion_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_token_transaction_type_filtering() {
+    let storage = create_test_storage("token_tx_type_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_token_transaction_type_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_sync_storage() {
+    let storage = create_test_storage("sync_storage").await;
+
+    breez_sdk_spark::storage_tests::test_sync_storage(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_contacts_crud() {
+    let storage = create_test_storage("contacts_crud").await;
+
+    breez_sdk_spark::storage_tests::test_contacts_crud(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_cross_chain_swaps_crud() {
+    let storage = create_test_storage("cross_chain_swaps_crud").await;
+
+    breez_sdk_spark::storage_tests::test_cross_chain_swaps_crud(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_conversion_status_persistence() {
+    let storage = create_test_storage("conversion_status_persistence").await;
+
+    breez_sdk_spark::storage_tests::test_conversion_status_persistence(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_insert_boltz_conversion_info() {
+    let storage = create_test_storage("insert_boltz_conversion_info").await;
+    breez_sdk_spark::storage_tests::test_insert_boltz_conversion_info(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_update_boltz_status_to_completed() {
+    let storage = create_test_storage("update_boltz_status_to_completed").await;
+    breez_sdk_spark::storage_tests::test_update_boltz_status_to_completed(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_migration_from_indexeddb_keeps_sync_state() {
+    let db_name = "indexeddb_sync_state_migration";
+    let indexeddb = WasmStorage {
+        storage: create_default_storage(&format!("opfs_{db_name}"), None)
+            .await
+            .expect("Failed to create IndexedDB storage instance"),
+    };
+
+    let mut data = HashMap::new();
+    data.insert("name".to_string(), "\"Alice\"".to_string());
+    breez_sdk_spark::Storage::update_record_from_incoming(
+        &indexeddb,
+        Record {
+            id: RecordId::new("user".to_string(), "user1".to_string()),
+            revision: 5,
+            schema_version: "1.0.0".to_string(),
+            data: data.clone(),
+        },
+    )
+    .await
+    .expect("Failed to save synced record");
+    for data_id in ["user1", "user2"] {
+        breez_sdk_spark::Storage::add_outgoing_change(
+            &indexeddb,
+            UnversionedRecordChange {
+                id: RecordId::new("user".to_string(), data_id.to_string()),
+                schema_version: "1.0.0".to_string(),
+                updated_fields: data.clone(),
+            },
+        )
+        .await
+        .expect("Failed to add outgoing change");
+    }
+
+    let storage = create_test_storage(db_name).await;
+    assert_eq!(
+        breez_sdk_spark::Storage::get_last_revision(&storage)
+            .await
+            .expect("Failed to get last revision"),
+        5
+    );
+    let pending = breez_sdk_spark::Storage::get_pending_outgoing_changes(&storage, 10)
+        .await
+        .expect("Failed to get pending outgoing changes");
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].change.id.data_id, "user1");
+    assert_eq!(
+        pending[0].parent.as_ref().map(|parent| parent.revision),
+        Some(5)
+    );
+    assert_eq!(pending[1].change.id.data_id, "user2");
+    assert!(pending[1].parent.is_none());
+}
@@ -78,6 +78,62 @@ async fn test_asset_filtering() {
     breez_sdk_spark::storage_tests::test_asset_filtering(Box::new(storage)).await;
 }
 
+#[wasm_bindgen_test]
+async fn test_counterparty_filtering() {
+    let storage = create_test_storage("counterparty_filtering").await;
+
+    breez_sdk_spark::storage_tests::test_counterparty_filtering(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_imported_payment_flag() {
+    let storage = create_test_storage("imported_payment_flag").await;
+
+    breez_sdk_spark::storage_tests::test_imported_payment_flag(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_display_amount() {
+    let storage = create_test_storage("payment_display_amount").await;
+
+    breez_sdk_spark::storage_tests::test_payment_display_amount(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_group_id() {
+    let storage = create_test_storage("payment_group_id").await;
+
+    breez_sdk_spark::storage_tests::test_payment_group_id(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_dispute() {
+    let storage = create_test_storage("payment_dispute").await;
+
+    breez_sdk_spark::storage_tests::test_payment_dispute(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_list_payments_cursor() {
+    let storage = create_test_storage("list_payments_cursor").await;
+
+    breez_sdk_spark::storage_tests::test_list_payments_cursor(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_payment_search() {
+    let storage = create_test_storage("payment_search").await;
+
+    breez_sdk_spark::storage_tests::test_payment_search(Box::new(storage)).await;
+}
+
+#[wasm_bindgen_test]
+async fn test_check_integrity() {
+    let storage = create_test_storage("check_integrity").await;
+
+    breez_sdk_spark::storage_tests::test_check_integrity(Box::new(storage)).await;
+}
+
 #[wasm_bindgen_test]
 async fn test_timestamp_filtering() {
     let storage = create_test_storage("timestamp_filtering").await;
@@ -243,6 +299,10 @@ async fn test_migration_from_v2_to_v3() {
         method: breez_sdk_spark::PaymentMethod::Lightning,
         details: None,
         conversion_details: None,
+        imported: false,
+        display_amount: breez_sdk_spark::DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     breez_sdk_spark::Storage::apply_payment_update(&storage, new_payment.clone())
@@ -260,6 +320,11 @@ async fn test_migration_from_v2_to_v3() {
         offset: None,
         limit: None,
         sort_ascending: Some(true),
+        counterparty_filter: None,
+        search: None,
+        after: None,
+        group_id_filter: None,
+        dispute_status_filter: None,
     };
 
     let payments = breez_sdk_spark::Storage::list_payments(&storage, request)
@@ -375,6 +440,10 @@ async fn test_migration_from_v8_to_v9() {
             conversion_info: None,
         }),
         conversion_details: None,
+        imported: false,
+        display_amount: breez_sdk_spark::DisplayAmount::default(),
+        group_id: None,
+        dispute: None,
     };
 
     breez_sdk_spark::Storage::apply_payment_update(&storage, new_payment.clone())
@@ -392,6 +461,11 @@ async fn test_migration_from_v8_to_v9() {
         offset: None,
         limit: None,
         sort_ascending: Some(true),
+        counterparty_filter: None,
+        search: None,
+        after: None,
+        group_id_filter: None,
+        dispute_status_filter: None,
     };
 
     let payments = breez_sdk_spark::Storage::list_payments(&storage, request)
@@ -443,6 +517,11 @@ async fn test_migration_from_v8_to_v9() {
         offset: None,
         limit: None,
         sort_ascending: Some(true),
+        counterparty_filter: None,
+        search: None,
+        after: None,
+        group_id_filter: None,
+        dispute_status_filter: None,
     };
 
     let transfer_payments =
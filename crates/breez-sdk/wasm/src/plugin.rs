@@ -0,0 +1,103 @@
+use std::{rc::Rc, sync::Arc};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, js_sys::Promise};
+
+use crate::{error::WasmResult, models::SdkEvent, sdk::BreezSdk};
+
+pub struct WasmPlugin {
+    pub plugin: Plugin,
+}
+
+// This assumes that we'll always be running in a single thread (true for Wasm environments)
+unsafe impl Send for WasmPlugin {}
+unsafe impl Sync for WasmPlugin {}
+
+#[macros::async_trait]
+impl breez_sdk_spark::Plugin for WasmPlugin {
+    fn id(&self) -> String {
+        self.plugin.id()
+    }
+
+    async fn on_start(
+        &self,
+        sdk: Arc<breez_sdk_spark::BreezSdk>,
+        storage: Arc<breez_sdk_spark::PluginStorage>,
+    ) {
+        let sdk = BreezSdk {
+            sdk: Rc::new((*sdk).clone()),
+        };
+        await_promise(self.plugin.on_start(sdk, PluginStorage { storage })).await;
+    }
+
+    async fn on_event(&self, event: breez_sdk_spark::SdkEvent) {
+        await_promise(self.plugin.on_event(event.into())).await;
+    }
+
+    async fn on_shutdown(&self) {
+        await_promise(self.plugin.on_shutdown()).await;
+    }
+}
+
+async fn await_promise(promise: Result<Promise, JsValue>) {
+    let result = match promise {
+        Ok(promise) => JsFuture::from(promise).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        tracing::error!("Plugin callback failed: {e:?}");
+    }
+}
+
+#[wasm_bindgen]
+pub struct PluginStorage {
+    storage: Arc<breez_sdk_spark::PluginStorage>,
+}
+
+#[wasm_bindgen]
+impl PluginStorage {
+    #[wasm_bindgen(js_name = "getItem")]
+    pub async fn get_item(&self, key: String) -> WasmResult<Option<String>> {
+        Ok(self.storage.get_item(key).await?)
+    }
+
+    #[wasm_bindgen(js_name = "setItem")]
+    pub async fn set_item(&self, key: String, value: String) -> WasmResult<()> {
+        Ok(self.storage.set_item(key, value).await?)
+    }
+
+    #[wasm_bindgen(js_name = "deleteItem")]
+    pub async fn delete_item(&self, key: String) -> WasmResult<()> {
+        Ok(self.storage.delete_item(key).await?)
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const PLUGIN_INTERFACE: &'static str = r#"export interface Plugin {
+    id: () => string;
+    onStart: (sdk: BreezSdk, storage: PluginStorage) => Promise<void>;
+    onEvent: (e: SdkEvent) => Promise<void>;
+    onShutdown: () => Promise<void>;
+}"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Plugin")]
+    pub type Plugin;
+
+    #[wasm_bindgen(structural, method, js_name = id)]
+    pub fn id(this: &Plugin) -> String;
+
+    #[wasm_bindgen(structural, method, js_name = onStart, catch)]
+    pub fn on_start(
+        this: &Plugin,
+        sdk: BreezSdk,
+        storage: PluginStorage,
+    ) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(structural, method, js_name = onEvent, catch)]
+    pub fn on_event(this: &Plugin, e: SdkEvent) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(structural, method, js_name = onShutdown, catch)]
+    pub fn on_shutdown(this: &Plugin) -> Result<Promise, JsValue>;
+}
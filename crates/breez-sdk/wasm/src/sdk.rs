@@ -6,7 +6,6 @@ use wasm_bindgen::prelude::*;
 use crate::{
     error::WasmResult,
     event::{EventListener, WasmEventListener},
-    issuer::TokenIssuer,
     logger::{Logger, WasmTracingLayer},
     models::{chain_service::RecommendedFees, *},
     sdk_builder::SdkBuilder,
@@ -77,16 +76,35 @@ pub fn default_config(network: Network) -> Config {
     breez_sdk_spark::default_config(network.into()).into()
 }
 
+#[wasm_bindgen(js_name = "defaultProfileConfig")]
+pub fn default_profile_config(network: Network, profile: ConfigProfile) -> Config {
+    breez_sdk_spark::default_profile_config(network.into(), profile.into()).into()
+}
+
 #[wasm_bindgen(js_name = "defaultServerConfig")]
 pub fn default_server_config(network: Network) -> Config {
     breez_sdk_spark::default_server_config(network.into()).into()
 }
 
+#[wasm_bindgen(js_name = "decodePaymentRequest")]
+pub fn decode_payment_request(input: String) -> WasmResult<DecodedPaymentRequest> {
+    Ok(breez_sdk_spark::decode_payment_request(input)?.into())
+}
+
 #[wasm_bindgen(js_name = "getSparkStatus")]
 pub async fn get_spark_status() -> WasmResult<SparkStatus> {
     Ok(breez_sdk_spark::get_spark_status().await?.into())
 }
 
+#[wasm_bindgen(js_name = "discoverAccounts")]
+pub async fn discover_accounts(
+    request: DiscoverAccountsRequest,
+) -> WasmResult<DiscoverAccountsResponse> {
+    Ok(breez_sdk_spark::discover_accounts(request.into())
+        .await?
+        .into())
+}
+
 /// The two external signers for the SDK's signer-based connect. Returned by
 /// `defaultExternalSigners` (seed) and `createTurnkeySigner` (Turnkey); pass
 /// both halves to `connectWithSigner` or `SdkBuilder.newWithSigner`.
@@ -203,6 +221,17 @@ impl BreezSdk {
             .await
     }
 
+    #[wasm_bindgen(js_name = "addFilteredEventListener")]
+    pub async fn add_filtered_event_listener(
+        &self,
+        listener: EventListener,
+        filter: EventFilter,
+    ) -> String {
+        self.sdk
+            .add_filtered_event_listener(Box::new(WasmEventListener { listener }), filter.into())
+            .await
+    }
+
     #[wasm_bindgen(js_name = "removeEventListener")]
     pub async fn remove_event_listener(&self, id: &str) -> bool {
         self.sdk.remove_event_listener(id).await
@@ -264,6 +293,33 @@ impl BreezSdk {
             .into())
     }
 
+    #[wasm_bindgen(js_name = "exportEmergencyExitPackage")]
+    pub async fn export_emergency_exit_package(
+        &self,
+        request: ExportEmergencyExitPackageRequest,
+        signer: crate::signer::JsCpfpSigner,
+    ) -> WasmResult<EmergencyExitPackage> {
+        let signer = std::sync::Arc::new(crate::signer::WasmCpfpSigner::new(signer));
+        Ok(self
+            .sdk
+            .export_emergency_exit_package(request.into(), signer)
+            .await?
+            .into())
+    }
+
+    #[wasm_bindgen(js_name = "exportViewerBundle")]
+    pub fn export_viewer_bundle(&self) -> ViewerBundle {
+        self.sdk.export_viewer_bundle().into()
+    }
+
+    #[wasm_bindgen(js_name = "sweepPrivateKey")]
+    pub async fn sweep_private_key(
+        &self,
+        request: SweepPrivateKeyRequest,
+    ) -> WasmResult<SweepPrivateKeyResponse> {
+        Ok(self.sdk.sweep_private_key(request.into()).await?.into())
+    }
+
     #[wasm_bindgen(js_name = "receivePayment")]
     pub async fn receive_payment(
         &self,
@@ -280,6 +336,22 @@ impl BreezSdk {
         Ok(self.sdk.claim_htlc_payment(request.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "createHtlcReceive")]
+    pub async fn create_htlc_receive(
+        &self,
+        request: CreateHtlcReceiveRequest,
+    ) -> WasmResult<CreateHtlcReceiveResponse> {
+        Ok(self.sdk.create_htlc_receive(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "getHtlcReceive")]
+    pub async fn get_htlc_receive(
+        &self,
+        request: GetHtlcReceiveRequest,
+    ) -> WasmResult<GetHtlcReceiveResponse> {
+        Ok(self.sdk.get_htlc_receive(request.into()).await?.into())
+    }
+
     #[wasm_bindgen(js_name = "prepareSendPayment")]
     pub async fn prepare_send_payment(
         &self,
@@ -361,6 +433,11 @@ impl BreezSdk {
         Ok(self.sdk.send_payment(request.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "paySplit")]
+    pub async fn pay_split(&self, request: PaySplitRequest) -> WasmResult<PaySplitResponse> {
+        Ok(self.sdk.pay_split(request.into()).await?.into())
+    }
+
     #[wasm_bindgen(js_name = "publishSignedTransferPackage")]
     pub async fn publish_signed_transfer_package(
         &self,
@@ -378,6 +455,70 @@ impl BreezSdk {
         Ok(self.sdk.sync_wallet(request.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "triggerSync")]
+    pub async fn trigger_sync(&self, request: TriggerSyncRequest) -> WasmResult<()> {
+        Ok(self.sdk.trigger_sync(request.into()).await?)
+    }
+
+    #[wasm_bindgen(js_name = "processNotification")]
+    pub async fn process_notification(
+        &self,
+        request: ProcessNotificationRequest,
+    ) -> WasmResult<ProcessNotificationResponse> {
+        Ok(self.sdk.process_notification(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "startSyncCapture")]
+    pub async fn start_sync_capture(&self) -> WasmResult<()> {
+        Ok(self.sdk.start_sync_capture().await?)
+    }
+
+    #[wasm_bindgen(js_name = "stopSyncCapture")]
+    pub async fn stop_sync_capture(&self) -> WasmResult<StopSyncCaptureResponse> {
+        Ok(self.sdk.stop_sync_capture().await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "recoverStuckPayment")]
+    pub async fn recover_stuck_payment(
+        &self,
+        request: RecoverStuckPaymentRequest,
+    ) -> WasmResult<RecoverStuckPaymentResponse> {
+        Ok(self.sdk.recover_stuck_payment(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "checkStorageIntegrity")]
+    pub async fn check_storage_integrity(
+        &self,
+        request: CheckStorageIntegrityRequest,
+    ) -> WasmResult<CheckStorageIntegrityResponse> {
+        Ok(self
+            .sdk
+            .check_storage_integrity(request.into())
+            .await?
+            .into())
+    }
+
+    #[wasm_bindgen(js_name = "getWithdrawStatus")]
+    pub async fn get_withdraw_status(
+        &self,
+        request: GetWithdrawStatusRequest,
+    ) -> WasmResult<GetWithdrawStatusResponse> {
+        Ok(self.sdk.get_withdraw_status(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "diagnoseReceive")]
+    pub async fn diagnose_receive(
+        &self,
+        request: DiagnoseReceiveRequest,
+    ) -> WasmResult<DiagnoseReceiveResponse> {
+        Ok(self.sdk.diagnose_receive(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "warmUp")]
+    pub async fn warm_up(&self) -> WasmResult<()> {
+        Ok(self.sdk.warm_up().await?)
+    }
+
     #[wasm_bindgen(js_name = "listPayments")]
     pub async fn list_payments(
         &self,
@@ -391,6 +532,92 @@ impl BreezSdk {
         Ok(self.sdk.get_payment(request.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "setPaymentDispute")]
+    pub async fn set_payment_dispute(
+        &self,
+        request: SetPaymentDisputeRequest,
+    ) -> WasmResult<SetPaymentDisputeResponse> {
+        Ok(self.sdk.set_payment_dispute(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "listUnacknowledgedPayments")]
+    pub async fn list_unacknowledged_payments(
+        &self,
+        request: ListUnacknowledgedPaymentsRequest,
+    ) -> WasmResult<ListUnacknowledgedPaymentsResponse> {
+        Ok(self
+            .sdk
+            .list_unacknowledged_payments(request.into())
+            .await?
+            .into())
+    }
+
+    #[wasm_bindgen(js_name = "acknowledgePayment")]
+    pub async fn acknowledge_payment(
+        &self,
+        request: AcknowledgePaymentRequest,
+    ) -> WasmResult<AcknowledgePaymentResponse> {
+        Ok(self.sdk.acknowledge_payment(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "getPaymentFiatValue")]
+    pub async fn get_payment_fiat_value(
+        &self,
+        request: GetPaymentFiatValueRequest,
+    ) -> WasmResult<GetPaymentFiatValueResponse> {
+        Ok(self
+            .sdk
+            .get_payment_fiat_value(request.into())
+            .await?
+            .into())
+    }
+
+    #[wasm_bindgen(js_name = "waitForPayment")]
+    pub async fn wait_for_payment(
+        &self,
+        request: WaitForPaymentRequest,
+    ) -> WasmResult<WaitForPaymentResponse> {
+        Ok(self.sdk.wait_for_payment(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "importPayments")]
+    pub async fn import_payments(
+        &self,
+        request: ImportPaymentsRequest,
+    ) -> WasmResult<ImportPaymentsResponse> {
+        Ok(self.sdk.import_payments(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "exportPayments")]
+    pub async fn export_payments(
+        &self,
+        request: ExportPaymentsRequest,
+    ) -> WasmResult<ExportPaymentsResponse> {
+        Ok(self.sdk.export_payments(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "backup")]
+    pub async fn backup(&self) -> WasmResult<BackupResponse> {
+        Ok(self.sdk.backup().await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "restore")]
+    pub async fn restore(&self, request: RestoreRequest) -> WasmResult<RestoreResponse> {
+        Ok(self.sdk.restore(request.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "listClaimFeeSponsorships")]
+    pub async fn list_claim_fee_sponsorships(
+        &self,
+        request: ListClaimFeeSponsorshipsRequest,
+    ) -> WasmResult<ListClaimFeeSponsorshipsResponse> {
+        Ok(self
+            .sdk
+            .list_claim_fee_sponsorships(request.into())
+            .await?
+            .into())
+    }
+
     #[wasm_bindgen(js_name = "claimDeposit")]
     pub async fn claim_deposit(
         &self,
@@ -399,6 +626,38 @@ impl BreezSdk {
         Ok(self.sdk.claim_deposit(request.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "setDepositClaimOverride")]
+    pub async fn set_deposit_claim_override(
+        &self,
+        request: SetDepositClaimOverrideRequest,
+    ) -> WasmResult<()> {
+        Ok(self.sdk.set_deposit_claim_override(request.into()).await?)
+    }
+
+    #[wasm_bindgen(js_name = "estimateClaimDepositFee")]
+    pub async fn estimate_claim_deposit_fee(
+        &self,
+        request: ClaimDepositRequest,
+    ) -> WasmResult<EstimateClaimDepositFeeResponse> {
+        Ok(self
+            .sdk
+            .estimate_claim_deposit_fee(request.into())
+            .await?
+            .into())
+    }
+
+    #[wasm_bindgen(js_name = "estimateRefundDepositFee")]
+    pub async fn estimate_refund_deposit_fee(
+        &self,
+        request: RefundDepositRequest,
+    ) -> WasmResult<EstimateRefundDepositFeeResponse> {
+        Ok(self
+            .sdk
+            .estimate_refund_deposit_fee(request.into())
+            .await?
+            .into())
+    }
+
     #[wasm_bindgen(js_name = "refundDeposit")]
     pub async fn refund_deposit(
         &self,
@@ -490,6 +749,16 @@ impl BreezSdk {
         Ok(self.sdk.list_fiat_rates().await?.into())
     }
 
+    #[wasm_bindgen(js_name = "getServiceStatus")]
+    pub async fn get_service_status(&self) -> WasmResult<GetServiceStatusResponse> {
+        Ok(self.sdk.get_service_status().await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "getDataUsage")]
+    pub fn get_data_usage(&self) -> GetDataUsageResponse {
+        self.sdk.get_data_usage().into()
+    }
+
     #[wasm_bindgen(js_name = "recommendedFees")]
     pub async fn recommended_fees(&self) -> WasmResult<RecommendedFees> {
         Ok(self.sdk.recommended_fees().await?.into())
@@ -503,6 +772,19 @@ impl BreezSdk {
         Ok(self.sdk.get_tokens_metadata(request.into()).await?.into())
     }
 
+    #[cfg(feature = "token-announcements")]
+    #[wasm_bindgen(js_name = "getTokenAnnouncements")]
+    pub async fn get_token_announcements(
+        &self,
+        request: crate::models::token_announcement::GetTokenAnnouncementsRequest,
+    ) -> WasmResult<crate::models::token_announcement::GetTokenAnnouncementsResponse> {
+        Ok(self
+            .sdk
+            .get_token_announcements(request.into())
+            .await?
+            .into())
+    }
+
     #[wasm_bindgen(js_name = "signMessage")]
     pub async fn sign_message(
         &self,
@@ -519,6 +801,14 @@ impl BreezSdk {
         Ok(self.sdk.check_message(request.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "deriveChildSecret")]
+    pub async fn derive_child_secret(
+        &self,
+        request: DeriveChildSecretRequest,
+    ) -> WasmResult<DeriveChildSecretResponse> {
+        Ok(self.sdk.derive_child_secret(request.into()).await?.into())
+    }
+
     #[wasm_bindgen(js_name = "getUserSettings")]
     pub async fn get_user_settings(&self) -> WasmResult<UserSettings> {
         Ok(self.sdk.get_user_settings().await?.into())
@@ -529,14 +819,27 @@ impl BreezSdk {
         Ok(self.sdk.update_user_settings(request.into()).await?)
     }
 
+    #[cfg(feature = "issuer")]
     #[wasm_bindgen(js_name = "getTokenIssuer")]
-    pub fn get_token_issuer(&self) -> TokenIssuer {
+    pub fn get_token_issuer(&self) -> crate::issuer::TokenIssuer {
         let token_issuer = self.sdk.get_token_issuer();
-        TokenIssuer {
+        crate::issuer::TokenIssuer {
             token_issuer: Rc::new(token_issuer),
         }
     }
 
+    #[cfg(feature = "dev-tools")]
+    #[wasm_bindgen(js_name = "getDevTools")]
+    pub fn get_dev_tools(
+        &self,
+        config: crate::models::dev_tools::DevToolsConfig,
+    ) -> WasmResult<crate::dev_tools::DevTools> {
+        let dev_tools = self.sdk.get_dev_tools(config.into())?;
+        Ok(crate::dev_tools::DevTools {
+            dev_tools: Rc::new(dev_tools),
+        })
+    }
+
     #[wasm_bindgen(js_name = "optimizeLeaves")]
     pub async fn optimize_leaves(
         &self,
@@ -591,6 +894,33 @@ impl BreezSdk {
             .collect())
     }
 
+    #[wasm_bindgen(js_name = "getFeeEstimationStats")]
+    pub async fn get_fee_estimation_stats(&self) -> WasmResult<FeeEstimationStats> {
+        Ok(self.sdk.get_fee_estimation_stats().await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "updateConfig")]
+    pub async fn update_config(&self, patch: ConfigPatch) -> WasmResult<()> {
+        Ok(self.sdk.update_config(patch.into()).await?)
+    }
+
+    #[wasm_bindgen(js_name = "getWithdrawalAllowlist")]
+    pub async fn get_withdrawal_allowlist(&self) -> WasmResult<WithdrawalAllowlist> {
+        Ok(self.sdk.get_withdrawal_allowlist().await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "updateWithdrawalAllowlist")]
+    pub async fn update_withdrawal_allowlist(
+        &self,
+        request: UpdateWithdrawalAllowlistRequest,
+    ) -> WasmResult<WithdrawalAllowlist> {
+        Ok(self
+            .sdk
+            .update_withdrawal_allowlist(request.into())
+            .await?
+            .into())
+    }
+
     #[wasm_bindgen(js_name = "addContact")]
     pub async fn add_contact(&self, request: AddContactRequest) -> WasmResult<Contact> {
         Ok(self.sdk.add_contact(request.into()).await?.into())
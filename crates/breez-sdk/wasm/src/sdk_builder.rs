@@ -4,12 +4,22 @@ use crate::{
     error::{WasmError, WasmResult},
     logger::{Logger, WASM_LOGGER},
     models::{
-        Config, Credentials, Network, Seed,
-        chain_service::{BitcoinChainService, ChainApiType, WasmBitcoinChainService},
+        Config, ConflictResolutionStrategy, Credentials, ExportEmergencyExitPackageRequest,
+        Network, Seed, ViewerBundle,
+        backup_store::{BackupStore, EmergencyExitStore, WasmBackupStore, WasmEmergencyExitStore},
+        chain_service::{
+            BitcoinChainService, ChainApiType, ChainServiceEndpoint, WasmBitcoinChainService,
+        },
+        claim_fee_sponsor::{ClaimFeeSponsor, WasmClaimFeeSponsor},
+        conflict_resolver::{ConflictResolver, WasmConflictResolver},
+        dns_resolver::{DnsResolver, WasmDnsResolver},
         fiat_service::{FiatService, WasmFiatService},
+        payment_approver::{PaymentApprover, WasmPaymentApprover},
         payment_observer::{PaymentObserver, WasmPaymentObserver},
         rest_client::{RestClient, WasmRestClient},
+        secret_store::{SecretStore, WasmSecretStore},
         session_store::{DefaultSessionStore, SessionStore, WasmSessionStore},
+        travel_rule_provider::{TravelRuleProvider, WasmTravelRuleProvider},
     },
     persist::{
         Storage, WasmStorage,
@@ -21,13 +31,16 @@ use crate::{
             create_postgres_token_store_with_pool, create_postgres_tree_store_with_pool,
         },
     },
+    plugin::{Plugin, WasmPlugin},
     sdk::BreezSdk,
     sdk_context::{SharedMysqlPool, SharedPostgresPool, WasmSdkContext},
     token_store::WasmTokenStore,
     tree_store::WasmTreeStore,
 };
 use bitcoin::secp256k1::PublicKey;
-use breez_sdk_spark::{PrebuiltBackend, SessionStoreAdapter, StorageBackend, identity_public_key};
+use breez_sdk_spark::{
+    PrebuiltBackend, SessionStoreAdapter, StorageBackend, identity_public_key, storage_tenant_key,
+};
 use platform_utils::tokio::sync::OnceCell;
 use wasm_bindgen::prelude::*;
 
@@ -145,6 +158,8 @@ enum WasmStorageConfigKind {
     /// File-based storage rooted at `storage_dir` (IndexedDB in the browser,
     /// SQLite under Node.js).
     Default { storage_dir: String },
+    /// SQLite storage persisted in the browser's Origin Private File System.
+    Opfs { storage_dir: String },
     /// `PostgreSQL`-backed storage.
     Postgres { config: PostgresStorageConfig },
     /// `MySQL`-backed storage.
@@ -153,8 +168,8 @@ enum WasmStorageConfigKind {
 
 /// Selects one of the SDK's built-in storage backends.
 ///
-/// Construct it via `defaultStorage`, `postgresStorage` or `mysqlStorage` and
-/// pass it to `SdkBuilder.withStorageBackend`.
+/// Construct it via `defaultStorage`, `opfsStorage`, `postgresStorage` or
+/// `mysqlStorage` and pass it to `SdkBuilder.withStorageBackend`.
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct WasmStorageConfig {
@@ -171,6 +186,17 @@ pub fn default_storage_config(storage_dir: String) -> WasmStorageConfig {
     }
 }
 
+/// SQLite storage rooted at `storageDir`, persisted in the browser's Origin
+/// Private File System. Browser only. On first use, the data of the IndexedDB
+/// storage for the same `storageDir` is migrated into it.
+#[wasm_bindgen(js_name = "opfsStorage")]
+#[must_use]
+pub fn opfs_storage_config(storage_dir: String) -> WasmStorageConfig {
+    WasmStorageConfig {
+        kind: WasmStorageConfigKind::Opfs { storage_dir },
+    }
+}
+
 /// `PostgreSQL`-backed storage built from `config`.
 #[wasm_bindgen(js_name = "postgresStorage")]
 #[must_use]
@@ -221,6 +247,7 @@ pub struct SdkBuilder {
     /// JS MySQL pool supplied via `withSharedContext(ctx_with_pool)`.
     context_mysql_pool: Option<SharedMysqlPool>,
     account_number: Option<u32>,
+    storage_namespace: Option<String>,
 }
 
 #[wasm_bindgen]
@@ -239,6 +266,7 @@ impl SdkBuilder {
             context_postgres_pool: None,
             context_mysql_pool: None,
             account_number: None,
+            storage_namespace: None,
         }
     }
 
@@ -270,6 +298,7 @@ impl SdkBuilder {
             context_postgres_pool: None,
             context_mysql_pool: None,
             account_number: None,
+            storage_namespace: None,
         }
     }
 
@@ -305,6 +334,46 @@ impl SdkBuilder {
             context_postgres_pool: None,
             context_mysql_pool: None,
             account_number: None,
+            storage_namespace: None,
+        }
+    }
+
+    /// Creates a new `SdkBuilder` for a watch-only wallet, which holds no keys.
+    /// Operations that need a signature fail, and the session store must hold
+    /// valid sessions for the wallet.
+    #[wasm_bindgen(js_name = "newWatchOnly")]
+    pub fn new_watch_only(config: Config, identity_public_key: String) -> Self {
+        let config_core: breez_sdk_spark::Config = config.into();
+
+        Self {
+            network: config_core.network,
+            seed: breez_sdk_spark::Seed::Entropy(vec![]), // Placeholder, won't be used
+            builder: breez_sdk_spark::SdkBuilder::new_watch_only(config_core, identity_public_key),
+            storage_config: None,
+            storage: None,
+            context_postgres_pool: None,
+            context_mysql_pool: None,
+            account_number: None,
+            storage_namespace: None,
+        }
+    }
+
+    /// Creates a new `SdkBuilder` for a watch-only wallet viewing the wallet
+    /// of a bundle from `exportViewerBundle`.
+    #[wasm_bindgen(js_name = "newViewer")]
+    pub fn new_viewer(config: Config, bundle: ViewerBundle) -> Self {
+        let config_core: breez_sdk_spark::Config = config.into();
+
+        Self {
+            network: config_core.network,
+            seed: breez_sdk_spark::Seed::Entropy(vec![]), // Placeholder, won't be used
+            builder: breez_sdk_spark::SdkBuilder::new_viewer(config_core, bundle.into()),
+            storage_config: None,
+            storage: None,
+            context_postgres_pool: None,
+            context_mysql_pool: None,
+            account_number: None,
+            storage_namespace: None,
         }
     }
 
@@ -323,7 +392,7 @@ impl SdkBuilder {
     /// Sets one of the SDK's built-in storage backends.
     ///
     /// Construct the [`WasmStorageConfig`] via `defaultStorage`,
-    /// `postgresStorage` or `mysqlStorage`.
+    /// `opfsStorage`, `postgresStorage` or `mysqlStorage`.
     #[wasm_bindgen(js_name = "withStorageBackend")]
     pub fn with_storage_backend(mut self, config: WasmStorageConfig) -> Self {
         self.storage_config = Some(config);
@@ -342,6 +411,17 @@ impl SdkBuilder {
         self
     }
 
+    /// Sets the store for secret material, such as the Breez partner JWT,
+    /// replacing the main storage. Secrets still in the main storage are moved
+    /// into it when the SDK is built.
+    #[wasm_bindgen(js_name = "withSecretStore")]
+    pub fn with_secret_store(mut self, secret_store: SecretStore) -> Self {
+        self.builder = self
+            .builder
+            .with_secret_store(Arc::new(WasmSecretStore { secret_store }));
+        self
+    }
+
     /// **Deprecated.** Use `withStorageBackend(postgresStorage(config))`.
     #[wasm_bindgen(js_name = "withPostgresBackend")]
     #[allow(clippy::unnecessary_wraps)]
@@ -376,6 +456,13 @@ impl SdkBuilder {
         self
     }
 
+    #[wasm_bindgen(js_name = "withStorageNamespace")]
+    pub fn with_storage_namespace(mut self, namespace: String) -> Self {
+        self.storage_namespace = Some(namespace.clone());
+        self.builder = self.builder.with_storage_namespace(namespace);
+        self
+    }
+
     #[wasm_bindgen(js_name = "withChainService")]
     pub fn with_chain_service(mut self, chain_service: BitcoinChainService) -> Self {
         self.builder = self
@@ -401,6 +488,17 @@ impl SdkBuilder {
         self
     }
 
+    #[wasm_bindgen(js_name = "withRestChainServiceEndpoints")]
+    pub fn with_rest_chain_service_endpoints(
+        mut self,
+        endpoints: Vec<ChainServiceEndpoint>,
+    ) -> Self {
+        self.builder = self
+            .builder
+            .with_rest_chain_service_endpoints(endpoints.into_iter().map(Into::into).collect());
+        self
+    }
+
     #[wasm_bindgen(js_name = "withFiatService")]
     pub fn with_fiat_service(mut self, fiat_service: FiatService) -> Self {
         self.builder = self.builder.with_fiat_service(Arc::new(WasmFiatService {
@@ -417,6 +515,20 @@ impl SdkBuilder {
         self
     }
 
+    #[wasm_bindgen(js_name = "withDnsResolver")]
+    pub fn with_dns_resolver(mut self, dns_resolver: DnsResolver) -> Self {
+        self.builder = self.builder.with_dns_resolver(Arc::new(WasmDnsResolver {
+            inner: dns_resolver,
+        }));
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withDohDnsResolver")]
+    pub fn with_doh_dns_resolver(mut self, url: Option<String>) -> Self {
+        self.builder = self.builder.with_doh_dns_resolver(url);
+        self
+    }
+
     #[wasm_bindgen(js_name = "withPaymentObserver")]
     pub fn with_payment_observer(mut self, payment_observer: PaymentObserver) -> Self {
         self.builder = self
@@ -425,6 +537,89 @@ impl SdkBuilder {
         self
     }
 
+    #[wasm_bindgen(js_name = "withPaymentApprover")]
+    pub fn with_payment_approver(mut self, payment_approver: PaymentApprover) -> Self {
+        self.builder = self
+            .builder
+            .with_payment_approver(Arc::new(WasmPaymentApprover { payment_approver }));
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withClaimFeeSponsor")]
+    pub fn with_claim_fee_sponsor(mut self, claim_fee_sponsor: ClaimFeeSponsor) -> Self {
+        self.builder = self
+            .builder
+            .with_claim_fee_sponsor(Arc::new(WasmClaimFeeSponsor { claim_fee_sponsor }));
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withBackupStore")]
+    pub fn with_backup_store(mut self, backup_store: BackupStore) -> Self {
+        self.builder = self
+            .builder
+            .with_backup_store(Arc::new(WasmBackupStore { backup_store }));
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withEmergencyExitPackage")]
+    pub fn with_emergency_exit_package(
+        mut self,
+        request: ExportEmergencyExitPackageRequest,
+        signer: crate::signer::JsCpfpSigner,
+        store: EmergencyExitStore,
+    ) -> Self {
+        self.builder = self.builder.with_emergency_exit_package(
+            request.into(),
+            Arc::new(crate::signer::WasmCpfpSigner::new(signer)),
+            Arc::new(WasmEmergencyExitStore {
+                emergency_exit_store: store,
+            }),
+        );
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withConflictResolution")]
+    pub fn with_conflict_resolution(mut self, strategy: ConflictResolutionStrategy) -> Self {
+        self.builder = self.builder.with_conflict_resolution(strategy.into());
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withConflictResolver")]
+    pub fn with_conflict_resolver(mut self, conflict_resolver: ConflictResolver) -> Self {
+        self.builder = self
+            .builder
+            .with_conflict_resolver(Arc::new(WasmConflictResolver { conflict_resolver }));
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withTravelRuleProvider")]
+    pub fn with_travel_rule_provider(mut self, travel_rule_provider: TravelRuleProvider) -> Self {
+        self.builder = self
+            .builder
+            .with_travel_rule_provider(Arc::new(WasmTravelRuleProvider {
+                travel_rule_provider,
+            }));
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withConfigChangeAccepted")]
+    pub fn with_config_change_accepted(mut self) -> Self {
+        self.builder = self.builder.with_config_change_accepted();
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withHeadlessMode")]
+    pub fn with_headless_mode(mut self) -> Self {
+        self.builder = self.builder.with_headless_mode();
+        self
+    }
+
+    #[wasm_bindgen(js_name = "withPlugin")]
+    pub fn with_plugin(mut self, plugin: Plugin) -> Self {
+        self.builder = self.builder.with_plugin(Arc::new(WasmPlugin { plugin }));
+        self
+    }
+
     #[wasm_bindgen(js_name = "build")]
     pub async fn build(mut self) -> WasmResult<BreezSdk> {
         // Derive the tenant identity from the seed. The JS-side stores use it
@@ -436,6 +631,7 @@ impl SdkBuilder {
         )
         .map_err(WasmError::new)?
         .serialize();
+        let identity_bytes = storage_tenant_key(&identity_bytes, self.storage_namespace.as_deref());
 
         let custom_storage = match (
             self.storage_config,
@@ -488,9 +684,14 @@ async fn resolve_storage_config(
 ) -> WasmResult<Arc<dyn StorageBackend>> {
     match config.kind {
         WasmStorageConfigKind::Default { storage_dir } => {
-            let identity_pub_key = PublicKey::from_slice(identity).map_err(WasmError::new)?;
             let storage = Arc::new(WasmStorage {
-                storage: default_storage(&storage_dir, network, &identity_pub_key).await?,
+                storage: default_storage(&storage_dir, network, identity).await?,
+            });
+            Ok(Arc::new(PrebuiltBackend::new(storage, None, None, None)))
+        }
+        WasmStorageConfigKind::Opfs { storage_dir } => {
+            let storage = Arc::new(WasmStorage {
+                storage: opfs_storage(&storage_dir, network, identity).await?,
             });
             Ok(Arc::new(PrebuiltBackend::new(storage, None, None, None)))
         }
@@ -645,13 +846,23 @@ fn get_wasm_logger_ref() -> Option<&'static Logger> {
 async fn default_storage(
     data_dir: &str,
     network: &breez_sdk_spark::Network,
-    identity_pub_key: &PublicKey,
+    tenant_key: &[u8],
 ) -> WasmResult<Storage> {
-    let db_path = breez_sdk_spark::default_storage_path(data_dir, network, identity_pub_key)?;
+    let db_path = breez_sdk_spark::tenant_storage_path(data_dir, network, tenant_key)?;
     let logger_ref = get_wasm_logger_ref();
     Ok(create_default_storage(db_path.to_string_lossy().as_ref(), logger_ref).await?)
 }
 
+async fn opfs_storage(
+    data_dir: &str,
+    network: &breez_sdk_spark::Network,
+    tenant_key: &[u8],
+) -> WasmResult<Storage> {
+    let db_path = breez_sdk_spark::tenant_storage_path(data_dir, network, tenant_key)?;
+    let logger_ref = get_wasm_logger_ref();
+    Ok(create_opfs_storage(db_path.to_string_lossy().as_ref(), logger_ref).await?)
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_name = "createDefaultStorage", catch)]
@@ -659,4 +870,10 @@ extern "C" {
         data_dir: &str,
         logger: Option<&Logger>,
     ) -> Result<crate::persist::Storage, JsValue>;
+
+    #[wasm_bindgen(js_name = "createOpfsStorage", catch)]
+    async fn create_opfs_storage(
+        data_dir: &str,
+        logger: Option<&Logger>,
+    ) -> Result<crate::persist::Storage, JsValue>;
 }
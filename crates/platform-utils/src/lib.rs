@@ -5,6 +5,7 @@
 
 mod auth;
 pub mod http;
+mod metered;
 
 #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
 pub use tokio;
@@ -22,3 +23,4 @@ pub use auth::{
     ContentType, add_basic_auth_header, add_content_type_header, make_basic_auth_header,
 };
 pub use http::{DefaultHttpClient, HttpClient, HttpError, HttpResponse, create_http_client};
+pub use metered::{ByteCounter, MeteredHttpClient};
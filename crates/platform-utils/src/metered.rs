@@ -0,0 +1,118 @@
+//! Byte accounting for network clients.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::http::{HttpClient, HttpError, HttpResponse};
+
+/// Counts the bytes sent and received by a network client.
+#[derive(Debug, Default)]
+pub struct ByteCounter {
+    sent: AtomicU64,
+    received: AtomicU64,
+}
+
+impl ByteCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_sent(&self, bytes: u64) {
+        self.sent.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_received(&self, bytes: u64) {
+        self.received.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    pub fn received(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+}
+
+/// An [`HttpClient`] counting the bytes of the requests and responses passing
+/// through it. Counts cover urls, headers and bodies, not TLS or HTTP framing.
+pub struct MeteredHttpClient {
+    inner: Arc<dyn HttpClient>,
+    counter: Arc<ByteCounter>,
+}
+
+impl MeteredHttpClient {
+    pub fn new(inner: Arc<dyn HttpClient>, counter: Arc<ByteCounter>) -> Self {
+        Self { inner, counter }
+    }
+
+    fn record_request(
+        &self,
+        url: &str,
+        headers: Option<&HashMap<String, String>>,
+        body: Option<&String>,
+    ) {
+        let bytes = url
+            .len()
+            .saturating_add(headers.map_or(0, headers_len))
+            .saturating_add(body.map_or(0, String::len));
+        self.counter.add_sent(bytes as u64);
+    }
+
+    fn record_response(&self, response: &Result<HttpResponse, HttpError>) {
+        let bytes = match response {
+            Ok(response) => response
+                .body
+                .len()
+                .saturating_add(headers_len(&response.headers)),
+            Err(HttpError::Status { body, .. }) => body.len(),
+            Err(_) => 0,
+        };
+        self.counter.add_received(bytes as u64);
+    }
+}
+
+fn headers_len(headers: &HashMap<String, String>) -> usize {
+    headers.iter().fold(0usize, |len, (k, v)| {
+        len.saturating_add(k.len()).saturating_add(v.len())
+    })
+}
+
+#[macros::async_trait]
+impl HttpClient for MeteredHttpClient {
+    async fn get(
+        &self,
+        url: String,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<HttpResponse, HttpError> {
+        self.record_request(&url, headers.as_ref(), None);
+        let response = self.inner.get(url, headers).await;
+        self.record_response(&response);
+        response
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: Option<HashMap<String, String>>,
+        body: Option<String>,
+    ) -> Result<HttpResponse, HttpError> {
+        self.record_request(&url, headers.as_ref(), body.as_ref());
+        let response = self.inner.post(url, headers, body).await;
+        self.record_response(&response);
+        response
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: Option<HashMap<String, String>>,
+        body: Option<String>,
+    ) -> Result<HttpResponse, HttpError> {
+        self.record_request(&url, headers.as_ref(), body.as_ref());
+        let response = self.inner.delete(url, headers, body).await;
+        self.record_response(&response);
+        response
+    }
+}
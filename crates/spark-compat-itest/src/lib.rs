@@ -45,9 +45,10 @@ fn find_vout(tx: &bitcoin::Transaction, address: &bitcoin::Address) -> Result<u3
 /// Generates the version-specific test API. Both modules expose the same
 /// functions over their own `spark-wallet` build; the crate path, the signer
 /// wiring, and the wallet construction (`$wallet`) are passed per version,
-/// since the builder API differs between releases.
+/// since the builder API differs between releases. Config fields added after
+/// the previous release are passed per version too.
 macro_rules! version_module {
-    ($name:ident, $krate:ident, $build_signer:item, $wallet:item) => {
+    ($name:ident, $krate:ident, { $($config_fields:tt)* }, $build_signer:item, $wallet:item) => {
         pub mod $name {
             use std::str::FromStr;
             use std::sync::Arc;
@@ -100,6 +101,7 @@ macro_rules! version_module {
                     },
                     self_payment_allowed: false,
                     max_concurrent_claims: 1,
+                    $($config_fields)*
                 })
             }
 
@@ -194,6 +196,7 @@ macro_rules! version_module {
 version_module!(
     vold,
     spark_wallet_old,
+    {},
     /// The previous release's builder takes the low-level `Signer` directly.
     fn build_signer(seed: &[u8; 32]) -> Result<Arc<spark_wallet_old::DefaultSigner>> {
         Ok(Arc::new(spark_wallet_old::DefaultSigner::new(
@@ -215,6 +218,12 @@ version_module!(
 version_module!(
     vnew,
     spark_wallet,
+    {
+        claim_batch_size: 50,
+        claim_batch_interval_ms: 0,
+        operator_rate_limit: None,
+        event_transport: spark_wallet::EventTransport::Grpc,
+    },
     /// The current builder takes the high-level `SparkSigner`; wrap the
     /// in-process signer in the adapter, as production does.
     fn build_signer(seed: &[u8; 32]) -> Result<Arc<spark_wallet::SparkSignerAdapter>> {
@@ -1,8 +1,9 @@
 use anyhow::Result;
 use rand::Rng;
 use spark_wallet::{
-    DefaultSigner, LeafOptimizationOptions, Network, OperatorConfig, OperatorPoolConfig, PublicKey,
-    RetryConfig, ServiceProviderConfig, SparkWalletConfig, TokenOutputsOptimizationOptions,
+    DefaultSigner, EventTransport, LeafOptimizationOptions, Network, OperatorConfig,
+    OperatorPoolConfig, PublicKey, RetryConfig, ServiceProviderConfig, SparkWalletConfig,
+    TokenOutputsOptimizationOptions,
 };
 use tracing::info;
 
@@ -76,6 +77,7 @@ impl TestFixtures {
             operator_pool: OperatorPoolConfig::new(0, operator_configs)?,
             split_secret_threshold: crate::fixtures::spark_so::MIN_SIGNERS as u32,
             reconnect_interval_seconds: 1,
+            event_transport: EventTransport::Grpc,
             service_provider_config: ServiceProviderConfig {
                 base_url: "".to_string(),
                 schema_endpoint: None,
@@ -93,6 +95,9 @@ impl TestFixtures {
             },
             self_payment_allowed: false,
             max_concurrent_claims: 1,
+            claim_batch_size: 50,
+            claim_batch_interval_ms: 0,
+            operator_rate_limit: None,
         })
     }
 }
@@ -4,7 +4,8 @@ use bitcoin::secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
 use spark::{
     Network,
-    operator::{OperatorConfig, OperatorPoolConfig},
+    events::EventTransport,
+    operator::{OperatorConfig, OperatorPoolConfig, rpc::OperatorRateLimit},
     ssp::{RetryConfig, ServiceProviderConfig},
     token::{DEFAULT_MAX_TOKEN_TX_INPUTS, TokensConfig},
     tree::LeafOptimizationOptions,
@@ -17,6 +18,8 @@ pub struct SparkWalletConfig {
     pub network: Network,
     pub operator_pool: OperatorPoolConfig,
     pub reconnect_interval_seconds: u64,
+    /// Transport used to subscribe to server events. Defaults to gRPC.
+    pub event_transport: EventTransport,
     pub service_provider_config: ServiceProviderConfig,
     pub split_secret_threshold: u32,
     pub tokens_config: TokensConfig,
@@ -30,6 +33,20 @@ pub struct SparkWalletConfig {
     /// Default is 1 (sequential claiming). Increase for server environments
     /// with high incoming payment volume to improve throughput.
     pub max_concurrent_claims: u32,
+    /// Maximum number of pending transfers claimed per batch.
+    ///
+    /// When catching up on many pending transfers, claims are split into
+    /// batches that are processed one after another, each with up to
+    /// `max_concurrent_claims` claims in flight. Claimed transfers are
+    /// reported after every batch.
+    pub claim_batch_size: u32,
+    /// Minimum delay in milliseconds between two claim batches, limiting the
+    /// rate at which claims are sent to the operators. 0 disables the delay.
+    pub claim_batch_interval_ms: u64,
+    /// Rate limit applied to the read-only queries sent to each operator.
+    /// Identical concurrent queries are always coalesced into one, `None`
+    /// leaves the remaining queries unlimited.
+    pub operator_rate_limit: Option<OperatorRateLimit>,
 }
 
 impl SparkWalletConfig {
@@ -46,6 +63,19 @@ impl SparkWalletConfig {
 
         self.token_outputs_optimization_options.validate()?;
 
+        #[cfg(feature = "websocket-events")]
+        if let EventTransport::WebSocket { max_failures: 0 } = self.event_transport {
+            return Err(SparkWalletError::ValidationError(
+                "WebSocket event transport requires max_failures greater than 0".to_string(),
+            ));
+        }
+
+        if self.max_concurrent_claims == 0 || self.claim_batch_size == 0 {
+            return Err(SparkWalletError::ValidationError(
+                "max_concurrent_claims and claim_batch_size must be greater than 0".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -55,6 +85,7 @@ impl SparkWalletConfig {
                 network,
                 operator_pool: Self::default_operator_pool_config(network),
                 reconnect_interval_seconds: 1,
+                event_transport: EventTransport::Grpc,
                 service_provider_config: Self::create_service_provider_config(
                     "https://api.lightspark.com",
                     "023e33e2920326f64ea31058d44777442d97d7d5cbfcf54e3060bc1695e5261c93",
@@ -72,11 +103,15 @@ impl SparkWalletConfig {
                 },
                 self_payment_allowed: false,
                 max_concurrent_claims: 1,
+                claim_batch_size: 50,
+                claim_batch_interval_ms: 0,
+                operator_rate_limit: None,
             },
             _ => Self {
                 network,
                 operator_pool: Self::default_operator_pool_config(network),
                 reconnect_interval_seconds: 1,
+                event_transport: EventTransport::Grpc,
                 service_provider_config: Self::create_service_provider_config(
                     "https://api.lightspark.com",
                     "022bf283544b16c0622daecb79422007d167eca6ce9f0c98c0c49833b1f7170bfe",
@@ -94,6 +129,9 @@ impl SparkWalletConfig {
                 },
                 self_payment_allowed: false,
                 max_concurrent_claims: 1,
+                claim_batch_size: 50,
+                claim_batch_interval_ms: 0,
+                operator_rate_limit: None,
             },
         }
     }
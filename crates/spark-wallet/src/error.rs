@@ -57,6 +57,23 @@ pub enum SparkWalletError {
     Generic(String),
 }
 
+impl SparkWalletError {
+    /// Whether the operators couldn't be reached, e.g. because the device is offline.
+    pub fn is_transport_error(&self) -> bool {
+        let rpc_error = match self {
+            SparkWalletError::OperatorRpcError(e)
+            | SparkWalletError::ServiceError(
+                spark::services::ServiceError::ServiceConnectionError(e),
+            ) => e,
+            _ => return false,
+        };
+        matches!(
+            rpc_error.as_ref(),
+            spark::operator::rpc::OperatorRpcError::Transport(_)
+        )
+    }
+}
+
 impl From<spark::operator::rpc::OperatorRpcError> for SparkWalletError {
     fn from(error: spark::operator::rpc::OperatorRpcError) -> Self {
         SparkWalletError::OperatorRpcError(Box::new(error))
@@ -14,8 +14,12 @@ pub use spark::operator::{OperatorConfig, OperatorError, OperatorPoolConfig};
 pub use spark::{
     Identifier, Network,
     address::{SparkAddress, SparkAddressPaymentType},
+    events::EventTransport,
     header_provider::*,
-    operator::rpc::{BalancedConnectionManager, ConnectionManager, DefaultConnectionManager},
+    operator::rpc::{
+        BalancedConnectionManager, ConnectionManager, DefaultConnectionManager, OperatorRateLimit,
+        OperatorRpcStats,
+    },
     services::{
         CoopExitFeeQuote, CoopExitSpeedFeeQuote, CpfpChild, CpfpInput, ExitSpeed, Fee,
         FreezeIssuerTokenResponse, InvoiceDescription, LightningReceivePayment,
@@ -70,6 +74,9 @@ pub use unilateral_exit::*;
 pub use wallet::{SendPackagePreparation, SparkWallet};
 pub use wallet_builder::WalletBuilder;
 
+#[cfg(feature = "websocket-events")]
+pub use spark::events::DEFAULT_WEBSOCKET_MAX_FAILURES;
+
 #[cfg(feature = "test-utils")]
 pub use spark::session_store::tests as session_store_tests;
 #[cfg(feature = "test-utils")]
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use bitcoin::{Address, Transaction, hashes::sha256, secp256k1::PublicKey};
+use bitcoin::{Address, Transaction, Txid, hashes::sha256, secp256k1::PublicKey};
 use platform_utils::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use spark::{
@@ -281,6 +281,70 @@ impl From<TreeNode> for WalletLeaf {
     }
 }
 
+/// Local state of a wallet leaf, as tracked by the tree store.
+#[cfg(feature = "tree-inspection")]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub enum LeafLocalState {
+    Available,
+    NotAvailable,
+    MissingFromOperators,
+    ReservedForPayment,
+    ReservedForSwap,
+}
+
+/// A node of the wallet's trees as seen locally and by the operators.
+#[cfg(feature = "tree-inspection")]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct InspectedTreeNode {
+    pub id: TreeNodeId,
+    pub tree_id: String,
+    pub parent_node_id: Option<TreeNodeId>,
+    pub value: u64,
+    pub vout: u32,
+    pub node_txid: Txid,
+    pub refund_txid: Option<Txid>,
+    pub verifying_public_key: PublicKey,
+    pub owner_identity_public_key: Option<PublicKey>,
+    pub signing_keyshare: SigningKeyshare,
+    /// Local state if the node is one of the wallet's leaves, `None` for ancestors.
+    pub local_state: Option<LeafLocalState>,
+    /// Status reported by the operators, `None` if they didn't return the node.
+    pub operator_status: Option<String>,
+}
+
+#[cfg(feature = "tree-inspection")]
+impl InspectedTreeNode {
+    pub(crate) fn new(
+        node: TreeNode,
+        local_state: Option<LeafLocalState>,
+        operator_status: Option<String>,
+    ) -> Self {
+        InspectedTreeNode {
+            id: node.id,
+            tree_id: node.tree_id,
+            parent_node_id: node.parent_node_id,
+            value: node.value,
+            vout: node.vout,
+            node_txid: node.node_tx.compute_txid(),
+            refund_txid: node.refund_tx.map(|tx| tx.compute_txid()),
+            verifying_public_key: node.verifying_public_key,
+            owner_identity_public_key: node.owner_identity_public_key,
+            signing_keyshare: node.signing_keyshare,
+            local_state,
+            operator_status,
+        }
+    }
+}
+
+/// Node and keyshare topology of the wallet's trees, used to diagnose
+/// leaves whose local state diverges from the operators.
+#[cfg(feature = "tree-inspection")]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct TreeInspection {
+    /// The wallet's leaves followed by their ancestors up to the tree roots
+    pub nodes: Vec<InspectedTreeNode>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 pub enum TransferDirection {
     Incoming,
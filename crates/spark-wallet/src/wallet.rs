@@ -1,6 +1,6 @@
 mod external_signing;
 
-use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+use std::{collections::HashMap, pin::pin, str::FromStr, sync::Arc, time::Duration};
 
 use bitcoin::{
     Address, Amount, Transaction, TxIn, TxOut, Witness,
@@ -12,7 +12,10 @@ use bitcoin::{
     transaction::Version,
 };
 
-use futures::stream::{self, StreamExt};
+use futures::{
+    Stream, future,
+    stream::{self, StreamExt},
+};
 use platform_utils::time::{SystemTime, UNIX_EPOCH};
 use platform_utils::tokio;
 use spark::bitcoin::sighash_from_multi_input_tx;
@@ -21,12 +24,12 @@ use spark::{
         SatsPayment, SparkAddress, SparkAddressPaymentType, SparkInvoiceFields, TokensPayment,
     },
     bitcoin::BitcoinService,
-    events::{SparkEvent, subscribe_server_events},
+    events::{EventTransport, SparkEvent, subscribe_server_events},
     header_provider::HeaderProvider,
     operator::{
         OperatorPool,
         rpc::{
-            ConnectionManager, DefaultConnectionManager, OperatorRpcError,
+            ConnectionManager, DefaultConnectionManager, OperatorRpcError, OperatorRpcStats,
             spark::{PreimageRequestRole, QuerySparkInvoicesRequest, UpdateWalletSettingRequest},
         },
     },
@@ -68,8 +71,16 @@ use crate::{
     unilateral_exit::{CpfpChangeInput, ExitLeafSelection, PreparedUnilateralExit, RefundOutput},
 };
 
+#[cfg(feature = "tree-inspection")]
+use crate::model::{InspectedTreeNode, LeafLocalState, TreeInspection};
+#[cfg(feature = "tree-inspection")]
+use spark::tree::Leaves;
+
 const SELECT_LEAVES_MAX_RETRIES: usize = 3;
 const MAX_LEAF_SPENT_RETRIES: usize = 3;
+/// Withdrawals spending more leaves than this first swap them for fewer,
+/// larger leaves, keeping the cooperative exit transaction small.
+const MAX_COOP_EXIT_LEAVES: usize = 16;
 
 pub enum SendPackagePreparation {
     Ready(PrepareTransferRequest),
@@ -161,6 +172,12 @@ fn is_backoff_retryable_error(error: &OperatorRpcError) -> bool {
     is_resource_exhausted_error(error) || is_leaf_unavailable_error(error)
 }
 
+/// Leaf selection options for cooperative exits. Fee quotes and withdrawals
+/// select leaves the same way, so the quote covers the leaves that are spent.
+fn coop_exit_select_options() -> SelectLeavesOptions {
+    SelectLeavesOptions::default().with_max_leaves(MAX_COOP_EXIT_LEAVES)
+}
+
 /// Macro to handle retry logic for operations that may fail due to concurrent leaf spending.
 /// This retries the operation up to MAX_LEAF_SPENT_RETRIES times.
 macro_rules! with_leafs_spent_retry {
@@ -169,6 +186,21 @@ macro_rules! with_leafs_spent_retry {
         $target_amounts:expr,
         $operation_name:literal,
         |$leaves_reservation:ident| $operation:expr
+    ) => {
+        with_leafs_spent_retry!(
+            $self,
+            $target_amounts,
+            SelectLeavesOptions::default(),
+            $operation_name,
+            |$leaves_reservation| $operation
+        )
+    };
+    (
+        $self:expr,
+        $target_amounts:expr,
+        $select_options:expr,
+        $operation_name:literal,
+        |$leaves_reservation:ident| $operation:expr
     ) => {{
         let mut attempt = 0;
         let mut backoff_attempt: u32 = 0;
@@ -188,7 +220,9 @@ macro_rules! with_leafs_spent_retry {
                 );
                 $self.tree_service.refresh_leaves().await?;
             }
-            let $leaves_reservation = $self.select_leaves_with_retry($target_amounts).await?;
+            let $leaves_reservation = $self
+                .select_leaves_with_retry($target_amounts, $select_options)
+                .await?;
 
             let result = with_reserved_leaves(
                 $self.tree_service.as_ref(),
@@ -307,6 +341,7 @@ impl SparkWallet {
         transfer_observer: Option<Arc<dyn TransferObserver>>,
         ssp_extra_header_provider: Option<Arc<dyn HeaderProvider>>,
         so_extra_header_provider: Option<Arc<dyn HeaderProvider>>,
+        operator_byte_counter: Option<Arc<platform_utils::ByteCounter>>,
         cancellation_token: Option<watch::Receiver<()>>,
     ) -> Result<Self, SparkWalletError> {
         config.validate()?;
@@ -336,6 +371,8 @@ impl SparkWallet {
                 Arc::clone(&session_store),
                 Arc::clone(&spark_signer),
                 so_extra_header_provider,
+                operator_byte_counter,
+                config.operator_rate_limit.as_ref(),
             )
             .await?,
         );
@@ -484,6 +521,18 @@ impl SparkWallet {
         Ok(leaves.into())
     }
 
+    /// Returns the node and keyshare topology of the wallet's leaves and their
+    /// ancestors, comparing the local tree store with the operators' view.
+    ///
+    /// Intended for support tooling diagnosing stuck transfers.
+    #[cfg(feature = "tree-inspection")]
+    pub async fn inspect_tree(&self) -> Result<TreeInspection, SparkWalletError> {
+        let local_leaves = local_leaf_states(self.tree_service.list_leaves().await?);
+        let leaf_ids: Vec<TreeNodeId> = local_leaves.iter().map(|(n, _)| n.id.clone()).collect();
+        let operator_nodes = self.tree_service.fetch_nodes(&leaf_ids, true).await?;
+        Ok(tree_inspection(local_leaves, operator_nodes))
+    }
+
     /// Starts leaf optimization if auto-optimization is enabled.
     async fn maybe_start_optimization(&self) {
         if self.config.leaf_auto_optimize_enabled {
@@ -663,7 +712,7 @@ impl SparkWallet {
         let target_amounts =
             amount_sats.map(|amount| TargetAmounts::new_amount_and_fee(amount, None));
         let reservation = self
-            .select_leaves_with_retry(target_amounts.as_ref())
+            .select_leaves_with_retry(target_amounts.as_ref(), coop_exit_select_options())
             .await?;
 
         // Fetches fee quote for the coop exit then cancels the reservation.
@@ -759,6 +808,18 @@ impl SparkWallet {
         Ok(transfer_id)
     }
 
+    /// The fee of refunding a static deposit to `refund_address`
+    pub fn static_deposit_refund_fee(
+        &self,
+        refund_address: &str,
+        fee: Fee,
+    ) -> Result<u64, SparkWalletError> {
+        let refund_address = self.parse_refund_address(refund_address)?;
+        Ok(self
+            .deposit_service
+            .static_deposit_refund_fee(&refund_address, fee))
+    }
+
     pub async fn refund_static_deposit(
         &self,
         tx: Transaction,
@@ -766,15 +827,7 @@ impl SparkWallet {
         refund_address: &str,
         fee: Fee,
     ) -> Result<Transaction, SparkWalletError> {
-        let refund_address = refund_address
-            .parse::<Address<NetworkUnchecked>>()
-            .map_err(|_| {
-                SparkWalletError::InvalidAddress(format!(
-                    "Invalid refund address: {refund_address}"
-                ))
-            })?
-            .require_network(self.config.network.into())
-            .map_err(|_| SparkWalletError::InvalidNetwork)?;
+        let refund_address = self.parse_refund_address(refund_address)?;
 
         // The operators reject the refund with MISSING_ENTITY until their chain
         // watcher indexes the deposit UTXO the coordinator already revealed. Back
@@ -809,6 +862,18 @@ impl SparkWallet {
         Ok(refund_tx)
     }
 
+    fn parse_refund_address(&self, refund_address: &str) -> Result<Address, SparkWalletError> {
+        refund_address
+            .parse::<Address<NetworkUnchecked>>()
+            .map_err(|_| {
+                SparkWalletError::InvalidAddress(format!(
+                    "Invalid refund address: {refund_address}"
+                ))
+            })?
+            .require_network(self.config.network.into())
+            .map_err(|_| SparkWalletError::InvalidNetwork)
+    }
+
     pub async fn generate_deposit_address(
         &self,
     ) -> Result<spark::services::SingleUseDepositAddress, SparkWalletError> {
@@ -942,7 +1007,8 @@ impl SparkWallet {
             &self.tree_service,
             &self.htlc_service,
             &self.ssp_client,
-            self.config.max_concurrent_claims,
+            ClaimLimits::from(&self.config),
+            |_| {},
         )
         .await?;
 
@@ -1085,6 +1151,38 @@ impl SparkWallet {
             .collect()
     }
 
+    /// Returns the HTLC transfers sent to this wallet for the given payment
+    /// hash, whatever their status.
+    pub async fn list_received_htlc_transfers(
+        &self,
+        payment_hash: Hash,
+    ) -> Result<Vec<WalletTransfer>, SparkWalletError> {
+        let htlcs = self
+            .htlc_service
+            .query_htlc(
+                QueryHtlcFilter {
+                    identity_public_key: self.identity_public_key,
+                    status: None,
+                    transfer_ids: Vec::new(),
+                    payment_hashes: vec![payment_hash.to_string()],
+                    match_role: PreimageRequestRole::Receiver,
+                },
+                None,
+            )
+            .await?
+            .items;
+        htlcs
+            .into_iter()
+            .map(|h| {
+                WalletTransfer::from_preimage_request_with_transfer(
+                    h,
+                    self.identity_public_key,
+                    self.config.service_provider_config.identity_public_key,
+                )
+            })
+            .collect()
+    }
+
     pub fn get_info(&self) -> WalletInfo {
         WalletInfo {
             identity_public_key: self.identity_public_key,
@@ -1092,6 +1190,12 @@ impl SparkWallet {
         }
     }
 
+    /// Returns the counters of the operator queries coalesced, delayed or
+    /// dropped by the rate limit since the wallet connected.
+    pub fn operator_rpc_stats(&self) -> Arc<OperatorRpcStats> {
+        self.operator_pool.rpc_stats()
+    }
+
     pub fn get_spark_address(&self) -> Result<SparkAddress, SparkWalletError> {
         Ok(SparkAddress::new(
             self.identity_public_key,
@@ -1262,7 +1366,30 @@ impl SparkWallet {
         // Claiming here any transfers that may have been missed in the event stream handling.
         // Note: recent counter swap transfers are skipped as they are claimed synchronously
         // by the Swap::swap_leaves() method. Older ones are claimed as fallback.
-        self.claim_pending_transfers().await?;
+        if !self.spark_signer.is_watch_only() {
+            self.claim_pending_transfers().await?;
+        }
+        Ok(())
+    }
+
+    /// Pays the cold-start cost of the first payment ahead of time.
+    ///
+    /// Connects to and authenticates with all operators and the SSP, and
+    /// pre-fetches the swap fee estimate for the current balance.
+    pub async fn warm_up(&self) -> Result<(), SparkWalletError> {
+        let operators = futures::future::try_join_all(
+            self.operator_pool
+                .get_all_operators()
+                .map(|operator| operator.client.auth_headers()),
+        );
+        let (operators, ssp) = futures::join!(operators, self.ssp_client.ensure_session());
+        operators?;
+        ssp?;
+
+        let balance = self.get_balance().await?;
+        if balance > 0 {
+            self.ssp_client.get_swap_fee_estimate(balance).await?;
+        }
         Ok(())
     }
 
@@ -1295,6 +1422,7 @@ impl SparkWallet {
         let transfer = with_leafs_spent_retry!(
             self,
             target_amounts.as_ref(),
+            coop_exit_select_options(),
             "Withdrawal",
             |leaves_reservation| self.withdraw_inner(WithdrawInnerParams {
                 address: withdrawal_address.clone(),
@@ -1695,6 +1823,7 @@ impl SparkWallet {
                     Arc::clone(&self.event_manager),
                     self.identity_public_key,
                     reconnect_interval,
+                    self.config.event_transport.clone(),
                     Arc::clone(&self.tree_service),
                     Arc::clone(&self.ssp_client),
                     Arc::clone(&self.transfer_service),
@@ -1703,7 +1832,8 @@ impl SparkWallet {
                     self.config.leaf_auto_optimize_enabled,
                     Arc::clone(&self.token_service),
                     self.config.token_outputs_optimization_options.clone(),
-                    self.config.max_concurrent_claims,
+                    ClaimLimits::from(&self.config),
+                    !self.spark_signer.is_watch_only(),
                 ));
                 background_processor
                     .run_background_tasks(cancellation_token)
@@ -2088,6 +2218,7 @@ impl SparkWallet {
     async fn select_leaves_with_retry(
         &self,
         target_amounts: Option<&TargetAmounts>,
+        options: SelectLeavesOptions,
     ) -> Result<spark::tree::LeavesReservation, SparkWalletError> {
         use spark::tree::TreeServiceError;
 
@@ -2096,7 +2227,7 @@ impl SparkWallet {
         for i in 0..SELECT_LEAVES_MAX_RETRIES {
             // Try to select leaves with inner optimization retry logic
             let reserve_result = self
-                .try_select_leaves_with_optimization_retry(target_amounts)
+                .try_select_leaves_with_optimization_retry(target_amounts, options.clone())
                 .await;
 
             match reserve_result {
@@ -2160,16 +2291,13 @@ impl SparkWallet {
     async fn try_select_leaves_with_optimization_retry(
         &self,
         target_amounts: Option<&TargetAmounts>,
+        options: SelectLeavesOptions,
     ) -> Result<spark::tree::LeavesReservation, SparkWalletError> {
         use spark::tree::{ReservationPurpose, TreeServiceError};
 
         match self
             .tree_service
-            .select_leaves(
-                target_amounts,
-                ReservationPurpose::Payment,
-                SelectLeavesOptions::default(),
-            )
+            .select_leaves(target_amounts, ReservationPurpose::Payment, options.clone())
             .await
         {
             Ok(reservation) => Ok(reservation),
@@ -2188,11 +2316,7 @@ impl SparkWallet {
                     // Retry select_leaves
                     let reservation = self
                         .tree_service
-                        .select_leaves(
-                            target_amounts,
-                            ReservationPurpose::Payment,
-                            SelectLeavesOptions::default(),
-                        )
+                        .select_leaves(target_amounts, ReservationPurpose::Payment, options)
                         .await?;
                     Ok(reservation)
                 } else {
@@ -2204,14 +2328,38 @@ impl SparkWallet {
     }
 }
 
-async fn claim_pending_transfers(
+/// Limits applied when claiming pending transfers.
+#[derive(Clone, Copy, Debug)]
+struct ClaimLimits {
+    max_concurrent_claims: u32,
+    batch_size: u32,
+    batch_interval: Duration,
+}
+
+impl From<&SparkWalletConfig> for ClaimLimits {
+    fn from(config: &SparkWalletConfig) -> Self {
+        ClaimLimits {
+            max_concurrent_claims: config.max_concurrent_claims.max(1),
+            batch_size: config.claim_batch_size.max(1),
+            batch_interval: Duration::from_millis(config.claim_batch_interval_ms),
+        }
+    }
+}
+
+/// Claims all pending transfers in batches, calling `on_batch_claimed` with
+/// the transfers claimed by each batch as soon as it completes.
+async fn claim_pending_transfers<F>(
     our_pubkey: PublicKey,
     transfer_service: &Arc<TransferService>,
     tree_service: &Arc<dyn TreeService>,
     htlc_service: &Arc<HtlcService>,
     ssp_client: &Arc<ServiceProvider>,
-    max_concurrent_claims: u32,
-) -> Result<Vec<WalletTransfer>, SparkWalletError> {
+    limits: ClaimLimits,
+    on_batch_claimed: F,
+) -> Result<Vec<WalletTransfer>, SparkWalletError>
+where
+    F: Fn(&[WalletTransfer]),
+{
     debug!("Claiming all pending transfers");
     let transfers = transfer_service
         .query_claimable_receiver_transfers(None)
@@ -2223,9 +2371,10 @@ async fn claim_pending_transfers(
     }
 
     debug!(
-        "Retrieved {} pending transfers, claiming with max concurrency {}",
+        "Retrieved {} pending transfers, claiming in batches of {} with max concurrency {}",
         transfers.len(),
-        max_concurrent_claims
+        limits.batch_size,
+        limits.max_concurrent_claims
     );
 
     // Skip recent counter-swap transfers — they are claimed synchronously
@@ -2236,7 +2385,6 @@ async fn claim_pending_transfers(
         .unwrap_or_default()
         .as_secs();
 
-    // Concurrent claiming with best-effort error handling
     let transfers_to_claim: Vec<_> = transfers
         .items
         .iter()
@@ -2255,56 +2403,92 @@ async fn claim_pending_transfers(
         .cloned()
         .collect();
 
-    let claim_results: Vec<_> = stream::iter(transfers_to_claim)
-        .enumerate()
-        .map(|(i, transfer)| {
-            let transfer_service = Arc::clone(transfer_service);
-            let tree_service = Arc::clone(tree_service);
-            async move {
-                debug!("Claiming transfer {}: {}", i + 1, transfer.id);
-                let result = claim_transfer(&transfer, &transfer_service, &tree_service).await;
-                match &result {
-                    Ok(_) => debug!("Successfully claimed transfer: {}", transfer.id),
-                    Err(e) => warn!("Failed to claim transfer {}: {:?}", transfer.id, e),
+    // Best-effort claiming, failures are logged and skipped
+    let claim = |mut transfer: Transfer| {
+        let transfer_service = Arc::clone(transfer_service);
+        let tree_service = Arc::clone(tree_service);
+        async move {
+            debug!("Claiming transfer {}", transfer.id);
+            match claim_transfer(&transfer, &transfer_service, &tree_service).await {
+                Ok(_) => {
+                    debug!("Successfully claimed transfer: {}", transfer.id);
+                    transfer.status = TransferStatus::Completed;
+                    Some(transfer)
+                }
+                Err(e) => {
+                    warn!("Failed to claim transfer {}: {:?}", transfer.id, e);
+                    None
                 }
-                (transfer, result)
             }
-        })
-        .buffer_unordered(max_concurrent_claims as usize)
-        .collect()
-        .await;
+        }
+    };
 
-    // Collect successful claims, log failures (best-effort).
-    let mut successful_items = Vec::new();
+    let mut batches = pin!(claim_in_batches(transfers_to_claim, limits, claim).enumerate());
+    let mut claimed = Vec::new();
+    while let Some((batch_index, successful_items)) = batches.next().await {
+        debug!(
+            "Claimed {} transfers in batch {}, creating wallet transfers",
+            successful_items.len(),
+            batch_index + 1
+        );
 
-    for (transfer, result) in claim_results {
-        if result.is_ok() {
-            let mut completed = transfer;
-            completed.status = TransferStatus::Completed;
-            successful_items.push(completed);
+        if successful_items.is_empty() {
+            continue;
         }
-        // Failures already logged above, continue with others
-    }
 
-    debug!(
-        "Claimed {} transfers, creating wallet transfers",
-        successful_items.len()
-    );
+        let batch_transfers = create_transfers(
+            PagingResult {
+                items: successful_items,
+                next: transfers.next.clone(),
+            },
+            ssp_client,
+            htlc_service,
+            our_pubkey,
+            ssp_client.identity_public_key(),
+        )
+        .await?
+        .items;
+        on_batch_claimed(&batch_transfers);
+        claimed.extend(batch_transfers);
+    }
 
-    let successful_transfers = PagingResult {
-        items: successful_items,
-        next: transfers.next.clone(),
-    };
+    Ok(claimed)
+}
 
-    Ok(create_transfers(
-        successful_transfers,
-        ssp_client,
-        htlc_service,
-        our_pubkey,
-        ssp_client.identity_public_key(),
-    )
-    .await?
-    .items)
+/// Claims `items` in batches of `limits.batch_size`, with up to
+/// `limits.max_concurrent_claims` claims in flight and `limits.batch_interval`
+/// between batches. Yields the items claimed by each batch once it completes,
+/// `claim` returning `None` for the items it failed to claim.
+fn claim_in_batches<T, C, Fut>(
+    items: Vec<T>,
+    limits: ClaimLimits,
+    claim: C,
+) -> impl Stream<Item = Vec<T>>
+where
+    T: Clone,
+    C: Fn(T) -> Fut + Clone,
+    Fut: Future<Output = Option<T>>,
+{
+    let batches: Vec<Vec<T>> = items
+        .chunks(limits.batch_size as usize)
+        .map(<[T]>::to_vec)
+        .collect();
+    stream::iter(batches)
+        .enumerate()
+        .then(move |(batch_index, batch)| {
+            let claim = claim.clone();
+            async move {
+                if batch_index > 0 && !limits.batch_interval.is_zero() {
+                    tokio::time::sleep(limits.batch_interval).await;
+                }
+                stream::iter(batch)
+                    .map(claim)
+                    .buffer_unordered(limits.max_concurrent_claims as usize)
+                    .filter_map(future::ready)
+                    .collect()
+                    .await
+            }
+        })
 }
 
 async fn create_transfers(
@@ -2325,10 +2509,10 @@ async fn create_transfers(
         transfer_ids.push(transfer_id);
     }
 
-    let ssp_tranfers = ssp_client.get_transfers(transfer_ids.clone()).await?;
-    let ssp_transfers_map: HashMap<String, SspTransfer> = ssp_tranfers
+    let ssp_tranfers = ssp_client.get_transfers(transfer_ids).await?;
+    let mut ssp_transfers_map: HashMap<String, SspTransfer> = ssp_tranfers
         .into_iter()
-        .filter_map(|t| t.spark_id.clone().map(|spark_id| (spark_id, t.clone())))
+        .filter_map(|t| Some((t.spark_id.clone()?, t)))
         .collect();
 
     let htlc_requests = if preimage_swap_transfer_ids.is_empty() {
@@ -2349,23 +2533,19 @@ async fn create_transfers(
             .items
     };
 
-    let htlc_requests_map: HashMap<String, PreimageRequestWithTransfer> = htlc_requests
+    let mut htlc_requests_map: HashMap<String, PreimageRequestWithTransfer> = htlc_requests
         .into_iter()
-        .filter_map(|t| {
-            t.transfer
-                .clone()
-                .map(|transfer| (transfer.id.to_string(), t))
-        })
+        .filter_map(|t| Some((t.transfer.as_ref()?.id.to_string(), t)))
         .collect();
 
+    // Each transfer id appears once, so the matching entries are moved out of
+    // the maps instead of cloned.
     Ok(transfers.map(|t| {
+        let transfer_id = t.id.to_string();
         WalletTransfer::from_transfer(
-            t.clone(),
-            ssp_transfers_map.get(&t.id.to_string()).cloned(),
-            htlc_requests_map
-                .get(&t.id.to_string())
-                .cloned()
-                .map(Into::into),
+            t,
+            ssp_transfers_map.remove(&transfer_id),
+            htlc_requests_map.remove(&transfer_id).map(Into::into),
             our_public_key,
             ssp_public_key,
         )
@@ -2414,6 +2594,56 @@ async fn create_transfer(
     ))
 }
 
+/// The wallet's leaves with their local state
+#[cfg(feature = "tree-inspection")]
+fn local_leaf_states(leaves: Leaves) -> Vec<(TreeNode, LeafLocalState)> {
+    [
+        (leaves.available, LeafLocalState::Available),
+        (leaves.not_available, LeafLocalState::NotAvailable),
+        (
+            leaves.available_missing_from_operators,
+            LeafLocalState::MissingFromOperators,
+        ),
+        (
+            leaves.reserved_for_payment,
+            LeafLocalState::ReservedForPayment,
+        ),
+        (leaves.reserved_for_swap, LeafLocalState::ReservedForSwap),
+    ]
+    .into_iter()
+    .flat_map(|(nodes, state)| nodes.into_iter().map(move |node| (node, state.clone())))
+    .collect()
+}
+
+/// The local leaves, with the status the operators report for them, followed
+/// by the other nodes the operators returned, their ancestors, ordered by tree.
+#[cfg(feature = "tree-inspection")]
+fn tree_inspection(
+    local_leaves: Vec<(TreeNode, LeafLocalState)>,
+    operator_nodes: Vec<TreeNode>,
+) -> TreeInspection {
+    let mut operator_nodes: HashMap<TreeNodeId, TreeNode> = operator_nodes
+        .into_iter()
+        .map(|n| (n.id.clone(), n))
+        .collect();
+
+    let mut nodes = Vec::with_capacity(operator_nodes.len().max(local_leaves.len()));
+    for (leaf, state) in local_leaves {
+        let operator_status = operator_nodes
+            .remove(&leaf.id)
+            .map(|n| n.status.to_string());
+        nodes.push(InspectedTreeNode::new(leaf, Some(state), operator_status));
+    }
+    let mut ancestors: Vec<TreeNode> = operator_nodes.into_values().collect();
+    ancestors.sort_by(|a, b| (&a.tree_id, &a.id).cmp(&(&b.tree_id, &b.id)));
+    for node in ancestors {
+        let status = node.status.to_string();
+        nodes.push(InspectedTreeNode::new(node, None, Some(status)));
+    }
+
+    TreeInspection { nodes }
+}
+
 async fn claim_transfer(
     transfer: &Transfer,
     transfer_service: &Arc<TransferService>,
@@ -2441,6 +2671,7 @@ struct BackgroundProcessor {
     event_manager: Arc<EventManager>,
     identity_public_key: PublicKey,
     reconnect_interval: Duration,
+    event_transport: EventTransport,
     tree_service: Arc<dyn TreeService>,
     ssp_client: Arc<ServiceProvider>,
     transfer_service: Arc<TransferService>,
@@ -2449,7 +2680,9 @@ struct BackgroundProcessor {
     auto_optimize_enabled: bool,
     token_service: Arc<TokenService>,
     token_outputs_optimization_options: TokenOutputsOptimizationOptions,
-    max_concurrent_claims: u32,
+    claim_limits: ClaimLimits,
+    /// False for a watch-only wallet, which can't sign the claims.
+    claims_enabled: bool,
 }
 
 impl BackgroundProcessor {
@@ -2459,6 +2692,7 @@ impl BackgroundProcessor {
         event_manager: Arc<EventManager>,
         identity_public_key: PublicKey,
         reconnect_interval: Duration,
+        event_transport: EventTransport,
         tree_service: Arc<dyn TreeService>,
         ssp_client: Arc<ServiceProvider>,
         transfer_service: Arc<TransferService>,
@@ -2467,13 +2701,15 @@ impl BackgroundProcessor {
         auto_optimize_enabled: bool,
         token_service: Arc<TokenService>,
         token_outputs_optimization_options: TokenOutputsOptimizationOptions,
-        max_concurrent_claims: u32,
+        claim_limits: ClaimLimits,
+        claims_enabled: bool,
     ) -> Self {
         Self {
             operator_pool,
             event_manager,
             identity_public_key,
             reconnect_interval,
+            event_transport,
             tree_service,
             ssp_client,
             transfer_service,
@@ -2482,7 +2718,8 @@ impl BackgroundProcessor {
             auto_optimize_enabled,
             token_service,
             token_outputs_optimization_options,
-            max_concurrent_claims,
+            claim_limits,
+            claims_enabled,
         }
     }
 
@@ -2509,6 +2746,7 @@ impl BackgroundProcessor {
         let (event_tx, event_stream) = broadcast::channel(100);
         let operator_pool = Arc::clone(&self.operator_pool);
         let reconnect_interval = self.reconnect_interval;
+        let event_transport = self.event_transport.clone();
         let identity_public_key = self.identity_public_key;
         let mut cancellation_token_for_events = cancellation_token.clone();
         let span = tracing::Span::current();
@@ -2519,6 +2757,7 @@ impl BackgroundProcessor {
                     operator_pool,
                     &event_tx,
                     reconnect_interval,
+                    event_transport,
                     &mut cancellation_token_for_events,
                 )
                 .await;
@@ -2636,6 +2875,10 @@ impl BackgroundProcessor {
             );
             return Ok(());
         }
+        if !self.claims_enabled {
+            debug!("Watch-only wallet, not claiming transfer {}", transfer.id);
+            return Ok(());
+        }
 
         // get the ssp transfer details, if it fails just use None
         // Internal transfers will not have an SSP entry so just skip it
@@ -2705,6 +2948,10 @@ impl BackgroundProcessor {
     async fn process_connected_event(&self) -> Result<(), SparkWalletError> {
         self.event_manager
             .notify_listeners(WalletEvent::StreamConnected);
+        if !self.claims_enabled {
+            self.event_manager.notify_listeners(WalletEvent::Synced);
+            return Ok(());
+        }
 
         match claim_pending_transfers(
             self.identity_public_key,
@@ -2712,7 +2959,15 @@ impl BackgroundProcessor {
             &self.tree_service,
             &self.htlc_service,
             &self.ssp_client,
-            self.max_concurrent_claims,
+            self.claim_limits,
+            // Report claimed transfers per batch so listeners see progress while
+            // catching up on a large backlog.
+            |batch| {
+                for transfer in batch {
+                    self.event_manager
+                        .notify_listeners(WalletEvent::TransferClaimed(transfer.clone()));
+                }
+            },
         )
         .await
         {
@@ -2724,10 +2979,6 @@ impl BackgroundProcessor {
                 if !transfers.is_empty() {
                     self.maybe_start_optimization().await;
                 }
-                for transfer in &transfers {
-                    self.event_manager
-                        .notify_listeners(WalletEvent::TransferClaimed(transfer.clone()));
-                }
             }
             Err(e) => {
                 debug!(
@@ -2935,3 +3186,351 @@ mod tests {
         assert!(!is_backoff_retryable_error(&err));
     }
 }
+
+#[cfg(all(test, feature = "tree-inspection"))]
+mod tree_inspection_tests {
+    use super::*;
+    use bitcoin::{ScriptBuf, absolute::LockTime};
+    use spark::{
+        Identifier,
+        tree::{SigningKeyshare, TreeNodeStatus},
+    };
+
+    const TEST_PUBKEY: &str = "02e6642fd69bd211f93f7f1f36ca51a26a5290eb2dd1b0d8279a87bb0d480c8443";
+
+    fn tx(nonce: u32) -> Transaction {
+        Transaction {
+            version: Version::non_standard(3),
+            lock_time: LockTime::from_height(nonce).unwrap(),
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(0),
+                script_pubkey: ScriptBuf::from(vec![0x51, 0x02, 0x4e, 0x73]),
+            }],
+        }
+    }
+
+    fn node(id: &str, tree_id: &str, parent: Option<&str>, status: TreeNodeStatus) -> TreeNode {
+        let pk = PublicKey::from_str(TEST_PUBKEY).unwrap();
+        let nonce = id.bytes().map(u32::from).sum();
+        TreeNode {
+            id: TreeNodeId::from_str(id).unwrap(),
+            tree_id: tree_id.to_string(),
+            value: 1_000,
+            parent_node_id: parent.map(|p| TreeNodeId::from_str(p).unwrap()),
+            node_tx: tx(nonce),
+            refund_tx: parent.map(|_| tx(nonce + 1)),
+            direct_tx: None,
+            direct_refund_tx: None,
+            direct_from_cpfp_refund_tx: None,
+            vout: 0,
+            verifying_public_key: pk,
+            owner_identity_public_key: Some(pk),
+            signing_keyshare: SigningKeyshare {
+                public_key: pk,
+                owner_identifiers: vec![Identifier::try_from(1u16).unwrap()],
+                threshold: 2,
+            },
+            status,
+        }
+    }
+
+    fn ids(inspection: &TreeInspection) -> Vec<String> {
+        inspection.nodes.iter().map(|n| n.id.to_string()).collect()
+    }
+
+    #[test]
+    fn local_leaf_states_tag_each_leaf_set() {
+        let leaves = Leaves {
+            available: vec![node("a", "t", Some("r"), TreeNodeStatus::Available)],
+            not_available: vec![node("b", "t", Some("r"), TreeNodeStatus::Creating)],
+            available_missing_from_operators: vec![node(
+                "c",
+                "t",
+                Some("r"),
+                TreeNodeStatus::Available,
+            )],
+            reserved_for_payment: vec![node("d", "t", Some("r"), TreeNodeStatus::Available)],
+            reserved_for_swap: vec![node("e", "t", Some("r"), TreeNodeStatus::Available)],
+        };
+        let states: Vec<(String, LeafLocalState)> = local_leaf_states(leaves)
+            .into_iter()
+            .map(|(n, state)| (n.id.to_string(), state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("a".to_string(), LeafLocalState::Available),
+                ("b".to_string(), LeafLocalState::NotAvailable),
+                ("c".to_string(), LeafLocalState::MissingFromOperators),
+                ("d".to_string(), LeafLocalState::ReservedForPayment),
+                ("e".to_string(), LeafLocalState::ReservedForSwap),
+            ]
+        );
+    }
+
+    #[test]
+    fn tree_inspection_lists_leaves_then_ancestors() {
+        let leaf = node("leaf", "t1", Some("branch"), TreeNodeStatus::Available);
+        let local_leaves = vec![(leaf.clone(), LeafLocalState::Available)];
+        let operator_nodes = vec![
+            node("root", "t1", None, TreeNodeStatus::Splitted),
+            leaf.clone(),
+            node("branch", "t1", Some("root"), TreeNodeStatus::Splitted),
+        ];
+
+        let inspection = tree_inspection(local_leaves, operator_nodes);
+        assert_eq!(ids(&inspection), vec!["leaf", "branch", "root"]);
+
+        let inspected_leaf = &inspection.nodes[0];
+        assert_eq!(inspected_leaf.local_state, Some(LeafLocalState::Available));
+        assert_eq!(
+            inspected_leaf.operator_status,
+            Some(TreeNodeStatus::Available.to_string())
+        );
+        assert_eq!(inspected_leaf.node_txid, leaf.node_tx.compute_txid());
+        assert_eq!(
+            inspected_leaf.refund_txid,
+            leaf.refund_tx.as_ref().map(Transaction::compute_txid)
+        );
+        assert_eq!(
+            inspected_leaf.parent_node_id,
+            Some(TreeNodeId::from_str("branch").unwrap())
+        );
+
+        for ancestor in &inspection.nodes[1..] {
+            assert_eq!(ancestor.local_state, None);
+            assert_eq!(
+                ancestor.operator_status,
+                Some(TreeNodeStatus::Splitted.to_string())
+            );
+        }
+        assert_eq!(inspection.nodes[2].refund_txid, None);
+    }
+
+    #[test]
+    fn tree_inspection_flags_leaves_unknown_to_operators() {
+        let local_leaves = vec![
+            (
+                node("known", "t1", Some("root"), TreeNodeStatus::Available),
+                LeafLocalState::Available,
+            ),
+            (
+                node("unknown", "t1", Some("root"), TreeNodeStatus::Available),
+                LeafLocalState::MissingFromOperators,
+            ),
+        ];
+        let operator_nodes = vec![
+            node("known", "t1", Some("root"), TreeNodeStatus::TransferLocked),
+            node("root", "t1", None, TreeNodeStatus::Splitted),
+        ];
+
+        let inspection = tree_inspection(local_leaves, operator_nodes);
+        assert_eq!(ids(&inspection), vec!["known", "unknown", "root"]);
+        // The operators' status is reported even when it diverges locally
+        assert_eq!(
+            inspection.nodes[0].operator_status,
+            Some(TreeNodeStatus::TransferLocked.to_string())
+        );
+        assert_eq!(inspection.nodes[1].operator_status, None);
+        assert_eq!(
+            inspection.nodes[1].local_state,
+            Some(LeafLocalState::MissingFromOperators)
+        );
+    }
+
+    #[test]
+    fn tree_inspection_orders_ancestors_by_tree() {
+        let local_leaves = vec![
+            (
+                node("leaf-b", "t2", Some("root-b"), TreeNodeStatus::Available),
+                LeafLocalState::Available,
+            ),
+            (
+                node("leaf-a", "t1", Some("root-a"), TreeNodeStatus::Available),
+                LeafLocalState::ReservedForSwap,
+            ),
+        ];
+        let operator_nodes = vec![
+            node("root-b", "t2", None, TreeNodeStatus::Splitted),
+            node("root-a", "t1", None, TreeNodeStatus::Splitted),
+        ];
+
+        let inspection = tree_inspection(local_leaves, operator_nodes);
+        // Leaves keep their local order, ancestors are ordered by tree
+        assert_eq!(
+            ids(&inspection),
+            vec!["leaf-b", "leaf-a", "root-a", "root-b"]
+        );
+    }
+
+    #[test]
+    fn tree_inspection_empty_wallet() {
+        let inspection = tree_inspection(Vec::new(), Vec::new());
+        assert!(inspection.nodes.is_empty());
+    }
+}
+
+#[cfg(all(test, not(all(target_family = "wasm", target_os = "unknown"))))]
+mod claim_batching_tests {
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+
+    fn limits(max_concurrent_claims: u32, batch_size: u32, batch_interval_ms: u64) -> ClaimLimits {
+        ClaimLimits {
+            max_concurrent_claims,
+            batch_size,
+            batch_interval: Duration::from_millis(batch_interval_ms),
+        }
+    }
+
+    async fn claimed_batches(items: Vec<u32>, limits: ClaimLimits) -> Vec<Vec<u32>> {
+        let batches: Vec<Vec<u32>> = claim_in_batches(items, limits, |item| async move {
+            tokio::task::yield_now().await;
+            Some(item)
+        })
+        .collect()
+        .await;
+        batches
+            .into_iter()
+            .map(|mut batch| {
+                batch.sort_unstable();
+                batch
+            })
+            .collect()
+    }
+
+    #[test]
+    fn claim_limits_from_config_clamp_zero_values() {
+        let mut config = SparkWalletConfig::default_config(crate::Network::Regtest);
+        config.max_concurrent_claims = 0;
+        config.claim_batch_size = 0;
+        config.claim_batch_interval_ms = 250;
+        let limits = ClaimLimits::from(&config);
+        assert_eq!(limits.max_concurrent_claims, 1);
+        assert_eq!(limits.batch_size, 1);
+        assert_eq!(limits.batch_interval, Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn claim_in_batches_splits_at_batch_size() {
+        assert_eq!(
+            claimed_batches((0..7).collect(), limits(2, 3, 0)).await,
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+        );
+        // An exact multiple of the batch size leaves no partial batch
+        assert_eq!(
+            claimed_batches((0..6).collect(), limits(2, 3, 0)).await,
+            vec![vec![0, 1, 2], vec![3, 4, 5]]
+        );
+        assert_eq!(
+            claimed_batches((0..2).collect(), limits(2, 3, 0)).await,
+            vec![vec![0, 1]]
+        );
+        assert_eq!(
+            claimed_batches((0..3).collect(), limits(1, 1, 0)).await,
+            vec![vec![0], vec![1], vec![2]]
+        );
+        assert!(
+            claimed_batches(Vec::new(), limits(2, 3, 0))
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn claim_in_batches_skips_failed_claims() {
+        let batches: Vec<Vec<u32>> = claim_in_batches((0..6).collect(), limits(3, 3, 0), |item| {
+            std::future::ready((item % 2 == 0).then_some(item))
+        })
+        .collect()
+        .await;
+        let batches: Vec<Vec<u32>> = batches
+            .into_iter()
+            .map(|mut batch| {
+                batch.sort_unstable();
+                batch
+            })
+            .collect();
+        // A batch whose claims all fail is still yielded, empty
+        assert_eq!(batches, vec![vec![0, 2], vec![4]]);
+
+        let batches: Vec<Vec<u32>> = claim_in_batches((0..4).collect(), limits(2, 2, 0), |_| {
+            std::future::ready(None)
+        })
+        .collect()
+        .await;
+        assert_eq!(batches, vec![Vec::<u32>::new(), Vec::new()]);
+    }
+
+    #[tokio::test]
+    async fn claim_in_batches_limits_concurrency_within_batches() {
+        for (max_concurrent_claims, batch_size, expected_max) in [(2, 5, 2), (5, 3, 3), (1, 4, 1)] {
+            let in_flight = AtomicUsize::new(0);
+            let max_in_flight = AtomicUsize::new(0);
+            let claimed: Vec<Vec<u32>> = claim_in_batches(
+                (0..10).collect(),
+                limits(max_concurrent_claims, batch_size, 0),
+                |item| {
+                    let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+                    async move {
+                        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(1)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                        Some(item)
+                    }
+                },
+            )
+            .collect()
+            .await;
+            assert_eq!(claimed.iter().map(Vec::len).sum::<usize>(), 10);
+            // Claims of the next batch only start once the batch completed
+            assert_eq!(max_in_flight.load(Ordering::SeqCst), expected_max);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn claim_in_batches_waits_between_batches() {
+        let start = tokio::time::Instant::now();
+        let started: Mutex<Vec<(u32, Duration)>> = Mutex::new(Vec::new());
+        let batches: Vec<Vec<u32>> =
+            claim_in_batches((0..5).collect(), limits(2, 2, 100), |item| {
+                let started = &started;
+                async move {
+                    started.lock().unwrap().push((item, start.elapsed()));
+                    Some(item)
+                }
+            })
+            .collect()
+            .await;
+        assert_eq!(batches.len(), 3);
+
+        let mut started = started.into_inner().unwrap();
+        started.sort_unstable();
+        // The first batch starts right away, each next one an interval later
+        let ms = Duration::from_millis;
+        assert_eq!(
+            started,
+            vec![
+                (0, ms(0)),
+                (1, ms(0)),
+                (2, ms(100)),
+                (3, ms(100)),
+                (4, ms(200)),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn claim_in_batches_without_interval_does_not_wait() {
+        let start = tokio::time::Instant::now();
+        let batches = claimed_batches((0..9).collect(), limits(3, 3, 0)).await;
+        assert_eq!(batches.len(), 3);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+}
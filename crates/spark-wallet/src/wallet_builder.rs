@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use platform_utils::HttpClient;
+use platform_utils::{ByteCounter, HttpClient};
 use spark::{
     header_provider::HeaderProvider,
     operator::rpc::{ConnectionManager, DefaultConnectionManager},
@@ -26,6 +26,7 @@ pub struct WalletBuilder {
     transfer_observer: Option<Arc<dyn TransferObserver>>,
     ssp_extra_header_provider: Option<Arc<dyn HeaderProvider>>,
     so_extra_header_provider: Option<Arc<dyn HeaderProvider>>,
+    operator_byte_counter: Option<Arc<ByteCounter>>,
 }
 
 impl WalletBuilder {
@@ -42,6 +43,7 @@ impl WalletBuilder {
             transfer_observer: None,
             ssp_extra_header_provider: None,
             so_extra_header_provider: None,
+            operator_byte_counter: None,
         }
     }
 
@@ -115,6 +117,13 @@ impl WalletBuilder {
         self
     }
 
+    /// Counts the bytes of the calls to the Spark Operators.
+    #[must_use]
+    pub fn with_operator_byte_counter(mut self, counter: Arc<ByteCounter>) -> Self {
+        self.operator_byte_counter = Some(counter);
+        self
+    }
+
     pub async fn build(self) -> Result<SparkWallet, SparkWalletError> {
         SparkWallet::new(
             self.config,
@@ -131,6 +140,7 @@ impl WalletBuilder {
             self.transfer_observer,
             self.ssp_extra_header_provider,
             self.so_extra_header_provider,
+            self.operator_byte_counter,
             self.cancellation_token,
         )
         .await
@@ -1,5 +1,9 @@
 mod models;
 mod server_stream;
+#[cfg(feature = "websocket-events")]
+mod ws_stream;
 
-pub use models::{EventPublisher, EventStream, SparkEvent};
+#[cfg(feature = "websocket-events")]
+pub use models::DEFAULT_WEBSOCKET_MAX_FAILURES;
+pub use models::{EventPublisher, EventStream, EventTransport, SparkEvent};
 pub use server_stream::subscribe_server_events;
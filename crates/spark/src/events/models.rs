@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 
 use crate::{services::Transfer, tree::TreeNode};
@@ -7,6 +8,27 @@ use crate::{services::Transfer, tree::TreeNode};
 pub type EventPublisher = broadcast::Sender<SparkEvent>;
 pub type EventStream = broadcast::Receiver<SparkEvent>;
 
+/// Number of consecutive failed WebSocket sessions after which the server
+/// event subscription falls back to gRPC.
+#[cfg(feature = "websocket-events")]
+pub const DEFAULT_WEBSOCKET_MAX_FAILURES: u32 = 3;
+
+/// Transport used to subscribe to the coordinator's server events.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum EventTransport {
+    /// Server-streaming gRPC (gRPC-web in WASM).
+    #[default]
+    Grpc,
+    /// The coordinator's gRPC-web WebSocket endpoint, at the coordinator's
+    /// own address.
+    ///
+    /// After `max_failures` consecutive sessions fail to connect or drop before
+    /// receiving any event, the subscription falls back to gRPC for the
+    /// lifetime of the wallet.
+    #[cfg(feature = "websocket-events")]
+    WebSocket { max_failures: u32 },
+}
+
 #[derive(Clone, Debug)]
 pub enum SparkEvent {
     Connected,
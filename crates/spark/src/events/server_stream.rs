@@ -6,22 +6,133 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, trace, warn};
 
 use crate::{
-    events::{EventPublisher, models::SparkEvent},
+    events::{
+        EventPublisher,
+        models::{EventTransport, SparkEvent},
+    },
     operator::{
-        OperatorPool,
-        rpc::spark::{SubscribeToEventsRequest, subscribe_to_events_response::Event},
+        Operator, OperatorPool,
+        rpc::spark::{
+            SubscribeToEventsRequest, SubscribeToEventsResponse,
+            subscribe_to_events_response::Event,
+        },
     },
     services::Transfer,
     tree::TreeNode,
 };
 
+#[cfg(feature = "websocket-events")]
+use crate::events::ws_stream::WebSocketEventStream;
+
+/// WebSocket transport state, tracking failed sessions for the gRPC fallback.
+#[cfg(feature = "websocket-events")]
+struct WebSocketTransport {
+    max_failures: u32,
+    failures: u32,
+}
+
+/// Picks the transport of each subscription session.
+struct TransportSelector {
+    /// The WebSocket transport, until it failed too often and the selector
+    /// fell back to gRPC.
+    #[cfg(feature = "websocket-events")]
+    websocket: Option<WebSocketTransport>,
+}
+
+impl TransportSelector {
+    fn new(transport: &EventTransport) -> Self {
+        match transport {
+            EventTransport::Grpc => Self {
+                #[cfg(feature = "websocket-events")]
+                websocket: None,
+            },
+            #[cfg(feature = "websocket-events")]
+            EventTransport::WebSocket { max_failures } => Self {
+                websocket: Some(WebSocketTransport {
+                    max_failures: *max_failures,
+                    failures: 0,
+                }),
+            },
+        }
+    }
+
+    #[cfg_attr(not(feature = "websocket-events"), allow(clippy::unused_self))]
+    async fn subscribe(
+        &self,
+        operator: &Operator,
+        request: SubscribeToEventsRequest,
+    ) -> Result<ServerEventStream, String> {
+        #[cfg(feature = "websocket-events")]
+        if self.websocket.is_some() {
+            return WebSocketEventStream::connect(operator, request)
+                .await
+                .map(ServerEventStream::WebSocket)
+                .map_err(|e| e.to_string());
+        }
+        operator
+            .client
+            .subscribe_to_events(request)
+            .await
+            .map(ServerEventStream::Grpc)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Records a session that received events, resetting the failure count.
+    #[cfg_attr(not(feature = "websocket-events"), allow(clippy::unused_self))]
+    fn record_success(&mut self) {
+        #[cfg(feature = "websocket-events")]
+        if let Some(ws) = self.websocket.as_mut() {
+            ws.failures = 0;
+        }
+    }
+
+    /// Records a session that failed to connect or dropped before receiving
+    /// any event, and switches to gRPC once the failure limit is reached.
+    #[cfg_attr(not(feature = "websocket-events"), allow(clippy::unused_self))]
+    fn record_failure(&mut self) {
+        #[cfg(feature = "websocket-events")]
+        if let Some(ws) = self.websocket.as_mut() {
+            ws.failures = ws.failures.saturating_add(1);
+            if ws.failures >= ws.max_failures {
+                warn!(
+                    "WebSocket event transport failed {} times in a row, falling back to gRPC",
+                    ws.failures
+                );
+                self.websocket = None;
+            }
+        }
+    }
+}
+
+/// An open event stream on either transport.
+enum ServerEventStream {
+    Grpc(tonic::codec::Streaming<SubscribeToEventsResponse>),
+    #[cfg(feature = "websocket-events")]
+    WebSocket(WebSocketEventStream),
+}
+
+impl ServerEventStream {
+    async fn message(&mut self) -> Result<Option<SubscribeToEventsResponse>, String> {
+        match self {
+            ServerEventStream::Grpc(stream) => stream.message().await.map_err(|e| e.to_string()),
+            #[cfg(feature = "websocket-events")]
+            ServerEventStream::WebSocket(stream) => {
+                stream.message().await.map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
 pub async fn subscribe_server_events(
     identity_public_key: PublicKey,
     operator_pool: Arc<OperatorPool>,
     publisher: &EventPublisher,
     reconnect_interval: Duration,
+    transport: EventTransport,
     cancellation_token: &mut tokio::sync::watch::Receiver<()>,
 ) {
+    let mut transport = TransportSelector::new(&transport);
+
     loop {
         match cancellation_token.has_changed() {
             Ok(true) => {
@@ -35,17 +146,17 @@ pub async fn subscribe_server_events(
             }
         }
 
-        let mut stream = match operator_pool
-            .get_coordinator()
-            .client
-            .subscribe_to_events(SubscribeToEventsRequest {
-                identity_public_key: identity_public_key.serialize().to_vec(),
-            })
-            .await
-        {
+        let request = SubscribeToEventsRequest {
+            identity_public_key: identity_public_key.serialize().to_vec(),
+        };
+        let subscription = transport
+            .subscribe(operator_pool.get_coordinator(), request)
+            .await;
+        let mut stream = match subscription {
             Ok(stream) => stream,
             Err(e) => {
                 error!("Failed to subscribe to server events: {}", e);
+                transport.record_failure();
                 tokio::select! {
                     _ = cancellation_token.changed() => {
                         info!("Cancellation token changed during backoff, stopping event subscription");
@@ -57,6 +168,7 @@ pub async fn subscribe_server_events(
             }
         };
 
+        let mut received_any = false;
         loop {
             let message = tokio::select! {
                 message = stream.message() => message,
@@ -77,89 +189,18 @@ pub async fn subscribe_server_events(
                 }
             };
 
+            if !received_any {
+                received_any = true;
+                transport.record_success();
+            }
+
             let Some(event) = response.event else {
                 warn!("Received empty event, skipping");
                 continue;
             };
 
-            let spark_event = match event {
-                Event::ReceiverTransfer(transfer_event) => {
-                    let Some(transfer) = transfer_event.transfer else {
-                        warn!("Received empty receiver transfer event, skipping");
-                        continue;
-                    };
-                    debug!(
-                        "Received receiver transfer event with transfer id {}",
-                        transfer.id
-                    );
-                    trace!(
-                        "Received receiver transfer event with transfer: {:?}",
-                        transfer
-                    );
-                    let transfer: Transfer = match transfer.try_into() {
-                        Ok(transfer) => transfer,
-                        Err(e) => {
-                            error!("Failed to convert receiver transfer event: {}", e);
-                            continue;
-                        }
-                    };
-                    SparkEvent::ReceiverTransfer(Box::new(transfer))
-                }
-                Event::SenderTransfer(transfer_event) => {
-                    let Some(transfer) = transfer_event.transfer else {
-                        warn!("Received empty sender transfer event, skipping");
-                        continue;
-                    };
-                    debug!(
-                        "Received sender transfer event with transfer id {}",
-                        transfer.id
-                    );
-                    trace!(
-                        "Received sender transfer event with transfer: {:?}",
-                        transfer
-                    );
-                    let transfer: Transfer = match transfer.try_into() {
-                        Ok(transfer) => transfer,
-                        Err(e) => {
-                            error!("Failed to convert sender transfer event: {}", e);
-                            continue;
-                        }
-                    };
-                    SparkEvent::SenderTransfer(Box::new(transfer))
-                }
-                Event::Deposit(deposit_event) => {
-                    let Some(deposit) = deposit_event.deposit else {
-                        warn!("Received empty deposit event, skipping");
-                        continue;
-                    };
-                    debug!("Received deposit event with tree node id {}", deposit.id);
-                    trace!("Received deposit event with tree node: {:?}", deposit);
-                    let deposit: TreeNode = match deposit.try_into() {
-                        Ok(deposit) => deposit,
-                        Err(e) => {
-                            error!("Failed to convert deposit event: {}", e);
-                            continue;
-                        }
-                    };
-                    SparkEvent::Deposit(Box::new(deposit))
-                }
-                Event::Connected(_) => {
-                    debug!("Received connected event");
-                    SparkEvent::Connected
-                }
-                Event::Heartbeat(_) => {
-                    trace!("Received heartbeat event");
-                    continue;
-                }
-                Event::TokenTransaction(token_transaction_event) => {
-                    let hash = hex::encode(&token_transaction_event.token_transaction_hash);
-                    debug!("Received token transaction event with hash {hash}");
-                    trace!(
-                        "Received token transaction event: {:?}",
-                        token_transaction_event
-                    );
-                    SparkEvent::TokenTransaction { hash }
-                }
+            let Some(spark_event) = to_spark_event(event) else {
+                continue;
             };
 
             debug!("Emitting spark event: {spark_event}");
@@ -172,6 +213,10 @@ pub async fn subscribe_server_events(
             }
         }
 
+        if !received_any {
+            transport.record_failure();
+        }
+
         if publisher.send(SparkEvent::Disconnected).is_err() {
             error!(
                 "Failed to send disconnected event, all receivers dropped. Quitting event subscription."
@@ -180,3 +225,88 @@ pub async fn subscribe_server_events(
         }
     }
 }
+
+/// Converts a server event into a [`SparkEvent`]. Returns `None` for heartbeats
+/// and for events that are empty or fail to convert.
+fn to_spark_event(event: Event) -> Option<SparkEvent> {
+    let spark_event = match event {
+        Event::ReceiverTransfer(transfer_event) => {
+            let Some(transfer) = transfer_event.transfer else {
+                warn!("Received empty receiver transfer event, skipping");
+                return None;
+            };
+            debug!(
+                "Received receiver transfer event with transfer id {}",
+                transfer.id
+            );
+            trace!(
+                "Received receiver transfer event with transfer: {:?}",
+                transfer
+            );
+            let transfer: Transfer = match transfer.try_into() {
+                Ok(transfer) => transfer,
+                Err(e) => {
+                    error!("Failed to convert receiver transfer event: {}", e);
+                    return None;
+                }
+            };
+            SparkEvent::ReceiverTransfer(Box::new(transfer))
+        }
+        Event::SenderTransfer(transfer_event) => {
+            let Some(transfer) = transfer_event.transfer else {
+                warn!("Received empty sender transfer event, skipping");
+                return None;
+            };
+            debug!(
+                "Received sender transfer event with transfer id {}",
+                transfer.id
+            );
+            trace!(
+                "Received sender transfer event with transfer: {:?}",
+                transfer
+            );
+            let transfer: Transfer = match transfer.try_into() {
+                Ok(transfer) => transfer,
+                Err(e) => {
+                    error!("Failed to convert sender transfer event: {}", e);
+                    return None;
+                }
+            };
+            SparkEvent::SenderTransfer(Box::new(transfer))
+        }
+        Event::Deposit(deposit_event) => {
+            let Some(deposit) = deposit_event.deposit else {
+                warn!("Received empty deposit event, skipping");
+                return None;
+            };
+            debug!("Received deposit event with tree node id {}", deposit.id);
+            trace!("Received deposit event with tree node: {:?}", deposit);
+            let deposit: TreeNode = match deposit.try_into() {
+                Ok(deposit) => deposit,
+                Err(e) => {
+                    error!("Failed to convert deposit event: {}", e);
+                    return None;
+                }
+            };
+            SparkEvent::Deposit(Box::new(deposit))
+        }
+        Event::Connected(_) => {
+            debug!("Received connected event");
+            SparkEvent::Connected
+        }
+        Event::Heartbeat(_) => {
+            trace!("Received heartbeat event");
+            return None;
+        }
+        Event::TokenTransaction(token_transaction_event) => {
+            let hash = hex::encode(&token_transaction_event.token_transaction_hash);
+            debug!("Received token transaction event with hash {hash}");
+            trace!(
+                "Received token transaction event: {:?}",
+                token_transaction_event
+            );
+            SparkEvent::TokenTransaction { hash }
+        }
+    };
+    Some(spark_event)
+}
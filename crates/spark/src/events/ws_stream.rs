@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use futures::{SinkExt, StreamExt};
+use prost::Message as _;
+use tokio_tungstenite_wasm::{Message, WebSocketStream};
+use tracing::trace;
+
+use crate::operator::{
+    Operator,
+    rpc::{
+        OperatorRpcError,
+        spark::{SubscribeToEventsRequest, SubscribeToEventsResponse},
+    },
+};
+
+/// Subprotocol of the gRPC-web WebSocket transport.
+const GRPC_WEBSOCKETS_PROTOCOL: &str = "grpc-websockets";
+const SUBSCRIBE_TO_EVENTS_PATH: &str = "/spark.SparkService/subscribe_to_events";
+/// Length of the gRPC-web frame prefix: one flag byte and a big-endian u32 length.
+const FRAME_PREFIX_LEN: usize = 5;
+/// Flag bit marking a frame carrying headers or trailers instead of a message.
+const FRAME_FLAG_TRAILERS: u8 = 0x80;
+/// Flag bit marking a compressed message, which the client never asks for.
+const FRAME_FLAG_COMPRESSED: u8 = 0x01;
+/// Prefix of a client WebSocket message carrying request bytes.
+const CLIENT_MESSAGE_DATA: u8 = 0x00;
+/// Client WebSocket message closing the request stream.
+const CLIENT_MESSAGE_FINISH: u8 = 0x01;
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum WebSocketEventError {
+    #[error("websocket error: {0}")]
+    Socket(#[from] tokio_tungstenite_wasm::Error),
+    #[error("failed to get session headers: {0}")]
+    Auth(#[from] OperatorRpcError),
+    #[error("failed to decode event: {0}")]
+    Decode(#[from] prost::DecodeError),
+    #[error("invalid operator address: {0}")]
+    Address(String),
+    #[error("protocol error: {0}")]
+    Protocol(String),
+    #[error("grpc status {status}: {message}")]
+    Status { status: String, message: String },
+}
+
+/// Server event subscription over the operator's gRPC-web WebSocket endpoint.
+///
+/// Follows the `grpc-websockets` protocol of the gRPC-web proxies: browsers
+/// can't set headers on a WebSocket handshake, so the first message carries
+/// the request headers as `key: value\r\n` lines, followed by the gRPC-web
+/// framed [`SubscribeToEventsRequest`] prefixed with `0x00`, and a `0x01`
+/// message closing the request side. The operator answers with the gRPC-web
+/// response body, whose frames may be split across or merged into messages.
+///
+/// The socket is only opened to the coordinator's own address, as the session
+/// headers must not be sent anywhere else.
+pub(super) struct WebSocketEventStream {
+    socket: WebSocketStream,
+    reader: FrameReader,
+}
+
+impl WebSocketEventStream {
+    pub(super) async fn connect(
+        operator: &Operator,
+        request: SubscribeToEventsRequest,
+    ) -> Result<Self, WebSocketEventError> {
+        let url = websocket_url(&operator.address)?;
+        let mut headers = operator.client.auth_headers().await?;
+        headers.insert(
+            "content-type".to_string(),
+            "application/grpc-web+proto".to_string(),
+        );
+        headers.insert("x-grpc-web".to_string(), "1".to_string());
+
+        let mut socket =
+            tokio_tungstenite_wasm::connect_with_protocols(&url, &[GRPC_WEBSOCKETS_PROTOCOL])
+                .await?;
+        socket
+            .send(Message::binary(encode_headers(&headers)))
+            .await?;
+        let mut message = vec![CLIENT_MESSAGE_DATA];
+        message.extend(encode_frame(&request.encode_to_vec())?);
+        socket.send(Message::binary(message)).await?;
+        socket
+            .send(Message::binary(vec![CLIENT_MESSAGE_FINISH]))
+            .await?;
+        Ok(Self {
+            socket,
+            reader: FrameReader::default(),
+        })
+    }
+
+    /// Returns the next event, or `None` once the server ended the stream.
+    pub(super) async fn message(
+        &mut self,
+    ) -> Result<Option<SubscribeToEventsResponse>, WebSocketEventError> {
+        loop {
+            while let Some(frame) = self.reader.next_frame()? {
+                match frame {
+                    Frame::Data(data) => {
+                        return Ok(Some(SubscribeToEventsResponse::decode(&data[..])?));
+                    }
+                    Frame::Trailers(trailers) => {
+                        if check_status(&trailers)? {
+                            return Ok(None);
+                        }
+                    }
+                }
+            }
+            let Some(message) = self.socket.next().await else {
+                return Ok(None);
+            };
+            match message? {
+                Message::Binary(data) => self.reader.push(&data),
+                Message::Close(frame) => {
+                    trace!("WebSocket closed by server: {frame:?}");
+                    return Ok(None);
+                }
+                other => trace!("Ignoring non-binary WebSocket message: {other:?}"),
+            }
+        }
+    }
+}
+
+/// Builds the WebSocket url of the event subscription from an operator's
+/// `http(s)` address.
+fn websocket_url(address: &str) -> Result<String, WebSocketEventError> {
+    let base = address.trim_end_matches('/');
+    let base = if let Some(host) = base.strip_prefix("https://") {
+        format!("wss://{host}")
+    } else if let Some(host) = base.strip_prefix("http://") {
+        format!("ws://{host}")
+    } else {
+        return Err(WebSocketEventError::Address(address.to_string()));
+    };
+    Ok(format!("{base}{SUBSCRIBE_TO_EVENTS_PATH}"))
+}
+
+fn encode_headers(headers: &HashMap<String, String>) -> Vec<u8> {
+    headers
+        .iter()
+        .map(|(key, value)| format!("{key}: {value}\r\n"))
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// Prefixes a message with its uncompressed gRPC-web frame header.
+fn encode_frame(message: &[u8]) -> Result<Vec<u8>, WebSocketEventError> {
+    let len = u32::try_from(message.len())
+        .map_err(|_| WebSocketEventError::Protocol("request too large".to_string()))?;
+    let mut frame = Vec::with_capacity(message.len().saturating_add(FRAME_PREFIX_LEN));
+    frame.push(0);
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(message);
+    Ok(frame)
+}
+
+/// Parses a headers or trailers frame made of `key: value\r\n` lines. Keys
+/// are lowercased.
+fn parse_trailers(data: &[u8]) -> HashMap<String, String> {
+    String::from_utf8_lossy(data)
+        .split("\r\n")
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect()
+}
+
+/// Returns true if the headers end the stream successfully, an error if they
+/// carry a failed gRPC status, and false if they carry no status at all, as
+/// the response headers sent before the first event.
+fn check_status(trailers: &HashMap<String, String>) -> Result<bool, WebSocketEventError> {
+    match trailers.get("grpc-status").map(String::as_str) {
+        None => Ok(false),
+        Some("0") => Ok(true),
+        Some(status) => Err(WebSocketEventError::Status {
+            status: status.to_string(),
+            message: trailers.get("grpc-message").cloned().unwrap_or_default(),
+        }),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Frame {
+    Data(Vec<u8>),
+    Trailers(HashMap<String, String>),
+}
+
+/// Reassembles the gRPC-web frames of the response body from the WebSocket
+/// messages.
+#[derive(Default)]
+struct FrameReader {
+    buffer: Vec<u8>,
+}
+
+impl FrameReader {
+    fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Returns the next complete frame, or `None` if more data is needed.
+    fn next_frame(&mut self) -> Result<Option<Frame>, WebSocketEventError> {
+        let Some((prefix, rest)) = self.buffer.split_first_chunk::<FRAME_PREFIX_LEN>() else {
+            return Ok(None);
+        };
+        let [flag, len @ ..] = *prefix;
+        let len = usize::try_from(u32::from_be_bytes(len))
+            .map_err(|_| WebSocketEventError::Protocol("frame too large".to_string()))?;
+        let Some(payload) = rest.get(..len) else {
+            return Ok(None);
+        };
+        let frame = if flag & FRAME_FLAG_TRAILERS != 0 {
+            Frame::Trailers(parse_trailers(payload))
+        } else if flag & FRAME_FLAG_COMPRESSED != 0 {
+            return Err(WebSocketEventError::Protocol(
+                "unexpected compressed frame".to_string(),
+            ));
+        } else {
+            Frame::Data(payload.to_vec())
+        };
+        self.buffer.drain(..FRAME_PREFIX_LEN.saturating_add(len));
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn trailers_frame(text: &str) -> Vec<u8> {
+        let mut frame = encode_frame(text.as_bytes()).unwrap();
+        frame[0] = FRAME_FLAG_TRAILERS;
+        frame
+    }
+
+    #[test_all]
+    fn test_websocket_url_from_operator_address() {
+        assert_eq!(
+            websocket_url("https://0.spark.lightspark.com/").unwrap(),
+            "wss://0.spark.lightspark.com/spark.SparkService/subscribe_to_events"
+        );
+        assert_eq!(
+            websocket_url("http://localhost:8535").unwrap(),
+            "ws://localhost:8535/spark.SparkService/subscribe_to_events"
+        );
+        assert!(websocket_url("localhost:8535").is_err());
+    }
+
+    #[test_all]
+    fn test_frame_reader_reassembles_split_frames() {
+        let mut data = encode_frame(b"first").unwrap();
+        data.extend(encode_frame(b"second").unwrap());
+        let mut reader = FrameReader::default();
+        reader.push(&data[..3]);
+        assert_eq!(reader.next_frame().unwrap(), None);
+        reader.push(&data[3..8]);
+        assert_eq!(reader.next_frame().unwrap(), None);
+        reader.push(&data[8..]);
+        assert_eq!(
+            reader.next_frame().unwrap(),
+            Some(Frame::Data(b"first".to_vec()))
+        );
+        assert_eq!(
+            reader.next_frame().unwrap(),
+            Some(Frame::Data(b"second".to_vec()))
+        );
+        assert_eq!(reader.next_frame().unwrap(), None);
+    }
+
+    #[test_all]
+    fn test_frame_reader_parses_trailers() {
+        let mut reader = FrameReader::default();
+        reader.push(&trailers_frame(
+            "Grpc-Status: 14\r\ngrpc-message: unavailable\r\n",
+        ));
+        let Some(Frame::Trailers(trailers)) = reader.next_frame().unwrap() else {
+            panic!("expected trailers");
+        };
+        assert!(matches!(
+            check_status(&trailers),
+            Err(WebSocketEventError::Status { status, message })
+                if status == "14" && message == "unavailable"
+        ));
+    }
+
+    #[test_all]
+    fn test_check_status() {
+        assert!(
+            !check_status(&parse_trailers(
+                b"content-type: application/grpc-web+proto\r\n"
+            ))
+            .unwrap()
+        );
+        assert!(check_status(&parse_trailers(b"grpc-status: 0\r\n")).unwrap());
+    }
+
+    #[test_all]
+    fn test_frame_reader_rejects_compressed_frames() {
+        let mut frame = encode_frame(b"data").unwrap();
+        frame[0] = FRAME_FLAG_COMPRESSED;
+        let mut reader = FrameReader::default();
+        reader.push(&frame);
+        assert!(matches!(
+            reader.next_frame(),
+            Err(WebSocketEventError::Protocol(_))
+        ));
+    }
+}
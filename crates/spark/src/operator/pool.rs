@@ -2,12 +2,16 @@ use std::sync::Arc;
 
 use bitcoin::secp256k1::PublicKey;
 use frost_secp256k1_tr::Identifier;
+use platform_utils::ByteCounter;
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as};
 
 use crate::{
     header_provider::{CombinedHeaderProvider, HeaderProvider},
-    operator::rpc::{ConnectionManager, OperatorRpcError, SoAuthHeaderProvider, SparkRpcClient},
+    operator::rpc::{
+        ConnectionManager, OperatorRateLimit, OperatorRpcError, OperatorRpcStats,
+        SoAuthHeaderProvider, SparkRpcClient,
+    },
     session_store::SessionStore,
     signer::SparkSigner,
 };
@@ -91,6 +95,7 @@ impl OperatorConfig {}
 #[derive(Clone)]
 pub struct Operator {
     pub client: SparkRpcClient,
+    pub address: String,
     pub id: usize,
     pub identifier: Identifier,
     pub identity_public_key: PublicKey,
@@ -99,6 +104,7 @@ pub struct Operator {
 pub struct OperatorPool {
     coordinator_index: usize,
     operators: Vec<Operator>,
+    rpc_stats: Arc<OperatorRpcStats>,
 }
 
 impl OperatorPool {
@@ -108,7 +114,10 @@ impl OperatorPool {
         session_store: Arc<dyn SessionStore>,
         spark_signer: Arc<dyn SparkSigner>,
         extra_header_provider: Option<Arc<dyn HeaderProvider>>,
+        byte_counter: Option<Arc<ByteCounter>>,
+        rate_limit: Option<&OperatorRateLimit>,
     ) -> Result<Self, OperatorRpcError> {
+        let rpc_stats = Arc::new(OperatorRpcStats::default());
         let mut operators = Vec::new();
         for operator in &config.operators {
             let transport = connection_manager.get_transport(operator).await?;
@@ -125,9 +134,17 @@ impl OperatorPool {
                 ])),
                 None => auth_provider,
             };
-            let client = SparkRpcClient::new(transport, header_provider, operator.id);
+            let client = SparkRpcClient::new(
+                transport,
+                header_provider,
+                operator.id,
+                byte_counter.clone(),
+                rate_limit,
+                Arc::clone(&rpc_stats),
+            );
             operators.push(Operator {
                 client,
+                address: operator.address.clone(),
                 id: operator.id,
                 identifier: operator.identifier,
                 identity_public_key: operator.identity_public_key,
@@ -137,8 +154,16 @@ impl OperatorPool {
         Ok(Self {
             coordinator_index: config.coordinator_index,
             operators,
+            rpc_stats,
         })
     }
+
+    /// Returns the counters of the queries coalesced, delayed or dropped
+    /// across all operators.
+    pub fn rpc_stats(&self) -> Arc<OperatorRpcStats> {
+        Arc::clone(&self.rpc_stats)
+    }
+
     /// Returns the coordinator operator.
     pub fn get_coordinator(&self) -> &Operator {
         self.operators.get(self.coordinator_index).unwrap()
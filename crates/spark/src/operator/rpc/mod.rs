@@ -3,11 +3,13 @@ mod connection_manager;
 mod error;
 pub(crate) mod metadata;
 mod spark_rpc_client;
+mod throttle;
 mod transport;
 pub use auth::SoAuthHeaderProvider;
 pub use connection_manager::*;
 pub use error::*;
 pub use spark_rpc_client::*;
+pub use throttle::{OperatorRateLimit, OperatorRpcStats};
 
 pub mod spark {
     #![allow(clippy::all)]
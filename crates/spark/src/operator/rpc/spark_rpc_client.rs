@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::error::Result;
 use super::metadata::set_idempotency_key;
 use super::spark::*;
 use super::spark_token;
+use super::throttle::{OperatorRateLimit, OperatorRpcStats, QueryThrottle};
 use crate::header_provider::HeaderProvider;
 use crate::operator::rpc::OperatorRpcError;
 use crate::operator::rpc::spark::query_nodes_request::Source;
@@ -16,7 +18,9 @@ use crate::operator::rpc::spark_token::StartTransactionRequest;
 use crate::operator::rpc::spark_token::StartTransactionResponse;
 use crate::operator::rpc::spark_token::spark_token_service_client::SparkTokenServiceClient;
 use crate::operator::rpc::transport::grpc_client::Transport;
+use crate::operator::rpc::transport::metered::MeteredTransport;
 use crate::utils::paging::{PagingFilter, PagingResult};
+use platform_utils::ByteCounter;
 use tonic::Request;
 use tonic::Status;
 use tonic::metadata::Ascii;
@@ -43,13 +47,16 @@ pub struct QueryAllTokenOutputsRequest {
 
 #[derive(Clone)]
 pub struct SparkRpcClient {
-    transport: Transport,
+    transport: MeteredTransport<Transport>,
     header_provider: Arc<dyn HeaderProvider>,
     /// Operator index in the pool (0..N). Surfaced as a span field by
     /// the per-method `#[instrument]` attributes on the
     /// `spark::operator_rpc` target, so a downstream subscriber can
     /// attribute a slow RPC to a specific operator.
     operator_id: usize,
+    /// Coalesces and rate limits the read-only queries to this operator,
+    /// shared by all clones of the client.
+    throttle: Arc<QueryThrottle>,
 }
 
 impl SparkRpcClient {
@@ -57,11 +64,15 @@ impl SparkRpcClient {
         channel: Transport,
         header_provider: Arc<dyn HeaderProvider>,
         operator_id: usize,
+        byte_counter: Option<Arc<ByteCounter>>,
+        rate_limit: Option<&OperatorRateLimit>,
+        rpc_stats: Arc<OperatorRpcStats>,
     ) -> Self {
         Self {
-            transport: channel,
+            transport: MeteredTransport::new(channel, byte_counter),
             header_provider,
             operator_id,
+            throttle: Arc::new(QueryThrottle::new(rate_limit, rpc_stats)),
         }
     }
 
@@ -88,12 +99,15 @@ impl SparkRpcClient {
             "Calling query_unused_deposit_addresses with request: {:?}",
             req
         );
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_unused_deposit_addresses(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_unused_deposit_addresses", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_unused_deposit_addresses(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -144,23 +158,29 @@ impl SparkRpcClient {
         req: TransferFilter,
     ) -> Result<QueryTransfersResponse> {
         debug!("Querying pending transfers with filter: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_pending_transfers(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_pending_transfers", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_pending_transfers(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
     pub async fn query_all_transfers(&self, req: TransferFilter) -> Result<QueryTransfersResponse> {
         debug!("Calling query_all_transfers with filter: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_all_transfers(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_all_transfers", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_all_transfers(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -280,12 +300,15 @@ impl SparkRpcClient {
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
     pub async fn query_nodes(&self, req: QueryNodesRequest) -> Result<QueryNodesResponse> {
         debug!("Calling query_nodes with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_nodes(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_nodes", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_nodes(req).await?) }
+                })
+            })
+            .await
     }
 
     /// Paginated version of query_nodes
@@ -356,12 +379,15 @@ impl SparkRpcClient {
         req: spark_token::QueryTokenOutputsRequest,
     ) -> Result<spark_token::QueryTokenOutputsResponse> {
         debug!("Calling query_token_outputs with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_token_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_token_outputs(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_token_outputs", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_token_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_token_outputs(req).await?) }
+                })
+            })
+            .await
     }
 
     /// Query all token outputs by automatically fetching all pages.
@@ -413,12 +439,15 @@ impl SparkRpcClient {
         req: spark_token::QueryTokenMetadataRequest,
     ) -> Result<spark_token::QueryTokenMetadataResponse> {
         debug!("Calling query_token_metadata with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_token_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_token_metadata(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_token_metadata", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_token_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_token_metadata(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -427,12 +456,15 @@ impl SparkRpcClient {
         req: spark_token::QueryTokenTransactionsRequest,
     ) -> Result<spark_token::QueryTokenTransactionsResponse> {
         debug!("Calling query_token_transactions with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_token_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_token_transactions(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_token_transactions", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_token_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_token_transactions(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -441,23 +473,29 @@ impl SparkRpcClient {
         req: QuerySparkInvoicesRequest,
     ) -> Result<QuerySparkInvoicesResponse> {
         debug!("Calling query_spark_invoices with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_spark_invoices(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_spark_invoices", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_spark_invoices(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
     pub async fn query_htlc(&self, req: QueryHtlcRequest) -> Result<QueryHtlcResponse> {
         debug!("Calling query_htlc with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_htlc(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_htlc", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_htlc(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -545,12 +583,15 @@ impl SparkRpcClient {
             "Calling query_static_deposit_addresses with request: {:?}",
             req
         );
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.query_static_deposit_addresses(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("query_static_deposit_addresses", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.query_static_deposit_addresses(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -559,12 +600,15 @@ impl SparkRpcClient {
         req: GetUtxosForIdentityRequest,
     ) -> Result<GetUtxosForIdentityResponse> {
         debug!("Calling get_utxos_for_identity with request: {:?}", req);
-        self.call_with_auth_retry(|interceptor| {
-            let mut client = self.spark_service_client(interceptor);
-            let req = req.clone();
-            async move { Ok(client.get_utxos_for_identity(req).await?) }
-        })
-        .await
+        self.throttle
+            .query("get_utxos_for_identity", &req, || {
+                self.call_with_auth_retry(|interceptor| {
+                    let mut client = self.spark_service_client(interceptor);
+                    let req = req.clone();
+                    async move { Ok(client.get_utxos_for_identity(req).await?) }
+                })
+            })
+            .await
     }
 
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
@@ -598,6 +642,15 @@ impl SparkRpcClient {
         .await
     }
 
+    /// Returns the session headers this client attaches to its calls, for
+    /// transports that talk to the operator outside of gRPC.
+    pub async fn auth_headers(&self) -> Result<HashMap<String, String>> {
+        self.header_provider
+            .headers()
+            .await
+            .map_err(|e| OperatorRpcError::Authentication(e.to_string()))
+    }
+
     #[instrument(level = "info", target = "spark::operator_rpc", skip_all, fields(operator_id = self.operator_id))]
     pub async fn update_wallet_setting(
         &self,
@@ -668,14 +721,16 @@ impl SparkRpcClient {
     fn spark_service_client(
         &self,
         interceptor: HeaderInterceptor,
-    ) -> SparkServiceClient<InterceptedService<Transport, HeaderInterceptor>> {
+    ) -> SparkServiceClient<InterceptedService<MeteredTransport<Transport>, HeaderInterceptor>>
+    {
         SparkServiceClient::with_interceptor(self.transport.clone(), interceptor)
     }
 
     fn spark_token_service_client(
         &self,
         interceptor: HeaderInterceptor,
-    ) -> SparkTokenServiceClient<InterceptedService<Transport, HeaderInterceptor>> {
+    ) -> SparkTokenServiceClient<InterceptedService<MeteredTransport<Transport>, HeaderInterceptor>>
+    {
         SparkTokenServiceClient::with_interceptor(self.transport.clone(), interceptor)
     }
 
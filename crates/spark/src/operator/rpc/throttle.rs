@@ -0,0 +1,230 @@
+use std::any::Any;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use platform_utils::time::Instant;
+use serde::{Deserialize, Serialize};
+use tonic::Status;
+
+use super::error::{OperatorRpcError, Result};
+use crate::utils::coalesce::Coalescer;
+
+/// Limits the rate of the read-only queries sent to each operator.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OperatorRateLimit {
+    /// The number of queries sent per second, at most
+    pub max_queries_per_second: u32,
+    /// How long a query may wait for its turn before it's dropped
+    pub max_wait_ms: u64,
+}
+
+/// Counts the queries the throttles of an operator pool saved or held back.
+#[derive(Debug, Default)]
+pub struct OperatorRpcStats {
+    coalesced: AtomicU64,
+    delayed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl OperatorRpcStats {
+    /// Queries answered by an identical query already in flight
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+
+    /// Queries that waited for the rate limit
+    pub fn delayed(&self) -> u64 {
+        self.delayed.load(Ordering::Relaxed)
+    }
+
+    /// Queries that failed because the rate limit would delay them too long
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+type QueryKey = (&'static str, Vec<u8>);
+
+/// Deduplicates concurrent identical queries to an operator and applies the
+/// rate limit to the ones actually sent.
+///
+/// Only the queries in flight are shared, responses aren't cached. If the
+/// shared query fails, each waiting caller sends its own.
+pub(crate) struct QueryThrottle {
+    in_flight: Coalescer<QueryKey, Box<dyn Any + Send + Sync>>,
+    rate_limit: Option<RateLimiter>,
+    stats: Arc<OperatorRpcStats>,
+}
+
+impl QueryThrottle {
+    pub(crate) fn new(
+        rate_limit: Option<&OperatorRateLimit>,
+        stats: Arc<OperatorRpcStats>,
+    ) -> Self {
+        Self {
+            in_flight: Coalescer::new(),
+            rate_limit: rate_limit
+                .filter(|limit| limit.max_queries_per_second > 0)
+                .map(RateLimiter::new),
+            stats,
+        }
+    }
+
+    /// Runs `query` unless an identical query, `method` with the same
+    /// `request`, is in flight, in which case its response is returned.
+    pub(crate) async fn query<Req, T, F, Fut>(
+        &self,
+        method: &'static str,
+        request: &Req,
+        query: F,
+    ) -> Result<T>
+    where
+        Req: prost::Message,
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let key = (method, request.encode_to_vec());
+        let cell = self.in_flight.cell(
+            &key,
+            |_| {},
+            |_| {
+                self.stats.coalesced.fetch_add(1, Ordering::Relaxed);
+            },
+            || (),
+        );
+        let result = cell
+            .get_or_try_init(|| async move {
+                self.wait_for_turn().await?;
+                let response = query().await?;
+                Ok::<_, OperatorRpcError>(Box::new(response) as Box<dyn Any + Send + Sync>)
+            })
+            .await
+            .map(|response| response.downcast_ref::<T>().cloned());
+
+        self.in_flight.release(&key, &cell);
+
+        result?.ok_or_else(|| {
+            OperatorRpcError::Unexpected(format!("Mismatched response type for {method}"))
+        })
+    }
+
+    async fn wait_for_turn(&self) -> Result<()> {
+        let Some(rate_limit) = &self.rate_limit else {
+            return Ok(());
+        };
+        let Some(wait) = rate_limit.reserve() else {
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            return Err(OperatorRpcError::Connection(Box::new(
+                Status::resource_exhausted("Operator query rate limit exceeded"),
+            )));
+        };
+        if !wait.is_zero() {
+            self.stats.delayed.fetch_add(1, Ordering::Relaxed);
+            platform_utils::tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+}
+
+/// Spaces the queries evenly, `interval` apart.
+struct RateLimiter {
+    interval: Duration,
+    max_wait: Duration,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(limit: &OperatorRateLimit) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / limit.max_queries_per_second,
+            max_wait: Duration::from_millis(limit.max_wait_ms),
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// Reserves the next free slot, returning how long to wait for it, or
+    /// `None` if that's longer than `max_wait`.
+    fn reserve(&self) -> Option<Duration> {
+        let now = Instant::now();
+        let mut next_slot = self.next_slot.lock().unwrap();
+        let slot = next_slot.map_or(now, |next| next.max(now));
+        let wait = slot.saturating_duration_since(now);
+        if wait > self.max_wait {
+            return None;
+        }
+        *next_slot = Some(slot + self.interval);
+        Some(wait)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use macros::async_test_all;
+
+    use super::*;
+    use crate::operator::rpc::spark::QueryHtlcRequest;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn request(limit: i64) -> QueryHtlcRequest {
+        QueryHtlcRequest {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    #[async_test_all]
+    async fn test_coalesces_identical_queries() {
+        let stats = Arc::new(OperatorRpcStats::default());
+        let throttle = QueryThrottle::new(None, Arc::clone(&stats));
+        let calls = AtomicUsize::new(0);
+        let query = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            platform_utils::tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(7u64)
+        };
+
+        let (a, b, c) = futures::join!(
+            throttle.query("query_htlc", &request(1), query),
+            throttle.query("query_htlc", &request(1), query),
+            throttle.query("query_htlc", &request(2), query),
+        );
+
+        assert_eq!((a.unwrap(), b.unwrap(), c.unwrap()), (7, 7, 7));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(stats.coalesced(), 1);
+        assert!(throttle.in_flight.is_empty());
+    }
+
+    #[async_test_all]
+    async fn test_drops_queries_waiting_too_long() {
+        let stats = Arc::new(OperatorRpcStats::default());
+        let limit = OperatorRateLimit {
+            max_queries_per_second: 1,
+            max_wait_ms: 0,
+        };
+        let throttle = QueryThrottle::new(Some(&limit), Arc::clone(&stats));
+
+        let first = throttle
+            .query("query_htlc", &request(1), || async { Ok(1u64) })
+            .await;
+        let second = throttle
+            .query("query_htlc", &request(2), || async { Ok(2u64) })
+            .await;
+
+        assert_eq!(first.unwrap(), 1);
+        assert!(matches!(
+            second,
+            Err(OperatorRpcError::Connection(status))
+                if status.code() == tonic::Code::ResourceExhausted
+        ));
+        assert_eq!(stats.dropped(), 1);
+        assert_eq!(stats.delayed(), 0);
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, ready},
+};
+
+use bytes::Buf;
+use http::{Request, Response};
+use http_body::{Body, Frame, SizeHint};
+use platform_utils::ByteCounter;
+use tonic::body::BoxBody;
+use tower_service::Service;
+
+/// A transport counting the body bytes of the calls passing through it.
+#[derive(Clone)]
+pub struct MeteredTransport<T> {
+    inner: T,
+    counter: Option<Arc<ByteCounter>>,
+}
+
+impl<T> MeteredTransport<T> {
+    pub fn new(inner: T, counter: Option<Arc<ByteCounter>>) -> Self {
+        Self { inner, counter }
+    }
+}
+
+impl<T, ResBody> Service<Request<BoxBody>> for MeteredTransport<T>
+where
+    T: Service<Request<BoxBody>, Response = Response<ResBody>>,
+{
+    type Response = Response<MeteredBody<ResBody>>;
+    type Error = T::Error;
+    type Future = MeteredFuture<T::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<BoxBody>) -> Self::Future {
+        let req = match &self.counter {
+            Some(counter) => {
+                let counter = Arc::clone(counter);
+                req.map(|body| BoxBody::new(MeteredBody::new(body, Some(counter), true)))
+            }
+            None => req,
+        };
+        MeteredFuture {
+            inner: Box::pin(self.inner.call(req)),
+            counter: self.counter.clone(),
+        }
+    }
+}
+
+pub struct MeteredFuture<F> {
+    inner: Pin<Box<F>>,
+    counter: Option<Arc<ByteCounter>>,
+}
+
+impl<F, ResBody, E> Future for MeteredFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = Result<Response<MeteredBody<ResBody>>, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = ready!(self.inner.as_mut().poll(cx));
+        let counter = self.counter.clone();
+        Poll::Ready(result.map(|res| res.map(|body| MeteredBody::new(body, counter, false))))
+    }
+}
+
+/// A body counting the bytes of its data frames.
+pub struct MeteredBody<B> {
+    inner: Pin<Box<B>>,
+    counter: Option<Arc<ByteCounter>>,
+    sent: bool,
+}
+
+impl<B> MeteredBody<B> {
+    fn new(inner: B, counter: Option<Arc<ByteCounter>>, sent: bool) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            counter,
+            sent,
+        }
+    }
+}
+
+impl<B: Body> Body for MeteredBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let poll = self.inner.as_mut().poll_frame(cx);
+        if let (Poll::Ready(Some(Ok(frame))), Some(counter)) = (&poll, &self.counter)
+            && let Some(data) = frame.data_ref()
+        {
+            let bytes = data.remaining() as u64;
+            if self.sent {
+                counter.add_sent(bytes);
+            } else {
+                counter.add_received(bytes);
+            }
+        }
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
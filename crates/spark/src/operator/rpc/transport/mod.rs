@@ -3,6 +3,7 @@
     path = "grpc_client_wasm.rs"
 )]
 pub mod grpc_client;
+pub mod metered;
 
 #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
 pub(crate) mod retry_channel;
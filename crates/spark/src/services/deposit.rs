@@ -333,6 +333,15 @@ impl DepositService {
         Ok(resp.transfer_id)
     }
 
+    /// The fee of refunding a static deposit to `refund_address`. The refund
+    /// spends a single output, so its size doesn't depend on the deposit.
+    pub fn static_deposit_refund_fee(&self, refund_address: &Address, fee: Fee) -> u64 {
+        let refund_tx = create_static_deposit_refund_tx(OutPoint::null(), 0, refund_address);
+        // Account for witness data that will be added after signing
+        let signed_vsize = refund_tx.vsize() as u64 + SCHNORR_SIG_WITNESS_VBYTES;
+        fee.to_sats(signed_vsize)
+    }
+
     pub async fn refund_static_deposit(
         &self,
         tx: Transaction,
@@ -354,7 +363,6 @@ impl DepositService {
             .ok_or(ServiceError::InvalidOutputIndex)?;
 
         // Create the refund transaction with a dummy output amount.
-        // The witness vsize is accounted for separately via SCHNORR_SIG_WITNESS_VBYTES.
         let mut refund_tx = create_static_deposit_refund_tx(
             OutPoint {
                 txid,
@@ -364,9 +372,7 @@ impl DepositService {
             &refund_address,
         );
 
-        // Account for witness data that will be added after signing
-        let signed_vsize = refund_tx.vsize() as u64 + SCHNORR_SIG_WITNESS_VBYTES;
-        let fee_sats = fee.to_sats(signed_vsize);
+        let fee_sats = self.static_deposit_refund_fee(&refund_address, fee);
         if fee_sats < MIN_REFUND_FEE_SATS {
             return Err(ServiceError::Generic(format!(
                 "fee must be at least {} sats",
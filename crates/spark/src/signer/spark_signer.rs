@@ -341,6 +341,13 @@ pub trait SparkSigner: Send + Sync + 'static {
         false
     }
 
+    /// Whether this signer only knows the identity public key and can't sign.
+    /// The wallet then skips everything that needs the keys: verifying leaf
+    /// ownership, renewing timelocks and claiming transfers. Defaults to false.
+    fn is_watch_only(&self) -> bool {
+        false
+    }
+
     /// Returns the static-deposit public key at `index`. The wallet hands this
     /// to the operators to derive a static-deposit address. Analogous to
     /// [`get_public_key_for_leaf`](Self::get_public_key_for_leaf).
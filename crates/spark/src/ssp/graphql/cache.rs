@@ -0,0 +1,184 @@
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+use platform_utils::time::Instant;
+use platform_utils::tokio::sync::OnceCell;
+
+use crate::ssp::graphql::error::GraphQLResult;
+use crate::utils::coalesce::Coalescer;
+
+/// Size-bounded cache for idempotent SSP queries.
+///
+/// Concurrent lookups of the same key share a single request, and successful
+/// responses are served from the cache until `ttl` passes. Errors are never
+/// cached. When the cache is full, the least recently used entry is evicted.
+pub(crate) struct QueryCache<K, V> {
+    ttl: Duration,
+    capacity: usize,
+    entries: Coalescer<K, V, CacheEntry>,
+}
+
+struct CacheEntry {
+    created_at: Instant,
+    last_used: Instant,
+}
+
+impl<K, V> QueryCache<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Coalescer::new(),
+        }
+    }
+
+    /// Returns the cached response for `key`, joins an identical request that
+    /// is in flight, or runs `fetch`.
+    pub(crate) async fn get_or_fetch<F, Fut>(&self, key: K, fetch: F) -> GraphQLResult<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = GraphQLResult<V>>,
+    {
+        let cell = self.cell(&key);
+        let result = cell.get_or_try_init(fetch).await.cloned();
+        if result.is_err() {
+            // Drop the failed entry so the next lookup sends a new request
+            self.entries.release(&key, &cell);
+        }
+        result
+    }
+
+    fn cell(&self, key: &K) -> Arc<OnceCell<V>> {
+        let now = Instant::now();
+        self.entries.cell(
+            key,
+            |entries| {
+                entries.retain(|_, entry| {
+                    now.saturating_duration_since(entry.meta.created_at) < self.ttl
+                        || !entry.is_done()
+                });
+                if !entries.contains_key(key)
+                    && entries.len() >= self.capacity
+                    && let Some(lru_key) = entries
+                        .iter()
+                        .min_by_key(|(_, entry)| entry.meta.last_used)
+                        .map(|(key, _)| key.clone())
+                {
+                    entries.remove(&lru_key);
+                }
+            },
+            |entry| entry.last_used = now,
+            || CacheEntry {
+                created_at: now,
+                last_used: now,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use macros::async_test_all;
+
+    use super::*;
+    use crate::ssp::graphql::GraphQLError;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn fetch(calls: &AtomicUsize, value: u64) -> GraphQLResult<u64> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(value)
+    }
+
+    #[async_test_all]
+    async fn test_coalesces_concurrent_requests() {
+        let cache = QueryCache::new(Duration::from_secs(60), 10);
+        let calls = AtomicUsize::new(0);
+
+        // The first request only completes after the second one has joined it
+        let (tx, rx) = futures::channel::oneshot::channel::<()>();
+        let (a, b, ()) = futures::join!(
+            cache.get_or_fetch("key", || async {
+                rx.await.unwrap();
+                fetch(&calls, 1).await
+            }),
+            cache.get_or_fetch("key", || fetch(&calls, 2)),
+            async { tx.send(()).unwrap() },
+        );
+        assert_eq!(a.unwrap(), 1);
+        assert_eq!(b.unwrap(), 1);
+        assert_eq!(
+            cache
+                .get_or_fetch("key", || fetch(&calls, 3))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[async_test_all]
+    async fn test_does_not_cache_errors() {
+        let cache = QueryCache::new(Duration::from_secs(60), 10);
+        let calls = AtomicUsize::new(0);
+
+        let result = cache
+            .get_or_fetch("key", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(GraphQLError::GraphQL("boom".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            cache
+                .get_or_fetch("key", || fetch(&calls, 2))
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[async_test_all]
+    async fn test_expires_and_evicts_entries() {
+        let calls = AtomicUsize::new(0);
+
+        let cache = QueryCache::new(Duration::ZERO, 10);
+        cache
+            .get_or_fetch("key", || fetch(&calls, 1))
+            .await
+            .unwrap();
+        assert_eq!(
+            cache
+                .get_or_fetch("key", || fetch(&calls, 2))
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let cache = QueryCache::new(Duration::from_secs(60), 2);
+        cache.get_or_fetch("a", || fetch(&calls, 1)).await.unwrap();
+        cache.get_or_fetch("b", || fetch(&calls, 2)).await.unwrap();
+        // "a" is used more recently than "b", so "b" is evicted for "c"
+        cache.get_or_fetch("a", || fetch(&calls, 0)).await.unwrap();
+        cache.get_or_fetch("c", || fetch(&calls, 3)).await.unwrap();
+        assert_eq!(
+            cache.get_or_fetch("a", || fetch(&calls, 0)).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            cache.get_or_fetch("b", || fetch(&calls, 4)).await.unwrap(),
+            4
+        );
+    }
+}
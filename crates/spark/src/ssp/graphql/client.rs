@@ -11,6 +11,7 @@ use platform_utils::{ContentType, HttpClient, add_content_type_header};
 use tokio::time::sleep;
 
 use crate::header_provider::HeaderProvider;
+use crate::ssp::graphql::cache::QueryCache;
 use crate::ssp::graphql::error::{GraphQLError, GraphQLResult};
 use crate::ssp::graphql::queries::{
     self, claim_static_deposit, complete_coop_exit, coop_exit_fee_quote, delete_wallet_webhook,
@@ -72,6 +73,11 @@ where
     ))
 }
 
+/// How long a fee estimate is served from the cache before it is requested again.
+const FEE_ESTIMATE_CACHE_TTL: Duration = Duration::from_secs(15);
+/// Maximum number of fee estimates kept in each cache.
+const FEE_ESTIMATE_CACHE_CAPACITY: usize = 100;
+
 /// GraphQL client for interacting with the Spark server
 pub struct GraphQLClient {
     client: Arc<dyn HttpClient>,
@@ -79,6 +85,10 @@ pub struct GraphQLClient {
     schema_endpoint: String,
     retry_config: RetryConfig,
     header_provider: Arc<dyn HeaderProvider>,
+    /// Lightning send fee estimates by invoice and amount
+    lightning_send_fee_cache: QueryCache<(String, Option<u64>), CurrencyAmount>,
+    /// Swap fee estimates by amount
+    swap_fee_cache: QueryCache<u64, CurrencyAmount>,
 }
 
 impl GraphQLClient {
@@ -101,9 +111,20 @@ impl GraphQLClient {
             schema_endpoint,
             retry_config: config.retry_config,
             header_provider,
+            lightning_send_fee_cache: fee_estimate_cache(),
+            swap_fee_cache: fee_estimate_cache(),
         }
     }
 
+    /// Makes sure a valid SSP session exists, authenticating if needed.
+    pub async fn ensure_session(&self) -> GraphQLResult<()> {
+        self.header_provider
+            .headers()
+            .await
+            .map_err(|e| GraphQLError::Authentication(e.to_string()))?;
+        Ok(())
+    }
+
     fn get_full_url(&self) -> String {
         format!("{}/{}", self.base_url, self.schema_endpoint)
     }
@@ -195,38 +216,52 @@ impl GraphQLClient {
     }
 
     /// Get a swap fee estimate
+    ///
+    /// Identical concurrent requests share one query and the estimate is
+    /// cached for a short time.
     pub async fn get_swap_fee_estimate(&self, amount_sats: u64) -> GraphQLResult<CurrencyAmount> {
-        let vars = leaves_swap_fee_estimate::Variables {
-            input: leaves_swap_fee_estimate::LeavesSwapFeeEstimateInput {
-                total_amount_sats: amount_sats as i64,
-            },
-        };
-
-        let response = self
-            .post_query::<queries::LeavesSwapFeeEstimate, _>(vars)
-            .await?;
-
-        Ok(response.leaves_swap_fee_estimate.fee_estimate.into())
+        self.swap_fee_cache
+            .get_or_fetch(amount_sats, || async {
+                let vars = leaves_swap_fee_estimate::Variables {
+                    input: leaves_swap_fee_estimate::LeavesSwapFeeEstimateInput {
+                        total_amount_sats: amount_sats as i64,
+                    },
+                };
+
+                let response = self
+                    .post_query::<queries::LeavesSwapFeeEstimate, _>(vars)
+                    .await?;
+
+                Ok(response.leaves_swap_fee_estimate.fee_estimate.into())
+            })
+            .await
     }
 
     /// Get a lightning send fee estimate
+    ///
+    /// Identical concurrent requests share one query and the estimate is
+    /// cached for a short time.
     pub async fn get_lightning_send_fee_estimate(
         &self,
         encoded_invoice: &str,
         amount_sats: Option<u64>,
     ) -> GraphQLResult<CurrencyAmount> {
-        let vars = lightning_send_fee_estimate::Variables {
-            input: lightning_send_fee_estimate::LightningSendFeeEstimateInput {
-                encoded_invoice: encoded_invoice.to_string(),
-                amount_sats,
-            },
-        };
-
-        let response = self
-            .post_query::<queries::LightningSendFeeEstimate, _>(vars)
-            .await?;
-
-        Ok(response.lightning_send_fee_estimate.fee_estimate.into())
+        self.lightning_send_fee_cache
+            .get_or_fetch((encoded_invoice.to_string(), amount_sats), || async {
+                let vars = lightning_send_fee_estimate::Variables {
+                    input: lightning_send_fee_estimate::LightningSendFeeEstimateInput {
+                        encoded_invoice: encoded_invoice.to_string(),
+                        amount_sats,
+                    },
+                };
+
+                let response = self
+                    .post_query::<queries::LightningSendFeeEstimate, _>(vars)
+                    .await?;
+
+                Ok(response.lightning_send_fee_estimate.fee_estimate.into())
+            })
+            .await
     }
 
     /// Get a coop exit fee quote
@@ -509,6 +544,14 @@ impl GraphQLClient {
     }
 }
 
+fn fee_estimate_cache<K, V>() -> QueryCache<K, V>
+where
+    K: Clone + Eq + std::hash::Hash,
+    V: Clone,
+{
+    QueryCache::new(FEE_ESTIMATE_CACHE_TTL, FEE_ESTIMATE_CACHE_CAPACITY)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::VecDeque;
@@ -525,6 +568,8 @@ mod tests {
     /// for "any successful GraphQL response" in the retry tests.
     const VALID_WEBHOOKS_RESPONSE: &str = r#"{"data":{"wallet_webhooks":{"webhooks":[]}}}"#;
 
+    const VALID_SWAP_FEE_ESTIMATE_RESPONSE: &str = r#"{"data":{"leaves_swap_fee_estimate":{"fee_estimate":{"__typename":"CurrencyAmount","original_value":12,"original_unit":"SATOSHI","preferred_currency_unit":"SATOSHI","preferred_currency_value_rounded":12}}}}"#;
+
     #[derive(Default)]
     struct MockHttpInner {
         responses: Mutex<VecDeque<(u16, String)>>,
@@ -645,6 +690,8 @@ mod tests {
             schema_endpoint: "graphql".to_string(),
             retry_config,
             header_provider: Arc::new(StaticHeaderProvider),
+            lightning_send_fee_cache: fee_estimate_cache(),
+            swap_fee_cache: fee_estimate_cache(),
         }
     }
 
@@ -743,6 +790,8 @@ mod tests {
             schema_endpoint: "graphql".to_string(),
             retry_config: fast_retry(2),
             header_provider: provider.clone(),
+            lightning_send_fee_cache: fee_estimate_cache(),
+            swap_fee_cache: fee_estimate_cache(),
         };
 
         let result = client.list_wallet_webhooks().await;
@@ -793,4 +842,25 @@ mod tests {
         );
         assert_eq!(handle.post_calls(), 1);
     }
+
+    #[async_test_all]
+    async fn fee_estimates_are_cached() {
+        let http = MockHttpClient::with_responses(vec![
+            (200, VALID_SWAP_FEE_ESTIMATE_RESPONSE),
+            (200, VALID_SWAP_FEE_ESTIMATE_RESPONSE),
+        ]);
+        let handle = http.clone();
+        let client = build_test_client(http, fast_retry(0)).await;
+
+        let (a, b) = futures::join!(
+            client.get_swap_fee_estimate(1_000),
+            client.get_swap_fee_estimate(1_000)
+        );
+        assert_eq!(a.unwrap().original_value, 12);
+        assert_eq!(b.unwrap().original_value, 12);
+        assert_eq!(handle.post_calls(), 1);
+
+        client.get_swap_fee_estimate(2_000).await.unwrap();
+        assert_eq!(handle.post_calls(), 2);
+    }
 }
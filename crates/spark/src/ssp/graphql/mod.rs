@@ -1,3 +1,4 @@
+mod cache;
 pub(crate) mod client;
 pub(crate) mod error;
 pub(crate) mod models;
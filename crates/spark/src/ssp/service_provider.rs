@@ -100,6 +100,12 @@ impl ServiceProvider {
         self.identity_public_key
     }
 
+    /// Makes sure a valid SSP session exists, authenticating if needed
+    #[instrument(level = "info", target = "spark::ssp", skip_all)]
+    pub async fn ensure_session(&self) -> ServiceProviderResult<()> {
+        Ok(self.gql_client.ensure_session().await?)
+    }
+
     /// Get a swap fee estimate
     #[instrument(level = "info", target = "spark::ssp", skip_all)]
     pub async fn get_swap_fee_estimate(
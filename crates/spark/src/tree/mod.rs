@@ -393,12 +393,19 @@ pub struct SelectLeavesOptions {
     ///
     /// Default: 60 seconds
     pub max_wait_for_pending: Duration,
+    /// Maximum number of leaves to return for target amounts. If the leaves
+    /// matching the target amounts exceed it, they are swapped for fewer,
+    /// larger leaves, returning any change to the wallet.
+    ///
+    /// Default: no limit
+    pub max_leaves: Option<usize>,
 }
 
 impl Default for SelectLeavesOptions {
     fn default() -> Self {
         Self {
             max_wait_for_pending: DEFAULT_MAX_WAIT_FOR_PENDING,
+            max_leaves: None,
         }
     }
 }
@@ -408,8 +415,15 @@ impl SelectLeavesOptions {
     pub fn no_wait() -> Self {
         Self {
             max_wait_for_pending: Duration::ZERO,
+            max_leaves: None,
         }
     }
+
+    /// Limits the number of leaves returned for target amounts.
+    pub fn with_max_leaves(mut self, max_leaves: usize) -> Self {
+        self.max_leaves = Some(max_leaves);
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
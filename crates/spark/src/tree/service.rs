@@ -166,7 +166,9 @@ impl TreeService for SynchronousTreeService {
             let reservation = self.renew_reservation_timelocks(reservation).await?;
 
             // Check if swap is needed
-            if self.reservation_matches_target(&reservation, target_amounts) {
+            if self.reservation_matches_target(&reservation, target_amounts)
+                && !exceeds_max_leaves(&reservation, target_amounts, options.max_leaves)
+            {
                 trace!("Selected leaves match requirements, no swap needed");
                 return Ok(reservation);
             }
@@ -304,14 +306,17 @@ impl TreeService for SynchronousTreeService {
         } else {
             HashMap::new()
         };
+        // A watch-only signer can't derive leaf keys, so it trusts the operators
+        let watch_only = self.spark_signer.is_watch_only();
         let unverified_leaves: Vec<&TreeNode> = available_leaves
             .iter()
             .copied()
             .filter(|leaf| {
-                !already_verified.get(&leaf.id).is_some_and(|keys| {
-                    keys.verifying_public_key == leaf.verifying_public_key
-                        && keys.signing_keyshare_public_key == leaf.signing_keyshare.public_key
-                })
+                !watch_only
+                    && !already_verified.get(&leaf.id).is_some_and(|keys| {
+                        keys.verifying_public_key == leaf.verifying_public_key
+                            && keys.signing_keyshare_public_key == leaf.signing_keyshare.public_key
+                    })
             })
             .collect();
 
@@ -351,8 +356,10 @@ impl TreeService for SynchronousTreeService {
             .filter(|leaf_id| !ignored_leaves_map.contains_key(&leaf_id.id))
             .cloned()
             .collect::<Vec<_>>();
-        let refreshed_leaves = self
-            .check_renew_nodes(new_leaves, async |e| {
+        let refreshed_leaves = if watch_only {
+            new_leaves
+        } else {
+            self.check_renew_nodes(new_leaves, async |e| {
                 // If this is a partial check timelock error, the extend node timelock failed
                 // but we can still update the leaves that were refreshed
                 if let ServiceError::PartialCheckTimelockError(ref nodes) = e
@@ -364,7 +371,8 @@ impl TreeService for SynchronousTreeService {
                     error!("Failed to set leaves: {e:?}");
                 }
             })
-            .await?;
+            .await?
+        };
 
         self.state
             .set_leaves(
@@ -829,6 +837,17 @@ fn query_nodes_request(
     }
 }
 
+/// Whether the leaves reserved for `target_amounts` should be swapped for fewer
+/// leaves. Reservations without target amounts take all leaves and can't be
+/// consolidated.
+fn exceeds_max_leaves(
+    reservation: &LeavesReservation,
+    target_amounts: Option<&TargetAmounts>,
+    max_leaves: Option<usize>,
+) -> bool {
+    target_amounts.is_some() && max_leaves.is_some_and(|max| reservation.leaves.len() > max)
+}
+
 #[cfg(test)]
 mod tests {
     use bitcoin::{Transaction, absolute::LockTime, transaction::Version};
@@ -913,6 +932,18 @@ mod tests {
         assert!(matches!(req.source, Some(Source::NodeIds(_))));
     }
 
+    #[test_all]
+    fn test_exceeds_max_leaves() {
+        let reservation = LeavesReservation::new(create_test_leaves(&[1, 2, 4]), "r".to_string());
+        let target = TargetAmounts::new_amount_and_fee(7, None);
+
+        assert!(!exceeds_max_leaves(&reservation, Some(&target), None));
+        assert!(!exceeds_max_leaves(&reservation, Some(&target), Some(3)));
+        assert!(exceeds_max_leaves(&reservation, Some(&target), Some(2)));
+        // Without target amounts all leaves are taken
+        assert!(!exceeds_max_leaves(&reservation, None, Some(2)));
+    }
+
     #[test_all]
     fn test_find_exact_single_match() {
         let leaves = create_test_leaves(&[8192, 4096, 2048, 1024]);
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+use platform_utils::tokio::sync::OnceCell;
+
+/// The cell shared by the requests for a key, with the caller's metadata.
+pub(crate) struct Coalesced<V, M> {
+    cell: Arc<OnceCell<V>>,
+    pub(crate) meta: M,
+}
+
+impl<V, M> Coalesced<V, M> {
+    /// Whether the shared request completed successfully.
+    pub(crate) fn is_done(&self) -> bool {
+        self.cell.initialized()
+    }
+}
+
+/// Lets concurrent requests for the same key share a single request.
+///
+/// Each key maps to a `OnceCell` the requests initialize together: the first
+/// one runs the request, the others wait for its response. An entry stays
+/// until [`Coalescer::release`] removes it, so callers decide whether
+/// completed responses are served again.
+pub(crate) struct Coalescer<K, V, M = ()> {
+    entries: Mutex<HashMap<K, Coalesced<V, M>>>,
+}
+
+impl<K, V, M> Coalescer<K, V, M>
+where
+    K: Clone + Eq + Hash,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cell shared by the requests for `key`, created with `meta`
+    /// if there is none. Under the lock, `prepare` first runs on the entries,
+    /// e.g. to expire or evict some, and `joined` runs on the metadata of an
+    /// existing cell.
+    pub(crate) fn cell(
+        &self,
+        key: &K,
+        prepare: impl FnOnce(&mut HashMap<K, Coalesced<V, M>>),
+        joined: impl FnOnce(&mut M),
+        meta: impl FnOnce() -> M,
+    ) -> Arc<OnceCell<V>> {
+        let mut entries = self.entries.lock().unwrap();
+        prepare(&mut entries);
+        if let Some(entry) = entries.get_mut(key) {
+            joined(&mut entry.meta);
+            return Arc::clone(&entry.cell);
+        }
+        let cell = Arc::new(OnceCell::new());
+        entries.insert(
+            key.clone(),
+            Coalesced {
+                cell: Arc::clone(&cell),
+                meta: meta(),
+            },
+        );
+        cell
+    }
+
+    /// Removes the entry of `key` if it still holds `cell`, so the next
+    /// request for the key runs again. An entry replaced in the meantime is
+    /// kept.
+    pub(crate) fn release(&self, key: &K, cell: &Arc<OnceCell<V>>) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries
+            .get(key)
+            .is_some_and(|entry| Arc::ptr_eq(&entry.cell, cell))
+        {
+            entries.remove(key);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.lock().unwrap().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macros::test_all;
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test_all]
+    fn test_release_keeps_replaced_entries() {
+        let coalescer: Coalescer<&str, u64> = Coalescer::new();
+        let first = coalescer.cell(&"key", |_| {}, |_| {}, || ());
+        let joined = coalescer.cell(&"key", |_| {}, |_| {}, || ());
+        assert!(Arc::ptr_eq(&first, &joined));
+
+        coalescer.release(&"key", &first);
+        assert!(coalescer.is_empty());
+
+        let second = coalescer.cell(&"key", |_| {}, |_| {}, || ());
+        assert!(!Arc::ptr_eq(&first, &second));
+        coalescer.release(&"key", &first);
+        assert!(!coalescer.is_empty());
+        coalescer.release(&"key", &second);
+        assert!(coalescer.is_empty());
+    }
+}
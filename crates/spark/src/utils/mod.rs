@@ -1,4 +1,5 @@
 pub mod byte_padding;
+pub(crate) mod coalesce;
 pub mod frost;
 pub mod htlc_transactions;
 pub(crate) mod leaf_key_tweak;
@@ -82,7 +82,7 @@ impl<T> PagingResult<T> {
 
     pub fn map<U, F>(self, f: F) -> PagingResult<U>
     where
-        F: Fn(T) -> U,
+        F: FnMut(T) -> U,
     {
         PagingResult {
             items: self.items.into_iter().map(f).collect(),
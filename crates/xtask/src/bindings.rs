@@ -0,0 +1,180 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+const CORE_SRC: &str = "crates/breez-sdk/core/src";
+const WASM_SRC: &str = "crates/breez-sdk/wasm/src";
+const FLUTTER_SRC: &str = "packages/flutter/rust/src";
+/// Generated by uniffi-bindgen-react-native, not committed.
+const REACT_NATIVE_GENERATED: &str = "packages/react-native/src/generated/breez_sdk_spark.ts";
+
+/// Request/response models that are deliberately not exposed by a binding.
+const WASM_EXCLUSIONS: &[&str] = &[
+    // Signers and passkeys are connected through the wasm-specific API
+    "ConnectWithSignerRequest",
+    "ConnectWithSigningOnlySignerRequest",
+    "DeriveSeedsRequest",
+    "SetupWalletRequest",
+];
+const FLUTTER_EXCLUSIONS: &[&str] = &[
+    // External signers, custom storage and payment approvers aren't
+    // supported by the Flutter plugin
+    "ConnectWithSignerRequest",
+    "ConnectWithSigningOnlySignerRequest",
+    "ExternalPrepareClaimRequest",
+    "ExternalPrepareLightningReceiveRequest",
+    "ExternalPrepareStaticDepositClaimRequest",
+    "ExternalPrepareStaticDepositRequest",
+    "ExternalSignSparkInvoiceRequest",
+    "ExternalSignStaticDepositRefundRequest",
+    "ExternalStartStaticDepositRefundRequest",
+    "PaymentApprovalRequest",
+    "RestResponse",
+    "SetupWalletRequest",
+    "StorageListPaymentsRequest",
+];
+
+/// Verifies that the request/response models exported through uniffi by the
+/// core crate are covered by the wasm, Flutter and React Native bindings.
+pub fn bindings_check_cmd() -> Result<()> {
+    let models: BTreeSet<String> = uniffi_types(Path::new(CORE_SRC))?
+        .into_iter()
+        .filter(|name| name.ends_with("Request") || name.ends_with("Response"))
+        .collect();
+    println!("Found {} request/response models in core", models.len());
+
+    let mut missing = Vec::new();
+
+    let wasm = attribute_types(Path::new(WASM_SRC), "extern_wasm_bindgen(")?;
+    missing.extend(missing_from("wasm", &models, &wasm, WASM_EXCLUSIONS));
+
+    let flutter = attribute_types(Path::new(FLUTTER_SRC), "frb(mirror(")?;
+    missing.extend(missing_from(
+        "flutter",
+        &models,
+        &flutter,
+        FLUTTER_EXCLUSIONS,
+    ));
+
+    // The React Native bindings are generated from the uniffi exports, so they
+    // can only be checked once generated.
+    match fs::read_to_string(REACT_NATIVE_GENERATED) {
+        Ok(generated) => {
+            let react_native = models
+                .iter()
+                .filter(|name| {
+                    ["type", "const", "enum"]
+                        .iter()
+                        .any(|kind| generated.contains(&format!("export {kind} {name} ")))
+                })
+                .cloned()
+                .collect();
+            missing.extend(missing_from("react-native", &models, &react_native, &[]));
+        }
+        Err(_) => println!(
+            "Skipping react-native: {REACT_NATIVE_GENERATED} not found, run the ubrn codegen first"
+        ),
+    }
+
+    for (binding, name) in &missing {
+        println!("{name} is missing from the {binding} bindings");
+    }
+    if !missing.is_empty() {
+        bail!("{} models are missing from the bindings", missing.len());
+    }
+    println!("All bindings cover the core request/response models");
+    Ok(())
+}
+
+fn missing_from<'a>(
+    binding: &'static str,
+    models: &'a BTreeSet<String>,
+    covered: &BTreeSet<String>,
+    exclusions: &[&str],
+) -> impl Iterator<Item = (&'static str, &'a String)> {
+    models
+        .iter()
+        .filter(|name| !covered.contains(*name) && !exclusions.contains(&name.as_str()))
+        .map(move |name| (binding, name))
+}
+
+/// Names of the structs and enums deriving a uniffi type under `dir`.
+fn uniffi_types(dir: &Path) -> Result<BTreeSet<String>> {
+    let mut types = BTreeSet::new();
+    for file in rust_files(dir)? {
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        let mut derives_uniffi = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.contains("derive(")
+                && ["uniffi::Record", "uniffi::Enum", "uniffi::Error"]
+                    .iter()
+                    .any(|derive| line.contains(derive))
+            {
+                derives_uniffi = true;
+                continue;
+            }
+            if !derives_uniffi {
+                continue;
+            }
+            if let Some(name) = declared_type(line) {
+                types.insert(name.to_string());
+                derives_uniffi = false;
+            }
+        }
+    }
+    Ok(types)
+}
+
+/// The name of the struct or enum declared on `line`, if any.
+fn declared_type(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("pub")?;
+    let rest = match rest.strip_prefix('(') {
+        Some(rest) => rest.split_once(')')?.1,
+        None => rest,
+    };
+    let rest = rest.trim_start();
+    let rest = rest
+        .strip_prefix("struct ")
+        .or_else(|| rest.strip_prefix("enum "))?;
+    rest.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+/// Type names given as (comma separated) paths to the `attribute` under `dir`.
+fn attribute_types(dir: &Path, attribute: &str) -> Result<BTreeSet<String>> {
+    let mut types = BTreeSet::new();
+    for file in rust_files(dir)? {
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        for (start, _) in content.match_indices(attribute) {
+            let rest = &content[start + attribute.len()..];
+            let Some((args, _)) = rest.split_once(')') else {
+                continue;
+            };
+            for path in args.split(',') {
+                if let Some(name) = path.trim().rsplit("::").next().filter(|n| !n.is_empty()) {
+                    types.insert(name.to_string());
+                }
+            }
+        }
+    }
+    Ok(types)
+}
+
+fn rust_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(rust_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
@@ -0,0 +1,338 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use serde_json::json;
+
+use crate::prepare_itest_images;
+
+/// A group of integration tests that can be run on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItestProfile {
+    /// spark-itest's local-cluster tests
+    SparkOnly,
+    /// breez-sdk-itest's local-cluster tests (unilateral exit)
+    BreezCore,
+    /// breez-sdk-itest's LNURL tests, faucet-based
+    Lnurl,
+    /// wasm tests in a headless browser
+    WasmBrowser,
+}
+
+/// The profiles run when none is given, matching the former `cargo xtask itest`.
+const DEFAULT_PROFILES: &[ItestProfile] = &[ItestProfile::SparkOnly, ItestProfile::BreezCore];
+
+impl FromStr for ItestProfile {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spark-only" => Ok(ItestProfile::SparkOnly),
+            "breez-core" => Ok(ItestProfile::BreezCore),
+            "lnurl" => Ok(ItestProfile::Lnurl),
+            "wasm-browser" => Ok(ItestProfile::WasmBrowser),
+            _ => bail!(
+                "invalid itest profile: {} - expected one of: spark-only, breez-core, lnurl, wasm-browser",
+                s
+            ),
+        }
+    }
+}
+
+impl ItestProfile {
+    fn name(self) -> &'static str {
+        match self {
+            ItestProfile::SparkOnly => "spark-only",
+            ItestProfile::BreezCore => "breez-core",
+            ItestProfile::Lnurl => "lnurl",
+            ItestProfile::WasmBrowser => "wasm-browser",
+        }
+    }
+
+    /// Whether the profile runs on the locally built bitcoind + operator images.
+    fn needs_images(self) -> bool {
+        matches!(self, ItestProfile::SparkOnly | ItestProfile::BreezCore)
+    }
+
+    fn command(self, filter: &TestFilter) -> Command {
+        let mut c = Command::new("cargo");
+        let test_threads = match self {
+            ItestProfile::SparkOnly => {
+                c.args(["test", "-p", "spark-itest", "--no-fail-fast"]);
+                None
+            }
+            ItestProfile::BreezCore => {
+                // The unilateral-exit suite is the only local-cluster test in
+                // breez-itest. Each test starts its own bitcoind + operator
+                // cluster, hence the limited parallelism.
+                c.args([
+                    "test",
+                    "-p",
+                    "breez-sdk-itest",
+                    "--features",
+                    "local-itest",
+                    "--test",
+                    "unilateral_exit",
+                    "--no-fail-fast",
+                ]);
+                Some(2)
+            }
+            ItestProfile::Lnurl => {
+                // Two threads to bound faucet pressure, as in `make cli-itest`.
+                c.args([
+                    "test",
+                    "-p",
+                    "breez-sdk-itest",
+                    "--test",
+                    "lnurl",
+                    "--test",
+                    "lnurl_auth",
+                    "--no-fail-fast",
+                ]);
+                Some(2)
+            }
+            ItestProfile::WasmBrowser => {
+                c.args(["xtask", "wasm-test", "--browser", "firefox"]);
+                None
+            }
+        };
+
+        c.arg("--");
+        if let Some(name) = &filter.name {
+            c.arg(name);
+        }
+        for skip in &filter.skip {
+            c.args(["--skip", skip]);
+        }
+        if let Some(threads) = test_threads {
+            c.arg(format!("--test-threads={threads}"));
+        }
+        c
+    }
+}
+
+/// Selects the tests to run within each profile, by test name.
+#[derive(Debug, Default)]
+pub struct TestFilter {
+    /// Only run tests whose name contains this
+    pub name: Option<String>,
+    /// Skip tests whose name contains any of these
+    pub skip: Vec<String>,
+}
+
+/// The outcome of running one profile.
+#[derive(Debug)]
+struct ProfileResult {
+    profile: ItestProfile,
+    success: bool,
+    duration: Duration,
+    passed: u64,
+    failed: u64,
+    ignored: u64,
+    log_path: PathBuf,
+}
+
+pub fn itest_cmd(
+    profiles: Vec<ItestProfile>,
+    jobs: Option<usize>,
+    filter: TestFilter,
+) -> Result<()> {
+    let mut profiles = if profiles.is_empty() {
+        DEFAULT_PROFILES.to_vec()
+    } else {
+        profiles
+    };
+    let mut seen = Vec::new();
+    profiles.retain(|p| {
+        let new = !seen.contains(p);
+        seen.push(*p);
+        new
+    });
+
+    if profiles.iter().any(|p| p.needs_images()) {
+        prepare_itest_images()?;
+    }
+
+    let out_dir = std::env::current_dir()?.join("target/itest");
+    fs::create_dir_all(&out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+
+    let jobs = jobs.unwrap_or(profiles.len()).clamp(1, profiles.len());
+    // Output is only echoed when a single profile runs at a time, so
+    // concurrent runs don't interleave.
+    let echo = jobs == 1;
+    let queue = Arc::new(Mutex::new(profiles.into_iter().rev().collect::<Vec<_>>()));
+    let filter = Arc::new(filter);
+    let workers = (0..jobs)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let filter = Arc::clone(&filter);
+            let out_dir = out_dir.clone();
+            thread::spawn(move || -> Result<Vec<ProfileResult>> {
+                let mut results = Vec::new();
+                loop {
+                    let Some(profile) = queue.lock().unwrap().pop() else {
+                        return Ok(results);
+                    };
+                    results.push(run_profile(profile, &filter, &out_dir, echo)?);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for worker in workers {
+        let worker_results = worker
+            .join()
+            .map_err(|_| anyhow::anyhow!("itest worker panicked"))??;
+        results.extend(worker_results);
+    }
+    results.sort_by_key(|r| r.profile.name());
+
+    print_summary(&results);
+    let summary_path = out_dir.join("summary.json");
+    write_summary(&results, &summary_path)?;
+    println!("Summary written to {}", summary_path.display());
+
+    let failed: Vec<_> = results
+        .iter()
+        .filter(|r| !r.success)
+        .map(|r| r.profile.name())
+        .collect();
+    if !failed.is_empty() {
+        bail!("itest profiles failed: {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+fn run_profile(
+    profile: ItestProfile,
+    filter: &TestFilter,
+    out_dir: &Path,
+    echo: bool,
+) -> Result<ProfileResult> {
+    let log_path = out_dir.join(format!("{}.log", profile.name()));
+    let log =
+        Arc::new(Mutex::new(File::create(&log_path).with_context(|| {
+            format!("failed to create {}", log_path.display())
+        })?));
+    println!(
+        "Running itest profile {} (log: {})",
+        profile.name(),
+        log_path.display()
+    );
+
+    let start = Instant::now();
+    let mut child = profile
+        .command(filter)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start itest profile {}", profile.name()))?;
+
+    // Both streams go to the log; stdout also carries the test results.
+    let stderr = child.stderr.take().context("missing stderr")?;
+    let stderr_log = Arc::clone(&log);
+    let stderr_reader = thread::spawn(move || copy_lines(stderr, &stderr_log, echo));
+    let stdout = child.stdout.take().context("missing stdout")?;
+    let stdout_lines = copy_lines(stdout, &log, echo)?;
+    stderr_reader
+        .join()
+        .map_err(|_| anyhow::anyhow!("stderr reader panicked"))??;
+    let status = child.wait()?;
+
+    let (passed, failed, ignored) = count_test_results(&stdout_lines);
+    Ok(ProfileResult {
+        profile,
+        success: status.success(),
+        duration: start.elapsed(),
+        passed,
+        failed,
+        ignored,
+        log_path,
+    })
+}
+
+/// Copies `stream` to `log` line by line and returns the lines.
+fn copy_lines(stream: impl Read, log: &Mutex<File>, echo: bool) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if echo {
+            println!("{line}");
+        }
+        writeln!(log.lock().unwrap(), "{line}")?;
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+/// Sums the `test result:` lines libtest prints per test binary.
+fn count_test_results(lines: &[String]) -> (u64, u64, u64) {
+    let (mut passed, mut failed, mut ignored) = (0, 0, 0);
+    for line in lines {
+        let Some(result) = line.trim().strip_prefix("test result:") else {
+            continue;
+        };
+        for part in result.split(';') {
+            let mut words = part.split_whitespace().rev();
+            let (Some(label), Some(count)) = (words.next(), words.next()) else {
+                continue;
+            };
+            let Ok(count) = count.parse::<u64>() else {
+                continue;
+            };
+            match label {
+                "passed" => passed += count,
+                "failed" => failed += count,
+                "ignored" => ignored += count,
+                _ => {}
+            }
+        }
+    }
+    (passed, failed, ignored)
+}
+
+fn print_summary(results: &[ProfileResult]) {
+    println!();
+    println!(
+        "{:<14} {:<6} {:>8} {:>7} {:>7} {:>8}",
+        "profile", "status", "duration", "passed", "failed", "ignored"
+    );
+    for r in results {
+        println!(
+            "{:<14} {:<6} {:>7}s {:>7} {:>7} {:>8}",
+            r.profile.name(),
+            if r.success { "ok" } else { "FAILED" },
+            r.duration.as_secs(),
+            r.passed,
+            r.failed,
+            r.ignored
+        );
+    }
+    println!();
+}
+
+fn write_summary(results: &[ProfileResult], path: &Path) -> Result<()> {
+    let summary = json!({
+        "profiles": results
+            .iter()
+            .map(|r| json!({
+                "profile": r.profile.name(),
+                "success": r.success,
+                "duration_secs": r.duration.as_secs(),
+                "passed": r.passed,
+                "failed": r.failed,
+                "ignored": r.ignored,
+                "log": r.log_path.display().to_string(),
+            }))
+            .collect::<Vec<_>>(),
+    });
+    fs::write(path, serde_json::to_string_pretty(&summary)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
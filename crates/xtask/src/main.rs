@@ -1,4 +1,6 @@
+mod bindings;
 mod docs;
+mod itest;
 mod package;
 
 use std::fs;
@@ -10,8 +12,10 @@ use cargo_metadata::{Metadata, MetadataCommand, Package};
 use clap::{Parser, Subcommand};
 use xshell::{Shell, cmd};
 
+use crate::bindings::bindings_check_cmd;
 use crate::docs::{DocSnippetsPackage, check_doc_snippets_cmd};
-use crate::package::{TargetPackage, package_cmd};
+use crate::itest::{ItestProfile, TestFilter, itest_cmd};
+use crate::package::{TargetPackage, build_opfs_worker, package_cmd};
 
 const OUT_OF_WORKSPACE_PACKAGES: &[&str] = &["crates/breez-sdk/lnurl/Cargo.toml"];
 #[derive(Parser, Debug)]
@@ -114,7 +118,27 @@ enum Commands {
     },
 
     /// Run integration tests (containers etc.)
-    Itest {},
+    ///
+    /// Each profile's output goes to target/itest/<profile>.log, and a
+    /// summary of all profiles to target/itest/summary.json.
+    Itest {
+        /// Profile to run (spark-only|breez-core|lnurl|wasm-browser); can be
+        /// repeated. Defaults to spark-only and breez-core
+        #[arg(long = "profile")]
+        profiles: Vec<ItestProfile>,
+
+        /// Number of profiles to run in parallel; defaults to all of them
+        #[arg(short = 'j', long)]
+        jobs: Option<usize>,
+
+        /// Only run tests whose name contains this
+        #[arg(short = 't', long = "test")]
+        test: Option<String>,
+
+        /// Skip tests whose name contains this; can be repeated
+        #[arg(long)]
+        skip: Vec<String>,
+    },
 
     /// Run cross-version signer compatibility tests: flows started by the
     /// previous SDK release (git tag pinned in spark-compat-itest) are
@@ -124,6 +148,10 @@ enum Commands {
     /// Check Flutter package (generate bindings and build)
     FlutterCheck {},
 
+    /// Check that the wasm, Flutter and React Native bindings cover the
+    /// request/response models exported by the core crate
+    BindingsCheck {},
+
     /// Sync the canonical native passkey cores into the Flutter and
     /// React Native plugin trees.
     ///
@@ -169,9 +197,15 @@ fn main() -> Result<()> {
             package,
             skip_build,
         } => check_doc_snippets_cmd(package, skip_build),
-        Commands::Itest {} => itest_cmd(),
+        Commands::Itest {
+            profiles,
+            jobs,
+            test,
+            skip,
+        } => itest_cmd(profiles, jobs, TestFilter { name: test, skip }),
         Commands::CompatItest {} => compat_itest_cmd(),
         Commands::FlutterCheck {} => flutter_check_cmd(),
+        Commands::BindingsCheck {} => bindings_check_cmd(),
         Commands::SyncPasskeyCore { check } => sync_passkey_core_cmd(check),
     }
 }
@@ -350,6 +384,23 @@ fn test_cmd(
         if !status.success() {
             bail!("spark arbitrary_precision tests failed");
         }
+
+        // Run spark-wallet tests again with tree-inspection enabled, as no
+        // workspace crate enables it.
+        let status = Command::new("cargo")
+            .args([
+                "test",
+                "-p",
+                "spark-wallet",
+                "--features",
+                "tree-inspection",
+                "--no-fail-fast",
+            ])
+            .status()
+            .with_context(|| "failed to run spark-wallet tree-inspection tests")?;
+        if !status.success() {
+            bail!("spark-wallet tree-inspection tests failed");
+        }
     }
 
     Ok(())
@@ -529,6 +580,11 @@ fn wasm_test_cmd(
             }
         }
 
+        // The OPFS storage tests load the worker bundle
+        if pkg.name == "breez-sdk-spark-wasm" && !node {
+            build_opfs_worker(Path::new("crates/breez-sdk/wasm/js/web-storage"))?;
+        }
+
         let package_dir = pkg
             .manifest_path
             .parent()
@@ -889,25 +945,6 @@ fn wasm_clippy_cmd(fix: bool, rest: Vec<String>) -> Result<()> {
     Ok(())
 }
 
-fn itest_cmd() -> Result<()> {
-    let sh = prepare_itest_images()?;
-
-    // spark-itest's own local-cluster tests.
-    cmd!(sh, "cargo test -p spark-itest --no-fail-fast").run()?;
-
-    // The unilateral-exit suite is the only local-cluster test in breez-itest, so
-    // scope to that binary: the rest of breez-itest is faucet-based and runs (with
-    // its secrets) in the 8-thread `make breez-itest`; re-running it here would be
-    // redundant and trips tests that need secrets absent from this job. Limited
-    // parallelism because each test starts its own bitcoind + operator cluster.
-    cmd!(
-        sh,
-        "cargo test -p breez-sdk-itest --features local-itest --test unilateral_exit --no-fail-fast -- --test-threads=2"
-    )
-    .run()?;
-    Ok(())
-}
-
 fn compat_itest_cmd() -> Result<()> {
     let sh = prepare_itest_images()?;
 
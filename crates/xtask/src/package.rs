@@ -6,6 +6,7 @@ use std::{
 };
 
 use anyhow::{Context as _, Result, bail};
+use cargo_metadata::MetadataCommand;
 use xshell::{Shell, cmd};
 
 use crate::detect_brew_llvm_paths;
@@ -13,6 +14,7 @@ use crate::detect_brew_llvm_paths;
 #[derive(Debug, Clone)]
 pub enum TargetPackage {
     Wasm(WasmPackages),
+    Lnurl(LinuxPlatforms),
 }
 
 impl FromStr for TargetPackage {
@@ -37,6 +39,13 @@ impl FromStr for TargetPackage {
                 };
                 Ok(TargetPackage::Wasm(wasm_package))
             }
+            "lnurl" => {
+                let platforms = match split.get(1) {
+                    Some(platform) => LinuxPlatforms::from_str(platform)?,
+                    None => LinuxPlatforms::All,
+                };
+                Ok(TargetPackage::Lnurl(platforms))
+            }
             _ => bail!("invalid target package: {}", s),
         }
     }
@@ -48,6 +57,9 @@ pub enum WasmPackages {
     Node,
     Deno,
     Web,
+    /// The web target built without the optional subsystems (token issuer,
+    /// passkey, Turnkey), for wallets that want a smaller bundle.
+    WebMinimal,
     Bundle,
 }
 
@@ -59,17 +71,75 @@ impl FromStr for WasmPackages {
             "node" => Ok(WasmPackages::Node),
             "deno" => Ok(WasmPackages::Deno),
             "web" => Ok(WasmPackages::Web),
+            "web-minimal" => Ok(WasmPackages::WebMinimal),
             "bundle" => Ok(WasmPackages::Bundle),
             _ => bail!("invalid wasm package: {}", s),
         }
     }
 }
 
+/// The Linux platforms server binaries and images are built for.
+#[derive(Debug, Clone)]
+pub enum LinuxPlatforms {
+    All,
+    Amd64,
+    Arm64,
+}
+
+impl FromStr for LinuxPlatforms {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(LinuxPlatforms::All),
+            "amd64" => Ok(LinuxPlatforms::Amd64),
+            "arm64" => Ok(LinuxPlatforms::Arm64),
+            _ => bail!(
+                "invalid platform: {} - expected one of: all, amd64, arm64",
+                s
+            ),
+        }
+    }
+}
+
+impl LinuxPlatforms {
+    /// Docker architecture names of the selected platforms.
+    fn arches(&self) -> &'static [&'static str] {
+        match self {
+            LinuxPlatforms::All => &["amd64", "arm64"],
+            LinuxPlatforms::Amd64 => &["amd64"],
+            LinuxPlatforms::Arm64 => &["arm64"],
+        }
+    }
+}
+
+/// A self-hostable server, packaged as a binary tarball and a Docker image
+/// per platform.
+struct ServerPackage {
+    /// Binary name, also used for the output directory
+    name: &'static str,
+    /// Crate directory, relative to the workspace root
+    crate_dir: &'static str,
+    /// Cross-compiling Dockerfile with `binary` and `final` stages, relative
+    /// to the crate directory
+    dockerfile: &'static str,
+    image: &'static str,
+}
+
+const LNURL_SERVER: ServerPackage = ServerPackage {
+    name: "lnurl",
+    crate_dir: "crates/breez-sdk/lnurl",
+    dockerfile: "Dockerfile.release",
+    image: "breez-lnurl",
+};
+
 pub fn package_cmd(package: Option<TargetPackage>) -> Result<()> {
     match package {
         Some(TargetPackage::Wasm(wasm_package)) => {
             package_wasm_cmd(wasm_package)?;
         }
+        Some(TargetPackage::Lnurl(platforms)) => {
+            package_server_cmd(&LNURL_SERVER, &platforms)?;
+        }
         None => {
             println!("No package specified, packaging all packages");
             package_wasm_cmd(WasmPackages::All)?;
@@ -78,6 +148,61 @@ pub fn package_cmd(package: Option<TargetPackage>) -> Result<()> {
     Ok(())
 }
 
+/// Cross-compiles `server` for each platform with `docker buildx`, writing
+/// `target/package/<name>/<name>-<version>-linux-<arch>.tar.gz` and loading
+/// the `<image>:<version>-<arch>` image.
+fn package_server_cmd(server: &ServerPackage, platforms: &LinuxPlatforms) -> Result<()> {
+    let sh = Shell::new()?;
+    let workspace_root = std::env::current_dir()?;
+    let crate_dir = workspace_root.join(server.crate_dir);
+    let dockerfile = crate_dir.join(server.dockerfile);
+    let out_dir = workspace_root.join("target/package").join(server.name);
+    fs::create_dir_all(&out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+
+    let metadata = MetadataCommand::new()
+        .manifest_path(crate_dir.join("Cargo.toml"))
+        .no_deps()
+        .exec()
+        .with_context(|| format!("failed to read the {} manifest", server.name))?;
+    let version = metadata
+        .packages
+        .iter()
+        .find(|p| p.name == server.name)
+        .map(|p| p.version.to_string())
+        .with_context(|| format!("package {} not found", server.name))?;
+
+    let name = server.name;
+    let image = server.image;
+    for arch in platforms.arches() {
+        println!("Packaging {name} {version} for linux/{arch}");
+        let platform = format!("linux/{arch}");
+        let binary_dir = out_dir.join(format!("linux-{arch}"));
+        let output = format!("type=local,dest={}", binary_dir.display());
+        cmd!(
+            sh,
+            "docker buildx build --platform {platform} -f {dockerfile} --target binary --output {output} {workspace_root}"
+        )
+        .run()
+        .with_context(|| format!("failed to build {name} for {platform}"))?;
+
+        let tarball = out_dir.join(format!("{name}-{version}-linux-{arch}.tar.gz"));
+        cmd!(sh, "tar -czf {tarball} -C {binary_dir} {name}").run()?;
+        println!("Wrote {}", tarball.display());
+
+        // Reuses the build cache of the binary stage
+        let tag = format!("{image}:{version}-{arch}");
+        cmd!(
+            sh,
+            "docker buildx build --platform {platform} -f {dockerfile} --target final --load -t {tag} {workspace_root}"
+        )
+        .run()
+        .with_context(|| format!("failed to build the {name} image for {platform}"))?;
+        println!("Built image {tag}");
+    }
+    Ok(())
+}
+
 fn package_wasm_cmd(wasm_package: WasmPackages) -> Result<()> {
     let sh = Shell::new()?;
 
@@ -106,31 +231,36 @@ fn package_wasm_cmd(wasm_package: WasmPackages) -> Result<()> {
     match wasm_package {
         WasmPackages::All => {
             println!("Packaging all WASM targets");
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "bundler", &clang_env)?;
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "deno", &clang_env)?;
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "nodejs", &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "bundler", false, &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "deno", false, &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "nodejs", false, &clang_env)?;
             create_nodejs_esm_wrapper(&pkg_dir)?;
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "web", &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "web", false, &clang_env)?;
             create_ssr_entry_point(&pkg_dir)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "web", true, &clang_env)?;
         }
         WasmPackages::Bundle => {
             println!("Packaging Bundle WASM target");
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "bundler", &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "bundler", false, &clang_env)?;
         }
         WasmPackages::Deno => {
             println!("Packaging Deno WASM target");
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "deno", &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "deno", false, &clang_env)?;
         }
         WasmPackages::Node => {
             println!("Packaging Node.js WASM target");
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "nodejs", &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "nodejs", false, &clang_env)?;
             create_nodejs_esm_wrapper(&pkg_dir)?;
         }
         WasmPackages::Web => {
             println!("Packaging Web WASM target");
-            package_wasm_target(&wasm_crate_dir, &pkg_dir, "web", &clang_env)?;
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "web", false, &clang_env)?;
             create_ssr_entry_point(&pkg_dir)?;
         }
+        WasmPackages::WebMinimal => {
+            println!("Packaging minimal Web WASM target");
+            package_wasm_target(&wasm_crate_dir, &pkg_dir, "web", true, &clang_env)?;
+        }
     }
 
     // Remove tarballs from prior `yarn pack` runs before packing. `yarn
@@ -160,13 +290,20 @@ fn package_wasm_cmd(wasm_package: WasmPackages) -> Result<()> {
     Ok(())
 }
 
+/// Builds `target` into `pkg_dir/<target>`. A `minimal` build disables the
+/// wasm crate's default features and lands in `pkg_dir/<target>-minimal`.
 fn package_wasm_target(
     crate_dir: &PathBuf,
     pkg_dir: &Path,
     target: &str,
+    minimal: bool,
     clang_env: &[(String, String)],
 ) -> Result<()> {
-    let out_path = pkg_dir.join(target);
+    let out_path = if minimal {
+        pkg_dir.join(format!("{target}-minimal"))
+    } else {
+        pkg_dir.join(target)
+    };
 
     // Remove existing output directory if it exists
     if out_path.exists() {
@@ -176,7 +313,7 @@ fn package_wasm_target(
     let mut c = Command::new("wasm-pack");
     c.current_dir(crate_dir);
 
-    let args = vec![
+    let mut args = vec![
         "build",
         "--target",
         target,
@@ -184,6 +321,10 @@ fn package_wasm_target(
         "--out-dir",
         out_path.to_str().unwrap(),
     ];
+    if minimal {
+        // Arguments after `--` are passed on to cargo
+        args.extend(["--", "--no-default-features"]);
+    }
 
     c.args(args);
 
@@ -222,7 +363,7 @@ fn package_wasm_target(
     // so the helper only needs to land in the `web` target output to be
     // reachable via the `@breeztech/breez-sdk-spark/passkey-prf-provider`
     // sub-export.
-    if target == "web" {
+    if target == "web" && !minimal {
         copy_passkey_prf_provider_files(crate_dir, &out_path)?;
     }
 
@@ -1236,8 +1377,16 @@ fn copy_web_storage_files(crate_dir: &Path, out_path: &Path) -> Result<()> {
     // Create storage directory in output
     std::fs::create_dir_all(&storage_dest)?;
 
-    // Copy the ES6 storage implementation files
-    let files_to_copy = ["index.js"];
+    build_opfs_worker(&js_storage_src)?;
+
+    // Copy the ES6 storage implementation files, and the OPFS worker bundle
+    // with the SQLite-wasm binary it loads
+    let files_to_copy = [
+        "index.js",
+        "opfs.js",
+        "opfs-worker.bundle.js",
+        "sqlite3.wasm",
+    ];
 
     for file_name in files_to_copy {
         let src_file = js_storage_src.join(file_name);
@@ -1264,12 +1413,13 @@ fn copy_web_storage_files(crate_dir: &Path, out_path: &Path) -> Result<()> {
     let storage_package_json = serde_json::json!({
         "name": "@breez-sdk/web-storage",
         "version": "1.0.0",
-        "description": "Web IndexedDB storage implementation for Breez SDK WASM (ES6 modules)",
+        "description": "Web IndexedDB and OPFS storage implementations for Breez SDK WASM (ES6 modules)",
         "type": "module",
         "main": "index.js",
         "exports": {
             ".": "./index.js",
             "./storage": "./index.js",
+            "./opfs": "./opfs.js",
         },
         "dependencies": {}
     });
@@ -1295,6 +1445,21 @@ fn copy_web_storage_files(crate_dir: &Path, out_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Bundles the OPFS worker with the SQLite storage it runs and SQLite-wasm,
+/// so the worker loads no code at runtime.
+pub(crate) fn build_opfs_worker(js_storage_src: &Path) -> Result<()> {
+    let sh = Shell::new()?;
+    sh.change_dir(js_storage_src);
+    println!("Building the OPFS worker bundle...");
+    cmd!(sh, "npm install")
+        .run()
+        .with_context(|| "Failed to install web-storage npm dependencies")?;
+    cmd!(sh, "npm run build")
+        .run()
+        .with_context(|| "Failed to build the OPFS worker bundle")?;
+    Ok(())
+}
+
 fn create_web_entry_point(out_path: &Path) -> Result<()> {
     let entry_content = r#"// Web/Browser entry point for Breez SDK with automatic IndexedDB storage support
 import wasmInit, * as wasmModule from './breez_sdk_spark_wasm.js';
@@ -1318,6 +1483,14 @@ const setupWebStorage = async () => {
         console.warn('Breez SDK: Failed to load Web storage:', error.message);
         console.warn('Breez SDK: Storage operations may not work properly. Ignore this warning if you are not using the default storage.');
     }
+
+    try {
+        // The OPFS storage only loads SQLite-wasm in its worker, once it is used
+        const { createOpfsStorage } = await import('./storage/opfs.js');
+        globalThis.createOpfsStorage = createOpfsStorage;
+    } catch (error) {
+        console.warn('Breez SDK: Failed to load OPFS storage:', error.message);
+    }
 };
 
 // Initialize WASM and storage
@@ -1383,6 +1556,10 @@ fn update_web_package_json(out_path: &Path) -> Result<()> {
         "./storage": {
             "import": "./storage/index.js",
             "default": "./storage/index.js"
+        },
+        "./storage/opfs": {
+            "import": "./storage/opfs.js",
+            "default": "./storage/opfs.js"
         }
     });
 
@@ -241,7 +241,9 @@ namespace BreezSdkSnippets
                 description: "<invoice description>",
                 amountSats: 5_000UL,
                 expirySecs: 3600U,
-                paymentHash: null
+                paymentHash: null,
+                minAmountSats: null,
+                maxAmountSats: null
             );
             var response = await sdk.ReceivePayment(
                 request: new ReceivePaymentRequest(paymentMethod: paymentMethod)
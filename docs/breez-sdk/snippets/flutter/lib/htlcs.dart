@@ -53,7 +53,9 @@ Future<void> receiveHodlInvoicePayment(BreezSdk sdk) async {
               description: "HODL invoice",
               amountSats: BigInt.from(50000),
               expirySecs: null,
-              paymentHash: paymentHash)));
+              paymentHash: paymentHash,
+              minAmountSats: null,
+              maxAmountSats: null)));
 
   final invoice = response.paymentRequest;
   print("HODL invoice: $invoice");
@@ -90,6 +90,8 @@ Future<String> serverModeRequestHandler(BreezSdk sdk) async {
         amountSats: BigInt.from(5000),
         expirySecs: 3600,
         paymentHash: null,
+        minAmountSats: null,
+        maxAmountSats: null,
       ),
     ),
   );
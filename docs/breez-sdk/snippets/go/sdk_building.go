@@ -259,10 +259,12 @@ func ServerModeRequestHandler(sdk *breez_sdk_spark.BreezSdk) (string, error) {
 	expirySecs := uint32(3600)
 	response, err := sdk.ReceivePayment(breez_sdk_spark.ReceivePaymentRequest{
 		PaymentMethod: breez_sdk_spark.ReceivePaymentMethodBolt11Invoice{
-			Description: "<invoice description>",
-			AmountSats:  &amountSats,
-			ExpirySecs:  &expirySecs,
-			PaymentHash: nil,
+			Description:   "<invoice description>",
+			AmountSats:    &amountSats,
+			ExpirySecs:    &expirySecs,
+			PaymentHash:   nil,
+			MinAmountSats: nil,
+			MaxAmountSats: nil,
 		},
 	})
 	if err != nil {
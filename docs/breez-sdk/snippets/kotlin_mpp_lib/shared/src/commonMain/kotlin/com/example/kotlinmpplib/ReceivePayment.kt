@@ -18,6 +18,8 @@ class ReceivePayment {
                     description,
                     optionalAmountSats,
                     optionalExpirySecs,
+                    null,
+                    null,
                     null
                 )
             )
@@ -216,6 +216,8 @@ class SdkBuilding {
                     5_000.toULong(),
                     3600.toUInt(),
                     null,
+                    null,
+                    null,
                 )
             )
         )
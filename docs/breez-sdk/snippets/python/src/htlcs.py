@@ -73,6 +73,8 @@ async def receive_hodl_invoice_payment(sdk: BreezSdk):
                 amount_sats=50_000,
                 expiry_secs=None,
                 payment_hash=payment_hash,
+                min_amount_sats=None,
+                max_amount_sats=None,
             )
         )
     )
@@ -244,6 +244,8 @@ async def server_mode_request_handler(sdk: BreezSdk):
         amount_sats=5_000,
         expiry_secs=3600,
         payment_hash=None,
+        min_amount_sats=None,
+        max_amount_sats=None,
     )
     response = await sdk.receive_payment(
         request=ReceivePaymentRequest(payment_method=payment_method)
@@ -47,7 +47,7 @@ const exampleBuildWithSigner = async (signers: ExternalSigners) => {
   config.apiKey = '<breez api key>'
 
   const builder = SdkBuilder.newWithSigner(config, signers.breezSigner, signers.sparkSigner)
-  // await builder.withStorage(<your storage implementation>)
+  // await builder.withStorageBackend(customStorage(<your storage implementation>))
   // await builder.withAccountNumber(<account number>)
   const sdk = await builder.build()
 }
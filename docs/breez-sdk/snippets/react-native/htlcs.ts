@@ -67,7 +67,9 @@ const exampleReceiveHodlInvoicePayment = async (sdk: BreezSdk) => {
       description: 'HODL invoice',
       amountSats: BigInt(50_000),
       expirySecs: undefined,
-      paymentHash
+      paymentHash,
+      minAmountSats: undefined,
+      maxAmountSats: undefined
     })
   })
 
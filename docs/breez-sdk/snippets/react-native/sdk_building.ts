@@ -31,7 +31,7 @@ const exampleGettingStartedAdvanced = async () => {
   const builder = new SdkBuilder(config, seed)
   await builder.withDefaultStorage(`${RNFS.DocumentDirectoryPath}/data`)
   // You can also pass your custom implementations:
-  // await builder.withStorage(<your storage implementation>)
+  // await builder.withStorageBackend(customStorage(<your storage implementation>))
   // await builder.withChainService(<your chain service implementation>)
   // await builder.withRestClient(<your rest client implementation>)
   // await builder.withAccountNumber(<account number>)
@@ -152,7 +152,9 @@ const exampleServerModeRequestHandler = async (sdk: BreezSdk) => {
       description: '<invoice description>',
       amountSats: BigInt(5_000),
       expirySecs: 3600,
-      paymentHash: undefined
+      paymentHash: undefined,
+      minAmountSats: undefined,
+      maxAmountSats: undefined
     })
   })
 
@@ -78,6 +78,8 @@ async fn send_payment_cross_chain(
             prepare_response,
             options: None,
             idempotency_key: optional_idempotency_key,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let payment = send_response.payment;
@@ -76,15 +76,71 @@ impl EventListener for SdkEventListener {
             SdkEvent::ClaimedDeposits { claimed_deposits } => {
                 // Deposits were successfully claimed
             }
+            SdkEvent::DepositClaimFeeEvaluated {
+                deposit,
+                required_fee_sats,
+                max_fee_sats,
+                claimed,
+            } => {
+                // The claim fee of a deposit blocked by the maximum fee was re-evaluated
+            }
+            SdkEvent::SyncProgress { synced } => {
+                // A batch of payments was synced
+            }
+            SdkEvent::TokenBalanceChanged {
+                token_identifier,
+                old_balance,
+                new_balance,
+            } => {
+                // The balance of a token changed
+            }
             SdkEvent::PaymentSucceeded { payment } => {
                 // A payment completed successfully
             }
             SdkEvent::PaymentPending { payment } => {
-                // A payment is pending (waiting for confirmation)
+                // A payment is pending
             }
-            SdkEvent::PaymentFailed { payment } => {
+            SdkEvent::PaymentWaitingConfirmation { payment } => {
+                // A payment is waiting for an on-chain confirmation
+            }
+            SdkEvent::PaymentFailed { payment, reason } => {
                 // A payment failed
             }
+            SdkEvent::PaymentStuck {
+                payment_id,
+                recommendation,
+            } => {
+                // A payment is pending for longer than expected
+            }
+            SdkEvent::WithdrawConfirmed { txid, height } => {
+                // The exit transaction of a withdrawal was confirmed
+            }
+            SdkEvent::LightningAddressPaymentReceived {
+                payment,
+                sender_comment,
+                zap_sender_pubkey,
+                nostr_zap_request,
+            } => {
+                // A payment to the lightning address carried a comment or a zap
+            }
+            SdkEvent::ReceiveStageChanged {
+                payment_hash,
+                update,
+            } => {
+                // A Lightning receive reached a new stage
+            }
+            SdkEvent::ChainServiceFailover { from_url, to_url } => {
+                // Chain API requests switched to another endpoint
+            }
+            SdkEvent::QueuedPaymentResolved {
+                queued_payment_id,
+                outcome,
+            } => {
+                // A payment queued while offline was sent, failed or expired
+            }
+            SdkEvent::HistoryBackfillProgress { synced, completed } => {
+                // Older payments were backfilled after a fast sync
+            }
             SdkEvent::AutoOptimization { optimization_event } => {
                 // An auto-optimization event occurred
             }
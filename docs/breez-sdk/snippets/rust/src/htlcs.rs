@@ -40,6 +40,8 @@ async fn send_htlc_payment(sdk: &BreezSdk) -> Result<()> {
         prepare_response,
         options: Some(options),
         idempotency_key: None,
+        compliance_metadata: None,
+        queue_if_offline: None,
     };
     let send_response = sdk.send_payment(request).await?;
     let payment = send_response.payment;
@@ -61,7 +63,10 @@ async fn receive_hodl_invoice_payment(sdk: &BreezSdk) -> Result<()> {
                 amount_sats: Some(50_000),
                 expiry_secs: None,
                 payment_hash: Some(payment_hash),
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -42,9 +42,18 @@ async fn list_payments_filtered(sdk: &BreezSdk) -> Result<Vec<Payment>> {
             // Pagination
             offset: Some(0),
             limit: Some(50),
+            // Or resume after a previous page's `next_cursor`
+            cursor: None,
             // Sort order (true = oldest first, false = newest first)
             sort_ascending: Some(false),
             payment_details_filter: None,
+            counterparty_filter: None,
+            // Free-text search over descriptions, invoices and transaction ids
+            search: None,
+            // Also return the payments aggregated by group
+            grouped: None,
+            // Filter by dispute status
+            dispute_status_filter: None,
         })
         .await?;
     let payments = response.payments;
@@ -17,7 +17,10 @@ async fn receive_lightning_bolt11(sdk: &BreezSdk) -> Result<()> {
                 amount_sats: optional_amount_sats,
                 expiry_secs: optional_expiry_secs,
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -35,6 +38,7 @@ async fn receive_onchain(sdk: &BreezSdk) -> Result<()> {
     let response = sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::BitcoinAddress { new_address },
+            order_ref: None,
         })
         .await?;
 
@@ -51,6 +55,7 @@ async fn receive_spark_address(sdk: &BreezSdk) -> Result<()> {
     let response = sdk
         .receive_payment(ReceivePaymentRequest {
             payment_method: ReceivePaymentMethod::SparkAddress,
+            order_ref: None,
         })
         .await?;
 
@@ -79,6 +84,7 @@ async fn receive_spark_invoice(sdk: &BreezSdk) -> Result<()> {
                 expiry_time: optional_expiry_time_seconds,
                 sender_public_key: optional_sender_public_key,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -247,7 +247,10 @@ pub(crate) async fn server_mode_request_handler(sdk: &BreezSdk) -> Result<String
                 amount_sats: Some(5_000),
                 expiry_secs: Some(3600),
                 payment_hash: None,
+                min_amount_sats: None,
+                max_amount_sats: None,
             },
+            order_ref: None,
         })
         .await?;
 
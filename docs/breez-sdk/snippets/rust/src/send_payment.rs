@@ -175,6 +175,8 @@ async fn send_payment_lightning_bolt11(
             prepare_response,
             options,
             idempotency_key: optional_idempotency_key,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let payment = send_response.payment;
@@ -198,6 +200,8 @@ async fn send_payment_onchain(
             prepare_response,
             options,
             idempotency_key: optional_idempotency_key,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let payment = send_response.payment;
@@ -217,6 +221,8 @@ async fn send_payment_spark(
             prepare_response,
             options: None,
             idempotency_key: optional_idempotency_key,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let payment = send_response.payment;
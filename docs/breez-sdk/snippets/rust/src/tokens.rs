@@ -67,6 +67,7 @@ async fn receive_token_payment_spark_invoice(sdk: &BreezSdk) -> Result<()> {
                 expiry_time: optional_expiry_time_seconds,
                 sender_public_key: optional_sender_public_key,
             },
+            order_ref: None,
         })
         .await?;
 
@@ -125,6 +126,8 @@ async fn send_token_payment(sdk: &BreezSdk) -> Result<()> {
             prepare_response,
             options: None,
             idempotency_key: None,
+            compliance_metadata: None,
+            queue_if_offline: None,
         })
         .await?;
     let payment = send_response.payment;
@@ -16,7 +16,9 @@ func receiveLightning(sdk: BreezSdk) async throws -> ReceivePaymentResponse {
                     description: description,
                     amountSats: optionalAmountSats,
                     expirySecs: optionalExpirySecs,
-                    paymentHash: nil
+                    paymentHash: nil,
+                    minAmountSats: nil,
+                    maxAmountSats: nil
                 )
             ))
 
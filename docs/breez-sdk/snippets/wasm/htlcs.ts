@@ -52,7 +52,9 @@ const exampleReceiveHodlInvoicePayment = async (sdk: BreezSdk) => {
       description: 'HODL invoice',
       amountSats: 50_000,
       expirySecs: undefined,
-      paymentHash
+      paymentHash,
+      minAmountSats: undefined,
+      maxAmountSats: undefined
     }
   })
 
@@ -14,7 +14,9 @@ const exampleReceiveLightningPayment = async (sdk: BreezSdk) => {
       description,
       amountSats: optionalAmountSats,
       expirySecs: optionalExpirySecs,
-      paymentHash: undefined
+      paymentHash: undefined,
+      minAmountSats: undefined,
+      maxAmountSats: undefined
     }
   })
 
@@ -16,11 +16,14 @@ extension ConfigCopyWith on Config {
     List<ExternalInputParser>? externalInputParsers,
     bool? useDefaultExternalInputParsers,
     String? realTimeSyncServerUrl,
+    bool? eventStreamWebsocket,
     bool? privateEnabledDefault,
     LeafOptimizationConfig? leafOptimizationConfig,
     TokenOptimizationConfig? tokenOptimizationConfig,
     StableBalanceConfig? stableBalanceConfig,
     int? maxConcurrentClaims,
+    int? claimBatchSize,
+    int? claimBatchIntervalMs,
     SparkConfig? sparkConfig,
     bool? backgroundTasksEnabled,
     CrossChainConfig? crossChainConfig,
@@ -35,11 +38,14 @@ extension ConfigCopyWith on Config {
       externalInputParsers: externalInputParsers ?? this.externalInputParsers,
       useDefaultExternalInputParsers: useDefaultExternalInputParsers ?? this.useDefaultExternalInputParsers,
       realTimeSyncServerUrl: realTimeSyncServerUrl ?? this.realTimeSyncServerUrl,
+      eventStreamWebsocket: eventStreamWebsocket ?? this.eventStreamWebsocket,
       privateEnabledDefault: privateEnabledDefault ?? this.privateEnabledDefault,
       leafOptimizationConfig: leafOptimizationConfig ?? this.leafOptimizationConfig,
       tokenOptimizationConfig: tokenOptimizationConfig ?? this.tokenOptimizationConfig,
       stableBalanceConfig: stableBalanceConfig ?? this.stableBalanceConfig,
       maxConcurrentClaims: maxConcurrentClaims ?? this.maxConcurrentClaims,
+      claimBatchSize: claimBatchSize ?? this.claimBatchSize,
+      claimBatchIntervalMs: claimBatchIntervalMs ?? this.claimBatchIntervalMs,
       sparkConfig: sparkConfig ?? this.sparkConfig,
       backgroundTasksEnabled: backgroundTasksEnabled ?? this.backgroundTasksEnabled,
       crossChainConfig: crossChainConfig ?? this.crossChainConfig,
@@ -15,6 +15,10 @@ pub enum _DepositClaimError {
         tx: String,
         vout: u32,
     },
+    NotOurDeposit {
+        tx: String,
+        vout: u32,
+    },
     Generic {
         message: String,
     },
@@ -40,12 +44,23 @@ pub enum _SdkError {
         tx: String,
         vout: u32,
     },
+    NotOurDeposit {
+        tx: String,
+        vout: u32,
+    },
     LnurlError(String),
     Signer(String),
     OptimizationAlreadyRunning,
     OptimizationCancelled,
     InsufficientCpfpFunds { required_sat: u64 },
     FundingUtxoConflict { txid: String, vout: u32 },
+    ConfigMismatch { details: String },
+    DestinationNotAllowed { destination: String },
+    ImmutableConfig { field: String },
+    BuilderError { problems: Vec<String> },
+    ServiceMaintenance { until: u64 },
+    WatchOnly { operation: String },
+    PaymentQueued { queued_payment_id: String },
     Generic(String),
 }
 
@@ -1,6 +1,11 @@
 use crate::frb_generated::StreamSink;
-use breez_sdk_spark::{DepositInfo, EventListener, LightningAddressInfo, Payment};
-pub use breez_sdk_spark::{AutoOptimizationEvent, SdkEvent};
+pub use breez_sdk_spark::{
+    AutoOptimizationEvent, EventFilter, QueuedPaymentOutcome, SdkEvent, SdkEventKind,
+};
+use breez_sdk_spark::{
+    DepositInfo, EventListener, LightningAddressInfo, Payment, ReceiveStageUpdate,
+    StuckPaymentRecommendation,
+};
 use flutter_rust_bridge::frb;
 
 #[frb(mirror(SdkEvent))]
@@ -18,8 +23,12 @@ pub enum _SdkEvent {
     PaymentPending {
         payment: Payment,
     },
+    PaymentWaitingConfirmation {
+        payment: Payment,
+    },
     PaymentFailed {
         payment: Payment,
+        reason: Option<String>,
     },
     AutoOptimization {
         optimization_event: AutoOptimizationEvent,
@@ -30,6 +39,80 @@ pub enum _SdkEvent {
     NewDeposits {
         new_deposits: Vec<DepositInfo>,
     },
+    DepositClaimFeeEvaluated {
+        deposit: DepositInfo,
+        required_fee_sats: u64,
+        max_fee_sats: Option<u64>,
+        claimed: bool,
+    },
+    SyncProgress {
+        synced: u64,
+    },
+    TokenBalanceChanged {
+        token_identifier: String,
+        old_balance: u128,
+        new_balance: u128,
+    },
+    PaymentStuck {
+        payment_id: String,
+        recommendation: StuckPaymentRecommendation,
+    },
+    WithdrawConfirmed {
+        txid: String,
+        height: u32,
+    },
+    LightningAddressPaymentReceived {
+        payment: Payment,
+        sender_comment: Option<String>,
+        zap_sender_pubkey: Option<String>,
+        nostr_zap_request: Option<String>,
+    },
+    ChainServiceFailover {
+        from_url: String,
+        to_url: String,
+    },
+    ReceiveStageChanged {
+        payment_hash: String,
+        update: ReceiveStageUpdate,
+    },
+    QueuedPaymentResolved {
+        queued_payment_id: String,
+        outcome: QueuedPaymentOutcome,
+    },
+    HistoryBackfillProgress {
+        synced: u64,
+        completed: bool,
+    },
+}
+
+#[frb(mirror(SdkEventKind))]
+pub enum _SdkEventKind {
+    Synced,
+    UnclaimedDeposits,
+    ClaimedDeposits,
+    PaymentSucceeded,
+    PaymentPending,
+    PaymentWaitingConfirmation,
+    PaymentFailed,
+    AutoOptimization,
+    LightningAddressChanged,
+    NewDeposits,
+    DepositClaimFeeEvaluated,
+    SyncProgress,
+    TokenBalanceChanged,
+    PaymentStuck,
+    WithdrawConfirmed,
+    LightningAddressPaymentReceived,
+    ChainServiceFailover,
+    ReceiveStageChanged,
+    QueuedPaymentResolved,
+    HistoryBackfillProgress,
+}
+
+#[frb(mirror(EventFilter))]
+pub struct _EventFilter {
+    pub kinds: Vec<SdkEventKind>,
+    pub payment_id: Option<String>,
 }
 
 #[frb(mirror(AutoOptimizationEvent))]
@@ -49,6 +132,13 @@ pub enum _AutoOptimizationEvent {
     Skipped,
 }
 
+#[frb(mirror(QueuedPaymentOutcome))]
+pub enum _QueuedPaymentOutcome {
+    Sent { payment: Payment },
+    Failed { error: String },
+    Expired,
+}
+
 pub struct BindingEventListener {
     pub listener: StreamSink<SdkEvent>,
 }
@@ -2,8 +2,9 @@ use std::sync::Arc;
 
 use breez_sdk_spark::{
     BurnIssuerTokenRequest, CreateIssuerTokenRequest, FreezeIssuerTokenRequest,
-    FreezeIssuerTokenResponse, MintIssuerTokenRequest, Payment, SdkError, TokenBalance,
-    TokenMetadata, UnfreezeIssuerTokenRequest, UnfreezeIssuerTokenResponse,
+    FreezeIssuerTokenResponse, MintIssuerTokenRequest, Payment, PublishTokenAnnouncementRequest,
+    SdkError, TokenAnnouncement, TokenBalance, TokenMetadata, UnfreezeIssuerTokenRequest,
+    UnfreezeIssuerTokenResponse,
 };
 
 pub struct TokenIssuer {
@@ -53,4 +54,11 @@ impl TokenIssuer {
     ) -> Result<UnfreezeIssuerTokenResponse, SdkError> {
         self.token_issuer.unfreeze_issuer_token(request).await
     }
+
+    pub async fn publish_token_announcement(
+        &self,
+        request: PublishTokenAnnouncementRequest,
+    ) -> Result<TokenAnnouncement, SdkError> {
+        self.token_issuer.publish_token_announcement(request).await
+    }
 }
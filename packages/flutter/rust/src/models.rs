@@ -27,12 +27,14 @@ pub struct _Config {
     pub api_key: Option<String>,
     pub network: Network,
     pub sync_interval_secs: u32,
+    pub sync_trigger_debounce_secs: u32,
     pub max_deposit_claim_fee: Option<MaxFee>,
     pub lnurl_domain: Option<String>,
     pub prefer_spark_over_lightning: bool,
     pub external_input_parsers: Option<Vec<ExternalInputParser>>,
     pub use_default_external_input_parsers: bool,
     pub real_time_sync_server_url: Option<String>,
+    pub event_stream_websocket: bool,
     pub private_enabled_default: bool,
     pub leaf_optimization_config: LeafOptimizationConfig,
     pub token_optimization_config: TokenOptimizationConfig,
@@ -43,9 +45,18 @@ pub struct _Config {
     /// Default is 4. Increase for server environments with high incoming
     /// payment volume to improve throughput.
     pub max_concurrent_claims: u32,
+    pub claim_batch_size: u32,
+    pub claim_batch_interval_ms: u32,
     pub spark_config: Option<SparkConfig>,
     pub background_tasks_enabled: bool,
     pub cross_chain_config: Option<CrossChainConfig>,
+    pub invoice_description_template: Option<InvoiceDescriptionTemplate>,
+    pub webhook_config: Option<WebhookConfig>,
+    pub deposit_claim_policy: Option<DepositClaimPolicy>,
+    pub service_status_url: Option<String>,
+    pub historical_rates_url: Option<String>,
+    pub low_data_mode: bool,
+    pub sync_mode: SyncMode,
 }
 
 #[frb(mirror(CrossChainConfig))]
@@ -54,6 +65,59 @@ pub struct _CrossChainConfig {
     pub default_target_overpay_bps: Option<u32>,
 }
 
+#[frb(mirror(InvoiceDescriptionTemplate))]
+pub struct _InvoiceDescriptionTemplate {
+    pub template: String,
+    pub app_name: String,
+}
+
+#[frb(mirror(MaintenanceWindow))]
+pub struct _MaintenanceWindow {
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub message: Option<String>,
+}
+
+#[frb(mirror(GetServiceStatusResponse))]
+pub struct _GetServiceStatusResponse {
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    pub active_maintenance: Option<MaintenanceWindow>,
+}
+
+#[frb(mirror(WebhookConfig))]
+pub struct _WebhookConfig {
+    pub url: String,
+    pub secret: String,
+    pub max_attempts: Option<u32>,
+}
+
+#[frb(mirror(ConfigPatch))]
+pub struct _ConfigPatch {
+    pub network: Option<Network>,
+    pub sync_interval_secs: Option<u32>,
+    pub sync_trigger_debounce_secs: Option<u32>,
+    pub max_deposit_claim_fee: Option<MaxFee>,
+    pub prefer_spark_over_lightning: Option<bool>,
+    pub invoice_description_template: Option<InvoiceDescriptionTemplate>,
+    pub webhook_config: Option<WebhookConfig>,
+    pub deposit_claim_policy: Option<DepositClaimPolicy>,
+    pub low_data_mode: Option<bool>,
+}
+
+#[frb(mirror(DataUsage))]
+pub struct _DataUsage {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+#[frb(mirror(GetDataUsageResponse))]
+pub struct _GetDataUsageResponse {
+    pub operator: DataUsage,
+    pub ssp: DataUsage,
+    pub chain: DataUsage,
+    pub sync: DataUsage,
+}
+
 #[frb(mirror(SparkConfig))]
 pub struct _SparkConfig {
     pub coordinator_identifier: String,
@@ -81,6 +145,19 @@ pub struct _SparkSspConfig {
     pub schema_endpoint: Option<String>,
 }
 
+#[frb(mirror(ConfigProfile))]
+pub enum _ConfigProfile {
+    LowLatency,
+    LowFee,
+    Privacy,
+}
+
+#[frb(mirror(SyncMode))]
+pub enum _SyncMode {
+    Full,
+    Fast { recent_payments: u32 },
+}
+
 #[frb(mirror(LeafOptimizationConfig))]
 pub struct _LeafOptimizationConfig {
     pub auto_enabled: bool,
@@ -131,6 +208,24 @@ pub struct _ConnectRequest {
     pub storage_dir: String,
 }
 
+#[frb(mirror(DiscoverAccountsRequest))]
+pub struct _DiscoverAccountsRequest {
+    pub config: Config,
+    pub seed: Seed,
+    pub gap_limit: Option<u32>,
+}
+
+#[frb(mirror(DiscoverAccountsResponse))]
+pub struct _DiscoverAccountsResponse {
+    pub accounts: Vec<AccountActivity>,
+}
+
+#[frb(mirror(AccountActivity))]
+pub struct _AccountActivity {
+    pub account_number: u32,
+    pub identity_public_key: String,
+}
+
 #[frb(mirror(CheckMessageRequest))]
 pub struct _CheckMessageRequest {
     pub message: String,
@@ -143,6 +238,26 @@ pub struct _CheckMessageResponse {
     pub is_valid: bool,
 }
 
+#[frb(mirror(ChildSecretPurpose))]
+pub enum _ChildSecretPurpose {
+    NwcKey,
+    WebhookHmacKey,
+    BackupEncryptionKey,
+    Custom { application: u32 },
+}
+
+#[frb(mirror(DeriveChildSecretRequest))]
+pub struct _DeriveChildSecretRequest {
+    pub purpose: ChildSecretPurpose,
+    pub index: Option<u32>,
+}
+
+#[frb(mirror(DeriveChildSecretResponse))]
+pub struct _DeriveChildSecretResponse {
+    pub secret: String,
+    pub derivation_path: String,
+}
+
 #[frb(mirror(ClaimDepositRequest))]
 pub struct _ClaimDepositRequest {
     pub txid: String,
@@ -150,6 +265,13 @@ pub struct _ClaimDepositRequest {
     pub max_fee: Option<MaxFee>,
 }
 
+#[frb(mirror(SetDepositClaimOverrideRequest))]
+pub struct _SetDepositClaimOverrideRequest {
+    pub txid: String,
+    pub vout: u32,
+    pub max_fee: Option<MaxFee>,
+}
+
 #[frb(mirror(ClaimDepositResponse))]
 pub struct _ClaimDepositResponse {
     pub payment: Payment,
@@ -170,6 +292,7 @@ pub struct _DepositInfo {
     pub refund_tx: Option<String>,
     pub refund_tx_id: Option<String>,
     pub claim_error: Option<DepositClaimError>,
+    pub claim_max_fee: Option<MaxFee>,
 }
 
 #[frb(mirror(MaxFee))]
@@ -179,6 +302,14 @@ pub enum _MaxFee {
     NetworkRecommended { leeway_sat_per_vbyte: u64 },
 }
 
+#[frb(mirror(DepositClaimPolicy))]
+pub enum _DepositClaimPolicy {
+    Always,
+    Manual,
+    MaxFee { max_fee: MaxFee },
+    MaxFeePercent { percent: f64 },
+}
+
 #[frb(mirror(Fee))]
 pub enum _Fee {
     Fixed { amount: u64 },
@@ -302,11 +433,25 @@ pub struct _GetInfoRequest {
     pub ensure_synced: Option<bool>,
 }
 
+#[frb(mirror(FeeEstimationStats))]
+pub struct _FeeEstimationStats {
+    pub sample_count: u32,
+    pub pending_count: u32,
+    pub underestimated_count: u32,
+    pub overestimated_count: u32,
+    pub mean_estimated_fee_sats: f64,
+    pub mean_actual_fee_sats: f64,
+    pub mean_absolute_error_sats: f64,
+    pub mean_relative_error: f64,
+    pub max_relative_error: f64,
+}
+
 #[frb(mirror(GetInfoResponse))]
 pub struct _GetInfoResponse {
     pub identity_pubkey: String,
     pub balance_sats: u64,
     pub token_balances: HashMap<String, TokenBalance>,
+    pub history_partially_synced: bool,
 }
 
 #[frb(mirror(TokenBalance))]
@@ -334,6 +479,174 @@ pub struct _GetPaymentRequest {
 #[frb(mirror(GetPaymentResponse))]
 pub struct _GetPaymentResponse {
     pub payment: Payment,
+    pub compliance_metadata: Option<ComplianceMetadata>,
+}
+
+#[frb(mirror(GetPaymentFiatValueRequest))]
+pub struct _GetPaymentFiatValueRequest {
+    pub payment_id: String,
+    pub currency: String,
+}
+
+#[frb(mirror(GetPaymentFiatValueResponse))]
+pub struct _GetPaymentFiatValueResponse {
+    pub fiat_value_at_payment: f64,
+    pub rate: f64,
+}
+
+#[frb(mirror(PaymentIdentifier))]
+pub enum _PaymentIdentifier {
+    PaymentId { payment_id: String },
+    PaymentHash { payment_hash: String },
+    Invoice { invoice: String },
+}
+
+#[frb(mirror(WaitForPaymentRequest))]
+pub struct _WaitForPaymentRequest {
+    pub identifier: PaymentIdentifier,
+    pub timeout_secs: Option<u32>,
+}
+
+#[frb(mirror(WaitForPaymentResponse))]
+pub struct _WaitForPaymentResponse {
+    pub payment: Payment,
+}
+
+#[frb(mirror(PaymentImportSource))]
+pub enum _PaymentImportSource {
+    Greenlight,
+    Liquid,
+}
+
+#[frb(mirror(ImportPaymentsRequest))]
+pub struct _ImportPaymentsRequest {
+    pub source: PaymentImportSource,
+    pub data: String,
+}
+
+#[frb(mirror(ImportPaymentsResponse))]
+pub struct _ImportPaymentsResponse {
+    pub imported_count: u32,
+    pub skipped_count: u32,
+}
+
+#[frb(mirror(ExportFormat))]
+pub enum _ExportFormat {
+    Csv,
+    Json,
+}
+
+#[frb(mirror(ExportPaymentsRequest))]
+pub struct _ExportPaymentsRequest {
+    pub format: ExportFormat,
+    pub from_timestamp: Option<u64>,
+    pub to_timestamp: Option<u64>,
+    pub include_metadata: bool,
+    pub fiat_currency: Option<String>,
+    pub cursor: Option<String>,
+}
+
+#[frb(mirror(ExportPaymentsResponse))]
+pub struct _ExportPaymentsResponse {
+    pub data: String,
+    pub next_cursor: Option<String>,
+}
+
+#[frb(mirror(ClaimFeeSponsorship))]
+pub struct _ClaimFeeSponsorship {
+    pub deposit_txid: String,
+    pub deposit_vout: u32,
+    pub claim_payment_id: String,
+    pub fee_sats: u64,
+    pub sponsor_payment_id: String,
+    pub created_at: u64,
+}
+
+#[frb(mirror(ListClaimFeeSponsorshipsRequest))]
+pub struct _ListClaimFeeSponsorshipsRequest {
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[frb(mirror(ListClaimFeeSponsorshipsResponse))]
+pub struct _ListClaimFeeSponsorshipsResponse {
+    pub sponsorships: Vec<ClaimFeeSponsorship>,
+}
+
+#[frb(mirror(BackupResponse))]
+pub struct _BackupResponse {
+    pub backup: Vec<u8>,
+}
+
+#[frb(mirror(RestoreRequest))]
+pub struct _RestoreRequest {
+    pub backup: Vec<u8>,
+}
+
+#[frb(mirror(RestoreResponse))]
+pub struct _RestoreResponse {
+    pub payments_count: u32,
+    pub deposits_count: u32,
+    pub contacts_count: u32,
+}
+
+#[frb(mirror(ViewerBundle))]
+pub struct _ViewerBundle {
+    pub identity_public_key: String,
+    pub created_at: u64,
+}
+
+#[frb(mirror(SweepPrivateKeyRequest))]
+pub struct _SweepPrivateKeyRequest {
+    pub wif: String,
+    pub fee_rate_sat_per_vbyte: u64,
+}
+
+#[frb(mirror(SweepPrivateKeyResponse))]
+pub struct _SweepPrivateKeyResponse {
+    pub tx_id: String,
+    pub tx_hex: String,
+    pub deposit_address: String,
+    pub amount_sats: u64,
+    pub fee_sats: u64,
+}
+
+#[frb(mirror(SetPaymentDisputeRequest))]
+pub struct _SetPaymentDisputeRequest {
+    pub payment_id: String,
+    pub status: DisputeStatus,
+    pub note: Option<String>,
+}
+
+#[frb(mirror(SetPaymentDisputeResponse))]
+pub struct _SetPaymentDisputeResponse {
+    pub payment: Payment,
+}
+
+#[frb(mirror(AcknowledgePaymentRequest))]
+pub struct _AcknowledgePaymentRequest {
+    pub payment_id: String,
+    pub consumer_id: String,
+}
+
+#[frb(mirror(AcknowledgePaymentResponse))]
+pub struct _AcknowledgePaymentResponse {}
+
+#[frb(mirror(ListUnacknowledgedPaymentsRequest))]
+pub struct _ListUnacknowledgedPaymentsRequest {
+    pub consumer_id: String,
+    pub limit: Option<u32>,
+}
+
+#[frb(mirror(ListUnacknowledgedPaymentsResponse))]
+pub struct _ListUnacknowledgedPaymentsResponse {
+    pub payments: Vec<Payment>,
+}
+
+#[frb(mirror(DecodedPaymentRequest))]
+pub enum _DecodedPaymentRequest {
+    Bolt11Invoice(Bolt11InvoiceDetails),
+    SparkInvoice(SparkInvoiceDetails),
 }
 
 #[frb(mirror(InputType))]
@@ -447,6 +760,11 @@ pub struct _ListPaymentsRequest {
     pub offset: Option<u32>,
     pub limit: Option<u32>,
     pub sort_ascending: Option<bool>,
+    pub counterparty_filter: Option<CounterpartyFilter>,
+    pub search: Option<String>,
+    pub cursor: Option<String>,
+    pub grouped: Option<bool>,
+    pub dispute_status_filter: Option<Vec<DisputeStatus>>,
 }
 
 #[frb(mirror(AssetFilter))]
@@ -455,9 +773,26 @@ pub enum _AssetFilter {
     Token { token_identifier: Option<String> },
 }
 
+#[frb(mirror(CounterpartyFilter))]
+pub enum _CounterpartyFilter {
+    LightningDestination { destination_pubkey: String },
+    LightningAddress { address: String },
+}
+
 #[frb(mirror(ListPaymentsResponse))]
 pub struct _ListPaymentsResponse {
     pub payments: Vec<Payment>,
+    pub next_cursor: Option<String>,
+    pub groups: Option<Vec<PaymentGroup>>,
+}
+
+#[frb(mirror(PaymentGroup))]
+pub struct _PaymentGroup {
+    pub group_id: String,
+    pub status: PaymentStatus,
+    pub timestamp: u64,
+    pub fees: u128,
+    pub payments: Vec<Payment>,
 }
 
 #[frb(mirror(ListUnclaimedDepositsRequest))]
@@ -488,6 +823,7 @@ pub struct _LnurlPayRequest {
 pub struct _LnurlPayResponse {
     pub payment: Payment,
     pub success_action: Option<SuccessActionProcessed>,
+    pub comment: Option<String>,
 }
 
 #[frb(mirror(LnurlWithdrawInfo))]
@@ -533,6 +869,12 @@ pub enum _OnchainConfirmationSpeed {
     Slow,
 }
 
+#[frb(mirror(ConflictResolutionStrategy))]
+pub enum _ConflictResolutionStrategy {
+    FieldMerge,
+    LastWriterWins,
+}
+
 #[frb(mirror(FeePolicy))]
 pub enum _FeePolicy {
     /// Fees are added on top of the specified amount (default behavior).
@@ -761,6 +1103,8 @@ pub struct _PrepareSendPaymentResponse {
     pub token_identifier: Option<String>,
     pub conversion_estimate: Option<ConversionEstimate>,
     pub fee_policy: FeePolicy,
+    pub bip21_details: Option<Bip21Details>,
+    pub fee_breakdown: Option<FeeBreakdown>,
 }
 
 #[frb(mirror(ReceivePaymentMethod))]
@@ -781,12 +1125,20 @@ pub enum _ReceivePaymentMethod {
         amount_sats: Option<u64>,
         expiry_secs: Option<u32>,
         payment_hash: Option<String>,
+        min_amount_sats: Option<u64>,
+        max_amount_sats: Option<u64>,
+    },
+    TokenInvoice {
+        token_identifier: String,
+        amount: u128,
+        memo: Option<String>,
     },
 }
 
 #[frb(mirror(ReceivePaymentRequest))]
 pub struct _ReceivePaymentRequest {
     pub payment_method: ReceivePaymentMethod,
+    pub order_ref: Option<String>,
 }
 
 #[frb(mirror(ReceivePaymentResponse))]
@@ -809,6 +1161,20 @@ pub struct _RefundDepositResponse {
     pub tx_hex: String,
 }
 
+#[frb(mirror(EstimateClaimDepositFeeResponse))]
+pub struct _EstimateClaimDepositFeeResponse {
+    pub fee_sats: u64,
+    pub fee_rate_sat_per_vbyte: u64,
+    pub max_fee_sats: Option<u64>,
+    pub within_max_fee: bool,
+}
+
+#[frb(mirror(EstimateRefundDepositFeeResponse))]
+pub struct _EstimateRefundDepositFeeResponse {
+    pub fee_sats: u64,
+    pub refund_amount_sats: u64,
+}
+
 #[frb(mirror(SendOnchainFeeQuote))]
 pub struct _SendOnchainFeeQuote {
     pub id: String,
@@ -824,6 +1190,26 @@ pub struct _SendOnchainSpeedFeeQuote {
     pub l1_broadcast_fee_sat: u64,
 }
 
+#[frb(mirror(FeeBreakdown))]
+pub enum _FeeBreakdown {
+    Lightning {
+        ssp_fee_sats: u64,
+    },
+    BitcoinAddress {
+        speed_fast: OnchainFeeBreakdown,
+        speed_medium: OnchainFeeBreakdown,
+        speed_slow: OnchainFeeBreakdown,
+    },
+}
+
+#[frb(mirror(OnchainFeeBreakdown))]
+pub struct _OnchainFeeBreakdown {
+    pub ssp_fee_sat: u64,
+    pub l1_broadcast_fee_sat: u64,
+    pub fee_rate_sat_per_vbyte: u64,
+    pub estimated_vbytes: u64,
+}
+
 #[frb(mirror(SendPaymentMethod))]
 pub enum _SendPaymentMethod {
     BitcoinAddress {
@@ -886,6 +1272,25 @@ pub struct _SendPaymentRequest {
     pub prepare_response: PrepareSendPaymentResponse,
     pub options: Option<SendPaymentOptions>,
     pub idempotency_key: Option<String>,
+    pub compliance_metadata: Option<ComplianceMetadata>,
+    pub queue_if_offline: Option<bool>,
+}
+
+#[frb(mirror(ComplianceParty))]
+pub struct _ComplianceParty {
+    pub name: String,
+    pub account_id: Option<String>,
+    pub physical_address: Option<String>,
+    pub national_id: Option<String>,
+    pub date_of_birth: Option<String>,
+    pub vasp_name: Option<String>,
+}
+
+#[frb(mirror(ComplianceMetadata))]
+pub struct _ComplianceMetadata {
+    pub originator: ComplianceParty,
+    pub beneficiary: ComplianceParty,
+    pub reference: Option<String>,
 }
 
 #[frb(mirror(PublishSignedTransferPackageRequest))]
@@ -920,6 +1325,42 @@ pub struct _SendPaymentResponse {
     pub payment: Payment,
 }
 
+#[frb(mirror(SplitExecutionMode))]
+pub enum _SplitExecutionMode {
+    Sequential,
+    Parallel,
+}
+
+#[frb(mirror(PaySplitRequest))]
+pub struct _PaySplitRequest {
+    pub invoices: Vec<String>,
+    pub total_budget_sats: u64,
+    pub execution_mode: Option<SplitExecutionMode>,
+}
+
+#[frb(mirror(SplitPaymentStatus))]
+pub enum _SplitPaymentStatus {
+    Sent { payment: Payment },
+    Failed { error: String },
+    Skipped,
+}
+
+#[frb(mirror(SplitPaymentResult))]
+pub struct _SplitPaymentResult {
+    pub invoice: String,
+    pub amount_sats: u64,
+    pub estimated_fee_sats: u64,
+    pub status: SplitPaymentStatus,
+}
+
+#[frb(mirror(PaySplitResponse))]
+pub struct _PaySplitResponse {
+    pub results: Vec<SplitPaymentResult>,
+    pub total_sent_sats: u64,
+    pub total_fees_sats: u64,
+    pub all_succeeded: bool,
+}
+
 #[frb(mirror(SignMessageRequest))]
 pub struct _SignMessageRequest {
     pub message: String,
@@ -952,6 +1393,119 @@ pub struct _SyncWalletRequest {}
 #[frb(mirror(SyncWalletResponse))]
 pub struct _SyncWalletResponse {}
 
+#[frb(mirror(TriggerSyncRequest))]
+pub struct _TriggerSyncRequest {
+    pub trigger: SyncTrigger,
+}
+
+#[frb(mirror(SyncTrigger))]
+pub enum _SyncTrigger {
+    AppForeground,
+    PushNotification,
+}
+
+#[frb(mirror(ProcessNotificationRequest))]
+pub struct _ProcessNotificationRequest {
+    pub payload: String,
+}
+
+#[frb(mirror(ProcessNotificationResponse))]
+pub struct _ProcessNotificationResponse {
+    pub payment: Option<Payment>,
+}
+
+#[frb(mirror(StopSyncCaptureResponse))]
+pub struct _StopSyncCaptureResponse {
+    pub capture: String,
+}
+
+#[frb(mirror(StuckPaymentRecommendation))]
+pub enum _StuckPaymentRecommendation {
+    Sync,
+    ContactSupport,
+}
+
+#[frb(mirror(RecoverStuckPaymentRequest))]
+pub struct _RecoverStuckPaymentRequest {
+    pub payment_id: String,
+}
+
+#[frb(mirror(RecoverStuckPaymentResponse))]
+pub struct _RecoverStuckPaymentResponse {
+    pub payment: Payment,
+    pub recommendation: Option<StuckPaymentRecommendation>,
+}
+
+#[frb(mirror(StorageIntegrityIssueKind))]
+pub enum _StorageIntegrityIssueKind {
+    Corruption,
+    InvalidJson,
+    OrphanedRecord,
+}
+
+#[frb(mirror(StorageIntegrityIssue))]
+pub struct _StorageIntegrityIssue {
+    pub kind: StorageIntegrityIssueKind,
+    pub table: Option<String>,
+    pub record_id: Option<String>,
+    pub description: String,
+    pub repaired: bool,
+}
+
+#[frb(mirror(CheckStorageIntegrityRequest))]
+pub struct _CheckStorageIntegrityRequest {
+    pub repair: bool,
+}
+
+#[frb(mirror(CheckStorageIntegrityResponse))]
+pub struct _CheckStorageIntegrityResponse {
+    pub issues: Vec<StorageIntegrityIssue>,
+}
+
+#[frb(mirror(GetWithdrawStatusRequest))]
+pub struct _GetWithdrawStatusRequest {
+    pub payment_id: String,
+}
+
+#[frb(mirror(GetWithdrawStatusResponse))]
+pub struct _GetWithdrawStatusResponse {
+    pub tx_id: String,
+    pub confirmed: bool,
+    pub block_height: Option<u32>,
+    pub estimated_confirmations: Option<u64>,
+}
+
+#[frb(mirror(ReceiveStage))]
+pub enum _ReceiveStage {
+    InvoiceIssued,
+    HtlcSeen,
+    ClaimAttempted,
+    Settled,
+    Failed,
+}
+
+#[frb(mirror(ReceiveStageUpdate))]
+pub struct _ReceiveStageUpdate {
+    pub stage: ReceiveStage,
+    pub timestamp: u64,
+    pub ssp_status: Option<String>,
+}
+
+#[frb(mirror(DiagnoseReceiveRequest))]
+pub struct _DiagnoseReceiveRequest {
+    pub invoice: String,
+}
+
+#[frb(mirror(DiagnoseReceiveResponse))]
+pub struct _DiagnoseReceiveResponse {
+    pub payment_hash: String,
+    pub stage: ReceiveStage,
+    pub ssp_status: Option<String>,
+    pub trail: Vec<ReceiveStageUpdate>,
+    pub invoice_expired: bool,
+    pub summary: String,
+}
+
 #[frb(mirror(AesSuccessActionData))]
 pub struct _AesSuccessActionData {
     pub description: String,
@@ -1013,6 +1567,41 @@ pub struct _Payment {
     pub method: PaymentMethod,
     pub details: Option<PaymentDetails>,
     pub conversion_details: Option<ConversionDetails>,
+    pub imported: bool,
+    pub display_amount: DisplayAmount,
+    pub group_id: Option<String>,
+    pub dispute: Option<PaymentDispute>,
+}
+
+#[frb(mirror(PaymentDispute))]
+pub struct _PaymentDispute {
+    pub status: DisputeStatus,
+    pub note: Option<String>,
+    pub disputed_at: u64,
+    pub resolved_at: Option<u64>,
+    pub updated_at: u64,
+}
+
+#[frb(mirror(DisputeStatus))]
+pub enum _DisputeStatus {
+    Disputed,
+    Resolved,
+}
+
+#[frb(mirror(DisplayAmount))]
+pub struct _DisplayAmount {
+    pub value: u128,
+    pub asset: DisplayAsset,
+}
+
+#[frb(mirror(DisplayAsset))]
+pub enum _DisplayAsset {
+    Bitcoin,
+    Token {
+        identifier: String,
+        ticker: String,
+        decimals: u32,
+    },
 }
 
 #[frb(mirror(ConversionDetails))]
@@ -1460,6 +2049,28 @@ pub struct _GetTokensMetadataResponse {
     pub tokens_metadata: Vec<TokenMetadata>,
 }
 
+#[frb(mirror(TokenAnnouncement))]
+pub struct _TokenAnnouncement {
+    pub metadata: TokenMetadata,
+    pub icon_url: Option<String>,
+    pub published_at: u64,
+}
+
+#[frb(mirror(PublishTokenAnnouncementRequest))]
+pub struct _PublishTokenAnnouncementRequest {
+    pub icon_url: Option<String>,
+}
+
+#[frb(mirror(GetTokenAnnouncementsRequest))]
+pub struct _GetTokenAnnouncementsRequest {
+    pub token_identifiers: Vec<String>,
+}
+
+#[frb(mirror(GetTokenAnnouncementsResponse))]
+pub struct _GetTokenAnnouncementsResponse {
+    pub announcements: Vec<TokenAnnouncement>,
+}
+
 #[frb(mirror(RecordId))]
 pub struct _RecordId {
     pub r#type: String,
@@ -1576,6 +2187,13 @@ pub enum _ChainApiType {
     MempoolSpace,
 }
 
+#[frb(mirror(ChainServiceEndpoint))]
+pub struct _ChainServiceEndpoint {
+    pub url: String,
+    pub api_type: ChainApiType,
+    pub credentials: Option<Credentials>,
+}
+
 #[frb(mirror(ClaimHtlcPaymentRequest))]
 pub struct _ClaimHtlcPaymentRequest {
     pub preimage: String,
@@ -1586,6 +2204,43 @@ pub struct _ClaimHtlcPaymentResponse {
     pub payment: Payment,
 }
 
+#[frb(mirror(CreateHtlcReceiveRequest))]
+pub struct _CreateHtlcReceiveRequest {
+    pub payment_hash: String,
+}
+
+#[frb(mirror(CreateHtlcReceiveResponse))]
+pub struct _CreateHtlcReceiveResponse {
+    pub spark_address: String,
+    pub payment_hash: String,
+}
+
+#[frb(mirror(GetHtlcReceiveRequest))]
+pub struct _GetHtlcReceiveRequest {
+    pub payment_hash: String,
+}
+
+#[frb(mirror(GetHtlcReceiveResponse))]
+pub struct _GetHtlcReceiveResponse {
+    pub htlc_receive: HtlcReceive,
+}
+
+#[frb(mirror(HtlcReceive))]
+pub struct _HtlcReceive {
+    pub payment_hash: String,
+    pub status: HtlcReceiveStatus,
+    pub htlc_details: Option<SparkHtlcDetails>,
+    pub payment: Option<Payment>,
+}
+
+#[frb(mirror(HtlcReceiveStatus))]
+pub enum _HtlcReceiveStatus {
+    AwaitingHtlc,
+    WaitingForPreimage,
+    PreimageShared,
+    Returned,
+}
+
 #[frb(mirror(OptimizationMode))]
 pub enum _OptimizationMode {
     Full,
@@ -1740,6 +2395,26 @@ pub struct _SparkStatus {
     pub last_updated: u64,
 }
 
+#[frb(mirror(AllowlistEntry))]
+pub enum _AllowlistEntry {
+    SparkIdentity { identity_public_key: String },
+    LightningAddress { address: String },
+    BitcoinAddress { address: String },
+}
+
+#[frb(mirror(WithdrawalAllowlist))]
+pub struct _WithdrawalAllowlist {
+    pub enabled: bool,
+    pub entries: Vec<AllowlistEntry>,
+}
+
+#[frb(mirror(UpdateWithdrawalAllowlistRequest))]
+pub struct _UpdateWithdrawalAllowlistRequest {
+    pub enabled: Option<bool>,
+    pub add: Vec<AllowlistEntry>,
+    pub remove: Vec<AllowlistEntry>,
+}
+
 #[frb(mirror(Contact))]
 pub struct _Contact {
     pub id: String,
@@ -12,6 +12,12 @@ pub async fn get_spark_status() -> Result<SparkStatus, SdkError> {
     breez_sdk_spark::get_spark_status().await
 }
 
+pub async fn discover_accounts(
+    request: DiscoverAccountsRequest,
+) -> Result<DiscoverAccountsResponse, SdkError> {
+    breez_sdk_spark::discover_accounts(request).await
+}
+
 pub async fn connect(request: ConnectRequest) -> Result<BreezSdk, SdkError> {
     let sdk = breez_sdk_spark::connect(request).await?;
     Ok(BreezSdk {
@@ -24,11 +30,21 @@ pub fn default_config(network: Network) -> Config {
     breez_sdk_spark::default_config(network)
 }
 
+#[frb(sync)]
+pub fn decode_payment_request(input: String) -> Result<DecodedPaymentRequest, SdkError> {
+    breez_sdk_spark::decode_payment_request(input)
+}
+
 #[frb(sync)]
 pub fn default_server_config(network: Network) -> Config {
     breez_sdk_spark::default_server_config(network)
 }
 
+#[frb(sync)]
+pub fn default_profile_config(network: Network, profile: ConfigProfile) -> Config {
+    breez_sdk_spark::default_profile_config(network, profile)
+}
+
 #[frb(sync)]
 pub fn init_logging(
     log_dir: Option<String>,
@@ -50,6 +66,16 @@ impl BreezSdk {
             .await
     }
 
+    pub async fn add_filtered_event_listener(
+        &self,
+        listener: StreamSink<SdkEvent>,
+        filter: EventFilter,
+    ) -> String {
+        self.inner
+            .add_filtered_event_listener(Box::new(BindingEventListener { listener }), filter)
+            .await
+    }
+
     pub async fn remove_event_listener(&self, id: &str) -> bool {
         self.inner.remove_event_listener(id).await
     }
@@ -106,10 +132,7 @@ impl BreezSdk {
     pub async fn unilateral_exit_with_signer(
         &self,
         request: UnilateralExitRequest,
-        sign_psbt: impl Fn(Vec<u8>) -> DartFnFuture<anyhow::Result<Vec<u8>>>
-        + Send
-        + Sync
-        + 'static,
+        sign_psbt: impl Fn(Vec<u8>) -> DartFnFuture<anyhow::Result<Vec<u8>>> + Send + Sync + 'static,
     ) -> Result<UnilateralExitResponse, SdkError> {
         let signer = Arc::new(CallbackCpfpSigner {
             sign_psbt: Arc::new(sign_psbt),
@@ -131,6 +154,20 @@ impl BreezSdk {
         self.inner.claim_htlc_payment(request).await
     }
 
+    pub async fn create_htlc_receive(
+        &self,
+        request: CreateHtlcReceiveRequest,
+    ) -> Result<CreateHtlcReceiveResponse, SdkError> {
+        self.inner.create_htlc_receive(request).await
+    }
+
+    pub async fn get_htlc_receive(
+        &self,
+        request: GetHtlcReceiveRequest,
+    ) -> Result<GetHtlcReceiveResponse, SdkError> {
+        self.inner.get_htlc_receive(request).await
+    }
+
     pub async fn prepare_lnurl_pay(
         &self,
         request: PrepareLnurlPayRequest,
@@ -191,6 +228,10 @@ impl BreezSdk {
         self.inner.send_payment(request).await
     }
 
+    pub async fn pay_split(&self, request: PaySplitRequest) -> Result<PaySplitResponse, SdkError> {
+        self.inner.pay_split(request).await
+    }
+
     pub async fn publish_signed_transfer_package(
         &self,
         request: PublishSignedTransferPackageRequest,
@@ -205,6 +246,57 @@ impl BreezSdk {
         self.inner.sync_wallet(request).await
     }
 
+    pub async fn trigger_sync(&self, request: TriggerSyncRequest) -> Result<(), SdkError> {
+        self.inner.trigger_sync(request).await
+    }
+
+    pub async fn process_notification(
+        &self,
+        request: ProcessNotificationRequest,
+    ) -> Result<ProcessNotificationResponse, SdkError> {
+        self.inner.process_notification(request).await
+    }
+
+    pub async fn start_sync_capture(&self) -> Result<(), SdkError> {
+        self.inner.start_sync_capture().await
+    }
+
+    pub async fn stop_sync_capture(&self) -> Result<StopSyncCaptureResponse, SdkError> {
+        self.inner.stop_sync_capture().await
+    }
+
+    pub async fn recover_stuck_payment(
+        &self,
+        request: RecoverStuckPaymentRequest,
+    ) -> Result<RecoverStuckPaymentResponse, SdkError> {
+        self.inner.recover_stuck_payment(request).await
+    }
+
+    pub async fn check_storage_integrity(
+        &self,
+        request: CheckStorageIntegrityRequest,
+    ) -> Result<CheckStorageIntegrityResponse, SdkError> {
+        self.inner.check_storage_integrity(request).await
+    }
+
+    pub async fn get_withdraw_status(
+        &self,
+        request: GetWithdrawStatusRequest,
+    ) -> Result<GetWithdrawStatusResponse, SdkError> {
+        self.inner.get_withdraw_status(request).await
+    }
+
+    pub async fn diagnose_receive(
+        &self,
+        request: DiagnoseReceiveRequest,
+    ) -> Result<DiagnoseReceiveResponse, SdkError> {
+        self.inner.diagnose_receive(request).await
+    }
+
+    pub async fn warm_up(&self) -> Result<(), SdkError> {
+        self.inner.warm_up().await
+    }
+
     pub async fn list_payments(
         &self,
         request: ListPaymentsRequest,
@@ -219,6 +311,82 @@ impl BreezSdk {
         self.inner.get_payment(request).await
     }
 
+    pub async fn set_payment_dispute(
+        &self,
+        request: SetPaymentDisputeRequest,
+    ) -> Result<SetPaymentDisputeResponse, SdkError> {
+        self.inner.set_payment_dispute(request).await
+    }
+
+    pub async fn list_unacknowledged_payments(
+        &self,
+        request: ListUnacknowledgedPaymentsRequest,
+    ) -> Result<ListUnacknowledgedPaymentsResponse, SdkError> {
+        self.inner.list_unacknowledged_payments(request).await
+    }
+
+    pub async fn acknowledge_payment(
+        &self,
+        request: AcknowledgePaymentRequest,
+    ) -> Result<AcknowledgePaymentResponse, SdkError> {
+        self.inner.acknowledge_payment(request).await
+    }
+
+    pub async fn get_payment_fiat_value(
+        &self,
+        request: GetPaymentFiatValueRequest,
+    ) -> Result<GetPaymentFiatValueResponse, SdkError> {
+        self.inner.get_payment_fiat_value(request).await
+    }
+
+    pub async fn wait_for_payment(
+        &self,
+        request: WaitForPaymentRequest,
+    ) -> Result<WaitForPaymentResponse, SdkError> {
+        self.inner.wait_for_payment(request).await
+    }
+
+    pub async fn import_payments(
+        &self,
+        request: ImportPaymentsRequest,
+    ) -> Result<ImportPaymentsResponse, SdkError> {
+        self.inner.import_payments(request).await
+    }
+
+    pub async fn export_payments(
+        &self,
+        request: ExportPaymentsRequest,
+    ) -> Result<ExportPaymentsResponse, SdkError> {
+        self.inner.export_payments(request).await
+    }
+
+    pub async fn backup(&self) -> Result<BackupResponse, SdkError> {
+        self.inner.backup().await
+    }
+
+    pub async fn restore(&self, request: RestoreRequest) -> Result<RestoreResponse, SdkError> {
+        self.inner.restore(request).await
+    }
+
+    #[frb(sync)]
+    pub fn export_viewer_bundle(&self) -> ViewerBundle {
+        self.inner.export_viewer_bundle()
+    }
+
+    pub async fn sweep_private_key(
+        &self,
+        request: SweepPrivateKeyRequest,
+    ) -> Result<SweepPrivateKeyResponse, SdkError> {
+        self.inner.sweep_private_key(request).await
+    }
+
+    pub async fn list_claim_fee_sponsorships(
+        &self,
+        request: ListClaimFeeSponsorshipsRequest,
+    ) -> Result<ListClaimFeeSponsorshipsResponse, SdkError> {
+        self.inner.list_claim_fee_sponsorships(request).await
+    }
+
     pub async fn claim_deposit(
         &self,
         request: ClaimDepositRequest,
@@ -226,6 +394,27 @@ impl BreezSdk {
         self.inner.claim_deposit(request).await
     }
 
+    pub async fn set_deposit_claim_override(
+        &self,
+        request: SetDepositClaimOverrideRequest,
+    ) -> Result<(), SdkError> {
+        self.inner.set_deposit_claim_override(request).await
+    }
+
+    pub async fn estimate_claim_deposit_fee(
+        &self,
+        request: ClaimDepositRequest,
+    ) -> Result<EstimateClaimDepositFeeResponse, SdkError> {
+        self.inner.estimate_claim_deposit_fee(request).await
+    }
+
+    pub async fn estimate_refund_deposit_fee(
+        &self,
+        request: RefundDepositRequest,
+    ) -> Result<EstimateRefundDepositFeeResponse, SdkError> {
+        self.inner.estimate_refund_deposit_fee(request).await
+    }
+
     pub async fn refund_deposit(
         &self,
         request: RefundDepositRequest,
@@ -286,6 +475,15 @@ impl BreezSdk {
         self.inner.list_fiat_rates().await
     }
 
+    pub async fn get_service_status(&self) -> Result<GetServiceStatusResponse, SdkError> {
+        self.inner.get_service_status().await
+    }
+
+    #[frb(sync)]
+    pub fn get_data_usage(&self) -> GetDataUsageResponse {
+        self.inner.get_data_usage()
+    }
+
     pub async fn recommended_fees(&self) -> Result<RecommendedFees, SdkError> {
         self.inner.recommended_fees().await
     }
@@ -297,6 +495,13 @@ impl BreezSdk {
         self.inner.get_tokens_metadata(request).await
     }
 
+    pub async fn get_token_announcements(
+        &self,
+        request: GetTokenAnnouncementsRequest,
+    ) -> Result<GetTokenAnnouncementsResponse, SdkError> {
+        self.inner.get_token_announcements(request).await
+    }
+
     pub async fn sign_message(
         &self,
         request: SignMessageRequest,
@@ -311,6 +516,13 @@ impl BreezSdk {
         self.inner.check_message(request).await
     }
 
+    pub async fn derive_child_secret(
+        &self,
+        request: DeriveChildSecretRequest,
+    ) -> Result<DeriveChildSecretResponse, SdkError> {
+        self.inner.derive_child_secret(request).await
+    }
+
     pub async fn get_user_settings(&self) -> Result<UserSettings, SdkError> {
         self.inner.get_user_settings().await
     }
@@ -373,6 +585,25 @@ impl BreezSdk {
         self.inner.refund_pending_conversions().await
     }
 
+    pub async fn get_fee_estimation_stats(&self) -> Result<FeeEstimationStats, SdkError> {
+        self.inner.get_fee_estimation_stats().await
+    }
+
+    pub async fn update_config(&self, patch: ConfigPatch) -> Result<(), SdkError> {
+        self.inner.update_config(patch).await
+    }
+
+    pub async fn get_withdrawal_allowlist(&self) -> Result<WithdrawalAllowlist, SdkError> {
+        self.inner.get_withdrawal_allowlist().await
+    }
+
+    pub async fn update_withdrawal_allowlist(
+        &self,
+        request: UpdateWithdrawalAllowlistRequest,
+    ) -> Result<WithdrawalAllowlist, SdkError> {
+        self.inner.update_withdrawal_allowlist(request).await
+    }
+
     pub async fn add_contact(&self, request: AddContactRequest) -> Result<Contact, SdkError> {
         self.inner.add_contact(request).await
     }
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
-use breez_sdk_spark::{ChainApiType, Config, Credentials, SdkError, Seed};
+use breez_sdk_spark::{
+    ChainApiType, ChainServiceEndpoint, Config, ConflictResolutionStrategy, Credentials, SdkError,
+    Seed, ViewerBundle,
+};
 use flutter_rust_bridge::frb;
 
-use crate::{
-    chain_service::BitcoinChainServiceHandle, sdk::BreezSdk, sdk_context::SdkContext,
-};
+use crate::{chain_service::BitcoinChainServiceHandle, sdk::BreezSdk, sdk_context::SdkContext};
 
 pub struct SdkBuilder {
     inner: Arc<breez_sdk_spark::SdkBuilder>,
@@ -19,6 +20,23 @@ impl SdkBuilder {
         }
     }
 
+    #[frb(sync)]
+    pub fn new_watch_only(config: Config, identity_public_key: String) -> Self {
+        Self {
+            inner: Arc::new(breez_sdk_spark::SdkBuilder::new_watch_only(
+                config,
+                identity_public_key,
+            )),
+        }
+    }
+
+    #[frb(sync)]
+    pub fn new_viewer(config: Config, bundle: ViewerBundle) -> Self {
+        Self {
+            inner: Arc::new(breez_sdk_spark::SdkBuilder::new_viewer(config, bundle)),
+        }
+    }
+
     #[frb(sync)]
     pub fn with_default_storage(self, storage_dir: String) -> Self {
         let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
@@ -28,6 +46,42 @@ impl SdkBuilder {
         }
     }
 
+    #[frb(sync)]
+    pub fn with_storage_namespace(self, namespace: String) -> Self {
+        let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
+            .with_storage_namespace(namespace);
+        Self {
+            inner: Arc::new(builder),
+        }
+    }
+
+    #[frb(sync)]
+    pub fn with_config_change_accepted(self) -> Self {
+        let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
+            .with_config_change_accepted();
+        Self {
+            inner: Arc::new(builder),
+        }
+    }
+
+    #[frb(sync)]
+    pub fn with_headless_mode(self) -> Self {
+        let builder =
+            <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner).with_headless_mode();
+        Self {
+            inner: Arc::new(builder),
+        }
+    }
+
+    #[frb(sync)]
+    pub fn with_conflict_resolution(self, strategy: ConflictResolutionStrategy) -> Self {
+        let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
+            .with_conflict_resolution(strategy);
+        Self {
+            inner: Arc::new(builder),
+        }
+    }
+
     #[frb(sync)]
     pub fn with_account_number(self, account_number: u32) -> Self {
         let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
@@ -51,9 +105,27 @@ impl SdkBuilder {
         }
     }
 
+    #[frb(sync)]
+    pub fn with_rest_chain_service_endpoints(self, endpoints: Vec<ChainServiceEndpoint>) -> Self {
+        let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
+            .with_rest_chain_service_endpoints(endpoints);
+        Self {
+            inner: Arc::new(builder),
+        }
+    }
+
     /// Sets a Rust-built chain service. Pass a handle from
     /// [`new_rest_chain_service`](crate::chain_service::new_rest_chain_service)
     /// to multiple `SdkBuilder`s to share one HTTP client across SDK instances.
+    #[frb(sync)]
+    pub fn with_doh_dns_resolver(self, url: Option<String>) -> Self {
+        let builder =
+            <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner).with_doh_dns_resolver(url);
+        Self {
+            inner: Arc::new(builder),
+        }
+    }
+
     #[frb(sync)]
     pub fn with_chain_service(self, handle: &BitcoinChainServiceHandle) -> Self {
         let builder = <breez_sdk_spark::SdkBuilder as Clone>::clone(&self.inner)
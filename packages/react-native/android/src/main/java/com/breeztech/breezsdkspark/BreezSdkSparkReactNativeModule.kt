@@ -2,6 +2,7 @@
 package com.breeztech.breezsdkspark
 
 import com.facebook.react.bridge.ReactApplicationContext
+import com.facebook.react.bridge.ReactMethod
 import com.facebook.react.module.annotations.ReactModule
 import com.facebook.react.turbomodule.core.interfaces.CallInvokerHolder
 
@@ -19,6 +20,7 @@ class BreezSdkSparkReactNativeModule(reactContext: ReactApplicationContext) :
   external fun nativeInstallRustCrate(runtimePointer: Long, callInvoker: CallInvokerHolder): Boolean
   external fun nativeCleanupRustCrate(runtimePointer: Long): Boolean
 
+  @ReactMethod(isBlockingSynchronousMethod = true)
   override fun installRustCrate(): Boolean {
     val context = this.reactApplicationContext
     return nativeInstallRustCrate(
@@ -27,6 +29,7 @@ class BreezSdkSparkReactNativeModule(reactContext: ReactApplicationContext) :
     )
   }
 
+  @ReactMethod(isBlockingSynchronousMethod = true)
   override fun cleanupRustCrate(): Boolean {
     return nativeCleanupRustCrate(
       this.reactApplicationContext.javaScriptContextHolder!!.get()
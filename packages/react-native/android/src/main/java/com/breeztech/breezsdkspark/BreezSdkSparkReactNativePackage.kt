@@ -26,7 +26,7 @@ class BreezSdkSparkReactNativePackage : TurboReactPackage() {
         false,  // canOverrideExistingModule
         false,  // needsEagerInit
         false,  // isCxxModule
-        true // isTurboModule
+        BuildConfig.IS_NEW_ARCHITECTURE_ENABLED // isTurboModule
       )
       moduleInfos[BreezSdkSparkPasskeyModule.NAME] = ReactModuleInfo(
         BreezSdkSparkPasskeyModule.NAME,
@@ -36,7 +36,7 @@ class BreezSdkSparkPasskeyModule(
 ) : ReactContextBaseJavaModule(reactContext) {
 
     /**
-     * Module-scoped coroutine scope. Cancelled in [onCatalystInstanceDestroy]
+     * Module-scoped coroutine scope. Cancelled in [invalidate]
      * so any in-flight passkey ceremony does not outlive the React context
      * and leak the captured Activity. SupervisorJob keeps siblings alive if
      * one branch fails, matching the per-call try/catch pattern below.
@@ -53,9 +53,9 @@ class BreezSdkSparkPasskeyModule(
 
     override fun getName(): String = NAME
 
-    override fun onCatalystInstanceDestroy() {
+    override fun invalidate() {
         scope.cancel()
-        super.onCatalystInstanceDestroy()
+        super.invalidate()
     }
 
     /**
@@ -78,7 +78,7 @@ class BreezSdkSparkPasskeyModule(
         preferImmediatelyAvailableCredentials: Boolean?,
         promise: Promise,
     ) {
-        val activity = currentActivity
+        val activity = reactContext.currentActivity
         if (activity == null) {
             promise.reject("ERR_NO_ACTIVITY", "No current activity available")
             return
@@ -147,7 +147,7 @@ class BreezSdkSparkPasskeyModule(
      */
     @ReactMethod
     fun checkDomainAssociation(rpId: String, promise: Promise) {
-        val activity = currentActivity
+        val activity = reactContext.currentActivity
         if (activity == null) {
             promise.reject("ERR_NO_ACTIVITY", "No current activity available")
             return
@@ -202,7 +202,7 @@ class BreezSdkSparkPasskeyModule(
         excludeCredentialsBase64: com.facebook.react.bridge.ReadableArray,
         promise: Promise,
     ) {
-        val activity = currentActivity
+        val activity = reactContext.currentActivity
         if (activity == null) {
             promise.reject("ERR_NO_ACTIVITY", "No current activity available")
             return
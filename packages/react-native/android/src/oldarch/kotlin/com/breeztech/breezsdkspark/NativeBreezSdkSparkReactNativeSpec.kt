@@ -0,0 +1,22 @@
+package com.breeztech.breezsdkspark
+
+import com.facebook.react.bridge.ReactApplicationContext
+import com.facebook.react.bridge.ReactContextBaseJavaModule
+
+/**
+ * Old architecture (bridge) stand-in for the `NativeBreezSdkSparkReactNativeSpec`
+ * base class that React Native codegen generates with the new architecture.
+ *
+ * Only compiled when `newArchEnabled` is false (see the `sourceSets` block in
+ * `build.gradle`), so [BreezSdkSparkReactNativeModule] builds against either
+ * architecture. The bridge exposes the methods annotated with `@ReactMethod`
+ * on the module itself, as blocking synchronous methods, since installing the
+ * Rust crate needs the JS thread's runtime.
+ */
+abstract class NativeBreezSdkSparkReactNativeSpec(
+    reactContext: ReactApplicationContext,
+) : ReactContextBaseJavaModule(reactContext) {
+    abstract fun installRustCrate(): Boolean
+
+    abstract fun cleanupRustCrate(): Boolean
+}
@@ -1,6 +1,7 @@
 // Generated by uniffi-bindgen-react-native
 #import "BreezSdkSparkReactNative.h"
 
+#ifdef RCT_NEW_ARCH_ENABLED
 namespace uniffi_generated {
     using namespace facebook::react;
     /**
@@ -29,6 +30,9 @@ namespace uniffi_generated {
             this->methodMap_["cleanupRustCrate"] = MethodMetadata {1, __hostFunction_BreezSdkSparkReactNative_cleanupRustCrate};
     }
 } // namespace uniffi_generated
+#else
+#import <React/RCTBridge+Private.h>
+#endif
 
 @implementation BreezSdkSparkReactNative
 RCT_EXPORT_MODULE()
@@ -61,6 +65,34 @@ RCT_EXPORT_MODULE()
 {
     return std::make_shared<uniffi_generated::NativeBreezSdkSparkReactNativeSpecJSI>(params);
 }
+#else
+
+// With the old architecture the methods are exposed through the bridge as
+// blocking synchronous methods, which run on the JS thread and so can reach
+// its runtime.
+@synthesize bridge = _bridge;
+
+RCT_EXPORT_BLOCKING_SYNCHRONOUS_METHOD(installRustCrate)
+{
+    RCTCxxBridge *cxxBridge = (RCTCxxBridge *)self.bridge;
+    if (cxxBridge == nil || cxxBridge.runtime == nil) {
+        return @NO;
+    }
+    auto runtime = reinterpret_cast<facebook::jsi::Runtime *>(cxxBridge.runtime);
+    uint8_t result = breeztech_breezsdksparkreactnative::installRustCrate(*runtime, cxxBridge.jsCallInvoker);
+    return @(result != 0);
+}
+
+RCT_EXPORT_BLOCKING_SYNCHRONOUS_METHOD(cleanupRustCrate)
+{
+    RCTCxxBridge *cxxBridge = (RCTCxxBridge *)self.bridge;
+    if (cxxBridge == nil || cxxBridge.runtime == nil) {
+        return @NO;
+    }
+    auto runtime = reinterpret_cast<facebook::jsi::Runtime *>(cxxBridge.runtime);
+    uint8_t result = breeztech_breezsdksparkreactnative::cleanupRustCrate(*runtime);
+    return @(result != 0);
+}
 #endif
 
 @end
\ No newline at end of file
@@ -16,9 +16,29 @@
  *          `android/src/main/kotlin/...` to survive `yarn ubrn:clean`)
  *          are actually compiled
  *
+ *        - add `src/oldarch/kotlin` to the sourceSets with the old
+ *          architecture, for the hand-written bridge stand-in of the
+ *          codegen'd NativeBreezSdkSparkReactNativeSpec base class
+ *
  *   2. android/src/main/java/.../BreezSdkSparkReactNativePackage.kt
  *        - register BreezSdkSparkPasskeyModule alongside the generated
  *          UniFFI TurboModule so React Native can find it at runtime
+ *        - only flag the UniFFI module as a TurboModule with the new
+ *          architecture, so the old bridge registers it
+ *
+ *   3. android/src/main/java/.../BreezSdkSparkReactNativeModule.kt
+ *        - expose installRustCrate / cleanupRustCrate as blocking
+ *          synchronous bridge methods for the old architecture
+ *
+ *   4. ios/BreezSdkSparkReactNative.mm
+ *        - only compile the TurboModule JSI glue with the new architecture
+ *        - expose installRustCrate / cleanupRustCrate as blocking
+ *          synchronous bridge methods for the old architecture
+ *
+ *   5. src/NativeBreezSdkSparkReactNative.ts
+ *        - look the module up in the TurboModule registry or in
+ *          `NativeModules`, depending on the architecture detected at
+ *          runtime, so one package works on both
  *
  * The PasskeyProvider class is exposed via a subpath export
  * (`@breeztech/breez-sdk-spark-react-native/passkey-prf-provider`) declared
@@ -131,6 +151,28 @@ patchFile(
   }
 );
 
+patchFile(
+  'android/build.gradle',
+  'src/oldarch/kotlin sourceSet',
+  (content, label, relPath) => {
+    if (content.includes("main.kotlin.srcDirs += 'src/oldarch/kotlin'")) {
+      return content;
+    }
+    const anchor = `            "generated/jni"
+          ]
+      }`;
+    requireAnchor(content, anchor, label, relPath);
+    return content.replace(
+      anchor,
+      `            "generated/jni"
+          ]
+      } else {
+          main.kotlin.srcDirs += 'src/oldarch/kotlin'
+      }`
+    );
+  }
+);
+
 // ---------------------------------------------------------------------------
 // 2. android/src/main/java/.../BreezSdkSparkReactNativePackage.kt
 // ---------------------------------------------------------------------------
@@ -196,6 +238,175 @@ patchFile(
   }
 );
 
+patchFile(
+  PACKAGE_KT_REL,
+  'BreezSdkSparkReactNativeModule isTurboModule flag',
+  (content, label, relPath) => {
+    if (content.includes('BuildConfig.IS_NEW_ARCHITECTURE_ENABLED // isTurboModule')) {
+      return content;
+    }
+    const anchor = `        false,  // isCxxModule
+        true // isTurboModule`;
+    requireAnchor(content, anchor, label, relPath);
+    return content.replace(
+      anchor,
+      `        false,  // isCxxModule
+        BuildConfig.IS_NEW_ARCHITECTURE_ENABLED // isTurboModule`
+    );
+  }
+);
+
+// ---------------------------------------------------------------------------
+// 3. android/src/main/java/.../BreezSdkSparkReactNativeModule.kt
+// ---------------------------------------------------------------------------
+
+patchFile(
+  'android/src/main/java/com/breeztech/breezsdkspark/BreezSdkSparkReactNativeModule.kt',
+  'old architecture synchronous bridge methods',
+  (content, label, relPath) => {
+    if (content.includes('@ReactMethod(isBlockingSynchronousMethod = true)')) {
+      return content;
+    }
+    const anchors = [
+      'import com.facebook.react.bridge.ReactApplicationContext\n',
+      '  override fun installRustCrate(): Boolean {',
+      '  override fun cleanupRustCrate(): Boolean {',
+    ];
+    for (const anchor of anchors) {
+      requireAnchor(content, anchor, label, relPath);
+    }
+    return content
+      .replace(
+        anchors[0],
+        `${anchors[0]}import com.facebook.react.bridge.ReactMethod\n`
+      )
+      .replace(
+        anchors[1],
+        `  @ReactMethod(isBlockingSynchronousMethod = true)\n${anchors[1]}`
+      )
+      .replace(
+        anchors[2],
+        `  @ReactMethod(isBlockingSynchronousMethod = true)\n${anchors[2]}`
+      );
+  }
+);
+
+// ---------------------------------------------------------------------------
+// 4. ios/BreezSdkSparkReactNative.mm
+// ---------------------------------------------------------------------------
+
+const MM_REL = 'ios/BreezSdkSparkReactNative.mm';
+
+patchFile(MM_REL, 'new architecture JSI glue guard', (content, label, relPath) => {
+  if (content.includes('#ifdef RCT_NEW_ARCH_ENABLED\nnamespace uniffi_generated {')) {
+    return content;
+  }
+  const start = 'namespace uniffi_generated {';
+  const end = '} // namespace uniffi_generated\n';
+  requireAnchor(content, start, label, relPath);
+  requireAnchor(content, end, label, relPath);
+  return content
+    .replace(start, `#ifdef RCT_NEW_ARCH_ENABLED\n${start}`)
+    .replace(end, `${end}#else\n#import <React/RCTBridge+Private.h>\n#endif\n`);
+});
+
+patchFile(MM_REL, 'old architecture synchronous bridge methods', (content, label, relPath) => {
+  if (content.includes('RCT_EXPORT_BLOCKING_SYNCHRONOUS_METHOD(installRustCrate)')) {
+    return content;
+  }
+  const anchor = `    return std::make_shared<uniffi_generated::NativeBreezSdkSparkReactNativeSpecJSI>(params);
+}
+#endif
+`;
+  requireAnchor(content, anchor, label, relPath);
+  return content.replace(
+    anchor,
+    `    return std::make_shared<uniffi_generated::NativeBreezSdkSparkReactNativeSpecJSI>(params);
+}
+#else
+
+// With the old architecture the methods are exposed through the bridge as
+// blocking synchronous methods, which run on the JS thread and so can reach
+// its runtime.
+@synthesize bridge = _bridge;
+
+RCT_EXPORT_BLOCKING_SYNCHRONOUS_METHOD(installRustCrate)
+{
+    RCTCxxBridge *cxxBridge = (RCTCxxBridge *)self.bridge;
+    if (cxxBridge == nil || cxxBridge.runtime == nil) {
+        return @NO;
+    }
+    auto runtime = reinterpret_cast<facebook::jsi::Runtime *>(cxxBridge.runtime);
+    uint8_t result = breeztech_breezsdksparkreactnative::installRustCrate(*runtime, cxxBridge.jsCallInvoker);
+    return @(result != 0);
+}
+
+RCT_EXPORT_BLOCKING_SYNCHRONOUS_METHOD(cleanupRustCrate)
+{
+    RCTCxxBridge *cxxBridge = (RCTCxxBridge *)self.bridge;
+    if (cxxBridge == nil || cxxBridge.runtime == nil) {
+        return @NO;
+    }
+    auto runtime = reinterpret_cast<facebook::jsi::Runtime *>(cxxBridge.runtime);
+    uint8_t result = breeztech_breezsdksparkreactnative::cleanupRustCrate(*runtime);
+    return @(result != 0);
+}
+#endif
+`
+  );
+});
+
+// ---------------------------------------------------------------------------
+// 5. src/NativeBreezSdkSparkReactNative.ts
+// ---------------------------------------------------------------------------
+
+patchFile(
+  'src/NativeBreezSdkSparkReactNative.ts',
+  'runtime architecture check',
+  (content, label, relPath) => {
+    if (content.includes('const isTurboModuleEnabled =')) {
+      return content;
+    }
+    const importAnchor = "import { TurboModuleRegistry } from 'react-native';";
+    const exportAnchor =
+      "export default TurboModuleRegistry.getEnforcing<Spec>('BreezSdkSparkReactNative');";
+    requireAnchor(content, importAnchor, label, relPath);
+    requireAnchor(content, exportAnchor, label, relPath);
+    return content
+      .replace(
+        importAnchor,
+        "import { NativeModules, TurboModuleRegistry } from 'react-native';"
+      )
+      .replace(
+        exportAnchor,
+        `// Codegen requires the module name to be a literal in the registry call
+const MODULE_NAME = 'BreezSdkSparkReactNative';
+
+// The TurboModule proxy is only installed by the new architecture. Apps on
+// the old bridge get the module, with the same synchronous methods, through
+// \`NativeModules\` instead.
+const isTurboModuleEnabled =
+  (global as any).__turboModuleProxy != null ||
+  (global as any).RN$Bridgeless === true;
+
+const installer: Spec | null = isTurboModuleEnabled
+  ? TurboModuleRegistry.get<Spec>('BreezSdkSparkReactNative')
+  : (NativeModules[MODULE_NAME] as Spec | undefined) ?? null;
+
+if (installer == null) {
+  throw new Error(
+    \`The native module '\${MODULE_NAME}' could not be found \` +
+      \`(\${isTurboModuleEnabled ? 'new' : 'old'} architecture). Make sure the \` +
+      \`app was rebuilt after installing the package, e.g. with \` +
+      \`'pod install' on iOS.\`
+  );
+}
+
+export default installer;`
+      );
+  }
+);
+
 if (errors.length > 0) {
   console.error('');
   console.error(`[post-ubrn] ${errors.length} patch(es) failed:`);
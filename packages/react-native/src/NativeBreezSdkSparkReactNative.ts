@@ -1,10 +1,33 @@
 // Generated by uniffi-bindgen-react-native
 import type { TurboModule } from 'react-native';
-import { TurboModuleRegistry } from 'react-native';
+import { NativeModules, TurboModuleRegistry } from 'react-native';
 
 export interface Spec extends TurboModule {
   installRustCrate(): boolean;
   cleanupRustCrate(): boolean;
 }
 
-export default TurboModuleRegistry.getEnforcing<Spec>('BreezSdkSparkReactNative');
\ No newline at end of file
+// Codegen requires the module name to be a literal in the registry call
+const MODULE_NAME = 'BreezSdkSparkReactNative';
+
+// The TurboModule proxy is only installed by the new architecture. Apps on
+// the old bridge get the module, with the same synchronous methods, through
+// `NativeModules` instead.
+const isTurboModuleEnabled =
+  (global as any).__turboModuleProxy != null ||
+  (global as any).RN$Bridgeless === true;
+
+const installer: Spec | null = isTurboModuleEnabled
+  ? TurboModuleRegistry.get<Spec>('BreezSdkSparkReactNative')
+  : (NativeModules[MODULE_NAME] as Spec | undefined) ?? null;
+
+if (installer == null) {
+  throw new Error(
+    `The native module '${MODULE_NAME}' could not be found ` +
+      `(${isTurboModuleEnabled ? 'new' : 'old'} architecture). Make sure the ` +
+      `app was rebuilt after installing the package, e.g. with ` +
+      `'pod install' on iOS.`
+  );
+}
+
+export default installer;
\ No newline at end of file